@@ -0,0 +1,177 @@
+use std::collections::HashMap;
+
+use bevy_asset::{Assets, Handle};
+use bevy_ecs::prelude::*;
+use bevy_image::{Image, TextureAtlas, TextureAtlasLayout};
+use bevy_math::{URect, UVec2};
+use image::RgbaImage;
+use tex_packer_core::runtime::{AtlasSession, RuntimeBudget, RuntimeStrategy};
+use tex_packer_core::runtime_atlas::{RuntimeAtlas, UpdateRegion};
+use tex_packer_core::{PackerConfig, Result};
+
+use crate::{PackedAtlasPage, rgba_to_image, sync_region, urect};
+
+/// Runtime atlas resource: sprites are packed as they arrive (e.g. downloaded avatars, glyphs,
+/// generated icons), and only the changed regions are copied into the Bevy images.
+///
+/// Layout indices stay stable for the lifetime of a sprite; indices of removed sprites are
+/// reused.
+#[derive(Resource)]
+pub struct DynamicAtlas {
+    atlas: RuntimeAtlas,
+    pages: Vec<PackedAtlasPage>,
+    indices: HashMap<String, (usize, usize)>,
+    /// Free layout indices per page.
+    free: Vec<Vec<usize>>,
+}
+
+impl DynamicAtlas {
+    /// New empty atlas with page size `cfg.max_width` x `cfg.max_height` (rotation is forced off).
+    pub fn new(mut cfg: PackerConfig, strategy: RuntimeStrategy) -> Self {
+        cfg.allow_rotation = false;
+        Self {
+            atlas: RuntimeAtlas::new(cfg, strategy),
+            pages: Vec::new(),
+            indices: HashMap::new(),
+            free: Vec::new(),
+        }
+    }
+
+    /// Caps pages/pixels; least recently used sprites are evicted to make room.
+    pub fn with_budget(mut self, budget: RuntimeBudget) -> Self {
+        self.atlas = self.atlas.with_budget(budget);
+        self
+    }
+
+    /// Packs `image` under `key` and uploads it. Inserting an existing key returns the
+    /// existing entry.
+    pub fn insert(
+        &mut self,
+        key: &str,
+        image: &RgbaImage,
+        images: &mut Assets<Image>,
+        layouts: &mut Assets<TextureAtlasLayout>,
+    ) -> Result<(Handle<Image>, TextureAtlas)> {
+        if let Some(existing) = self.get(key) {
+            self.atlas.touch(key);
+            return Ok(existing);
+        }
+        let result = self.atlas.append_with_image(key.to_string(), image);
+        // Budget evictions happen inside the append, even when it fails
+        for evicted in self.atlas.drain_evicted() {
+            let region = UpdateRegion {
+                page_id: evicted.page_id,
+                x: evicted.slot.x,
+                y: evicted.slot.y,
+                width: evicted.slot.w,
+                height: evicted.slot.h,
+            };
+            self.release(&evicted.key, layouts);
+            self.upload(&region, images);
+        }
+        let (page, frame, region) = result?;
+
+        while self.pages.len() <= page {
+            let rgba = self
+                .atlas
+                .get_page_image(self.pages.len())
+                .cloned()
+                .expect("runtime atlas created the page");
+            let size = UVec2::new(rgba.width(), rgba.height());
+            self.pages.push(PackedAtlasPage {
+                image: images.add(rgba_to_image(rgba)),
+                layout: layouts.add(TextureAtlasLayout::new_empty(size)),
+            });
+            self.free.push(Vec::new());
+        }
+        self.upload(&region, images);
+
+        let rect = urect(frame.frame.x, frame.frame.y, frame.frame.w, frame.frame.h);
+        let layout = layouts
+            .get_mut(&self.pages[page].layout)
+            .expect("layout asset is alive while the atlas holds its handle");
+        let index = match self.free[page].pop() {
+            Some(i) => {
+                layout.textures[i] = rect;
+                i
+            }
+            None => layout.add_texture(rect),
+        };
+        self.indices.insert(key.to_string(), (page, index));
+        Ok(self.get(key).expect("just inserted"))
+    }
+
+    /// Image and [`TextureAtlas`] for `key`.
+    pub fn get(&self, key: &str) -> Option<(Handle<Image>, TextureAtlas)> {
+        let &(page, index) = self.indices.get(key)?;
+        let p = &self.pages[page];
+        Some((
+            p.image.clone(),
+            TextureAtlas {
+                layout: p.layout.clone(),
+                index,
+            },
+        ))
+    }
+
+    pub fn contains(&self, key: &str) -> bool {
+        self.indices.contains_key(key)
+    }
+
+    /// Frees the sprite's space and clears its pixels. Its layout index becomes a zero-size
+    /// rect until reused.
+    pub fn remove(
+        &mut self,
+        key: &str,
+        images: &mut Assets<Image>,
+        layouts: &mut Assets<TextureAtlasLayout>,
+    ) -> bool {
+        if !self.indices.contains_key(key) {
+            return false;
+        }
+        if let Some(region) = self.atlas.evict_by_key_with_clear(key, true) {
+            self.upload(&region, images);
+        }
+        self.release(key, layouts);
+        true
+    }
+
+    /// Pages as Bevy assets.
+    pub fn pages(&self) -> &[PackedAtlasPage] {
+        &self.pages
+    }
+
+    pub fn len(&self) -> usize {
+        self.indices.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.indices.is_empty()
+    }
+
+    /// Packing state, e.g. to serialize and restore later.
+    pub fn session(&self) -> &AtlasSession {
+        self.atlas.session()
+    }
+
+    fn release(&mut self, key: &str, layouts: &mut Assets<TextureAtlasLayout>) {
+        let Some((page, index)) = self.indices.remove(key) else {
+            return;
+        };
+        if let Some(layout) = layouts.get_mut(&self.pages[page].layout) {
+            layout.textures[index] = URect::default();
+        }
+        self.free[page].push(index);
+    }
+
+    fn upload(&self, region: &UpdateRegion, images: &mut Assets<Image>) {
+        if region.is_empty() {
+            return;
+        }
+        if let Some(page) = self.pages.get(region.page_id)
+            && let Some(image) = images.get_mut(&page.image)
+        {
+            sync_region(&self.atlas, region, image);
+        }
+    }
+}
@@ -0,0 +1,228 @@
+//! Bevy integration for tex-packer.
+//!
+//! - [`TexPackerPlugin`] packs sprite folders once at startup into [`Image`] +
+//!   [`TextureAtlasLayout`] assets, published in the [`PackedAtlases`] resource.
+//! - [`PackedAtlas::from_output`] does the same for any [`PackOutput`], e.g. from a build step.
+//! - [`DynamicAtlas`] is a runtime atlas (backed by [`AtlasSession`]) that grows as sprites are
+//!   inserted and keeps the GPU images and layouts in sync.
+//!
+//! Bevy cannot draw rotated atlas entries, so rotation is always disabled. Trimmed frames keep
+//! their offsets in [`PackedAtlas::atlas`].
+//!
+//! [`AtlasSession`]: tex_packer_core::runtime::AtlasSession
+
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+use bevy_app::{App, Plugin, PreStartup};
+use bevy_asset::{Assets, Handle, RenderAssetUsages};
+use bevy_ecs::prelude::*;
+use bevy_image::{Image, TextureAtlas, TextureAtlasLayout};
+use bevy_math::{URect, UVec2};
+use image::{DynamicImage, RgbaImage};
+use tex_packer_core::runtime::RuntimeStrategy;
+use tex_packer_core::runtime_atlas::{RuntimeAtlas, UpdateRegion};
+use tex_packer_core::{
+    Atlas, InputImage, PackOutput, PackerConfig, Result, TexPackerError, pack_images,
+};
+
+mod dynamic;
+
+pub use dynamic::DynamicAtlas;
+
+/// One atlas page as Bevy assets.
+#[derive(Debug, Clone)]
+pub struct PackedAtlasPage {
+    pub image: Handle<Image>,
+    pub layout: Handle<TextureAtlasLayout>,
+}
+
+/// A packed atlas uploaded to Bevy: one image + layout per page and a key -> (page, index) map.
+#[derive(Debug, Clone)]
+pub struct PackedAtlas {
+    pub pages: Vec<PackedAtlasPage>,
+    /// Frame key -> (page, index into that page's layout).
+    pub frames: HashMap<String, (usize, usize)>,
+    /// Original metadata (trim offsets, pivots, source sizes).
+    pub atlas: Atlas,
+}
+
+impl PackedAtlas {
+    /// Adds every page of `out` as an [`Image`] and a [`TextureAtlasLayout`].
+    /// Fails if a frame is rotated (pack with `allow_rotation = false`).
+    pub fn from_output(
+        out: PackOutput,
+        images: &mut Assets<Image>,
+        layouts: &mut Assets<TextureAtlasLayout>,
+    ) -> Result<Self> {
+        let mut pages = Vec::with_capacity(out.pages.len());
+        let mut frames = HashMap::new();
+        for (page_idx, page) in out.pages.iter().enumerate() {
+            let mut layout =
+                TextureAtlasLayout::new_empty(UVec2::new(page.page.width, page.page.height));
+            for f in &page.page.frames {
+                if f.rotated {
+                    return Err(TexPackerError::InvalidConfig(format!(
+                        "frame '{}' is rotated; TextureAtlasLayout cannot express rotation",
+                        f.key
+                    )));
+                }
+                let index = layout.add_texture(urect(f.frame.x, f.frame.y, f.frame.w, f.frame.h));
+                frames.insert(f.key.clone(), (page_idx, index));
+            }
+            let rgba = page.pixels()?.into_owned();
+            pages.push(PackedAtlasPage {
+                image: images.add(rgba_to_image(rgba)),
+                layout: layouts.add(layout),
+            });
+        }
+        Ok(Self {
+            pages,
+            frames,
+            atlas: out.atlas,
+        })
+    }
+
+    /// Image and [`TextureAtlas`] to put on a `Sprite` for `key`.
+    pub fn sprite(&self, key: &str) -> Option<(Handle<Image>, TextureAtlas)> {
+        let &(page, index) = self.frames.get(key)?;
+        let p = &self.pages[page];
+        Some((
+            p.image.clone(),
+            TextureAtlas {
+                layout: p.layout.clone(),
+                index,
+            },
+        ))
+    }
+}
+
+/// Packed atlases by the name they were registered under in [`TexPackerPlugin`].
+#[derive(Resource, Debug, Default)]
+pub struct PackedAtlases(pub HashMap<String, PackedAtlas>);
+
+impl PackedAtlases {
+    pub fn get(&self, name: &str) -> Option<&PackedAtlas> {
+        self.0.get(name)
+    }
+}
+
+/// Packs sprite folders in `PreStartup`, so `Startup` systems can spawn sprites from
+/// [`PackedAtlases`]. Optionally inserts a [`DynamicAtlas`] resource.
+#[derive(Debug, Clone, Default)]
+pub struct TexPackerPlugin {
+    folders: Vec<(String, PathBuf)>,
+    config: PackerConfig,
+    dynamic: Option<(PackerConfig, RuntimeStrategy)>,
+}
+
+impl TexPackerPlugin {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Packs every image under `dir` (recursively; paths are relative to the working
+    /// directory, e.g. `"assets/sprites"`) into the atlas `name`.
+    pub fn with_folder(mut self, name: impl Into<String>, dir: impl Into<PathBuf>) -> Self {
+        self.folders.push((name.into(), dir.into()));
+        self
+    }
+
+    /// Config used for all folders (rotation is forced off).
+    pub fn with_config(mut self, config: PackerConfig) -> Self {
+        self.config = config;
+        self
+    }
+
+    /// Also inserts an empty [`DynamicAtlas`] resource.
+    pub fn with_dynamic_atlas(mut self, config: PackerConfig, strategy: RuntimeStrategy) -> Self {
+        self.dynamic = Some((config, strategy));
+        self
+    }
+}
+
+impl Plugin for TexPackerPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<PackedAtlases>();
+        if let Some((cfg, strategy)) = &self.dynamic {
+            app.insert_resource(DynamicAtlas::new(cfg.clone(), strategy.clone()));
+        }
+        let folders = self.folders.clone();
+        let config = self.config.clone();
+        app.add_systems(
+            PreStartup,
+            move |mut atlases: ResMut<PackedAtlases>,
+                  mut images: ResMut<Assets<Image>>,
+                  mut layouts: ResMut<Assets<TextureAtlasLayout>>| {
+                for (name, dir) in &folders {
+                    let packed = pack_folder(dir, config.clone())
+                        .and_then(|out| PackedAtlas::from_output(out, &mut images, &mut layouts));
+                    match packed {
+                        Ok(atlas) => {
+                            atlases.0.insert(name.clone(), atlas);
+                        }
+                        Err(e) => tracing::error!("tex-packer: packing {}: {e}", dir.display()),
+                    }
+                }
+            },
+        );
+    }
+}
+
+/// Loads every decodable image under `dir` and packs it with rotation disabled. Keys are
+/// paths relative to `dir` with `/` separators and no extension (`ui/button`).
+pub fn pack_folder(dir: impl AsRef<Path>, mut cfg: PackerConfig) -> Result<PackOutput> {
+    cfg.allow_rotation = false;
+    let dir = dir.as_ref();
+    let mut files = Vec::new();
+    collect_files(dir, &mut files)?;
+    files.sort();
+    let mut inputs = Vec::new();
+    for path in files {
+        let Ok(image) = image::open(&path) else {
+            continue;
+        };
+        let rel = path.strip_prefix(dir).unwrap_or(&path).with_extension("");
+        let key = rel.to_string_lossy().replace('\\', "/");
+        inputs.push(InputImage { key, image });
+    }
+    pack_images(inputs, cfg)
+}
+
+fn collect_files(dir: &Path, out: &mut Vec<PathBuf>) -> Result<()> {
+    for entry in std::fs::read_dir(dir)? {
+        let path = entry?.path();
+        if path.is_dir() {
+            collect_files(&path, out)?;
+        } else {
+            out.push(path);
+        }
+    }
+    Ok(())
+}
+
+fn urect(x: u32, y: u32, w: u32, h: u32) -> URect {
+    URect::new(x, y, x + w, y + h)
+}
+
+fn rgba_to_image(rgba: RgbaImage) -> Image {
+    Image::from_dynamic(
+        DynamicImage::ImageRgba8(rgba),
+        true,
+        RenderAssetUsages::default(),
+    )
+}
+
+/// Copies `region` of `atlas`'s RGBA page into the matching Bevy image.
+fn sync_region(atlas: &RuntimeAtlas, region: &UpdateRegion, image: &mut Image) {
+    let (Some(src), Some(dst)) = (atlas.get_page_image(region.page_id), image.data.as_mut()) else {
+        return;
+    };
+    let stride = src.width() as usize * 4;
+    let src = src.as_raw();
+    for y in region.y..region.y + region.height {
+        let start = y as usize * stride + region.x as usize * 4;
+        let end = start + region.width as usize * 4;
+        dst[start..end].copy_from_slice(&src[start..end]);
+    }
+}
@@ -0,0 +1,138 @@
+use bevy_app::App;
+use bevy_asset::Assets;
+use bevy_image::{Image, TextureAtlasLayout};
+use image::{Rgba, RgbaImage};
+use tex_packer_bevy::{DynamicAtlas, PackedAtlas, PackedAtlases, TexPackerPlugin, pack_folder};
+use tex_packer_core::runtime::{RuntimeBudget, RuntimeStrategy};
+use tex_packer_core::{InputImage, PackerConfig, pack_images};
+
+fn cfg(size: u32) -> PackerConfig {
+    PackerConfig::builder()
+        .with_max_dimensions(size, size)
+        .texture_padding(0)
+        .trim(false)
+        .allow_rotation(false)
+        .build()
+}
+
+fn solid(w: u32, h: u32, v: u8) -> RgbaImage {
+    RgbaImage::from_pixel(w, h, Rgba([v, v, v, 255]))
+}
+
+fn pixel(image: &Image, x: u32, y: u32) -> [u8; 4] {
+    let at = ((y * image.width() + x) * 4) as usize;
+    image.data.as_ref().unwrap()[at..at + 4].try_into().unwrap()
+}
+
+#[test]
+fn pack_output_becomes_images_and_layouts() {
+    let inputs = vec![
+        InputImage {
+            key: "a".into(),
+            image: solid(10, 6, 40).into(),
+        },
+        InputImage {
+            key: "b".into(),
+            image: solid(4, 4, 90).into(),
+        },
+    ];
+    let out = pack_images(inputs, cfg(32)).unwrap();
+    let mut images = Assets::<Image>::default();
+    let mut layouts = Assets::<TextureAtlasLayout>::default();
+    let atlas = PackedAtlas::from_output(out, &mut images, &mut layouts).unwrap();
+
+    let (image, tex) = atlas.sprite("b").unwrap();
+    let rect = layouts.get(&tex.layout).unwrap().textures[tex.index];
+    assert_eq!(rect.size().to_array(), [4, 4]);
+    assert_eq!(
+        pixel(images.get(&image).unwrap(), rect.min.x, rect.min.y)[0],
+        90
+    );
+    assert!(atlas.sprite("missing").is_none());
+}
+
+#[test]
+fn dynamic_atlas_uploads_reuses_indices_and_follows_evictions() {
+    let mut images = Assets::<Image>::default();
+    let mut layouts = Assets::<TextureAtlasLayout>::default();
+    let mut atlas = DynamicAtlas::new(cfg(32), RuntimeStrategy::Guillotine);
+
+    let (image, a) = atlas
+        .insert("a", &solid(16, 16, 10), &mut images, &mut layouts)
+        .unwrap();
+    let (_, b) = atlas
+        .insert("b", &solid(16, 16, 20), &mut images, &mut layouts)
+        .unwrap();
+    assert_eq!((a.index, b.index), (0, 1));
+    let rect = layouts.get(&b.layout).unwrap().textures[b.index];
+    assert_eq!(
+        pixel(images.get(&image).unwrap(), rect.min.x, rect.min.y)[0],
+        20
+    );
+
+    // Same key: no second placement
+    let (_, again) = atlas
+        .insert("a", &solid(16, 16, 99), &mut images, &mut layouts)
+        .unwrap();
+    assert_eq!(again.index, a.index);
+
+    assert!(atlas.remove("a", &mut images, &mut layouts));
+    assert_eq!(
+        layouts.get(&a.layout).unwrap().textures[a.index].size().x,
+        0
+    );
+    let (_, c) = atlas
+        .insert("c", &solid(8, 8, 30), &mut images, &mut layouts)
+        .unwrap();
+    assert_eq!(c.index, a.index);
+    assert_eq!(atlas.len(), 2);
+
+    let mut budgeted =
+        DynamicAtlas::new(cfg(16), RuntimeStrategy::Guillotine).with_budget(RuntimeBudget {
+            max_pages: Some(1),
+            max_pixels: None,
+        });
+    budgeted
+        .insert("old", &solid(16, 16, 1), &mut images, &mut layouts)
+        .unwrap();
+    budgeted
+        .insert("new", &solid(16, 16, 2), &mut images, &mut layouts)
+        .unwrap();
+    assert!(!budgeted.contains("old"));
+    assert_eq!(budgeted.pages().len(), 1);
+}
+
+#[test]
+fn plugin_packs_folders_at_startup() {
+    let dir = std::env::temp_dir().join(format!("tex-packer-bevy-{}", std::process::id()));
+    std::fs::create_dir_all(dir.join("ui")).unwrap();
+    solid(8, 8, 1).save(dir.join("ui/button.png")).unwrap();
+    solid(6, 3, 2).save(dir.join("coin.png")).unwrap();
+    std::fs::write(dir.join("notes.txt"), "not an image").unwrap();
+
+    let keys = pack_folder(&dir, cfg(64)).unwrap().atlas.pages[0]
+        .frames
+        .iter()
+        .map(|f| f.key.clone())
+        .collect::<std::collections::BTreeSet<_>>();
+    assert_eq!(keys.into_iter().collect::<Vec<_>>(), ["coin", "ui/button"]);
+
+    let mut app = App::new();
+    app.init_resource::<Assets<Image>>()
+        .init_resource::<Assets<TextureAtlasLayout>>()
+        .add_plugins(
+            TexPackerPlugin::new()
+                .with_config(cfg(64))
+                .with_folder("sprites", &dir),
+        );
+    app.update();
+    let atlases = app.world().resource::<PackedAtlases>();
+    assert!(
+        atlases
+            .get("sprites")
+            .unwrap()
+            .sprite("ui/button")
+            .is_some()
+    );
+    std::fs::remove_dir_all(&dir).unwrap();
+}
@@ -0,0 +1,342 @@
+//! `tex-packer compare`: packs the same inputs with a matrix of algorithms, heuristics and sort
+//! orders and ranks the results.
+
+use std::fs;
+use std::path::PathBuf;
+use std::time::{Duration, Instant};
+
+use anyhow::Context;
+use clap::Parser;
+use serde::Serialize;
+use tex_packer_core::PackerConfig;
+use tex_packer_core::config::{
+    AlgorithmFamily, GuillotineChoice, MaxRectsHeuristic, NamedOption, ShelfHeuristic,
+    SkylineHeuristic, SortOrder, parse_option,
+};
+
+use crate::{
+    PackArgs, PackManifest, apply_input_options, bench_fmt_dur, layout_items, load_inputs,
+    load_manifest, packer_config,
+};
+
+#[derive(Parser, Debug, Clone)]
+pub(crate) struct CompareArgs {
+    /// Inputs and the base configuration (every pack option applies)
+    #[command(flatten)]
+    pack: PackArgs,
+    /// Algorithms, comma-separated (`all`: every algorithm except auto)
+    #[arg(
+        long,
+        value_delimiter = ',',
+        default_value = "skyline,maxrects,guillotine,shelf",
+        help_heading = "Matrix"
+    )]
+    algorithms: Vec<String>,
+    /// Heuristics, comma-separated; each applies to the algorithms that accept it (`all`: every
+    /// heuristic of each algorithm). Default: the base --heuristic/--skyline/--g-choice/--shelf
+    #[arg(long, value_delimiter = ',', help_heading = "Matrix")]
+    heuristics: Vec<String>,
+    /// Sort orders, comma-separated (`all`: every order). Default: the base --sort-order
+    #[arg(long, value_delimiter = ',', help_heading = "Matrix")]
+    sort_orders: Vec<String>,
+    /// Runs per configuration; the fastest is reported
+    #[arg(long, default_value_t = 1, value_parser = clap::value_parser!(u32).range(1..), help_heading = "Matrix")]
+    runs: u32,
+    /// Also write the results as CSV
+    #[arg(long, help_heading = "Matrix")]
+    csv: Option<PathBuf>,
+    /// Also write the results as JSON
+    #[arg(long, help_heading = "Matrix")]
+    json: Option<PathBuf>,
+}
+
+/// One configuration of the matrix and how it did.
+#[derive(Debug, Serialize)]
+struct CompareRow {
+    algorithm: String,
+    heuristic: String,
+    sort_order: String,
+    pages: usize,
+    occupancy: f64,
+    total_area: u64,
+    used_area: u64,
+    /// Inputs left out by `on_unplaceable = skip`.
+    skipped: usize,
+    /// Inputs downscaled by `on_unplaceable = scale_down`.
+    scaled: usize,
+    ms: f64,
+    /// Set when this configuration could not pack the inputs.
+    error: Option<String>,
+}
+
+pub(crate) fn run_compare(c: &CompareArgs, show_progress: bool) -> anyhow::Result<()> {
+    let manifest = match &c.pack.manifest {
+        Some(p) => load_manifest(p)?,
+        None => PackManifest::default(),
+    };
+    let cli = &PackArgs {
+        inputs: c
+            .pack
+            .inputs
+            .iter()
+            .chain(&manifest.inputs)
+            .cloned()
+            .collect(),
+        ..c.pack.clone()
+    };
+    let mut base = packer_config(cli, &manifest)?;
+    let (inputs, pivots, sprites, animations) = load_inputs(cli, show_progress)?;
+    apply_input_options(&mut base, pivots, sprites, animations)?;
+    // Trim once; every configuration lays out the same items
+    let items = layout_items(&inputs, &base)?;
+    drop(inputs);
+
+    let matrix = matrix(c, &base)?;
+    let bar = show_progress.then(|| {
+        let b = indicatif::ProgressBar::new(matrix.len() as u64);
+        b.set_style(
+            indicatif::ProgressStyle::with_template(
+                "{spinner:.green} comparing {pos}/{len} [{elapsed_precise}] {wide_msg}",
+            )
+            .unwrap(),
+        );
+        b
+    });
+    let mut rows = Vec::with_capacity(matrix.len());
+    for (cfg, heuristic) in matrix {
+        let algorithm = name_of(&cfg.family);
+        let sort_order = name_of(&cfg.sort_order);
+        if let Some(b) = &bar {
+            b.set_message(format!("{algorithm} {heuristic} {sort_order}"));
+        }
+        let mut best = Duration::MAX;
+        let mut result = None;
+        for _ in 0..c.runs {
+            let start = Instant::now();
+            let r = tex_packer_core::pack_layout_items_with(
+                items.clone(),
+                cfg.clone(),
+                &tex_packer_core::PackControl::default(),
+            );
+            best = best.min(start.elapsed());
+            result = Some(r);
+        }
+        let row = match result.expect("runs >= 1") {
+            Ok(out) => {
+                let stats = out.atlas.stats();
+                CompareRow {
+                    algorithm: algorithm.into(),
+                    heuristic,
+                    sort_order: sort_order.into(),
+                    pages: stats.num_pages,
+                    occupancy: stats.occupancy,
+                    total_area: stats.total_page_area,
+                    used_area: stats.used_frame_area,
+                    skipped: out.skipped.len(),
+                    scaled: out.scaled.len(),
+                    ms: best.as_secs_f64() * 1000.0,
+                    error: None,
+                }
+            }
+            Err(e) => CompareRow {
+                algorithm: algorithm.into(),
+                heuristic,
+                sort_order: sort_order.into(),
+                pages: 0,
+                occupancy: 0.0,
+                total_area: 0,
+                used_area: 0,
+                skipped: 0,
+                scaled: 0,
+                ms: best.as_secs_f64() * 1000.0,
+                error: Some(e.to_string()),
+            },
+        };
+        rows.push(row);
+        if let Some(b) = &bar {
+            b.inc(1);
+        }
+    }
+    if let Some(b) = bar {
+        b.finish_and_clear();
+    }
+    // Same ranking as auto mode: fewest pages, then least page area; failures last and
+    // configurations that left inputs out after those that placed everything
+    rows.sort_by(|a, b| {
+        (a.error.is_some(), a.skipped, a.pages, a.total_area)
+            .cmp(&(b.error.is_some(), b.skipped, b.pages, b.total_area))
+            .then(a.ms.total_cmp(&b.ms))
+    });
+
+    print_table(&rows);
+    if let Some(path) = &c.csv {
+        let mut csv = String::from(
+            "algorithm,heuristic,sort_order,pages,occupancy,total_area,used_area,skipped,scaled,ms,error\n",
+        );
+        for r in &rows {
+            csv += &format!(
+                "{},{},{},{},{:.6},{},{},{},{},{:.3},\"{}\"\n",
+                r.algorithm,
+                r.heuristic,
+                r.sort_order,
+                r.pages,
+                r.occupancy,
+                r.total_area,
+                r.used_area,
+                r.skipped,
+                r.scaled,
+                r.ms,
+                r.error.as_deref().unwrap_or("").replace('"', "\"\"")
+            );
+        }
+        fs::write(path, csv).with_context(|| format!("write {}", path.display()))?;
+    }
+    if let Some(path) = &c.json {
+        fs::write(path, serde_json::to_string_pretty(&rows)?)
+            .with_context(|| format!("write {}", path.display()))?;
+    }
+    Ok(())
+}
+
+/// Every (config, heuristic name) of the matrix, in algorithm, heuristic, sort order order.
+fn matrix(c: &CompareArgs, base: &PackerConfig) -> anyhow::Result<Vec<(PackerConfig, String)>> {
+    let families: Vec<AlgorithmFamily> = expand(&c.algorithms, "--algorithms")?
+        .into_iter()
+        .filter(|f| !(is_all(&c.algorithms) && *f == AlgorithmFamily::Auto))
+        .collect();
+    let sort_orders: Vec<SortOrder> = if c.sort_orders.is_empty() {
+        vec![base.sort_order.clone()]
+    } else {
+        expand(&c.sort_orders, "--sort-orders")?
+    };
+    for h in &c.heuristics {
+        let known = h == "all"
+            || h.parse::<MaxRectsHeuristic>().is_ok()
+            || h.parse::<SkylineHeuristic>().is_ok()
+            || h.parse::<GuillotineChoice>().is_ok()
+            || h.parse::<ShelfHeuristic>().is_ok();
+        if !known {
+            anyhow::bail!("--heuristics: '{h}' is not a heuristic of any algorithm");
+        }
+    }
+
+    let mut matrix = Vec::new();
+    for family in families {
+        let variants: Vec<(PackerConfig, String)> = match family {
+            AlgorithmFamily::MaxRects => {
+                heuristic_variants(c, base, base.mr_heuristic.clone(), |cfg, h| {
+                    cfg.mr_heuristic = h
+                })
+            }
+            AlgorithmFamily::Skyline => {
+                heuristic_variants(c, base, base.skyline_heuristic.clone(), |cfg, h| {
+                    cfg.skyline_heuristic = h
+                })
+            }
+            AlgorithmFamily::Guillotine => {
+                heuristic_variants(c, base, base.g_choice.clone(), |cfg, h| cfg.g_choice = h)
+            }
+            AlgorithmFamily::Shelf => {
+                heuristic_variants(c, base, base.shelf_heuristic, |cfg, h| {
+                    cfg.shelf_heuristic = h
+                })
+            }
+            // No heuristic axis
+            _ => vec![(base.clone(), "-".into())],
+        };
+        for (cfg, heuristic) in variants {
+            for order in &sort_orders {
+                let mut cfg = cfg.clone();
+                cfg.family = family.clone();
+                cfg.sort_order = order.clone();
+                matrix.push((cfg, heuristic.clone()));
+            }
+        }
+    }
+    Ok(matrix)
+}
+
+/// The heuristics of `T` selected by `--heuristics` (those `T` accepts), else `current`.
+fn heuristic_variants<T>(
+    c: &CompareArgs,
+    base: &PackerConfig,
+    current: T,
+    set: impl Fn(&mut PackerConfig, T),
+) -> Vec<(PackerConfig, String)>
+where
+    T: NamedOption + PartialEq,
+{
+    let names: Vec<&str> = if is_all(&c.heuristics) {
+        T::NAMES.to_vec()
+    } else {
+        c.heuristics
+            .iter()
+            .map(String::as_str)
+            .filter(|h| h.parse::<T>().is_ok())
+            .collect()
+    };
+    if names.is_empty() {
+        return vec![(base.clone(), name_of(&current).into())];
+    }
+    names
+        .into_iter()
+        .filter_map(|n| {
+            let mut cfg = base.clone();
+            set(&mut cfg, n.parse::<T>().ok()?);
+            Some((cfg, n.to_string()))
+        })
+        .collect()
+}
+
+fn is_all(values: &[String]) -> bool {
+    values.iter().any(|v| v == "all")
+}
+
+/// Parses a comma-separated axis; `all` expands to every name of `T`.
+fn expand<T: NamedOption>(values: &[String], flag: &str) -> anyhow::Result<Vec<T>> {
+    let names: Vec<&str> = if is_all(values) {
+        T::NAMES.to_vec()
+    } else {
+        values.iter().map(String::as_str).collect()
+    };
+    names
+        .into_iter()
+        .map(|n| Ok(parse_option(flag, n)?))
+        .collect()
+}
+
+/// Canonical name of a config enum value.
+fn name_of<T: NamedOption + PartialEq>(value: &T) -> &'static str {
+    T::NAMES
+        .iter()
+        .find(|n| n.parse::<T>().ok().as_ref() == Some(value))
+        .copied()
+        .unwrap_or("?")
+}
+
+fn print_table(rows: &[CompareRow]) {
+    println!(
+        "{:>3}  {:<10}  {:<11}  {:<13}  {:>5}  {:>9}  {:>11}  {:>7}  {:>9}",
+        "#", "algorithm", "heuristic", "sort", "pages", "occupancy", "page area", "skipped", "time"
+    );
+    for (i, r) in rows.iter().enumerate() {
+        let rank = i + 1;
+        let time = bench_fmt_dur(Duration::from_secs_f64(r.ms / 1000.0));
+        match &r.error {
+            None => println!(
+                "{rank:>3}  {:<10}  {:<11}  {:<13}  {:>5}  {:>8.2}%  {:>11}  {:>7}  {time:>9}",
+                r.algorithm,
+                r.heuristic,
+                r.sort_order,
+                r.pages,
+                r.occupancy * 100.0,
+                r.total_area,
+                r.skipped,
+            ),
+            Some(e) => println!(
+                "{rank:>3}  {:<10}  {:<11}  {:<13}  failed: {e}",
+                r.algorithm, r.heuristic, r.sort_order
+            ),
+        }
+    }
+}
@@ -0,0 +1,452 @@
+//! Export stage: `--metadata` formats (and `--engine`) resolved into a list of exporter tasks,
+//! each written from the atlas by the matching core exporter.
+
+use std::fs;
+use std::path::PathBuf;
+
+use anyhow::Context;
+use tex_packer_core::{Atlas, PackerConfig, RotationDirection};
+
+use crate::{PackArgs, page_file_name, page_file_names, page_stem};
+
+/// One metadata format of a run.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Exporter {
+    JsonArray,
+    JsonHash,
+    Plist,
+    Gdx,
+    Spine,
+    Aseprite,
+    Godot,
+    Unity,
+    BmFont,
+    BmFontBinary,
+    RustCode,
+    CHeader,
+    CSharp,
+    Binary,
+    Template,
+}
+
+impl Exporter {
+    fn parse(name: &str) -> anyhow::Result<Self> {
+        Ok(match name.trim().to_ascii_lowercase().as_str() {
+            // "json" is an alias of "json-array"
+            "json-array" | "json" => Self::JsonArray,
+            "json-hash" => Self::JsonHash,
+            "plist" => Self::Plist,
+            "gdx" => Self::Gdx,
+            "spine" => Self::Spine,
+            "aseprite" => Self::Aseprite,
+            "godot" => Self::Godot,
+            "unity" => Self::Unity,
+            "bmfont" | "fnt" => Self::BmFont,
+            "bmfont-binary" => Self::BmFontBinary,
+            "rust-code" => Self::RustCode,
+            "c-header" => Self::CHeader,
+            "csharp" => Self::CSharp,
+            "binary" => Self::Binary,
+            "template" => Self::Template,
+            other => anyhow::bail!("unknown metadata format: {}", other),
+        })
+    }
+
+    pub(crate) fn name(self) -> &'static str {
+        match self {
+            Self::JsonArray => "json-array",
+            Self::JsonHash => "json-hash",
+            Self::Plist => "plist",
+            Self::Gdx => "gdx",
+            Self::Spine => "spine",
+            Self::Aseprite => "aseprite",
+            Self::Godot => "godot",
+            Self::Unity => "unity",
+            Self::BmFont => "bmfont",
+            Self::BmFontBinary => "bmfont-binary",
+            Self::RustCode => "rust-code",
+            Self::CHeader => "c-header",
+            Self::CSharp => "csharp",
+            Self::Binary => "binary",
+            Self::Template => "template",
+        }
+    }
+
+    /// Formats whose `rotated` flag always means a 90° clockwise turn.
+    fn clockwise_only(self, cli: &PackArgs) -> bool {
+        match self {
+            Self::Plist | Self::Gdx | Self::Spine | Self::Aseprite => true,
+            Self::JsonArray | Self::JsonHash => cli.json_conformant,
+            Self::Template => matches!(
+                cli.engine
+                    .as_deref()
+                    .map(str::to_ascii_lowercase)
+                    .as_deref(),
+                Some("phaser3" | "phaser3_single" | "cocos")
+            ),
+            _ => false,
+        }
+    }
+
+    /// Extension of the `<name>.<ext>` file this exporter shares with others.
+    fn shared_file(self) -> Option<&'static str> {
+        match self {
+            Self::JsonArray | Self::JsonHash | Self::Aseprite => Some("json"),
+            Self::Gdx | Self::Spine => Some("atlas"),
+            Self::BmFont | Self::BmFontBinary => Some("fnt"),
+            _ => None,
+        }
+    }
+}
+
+/// Resolves `--metadata` (repeatable and comma-separated) and `--engine` into the exporters of
+/// one run, in order and without duplicates. `--engine spine` is served by the Spine exporter;
+/// any other `--engine` adds the template exporter. Fails on unknown formats, on exporters that
+/// would overwrite each other's file and on engines that cannot use rotated frames.
+pub(crate) fn export_tasks(cli: &PackArgs, cfg: &PackerConfig) -> anyhow::Result<Vec<Exporter>> {
+    let engine = cli.engine.as_deref().map(str::to_ascii_lowercase);
+    let engine_task = match engine.as_deref() {
+        None => None,
+        Some("spine") => Some(Exporter::Spine),
+        Some(e) if tex_packer_core::builtin_template(e).is_some() => Some(Exporter::Template),
+        Some(e) => anyhow::bail!("unknown engine template: {}", e),
+    };
+    let mut tasks: Vec<Exporter> = Vec::new();
+    for name in &cli.metadata {
+        let mut task = Exporter::parse(name)?;
+        if task == Exporter::Template && engine_task == Some(Exporter::Spine) {
+            task = Exporter::Spine;
+        }
+        if !tasks.contains(&task) {
+            tasks.push(task);
+        }
+    }
+    if let Some(task) = engine_task
+        && !tasks.contains(&task)
+    {
+        tasks.push(task);
+    }
+
+    for ext in ["json", "atlas", "fnt"] {
+        let clash: Vec<&str> = tasks
+            .iter()
+            .filter(|t| t.shared_file() == Some(ext))
+            .map(|t| t.name())
+            .collect();
+        if clash.len() > 1 {
+            anyhow::bail!(
+                "--metadata {} would all write {}.{ext}; pick one",
+                clash.join(","),
+                cli.name
+            );
+        }
+    }
+    if cli.font_metrics.is_none()
+        && let Some(t) = tasks
+            .iter()
+            .find(|t| matches!(t, Exporter::BmFont | Exporter::BmFontBinary))
+    {
+        anyhow::bail!("--metadata {} needs --font-metrics", t.name());
+    }
+    // Engines whose sprites cannot be rotated in the texture
+    if let Some(t) = tasks.iter().find(|t| {
+        cfg.allow_rotation
+            && matches!(
+                t,
+                Exporter::Godot | Exporter::Unity | Exporter::BmFont | Exporter::BmFontBinary
+            )
+    }) {
+        anyhow::bail!(
+            "--metadata {}: the engine cannot use rotated frames; set `allow_rotation: false` in the --config file",
+            t.name()
+        );
+    }
+    if let Some(t) = tasks.iter().find(|t| {
+        cfg.allow_rotation
+            && cfg.rotation_direction != RotationDirection::Clockwise
+            && t.clockwise_only(cli)
+    }) {
+        anyhow::bail!(
+            "--metadata {}: the format only describes clockwise rotation; use --rotation-direction clockwise or disable rotation",
+            t.name()
+        );
+    }
+    Ok(tasks)
+}
+
+/// Runs `tasks` against `atlas`, recording every file written.
+pub(crate) fn write_exports<K: ToString + Clone + serde::Serialize>(
+    cli: &PackArgs,
+    cfg: &PackerConfig,
+    atlas: &Atlas<K>,
+    tasks: &[Exporter],
+    written: &mut Vec<PathBuf>,
+) -> anyhow::Result<()> {
+    for &task in tasks {
+        let before = written.len();
+        if task.clockwise_only(cli) {
+            tex_packer_core::require_clockwise(atlas, task.name())?;
+        }
+        match task {
+            Exporter::JsonArray => write_json(cli, cfg, atlas, false, written)?,
+            Exporter::JsonHash => write_json(cli, cfg, atlas, true, written)?,
+            Exporter::Plist => {
+                let page_names = page_file_names(cli, cfg, atlas);
+                let text = tex_packer_core::to_plist_hash_with_pages(atlas, &page_names);
+                written.push(write_file(cli, "plist", text)?);
+            }
+            Exporter::Gdx => {
+                let page_names = page_file_names(cli, cfg, atlas);
+                let text = tex_packer_core::to_gdx_atlas(atlas, &page_names);
+                written.push(write_file(cli, "atlas", text)?);
+            }
+            Exporter::Spine => {
+                let page_names = page_file_names(cli, cfg, atlas);
+                let text = tex_packer_core::to_spine_atlas(atlas, &page_names);
+                written.push(write_file(cli, "atlas", text)?);
+            }
+            Exporter::Aseprite => write_aseprite(cli, cfg, atlas, written)?,
+            Exporter::Godot => write_godot(cli, cfg, atlas, written)?,
+            Exporter::Unity => write_unity(cli, cfg, atlas, written)?,
+            Exporter::BmFont | Exporter::BmFontBinary => {
+                written.push(write_bmfont(cli, cfg, atlas, task)?);
+            }
+            Exporter::RustCode | Exporter::CHeader | Exporter::CSharp | Exporter::Binary => {
+                written.push(write_code(cli, cfg, atlas, task)?);
+            }
+            Exporter::Template => write_template(cli, cfg, atlas, written)?,
+        }
+        tracing::info!(
+            format = task.name(),
+            files = written.len() - before,
+            pages = atlas.pages.len(),
+            "metadata written"
+        );
+    }
+    Ok(())
+}
+
+/// Writes `<name>.<ext>`.
+fn write_file(cli: &PackArgs, ext: &str, contents: impl AsRef<[u8]>) -> anyhow::Result<PathBuf> {
+    let path = cli.out_dir.join(format!("{}.{ext}", cli.name));
+    fs::write(&path, contents).with_context(|| format!("write {}", path.display()))?;
+    Ok(path)
+}
+
+/// Aseprite sheets reference a single image: one `<page_stem>.json` per page.
+fn write_aseprite<K: ToString>(
+    cli: &PackArgs,
+    cfg: &PackerConfig,
+    atlas: &Atlas<K>,
+    written: &mut Vec<PathBuf>,
+) -> anyhow::Result<()> {
+    let num_pages = atlas.pages.len();
+    for (idx, page) in atlas.pages.iter().enumerate() {
+        let image = page_file_name(cli, cfg, page.id, num_pages);
+        let json_path = cli
+            .out_dir
+            .join(format!("{}.json", page_stem(cli, page.id, num_pages)));
+        let json_value = tex_packer_core::to_aseprite_json(atlas, idx, &image);
+        fs::write(&json_path, serde_json::to_string_pretty(&json_value)?)
+            .with_context(|| format!("write {}", json_path.display()))?;
+        written.push(json_path);
+    }
+    Ok(())
+}
+
+fn json_options(cli: &PackArgs) -> tex_packer_core::JsonOptions {
+    tex_packer_core::JsonOptions {
+        uvs: cli.uvs || cli.uv_inset.is_some(),
+        uv_inset: cli.uv_inset.unwrap_or(0.0),
+    }
+}
+
+/// Writes `json-hash` (`hash`) or `json-array` metadata: `<name>.json`, or with
+/// `--json-conformant` one TexturePacker-style document per page (`<name>_<id>.json` when there
+/// are several, linked through `meta.related_multi_packs`).
+fn write_json<K: ToString + Clone + serde::Serialize>(
+    cli: &PackArgs,
+    cfg: &PackerConfig,
+    atlas: &Atlas<K>,
+    hash: bool,
+    written: &mut Vec<PathBuf>,
+) -> anyhow::Result<()> {
+    if !cli.json_conformant {
+        let json_path = cli.out_dir.join(format!("{}.json", cli.name));
+        let json_value = if hash {
+            tex_packer_core::to_json_hash_with(atlas, &json_options(cli))
+        } else {
+            tex_packer_core::to_json_array_with(atlas, &json_options(cli))
+        };
+        fs::write(&json_path, serde_json::to_string_pretty(&json_value)?)
+            .with_context(|| format!("write {}", json_path.display()))?;
+        written.push(json_path);
+        return Ok(());
+    }
+    let num_pages = atlas.pages.len();
+    let files: Vec<tex_packer_core::PackFile> = atlas
+        .pages
+        .iter()
+        .map(|p| tex_packer_core::PackFile {
+            image: page_file_name(cli, cfg, p.id, num_pages),
+            data: format!("{}.json", page_stem(cli, p.id, num_pages)),
+        })
+        .collect();
+    for (idx, file) in files.iter().enumerate() {
+        let text = if hash {
+            tex_packer_core::to_json_hash_conformant(atlas, idx, &files)
+        } else {
+            tex_packer_core::to_json_array_conformant(atlas, idx, &files)
+        };
+        let json_path = cli.out_dir.join(&file.data);
+        fs::write(&json_path, text).with_context(|| format!("write {}", json_path.display()))?;
+        written.push(json_path);
+    }
+    Ok(())
+}
+
+/// Writes `--metadata rust-code` / `c-header` / `csharp` / `binary` as `<name>.rs` / `.h` /
+/// `.cs` / `.bin`.
+fn write_code<K: ToString>(
+    cli: &PackArgs,
+    cfg: &PackerConfig,
+    atlas: &Atlas<K>,
+    task: Exporter,
+) -> anyhow::Result<PathBuf> {
+    let page_names = page_file_names(cli, cfg, atlas);
+    let (ext, code) = match task {
+        Exporter::CHeader => (
+            "h",
+            tex_packer_core::to_c_header(atlas, &page_names, &cli.name).into_bytes(),
+        ),
+        Exporter::CSharp => (
+            "cs",
+            tex_packer_core::to_csharp(atlas, &page_names, &cli.name).into_bytes(),
+        ),
+        Exporter::Binary => ("bin", tex_packer_core::to_binary_atlas(atlas, &page_names)),
+        _ => (
+            "rs",
+            tex_packer_core::to_rust_code(atlas, &page_names).into_bytes(),
+        ),
+    };
+    write_file(cli, ext, code)
+}
+
+/// Writes `--metadata template` from `--engine` or `--template` (default: the Unity template).
+/// Templates with a manifest header write one file per page or frame under `--out-dir`.
+fn write_template<K: ToString>(
+    cli: &PackArgs,
+    cfg: &PackerConfig,
+    atlas: &Atlas<K>,
+    written: &mut Vec<PathBuf>,
+) -> anyhow::Result<()> {
+    let engine = cli.engine.as_deref().map(str::to_ascii_lowercase);
+    let source = match (&engine, &cli.template) {
+        (Some(engine), _) => tex_packer_core::builtin_template(engine)
+            .ok_or_else(|| anyhow::anyhow!("unknown engine template: {}", engine))?
+            .to_string(),
+        (None, Some(path)) => {
+            fs::read_to_string(path).with_context(|| format!("read template {}", path.display()))?
+        }
+        (None, None) => tex_packer_core::builtin_template("unity")
+            .unwrap_or_default()
+            .to_string(),
+    };
+    let template = tex_packer_core::AtlasTemplate::parse(&source)?;
+    let page_names = page_file_names(cli, cfg, atlas);
+    for file in template.render(atlas, &page_names, &cli.name)? {
+        let path = match &file.path {
+            Some(path) => cli.out_dir.join(path),
+            None if engine.as_deref() == Some("phaser3") => {
+                cli.out_dir.join(format!("{}.multiatlas.json", cli.name))
+            }
+            None => cli.out_dir.join(format!("{}.template.json", cli.name)),
+        };
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent).with_context(|| format!("create {}", parent.display()))?;
+        }
+        fs::write(&path, file.contents).with_context(|| format!("write {}", path.display()))?;
+        written.push(path);
+    }
+    Ok(())
+}
+
+/// Writes `--metadata bmfont` (text) or `bmfont-binary` as `<name>.fnt`, with the glyph metrics
+/// of `--font-metrics`.
+fn write_bmfont<K: ToString>(
+    cli: &PackArgs,
+    cfg: &PackerConfig,
+    atlas: &Atlas<K>,
+    task: Exporter,
+) -> anyhow::Result<PathBuf> {
+    let path = cli
+        .font_metrics
+        .as_deref()
+        .context("--metadata bmfont needs --font-metrics")?;
+    let text = fs::read_to_string(path)
+        .with_context(|| format!("read font metrics {}", path.display()))?;
+    let ext = path
+        .extension()
+        .and_then(|s| s.to_str())
+        .map(|s| s.to_ascii_lowercase());
+    let metrics: tex_packer_core::FontMetrics = match ext.as_deref() {
+        Some("toml") => toml::from_str(&text).map_err(anyhow::Error::from),
+        Some("json") => serde_json::from_str(&text).map_err(anyhow::Error::from),
+        _ => serde_yaml::from_str(&text).map_err(anyhow::Error::from),
+    }
+    .with_context(|| format!("font metrics {}", path.display()))?;
+    let page_names = page_file_names(cli, cfg, atlas);
+    if task == Exporter::BmFont {
+        let text = tex_packer_core::to_bmfont_text(atlas, &metrics, &page_names)?;
+        write_file(cli, "fnt", text)
+    } else {
+        let bytes = tex_packer_core::to_bmfont_binary(atlas, &metrics, &page_names)?;
+        write_file(cli, "fnt", bytes)
+    }
+}
+
+/// Writes `--metadata unity`: one `<page>.tpsheet` per page for the TexturePacker Importer.
+fn write_unity<K: ToString>(
+    cli: &PackArgs,
+    cfg: &PackerConfig,
+    atlas: &Atlas<K>,
+    written: &mut Vec<PathBuf>,
+) -> anyhow::Result<()> {
+    let num_pages = atlas.pages.len();
+    for (idx, page) in atlas.pages.iter().enumerate() {
+        let image = page_file_name(cli, cfg, page.id, num_pages);
+        let text = tex_packer_core::to_unity_tpsheet(atlas, idx, &image)?;
+        let path = cli
+            .out_dir
+            .join(format!("{}.tpsheet", page_stem(cli, page.id, num_pages)));
+        fs::write(&path, text).with_context(|| format!("write {}", path.display()))?;
+        written.push(path);
+    }
+    Ok(())
+}
+
+/// Writes `--metadata godot`: one `AtlasTexture` `.tres` per frame (in subdirectories following
+/// the keys) and, with `--godot-sprite-frames`, `<name>_frames.tres`.
+fn write_godot<K: ToString>(
+    cli: &PackArgs,
+    cfg: &PackerConfig,
+    atlas: &Atlas<K>,
+    written: &mut Vec<PathBuf>,
+) -> anyhow::Result<()> {
+    let options = tex_packer_core::GodotOptions {
+        res_dir: cli.godot_res_dir.clone(),
+        sprite_frames: cli
+            .godot_sprite_frames
+            .then(|| format!("{}_frames.tres", cli.name)),
+    };
+    let page_names = page_file_names(cli, cfg, atlas);
+    for res in tex_packer_core::to_godot_resources(atlas, &page_names, &options)? {
+        let path = cli.out_dir.join(&res.path);
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir).with_context(|| format!("create {}", dir.display()))?;
+        }
+        fs::write(&path, res.contents).with_context(|| format!("write {}", path.display()))?;
+        written.push(path);
+    }
+    Ok(())
+}
@@ -1,21 +1,31 @@
 use std::fs;
 use std::path::{Path, PathBuf};
-use std::time::Duration;
+use std::time::{Duration, Instant};
 
 use anyhow::Context;
 use clap::{ArgAction, Parser, Subcommand};
 use globset::{Glob, GlobSetBuilder};
-use handlebars::Handlebars;
 use image::{DynamicImage, ImageReader};
 use serde::Deserialize;
 use tex_packer_core::config::{
-    AlgorithmFamily, AutoMode, GuillotineChoice, GuillotineSplit, MaxRectsHeuristic,
-    SkylineHeuristic, SortOrder,
+    AlgorithmFamily, AutoCandidate, AutoMode, GuillotineChoice, GuillotineSplit, MaxRectsHeuristic,
+    Padding, PageEncoding, PageSizeMode, SdfOptions, SkylineHeuristic, SpriteOptions, TrimOptions,
+    parse_option,
+};
+use tex_packer_core::{
+    Animation, AnimationFrame, AsepriteSplit, InputImage, PackerConfig, pack_images,
+    pack_images_with,
 };
-use tex_packer_core::{InputImage, PackerConfig, pack_images};
 use tracing::{error, info};
 use walkdir::WalkDir;
 
+mod compare;
+mod exporters;
+mod project;
+mod report;
+
+use report::PackReport;
+
 #[derive(Parser, Debug)]
 #[command(
     name = "tex-packer",
@@ -53,29 +63,139 @@ enum Commands {
     Layout(PackArgs),
     /// Simple timing bench (packs once, prints time + occupancy)
     Bench(BenchArgs),
+    /// Pack the inputs with a matrix of algorithms/heuristics/sort orders and rank the results
+    Compare(compare::CompareArgs),
+    /// Merge atlases and/or image folders into one re-packed atlas (pack options apply)
+    Merge(PackArgs),
+    /// Compare two atlases (JSON, plist or libGDX metadata) and report frame/page changes
+    Diff(DiffArgs),
+    /// Cut sprites back out of an atlas (JSON, plist or libGDX metadata) and its page images
+    Extract(ExtractArgs),
+    /// Check an atlas (JSON, plist or libGDX metadata) and its pages for bounds, overlap,
+    /// padding, rotation and content-hash problems
+    Verify(VerifyArgs),
+    /// Build every atlas declared in a project file (.yaml/.toml/.json)
+    Build(project::BuildArgs),
+    /// Print a JSON Schema: `config` (--config YAML), `packer-config` (--print-config output) or
+    /// `atlas` (JSON metadata)
+    Schema(SchemaArgs),
 }
 
 #[derive(Parser, Debug, Clone)]
 struct PackArgs {
     // Input/Output
-    /// Input file or directory
-    #[arg(help_heading = "Input/Output")]
-    input: PathBuf,
+    /// Input files or directories: images, image folders, or atlas metadata (.json/.plist/.atlas,
+    /// whose sprites are cut back out of the page images next to it)
+    #[arg(required_unless_present_any = ["manifest", "input_list"], help_heading = "Input/Output")]
+    inputs: Vec<PathBuf>,
+    /// File listing input images, one `path[:key]` per line (`-` reads stdin); blank lines and
+    /// `#` comments are skipped, relative paths resolve against the list's folder, and the key
+    /// defaults to the path as written
+    #[arg(long, help_heading = "Input/Output")]
+    input_list: Option<PathBuf>,
+    /// Pack manifest (.toml/.json/.yaml): extra `inputs` plus per-sprite overrides (`[sprites."key"]`
+    /// with padding, extrusion, pivot, rotate, group, nine_patch, exclude)
+    #[arg(long, help_heading = "Input/Output")]
+    manifest: Option<PathBuf>,
     /// Output directory
     #[arg(short, long, default_value = "out", help_heading = "Input/Output")]
     out_dir: PathBuf,
     /// Atlas base name (files will be name.png/.json)
     #[arg(short, long, default_value = "atlas", help_heading = "Input/Output")]
     name: String,
+    /// Page image format: png | jpeg | webp | avif | ktx2 | dds | exr (ktx2/dds require features `ktx2`/`dds`; exr packs 32-bit float pages, keeping the full range of .exr/.hdr inputs)
+    #[arg(long, default_value = "png", value_parser = ["png", "jpeg", "jpg", "webp", "avif", "ktx2", "dds", "exr"], help_heading = "Input/Output")]
+    page_format: String,
+    /// Quality (1-100) for jpeg/avif pages and lossy webp pages
+    #[arg(long, default_value_t = 90, value_parser = clap::value_parser!(u8).range(1..=100), help_heading = "Input/Output")]
+    page_quality: u8,
+    /// WebP pages: exact pixels (--webp-lossless false quantizes colors by --page-quality)
+    #[arg(long, default_value_t = true, action = ArgAction::Set, help_heading = "Input/Output")]
+    webp_lossless: bool,
+    /// AVIF encoder speed: 1 (slowest, smallest) to 10 (fastest)
+    #[arg(long, default_value_t = 6, value_parser = clap::value_parser!(u8).range(1..=10), help_heading = "Input/Output")]
+    avif_speed: u8,
+    /// Recompress PNG pages losslessly with oxipng at this level, 0-6 (requires feature `oxipng`)
+    #[arg(long, value_parser = clap::value_parser!(u8).range(0..=6), help_heading = "Input/Output")]
+    png_optimize: Option<u8>,
+    /// Block compression for DDS pages: bc1 | bc3 | bc7 (page sizes are padded to multiples of 4)
+    #[arg(long, default_value = "bc7", value_parser = ["bc1", "bc3", "bc7"], help_heading = "Input/Output")]
+    bcn: String,
+    /// Basis Universal codec for KTX2 pages: etc1s (small) | uastc (high quality)
+    #[arg(long, default_value = "etc1s", value_parser = ["etc1s", "uastc"], help_heading = "Input/Output")]
+    basis_codec: String,
+    /// Basis Universal quality (1-255) for KTX2 pages; UASTC maps it onto its 5 pack levels
+    #[arg(long, default_value_t = 128, value_parser = clap::value_parser!(u8).range(1..=255), help_heading = "Input/Output")]
+    basis_quality: u8,
+    /// Also write the --mip-levels chain of every page: inside .ktx2 pages, otherwise as
+    /// <page>_mip<N> files
+    #[arg(long, default_value_t = false, help_heading = "Input/Output")]
+    mip_chain: bool,
     /// YAML config file path (overrides algorithm-related options)
     #[arg(long, help_heading = "Input/Output")]
     config: Option<PathBuf>,
+    /// Inline config from a `build` project; takes the place of --config
+    #[arg(skip)]
+    inline_config: Option<YamlConfig>,
     /// Include patterns (glob). If set, only files matching any pattern are considered
     #[arg(long, help_heading = "Input/Output")]
     include: Vec<String>,
     /// Exclude patterns (glob). Files matching any pattern will be ignored
     #[arg(long, help_heading = "Input/Output")]
     exclude: Vec<String>,
+    /// Descend at most this many folder levels below each input folder (1: only its own files)
+    #[arg(long, help_heading = "Input/Output")]
+    max_depth: Option<usize>,
+    /// Follow symbolic links while walking input folders (loops are skipped)
+    #[arg(long, default_value_t = false, help_heading = "Input/Output")]
+    follow_symlinks: bool,
+    /// Also pack hidden files and folders (names starting with `.`) found in input folders
+    #[arg(long, default_value_t = false, help_heading = "Input/Output")]
+    include_hidden: bool,
+    /// How .ase/.aseprite inputs become sprites: frames (keyed by tag, e.g. `hero/walk_0`),
+    /// layers (keyed by layer path) or slices (keyed by slice name, with slice pivots and
+    /// nine-patches)
+    #[arg(long, default_value = "frames", value_parser = ["frames", "layers", "slices"], help_heading = "Input/Output")]
+    aseprite_split: String,
+    /// Pack every frame of animated GIF/APNG inputs (keyed `<name>#0`, `<name>#1`, ...) and
+    /// record them as an animation in the metadata; otherwise only the first frame is packed
+    #[arg(long, default_value_t = false, help_heading = "Input/Output")]
+    expand_animations: bool,
+    /// Key prefix per input, in input order (e.g. `--prefix ui/ --prefix hud/`)
+    #[arg(long = "prefix", help_heading = "Input/Output")]
+    prefixes: Vec<String>,
+    /// Prefix keys of inputs without an explicit --prefix with the input's file/folder stem and '/'
+    #[arg(long, default_value_t = false, help_heading = "Input/Output")]
+    auto_prefix: bool,
+
+    // Keys
+    /// Frame key format: path (as found) | relative (to the input folder) | name (file name) | stem (file name without extension)
+    #[arg(long, default_value = "path", value_parser = ["path", "relative", "name", "stem"], help_heading = "Keys")]
+    key_format: String,
+    /// Remove this literal prefix from keys (applied after --key-format)
+    #[arg(long, help_heading = "Keys")]
+    key_strip_prefix: Option<String>,
+    /// Regex rename `PATTERN=REPLACEMENT` (repeatable, applied in order; `$1` refers to groups)
+    #[arg(long, help_heading = "Keys")]
+    key_replace: Vec<String>,
+    /// Key case folding: keep | lower | upper
+    #[arg(long, default_value = "keep", value_parser = ["keep", "lower", "upper"], help_heading = "Keys")]
+    key_case: String,
+    /// Inputs that end up with the same key: error | suffix (rename to name_2, ...) | overwrite (last wins)
+    #[arg(long, default_value = "error", value_parser = ["error", "suffix", "overwrite"], help_heading = "Keys")]
+    duplicate_keys: String,
+
+    // Pivots
+    /// Default pivot: center | bottom-center | top-left | ... | `x,y` (normalized, y down)
+    #[arg(long, default_value = "center", help_heading = "Pivots")]
+    pivot: String,
+    /// Pivot detection for sprites without a manifest/sidecar pivot: manual (use --pivot) | center | alpha-centroid | bottom-center (feet)
+    #[arg(long, default_value = "manual", value_parser = ["manual", "center", "alpha-centroid", "bottom-center"], help_heading = "Pivots")]
+    pivot_mode: String,
+    /// Pivot manifest (JSON/YAML map of frame key -> `"bottom-center"`, `"0.5,1"`, `{x, y}` or `[x, y]`).
+    /// A `<image stem>.pivot` file next to an input image sets that image's pivot too.
+    #[arg(long, help_heading = "Pivots")]
+    pivots: Option<PathBuf>,
 
     // Layout
     /// Max width
@@ -93,6 +213,41 @@ struct PackArgs {
     /// Force square page
     #[arg(long, default_value_t = false, help_heading = "Layout")]
     square: bool,
+    /// Shrink a single-page atlas to the smallest page that still fits (re-packs per candidate size)
+    #[arg(long, default_value_t = false, help_heading = "Layout")]
+    minimize_page_size: bool,
+    /// Minimum page width (pages grow to at least this size)
+    #[arg(long, default_value_t = 0, help_heading = "Layout")]
+    min_width: u32,
+    /// Minimum page height
+    #[arg(long, default_value_t = 0, help_heading = "Layout")]
+    min_height: u32,
+    /// Allowed page sizes, comma-separated (e.g. 256,512,1024,2048); pages snap up to the next one
+    #[arg(long, value_delimiter = ',', help_heading = "Layout")]
+    allowed_sizes: Vec<u32>,
+    /// Page size policy: tight_crop[:MARGIN] | pow2 | fixed | allowed_list:SIZE,SIZE,...
+    /// (overrides --pow2, --force-max-dimensions and --allowed-sizes)
+    #[arg(long, help_heading = "Layout")]
+    page_size: Option<String>,
+    /// Maximum number of pages; fail instead of producing more
+    #[arg(long, help_heading = "Layout")]
+    max_pages: Option<usize>,
+    /// Inputs too large for a page: error | skip (leave them out) | scale_down (shrink them to fit)
+    #[arg(long, default_value = "error", help_heading = "Layout")]
+    on_unplaceable: String,
+    /// Page grouping: none | folder (inputs from different folders never share a page)
+    #[arg(long, default_value = "none", value_parser = ["none", "folder"], help_heading = "Layout")]
+    group_by: String,
+    /// Maximum pages per group (1 keeps each group on a single page)
+    #[arg(long, help_heading = "Layout")]
+    group_max_pages: Option<usize>,
+    /// Multi-page balance: fill_first | balanced (spread frames evenly over the same page count) |
+    /// multi_bin (keep every page open and place each frame on the page that fits it best)
+    #[arg(long, default_value = "fill_first", help_heading = "Layout")]
+    page_balance: String,
+    /// Frame order within each page in every export: packing (placement order) | key | input | position (top to bottom, left to right)
+    #[arg(long, default_value = "packing", help_heading = "Export")]
+    frame_order: String,
     /// Sort order: area_desc|max_side_desc|height_desc|width_desc|name_asc|none
     #[arg(long, default_value = "area_desc", help_heading = "Layout")]
     sort_order: String,
@@ -101,36 +256,81 @@ struct PackArgs {
     /// Allow rotation (90deg)
     #[arg(long, default_value_t = true, help_heading = "Image Processing")]
     allow_rotation: bool,
+    /// Direction rotated frames are turned: clockwise|counter_clockwise
+    #[arg(long, default_value = "clockwise", help_heading = "Image Processing")]
+    rotation_direction: String,
     /// Border padding (around entire page)
     #[arg(long, default_value_t = 0, help_heading = "Image Processing")]
     border_padding: u32,
     /// Padding between frames
     #[arg(long, default_value_t = 2, help_heading = "Image Processing")]
     texture_padding: u32,
+    /// Uneven padding between frames: N, X,Y or left,top,right,bottom (overrides --texture-padding)
+    #[arg(long, value_parser = parse_padding, help_heading = "Image Processing")]
+    padding: Option<Padding>,
     /// Extrude pixels around each frame
     #[arg(long, default_value_t = 0, help_heading = "Image Processing")]
     texture_extrusion: u32,
+    /// Fill of the extruded border: clamp (repeat edges) | wrap (tileable textures) | mirror
+    #[arg(long, default_value = "clamp", value_parser = ["clamp", "wrap", "mirror"], help_heading = "Image Processing")]
+    extrude_mode: String,
+    /// Place every frame at x/y multiples of N (e.g. 4 for BCn pages); 0 or 1 disables
+    #[arg(long, default_value_t = 0, help_heading = "Image Processing")]
+    align: u32,
+    /// With --align, also round frame sizes up to multiples of N
+    #[arg(long, default_value_t = false, help_heading = "Image Processing")]
+    align_size: bool,
     /// Trim transparent borders
     #[arg(long, default_value_t = true, help_heading = "Image Processing")]
     trim: bool,
     /// Trim alpha threshold (0..=255)
     #[arg(long, default_value_t = 0, help_heading = "Image Processing")]
     trim_threshold: u8,
+    /// Transparent pixels kept around trimmed content: N or left,top,right,bottom
+    #[arg(long, value_parser = parse_trim_margin, help_heading = "Image Processing")]
+    trim_margin: Option<[u32; 4]>,
+    /// Never trim a sprite below this width/height
+    #[arg(long, default_value_t = 0, help_heading = "Image Processing")]
+    trim_min_size: u32,
+    /// Round trimmed sizes up to a multiple of K (e.g. 4 for BCn blocks)
+    #[arg(long, default_value_t = 0, help_heading = "Image Processing")]
+    trim_multiple: u32,
     /// Draw red outlines (debug)
     #[arg(long, default_value_t = false, help_heading = "Image Processing")]
     outlines: bool,
+    /// Alpha bleed radius: fill transparent pixels in each frame with nearby opaque color
+    #[arg(long, help_heading = "Image Processing")]
+    alpha_bleed: Option<u32>,
+    /// Mip levels the pages will be sampled with (level 0 included); widens the gap between frames so mips do not bleed and records `meta.mip`
+    #[arg(long, help_heading = "Image Processing")]
+    mip_levels: Option<u32>,
+    /// Page background color (#rrggbb, #rrggbbaa or r,g,b[,a]); semi-transparent pixels are matted over it
+    #[arg(long, help_heading = "Image Processing")]
+    background: Option<String>,
+    /// Convert inputs to signed distance fields before packing: single (alpha field in every channel) | multi (per-channel R, G, B fields plus alpha)
+    #[arg(long, help_heading = "Image Processing")]
+    sdf: Option<String>,
+    /// Distance band of --sdf in pixels; inputs grow by half of it per side
+    #[arg(long, default_value_t = 8, help_heading = "Image Processing")]
+    sdf_range: u32,
+    /// Also write debug PNGs per page: free space, per-frame waste heat map, padding/extrusion
+    #[arg(long, default_value_t = false, help_heading = "Export")]
+    debug_output: bool,
     /// Layout-only: compute placements and export metadata (no PNGs)
     #[arg(long, default_value_t = false, help_heading = "Export")]
     layout_only: bool,
 
     // Algorithms/Heuristics/Auto
-    /// Algorithm: skyline | maxrects | guillotine | auto
-    #[arg(long, value_parser = ["skyline", "maxrects", "guillotine", "auto"], default_value = "skyline", help_heading = "Algorithms")]
+    /// Algorithm: skyline | maxrects | guillotine | shelf | grid | auto
+    #[arg(long, value_parser = ["skyline", "maxrects", "guillotine", "shelf", "grid", "auto"], default_value = "skyline", help_heading = "Algorithms")]
     algorithm: String,
+    /// Grid mode: uniform WxH cells (or N for NxN, or `auto` for the largest sprite); implies --algorithm grid
+    #[arg(long, help_heading = "Algorithms")]
+    grid: Option<String>,
     /// MaxRects heuristic: baf|bssf|blsf|bl|cp
     #[arg(long, default_value = "baf", help_heading = "Heuristics")]
     heuristic: String,
-    /// Skyline heuristic: bl|minwaste
+    /// Skyline heuristic: bl|minwaste|bl_wm|minwaste_wm (_wm adds a waste map)
     #[arg(long, default_value = "bl", help_heading = "Heuristics")]
     skyline: String,
     /// Guillotine choice: baf|bssf|blsf|waf|wssf|wlsf
@@ -139,15 +339,37 @@ struct PackArgs {
     /// Guillotine split: slas|llas|minas|maxas|sas|las
     #[arg(long, default_value = "slas", help_heading = "Heuristics")]
     g_split: String,
-    /// Auto mode: fast | quality
+    /// Guillotine: merge adjacent free rectangles after splits (--g-merge false to disable)
+    #[arg(long, default_value_t = true, action = ArgAction::Set, help_heading = "Heuristics")]
+    g_merge: bool,
+    /// Shelf heuristic: nf|ff|bhf (next fit, first fit, best height fit)
+    #[arg(long, default_value = "ff", help_heading = "Heuristics")]
+    shelf: String,
+    /// Auto mode: fast | quality | exhaustive | anneal
     #[arg(long, default_value = "quality", help_heading = "Auto/Portfolio")]
     auto_mode: String,
+    /// Auto candidates replacing the --auto-mode portfolio, comma-separated
+    /// `family[:heuristic][@sort_order]` (e.g. maxrects:baf,skyline:minwaste,shelf:bhf@height_desc)
+    #[arg(long, value_delimiter = ',', help_heading = "Auto/Portfolio")]
+    auto_candidates: Vec<String>,
+    /// Auto winner selection: pages | area | occupancy | max_dimension | weighted:PAGES,AREA,MAX_DIMENSION,OCCUPANCY
+    #[arg(long, default_value = "pages", help_heading = "Auto/Portfolio")]
+    auto_objective: String,
     /// Time budget for auto mode (ms)
     #[arg(long, help_heading = "Auto/Portfolio")]
     time_budget: Option<u64>,
     /// Evaluate auto candidates in parallel (requires core feature `parallel`)
     #[arg(long, default_value_t = false, help_heading = "Auto/Portfolio")]
     parallel: bool,
+    /// Deterministic auto mode: ignore the time budget so results are identical across runs and thread counts
+    #[arg(long, default_value_t = false, help_heading = "Auto/Portfolio")]
+    deterministic: bool,
+    /// Spend the rest of the time budget refining the auto winner (sort orders, rotation, MaxRects variants)
+    #[arg(long, default_value_t = false, help_heading = "Auto/Portfolio")]
+    auto_refine: bool,
+    /// Composite pages into temporary scratch files and stream PNG rows (for 8192x8192+ pages)
+    #[arg(long, default_value_t = false)]
+    low_memory: bool,
     /// Use waste map for skyline
     #[arg(long, default_value_t = false, help_heading = "Heuristics")]
     use_waste_map: bool,
@@ -157,6 +379,9 @@ struct PackArgs {
     /// Use reference-accurate MaxRects split/prune (SplitFreeNode style)
     #[arg(long, default_value_t = false, help_heading = "Auto/Portfolio")]
     mr_reference: bool,
+    /// Global MaxRects insertion: place the best-scoring remaining item each step (slower, denser)
+    #[arg(long, default_value_t = false, help_heading = "Heuristics")]
+    mr_global: bool,
     /// Auto: enable mr_reference when time budget >= this (ms) (overrides default heuristic)
     #[arg(long, help_heading = "Auto/Portfolio")]
     auto_mr_ref_time_threshold: Option<u64>,
@@ -165,15 +390,43 @@ struct PackArgs {
     auto_mr_ref_input_threshold: Option<usize>,
 
     // Export
-    /// Metadata format: json-array | json (alias) | json-hash | plist | template
-    #[arg(long, default_value = "json-array", help_heading = "Export")]
-    metadata: String,
-    /// Built-in engine template: unity | godot | phaser3 | phaser3_single | spine | cocos | unreal
+    /// Metadata format(s), repeatable or comma-separated; one run writes them all: json-array | json (alias) | json-hash | plist | gdx | spine | aseprite | godot | unity | bmfont | bmfont-binary | rust-code | c-header | csharp | binary | template
+    #[arg(
+        long,
+        default_value = "json-array",
+        value_delimiter = ',',
+        help_heading = "Export"
+    )]
+    metadata: Vec<String>,
+    /// Built-in engine template, written alongside --metadata (superseded by --metadata unity / godot): unity | godot | phaser3 | phaser3_single | spine | cocos | unreal
     #[arg(long, help_heading = "Export")]
     engine: Option<String>,
-    /// External template file (handlebars), used when --metadata template
+    /// External handlebars template, used when --metadata template; a `{{!-- tex-packer` manifest header can write one file per page or frame
     #[arg(long, help_heading = "Export")]
     template: Option<PathBuf>,
+    /// Add normalized UVs (uv: {u0, v0, u1, v1}) per frame to json-array/json-hash metadata
+    #[arg(long, default_value_t = false, help_heading = "Export")]
+    uvs: bool,
+    /// Texels to inset each UV edge by (0.5 = half-texel, for linear filtering); implies --uvs
+    #[arg(long, help_heading = "Export")]
+    uv_inset: Option<f32>,
+    /// Write json-array/json-hash as TexturePacker-conformant files (one per page, with
+    /// meta.image and related_multi_packs) for Pixi.js / Phaser loaders
+    #[arg(long, default_value_t = false, help_heading = "Export")]
+    json_conformant: bool,
+    /// Godot resource directory the output is imported under (for --metadata godot paths)
+    #[arg(long, default_value = "res://", help_heading = "Export")]
+    godot_res_dir: String,
+    /// With --metadata godot, also write <name>_frames.tres (SpriteFrames of the animations)
+    #[arg(long, default_value_t = false, help_heading = "Export")]
+    godot_sprite_frames: bool,
+    /// Glyph metrics for --metadata bmfont / bmfont-binary (.json/.toml/.yaml): `face`, `size`,
+    /// `line_height`, `base`, `glyphs` ({id, key, xoffset, yoffset, xadvance}) and `kernings`
+    #[arg(long, help_heading = "Export")]
+    font_metrics: Option<PathBuf>,
+    /// Degenerate frames (zero-sized or collapsed transparent inputs) in metadata: keep | skip
+    #[arg(long, default_value = "keep", value_parser = ["keep", "skip"], help_heading = "Export")]
+    degenerate: String,
     /// Export packing stats (JSON) to this file
     #[arg(long, help_heading = "Export")]
     export_stats: Option<PathBuf>,
@@ -186,16 +439,33 @@ struct PackArgs {
     /// Dry run: compute layout and stats but do not write files
     #[arg(long, default_value_t = false, help_heading = "Export")]
     dry_run: bool,
+    /// Result output: text (logs) | json (one result document on stdout, no logs)
+    #[arg(long, default_value = "text", value_parser = ["text", "json"], help_heading = "Export")]
+    report: String,
+
+    // CI gates
+    /// Fail (exit 1) when overall occupancy is below this ratio (0.0-1.0)
+    #[arg(long, value_parser = parse_ratio, help_heading = "CI Gates")]
+    fail_on_occupancy_below: Option<f64>,
+    /// Fail (exit 1) when the atlas has more than N pages
+    #[arg(long, help_heading = "CI Gates")]
+    fail_on_pages_above: Option<usize>,
+    /// Fail (exit 1) before packing when a sprite (trimmed, plus padding/extrusion) cannot fit on a page, listing all such sprites
+    #[arg(long, default_value_t = false, help_heading = "CI Gates")]
+    fail_on_oversize: bool,
+    /// Fail (exit 1) before writing when the layout breaks the config: overlapping frames or padding/extrusion, frames past the page border, inconsistent trimmed sources
+    #[arg(long, default_value_t = false, help_heading = "CI Gates")]
+    validate: bool,
 }
 
 #[derive(Parser, Debug, Clone)]
 struct BenchArgs {
     /// Input directory
     input: PathBuf,
-    /// Algorithm: skyline | maxrects | guillotine | auto
-    #[arg(long, value_parser = ["skyline", "maxrects", "guillotine", "auto"], default_value = "auto")]
+    /// Algorithm: skyline | maxrects | guillotine | shelf | grid | auto
+    #[arg(long, value_parser = ["skyline", "maxrects", "guillotine", "shelf", "grid", "auto"], default_value = "auto")]
     algorithm: String,
-    /// Auto mode: fast | quality
+    /// Auto mode: fast | quality | exhaustive | anneal
     #[arg(long, default_value = "quality")]
     auto_mode: String,
     /// Time budget for auto mode (ms)
@@ -203,394 +473,788 @@ struct BenchArgs {
     time_budget: Option<u64>,
 }
 
+#[derive(Parser, Debug, Clone)]
+struct DiffArgs {
+    /// Old atlas metadata (json-array/json-hash/TexturePacker JSON, plist or libGDX .atlas)
+    old: PathBuf,
+    /// New atlas metadata (same formats as OLD)
+    new: PathBuf,
+    /// Report format: text | json
+    #[arg(long, default_value = "text", value_parser = ["text", "json"])]
+    format: String,
+    /// Exit with status 1 when the atlases differ (for CI)
+    #[arg(long, default_value_t = false)]
+    fail_on_change: bool,
+}
+
+#[derive(Parser, Debug, Clone)]
+struct ExtractArgs {
+    /// Atlas metadata (json-array/json-hash/TexturePacker JSON, plist or libGDX .atlas)
+    atlas: PathBuf,
+    /// Page images in page order (default: texture names from the metadata, else `<atlas stem>.png` or `<atlas stem>_<id>.png`, next to the metadata)
+    pages: Vec<PathBuf>,
+    /// Output directory; sprites are written as `<out_dir>/<key>.png`
+    #[arg(short, long, default_value = "sprites")]
+    out_dir: PathBuf,
+}
+
+#[derive(Parser, Debug, Clone)]
+struct VerifyArgs {
+    /// Atlas metadata (json-array/json-hash/TexturePacker JSON, plist or libGDX .atlas)
+    atlas: PathBuf,
+    /// Page images in page order (default: resolved like `extract`; pixel checks are skipped if they are missing)
+    pages: Vec<PathBuf>,
+    /// Minimum gap between frames (default: texture padding recorded in the metadata)
+    #[arg(long)]
+    padding: Option<u32>,
+    /// Minimum gap between frames and page edges (default: border padding recorded in the metadata)
+    #[arg(long)]
+    border: Option<u32>,
+    /// Only check the metadata, not the page pixels
+    #[arg(long, default_value_t = false)]
+    no_pixels: bool,
+    /// Report format: text | json
+    #[arg(long, default_value = "text", value_parser = ["text", "json"])]
+    format: String,
+}
+
+#[derive(Parser, Debug, Clone)]
+struct SchemaArgs {
+    /// Which document: config | packer-config | atlas
+    #[arg(value_parser = ["config", "packer-config", "atlas"])]
+    kind: String,
+    /// Atlas metadata layout (for `atlas`): json-array | json-hash
+    #[arg(long, default_value = "json-array", value_parser = ["json-array", "json", "json-hash"])]
+    format: String,
+    /// Write the schema to this file instead of stdout
+    #[arg(short, long)]
+    out: Option<PathBuf>,
+}
+
 fn main() -> anyhow::Result<()> {
     let cli = Cli::parse();
-    init_tracing_with_level(cli.quiet, cli.verbose);
+    let json_report = match &cli.command {
+        Commands::Pack(a) | Commands::Template(a) | Commands::Layout(a) | Commands::Merge(a) => {
+            a.report == "json"
+        }
+        Commands::Build(b) => b.report == "json",
+        _ => false,
+    };
+    init_tracing_with_level(cli.quiet, cli.verbose, json_report);
+    let verbose = cli.verbose > 0 && !cli.quiet && !json_report;
+    let progress = cli.progress && !cli.quiet && !json_report;
     match &cli.command {
-        Commands::Pack(args) => run_pack(args, cli.progress && !cli.quiet),
+        Commands::Pack(args) => pack_command(args, progress, verbose),
         Commands::Template(args) => {
             let mut a = args.clone();
-            a.metadata = "template".into();
-            run_pack(&a, cli.progress && !cli.quiet)
+            a.metadata = vec!["template".into()];
+            pack_command(&a, progress, verbose)
         }
         Commands::Layout(args) => {
             let mut a = args.clone();
             a.layout_only = true;
-            run_pack(&a, false)
+            pack_command(&a, false, verbose)
         }
+        Commands::Merge(args) => pack_command(args, progress, verbose),
         Commands::Bench(b) => run_bench(b),
+        Commands::Compare(c) => compare::run_compare(c, progress),
+        Commands::Diff(d) => run_diff(d),
+        Commands::Extract(e) => run_extract(e),
+        Commands::Verify(v) => run_verify(v),
+        Commands::Build(b) => {
+            let result = project::run_build(b, progress, verbose);
+            if json_report {
+                report::print_json(&result)?;
+            }
+            let failed = result?.failed;
+            if !failed.is_empty() {
+                let names: Vec<&str> = failed.iter().map(|f| f.name.as_str()).collect();
+                anyhow::bail!("failed atlases: {}", names.join(", "));
+            }
+            Ok(())
+        }
+        Commands::Schema(s) => run_schema(s),
     }
 }
 
-fn run_pack(cli: &PackArgs, show_progress: bool) -> anyhow::Result<()> {
+fn run_pack(cli: &PackArgs, show_progress: bool, verbose: bool) -> anyhow::Result<PackReport> {
+    let started = Instant::now();
+    report::take_warnings();
+    let mut report = PackReport {
+        name: cli.name.clone(),
+        out_dir: cli.out_dir.clone(),
+        dry_run: cli.dry_run,
+        layout_only: cli.layout_only,
+        ..Default::default()
+    };
     fs::create_dir_all(&cli.out_dir)
         .with_context(|| format!("create out_dir {}", cli.out_dir.display()))?;
 
-    let (family, mr_heuristic, sky_heuristic, g_choice, g_split, auto_mode) = parse_algo(cli)?;
+    let manifest = match &cli.manifest {
+        Some(p) => load_manifest(p)?,
+        None => PackManifest::default(),
+    };
+    let cli = &PackArgs {
+        inputs: cli.inputs.iter().chain(&manifest.inputs).cloned().collect(),
+        ..cli.clone()
+    };
+    let cfg = packer_config(cli, &manifest)?;
+    let tasks = exporters::export_tasks(cli, &cfg)?;
 
-    // Load config file if provided; config file sets algorithm-related options en bloc
-    let cfg = if let Some(path) = &cli.config {
-        let file = fs::read_to_string(path)?;
-        let y: YamlConfig = serde_yaml::from_str(&file)?;
-        let mut tmp = y.into_packer_config(PackerConfig {
-            max_width: cli.max_width,
-            max_height: cli.max_height,
-            allow_rotation: cli.allow_rotation,
-            force_max_dimensions: cli.force_max_dimensions,
-            border_padding: cli.border_padding,
-            texture_padding: cli.texture_padding,
-            texture_extrusion: cli.texture_extrusion,
-            trim: cli.trim,
-            trim_threshold: cli.trim_threshold,
-            texture_outlines: cli.outlines,
-            power_of_two: cli.pow2,
-            square: cli.square,
-            use_waste_map: cli.use_waste_map,
-            family,
-            mr_heuristic,
-            skyline_heuristic: sky_heuristic,
-            g_choice,
-            g_split,
-            auto_mode,
-            sort_order: parse_sort_order(&cli.sort_order)?,
-            time_budget_ms: cli.time_budget,
-            parallel: cli.parallel,
-            mr_reference: false,
-            auto_mr_ref_time_ms_threshold: cli.auto_mr_ref_time_threshold,
-            auto_mr_ref_input_threshold: cli.auto_mr_ref_input_threshold,
-            transparent_policy: cli
-                .transparent_policy
-                .parse()
-                .unwrap_or(tex_packer_core::config::TransparentPolicy::Keep),
-        });
-        if cli.mr_reference {
-            tmp.mr_reference = true;
+    #[cfg(not(feature = "ktx2"))]
+    if cfg.page_format == tex_packer_core::config::PageFormat::Ktx2 {
+        anyhow::bail!("--page-format ktx2 requires building with `--features ktx2`");
+    }
+    #[cfg(not(feature = "dds"))]
+    if cfg.page_format == tex_packer_core::config::PageFormat::Dds {
+        anyhow::bail!("--page-format dds requires building with `--features dds`");
+    }
+    #[cfg(not(feature = "oxipng"))]
+    if cfg.page_encoding.png_optimize.is_some() {
+        anyhow::bail!("--png-optimize requires building with `--features oxipng`");
+    }
+    if cli.mip_chain {
+        use tex_packer_core::config::PageFormat;
+        if cfg.mip_levels.is_none() {
+            anyhow::bail!("--mip-chain needs --mip-levels");
         }
-        tmp
-    } else {
-        PackerConfig {
-            max_width: cli.max_width,
-            max_height: cli.max_height,
-            allow_rotation: cli.allow_rotation,
-            force_max_dimensions: cli.force_max_dimensions,
-            border_padding: cli.border_padding,
-            texture_padding: cli.texture_padding,
-            texture_extrusion: cli.texture_extrusion,
-            trim: cli.trim,
-            trim_threshold: cli.trim_threshold,
-            texture_outlines: cli.outlines,
-            power_of_two: cli.pow2,
-            square: cli.square,
-            use_waste_map: cli.use_waste_map,
-            family,
-            mr_heuristic,
-            skyline_heuristic: sky_heuristic,
-            g_choice,
-            g_split,
-            auto_mode,
-            sort_order: parse_sort_order(&cli.sort_order)?,
-            time_budget_ms: cli.time_budget,
-            parallel: cli.parallel,
-            mr_reference: cli.mr_reference,
-            auto_mr_ref_time_ms_threshold: cli.auto_mr_ref_time_threshold,
-            auto_mr_ref_input_threshold: cli.auto_mr_ref_input_threshold,
-            transparent_policy: cli
-                .transparent_policy
-                .parse()
-                .unwrap_or(tex_packer_core::config::TransparentPolicy::Keep),
+        if matches!(cfg.page_format, PageFormat::Dds | PageFormat::Exr) {
+            anyhow::bail!(
+                "--mip-chain does not support --page-format {}",
+                cli.page_format
+            );
         }
-    };
+    }
 
     if cli.print_config {
         match cli.print_config_format.as_str() {
             "yaml" => println!("{}", serde_yaml::to_string(&cfg)?),
             _ => println!("{}", serde_json::to_string_pretty(&cfg)?),
         }
-        return Ok(());
+        return Ok(report);
     }
 
-    let paths = gather_paths(&cli.input, &cli.include, &cli.exclude)?;
-    let inputs = load_images_with_progress(&paths, show_progress)?;
+    let (inputs, input_pivots, input_sprites, input_animations) = load_inputs(cli, show_progress)?;
+    report.timings_ms.load = started.elapsed().as_millis() as u64;
     info!(count = inputs.len(), "loaded input images");
+    let mut cfg = cfg;
+    apply_input_options(&mut cfg, input_pivots, input_sprites, input_animations)?;
+    if cli.fail_on_oversize {
+        let oversized = oversized_inputs(&inputs, &cfg)?;
+        if !oversized.is_empty() {
+            anyhow::bail!(
+                "--fail-on-oversize: {} sprite(s) do not fit a {}x{} page: {}",
+                oversized.len(),
+                cfg.max_width,
+                cfg.max_height,
+                oversized.join(", ")
+            );
+        }
+    }
     // layout-only branch
     if cli.layout_only {
-        use tex_packer_core::pipeline::LayoutItem;
-        let mut items: Vec<LayoutItem<String>> = Vec::with_capacity(inputs.len());
-        for inp in &inputs {
-            let rgba = inp.image.to_rgba8();
-            let (w, h) = rgba.dimensions();
-            let (tw, th, source, trimmed) = if cfg.trim {
-                let (trim_opt, src_rect) =
-                    tex_packer_core::pipeline::compute_trim_rect(&rgba, cfg.trim_threshold);
-                match trim_opt {
-                    Some(r) => (r.w, r.h, src_rect, true),
-                    None => (w, h, tex_packer_core::Rect::new(0, 0, w, h), false),
-                }
-            } else {
-                (w, h, tex_packer_core::Rect::new(0, 0, w, h), false)
-            };
-            items.push(LayoutItem {
-                key: inp.key.clone(),
-                w: tw,
-                h: th,
-                source: Some(source),
-                source_size: Some((w, h)),
-                trimmed,
-            });
+        let items = layout_items(&inputs, &cfg)?;
+        let bar = show_progress.then(pack_progress_bar);
+        let out = tex_packer_core::pack_layout_items_with(
+            items,
+            cfg.clone(),
+            &pack_control(bar.as_ref()),
+        )?;
+        if let Some(b) = bar {
+            b.finish_and_clear();
         }
-        let atlas = tex_packer_core::pack_layout_items(items, cfg.clone())?;
-        // Write metadata only
-        match cli.metadata.as_str() {
-            "json-array" | "json" => {
-                let json_path = cli.out_dir.join(format!("{}.json", cli.name));
-                let json_value = tex_packer_core::to_json_array(&atlas);
-                let json = serde_json::to_string_pretty(&json_value)?;
-                fs::write(&json_path, json)
-                    .with_context(|| format!("write {}", json_path.display()))?;
-                info!(
-                    ?json_path,
-                    pages = atlas.pages.len(),
-                    "atlas written (layout-only)"
-                );
-            }
-            "json-hash" => {
-                let json_path = cli.out_dir.join(format!("{}.json", cli.name));
-                let json_value = tex_packer_core::to_json_hash(&atlas);
-                let json = serde_json::to_string_pretty(&json_value)?;
-                fs::write(&json_path, json)
-                    .with_context(|| format!("write {}", json_path.display()))?;
-                info!(
-                    ?json_path,
-                    pages = atlas.pages.len(),
-                    "atlas written (layout-only)"
-                );
-            }
-            "plist" => {
-                let page_names: Vec<String> = if atlas.pages.len() == 1 {
-                    vec![format!("{}.png", cli.name)]
-                } else {
-                    atlas
-                        .pages
-                        .iter()
-                        .map(|p| format!("{}_{}.png", cli.name, p.id))
-                        .collect()
-                };
-                let plist = tex_packer_core::to_plist_hash_with_pages(&atlas, &page_names);
-                let plist_path = cli.out_dir.join(format!("{}.plist", cli.name));
-                fs::write(&plist_path, plist)
-                    .with_context(|| format!("write {}", plist_path.display()))?;
-                info!(
-                    ?plist_path,
-                    pages = atlas.pages.len(),
-                    "atlas written (layout-only)"
-                );
-            }
-            "template" => anyhow::bail!("template metadata is not supported in --layout-only mode"),
-            other => anyhow::bail!("unknown metadata format: {}", other),
+        report.skipped = out.skipped;
+        report.scaled = out.scaled;
+        let mut atlas = out.atlas;
+        if cli.degenerate == "skip" {
+            atlas = atlas.without_degenerate();
         }
+        validate_layout(cli, &cfg, &atlas)?;
+        report.timings_ms.pack = started.elapsed().as_millis() as u64 - report.timings_ms.load;
+        // Write metadata only
+        exporters::write_exports(cli, &cfg, &atlas, &tasks, &mut report.files)?;
         if let Some(stats_path) = &cli.export_stats {
-            let (used, total) = {
-                let mut u = 0;
-                let mut t = 0;
-                for p in &atlas.pages {
-                    t += (p.width as u64) * (p.height as u64);
-                    for f in &p.frames {
-                        u += (f.frame.w as u64) * (f.frame.h as u64);
-                    }
-                }
-                (u, t)
-            };
-            let occupancy = if total > 0 {
-                used as f64 / total as f64
-            } else {
-                0.0
-            };
-            let value = serde_json::json!({"pages": atlas.pages.len(),"used_area": used, "total_area": total, "occupancy": occupancy});
+            let mut stats = atlas.stats();
+            stats.timings = Some(tex_packer_core::PackTimings {
+                pack_ms: report.timings_ms.pack,
+                ..Default::default()
+            });
+            let value = tex_packer_core::to_stats_json(&stats);
             fs::write(stats_path, serde_json::to_string_pretty(&value)?)
                 .with_context(|| format!("write {}", stats_path.display()))?;
+            report.files.push(stats_path.clone());
+            if verbose {
+                print_page_stats_table(&atlas);
+            }
+        }
+        check_gates(cli, &atlas.stats())?;
+        return Ok(report.finish(started, atlas.stats(), atlas.page_stats()));
+    }
+    let (mut out, hdr_pages) = if cfg.page_format == tex_packer_core::config::PageFormat::Exr {
+        let hdr = tex_packer_core::pack_images_hdr(inputs, cfg.clone())?;
+        // Clamped 8-bit copies serve the debug renders; the float pages are written as EXR
+        let pages = hdr
+            .pages
+            .iter()
+            .map(|p| tex_packer_core::OutputPage {
+                page: p.page.clone(),
+                rgba: DynamicImage::ImageRgba32F(p.rgba.clone()).to_rgba8(),
+                scratch: None,
+            })
+            .collect();
+        let out = tex_packer_core::PackOutput {
+            atlas: hdr.atlas,
+            pages,
+            timings: tex_packer_core::PackTimings {
+                pack_ms: started.elapsed().as_millis() as u64 - report.timings_ms.load,
+                ..Default::default()
+            },
+            skipped: Vec::new(),
+            scaled: Vec::new(),
+        };
+        (out, Some(hdr.pages))
+    } else {
+        let bar = show_progress.then(pack_progress_bar);
+        let out = pack_images_with(inputs, cfg.clone(), &pack_control(bar.as_ref()))?;
+        if let Some(b) = bar {
+            b.finish_and_clear();
+        }
+        (out, None)
+    };
+    report.timings_ms.pack = started.elapsed().as_millis() as u64 - report.timings_ms.load;
+    report.skipped = std::mem::take(&mut out.skipped);
+    report.scaled = std::mem::take(&mut out.scaled);
+    if cli.degenerate == "skip" {
+        // Drop degenerate frames from metadata; their 1x1 slots remain in the page images.
+        out.atlas = out.atlas.without_degenerate();
+        for p in &mut out.pages {
+            p.page.frames.retain(|f| !f.degenerate);
         }
-        return Ok(());
     }
-    let out = pack_images(inputs, cfg.clone())?;
+    validate_layout(cli, &cfg, &out.atlas)?;
 
+    // (encoded, optimized) PNG bytes summed over pages when --png-optimize is set
+    let mut png_bytes: Option<(u64, u64)> = None;
     if !cli.dry_run {
-        // write png(s)
-        if out.pages.len() == 1 {
-            let png_path = cli.out_dir.join(format!("{}.png", cli.name));
-            out.pages[0]
-                .rgba
-                .save(&png_path)
-                .with_context(|| format!("write {}", png_path.display()))?;
-            info!(?png_path, "wrote page 0");
-        } else {
+        // write page image(s)
+        let encode_started = Instant::now();
+        for (i, p) in out.pages.iter().enumerate() {
+            let page_path = cli
+                .out_dir
+                .join(page_file_name(cli, &cfg, p.page.id, out.pages.len()));
+            if let Some(hdr) = &hdr_pages {
+                fs::write(&page_path, hdr[i].to_exr()?)
+                    .with_context(|| format!("write {}", page_path.display()))?;
+            } else {
+                if let Some((before, after)) = write_page_image(cli, &cfg, p, &page_path)? {
+                    let (b, a) = png_bytes.get_or_insert((0, 0));
+                    *b += before;
+                    *a += after;
+                }
+                report
+                    .files
+                    .extend(write_mip_files(cli, &cfg, p, out.pages.len())?);
+            }
+            report.files.push(page_path.clone());
+            info!(?page_path, id = p.page.id, "wrote page");
+        }
+        out.timings.encode_ms = encode_started.elapsed().as_millis() as u64;
+        if let Some((before, after)) = png_bytes {
+            info!(before, after, saved = before - after, "png pages optimized");
+        }
+        if cli.debug_output {
             for p in &out.pages {
-                let png_path = cli.out_dir.join(format!("{}_{}.png", cli.name, p.page.id));
-                p.rgba
-                    .save(&png_path)
-                    .with_context(|| format!("write {}", png_path.display()))?;
-                info!(?png_path, id = p.page.id, "wrote page");
+                let renders = tex_packer_core::debug_render::render_debug(p, &out.atlas.meta)?;
+                let stem = page_stem(cli, p.page.id, out.pages.len());
+                for (suffix, img) in [
+                    ("free", &renders.free_space),
+                    ("waste", &renders.waste_heatmap),
+                    ("padding", &renders.padding),
+                ] {
+                    let path = cli.out_dir.join(format!("{stem}_debug_{suffix}.png"));
+                    img.save(&path)
+                        .with_context(|| format!("write {}", path.display()))?;
+                    report.files.push(path.clone());
+                }
+                info!(id = p.page.id, "wrote debug renders");
             }
         }
     }
 
     // stats
-    let (used_area, total_area) = compute_stats(&out);
-    let occupancy = if total_area > 0 {
-        used_area as f64 / total_area as f64
-    } else {
-        0.0
-    };
+    let bound = out.stats();
     info!(
-        pages = out.pages.len(),
-        used_area,
-        total_area,
-        occupancy = format!("{:.2}%", occupancy * 100.0),
+        pages = bound.num_pages,
+        used_area = bound.used_frame_area,
+        total_area = bound.total_page_area,
+        occupancy = format!("{:.2}%", bound.occupancy * 100.0),
+        lower_bound_pages = bound.lower_bound_pages,
+        bound_efficiency = format!("{:.2}%", bound.bound_efficiency * 100.0),
+        rotated = bound.num_rotated,
+        trimmed_pixels_saved = bound.trimmed_pixels_saved,
+        padding_overhead = bound.padding_overhead,
         "stats"
     );
+    if let Some(t) = bound.timings {
+        info!(
+            prepare_ms = t.prepare_ms,
+            pack_ms = t.pack_ms,
+            composite_ms = t.composite_ms,
+            encode_ms = t.encode_ms,
+            "timings"
+        );
+    }
+    if let Some(choice) = &bound.winning_config {
+        info!(
+            family = ?choice.family,
+            heuristic = %choice.heuristic,
+            sort_order = ?choice.sort_order,
+            allow_rotation = choice.allow_rotation,
+            "auto winner"
+        );
+    }
+    if let Some(r) = &bound.auto_refinement {
+        info!(
+            from = %r.initial.heuristic,
+            variants = r.variants,
+            improvements = r.improvements,
+            budget_exhausted = r.budget_exhausted,
+            "auto refinement"
+        );
+    }
 
-    match cli.metadata.as_str() {
-        // Accept "json" as an alias of "json-array" to match layout-only behavior
-        "json-array" | "json" => {
-            if !cli.dry_run {
-                let json_path = cli.out_dir.join(format!("{}.json", cli.name));
-                let json_value = tex_packer_core::to_json_array(&out.atlas);
-                let json = serde_json::to_string_pretty(&json_value)?;
-                fs::write(&json_path, json)
-                    .with_context(|| format!("write {}", json_path.display()))?;
-                info!(?json_path, pages = out.pages.len(), "atlas written");
-            }
+    if !cli.dry_run {
+        exporters::write_exports(cli, &cfg, &out.atlas, &tasks, &mut report.files)?;
+    }
+
+    if let Some(stats_path) = &cli.export_stats {
+        let mut value = tex_packer_core::to_stats_json(&bound);
+        if let Some((before, after)) = png_bytes {
+            value["png_bytes_before"] = before.into();
+            value["png_bytes_after"] = after.into();
+            value["png_bytes_saved"] = (before - after).into();
         }
-        "json-hash" => {
-            if !cli.dry_run {
-                let json_path = cli.out_dir.join(format!("{}.json", cli.name));
-                let json_value = tex_packer_core::to_json_hash(&out.atlas);
-                let json = serde_json::to_string_pretty(&json_value)?;
-                fs::write(&json_path, json)
-                    .with_context(|| format!("write {}", json_path.display()))?;
-                info!(?json_path, pages = out.pages.len(), "atlas written");
-            }
+        if !cli.dry_run {
+            fs::write(stats_path, serde_json::to_string_pretty(&value)?)
+                .with_context(|| format!("write {}", stats_path.display()))?;
+            report.files.push(stats_path.clone());
+            info!(?stats_path, "stats exported");
+        } else if cli.report != "json" {
+            println!(
+                "pages={} used_area={} total_area={} occupancy={:.2}% lower_bound_pages={} bound_efficiency={:.2}% rotated={} trimmed_pixels_saved={} padding_overhead={}",
+                bound.num_pages,
+                bound.used_frame_area,
+                bound.total_page_area,
+                bound.occupancy * 100.0,
+                bound.lower_bound_pages,
+                bound.bound_efficiency * 100.0,
+                bound.num_rotated,
+                bound.trimmed_pixels_saved,
+                bound.padding_overhead
+            );
         }
-        "plist" => {
-            if !cli.dry_run {
-                let plist_path = cli.out_dir.join(format!("{}.plist", cli.name));
-                // Build page filenames for meta
-                let page_names: Vec<String> = if out.pages.len() == 1 {
-                    vec![format!("{}.png", cli.name)]
-                } else {
-                    out.pages
-                        .iter()
-                        .map(|p| format!("{}_{}.png", cli.name, p.page.id))
-                        .collect()
-                };
-                let plist = tex_packer_core::to_plist_hash_with_pages(&out.atlas, &page_names);
-                fs::write(&plist_path, plist)
-                    .with_context(|| format!("write {}", plist_path.display()))?;
-                info!(?plist_path, pages = out.pages.len(), "atlas written");
-            }
+        if verbose {
+            print_page_stats_table(&out.atlas);
         }
-        "template" => {
-            // Build context (pages + sprites) and render template
-            let page_names: Vec<String> = if out.pages.len() == 1 {
-                vec![format!("{}.png", cli.name)]
-            } else {
-                out.pages
-                    .iter()
-                    .map(|p| format!("{}_{}.png", cli.name, p.page.id))
-                    .collect()
-            };
-            let ctx = build_template_context(&out, &page_names);
-
-            let tpl_owned_from_file: Option<String> = if let Some(path) = &cli.template {
-                Some(std::fs::read_to_string(path)?)
-            } else {
-                None
-            };
-            let tpl_ref: &str = if let Some(engine) = &cli.engine {
-                match engine.to_ascii_lowercase().as_str() {
-                    "unity" => include_str!("templates/unity.hbs"),
-                    "godot" => include_str!("templates/godot.hbs"),
-                    "phaser3" => include_str!("templates/phaser3_multiatlas.hbs"),
-                    "phaser3_single" => include_str!("templates/phaser3_singleatlas.hbs"),
-                    "spine" => include_str!("templates/spine_atlas.hbs"),
-                    "cocos" => include_str!("templates/cocos.hbs"),
-                    "unreal" => include_str!("templates/unreal.hbs"),
-                    other => anyhow::bail!("unknown engine template: {}", other),
-                }
-            } else if let Some(ref s) = tpl_owned_from_file {
-                s.as_str()
-            } else {
-                // default to unity if not specified
-                include_str!("templates/unity.hbs")
-            };
+    }
+    check_gates(cli, &bound)?;
+    Ok(report.finish(started, bound, out.atlas.page_stats()))
+}
 
-            let mut reg = Handlebars::new();
-            reg.set_strict_mode(true);
-            reg.register_template_string("tpl", tpl_ref)?;
-            let rendered = reg.render("tpl", &ctx)?;
-
-            if !cli.dry_run {
-                let out_path = if let Some(engine) = &cli.engine {
-                    match engine.to_ascii_lowercase().as_str() {
-                        "spine" => cli.out_dir.join(format!("{}.atlas", cli.name)),
-                        "phaser3" => cli.out_dir.join(format!("{}.multiatlas.json", cli.name)),
-                        _ => cli.out_dir.join(format!("{}.template.json", cli.name)),
-                    }
-                } else {
-                    cli.out_dir.join(format!("{}.template.json", cli.name))
-                };
-                fs::write(&out_path, rendered)
-                    .with_context(|| format!("write {}", out_path.display()))?;
-                info!(?out_path, pages = out.pages.len(), "template written");
-            }
+/// Sidecar/imported pivots and folder configs fill in what the manifest leaves unset.
+fn apply_input_options(
+    cfg: &mut PackerConfig,
+    pivots: InputPivots,
+    sprites: InputSprites,
+    animations: Vec<Animation>,
+) -> anyhow::Result<()> {
+    for (key, pivot) in pivots {
+        cfg.pivots
+            .entry(cfg.key_transform.apply(&key)?)
+            .or_insert(pivot);
+    }
+    for (key, opts) in sprites {
+        let key = cfg.key_transform.apply(&key)?;
+        let merged = match cfg.sprites.remove(&key) {
+            Some(own) => own.inherit(&opts),
+            None => opts,
+        };
+        cfg.sprites.insert(key, merged);
+    }
+    for mut anim in animations {
+        for f in &mut anim.frames {
+            f.key = cfg.key_transform.apply(&f.key)?;
         }
-        other => anyhow::bail!("unknown metadata format: {}", other),
+        cfg.animations.push(anim);
     }
+    Ok(())
+}
 
-    if let Some(stats_path) = &cli.export_stats {
-        let (used_area, total_area) = compute_stats(&out);
-        let occupancy = if total_area > 0 {
-            used_area as f64 / total_area as f64
+/// Layout-only items: input sizes after trimming, with the trim metadata.
+fn layout_items(
+    inputs: &[InputImage],
+    cfg: &PackerConfig,
+) -> anyhow::Result<Vec<tex_packer_core::pipeline::LayoutItem<String>>> {
+    let mut items = Vec::with_capacity(inputs.len());
+    for inp in inputs {
+        let rgba = inp.image.to_rgba8();
+        let (w, h) = rgba.dimensions();
+        let (tw, th, source, trimmed) = if cfg.trim_for(&cfg.key_transform.apply(&inp.key)?) {
+            let (trim_opt, src_rect) = tex_packer_core::pipeline::compute_trim_rect_with(
+                &rgba,
+                cfg.trim_threshold,
+                &cfg.trim_options,
+            );
+            match trim_opt {
+                Some(r) => (r.w, r.h, src_rect, true),
+                None => (w, h, tex_packer_core::Rect::new(0, 0, w, h), false),
+            }
         } else {
-            0.0
+            (w, h, tex_packer_core::Rect::new(0, 0, w, h), false)
         };
-        let value = serde_json::json!({
-            "pages": out.pages.len(),
-            "used_area": used_area,
-            "total_area": total_area,
-            "occupancy": occupancy,
+        items.push(tex_packer_core::pipeline::LayoutItem {
+            key: inp.key.clone(),
+            w: tw,
+            h: th,
+            source: Some(source),
+            source_size: Some((w, h)),
+            trimmed,
         });
-        if !cli.dry_run {
-            fs::write(stats_path, serde_json::to_string_pretty(&value)?)
-                .with_context(|| format!("write {}", stats_path.display()))?;
-            info!(?stats_path, "stats exported");
-        } else {
-            println!(
-                "pages={} used_area={} total_area={} occupancy={:.2}%",
-                out.pages.len(),
-                used_area,
-                total_area,
-                occupancy * 100.0
+    }
+    Ok(items)
+}
+
+/// `--validate`: logs every problem [`tex_packer_core::Atlas::validate`] finds and fails on any.
+fn validate_layout(
+    cli: &PackArgs,
+    cfg: &PackerConfig,
+    atlas: &tex_packer_core::Atlas,
+) -> anyhow::Result<()> {
+    if !cli.validate {
+        return Ok(());
+    }
+    let issues = atlas.validate(cfg);
+    for issue in &issues {
+        error!("{issue}");
+    }
+    if !issues.is_empty() {
+        anyhow::bail!("--validate: {} layout problem(s)", issues.len());
+    }
+    info!("layout validated");
+    Ok(())
+}
+
+/// Checks the `--fail-on-*` thresholds, reporting every violated one.
+fn check_gates(cli: &PackArgs, stats: &tex_packer_core::PackStats) -> anyhow::Result<()> {
+    let mut violated = Vec::new();
+    if let Some(min) = cli.fail_on_occupancy_below
+        && stats.occupancy < min
+    {
+        violated.push(format!(
+            "occupancy {:.2}% is below --fail-on-occupancy-below {:.2}%",
+            stats.occupancy * 100.0,
+            min * 100.0
+        ));
+    }
+    if let Some(max) = cli.fail_on_pages_above
+        && stats.num_pages > max
+    {
+        violated.push(format!(
+            "{} page(s) exceed --fail-on-pages-above {max}",
+            stats.num_pages
+        ));
+    }
+    if !violated.is_empty() {
+        anyhow::bail!("gate failed: {}", violated.join("; "));
+    }
+    Ok(())
+}
+
+/// Inputs whose slot (trimmed size plus padding and extrusion, either orientation when rotation
+/// is allowed) is larger than a page minus its border, as `key (WxH)`.
+fn oversized_inputs(inputs: &[InputImage], cfg: &PackerConfig) -> anyhow::Result<Vec<String>> {
+    let avail_w = cfg.max_width.saturating_sub(2 * cfg.border_padding);
+    let avail_h = cfg.max_height.saturating_sub(2 * cfg.border_padding);
+    let mut oversized = Vec::new();
+    for inp in inputs {
+        let key = cfg.key_transform.apply(&inp.key)?;
+        if cfg.is_excluded(&key) {
+            continue;
+        }
+        let sprite = cfg.sprite(&key);
+        let spacing = cfg.sprite_padding();
+        let floor = sprite.and_then(|s| s.texture_padding).unwrap_or(0);
+        let extrusion = sprite
+            .and_then(|s| s.texture_extrusion)
+            .map_or(cfg.texture_extrusion, |e| e.max(cfg.texture_extrusion));
+        let rotate = sprite
+            .and_then(|s| s.rotate?.forced())
+            .unwrap_or(cfg.allow_rotation);
+        let grow_w = spacing.horizontal().max(floor) + 2 * extrusion;
+        let grow_h = spacing.vertical().max(floor) + 2 * extrusion;
+        let fits = |w: u32, h: u32| {
+            (w + grow_w <= avail_w && h + grow_h <= avail_h)
+                || (rotate && h + grow_w <= avail_w && w + grow_h <= avail_h)
+        };
+        let (mut w, mut h) = (inp.image.width(), inp.image.height());
+        // Trimming only matters for sprites that do not fit as they are
+        if !fits(w, h) && cfg.trim_for(&key) {
+            let (trimmed, _) = tex_packer_core::pipeline::compute_trim_rect_with(
+                &inp.image.to_rgba8(),
+                cfg.trim_threshold,
+                &cfg.trim_options,
             );
+            if let Some(r) = trimmed {
+                (w, h) = (r.w, r.h);
+            }
+        }
+        if !fits(w, h) {
+            oversized.push(format!("{key} ({w}x{h})"));
         }
     }
-    Ok(())
+    Ok(oversized)
+}
+
+/// The packer config for `cli`: `--config` (or the inline config) over the CLI flags, plus the
+/// manifest's per-sprite overrides.
+fn packer_config(cli: &PackArgs, manifest: &PackManifest) -> anyhow::Result<PackerConfig> {
+    let (family, mr_heuristic, sky_heuristic, g_choice, g_split, auto_mode) = parse_algo(cli)?;
+    let grid = cli.grid.as_deref().map(parse_grid).transpose()?;
+    let family = if grid.is_some() {
+        AlgorithmFamily::Grid
+    } else {
+        family
+    };
+    let (grid_cell_width, grid_cell_height) = grid.unwrap_or((0, 0));
+
+    // Load config file if provided; config file sets algorithm-related options en bloc
+    let yaml = match (&cli.inline_config, &cli.config) {
+        (Some(y), _) => Some((y.clone(), "inline config".to_string())),
+        (None, Some(path)) => {
+            let file = fs::read_to_string(path)?;
+            let y: YamlConfig = serde_yaml::from_str(&file)
+                .with_context(|| format!("config {}", path.display()))?;
+            Some((y, format!("config {}", path.display())))
+        }
+        (None, None) => None,
+    };
+    let cfg = if let Some((y, label)) = yaml {
+        let mut tmp = y
+            .into_packer_config(PackerConfig {
+                max_width: cli.max_width,
+                max_height: cli.max_height,
+                allow_rotation: cli.allow_rotation,
+                rotation_direction: parse_option("--rotation-direction", &cli.rotation_direction)?,
+                border_padding: cli.border_padding,
+                texture_padding: cli.texture_padding,
+                padding: cli.padding,
+                extrude_mode: parse_option("--extrude-mode", &cli.extrude_mode)?,
+                align: cli.align,
+                align_size: cli.align_size,
+                texture_extrusion: cli.texture_extrusion,
+                trim: cli.trim,
+                trim_threshold: cli.trim_threshold,
+                trim_options: TrimOptions {
+                    margin: cli.trim_margin.unwrap_or_default(),
+                    min_size: cli.trim_min_size,
+                    multiple_of: cli.trim_multiple,
+                },
+                texture_outlines: cli.outlines,
+                square: cli.square,
+                minimize_page_size: cli.minimize_page_size,
+                use_waste_map: cli.use_waste_map,
+                family,
+                mr_heuristic,
+                skyline_heuristic: sky_heuristic,
+                g_choice,
+                g_split,
+                g_merge: cli.g_merge,
+                shelf_heuristic: parse_option("--shelf", &cli.shelf)?,
+                grid_cell_width,
+                grid_cell_height,
+                auto_mode,
+                sort_order: parse_option("--sort-order", &cli.sort_order)?,
+                time_budget_ms: cli.time_budget,
+                parallel: cli.parallel,
+                deterministic: cli.deterministic,
+                low_memory: cli.low_memory,
+                key_transform: cli_key_transform(cli)?,
+                pivot_mode: parse_option("--pivot-mode", &cli.pivot_mode)?,
+                sprites: manifest.sprites.clone(),
+                animations: Vec::new(),
+                default_pivot: parse_pivot(&cli.pivot)?,
+                pivots: match &cli.pivots {
+                    Some(p) => load_pivot_manifest(p)?,
+                    None => Default::default(),
+                },
+                duplicate_keys: parse_option("--duplicate-keys", &cli.duplicate_keys)?,
+                mr_reference: false,
+                mr_global: false,
+                auto_mr_ref_time_ms_threshold: cli.auto_mr_ref_time_threshold,
+                auto_mr_ref_input_threshold: cli.auto_mr_ref_input_threshold,
+                transparent_policy: parse_option("--transparent-policy", &cli.transparent_policy)?,
+                on_unplaceable: parse_option("--on-unplaceable", &cli.on_unplaceable)?,
+                min_width: cli.min_width,
+                min_height: cli.min_height,
+                max_pages: cli.max_pages,
+                page_grouping: parse_option("--group-by", &cli.group_by)?,
+                group_max_pages: cli.group_max_pages,
+                page_balance: parse_option("--page-balance", &cli.page_balance)?,
+                frame_order: parse_option("--frame-order", &cli.frame_order)?,
+                alpha_bleed: cli.alpha_bleed,
+                mip_levels: cli.mip_levels,
+                background_color: cli
+                    .background
+                    .as_deref()
+                    .map(parse_background)
+                    .transpose()?,
+                page_format: parse_option("--page-format", &cli.page_format)?,
+                bcn_format: parse_option("--bcn", &cli.bcn)?,
+                basis_codec: parse_option("--basis-codec", &cli.basis_codec)?,
+                page_encoding: PageEncoding {
+                    quality: cli.page_quality,
+                    lossless: cli.webp_lossless,
+                    speed: cli.avif_speed,
+                    png_optimize: cli.png_optimize,
+                    basis_quality: cli.basis_quality,
+                },
+                sdf: cli_sdf(cli)?,
+                page_postprocess: None,
+                custom_packer: None,
+                auto_candidates: parse_auto_candidates("--auto-candidates", &cli.auto_candidates)?,
+                auto_objective: parse_option("--auto-objective", &cli.auto_objective)?,
+                auto_score: None,
+                auto_refine: cli.auto_refine,
+                page_size: cli_page_size(cli)?,
+            })
+            .context(label)?;
+        if cli.mr_reference {
+            tmp.mr_reference = true;
+        }
+        if cli.mr_global {
+            tmp.mr_global = true;
+        }
+        if grid.is_some() {
+            tmp.family = AlgorithmFamily::Grid;
+            tmp.grid_cell_width = grid_cell_width;
+            tmp.grid_cell_height = grid_cell_height;
+        }
+        tmp
+    } else {
+        PackerConfig {
+            max_width: cli.max_width,
+            max_height: cli.max_height,
+            allow_rotation: cli.allow_rotation,
+            rotation_direction: parse_option("--rotation-direction", &cli.rotation_direction)?,
+            border_padding: cli.border_padding,
+            texture_padding: cli.texture_padding,
+            padding: cli.padding,
+            extrude_mode: parse_option("--extrude-mode", &cli.extrude_mode)?,
+            align: cli.align,
+            align_size: cli.align_size,
+            texture_extrusion: cli.texture_extrusion,
+            trim: cli.trim,
+            trim_threshold: cli.trim_threshold,
+            trim_options: TrimOptions {
+                margin: cli.trim_margin.unwrap_or_default(),
+                min_size: cli.trim_min_size,
+                multiple_of: cli.trim_multiple,
+            },
+            texture_outlines: cli.outlines,
+            square: cli.square,
+            minimize_page_size: cli.minimize_page_size,
+            use_waste_map: cli.use_waste_map,
+            family,
+            mr_heuristic,
+            skyline_heuristic: sky_heuristic,
+            g_choice,
+            g_split,
+            g_merge: cli.g_merge,
+            shelf_heuristic: parse_option("--shelf", &cli.shelf)?,
+            grid_cell_width,
+            grid_cell_height,
+            auto_mode,
+            sort_order: parse_option("--sort-order", &cli.sort_order)?,
+            time_budget_ms: cli.time_budget,
+            parallel: cli.parallel,
+            deterministic: cli.deterministic,
+            low_memory: cli.low_memory,
+            key_transform: cli_key_transform(cli)?,
+            pivot_mode: parse_option("--pivot-mode", &cli.pivot_mode)?,
+            sprites: manifest.sprites.clone(),
+            animations: Vec::new(),
+            default_pivot: parse_pivot(&cli.pivot)?,
+            pivots: match &cli.pivots {
+                Some(p) => load_pivot_manifest(p)?,
+                None => Default::default(),
+            },
+            duplicate_keys: parse_option("--duplicate-keys", &cli.duplicate_keys)?,
+            mr_reference: cli.mr_reference,
+            mr_global: cli.mr_global,
+            auto_mr_ref_time_ms_threshold: cli.auto_mr_ref_time_threshold,
+            auto_mr_ref_input_threshold: cli.auto_mr_ref_input_threshold,
+            transparent_policy: parse_option("--transparent-policy", &cli.transparent_policy)?,
+            on_unplaceable: parse_option("--on-unplaceable", &cli.on_unplaceable)?,
+            min_width: cli.min_width,
+            min_height: cli.min_height,
+            max_pages: cli.max_pages,
+            page_grouping: parse_option("--group-by", &cli.group_by)?,
+            group_max_pages: cli.group_max_pages,
+            page_balance: parse_option("--page-balance", &cli.page_balance)?,
+            frame_order: parse_option("--frame-order", &cli.frame_order)?,
+            alpha_bleed: cli.alpha_bleed,
+            mip_levels: cli.mip_levels,
+            background_color: cli
+                .background
+                .as_deref()
+                .map(parse_background)
+                .transpose()?,
+            page_format: parse_option("--page-format", &cli.page_format)?,
+            bcn_format: parse_option("--bcn", &cli.bcn)?,
+            basis_codec: parse_option("--basis-codec", &cli.basis_codec)?,
+            page_encoding: PageEncoding {
+                quality: cli.page_quality,
+                lossless: cli.webp_lossless,
+                speed: cli.avif_speed,
+                png_optimize: cli.png_optimize,
+                basis_quality: cli.basis_quality,
+            },
+            sdf: cli_sdf(cli)?,
+            page_postprocess: None,
+            custom_packer: None,
+            auto_candidates: parse_auto_candidates("--auto-candidates", &cli.auto_candidates)?,
+            auto_objective: parse_option("--auto-objective", &cli.auto_objective)?,
+            auto_score: None,
+            auto_refine: cli.auto_refine,
+            page_size: cli_page_size(cli)?,
+        }
+    };
+    Ok(cfg)
+}
+
+/// Runs a pack-like command, printing the `--report json` document if asked.
+fn pack_command(args: &PackArgs, show_progress: bool, verbose: bool) -> anyhow::Result<()> {
+    let result = run_pack(args, show_progress, verbose);
+    if args.report == "json" {
+        report::print_json(&result)?;
+    }
+    result.map(drop)
 }
 
 fn run_bench(b: &BenchArgs) -> anyhow::Result<()> {
     use std::time::Instant;
     // Minimal bench: build a tiny config from args; pack once and print time + occupancy
-    let images = gather_paths(&b.input, &[], &[])?;
-    let inputs = load_images_with_progress(&images, false)?;
-    let family = match b.algorithm.to_ascii_lowercase().as_str() {
-        "skyline" => AlgorithmFamily::Skyline,
-        "maxrects" => AlgorithmFamily::MaxRects,
-        "guillotine" => AlgorithmFamily::Guillotine,
-        _ => AlgorithmFamily::Auto,
-    };
-    let auto_mode = match b.auto_mode.to_ascii_lowercase().as_str() {
-        "fast" => AutoMode::Fast,
-        _ => AutoMode::Quality,
-    };
+    let images: Vec<PathBuf> = gather_paths(&b.input, &[], &[], &WalkOptions::default())?
+        .into_iter()
+        .map(|(p, _)| p)
+        .collect();
+    let inputs = load_images_with_progress(&images, DecodeOptions::default(), false)?
+        .0
+        .into_iter()
+        .map(|(img, _)| img)
+        .collect();
+    let family = parse_option("--algorithm", &b.algorithm)?;
+    let auto_mode = parse_option("--auto-mode", &b.auto_mode)?;
     let cfg = PackerConfig {
         family,
         auto_mode,
@@ -615,6 +1279,209 @@ fn run_bench(b: &BenchArgs) -> anyhow::Result<()> {
     Ok(())
 }
 
+fn run_diff(d: &DiffArgs) -> anyhow::Result<()> {
+    let old = load_atlas(&d.old)?.atlas;
+    let new = load_atlas(&d.new)?.atlas;
+    let diff = tex_packer_core::diff_atlases(&old, &new);
+    if d.format == "json" {
+        println!("{}", serde_json::to_string_pretty(&diff)?);
+    } else {
+        println!("{}", diff.summary());
+        for k in &diff.added {
+            println!("+ {}", k);
+        }
+        for k in &diff.removed {
+            println!("- {}", k);
+        }
+        for c in &diff.moved {
+            println!(
+                "~ {} moved: page {} ({},{}) -> page {} ({},{}){}",
+                c.key,
+                c.old.page,
+                c.old.frame.x,
+                c.old.frame.y,
+                c.new.page,
+                c.new.frame.x,
+                c.new.frame.y,
+                if c.old.rotated != c.new.rotated {
+                    " [rotation changed]"
+                } else {
+                    ""
+                }
+            );
+        }
+        for c in &diff.resized {
+            println!(
+                "~ {} resized: {}x{} -> {}x{} (source {}x{} -> {}x{})",
+                c.key,
+                c.old.source.w,
+                c.old.source.h,
+                c.new.source.w,
+                c.new.source.h,
+                c.old.source_size.0,
+                c.old.source_size.1,
+                c.new.source_size.0,
+                c.new.source_size.1
+            );
+        }
+        for k in &diff.content_changed {
+            println!("* {} content changed", k);
+        }
+    }
+    if d.fail_on_change && !diff.is_empty() {
+        std::process::exit(1);
+    }
+    Ok(())
+}
+
+fn run_extract(e: &ExtractArgs) -> anyhow::Result<()> {
+    let imported = load_atlas(&e.atlas)?;
+    let page_paths = if e.pages.is_empty() {
+        atlas_page_paths(&e.atlas, &imported)
+    } else {
+        e.pages.clone()
+    };
+    let pages = page_paths
+        .iter()
+        .map(|p| Ok(load_image(p)?.to_rgba8()))
+        .collect::<anyhow::Result<Vec<_>>>()?;
+    let sprites = tex_packer_core::extract_frames(&imported.atlas, &pages)?;
+    let mut written = 0usize;
+    for (key, img) in sprites {
+        if img.width() == 0 || img.height() == 0 {
+            tracing::warn!(key = %key, "skipping zero-sized sprite");
+            continue;
+        }
+        // Keys are relative paths; drop anything that would escape the output directory.
+        let rel: PathBuf = Path::new(&key)
+            .components()
+            .filter(|c| matches!(c, std::path::Component::Normal(_)))
+            .collect();
+        let path = e.out_dir.join(rel).with_extension("png");
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)
+                .with_context(|| format!("create dir {}", parent.display()))?;
+        }
+        img.save(&path)
+            .with_context(|| format!("write {}", path.display()))?;
+        written += 1;
+    }
+    info!(sprites = written, out_dir = %e.out_dir.display(), "extracted");
+    Ok(())
+}
+
+fn run_verify(v: &VerifyArgs) -> anyhow::Result<()> {
+    let imported = load_atlas(&v.atlas)?;
+    let pages = if v.no_pixels {
+        None
+    } else if !v.pages.is_empty() {
+        Some(
+            v.pages
+                .iter()
+                .map(|p| Ok(load_image(p)?.to_rgba8()))
+                .collect::<anyhow::Result<Vec<_>>>()?,
+        )
+    } else {
+        let paths = atlas_page_paths(&v.atlas, &imported);
+        match paths.iter().find(|p| !p.exists()) {
+            Some(missing) => {
+                tracing::warn!(path = %missing.display(), "page image not found; skipping pixel checks");
+                None
+            }
+            None => Some(
+                paths
+                    .iter()
+                    .map(|p| Ok(load_image(p)?.to_rgba8()))
+                    .collect::<anyhow::Result<Vec<_>>>()?,
+            ),
+        }
+    };
+    if let Some(p) = &pages
+        && p.len() != imported.atlas.pages.len()
+    {
+        anyhow::bail!(
+            "atlas has {} page(s) but {} page image(s) were given",
+            imported.atlas.pages.len(),
+            p.len()
+        );
+    }
+    let opts = tex_packer_core::VerifyOptions {
+        padding: v.padding,
+        border: v.border,
+    };
+    let issues = tex_packer_core::verify_atlas(&imported.atlas, pages.as_deref(), opts);
+    if v.format == "json" {
+        println!("{}", serde_json::to_string_pretty(&issues)?);
+    } else {
+        let frames: usize = imported.atlas.pages.iter().map(|p| p.frames.len()).sum();
+        println!(
+            "{} page(s), {} frame(s), {} problem(s){}",
+            imported.atlas.pages.len(),
+            frames,
+            issues.len(),
+            if pages.is_none() {
+                " (metadata only)"
+            } else {
+                ""
+            }
+        );
+        for issue in &issues {
+            println!("! {}", issue);
+        }
+    }
+    if !issues.is_empty() {
+        std::process::exit(1);
+    }
+    Ok(())
+}
+
+fn run_schema(s: &SchemaArgs) -> anyhow::Result<()> {
+    use tex_packer_core::schema;
+    let value = match (s.kind.as_str(), s.format.as_str()) {
+        ("config", _) => schemars::schema_for!(YamlConfig).into(),
+        ("packer-config", _) => schema::config_schema(),
+        (_, "json-hash") => schema::atlas_hash_schema(),
+        _ => schema::atlas_array_schema(),
+    };
+    let text = serde_json::to_string_pretty(&value)?;
+    match &s.out {
+        Some(path) => {
+            fs::write(path, text).with_context(|| format!("write {}", path.display()))?;
+            info!(path = %path.display(), "schema written");
+        }
+        None => println!("{text}"),
+    }
+    Ok(())
+}
+
+/// Page image paths of an imported atlas: texture names recorded in the metadata, else
+/// `<stem>.png` / `<stem>_<id>.png`, resolved next to the metadata file.
+fn atlas_page_paths(meta_path: &Path, imported: &tex_packer_core::ImportedAtlas) -> Vec<PathBuf> {
+    let dir = meta_path.parent().unwrap_or(Path::new(""));
+    let stem = meta_path
+        .file_stem()
+        .and_then(|s| s.to_str())
+        .unwrap_or("atlas");
+    let num_pages = imported.atlas.pages.len();
+    imported
+        .atlas
+        .pages
+        .iter()
+        .zip(&imported.page_images)
+        .map(|(p, image)| match image {
+            Some(name) => dir.join(name),
+            None if num_pages == 1 => dir.join(format!("{}.png", stem)),
+            None => dir.join(format!("{}_{}.png", stem, p.id)),
+        })
+        .collect()
+}
+
+/// Reads atlas metadata in any importable format (JSON, plist, libGDX; detected from content).
+fn load_atlas(path: &Path) -> anyhow::Result<tex_packer_core::ImportedAtlas> {
+    let text = fs::read_to_string(path).with_context(|| format!("read {}", path.display()))?;
+    tex_packer_core::import_atlas(&text).with_context(|| format!("import {}", path.display()))
+}
+
 fn bench_fmt_dur(d: Duration) -> String {
     let ms = d.as_secs_f64() * 1000.0;
     if ms >= 1.0 {
@@ -634,50 +1501,14 @@ fn parse_algo(
     GuillotineSplit,
     AutoMode,
 )> {
-    let family = match cli.algorithm.to_ascii_lowercase().as_str() {
-        "skyline" => AlgorithmFamily::Skyline,
-        "maxrects" => AlgorithmFamily::MaxRects,
-        "guillotine" => AlgorithmFamily::Guillotine,
-        "auto" => AlgorithmFamily::Auto,
-        other => anyhow::bail!("unknown algorithm: {}", other),
-    };
-    let h = match cli.heuristic.to_ascii_lowercase().as_str() {
-        "baf" => MaxRectsHeuristic::BestAreaFit,
-        "bssf" => MaxRectsHeuristic::BestShortSideFit,
-        "blsf" => MaxRectsHeuristic::BestLongSideFit,
-        "bl" => MaxRectsHeuristic::BottomLeft,
-        "cp" => MaxRectsHeuristic::ContactPoint,
-        other => anyhow::bail!("unknown heuristic: {}", other),
-    };
-    let sky = match cli.skyline.to_ascii_lowercase().as_str() {
-        "bl" => SkylineHeuristic::BottomLeft,
-        "minwaste" => SkylineHeuristic::MinWaste,
-        other => anyhow::bail!("unknown skyline heuristic: {}", other),
-    };
-    let g_choice = match cli.g_choice.to_ascii_lowercase().as_str() {
-        "baf" => GuillotineChoice::BestAreaFit,
-        "bssf" => GuillotineChoice::BestShortSideFit,
-        "blsf" => GuillotineChoice::BestLongSideFit,
-        "waf" => GuillotineChoice::WorstAreaFit,
-        "wssf" => GuillotineChoice::WorstShortSideFit,
-        "wlsf" => GuillotineChoice::WorstLongSideFit,
-        other => anyhow::bail!("unknown guillotine choice: {}", other),
-    };
-    let g_split = match cli.g_split.to_ascii_lowercase().as_str() {
-        "slas" => GuillotineSplit::SplitShorterLeftoverAxis,
-        "llas" => GuillotineSplit::SplitLongerLeftoverAxis,
-        "minas" => GuillotineSplit::SplitMinimizeArea,
-        "maxas" => GuillotineSplit::SplitMaximizeArea,
-        "sas" => GuillotineSplit::SplitShorterAxis,
-        "las" => GuillotineSplit::SplitLongerAxis,
-        other => anyhow::bail!("unknown guillotine split: {}", other),
-    };
-    let auto_mode = match cli.auto_mode.to_ascii_lowercase().as_str() {
-        "fast" => AutoMode::Fast,
-        "quality" => AutoMode::Quality,
-        other => anyhow::bail!("unknown auto mode: {}", other),
-    };
-    Ok((family, h, sky, g_choice, g_split, auto_mode))
+    Ok((
+        parse_option("--algorithm", &cli.algorithm)?,
+        parse_option("--heuristic", &cli.heuristic)?,
+        parse_option("--skyline", &cli.skyline)?,
+        parse_option("--g-choice", &cli.g_choice)?,
+        parse_option("--g-split", &cli.g_split)?,
+        parse_option("--auto-mode", &cli.auto_mode)?,
+    ))
 }
 
 #[allow(dead_code)]
@@ -690,11 +1521,39 @@ fn fmt_dur(d: Duration) -> String {
     }
 }
 
+/// Per-folder overrides: a `.texpacker.yaml` holding [`tex_packer_core::SpriteOptions`] fields
+/// applies to every image below its folder; nested files override their parents field by field.
+const FOLDER_CONFIG: &str = ".texpacker.yaml";
+
+/// An image path and the merged folder options that apply to it.
+type GatheredPath = (PathBuf, Option<tex_packer_core::SpriteOptions>);
+
+/// How input folders are walked (`--max-depth`, `--follow-symlinks`, `--include-hidden`).
+#[derive(Debug, Clone, Copy, Default)]
+struct WalkOptions {
+    max_depth: Option<usize>,
+    follow_symlinks: bool,
+    include_hidden: bool,
+}
+
+impl From<&PackArgs> for WalkOptions {
+    fn from(cli: &PackArgs) -> Self {
+        Self {
+            max_depth: cli.max_depth,
+            follow_symlinks: cli.follow_symlinks,
+            include_hidden: cli.include_hidden,
+        }
+    }
+}
+
+/// Image files under `path` (or `path` itself), sorted by their `/`-separated path so the
+/// order is the same on every platform and file system.
 fn gather_paths(
     path: &Path,
     include: &[String],
     exclude: &[String],
-) -> anyhow::Result<Vec<PathBuf>> {
+    walk: &WalkOptions,
+) -> anyhow::Result<Vec<GatheredPath>> {
     // Build glob matchers
     let mut inc_set = None;
     if !include.is_empty() {
@@ -712,22 +1571,61 @@ fn gather_paths(
         }
         exc_set = Some(b.build()?);
     }
-    let mut list: Vec<PathBuf> = Vec::new();
+    let mut list: Vec<GatheredPath> = Vec::new();
     if path.is_file() {
         if !should_skip(path, inc_set.as_ref(), exc_set.as_ref()) && is_image(path) {
-            list.push(path.to_path_buf());
+            list.push((path.to_path_buf(), None));
         }
     } else {
-        for entry in WalkDir::new(path).into_iter().filter_map(|e| e.ok()) {
+        // WalkDir yields every folder before its contents, so parents are merged first.
+        let mut folders: std::collections::HashMap<PathBuf, tex_packer_core::SpriteOptions> =
+            std::collections::HashMap::new();
+        let mut walker = WalkDir::new(path)
+            .follow_links(walk.follow_symlinks)
+            .sort_by_file_name();
+        if let Some(depth) = walk.max_depth {
+            walker = walker.max_depth(depth);
+        }
+        let walker = walker.into_iter().filter_entry(|e| {
+            walk.include_hidden
+                || e.depth() == 0
+                || !e.file_name().to_string_lossy().starts_with('.')
+        });
+        for entry in walker.filter_map(|e| e.ok()) {
             let p = entry.path();
-            if p.is_file() && !should_skip(p, inc_set.as_ref(), exc_set.as_ref()) && is_image(p) {
-                list.push(p.to_path_buf());
+            let inherited = p.parent().and_then(|d| folders.get(d));
+            if entry.file_type().is_dir() {
+                let own = read_folder_config(p)?;
+                let merged = match (own, inherited) {
+                    (Some(own), Some(parent)) => Some(own.inherit(parent)),
+                    (own, parent) => own.or_else(|| parent.cloned()),
+                };
+                if let Some(opts) = merged {
+                    folders.insert(p.to_path_buf(), opts);
+                }
+            } else if p.is_file()
+                && !should_skip(p, inc_set.as_ref(), exc_set.as_ref())
+                && is_image(p)
+            {
+                list.push((p.to_path_buf(), inherited.cloned()));
             }
         }
     }
+    list.sort_by_cached_key(|(p, _)| p.to_string_lossy().replace('\\', "/"));
     Ok(list)
 }
 
+fn read_folder_config(dir: &Path) -> anyhow::Result<Option<tex_packer_core::SpriteOptions>> {
+    let path = dir.join(FOLDER_CONFIG);
+    if !path.is_file() {
+        return Ok(None);
+    }
+    let text = fs::read_to_string(&path)?;
+    serde_yaml::from_str(&text)
+        .with_context(|| format!("folder config {}", path.display()))
+        .map(Some)
+}
+
 fn should_skip(
     p: &Path,
     include: Option<&globset::GlobSet>,
@@ -752,11 +1650,372 @@ fn is_image(p: &Path) -> bool {
         p.extension()
             .and_then(|e| e.to_str())
             .map(|s| s.to_ascii_lowercase()),
-        Some(ext) if matches!(ext.as_str(), "png" | "jpg" | "jpeg" | "bmp" | "tga" | "gif")
+        Some(ext) if matches!(ext.as_str(), "png" | "jpg" | "jpeg" | "bmp" | "tga" | "gif" | "exr" | "hdr" | "ase" | "aseprite" | "psd")
+    )
+}
+
+/// Loads every input (image, folder or atlas metadata), applying the per-input key prefixes.
+/// Fails if two inputs produce the same key.
+/// Pivots carried by the inputs themselves, keyed by input key.
+type InputPivots = Vec<(String, tex_packer_core::Pivot)>;
+/// Folder options (see [`FOLDER_CONFIG`]) by input key.
+type InputSprites = Vec<(String, tex_packer_core::SpriteOptions)>;
+
+/// Loads every input and returns the images plus the pivots they carry (from `.pivot` sidecars
+/// or imported atlas metadata) and the animations of expanded GIF/APNG files.
+fn load_inputs(
+    cli: &PackArgs,
+    show_progress: bool,
+) -> anyhow::Result<(Vec<InputImage>, InputPivots, InputSprites, Vec<Animation>)> {
+    if cli.prefixes.len() > cli.inputs.len() {
+        anyhow::bail!(
+            "{} --prefix values given for {} input(s)",
+            cli.prefixes.len(),
+            cli.inputs.len()
+        );
+    }
+    let mut groups = Vec::with_capacity(cli.inputs.len());
+    let mut pivots = Vec::new();
+    let mut sprites = Vec::new();
+    let mut animations = Vec::new();
+    let decode = DecodeOptions::from_args(cli)?;
+    for (i, input) in cli.inputs.iter().enumerate() {
+        let mut folder_options = std::collections::HashMap::new();
+        let mut group_animations: Vec<Animation> = Vec::new();
+        let (images, group_pivots): (Vec<InputImage>, Vec<Option<_>>) =
+            if input.is_file() && is_atlas_metadata(input) {
+                let imported = load_atlas(input)?;
+                let pages = atlas_page_paths(input, &imported)
+                    .iter()
+                    .map(|p| {
+                        Ok(load_image(p)
+                            .with_context(|| format!("page image {}", p.display()))?
+                            .to_rgba8())
+                    })
+                    .collect::<anyhow::Result<Vec<_>>>()?;
+                let frame_pivots = imported
+                    .atlas
+                    .pages
+                    .iter()
+                    .flat_map(|p| p.frames.iter().map(|f| Some(f.pivot)))
+                    .collect();
+                let images = tex_packer_core::extract_frames(&imported.atlas, &pages)?
+                    .into_iter()
+                    .map(|(key, rgba)| InputImage {
+                        key,
+                        image: DynamicImage::ImageRgba8(rgba),
+                    })
+                    .collect();
+                (images, frame_pivots)
+            } else {
+                let mut paths = Vec::new();
+                for (path, opts) in
+                    gather_paths(input, &cli.include, &cli.exclude, &WalkOptions::from(cli))?
+                {
+                    if let Some(opts) = opts {
+                        folder_options.insert(path.to_string_lossy().replace('\\', "/"), opts);
+                    }
+                    paths.push(path);
+                }
+                let (loaded, anims) = load_images_with_progress(&paths, decode, show_progress)?;
+                group_animations = anims;
+                let mut images = Vec::new();
+                for (img, opts) in loaded {
+                    if let Some(opts) = opts {
+                        // Slice options first; folder options of the file fill in the rest
+                        let merged = match folder_options.get(&img.key) {
+                            Some(parent) => opts.inherit(parent),
+                            None => opts,
+                        };
+                        folder_options.insert(img.key.clone(), merged);
+                    }
+                    images.push(img);
+                }
+                let sidecars = images
+                    .iter()
+                    .map(|img| read_pivot_sidecar(Path::new(&img.key)))
+                    .collect::<anyhow::Result<Vec<_>>>()?;
+                if cli.key_format == "relative" {
+                    let root = input.to_string_lossy().replace('\\', "/");
+                    let root = if input.is_file() {
+                        root.rsplit_once('/').map_or("", |(dir, _)| dir).to_string()
+                    } else {
+                        root
+                    };
+                    let root = format!("{}/", root.trim_end_matches('/'));
+                    for f in group_animations.iter_mut().flat_map(|a| &mut a.frames) {
+                        if let Some(rel) = f.key.strip_prefix(&root) {
+                            f.key = rel.to_string();
+                        }
+                    }
+                    for img in &mut images {
+                        if let Some(rel) = img.key.strip_prefix(&root) {
+                            if let Some(opts) = folder_options.remove(&img.key) {
+                                folder_options.insert(rel.to_string(), opts);
+                            }
+                            img.key = rel.to_string();
+                        }
+                    }
+                }
+                (images, sidecars)
+            };
+        let prefix = match cli.prefixes.get(i) {
+            Some(p) => p.clone(),
+            None if cli.auto_prefix => format!(
+                "{}/",
+                input
+                    .file_stem()
+                    .and_then(|s| s.to_str())
+                    .unwrap_or("input")
+            ),
+            None => String::new(),
+        };
+        for (img, pivot) in images.iter().zip(group_pivots) {
+            if let Some(p) = pivot {
+                pivots.push((format!("{prefix}{}", img.key), p));
+            }
+            if let Some(opts) = folder_options.remove(&img.key) {
+                sprites.push((format!("{prefix}{}", img.key), opts));
+            }
+        }
+        for mut anim in group_animations {
+            anim.name = format!("{prefix}{}", anim.name);
+            for f in &mut anim.frames {
+                f.key = format!("{prefix}{}", f.key);
+            }
+            animations.push(anim);
+        }
+        groups.push((prefix, images));
+    }
+    if let Some(list) = &cli.input_list {
+        let entries = read_input_list(list)?;
+        let mut images = Vec::with_capacity(entries.len());
+        for (path, key) in entries {
+            if let Some(p) = read_pivot_sidecar(&path)? {
+                pivots.push((key.clone(), p));
+            }
+            let (loaded, anim) = load_sprites(&path, key, decode)
+                .with_context(|| format!("input {}", path.display()))?;
+            animations.extend(anim);
+            for (img, opts) in loaded {
+                if let Some(opts) = opts {
+                    sprites.push((img.key.clone(), opts));
+                }
+                images.push(img);
+            }
+        }
+        groups.push((String::new(), images));
+    }
+    Ok((
+        tex_packer_core::namespaced_inputs(groups),
+        pivots,
+        sprites,
+        animations,
+    ))
+}
+
+/// Reads an `--input-list`: one `path[:key]` per line, `#` comments, `-` for stdin. Paths are
+/// resolved against the list's folder; keys default to the path as written.
+fn read_input_list(list: &Path) -> anyhow::Result<Vec<(PathBuf, String)>> {
+    let (text, base) = if list == Path::new("-") {
+        let mut text = String::new();
+        std::io::Read::read_to_string(&mut std::io::stdin(), &mut text)
+            .context("read input list from stdin")?;
+        (text, PathBuf::new())
+    } else {
+        let text = fs::read_to_string(list)
+            .with_context(|| format!("read input list {}", list.display()))?;
+        (text, list.parent().unwrap_or(Path::new("")).to_path_buf())
+    };
+    let mut entries = Vec::new();
+    for line in text.lines() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        // `C:\sprites\a.png` is a path, not `C` with key `\sprites\a.png`
+        let (path, key) = match line.rsplit_once(':') {
+            Some((path, key)) if path.len() > 1 && !key.starts_with(['/', '\\']) => {
+                (path.trim(), key.trim().to_string())
+            }
+            _ => (line, line.replace('\\', "/")),
+        };
+        if key.is_empty() {
+            anyhow::bail!("input list {}: empty key in '{line}'", list.display());
+        }
+        entries.push((base.join(path), key));
+    }
+    Ok(entries)
+}
+
+/// `--manifest` contents: extra inputs and per-sprite overrides keyed by frame key.
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct PackManifest {
+    /// Inputs, relative to the manifest's folder.
+    #[serde(default)]
+    inputs: Vec<PathBuf>,
+    #[serde(default)]
+    sprites: std::collections::BTreeMap<String, tex_packer_core::SpriteOptions>,
+}
+
+fn load_manifest(path: &Path) -> anyhow::Result<PackManifest> {
+    let text =
+        fs::read_to_string(path).with_context(|| format!("read manifest {}", path.display()))?;
+    let ext = path
+        .extension()
+        .and_then(|s| s.to_str())
+        .map(|s| s.to_ascii_lowercase());
+    let mut manifest: PackManifest = match ext.as_deref() {
+        Some("toml") => toml::from_str(&text)?,
+        Some("json") => serde_json::from_str(&text)?,
+        _ => serde_yaml::from_str(&text)?,
+    };
+    let base = path.parent().unwrap_or(Path::new(""));
+    for input in &mut manifest.inputs {
+        *input = base.join(&*input);
+    }
+    Ok(manifest)
+}
+
+/// Pivot from `<image stem>.pivot` next to `image`, if present.
+fn read_pivot_sidecar(image: &Path) -> anyhow::Result<Option<tex_packer_core::Pivot>> {
+    let sidecar = image.with_extension("pivot");
+    if !sidecar.is_file() {
+        return Ok(None);
+    }
+    let text = fs::read_to_string(&sidecar)?;
+    parse_pivot(text.trim())
+        .with_context(|| format!("pivot sidecar {}", sidecar.display()))
+        .map(Some)
+}
+
+/// Parses `family[:heuristic][@sort_order]` Auto candidates.
+fn parse_auto_candidates(field: &str, list: &[String]) -> anyhow::Result<Vec<AutoCandidate>> {
+    list.iter()
+        .map(|s| s.trim())
+        .filter(|s| !s.is_empty())
+        .map(|s| {
+            s.parse().map_err(|_| {
+                anyhow::anyhow!(
+                    "invalid {field} entry '{s}': expected family[:heuristic][@sort_order], e.g. maxrects:baf"
+                )
+            })
+        })
+        .collect()
+}
+
+/// Parses a `--grid` cell size: `WxH`, `N` (square) or `auto` (0x0, derived from the sprites).
+fn parse_grid(s: &str) -> anyhow::Result<(u32, u32)> {
+    if s.eq_ignore_ascii_case("auto") {
+        return Ok((0, 0));
+    }
+    let parsed = match s.split_once(['x', 'X']) {
+        Some((w, h)) => w.trim().parse().ok().zip(h.trim().parse().ok()),
+        None => s.trim().parse().ok().map(|n| (n, n)),
+    };
+    parsed
+        .filter(|&(w, h)| w > 0 && h > 0)
+        .ok_or_else(|| anyhow::anyhow!("invalid grid '{}': expected WxH, N or auto", s))
+}
+
+fn parse_ratio(s: &str) -> anyhow::Result<f64> {
+    let v: f64 = s.parse()?;
+    if !(0.0..=1.0).contains(&v) {
+        anyhow::bail!("expected a ratio between 0.0 and 1.0, got {v}");
+    }
+    Ok(v)
+}
+
+fn parse_padding(s: &str) -> anyhow::Result<Padding> {
+    s.parse().map_err(|_| {
+        anyhow::anyhow!(
+            "invalid padding '{}': expected N, X,Y or left,top,right,bottom",
+            s
+        )
+    })
+}
+
+fn parse_trim_margin(s: &str) -> anyhow::Result<[u32; 4]> {
+    tex_packer_core::config::parse_sides(s).ok_or_else(|| {
+        anyhow::anyhow!(
+            "invalid trim margin '{}': expected N or left,top,right,bottom",
+            s
+        )
+    })
+}
+
+fn parse_background(s: &str) -> anyhow::Result<[u8; 4]> {
+    tex_packer_core::config::parse_color(s).ok_or_else(|| {
+        anyhow::anyhow!(
+            "invalid background color '{}': expected #rrggbb, #rrggbbaa or r,g,b[,a]",
+            s
+        )
+    })
+}
+
+fn parse_pivot(s: &str) -> anyhow::Result<tex_packer_core::Pivot> {
+    s.parse().map_err(|_| {
+        anyhow::anyhow!(
+            "invalid pivot '{}': expected x,y or a name like center, bottom-center, top-left",
+            s
+        )
+    })
+}
+
+/// Reads a `--pivots` manifest: a JSON/YAML map from frame key to pivot.
+fn load_pivot_manifest(
+    path: &Path,
+) -> anyhow::Result<std::collections::BTreeMap<String, tex_packer_core::Pivot>> {
+    let text = fs::read_to_string(path)?;
+    serde_yaml::from_str(&text).with_context(|| format!("pivot manifest {}", path.display()))
+}
+
+/// Atlas metadata files accepted as inputs (`.json`, `.plist`, `.atlas`).
+fn is_atlas_metadata(p: &Path) -> bool {
+    matches!(
+        p.extension()
+            .and_then(|s| s.to_str())
+            .map(|s| s.to_ascii_lowercase())
+            .as_deref(),
+        Some("json" | "plist" | "atlas")
     )
 }
 
-fn load_images_with_progress(paths: &[PathBuf], progress: bool) -> anyhow::Result<Vec<InputImage>> {
+fn pack_progress_bar() -> indicatif::ProgressBar {
+    let b = indicatif::ProgressBar::new(0);
+    b.set_style(
+        indicatif::ProgressStyle::with_template(
+            "{spinner:.green} {msg} {pos}/{len} [{elapsed_precise}]",
+        )
+        .unwrap(),
+    );
+    b
+}
+
+/// Mirrors core pack progress (prepare / layout / compose) onto `bar`.
+fn pack_control(bar: Option<&indicatif::ProgressBar>) -> tex_packer_core::PackControl {
+    use tex_packer_core::{PackControl, PackPhase};
+    let Some(bar) = bar.cloned() else {
+        return PackControl::new();
+    };
+    PackControl::new().on_progress(move |p| {
+        bar.set_message(match p.phase {
+            PackPhase::Prepare => "preparing",
+            PackPhase::Layout => "layout",
+            PackPhase::Compose => "compositing",
+        });
+        bar.set_length(p.total as u64);
+        bar.set_position(p.done as u64);
+    })
+}
+
+/// Loads `paths` (see [`load_sprites`]), skipping files that fail to decode.
+fn load_images_with_progress(
+    paths: &[PathBuf],
+    decode: DecodeOptions,
+    progress: bool,
+) -> anyhow::Result<(LoadedSprites, Vec<Animation>)> {
     use indicatif::{ProgressBar, ProgressStyle};
     let bar = if progress {
         let b = ProgressBar::new(paths.len() as u64);
@@ -771,15 +2030,17 @@ fn load_images_with_progress(paths: &[PathBuf], progress: bool) -> anyhow::Resul
         None
     };
     let mut list = Vec::with_capacity(paths.len());
+    let mut animations = Vec::new();
     for p in paths {
         let msg = p.file_name().and_then(|s| s.to_str()).unwrap_or("");
         if let Some(b) = &bar {
             b.set_message(msg.to_string());
         }
-        match load_image(p) {
-            Ok(img) => {
-                let key = p.to_string_lossy().replace('\\', "/");
-                list.push(InputImage { key, image: img });
+        let key = p.to_string_lossy().replace('\\', "/");
+        match load_sprites(p, key, decode) {
+            Ok((loaded, anim)) => {
+                list.extend(loaded);
+                animations.extend(anim);
             }
             Err(e) => {
                 error!(?p, error = %e, "skip image");
@@ -792,14 +2053,273 @@ fn load_images_with_progress(paths: &[PathBuf], progress: bool) -> anyhow::Resul
     if let Some(b) = &bar {
         b.finish_and_clear();
     }
-    Ok(list)
+    Ok((list, animations))
+}
+
+/// How input files are decoded (`--aseprite-split`, `--expand-animations`).
+#[derive(Debug, Clone, Copy, Default)]
+struct DecodeOptions {
+    aseprite_split: AsepriteSplit,
+    expand_animations: bool,
+}
+
+impl DecodeOptions {
+    fn from_args(cli: &PackArgs) -> anyhow::Result<Self> {
+        Ok(Self {
+            aseprite_split: parse_option("--aseprite-split", &cli.aseprite_split)?,
+            expand_animations: cli.expand_animations,
+        })
+    }
+}
+
+/// Images decoded from input files, with the sprite options they carry.
+type LoadedSprites = Vec<(InputImage, Option<SpriteOptions>)>;
+
+/// Loads one input file as `key`. Aseprite files expand to one image per frame, layer or
+/// slice, keyed `<key without extension>/<name>` (just `key` for an untagged single frame);
+/// slice pivots and nine-patches come back as sprite options. With `expand_animations`,
+/// animated GIF/APNG files expand to `<key without extension>#<i>` plus their animation.
+fn load_sprites(
+    p: &Path,
+    key: String,
+    decode: DecodeOptions,
+) -> anyhow::Result<(LoadedSprites, Option<Animation>)> {
+    let stem = match key.rsplit_once('.') {
+        Some((stem, ext)) if !ext.contains('/') => stem.to_string(),
+        _ => key.clone(),
+    };
+    if decode.expand_animations
+        && let Some(frames) = load_animation_frames(p)?
+    {
+        let mut sprites = Vec::with_capacity(frames.len());
+        let mut anim = Animation {
+            name: stem.clone(),
+            frames: Vec::with_capacity(frames.len()),
+        };
+        for (i, frame) in frames.into_iter().enumerate() {
+            let (num, den) = frame.delay().numer_denom_ms();
+            let key = format!("{stem}#{i}");
+            anim.frames.push(AnimationFrame {
+                key: key.clone(),
+                duration_ms: num / den.max(1),
+            });
+            let image = DynamicImage::ImageRgba8(frame.into_buffer());
+            sprites.push((InputImage { key, image }, None));
+        }
+        return Ok((sprites, Some(anim)));
+    }
+    if !is_aseprite(p) {
+        let image = load_image(p)?;
+        return Ok((vec![(InputImage { key, image }, None)], None));
+    }
+    let ase = tex_packer_core::AsepriteFile::parse(&fs::read(p)?)?;
+    let sprites = ase
+        .sprites(decode.aseprite_split)
+        .into_iter()
+        .map(|s| {
+            let key = if s.name.is_empty() {
+                key.clone()
+            } else {
+                format!("{stem}/{}", s.name)
+            };
+            let opts = (s.pivot.is_some() || s.nine_patch.is_some()).then(|| SpriteOptions {
+                pivot: s.pivot,
+                nine_patch: s.nine_patch,
+                ..Default::default()
+            });
+            let image = DynamicImage::ImageRgba8(s.image);
+            (InputImage { key, image }, opts)
+        })
+        .collect();
+    Ok((sprites, None))
+}
+
+/// Frames of an animated GIF or APNG (`None` for other files and single-frame images).
+fn load_animation_frames(p: &Path) -> anyhow::Result<Option<Vec<image::Frame>>> {
+    use image::AnimationDecoder;
+    use image::codecs::{gif::GifDecoder, png::PngDecoder};
+    let ext = p
+        .extension()
+        .and_then(|e| e.to_str())
+        .map(|e| e.to_ascii_lowercase());
+    let reader = || -> anyhow::Result<_> { Ok(std::io::BufReader::new(fs::File::open(p)?)) };
+    let frames = match ext.as_deref() {
+        Some("gif") => GifDecoder::new(reader()?)?.into_frames().collect_frames()?,
+        Some("png" | "apng") => {
+            let decoder = PngDecoder::new(reader()?)?;
+            if !decoder.is_apng()? {
+                return Ok(None);
+            }
+            decoder.apng()?.into_frames().collect_frames()?
+        }
+        _ => return Ok(None),
+    };
+    Ok((frames.len() > 1).then_some(frames))
+}
+
+fn is_aseprite(p: &Path) -> bool {
+    p.extension()
+        .and_then(|e| e.to_str())
+        .is_some_and(|e| e.eq_ignore_ascii_case("ase") || e.eq_ignore_ascii_case("aseprite"))
 }
 
+/// Decodes an image file. Aseprite files give their first frame, PSD files their merged
+/// composite.
 fn load_image(p: &Path) -> anyhow::Result<DynamicImage> {
+    if is_aseprite(p) {
+        let ase = tex_packer_core::AsepriteFile::parse(&fs::read(p)?)?;
+        return Ok(DynamicImage::ImageRgba8(ase.render_frame(0)));
+    }
+    let is_psd = p
+        .extension()
+        .and_then(|e| e.to_str())
+        .is_some_and(|e| e.eq_ignore_ascii_case("psd"));
+    if is_psd {
+        return Ok(DynamicImage::ImageRgba8(tex_packer_core::decode_psd(
+            &fs::read(p)?,
+        )?));
+    }
     let img = ImageReader::open(p)?.with_guessed_format()?.decode()?;
     Ok(img)
 }
 
+/// Page file stem: `name` for single-page atlases, `name_{id}` otherwise.
+fn page_stem(cli: &PackArgs, id: usize, num_pages: usize) -> String {
+    if num_pages == 1 {
+        cli.name.clone()
+    } else {
+        format!("{}_{}", cli.name, id)
+    }
+}
+
+/// Page image filename: the page stem plus the page format extension.
+fn page_file_name(cli: &PackArgs, cfg: &PackerConfig, id: usize, num_pages: usize) -> String {
+    format!(
+        "{}.{}",
+        page_stem(cli, id, num_pages),
+        cfg.page_format.extension()
+    )
+}
+
+fn page_file_names<K>(
+    cli: &PackArgs,
+    cfg: &PackerConfig,
+    atlas: &tex_packer_core::Atlas<K>,
+) -> Vec<String> {
+    atlas
+        .pages
+        .iter()
+        .map(|p| page_file_name(cli, cfg, p.id, atlas.pages.len()))
+        .collect()
+}
+
+/// Writes one page; returns `(encoded, optimized)` byte sizes when the page went through the
+/// PNG optimizer.
+fn write_page_image(
+    cli: &PackArgs,
+    cfg: &PackerConfig,
+    page: &tex_packer_core::OutputPage,
+    path: &Path,
+) -> anyhow::Result<Option<(u64, u64)>> {
+    use tex_packer_core::config::PageFormat;
+    match cfg.page_format {
+        PageFormat::Png if cfg.page_encoding.png_optimize.is_some() => {
+            let enc = PageEncoding {
+                png_optimize: None,
+                ..cfg.page_encoding
+            };
+            let level = cfg.page_encoding.png_optimize.unwrap_or_default();
+            let encoded = tex_packer_core::encode_page(&*page.pixels()?, PageFormat::Png, &enc)?;
+            let optimized = tex_packer_core::optimize_png(&encoded, level)?;
+            fs::write(path, &optimized).with_context(|| format!("write {}", path.display()))?;
+            return Ok(Some((encoded.len() as u64, optimized.len() as u64)));
+        }
+        PageFormat::Ktx2 => {
+            #[cfg(feature = "ktx2")]
+            {
+                let rgba = page.pixels()?;
+                let mips = match cfg.mip_levels.filter(|_| cli.mip_chain) {
+                    Some(levels) => tex_packer_core::mip_chain(&rgba, levels),
+                    None => Vec::new(),
+                };
+                let bytes = tex_packer_core::encode_ktx2_with_mips(
+                    &rgba,
+                    &mips,
+                    cfg.basis_codec,
+                    &cfg.page_encoding,
+                )?;
+                fs::write(path, bytes).with_context(|| format!("write {}", path.display()))?;
+            }
+            #[cfg(not(feature = "ktx2"))]
+            {
+                let _ = (cli, page, path);
+                anyhow::bail!("--page-format ktx2 requires building with `--features ktx2`");
+            }
+        }
+        PageFormat::Dds => {
+            #[cfg(feature = "dds")]
+            {
+                let bytes = tex_packer_core::encode_dds(&*page.pixels()?, cfg.bcn_format)?;
+                fs::write(path, bytes).with_context(|| format!("write {}", path.display()))?;
+            }
+            #[cfg(not(feature = "dds"))]
+            {
+                let _ = (page, path);
+                anyhow::bail!("--page-format dds requires building with `--features dds`");
+            }
+        }
+        PageFormat::Png if page.scratch.is_some() => {
+            let file =
+                fs::File::create(path).with_context(|| format!("write {}", path.display()))?;
+            page.write_png(std::io::BufWriter::new(file))
+                .with_context(|| format!("write {}", path.display()))?;
+        }
+        PageFormat::Png => page
+            .rgba
+            .save(path)
+            .with_context(|| format!("write {}", path.display()))?,
+        PageFormat::Jpeg | PageFormat::Webp | PageFormat::Avif | PageFormat::Exr => {
+            let bytes = tex_packer_core::encode_page(
+                &*page.pixels()?,
+                cfg.page_format,
+                &cfg.page_encoding,
+            )?;
+            fs::write(path, bytes).with_context(|| format!("write {}", path.display()))?;
+        }
+    }
+    Ok(None)
+}
+
+/// Writes `<page>_mip<N>` files for `--mip-chain` (KTX2 pages hold their levels instead).
+fn write_mip_files(
+    cli: &PackArgs,
+    cfg: &PackerConfig,
+    page: &tex_packer_core::OutputPage,
+    num_pages: usize,
+) -> anyhow::Result<Vec<PathBuf>> {
+    let ktx2 = cfg.page_format == tex_packer_core::config::PageFormat::Ktx2;
+    let Some(levels) = cfg.mip_levels.filter(|_| cli.mip_chain && !ktx2) else {
+        return Ok(Vec::new());
+    };
+    let stem = page_stem(cli, page.page.id, num_pages);
+    let mut files = Vec::new();
+    for (i, level) in tex_packer_core::mip_chain(&*page.pixels()?, levels)
+        .iter()
+        .enumerate()
+    {
+        let path = cli.out_dir.join(format!(
+            "{stem}_mip{}.{}",
+            i + 1,
+            cfg.page_format.extension()
+        ));
+        let bytes = tex_packer_core::encode_page(level, cfg.page_format, &cfg.page_encoding)?;
+        fs::write(&path, bytes).with_context(|| format!("write {}", path.display()))?;
+        files.push(path);
+    }
+    Ok(files)
+}
+
+/// `--export-stats` document: the flat totals earlier versions wrote plus the full breakdown.
 fn compute_stats(out: &tex_packer_core::PackOutput) -> (u64, u64) {
     let mut used: u64 = 0;
     let mut total: u64 = 0;
@@ -812,7 +2332,62 @@ fn compute_stats(out: &tex_packer_core::PackOutput) -> (u64, u64) {
     (used, total)
 }
 
-fn init_tracing_with_level(quiet: bool, verbose: u8) {
+/// Print a per-page efficiency table (used with --export-stats in verbose mode).
+fn print_page_stats_table<K>(atlas: &tex_packer_core::Atlas<K>) {
+    const BAR_WIDTH: usize = 20;
+    println!(
+        "{:>4}  {:>11}  {:>6}  {:>7}  {:<width$}  {:>7}  {:>10}",
+        "page",
+        "size",
+        "frames",
+        "rotated",
+        "occupancy",
+        "",
+        "wasted px",
+        width = BAR_WIDTH + 2
+    );
+    for ps in atlas.page_stats() {
+        let filled = ((ps.occupancy * BAR_WIDTH as f64).round() as usize).min(BAR_WIDTH);
+        let bar = format!("[{}{}]", "#".repeat(filled), "-".repeat(BAR_WIDTH - filled));
+        println!(
+            "{:>4}  {:>11}  {:>6}  {:>6.1}%  {}  {:>6.2}%  {:>10}",
+            ps.id,
+            format!("{}x{}", ps.width, ps.height),
+            ps.num_frames,
+            ps.rotated_ratio() * 100.0,
+            bar,
+            ps.occupancy * 100.0,
+            ps.wasted_area()
+        );
+    }
+    let total = atlas.stats();
+    println!(
+        "{:>4}  {:>11}  {:>6}  {:>6.1}%  {:<width$}  {:>6.2}%  {:>10}",
+        "all",
+        "",
+        total.num_frames,
+        if total.num_frames > 0 {
+            total.num_rotated as f64 / total.num_frames as f64 * 100.0
+        } else {
+            0.0
+        },
+        "",
+        total.occupancy * 100.0,
+        total.wasted_area(),
+        width = BAR_WIDTH + 2
+    );
+}
+
+fn init_tracing_with_level(quiet: bool, verbose: u8, json_report: bool) {
+    if json_report {
+        // stdout carries the report; warnings still go into it
+        use tracing_subscriber::layer::{Layer, SubscriberExt};
+        use tracing_subscriber::util::SubscriberInitExt;
+        let _ = tracing_subscriber::registry()
+            .with(report::WarningCapture.with_filter(tracing_subscriber::filter::LevelFilter::WARN))
+            .try_init();
+        return;
+    }
     let level = if quiet {
         "error".to_string()
     } else {
@@ -828,106 +2403,94 @@ fn init_tracing_with_level(quiet: bool, verbose: u8) {
         .try_init();
 }
 
-use serde::Serialize;
-#[derive(Serialize)]
-struct TemplateSprite {
-    name: String,
-    frame: serde_json::Value,
-    rotated: bool,
-    trimmed: bool,
-    sprite_source_size: serde_json::Value,
-    source_size: serde_json::Value,
-    pivot: serde_json::Value,
-}
-
-#[derive(Serialize)]
-struct TemplatePage {
-    image: String,
-    size: serde_json::Value,
-    sprites: Vec<TemplateSprite>,
-}
-
-#[derive(Serialize)]
-struct TemplateContext {
-    pages: Vec<TemplatePage>,
-    meta: serde_json::Value,
-}
-
-fn build_template_context(
-    out: &tex_packer_core::PackOutput,
-    page_names: &[String],
-) -> TemplateContext {
-    let mut pages: Vec<TemplatePage> = Vec::new();
-    for (idx, output_page) in out.pages.iter().enumerate() {
-        let page = &output_page.page;
-        let image = page_names
-            .get(idx)
-            .cloned()
-            .unwrap_or_else(|| format!("page_{}.png", page.id));
-        let size = serde_json::json!({"w": page.width, "h": page.height});
-        let mut sprites: Vec<TemplateSprite> = Vec::new();
-        for fr in &page.frames {
-            let frame = serde_json::json!({"x": fr.frame.x, "y": fr.frame.y, "w": fr.frame.w, "h": fr.frame.h});
-            let sss = serde_json::json!({"x": fr.source.x, "y": fr.source.y, "w": fr.source.w, "h": fr.source.h});
-            let ss = serde_json::json!({"w": fr.source_size.0, "h": fr.source_size.1});
-            let pivot = serde_json::json!({"x": 0.5_f32, "y": 0.5_f32});
-            sprites.push(TemplateSprite {
-                name: fr.key.clone(),
-                frame,
-                rotated: fr.rotated,
-                trimmed: fr.trimmed,
-                sprite_source_size: sss,
-                source_size: ss,
-                pivot,
-            });
-        }
-        pages.push(TemplatePage {
-            image,
-            size,
-            sprites,
-        });
-    }
-    let meta = serde_json::json!({
-        "app": out.atlas.meta.app,
-        "version": out.atlas.meta.version,
-        "format": out.atlas.meta.format,
-        "scale": out.atlas.meta.scale,
-    });
-    TemplateContext { pages, meta }
-}
-
-#[derive(Debug, Deserialize, Default)]
+#[derive(Debug, Clone, Deserialize, Default, schemars::JsonSchema)]
+#[serde(deny_unknown_fields)]
+#[schemars(title = "tex-packer --config file")]
 struct YamlConfig {
     family: Option<String>,
     skyline: Option<String>,
     heuristic: Option<String>,
     g_choice: Option<String>,
     g_split: Option<String>,
+    g_merge: Option<bool>,
+    shelf: Option<String>,
+    /// Grid cell size (`WxH`, `N` or `auto`); implies `family: grid`.
+    grid: Option<String>,
     auto_mode: Option<String>,
+    /// `family[:heuristic][@sort_order]` entries, like `--auto-candidates`.
+    auto_candidates: Option<Vec<String>>,
+    auto_objective: Option<String>,
     max_width: Option<u32>,
     max_height: Option<u32>,
     allow_rotation: Option<bool>,
+    rotation_direction: Option<String>,
+    /// Older spelling of `page_size`; only read when `page_size` is absent.
     force_max_dimensions: Option<bool>,
     border_padding: Option<u32>,
     texture_padding: Option<u32>,
+    /// `N`, `{x, y}` or `{left, top, right, bottom}`; overrides `texture_padding`.
+    padding: Option<Padding>,
     texture_extrusion: Option<u32>,
+    extrude_mode: Option<String>,
+    align: Option<u32>,
+    align_size: Option<bool>,
     trim: Option<bool>,
     trim_threshold: Option<u8>,
+    trim_margin: Option<String>,
+    trim_min_size: Option<u32>,
+    trim_multiple: Option<u32>,
     texture_outlines: Option<bool>,
+    /// Older spelling of `page_size`; only read when `page_size` is absent.
     power_of_two: Option<bool>,
     square: Option<bool>,
+    minimize_page_size: Option<bool>,
     use_waste_map: Option<bool>,
     sort_order: Option<String>,
     time_budget_ms: Option<u64>,
     parallel: Option<bool>,
+    deterministic: Option<bool>,
+    auto_refine: Option<bool>,
+    low_memory: Option<bool>,
+    key_transform: Option<tex_packer_core::config::KeyTransform>,
+    duplicate_keys: Option<String>,
+    pivot_mode: Option<String>,
+    default_pivot: Option<tex_packer_core::Pivot>,
+    pivots: Option<std::collections::BTreeMap<String, tex_packer_core::Pivot>>,
+    sprites: Option<std::collections::BTreeMap<String, tex_packer_core::SpriteOptions>>,
     mr_reference: Option<bool>,
+    mr_global: Option<bool>,
     auto_mr_ref_time_ms_threshold: Option<u64>,
     auto_mr_ref_input_threshold: Option<usize>,
     transparent_policy: Option<String>,
+    on_unplaceable: Option<String>,
+    min_width: Option<u32>,
+    min_height: Option<u32>,
+    /// Older spelling of `page_size`; only read when `page_size` is absent.
+    allowed_sizes: Option<Vec<u32>>,
+    page_size: Option<String>,
+    max_pages: Option<usize>,
+    page_grouping: Option<String>,
+    group_max_pages: Option<usize>,
+    page_balance: Option<String>,
+    frame_order: Option<String>,
+    alpha_bleed: Option<u32>,
+    mip_levels: Option<u32>,
+    background_color: Option<String>,
+    page_format: Option<String>,
+    bcn_format: Option<String>,
+    basis_codec: Option<String>,
+    basis_quality: Option<u8>,
+    page_quality: Option<u8>,
+    webp_lossless: Option<bool>,
+    avif_speed: Option<u8>,
+    png_optimize: Option<u8>,
+    sdf: Option<String>,
+    sdf_range: Option<u32>,
+    sdf_threshold: Option<u8>,
 }
 
 impl YamlConfig {
-    fn into_packer_config(self, mut cfg: PackerConfig) -> PackerConfig {
+    fn into_packer_config(self, mut cfg: PackerConfig) -> anyhow::Result<PackerConfig> {
         if let Some(v) = self.max_width {
             cfg.max_width = v;
         }
@@ -937,8 +2500,8 @@ impl YamlConfig {
         if let Some(v) = self.allow_rotation {
             cfg.allow_rotation = v;
         }
-        if let Some(v) = self.force_max_dimensions {
-            cfg.force_max_dimensions = v;
+        if let Some(v) = self.rotation_direction {
+            cfg.rotation_direction = parse_option("rotation_direction", &v)?;
         }
         if let Some(v) = self.border_padding {
             cfg.border_padding = v;
@@ -946,29 +2509,50 @@ impl YamlConfig {
         if let Some(v) = self.texture_padding {
             cfg.texture_padding = v;
         }
+        if let Some(v) = self.padding {
+            cfg.padding = Some(v);
+        }
         if let Some(v) = self.texture_extrusion {
             cfg.texture_extrusion = v;
         }
+        if let Some(v) = self.extrude_mode {
+            cfg.extrude_mode = parse_option("extrude_mode", &v)?;
+        }
+        if let Some(v) = self.align {
+            cfg.align = v;
+        }
+        if let Some(v) = self.align_size {
+            cfg.align_size = v;
+        }
         if let Some(v) = self.trim {
             cfg.trim = v;
         }
         if let Some(v) = self.trim_threshold {
             cfg.trim_threshold = v;
         }
+        if let Some(v) = self.trim_margin {
+            cfg.trim_options.margin = parse_trim_margin(&v)?;
+        }
+        if let Some(v) = self.trim_min_size {
+            cfg.trim_options.min_size = v;
+        }
+        if let Some(v) = self.trim_multiple {
+            cfg.trim_options.multiple_of = v;
+        }
         if let Some(v) = self.texture_outlines {
             cfg.texture_outlines = v;
         }
-        if let Some(v) = self.power_of_two {
-            cfg.power_of_two = v;
-        }
         if let Some(v) = self.square {
             cfg.square = v;
         }
+        if let Some(v) = self.minimize_page_size {
+            cfg.minimize_page_size = v;
+        }
         if let Some(v) = self.use_waste_map {
             cfg.use_waste_map = v;
         }
         if let Some(v) = self.sort_order {
-            cfg.sort_order = parse_sort_order(&v).unwrap_or(cfg.sort_order);
+            cfg.sort_order = parse_option("sort_order", &v)?;
         }
         if let Some(v) = self.time_budget_ms {
             cfg.time_budget_ms = Some(v);
@@ -976,30 +2560,72 @@ impl YamlConfig {
         if let Some(v) = self.parallel {
             cfg.parallel = v;
         }
+        if let Some(v) = self.deterministic {
+            cfg.deterministic = v;
+        }
+        if let Some(v) = self.auto_refine {
+            cfg.auto_refine = v;
+        }
+        if let Some(v) = self.low_memory {
+            cfg.low_memory = v;
+        }
+        if let Some(v) = self.key_transform {
+            cfg.key_transform = v;
+        }
+        if let Some(v) = self.duplicate_keys {
+            cfg.duplicate_keys = parse_option("duplicate_keys", &v)?;
+        }
+        if let Some(v) = self.pivot_mode {
+            cfg.pivot_mode = parse_option("pivot_mode", &v)?;
+        }
+        if let Some(v) = self.default_pivot {
+            cfg.default_pivot = v;
+        }
+        if let Some(v) = self.pivots {
+            cfg.pivots.extend(v);
+        }
+        if let Some(v) = self.sprites {
+            cfg.sprites.extend(v);
+        }
         if let Some(v) = self.mr_reference {
             cfg.mr_reference = v;
         }
+        if let Some(v) = self.mr_global {
+            cfg.mr_global = v;
+        }
         if let Some(v) = self.family {
-            cfg.family = v.parse().unwrap_or(cfg.family);
+            cfg.family = parse_option("family", &v)?;
         }
         if let Some(v) = self.skyline {
-            cfg.skyline_heuristic = v.parse().unwrap_or(cfg.skyline_heuristic);
+            cfg.skyline_heuristic = parse_option("skyline", &v)?;
         }
         if let Some(v) = self.heuristic {
-            cfg.mr_heuristic = v.parse().unwrap_or(cfg.mr_heuristic);
+            cfg.mr_heuristic = parse_option("heuristic", &v)?;
         }
         if let Some(v) = self.g_choice {
-            cfg.g_choice = v.parse().unwrap_or(cfg.g_choice);
+            cfg.g_choice = parse_option("g_choice", &v)?;
         }
         if let Some(v) = self.g_split {
-            cfg.g_split = v.parse().unwrap_or(cfg.g_split);
+            cfg.g_split = parse_option("g_split", &v)?;
+        }
+        if let Some(v) = self.g_merge {
+            cfg.g_merge = v;
+        }
+        if let Some(v) = self.shelf {
+            cfg.shelf_heuristic = parse_option("shelf", &v)?;
+        }
+        if let Some(v) = self.grid {
+            (cfg.grid_cell_width, cfg.grid_cell_height) = parse_grid(&v)?;
+            cfg.family = AlgorithmFamily::Grid;
         }
         if let Some(v) = self.auto_mode {
-            cfg.auto_mode = match v.to_ascii_lowercase().as_str() {
-                "fast" => AutoMode::Fast,
-                "quality" => AutoMode::Quality,
-                _ => cfg.auto_mode,
-            };
+            cfg.auto_mode = parse_option("auto_mode", &v)?;
+        }
+        if let Some(v) = self.auto_candidates {
+            cfg.auto_candidates = parse_auto_candidates("auto_candidates", &v)?;
+        }
+        if let Some(v) = self.auto_objective {
+            cfg.auto_objective = parse_option("auto_objective", &v)?;
         }
         if let Some(v) = self.auto_mr_ref_time_ms_threshold {
             cfg.auto_mr_ref_time_ms_threshold = Some(v);
@@ -1008,20 +2634,137 @@ impl YamlConfig {
             cfg.auto_mr_ref_input_threshold = Some(v);
         }
         if let Some(v) = self.transparent_policy {
-            cfg.transparent_policy = v.parse().unwrap_or(cfg.transparent_policy);
+            cfg.transparent_policy = parse_option("transparent_policy", &v)?;
+        }
+        if let Some(v) = self.on_unplaceable {
+            cfg.on_unplaceable = parse_option("on_unplaceable", &v)?;
+        }
+        if let Some(v) = self.min_width {
+            cfg.min_width = v;
         }
-        cfg
+        if let Some(v) = self.min_height {
+            cfg.min_height = v;
+        }
+        if let Some(v) = self.page_size {
+            cfg.page_size = parse_option("page_size", &v)?;
+        } else if self.power_of_two.is_some()
+            || self.force_max_dimensions.is_some()
+            || self.allowed_sizes.is_some()
+        {
+            cfg.page_size = PageSizeMode::from_legacy_flags(
+                self.power_of_two.unwrap_or(false),
+                self.force_max_dimensions.unwrap_or(false),
+                self.allowed_sizes.as_deref().unwrap_or_default(),
+                cfg.max_width,
+                cfg.max_height,
+            );
+        }
+        if let Some(v) = self.max_pages {
+            cfg.max_pages = Some(v);
+        }
+        if let Some(v) = self.page_grouping {
+            cfg.page_grouping = parse_option("page_grouping", &v)?;
+        }
+        if let Some(v) = self.group_max_pages {
+            cfg.group_max_pages = Some(v);
+        }
+        if let Some(v) = self.page_balance {
+            cfg.page_balance = parse_option("page_balance", &v)?;
+        }
+        if let Some(v) = self.frame_order {
+            cfg.frame_order = parse_option("frame_order", &v)?;
+        }
+        if let Some(v) = self.alpha_bleed {
+            cfg.alpha_bleed = Some(v);
+        }
+        if let Some(v) = self.mip_levels {
+            cfg.mip_levels = Some(v);
+        }
+        if let Some(v) = self.background_color {
+            cfg.background_color = Some(parse_background(&v)?);
+        }
+        if let Some(v) = self.page_format {
+            cfg.page_format = parse_option("page_format", &v)?;
+        }
+        if let Some(v) = self.bcn_format {
+            cfg.bcn_format = parse_option("bcn_format", &v)?;
+        }
+        if let Some(v) = self.basis_codec {
+            cfg.basis_codec = parse_option("basis_codec", &v)?;
+        }
+        if let Some(v) = self.basis_quality {
+            cfg.page_encoding.basis_quality = v;
+        }
+        if let Some(v) = self.page_quality {
+            cfg.page_encoding.quality = v;
+        }
+        if let Some(v) = self.webp_lossless {
+            cfg.page_encoding.lossless = v;
+        }
+        if let Some(v) = self.avif_speed {
+            cfg.page_encoding.speed = v;
+        }
+        if let Some(v) = self.png_optimize {
+            cfg.page_encoding.png_optimize = Some(v);
+        }
+        if let Some(v) = self.sdf {
+            cfg.set_str("sdf", &v)?;
+        }
+        if let Some(v) = self.sdf_range {
+            cfg.sdf.get_or_insert_default().range = v;
+        }
+        if let Some(v) = self.sdf_threshold {
+            cfg.sdf.get_or_insert_default().threshold = v;
+        }
+        Ok(cfg)
     }
 }
 
-fn parse_sort_order(s: &str) -> anyhow::Result<SortOrder> {
-    Ok(match s.to_ascii_lowercase().as_str() {
-        "area_desc" => SortOrder::AreaDesc,
-        "max_side_desc" => SortOrder::MaxSideDesc,
-        "height_desc" => SortOrder::HeightDesc,
-        "width_desc" => SortOrder::WidthDesc,
-        "name_asc" => SortOrder::NameAsc,
-        "none" => SortOrder::None,
-        other => anyhow::bail!("unknown sort order: {}", other),
+/// Page size policy from `--page-size`, else the one `--force-max-dimensions`, `--allowed-sizes`
+/// and `--pow2` stand for.
+fn cli_page_size(cli: &PackArgs) -> anyhow::Result<PageSizeMode> {
+    Ok(match &cli.page_size {
+        Some(v) => parse_option("--page-size", v)?,
+        None => PageSizeMode::from_legacy_flags(
+            cli.pow2,
+            cli.force_max_dimensions,
+            &cli.allowed_sizes,
+            cli.max_width,
+            cli.max_height,
+        ),
     })
 }
+
+/// Distance field settings from `--sdf` / `--sdf-range`.
+fn cli_sdf(cli: &PackArgs) -> anyhow::Result<Option<SdfOptions>> {
+    let Some(mode) = &cli.sdf else {
+        return Ok(None);
+    };
+    Ok(Some(SdfOptions {
+        mode: parse_option("--sdf", mode)?,
+        range: cli.sdf_range,
+        ..Default::default()
+    }))
+}
+
+/// Key rewriting from the `Keys` flags (`relative` is resolved while loading inputs).
+fn cli_key_transform(cli: &PackArgs) -> anyhow::Result<tex_packer_core::config::KeyTransform> {
+    use tex_packer_core::config::{KeyReplace, KeyTransform};
+    let mut t = match cli.key_format.as_str() {
+        "name" => KeyTransform::file_name(),
+        "stem" => KeyTransform::file_stem(),
+        _ => KeyTransform::default(),
+    };
+    t.strip_prefix = cli.key_strip_prefix.clone();
+    for r in &cli.key_replace {
+        let Some((pattern, replacement)) = r.split_once('=') else {
+            anyhow::bail!("--key-replace expects PATTERN=REPLACEMENT, got '{}'", r);
+        };
+        t.replace.push(KeyReplace {
+            pattern: pattern.to_string(),
+            replacement: replacement.to_string(),
+        });
+    }
+    t.case = parse_option("--key-case", &cli.key_case)?;
+    Ok(t)
+}
@@ -0,0 +1,391 @@
+//! `tex-packer build`: several atlases declared in one project file.
+//!
+//! ```yaml
+//! out_dir: build/atlases
+//! jobs: 4
+//! atlases:
+//!   - name: characters
+//!     inputs: ["art/characters/**/*.png"]
+//!     config: configs/characters.yaml
+//!     exporters: [json-hash, gdx]
+//!   - name: ui
+//!     inputs: [art/ui]
+//!     config: { max_width: 1024, allow_rotation: false }
+//!     args: ["--key-format", "relative"]
+//! ```
+//!
+//! Every atlas becomes one `pack` run. A fingerprint of each atlas (its project entry, config,
+//! templates and the size/mtime of every file under its inputs) is stored in a cache file shared
+//! by the whole project, so unchanged atlases are skipped on the next build.
+
+use std::collections::BTreeMap;
+use std::fs;
+use std::hash::{DefaultHasher, Hash, Hasher};
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use anyhow::Context;
+use clap::Parser;
+use serde::{Deserialize, Serialize};
+use tracing::{error, info};
+use walkdir::WalkDir;
+
+use crate::report::PackReport;
+use crate::{PackArgs, YamlConfig, run_pack};
+
+/// Default cache file, next to the project file.
+const DEFAULT_CACHE: &str = ".tex-packer-cache.json";
+
+#[derive(Parser, Debug, Clone)]
+pub(crate) struct BuildArgs {
+    /// Project file declaring the atlases (paths inside are relative to it)
+    project: PathBuf,
+    /// Build only these atlases (by name; repeatable)
+    #[arg(long)]
+    only: Vec<String>,
+    /// Atlases built at once (default: the project's `jobs`, else 1)
+    #[arg(short, long)]
+    jobs: Option<usize>,
+    /// Rebuild every atlas even if the cache says it is up to date
+    #[arg(long, default_value_t = false)]
+    force: bool,
+    /// Result output: text (logs) | json (one result document on stdout, no logs)
+    #[arg(long, default_value = "text", value_parser = ["text", "json"])]
+    pub(crate) report: String,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct Project {
+    /// Output directory for atlases that do not set their own (default `out`).
+    out_dir: Option<PathBuf>,
+    /// Atlases built at once.
+    jobs: Option<usize>,
+    /// Cache file shared by all atlases (default `.tex-packer-cache.json`); `false` disables it.
+    #[serde(default)]
+    cache: ProjectCache,
+    /// `pack` arguments applied to every atlas, before the atlas's own `args`.
+    #[serde(default)]
+    args: Vec<String>,
+    atlases: Vec<ProjectAtlas>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(untagged)]
+enum ProjectCache {
+    #[default]
+    Default,
+    Enabled(bool),
+    Path(PathBuf),
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct ProjectAtlas {
+    /// Atlas base name (`<name>.png`, `<name>.json`, ...); unique within the project.
+    name: String,
+    /// Image files, folders, atlas metadata or globs (`art/fx/**/*.png`).
+    inputs: Vec<String>,
+    /// Glob patterns to leave out.
+    #[serde(default)]
+    exclude: Vec<String>,
+    out_dir: Option<PathBuf>,
+    /// A `--config` YAML file, or the same settings inline.
+    config: Option<ProjectConfig>,
+    /// `--metadata` formats to write (default `json-array`).
+    #[serde(default)]
+    exporters: Vec<String>,
+    engine: Option<String>,
+    template: Option<PathBuf>,
+    /// Any other `pack` arguments, e.g. `["--key-format", "stem"]`. Paths in them are used
+    /// as given (relative to the working directory).
+    #[serde(default)]
+    args: Vec<String>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(untagged)]
+enum ProjectConfig {
+    File(PathBuf),
+    Inline(Box<YamlConfig>),
+}
+
+/// `--report json` result of a build.
+#[derive(Debug, Default, Serialize)]
+pub(crate) struct BuildReport {
+    /// No atlas failed.
+    pub ok: bool,
+    pub built: Vec<PackReport>,
+    /// Atlases skipped because their fingerprint is unchanged.
+    pub up_to_date: Vec<String>,
+    pub failed: Vec<BuildFailure>,
+}
+
+#[derive(Debug, Serialize)]
+pub(crate) struct BuildFailure {
+    pub name: String,
+    pub error: String,
+}
+
+/// One atlas ready to build.
+struct Job {
+    name: String,
+    args: PackArgs,
+    fingerprint: String,
+}
+
+/// Builds the project's atlases. Failing atlases are listed in the report, not returned as errors.
+pub(crate) fn run_build(
+    b: &BuildArgs,
+    show_progress: bool,
+    verbose: bool,
+) -> anyhow::Result<BuildReport> {
+    let text = fs::read_to_string(&b.project)
+        .with_context(|| format!("read project {}", b.project.display()))?;
+    let ext = b
+        .project
+        .extension()
+        .and_then(|s| s.to_str())
+        .map(|s| s.to_ascii_lowercase());
+    // Raw entries feed the fingerprints, so any edit to an atlas invalidates it.
+    let (project, raw): (Project, serde_json::Value) = match ext.as_deref() {
+        Some("toml") => (toml::from_str(&text)?, toml::from_str(&text)?),
+        Some("json") => (serde_json::from_str(&text)?, serde_json::from_str(&text)?),
+        _ => (serde_yaml::from_str(&text)?, serde_yaml::from_str(&text)?),
+    };
+    let base = b.project.parent().unwrap_or(Path::new(""));
+
+    for name in &b.only {
+        if !project.atlases.iter().any(|a| &a.name == name) {
+            anyhow::bail!("--only {name}: no such atlas in {}", b.project.display());
+        }
+    }
+    let mut jobs = Vec::new();
+    for (i, atlas) in project.atlases.iter().enumerate() {
+        if project.atlases[..i].iter().any(|a| a.name == atlas.name) {
+            anyhow::bail!("atlas name '{}' is used twice", atlas.name);
+        }
+        if !b.only.is_empty() && !b.only.contains(&atlas.name) {
+            continue;
+        }
+        let mut args = atlas
+            .pack_args(&project, base)
+            .with_context(|| format!("atlas {}", atlas.name))?;
+        args.report = b.report.clone();
+        let fingerprint = fingerprint(&raw, i, &args);
+        jobs.push(Job {
+            name: atlas.name.clone(),
+            args,
+            fingerprint,
+        });
+    }
+
+    let cache_path = match &project.cache {
+        ProjectCache::Default | ProjectCache::Enabled(true) => Some(base.join(DEFAULT_CACHE)),
+        ProjectCache::Enabled(false) => None,
+        ProjectCache::Path(p) => Some(base.join(p)),
+    };
+    let mut cache: BTreeMap<String, String> = match &cache_path {
+        Some(p) if p.is_file() => serde_json::from_str(&fs::read_to_string(p)?)
+            .with_context(|| format!("cache {}", p.display()))?,
+        _ => BTreeMap::new(),
+    };
+    let (todo, fresh): (Vec<Job>, Vec<Job>) = jobs.into_iter().partition(|j| {
+        b.force
+            || cache_path.is_none()
+            || cache.get(&j.name) != Some(&j.fingerprint)
+            || !j.args.out_dir.is_dir()
+    });
+    for job in &fresh {
+        info!(atlas = %job.name, "up to date");
+    }
+
+    let workers = b
+        .jobs
+        .or(project.jobs)
+        .unwrap_or(1)
+        .clamp(1, todo.len().max(1));
+    // Interleaved progress bars are unreadable; logs still say which atlas is done.
+    let show_progress = show_progress && workers == 1;
+    let next = AtomicUsize::new(0);
+    let results = Mutex::new(Vec::with_capacity(todo.len()));
+    std::thread::scope(|s| {
+        for _ in 0..workers {
+            s.spawn(|| {
+                loop {
+                    let i = next.fetch_add(1, Ordering::Relaxed);
+                    let Some(job) = todo.get(i) else {
+                        break;
+                    };
+                    info!(atlas = %job.name, "building");
+                    let result = run_pack(&job.args, show_progress, verbose);
+                    results.lock().unwrap().push((i, job, result));
+                }
+            });
+        }
+    });
+
+    let mut report = BuildReport {
+        up_to_date: fresh.iter().map(|j| j.name.clone()).collect(),
+        ..Default::default()
+    };
+    let mut results = results.into_inner().unwrap();
+    // Report in project order, not completion order
+    results.sort_by_key(|(i, _, _)| *i);
+    for (_, job, result) in results {
+        match result {
+            Ok(built) => {
+                cache.insert(job.name.clone(), job.fingerprint.clone());
+                report.built.push(built);
+            }
+            Err(e) => {
+                let error = format!("{e:#}");
+                error!(atlas = %job.name, %error, "build failed");
+                cache.remove(&job.name);
+                report.failed.push(BuildFailure {
+                    name: job.name.clone(),
+                    error,
+                });
+            }
+        }
+    }
+    report.ok = report.failed.is_empty();
+    if let Some(p) = &cache_path {
+        fs::write(p, serde_json::to_string_pretty(&cache)?)
+            .with_context(|| format!("write {}", p.display()))?;
+    }
+    info!(
+        built = report.built.len(),
+        up_to_date = report.up_to_date.len(),
+        failed = report.failed.len(),
+        "build finished"
+    );
+    Ok(report)
+}
+
+impl ProjectAtlas {
+    /// The `pack` command line for this atlas, with paths resolved against `base`.
+    fn pack_args(&self, project: &Project, base: &Path) -> anyhow::Result<PackArgs> {
+        let resolve = |s: &str| base.join(s).to_string_lossy().replace('\\', "/");
+        let globs = self.inputs.iter().any(|i| is_glob(i));
+        let mut argv: Vec<String> = vec!["pack".into()];
+        let mut include = Vec::new();
+        for input in &self.inputs {
+            let path = resolve(input);
+            if is_glob(input) {
+                let root = glob_root(&path);
+                if !argv.contains(&root) {
+                    argv.push(root);
+                }
+                include.push(path);
+            } else {
+                // With globs present, --include must not filter out the plain inputs.
+                if globs {
+                    include.push(if Path::new(&path).is_dir() {
+                        format!("{}/**", path.trim_end_matches('/'))
+                    } else {
+                        path.clone()
+                    });
+                }
+                argv.push(path);
+            }
+        }
+        for pattern in include {
+            argv.extend(["--include".into(), pattern]);
+        }
+        for pattern in &self.exclude {
+            argv.extend(["--exclude".into(), resolve(pattern)]);
+        }
+        let out_dir = self
+            .out_dir
+            .as_ref()
+            .or(project.out_dir.as_ref())
+            .map_or_else(|| "out".into(), |d| resolve(&d.to_string_lossy()));
+        argv.extend([
+            "--name".into(),
+            self.name.clone(),
+            "--out-dir".into(),
+            out_dir,
+        ]);
+        if !self.exporters.is_empty() {
+            argv.extend(["--metadata".into(), self.exporters.join(",")]);
+        }
+        if let Some(engine) = &self.engine {
+            argv.extend(["--engine".into(), engine.clone()]);
+        }
+        if let Some(template) = &self.template {
+            argv.extend(["--template".into(), resolve(&template.to_string_lossy())]);
+        }
+        if let Some(ProjectConfig::File(path)) = &self.config {
+            argv.extend(["--config".into(), resolve(&path.to_string_lossy())]);
+        }
+        argv.extend(project.args.iter().cloned());
+        argv.extend(self.args.iter().cloned());
+
+        let mut args = PackArgs::try_parse_from(argv)?;
+        if let Some(ProjectConfig::Inline(cfg)) = &self.config {
+            args.inline_config = Some((**cfg).clone());
+        }
+        Ok(args)
+    }
+}
+
+fn is_glob(s: &str) -> bool {
+    s.contains(['*', '?', '[', '{'])
+}
+
+/// Folder to walk for a glob: its path components before the first one with a metacharacter.
+fn glob_root(pattern: &str) -> String {
+    let root: Vec<&str> = pattern.split('/').take_while(|c| !is_glob(c)).collect();
+    if root.is_empty() {
+        ".".into()
+    } else {
+        root.join("/")
+    }
+}
+
+/// Hash of everything that decides an atlas's output: its project entry (plus the project-wide
+/// settings), the tool version, and size + mtime of every file it reads.
+fn fingerprint(raw: &serde_json::Value, index: usize, args: &PackArgs) -> String {
+    let mut h = DefaultHasher::new();
+    env!("CARGO_PKG_VERSION").hash(&mut h);
+    raw["atlases"][index].to_string().hash(&mut h);
+    raw["out_dir"].to_string().hash(&mut h);
+    raw["args"].to_string().hash(&mut h);
+
+    let mut files: Vec<PathBuf> = args.inputs.clone();
+    files.extend(args.config.iter().cloned());
+    files.extend(args.template.iter().cloned());
+    files.extend(args.manifest.iter().cloned());
+    files.extend(args.pivots.iter().cloned());
+    if let Some(list) = &args.input_list
+        && list != Path::new("-")
+    {
+        files.push(list.clone());
+        files.extend(
+            crate::read_input_list(list)
+                .into_iter()
+                .flatten()
+                .map(|(p, _)| p),
+        );
+    }
+    for root in files {
+        for entry in WalkDir::new(&root)
+            .sort_by_file_name()
+            .into_iter()
+            .filter_map(|e| e.ok())
+        {
+            if let Ok(meta) = entry.metadata()
+                && meta.is_file()
+            {
+                entry.path().hash(&mut h);
+                meta.len().hash(&mut h);
+                if let Ok(modified) = meta.modified() {
+                    modified.hash(&mut h);
+                }
+            }
+        }
+    }
+    format!("{:016x}", h.finish())
+}
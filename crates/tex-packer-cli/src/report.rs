@@ -0,0 +1,127 @@
+//! `--report json`: one machine-readable result document on stdout instead of human logs.
+
+use std::cell::RefCell;
+use std::fmt::Write as _;
+use std::path::PathBuf;
+use std::time::Instant;
+
+use serde::Serialize;
+use tex_packer_core::{PackStats, PageStats, ScaledItem, SkippedItem};
+use tracing::field::{Field, Visit};
+use tracing::{Event, Level, Subscriber};
+use tracing_subscriber::layer::{Context, Layer};
+
+/// Result of one pack run (`pack`, `template`, `layout`, `merge`, or one atlas of `build`).
+#[derive(Debug, Default, Serialize)]
+pub(crate) struct PackReport {
+    pub name: String,
+    pub out_dir: PathBuf,
+    pub dry_run: bool,
+    pub layout_only: bool,
+    /// Files written, in write order.
+    pub files: Vec<PathBuf>,
+    /// Totals, including `winning_config` for auto runs.
+    pub stats: Option<PackStats>,
+    pub pages: Vec<PageStats>,
+    pub timings_ms: Timings,
+    /// Inputs left out of the atlas (`--on-unplaceable skip`, `--transparent-policy skip`).
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub skipped: Vec<SkippedItem>,
+    /// Inputs shrunk to fit (`--on-unplaceable scale_down`).
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub scaled: Vec<ScaledItem>,
+    /// Warnings and errors logged during the run (e.g. skipped images, degenerate frames).
+    pub warnings: Vec<String>,
+}
+
+impl PackReport {
+    /// Fills in the results, the write time and the warnings captured on this thread.
+    pub fn finish(mut self, started: Instant, stats: PackStats, pages: Vec<PageStats>) -> Self {
+        let t = &mut self.timings_ms;
+        t.total = started.elapsed().as_millis() as u64;
+        t.write = t.total.saturating_sub(t.load + t.pack);
+        self.stats = Some(stats);
+        self.pages = pages;
+        self.warnings = take_warnings();
+        self
+    }
+}
+
+#[derive(Debug, Default, Serialize)]
+pub(crate) struct Timings {
+    /// Reading and decoding inputs.
+    pub load: u64,
+    /// Trimming and packing.
+    pub pack: u64,
+    /// Compositing and writing pages and metadata.
+    pub write: u64,
+    pub total: u64,
+}
+
+/// Prints the `--report json` document for `result`: the report plus `"ok": true` (unless the
+/// report sets `ok` itself), or `{"ok": false, "error": ...}`.
+pub(crate) fn print_json<T: Serialize>(result: &anyhow::Result<T>) -> anyhow::Result<()> {
+    let value = match result {
+        Ok(report) => {
+            let mut value = serde_json::to_value(report)?;
+            if value.get("ok").is_none() {
+                value["ok"] = true.into();
+            }
+            value
+        }
+        Err(e) => serde_json::json!({
+            "ok": false,
+            "error": format!("{e:#}"),
+            "warnings": take_warnings(),
+        }),
+    };
+    println!("{}", serde_json::to_string_pretty(&value)?);
+    Ok(())
+}
+
+thread_local! {
+    static WARNINGS: RefCell<Vec<String>> = const { RefCell::new(Vec::new()) };
+}
+
+/// Takes the warnings captured on this thread since the last call.
+pub(crate) fn take_warnings() -> Vec<String> {
+    WARNINGS.with(|w| std::mem::take(&mut *w.borrow_mut()))
+}
+
+/// Records WARN and ERROR events as `message key=value ...` for [`take_warnings`].
+pub(crate) struct WarningCapture;
+
+impl<S: Subscriber> Layer<S> for WarningCapture {
+    fn on_event(&self, event: &Event<'_>, _ctx: Context<'_, S>) {
+        if *event.metadata().level() > Level::WARN {
+            return;
+        }
+        let mut visitor = Fields::default();
+        event.record(&mut visitor);
+        WARNINGS.with(|w| w.borrow_mut().push(visitor.message + &visitor.fields));
+    }
+}
+
+#[derive(Default)]
+struct Fields {
+    message: String,
+    fields: String,
+}
+
+impl Visit for Fields {
+    fn record_str(&mut self, field: &Field, value: &str) {
+        if field.name() == "message" {
+            self.message = value.to_string();
+        } else {
+            let _ = write!(self.fields, " {}={value}", field.name());
+        }
+    }
+
+    fn record_debug(&mut self, field: &Field, value: &dyn std::fmt::Debug) {
+        if field.name() == "message" {
+            self.message = format!("{value:?}");
+        } else {
+            let _ = write!(self.fields, " {}={value:?}", field.name());
+        }
+    }
+}
@@ -0,0 +1,286 @@
+//! Offline (batch) packing benchmarks over the asset categories of `gen_assets`
+//! (basic / thin / trim / irregular), generated in memory from a fixed seed.
+//!
+//! `offline_packers` times `pack_layout_items` per family, `offline_pipeline` times the full
+//! `pack_images` path (trimming + compositing) and `offline_occupancy` reports page occupancy
+//! through the [`Occupancy`] measurement, so layout quality regressions show up next to speed
+//! ones. For occupancy, higher is better: criterion's "regressed" means the packer got denser.
+
+use criterion::measurement::{Measurement, ValueFormatter};
+use criterion::{BenchmarkId, Criterion, Throughput, criterion_group, criterion_main};
+use image::{DynamicImage, Rgba, RgbaImage};
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use std::hint::black_box;
+use tex_packer_core::prelude::*;
+
+const SEED: u64 = 0x7E5_BA5E;
+
+/// Opaque rectangles with varied sizes.
+fn basic(rng: &mut StdRng) -> Vec<LayoutItem> {
+    (0..120)
+        .map(|i| {
+            untrimmed(
+                format!("basic_{i:03}"),
+                rng.gen_range(16..=164),
+                rng.gen_range(16..=164),
+            )
+        })
+        .collect()
+}
+
+/// Very thin horizontal/vertical bars.
+fn thin(rng: &mut StdRng) -> Vec<LayoutItem> {
+    (0..80)
+        .map(|i| {
+            let (w, h) = if rng.gen_bool(0.5) {
+                (rng.gen_range(64..=256), rng.gen_range(4..=12))
+            } else {
+                (rng.gen_range(4..=12), rng.gen_range(64..=256))
+            };
+            untrimmed(format!("thin_{i:03}"), w, h)
+        })
+        .collect()
+}
+
+/// One opaque block inside a transparent canvas; the block is what trimming keeps.
+fn trim(rng: &mut StdRng) -> Vec<LayoutItem> {
+    (0..80)
+        .map(|i| {
+            let (w, h) = (rng.gen_range(48..=192), rng.gen_range(48..=192));
+            let bw = rng.gen_range(16..=(w / 2).max(16));
+            let bh = rng.gen_range(16..=(h / 2).max(16));
+            let x = rng.gen_range(0..=w - bw);
+            let y = rng.gen_range(0..=h - bh);
+            trimmed(format!("trim_{i:03}"), Rect::new(x, y, bw, bh), (w, h))
+        })
+        .collect()
+}
+
+/// Scattered blotches: the trimmed rect is their bounding box.
+fn irregular(rng: &mut StdRng) -> Vec<LayoutItem> {
+    (0..150)
+        .map(|i| {
+            let (w, h) = (rng.gen_range(32..=256), rng.gen_range(32..=256));
+            let (mut x0, mut y0, mut x1, mut y1) = (w, h, 0, 0);
+            for _ in 0..rng.gen_range(3..=9) {
+                let (cx, cy): (u32, u32) = (rng.gen_range(0..w), rng.gen_range(0..h));
+                let rw = rng.gen_range(6..=w.min(96));
+                let rh = rng.gen_range(6..=h.min(96));
+                x0 = x0.min(cx.saturating_sub(rw / 2));
+                y0 = y0.min(cy.saturating_sub(rh / 2));
+                x1 = x1.max((cx + rw / 2).min(w));
+                y1 = y1.max((cy + rh / 2).min(h));
+            }
+            let rect = Rect::new(x0, y0, (x1 - x0).max(1), (y1 - y0).max(1));
+            trimmed(format!("irregular_{i:03}"), rect, (w, h))
+        })
+        .collect()
+}
+
+fn untrimmed(key: String, w: u32, h: u32) -> LayoutItem {
+    LayoutItem {
+        key,
+        w,
+        h,
+        source: None,
+        source_size: None,
+        trimmed: false,
+    }
+}
+
+fn trimmed(key: String, source: Rect, size: (u32, u32)) -> LayoutItem {
+    LayoutItem {
+        key,
+        w: source.w,
+        h: source.h,
+        source: Some(source),
+        source_size: Some(size),
+        trimmed: true,
+    }
+}
+
+fn datasets() -> Vec<(&'static str, Vec<LayoutItem>)> {
+    let mut rng = StdRng::seed_from_u64(SEED);
+    vec![
+        ("basic", basic(&mut rng)),
+        ("thin", thin(&mut rng)),
+        ("trim", trim(&mut rng)),
+        ("irregular", irregular(&mut rng)),
+    ]
+}
+
+fn packers() -> Vec<(&'static str, PackerConfig)> {
+    let base = || {
+        PackerConfig::builder()
+            .with_max_dimensions(1024, 1024)
+            .texture_padding(2)
+    };
+    vec![
+        (
+            "Skyline_MinWaste",
+            base()
+                .family(AlgorithmFamily::Skyline)
+                .skyline_heuristic(SkylineHeuristic::MinWaste)
+                .build(),
+        ),
+        (
+            "MaxRects_BestAreaFit",
+            base()
+                .family(AlgorithmFamily::MaxRects)
+                .mr_heuristic(MaxRectsHeuristic::BestAreaFit)
+                .build(),
+        ),
+        (
+            "MaxRects_Reference",
+            base()
+                .family(AlgorithmFamily::MaxRects)
+                .mr_heuristic(MaxRectsHeuristic::BestAreaFit)
+                .mr_reference(true)
+                .build(),
+        ),
+        (
+            "Guillotine_BAF_SLAS",
+            base()
+                .family(AlgorithmFamily::Guillotine)
+                .g_choice(GuillotineChoice::BestAreaFit)
+                .g_split(GuillotineSplit::SplitShorterLeftoverAxis)
+                .build(),
+        ),
+    ]
+}
+
+fn bench_offline_packers(c: &mut Criterion) {
+    let mut group = c.benchmark_group("offline_packers");
+    for (name, items) in datasets() {
+        group.throughput(Throughput::Elements(items.len() as u64));
+        for (packer, cfg) in packers() {
+            group.bench_with_input(BenchmarkId::new(packer, name), &items, |b, items| {
+                b.iter(|| black_box(pack_layout_items(items.clone(), cfg.clone()).unwrap()));
+            });
+        }
+    }
+    group.finish();
+}
+
+/// Renders a `trim` fixture: the opaque block at its source rect inside a transparent canvas.
+fn render(item: &LayoutItem) -> InputImage {
+    let (w, h) = item.source_size.unwrap_or((item.w, item.h));
+    let src = item.source.unwrap_or(Rect::new(0, 0, item.w, item.h));
+    let mut img = RgbaImage::new(w, h);
+    for y in src.y..src.y + src.h {
+        for x in src.x..src.x + src.w {
+            img.put_pixel(x, y, Rgba([200, 80, 40, 255]));
+        }
+    }
+    InputImage {
+        key: item.key.clone(),
+        image: DynamicImage::ImageRgba8(img),
+    }
+}
+
+fn bench_offline_pipeline(c: &mut Criterion) {
+    let mut group = c.benchmark_group("offline_pipeline");
+    group.sample_size(20);
+    let (_, items) = datasets().swap_remove(2);
+    let images: Vec<InputImage> = items.iter().map(render).collect();
+    group.throughput(Throughput::Elements(images.len() as u64));
+    for (packer, cfg) in packers() {
+        let cfg = PackerConfig { trim: true, ..cfg };
+        group.bench_with_input(BenchmarkId::new(packer, "trim"), &images, |b, images| {
+            b.iter_batched(
+                || {
+                    images
+                        .iter()
+                        .map(|i| InputImage {
+                            key: i.key.clone(),
+                            image: i.image.clone(),
+                        })
+                        .collect::<Vec<_>>()
+                },
+                |inputs| black_box(pack_images(inputs, cfg.clone()).unwrap()),
+                criterion::BatchSize::LargeInput,
+            );
+        });
+    }
+    group.finish();
+}
+
+/// Measures page occupancy (percent) instead of time; benches report it via `iter_custom`.
+struct Occupancy;
+
+impl Measurement for Occupancy {
+    type Intermediate = ();
+    type Value = f64;
+
+    fn start(&self) -> Self::Intermediate {}
+
+    fn end(&self, _: Self::Intermediate) -> Self::Value {
+        0.0
+    }
+
+    fn add(&self, v1: &f64, v2: &f64) -> f64 {
+        v1 + v2
+    }
+
+    fn zero(&self) -> f64 {
+        0.0
+    }
+
+    fn to_f64(&self, value: &f64) -> f64 {
+        *value
+    }
+
+    fn formatter(&self) -> &dyn ValueFormatter {
+        &PercentFormatter
+    }
+}
+
+struct PercentFormatter;
+
+impl ValueFormatter for PercentFormatter {
+    fn scale_values(&self, _typical_value: f64, _values: &mut [f64]) -> &'static str {
+        "%"
+    }
+
+    fn scale_throughputs(
+        &self,
+        _typical_value: f64,
+        _throughput: &Throughput,
+        _values: &mut [f64],
+    ) -> &'static str {
+        "%"
+    }
+
+    fn scale_for_machines(&self, _values: &mut [f64]) -> &'static str {
+        "%"
+    }
+}
+
+fn bench_offline_occupancy(c: &mut Criterion<Occupancy>) {
+    let mut group = c.benchmark_group("offline_occupancy");
+    for (name, items) in datasets() {
+        for (packer, cfg) in packers() {
+            group.bench_with_input(BenchmarkId::new(packer, name), &items, |b, items| {
+                // Criterion sizes samples by wall time, so every iteration really packs
+                b.iter_custom(|iters| {
+                    (0..iters)
+                        .map(|_| {
+                            let atlas = pack_layout_items(items.clone(), cfg.clone()).unwrap();
+                            atlas.stats().occupancy * 100.0
+                        })
+                        .sum()
+                });
+            });
+        }
+    }
+    group.finish();
+}
+
+criterion_group!(benches, bench_offline_packers, bench_offline_pipeline);
+criterion_group! {
+    name = occupancy;
+    config = Criterion::default().with_measurement(Occupancy).sample_size(10);
+    targets = bench_offline_occupancy
+}
+criterion_main!(benches, occupancy);
@@ -1,4 +1,5 @@
-use criterion::{BenchmarkId, Criterion, Throughput, black_box, criterion_group, criterion_main};
+use criterion::{BenchmarkId, Criterion, Throughput, criterion_group, criterion_main};
+use std::hint::black_box;
 use tex_packer_core::prelude::*;
 
 fn generate_textures(count: usize, min_size: u32, max_size: u32) -> Vec<(String, u32, u32)> {
@@ -120,6 +121,27 @@ fn bench_runtime_strategy(c: &mut Criterion) {
                 });
             },
         );
+
+        // Benchmark MaxRects BestShortSideFit
+        group.bench_with_input(
+            BenchmarkId::new("MaxRects_BSSF", count),
+            &textures,
+            |b, textures| {
+                b.iter(|| {
+                    let cfg = PackerConfig::builder()
+                        .with_max_dimensions(2048, 2048)
+                        .build();
+                    let mut session = AtlasSession::new(
+                        cfg,
+                        RuntimeStrategy::MaxRects(MaxRectsHeuristic::BestShortSideFit),
+                    );
+                    for (key, w, h) in textures {
+                        let _ = session.append(key.clone(), *w, *h);
+                    }
+                    black_box(session)
+                });
+            },
+        );
     }
 
     group.finish();
@@ -158,6 +180,16 @@ fn bench_append_operations(c: &mut Criterion) {
         });
     });
 
+    group.bench_function("MaxRects_single_append", |b| {
+        b.iter(|| {
+            let mut session = AtlasSession::new(
+                cfg.clone(),
+                RuntimeStrategy::MaxRects(MaxRectsHeuristic::BestShortSideFit),
+            );
+            black_box(session.append("test".into(), 64, 64))
+        });
+    });
+
     group.finish();
 }
 
@@ -232,7 +264,12 @@ fn bench_space_efficiency(c: &mut Criterion) {
     let varied_textures = generate_textures(100, 16, 128);
 
     for (name, textures) in [("uniform", &uniform_textures), ("varied", &varied_textures)] {
-        for strategy_name in ["Guillotine", "Shelf_NextFit", "Skyline_BottomLeft"] {
+        for strategy_name in [
+            "Guillotine",
+            "Shelf_NextFit",
+            "Skyline_BottomLeft",
+            "MaxRects_BSSF",
+        ] {
             group.bench_with_input(
                 BenchmarkId::new(format!("{}_{}", strategy_name, name), textures.len()),
                 textures,
@@ -248,6 +285,9 @@ fn bench_space_efficiency(c: &mut Criterion) {
                             "Skyline_BottomLeft" => {
                                 RuntimeStrategy::Skyline(SkylineHeuristic::BottomLeft)
                             }
+                            "MaxRects_BSSF" => {
+                                RuntimeStrategy::MaxRects(MaxRectsHeuristic::BestShortSideFit)
+                            }
                             _ => unreachable!(),
                         };
 
@@ -267,6 +307,56 @@ fn bench_space_efficiency(c: &mut Criterion) {
     group.finish();
 }
 
+fn bench_churn(c: &mut Criterion) {
+    let mut group = c.benchmark_group("churn");
+
+    // Glyph-cache style workload: fill, evict every third entry, refill with new sizes
+    let rounds: Vec<Vec<(String, u32, u32)>> = (0..8)
+        .map(|r| {
+            generate_textures(120, 8, 48)
+                .into_iter()
+                .map(|(k, w, h)| (format!("r{r}_{k}"), w, h))
+                .collect()
+        })
+        .collect();
+
+    for (name, strategy) in [
+        ("Guillotine", RuntimeStrategy::Guillotine),
+        (
+            "Shelf_FirstFit",
+            RuntimeStrategy::Shelf(ShelfPolicy::FirstFit),
+        ),
+        (
+            "MaxRects_BSSF",
+            RuntimeStrategy::MaxRects(MaxRectsHeuristic::BestShortSideFit),
+        ),
+    ] {
+        group.bench_with_input(
+            BenchmarkId::new(name, rounds.len()),
+            &rounds,
+            |b, rounds| {
+                b.iter(|| {
+                    let cfg = PackerConfig::builder()
+                        .with_max_dimensions(512, 512)
+                        .build();
+                    let mut session = AtlasSession::new(cfg, strategy.clone());
+                    for round in rounds {
+                        for (key, w, h) in round {
+                            let _ = session.append(key.clone(), *w, *h);
+                        }
+                        for (key, _, _) in round.iter().step_by(3) {
+                            session.evict_by_key(key);
+                        }
+                    }
+                    black_box(session.stats().num_pages)
+                });
+            },
+        );
+    }
+
+    group.finish();
+}
+
 fn bench_with_rotation(c: &mut Criterion) {
     let mut group = c.benchmark_group("with_rotation");
 
@@ -312,6 +402,7 @@ criterion_group!(
     bench_query_operations,
     bench_evict_operations,
     bench_space_efficiency,
+    bench_churn,
     bench_with_rotation,
 );
 criterion_main!(benches);
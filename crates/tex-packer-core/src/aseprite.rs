@@ -0,0 +1,607 @@
+//! Aseprite (`.ase` / `.aseprite`) reader: frames, layers, tags and slices as packable sprites.
+//!
+//! Implements the file format documented in Aseprite's `docs/ase-file-specs.md` for RGBA,
+//! grayscale and indexed sprites. Layers are composited with normal blending (other blend
+//! modes are treated as normal); tilemap layers are skipped.
+
+use std::io::Read;
+use std::str::FromStr;
+
+use crate::config::NamedOption;
+use crate::error::{Result, TexPackerError};
+use crate::model::{NinePatch, Pivot, Rect};
+use image::{Rgba, RgbaImage};
+use serde::{Deserialize, Serialize};
+
+/// How an Aseprite file is cut into sprites.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[serde(rename_all = "snake_case")]
+pub enum AsepriteSplit {
+    /// One sprite per frame (all visible layers flattened), named by tag: `walk_0`, `walk_1`,
+    /// ...; frames outside every tag are named by their index.
+    #[default]
+    Frames,
+    /// One sprite per visible layer and frame, named by the layer path (`body/arm`), plus
+    /// `_<frame>` when the file has more than one frame.
+    Layers,
+    /// One sprite per slice and frame, cut from the flattened frame and named by the slice,
+    /// plus `_<frame>` when the file has more than one frame. Slice pivots and nine-patch
+    /// centers are carried over.
+    Slices,
+}
+
+impl FromStr for AsepriteSplit {
+    type Err = ();
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "frames" | "frame" => Ok(Self::Frames),
+            "layers" | "layer" => Ok(Self::Layers),
+            "slices" | "slice" => Ok(Self::Slices),
+            _ => Err(()),
+        }
+    }
+}
+
+impl NamedOption for AsepriteSplit {
+    const NAMES: &'static [&'static str] = &["frames", "layers", "slices"];
+}
+
+/// A sprite cut out of an Aseprite file. `name` is relative to the file (empty for the only
+/// frame of an untagged single-frame file); callers usually prefix it with the file stem.
+#[derive(Debug, Clone)]
+pub struct AsepriteSprite {
+    pub name: String,
+    pub image: RgbaImage,
+    /// Frame duration, for frame and layer sprites.
+    pub duration_ms: Option<u32>,
+    /// Slice pivot, normalized to the slice bounds.
+    pub pivot: Option<Pivot>,
+    /// Slice nine-patch center as insets.
+    pub nine_patch: Option<NinePatch>,
+}
+
+#[derive(Debug, Clone)]
+pub struct AsepriteLayer {
+    pub name: String,
+    /// Visible, including all enclosing groups.
+    pub visible: bool,
+    pub opacity: u8,
+    pub is_group: bool,
+    /// Nesting depth (0 = top level).
+    pub child_level: u16,
+    /// `group/.../name` through the enclosing groups.
+    pub path: String,
+    kind: u16,
+    background: bool,
+}
+
+#[derive(Debug, Clone)]
+pub struct AsepriteTag {
+    pub name: String,
+    /// First and last frame (inclusive).
+    pub from: u16,
+    pub to: u16,
+}
+
+#[derive(Debug, Clone)]
+pub struct AsepriteSlice {
+    pub name: String,
+    /// `(frame, key)`: the key applies from `frame` until the next key.
+    pub keys: Vec<(u32, SliceKey)>,
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct SliceKey {
+    pub bounds: Rect,
+    /// Nine-patch center, relative to `bounds`.
+    pub center: Option<Rect>,
+    /// Pivot in pixels, relative to `bounds`.
+    pub pivot: Option<(i32, i32)>,
+}
+
+#[derive(Debug, Clone)]
+struct Cel {
+    layer: usize,
+    x: i32,
+    y: i32,
+    opacity: u8,
+    z_index: i16,
+    image: RgbaImage,
+}
+
+#[derive(Debug, Clone)]
+pub struct AsepriteFrame {
+    pub duration_ms: u32,
+    cels: Vec<Cel>,
+}
+
+/// A parsed Aseprite file.
+#[derive(Debug, Clone)]
+pub struct AsepriteFile {
+    pub width: u32,
+    pub height: u32,
+    pub frames: Vec<AsepriteFrame>,
+    pub layers: Vec<AsepriteLayer>,
+    pub tags: Vec<AsepriteTag>,
+    pub slices: Vec<AsepriteSlice>,
+}
+
+impl AsepriteFile {
+    /// Parses an `.ase` / `.aseprite` file.
+    pub fn parse(bytes: &[u8]) -> Result<Self> {
+        parse(bytes)
+    }
+
+    /// Frame `frame` with every visible layer composited.
+    pub fn render_frame(&self, frame: usize) -> RgbaImage {
+        self.render(frame, |l| self.layers[l].visible)
+    }
+
+    /// Only `layer` (if it is a visible image layer) of frame `frame`.
+    pub fn render_layer(&self, frame: usize, layer: usize) -> RgbaImage {
+        self.render(frame, |l| l == layer && self.layers[l].visible)
+    }
+
+    fn render(&self, frame: usize, include: impl Fn(usize) -> bool) -> RgbaImage {
+        let mut canvas = RgbaImage::new(self.width, self.height);
+        let Some(f) = self.frames.get(frame) else {
+            return canvas;
+        };
+        let mut cels: Vec<&Cel> = f
+            .cels
+            .iter()
+            .filter(|c| c.layer < self.layers.len() && include(c.layer))
+            .collect();
+        // Aseprite's draw order: layer index plus z-index, ties by z-index
+        cels.sort_by_key(|c| (c.layer as i64 + c.z_index as i64, c.z_index));
+        for cel in cels {
+            let layer = &self.layers[cel.layer];
+            if layer.is_group || layer.kind == 2 {
+                continue;
+            }
+            let opacity = cel.opacity as u32 * layer.opacity as u32 / 255;
+            draw_over(&mut canvas, &cel.image, cel.x, cel.y, opacity);
+        }
+        canvas
+    }
+
+    /// Cuts the file into sprites according to `split`.
+    pub fn sprites(&self, split: AsepriteSplit) -> Vec<AsepriteSprite> {
+        let multi = self.frames.len() > 1;
+        let suffix = |name: &str, frame: usize| match (multi, name.is_empty()) {
+            (false, _) => name.to_string(),
+            (true, true) => frame.to_string(),
+            (true, false) => format!("{name}_{frame}"),
+        };
+        let sprite = |name: String, image: RgbaImage, duration_ms: Option<u32>| AsepriteSprite {
+            name,
+            image,
+            duration_ms,
+            pivot: None,
+            nine_patch: None,
+        };
+        let mut out = Vec::new();
+        match split {
+            AsepriteSplit::Frames => {
+                let mut tagged = vec![false; self.frames.len()];
+                for tag in &self.tags {
+                    let to = (tag.to as usize).min(self.frames.len().saturating_sub(1));
+                    for (i, frame) in (tag.from as usize..=to).enumerate() {
+                        tagged[frame] = true;
+                        let name = format!("{}_{i}", tag.name);
+                        let duration = self.frames[frame].duration_ms;
+                        out.push(sprite(name, self.render_frame(frame), Some(duration)));
+                    }
+                }
+                for (frame, f) in self.frames.iter().enumerate() {
+                    if !tagged[frame] {
+                        let name = suffix("", frame);
+                        out.push(sprite(name, self.render_frame(frame), Some(f.duration_ms)));
+                    }
+                }
+            }
+            AsepriteSplit::Layers => {
+                for (l, layer) in self.layers.iter().enumerate() {
+                    if !layer.visible || layer.is_group || layer.kind == 2 {
+                        continue;
+                    }
+                    for (frame, f) in self.frames.iter().enumerate() {
+                        let name = suffix(&layer.path, frame);
+                        out.push(sprite(
+                            name,
+                            self.render_layer(frame, l),
+                            Some(f.duration_ms),
+                        ));
+                    }
+                }
+            }
+            AsepriteSplit::Slices => {
+                for frame in 0..self.frames.len() {
+                    let flat = self.render_frame(frame);
+                    for slice in &self.slices {
+                        let Some(key) = slice.key_at(frame as u32) else {
+                            continue;
+                        };
+                        let b = key.bounds;
+                        if b.w == 0 || b.h == 0 || b.x + b.w > self.width || b.y + b.h > self.height
+                        {
+                            continue;
+                        }
+                        let image = image::imageops::crop_imm(&flat, b.x, b.y, b.w, b.h).to_image();
+                        out.push(AsepriteSprite {
+                            name: suffix(&slice.name, frame),
+                            image,
+                            duration_ms: None,
+                            pivot: key.pivot.map(|(x, y)| {
+                                Pivot::new(x as f64 / b.w as f64, y as f64 / b.h as f64)
+                            }),
+                            nine_patch: key.center.map(|c| NinePatch {
+                                left: c.x,
+                                top: c.y,
+                                right: b.w.saturating_sub(c.x + c.w),
+                                bottom: b.h.saturating_sub(c.y + c.h),
+                            }),
+                        });
+                    }
+                }
+            }
+        }
+        out
+    }
+}
+
+impl AsepriteSlice {
+    /// The key in effect at `frame`.
+    pub fn key_at(&self, frame: u32) -> Option<SliceKey> {
+        self.keys
+            .iter()
+            .filter(|(f, _)| *f <= frame)
+            .max_by_key(|(f, _)| *f)
+            .map(|(_, k)| *k)
+    }
+}
+
+/// Straight-alpha "over" of `src` at (`x`, `y`) with extra `opacity` (0-255).
+fn draw_over(canvas: &mut RgbaImage, src: &RgbaImage, x: i32, y: i32, opacity: u32) {
+    let (cw, ch) = (canvas.width() as i64, canvas.height() as i64);
+    for (sx, sy, p) in src.enumerate_pixels() {
+        let (dx, dy) = (x as i64 + sx as i64, y as i64 + sy as i64);
+        if dx < 0 || dy < 0 || dx >= cw || dy >= ch {
+            continue;
+        }
+        let sa = p[3] as u32 * opacity / 255;
+        if sa == 0 {
+            continue;
+        }
+        let d = canvas.get_pixel_mut(dx as u32, dy as u32);
+        let da = d[3] as u32 * (255 - sa) / 255;
+        let oa = sa + da;
+        let mut out = [0u8; 4];
+        for c in 0..3 {
+            out[c] = ((p[c] as u32 * sa + d[c] as u32 * da + oa / 2) / oa) as u8;
+        }
+        out[3] = oa as u8;
+        *d = Rgba(out);
+    }
+}
+
+fn invalid(msg: impl Into<String>) -> TexPackerError {
+    TexPackerError::InvalidInput(format!("aseprite: {}", msg.into()))
+}
+
+/// Little-endian cursor over a byte slice.
+struct Reader<'a> {
+    data: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> Reader<'a> {
+    fn new(data: &'a [u8]) -> Self {
+        Self { data, pos: 0 }
+    }
+
+    fn bytes(&mut self, n: usize) -> Result<&'a [u8]> {
+        let end = self
+            .pos
+            .checked_add(n)
+            .filter(|&e| e <= self.data.len())
+            .ok_or_else(|| invalid("unexpected end of file"))?;
+        let b = &self.data[self.pos..end];
+        self.pos = end;
+        Ok(b)
+    }
+
+    fn skip(&mut self, n: usize) -> Result<()> {
+        self.bytes(n).map(drop)
+    }
+
+    fn u8(&mut self) -> Result<u8> {
+        Ok(self.bytes(1)?[0])
+    }
+
+    fn u16(&mut self) -> Result<u16> {
+        Ok(u16::from_le_bytes(self.bytes(2)?.try_into().unwrap()))
+    }
+
+    fn i16(&mut self) -> Result<i16> {
+        Ok(i16::from_le_bytes(self.bytes(2)?.try_into().unwrap()))
+    }
+
+    fn u32(&mut self) -> Result<u32> {
+        Ok(u32::from_le_bytes(self.bytes(4)?.try_into().unwrap()))
+    }
+
+    fn i32(&mut self) -> Result<i32> {
+        Ok(i32::from_le_bytes(self.bytes(4)?.try_into().unwrap()))
+    }
+
+    fn string(&mut self) -> Result<String> {
+        let len = self.u16()? as usize;
+        Ok(String::from_utf8_lossy(self.bytes(len)?).into_owned())
+    }
+}
+
+const FLAG_LAYER_OPACITY: u32 = 1;
+
+fn parse(bytes: &[u8]) -> Result<AsepriteFile> {
+    let mut r = Reader::new(bytes);
+    r.skip(4)?; // file size
+    if r.u16()? != 0xA5E0 {
+        return Err(invalid("not an Aseprite file (bad magic number)"));
+    }
+    let num_frames = r.u16()? as usize;
+    let width = r.u16()? as u32;
+    let height = r.u16()? as u32;
+    let depth = r.u16()?;
+    let flags = r.u32()?;
+    r.skip(2 + 4 + 4)?; // speed, reserved
+    let transparent_index = r.u8()?;
+    r.skip(3)?;
+    r.skip(2 + 1 + 1 + 2 + 2 + 2 + 2 + 84)?; // colors, pixel ratio, grid, reserved
+    if !matches!(depth, 8 | 16 | 32) {
+        return Err(invalid(format!("unsupported color depth {depth}")));
+    }
+
+    let mut palette = vec![[0u8, 0, 0, 255]; 256];
+    let mut layers: Vec<AsepriteLayer> = Vec::new();
+    let mut tags = Vec::new();
+    let mut slices = Vec::new();
+    let mut frames: Vec<AsepriteFrame> = Vec::with_capacity(num_frames);
+    for _ in 0..num_frames {
+        let frame_start = r.pos;
+        let frame_len = r.u32()? as usize;
+        if r.u16()? != 0xF1FA {
+            return Err(invalid("bad frame magic number"));
+        }
+        let old_chunks = r.u16()? as u32;
+        let duration_ms = r.u16()? as u32;
+        r.skip(2)?;
+        let new_chunks = r.u32()?;
+        let chunks = if new_chunks == 0 {
+            old_chunks
+        } else {
+            new_chunks
+        };
+        let mut cels = Vec::new();
+        for _ in 0..chunks {
+            let chunk_start = r.pos;
+            let chunk_len = r.u32()? as usize;
+            if chunk_len < 6 {
+                return Err(invalid("chunk shorter than its header"));
+            }
+            let kind = r.u16()?;
+            let mut c = Reader::new(r.bytes(chunk_len - 6)?);
+            match kind {
+                0x0004 | 0x0011 => {
+                    // Old palette: packets of (skip, count, rgb...)
+                    let packets = c.u16()?;
+                    let mut index = 0usize;
+                    for _ in 0..packets {
+                        index += c.u8()? as usize;
+                        let n = match c.u8()? {
+                            0 => 256,
+                            n => n as usize,
+                        };
+                        for _ in 0..n {
+                            let rgb = c.bytes(3)?;
+                            let scale = |v: u8| if kind == 0x0011 { v << 2 | v >> 4 } else { v };
+                            if let Some(p) = palette.get_mut(index) {
+                                *p = [scale(rgb[0]), scale(rgb[1]), scale(rgb[2]), 255];
+                            }
+                            index += 1;
+                        }
+                    }
+                }
+                0x2019 => {
+                    let size = c.u32()? as usize;
+                    let first = c.u32()? as usize;
+                    let last = c.u32()? as usize;
+                    c.skip(8)?;
+                    if palette.len() < size {
+                        palette.resize(size, [0, 0, 0, 255]);
+                    }
+                    for i in first..=last {
+                        let entry_flags = c.u16()?;
+                        let rgba = c.bytes(4)?;
+                        if let Some(p) = palette.get_mut(i) {
+                            *p = [rgba[0], rgba[1], rgba[2], rgba[3]];
+                        }
+                        if entry_flags & 1 != 0 {
+                            c.string()?;
+                        }
+                    }
+                }
+                0x2004 => {
+                    let layer_flags = c.u16()?;
+                    let layer_kind = c.u16()?;
+                    let child_level = c.u16()?;
+                    c.skip(4)?; // default width/height
+                    c.skip(2)?; // blend mode
+                    let opacity = c.u8()?;
+                    c.skip(3)?;
+                    let name = c.string()?;
+                    // Parent: the closest earlier layer one level up
+                    let parent = layers
+                        .iter()
+                        .rev()
+                        .find(|l| l.child_level + 1 == child_level);
+                    let (parent_visible, path) = match parent {
+                        Some(p) if child_level > 0 => (p.visible, format!("{}/{name}", p.path)),
+                        _ => (true, name.clone()),
+                    };
+                    layers.push(AsepriteLayer {
+                        name,
+                        visible: parent_visible && layer_flags & 1 != 0,
+                        opacity: if flags & FLAG_LAYER_OPACITY != 0 {
+                            opacity
+                        } else {
+                            255
+                        },
+                        is_group: layer_kind == 1,
+                        child_level,
+                        path,
+                        kind: layer_kind,
+                        background: layer_flags & 8 != 0,
+                    });
+                }
+                0x2005 => {
+                    let layer = c.u16()? as usize;
+                    let x = c.i16()? as i32;
+                    let y = c.i16()? as i32;
+                    let opacity = c.u8()?;
+                    let cel_kind = c.u16()?;
+                    let z_index = c.i16()?;
+                    c.skip(5)?;
+                    let background = layers.get(layer).is_some_and(|l| l.background);
+                    let to_rgba = |raw: &[u8], w: u32, h: u32| -> Result<RgbaImage> {
+                        decode_pixels(raw, w, h, depth, &palette, transparent_index, background)
+                    };
+                    let image = match cel_kind {
+                        0 => {
+                            let (w, h) = (c.u16()? as u32, c.u16()? as u32);
+                            let n = (w * h) as usize * (depth as usize / 8);
+                            Some(to_rgba(c.bytes(n)?, w, h)?)
+                        }
+                        1 => {
+                            let linked = c.u16()? as usize;
+                            frames
+                                .get(linked)
+                                .and_then(|f| f.cels.iter().find(|cel| cel.layer == layer))
+                                .map(|cel| cel.image.clone())
+                        }
+                        2 => {
+                            let (w, h) = (c.u16()? as u32, c.u16()? as u32);
+                            let compressed = &c.data[c.pos..];
+                            let mut raw = Vec::new();
+                            flate2::read::ZlibDecoder::new(compressed)
+                                .read_to_end(&mut raw)
+                                .map_err(|e| invalid(format!("cel data: {e}")))?;
+                            Some(to_rgba(&raw, w, h)?)
+                        }
+                        // Compressed tilemap
+                        _ => None,
+                    };
+                    if let Some(image) = image {
+                        cels.push(Cel {
+                            layer,
+                            x,
+                            y,
+                            opacity,
+                            z_index,
+                            image,
+                        });
+                    }
+                }
+                0x2018 => {
+                    let n = c.u16()?;
+                    c.skip(8)?;
+                    for _ in 0..n {
+                        let from = c.u16()?;
+                        let to = c.u16()?;
+                        c.skip(1 + 2 + 6 + 3 + 1)?; // direction, repeat, reserved, color
+                        tags.push(AsepriteTag {
+                            name: c.string()?,
+                            from,
+                            to,
+                        });
+                    }
+                }
+                0x2022 => {
+                    let n = c.u32()?;
+                    let slice_flags = c.u32()?;
+                    c.skip(4)?;
+                    let name = c.string()?;
+                    let mut keys = Vec::new();
+                    for _ in 0..n {
+                        let frame = c.u32()?;
+                        let (x, y) = (c.i32()?, c.i32()?);
+                        let (w, h) = (c.u32()?, c.u32()?);
+                        let center = if slice_flags & 1 != 0 {
+                            let (cx, cy) = (c.i32()?, c.i32()?);
+                            let (cw, ch) = (c.u32()?, c.u32()?);
+                            Some(Rect::new(cx.max(0) as u32, cy.max(0) as u32, cw, ch))
+                        } else {
+                            None
+                        };
+                        let pivot = if slice_flags & 2 != 0 {
+                            Some((c.i32()?, c.i32()?))
+                        } else {
+                            None
+                        };
+                        keys.push((
+                            frame,
+                            SliceKey {
+                                bounds: Rect::new(x.max(0) as u32, y.max(0) as u32, w, h),
+                                center,
+                                pivot,
+                            },
+                        ));
+                    }
+                    slices.push(AsepriteSlice { name, keys });
+                }
+                _ => {}
+            }
+            r.pos = chunk_start + chunk_len;
+        }
+        frames.push(AsepriteFrame { duration_ms, cels });
+        if frame_len > 0 {
+            r.pos = frame_start + frame_len;
+        }
+    }
+    Ok(AsepriteFile {
+        width,
+        height,
+        frames,
+        layers,
+        tags,
+        slices,
+    })
+}
+
+fn decode_pixels(
+    raw: &[u8],
+    w: u32,
+    h: u32,
+    depth: u16,
+    palette: &[[u8; 4]],
+    transparent_index: u8,
+    background: bool,
+) -> Result<RgbaImage> {
+    let bpp = depth as usize / 8;
+    let n = w as usize * h as usize;
+    if raw.len() < n * bpp {
+        return Err(invalid("cel pixel data is truncated"));
+    }
+    let mut out = Vec::with_capacity(n * 4);
+    for px in raw[..n * bpp].chunks_exact(bpp) {
+        let rgba = match depth {
+            32 => [px[0], px[1], px[2], px[3]],
+            16 => [px[0], px[0], px[0], px[1]],
+            _ if px[0] == transparent_index && !background => [0, 0, 0, 0],
+            _ => palette.get(px[0] as usize).copied().unwrap_or([0, 0, 0, 0]),
+        };
+        out.extend_from_slice(&rgba);
+    }
+    RgbaImage::from_raw(w, h, out).ok_or_else(|| invalid("cel size mismatch"))
+}
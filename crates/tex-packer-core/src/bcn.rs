@@ -0,0 +1,429 @@
+use crate::config::BcnFormat;
+use image::RgbaImage;
+
+/// A 4x4 block of RGBA texels in row-major order.
+type Block = [[u8; 4]; 16];
+
+/// BC7 interpolation weights for 4-bit indices.
+const BC7_WEIGHTS4: [u32; 16] = [0, 4, 9, 13, 17, 21, 26, 30, 34, 38, 43, 47, 51, 55, 60, 64];
+
+/// Bytes per 4x4 block for the given format.
+pub fn bcn_block_bytes(format: BcnFormat) -> usize {
+    match format {
+        BcnFormat::Bc1 => 8,
+        BcnFormat::Bc3 | BcnFormat::Bc7 => 16,
+    }
+}
+
+/// Compress an RGBA image into BCn blocks (row-major block order).
+///
+/// Edge blocks of images whose dimensions are not multiples of 4 repeat the last row/column.
+/// BC1 uses punch-through alpha (alpha < 128 becomes transparent); BC7 always uses mode 6.
+pub fn compress_bcn(rgba: &RgbaImage, format: BcnFormat) -> Vec<u8> {
+    let (w, h) = rgba.dimensions();
+    let (bw, bh) = (w.div_ceil(4), h.div_ceil(4));
+    let mut out = Vec::with_capacity(bw as usize * bh as usize * bcn_block_bytes(format));
+    for by in 0..bh {
+        for bx in 0..bw {
+            let block = fetch_block(rgba, bx * 4, by * 4);
+            match format {
+                BcnFormat::Bc1 => out.extend_from_slice(&encode_bc1(&block, true)),
+                BcnFormat::Bc3 => {
+                    out.extend_from_slice(&encode_alpha_block(&block));
+                    out.extend_from_slice(&encode_bc1(&block, false));
+                }
+                BcnFormat::Bc7 => out.extend_from_slice(&encode_bc7_mode6(&block)),
+            }
+        }
+    }
+    out
+}
+
+fn fetch_block(rgba: &RgbaImage, x0: u32, y0: u32) -> Block {
+    let (w, h) = rgba.dimensions();
+    let mut block = [[0u8; 4]; 16];
+    for (i, texel) in block.iter_mut().enumerate() {
+        let x = (x0 + i as u32 % 4).min(w - 1);
+        let y = (y0 + i as u32 / 4).min(h - 1);
+        *texel = rgba.get_pixel(x, y).0;
+    }
+    block
+}
+
+/// Endpoints spanning the pixels along their principal axis, using the first `n` channels.
+fn principal_endpoints(pixels: &[[u8; 4]], n: usize) -> ([f32; 4], [f32; 4]) {
+    let count = pixels.len() as f32;
+    let mut mean = [0f32; 4];
+    let mut lo = [255f32; 4];
+    let mut hi = [0f32; 4];
+    for p in pixels {
+        for c in 0..n {
+            let v = p[c] as f32;
+            mean[c] += v / count;
+            lo[c] = lo[c].min(v);
+            hi[c] = hi[c].max(v);
+        }
+    }
+    let mut cov = [[0f32; 4]; 4];
+    for p in pixels {
+        for i in 0..n {
+            for j in 0..n {
+                cov[i][j] += (p[i] as f32 - mean[i]) * (p[j] as f32 - mean[j]);
+            }
+        }
+    }
+    // Power iteration seeded with the bounding-box diagonal.
+    let mut axis = [0f32; 4];
+    for c in 0..n {
+        axis[c] = hi[c] - lo[c];
+    }
+    for _ in 0..8 {
+        let mut next = [0f32; 4];
+        for i in 0..n {
+            for j in 0..n {
+                next[i] += cov[i][j] * axis[j];
+            }
+        }
+        let norm = next.iter().map(|v| v * v).sum::<f32>().sqrt();
+        if norm <= f32::EPSILON {
+            break;
+        }
+        axis = next.map(|v| v / norm);
+    }
+    let norm = axis.iter().map(|v| v * v).sum::<f32>().sqrt();
+    if norm <= f32::EPSILON {
+        return (mean, mean);
+    }
+    let axis = axis.map(|v| v / norm);
+    let (mut tmin, mut tmax) = (f32::MAX, f32::MIN);
+    for p in pixels {
+        let t: f32 = (0..n).map(|c| (p[c] as f32 - mean[c]) * axis[c]).sum();
+        tmin = tmin.min(t);
+        tmax = tmax.max(t);
+    }
+    let mut e0 = [0f32; 4];
+    let mut e1 = [0f32; 4];
+    for c in 0..n {
+        e0[c] = (mean[c] + axis[c] * tmin).clamp(0.0, 255.0);
+        e1[c] = (mean[c] + axis[c] * tmax).clamp(0.0, 255.0);
+    }
+    (e0, e1)
+}
+
+fn to_565(c: [f32; 4]) -> u16 {
+    let r = (c[0] * 31.0 / 255.0).round() as u16;
+    let g = (c[1] * 63.0 / 255.0).round() as u16;
+    let b = (c[2] * 31.0 / 255.0).round() as u16;
+    (r << 11) | (g << 5) | b
+}
+
+fn from_565(v: u16) -> [i32; 3] {
+    let r = ((v >> 11) & 31) as i32;
+    let g = ((v >> 5) & 63) as i32;
+    let b = (v & 31) as i32;
+    [
+        (r << 3) | (r >> 2),
+        (g << 2) | (g >> 4),
+        (b << 3) | (b >> 2),
+    ]
+}
+
+fn rgb_error(a: [i32; 3], p: &[u8; 4]) -> i32 {
+    (0..3).map(|c| (a[c] - p[c] as i32).pow(2)).sum()
+}
+
+fn nearest(palette: &[[i32; 3]], p: &[u8; 4]) -> u32 {
+    let mut best = (0, i32::MAX);
+    for (i, c) in palette.iter().enumerate() {
+        let e = rgb_error(*c, p);
+        if e < best.1 {
+            best = (i as u32, e);
+        }
+    }
+    best.0
+}
+
+/// BC1 color block. With `punch_through`, blocks containing alpha < 128 use the 3-color mode
+/// where index 3 is transparent; otherwise the 4-color mode is used (as required inside BC3).
+fn encode_bc1(block: &Block, punch_through: bool) -> [u8; 8] {
+    let transparent = punch_through && block.iter().any(|p| p[3] < 128);
+    let opaque: Vec<[u8; 4]> = if transparent {
+        block.iter().copied().filter(|p| p[3] >= 128).collect()
+    } else {
+        block.to_vec()
+    };
+    let mut out = [0u8; 8];
+    if opaque.is_empty() {
+        // c0 == c1 selects the 3-color mode; every texel uses the transparent index.
+        out[4..].copy_from_slice(&u32::MAX.to_le_bytes());
+        return out;
+    }
+    let (e0, e1) = principal_endpoints(&opaque, 3);
+    let (mut c0, mut c1) = (to_565(e0), to_565(e1));
+    // 4-color mode needs c0 > c1, 3-color mode c0 <= c1.
+    if (transparent && c0 > c1) || (!transparent && c0 < c1) {
+        std::mem::swap(&mut c0, &mut c1);
+    }
+    let (p0, p1) = (from_565(c0), from_565(c1));
+    let mut indices = 0u32;
+    if c0 != c1 || transparent {
+        let palette: Vec<[i32; 3]> = if transparent {
+            vec![p0, p1, [0, 1, 2].map(|c| (p0[c] + p1[c]) / 2)]
+        } else {
+            vec![
+                p0,
+                p1,
+                [0, 1, 2].map(|c| (2 * p0[c] + p1[c]) / 3),
+                [0, 1, 2].map(|c| (p0[c] + 2 * p1[c]) / 3),
+            ]
+        };
+        for (i, p) in block.iter().enumerate() {
+            let idx = if transparent && p[3] < 128 {
+                3
+            } else {
+                nearest(&palette, p)
+            };
+            indices |= idx << (2 * i);
+        }
+    }
+    out[0..2].copy_from_slice(&c0.to_le_bytes());
+    out[2..4].copy_from_slice(&c1.to_le_bytes());
+    out[4..].copy_from_slice(&indices.to_le_bytes());
+    out
+}
+
+/// BC3 alpha block using the 8-value interpolation mode.
+fn encode_alpha_block(block: &Block) -> [u8; 8] {
+    let a0 = block.iter().map(|p| p[3]).max().unwrap_or(0) as i32;
+    let a1 = block.iter().map(|p| p[3]).min().unwrap_or(0) as i32;
+    let mut out = [0u8; 8];
+    out[0] = a0 as u8;
+    out[1] = a1 as u8;
+    if a0 == a1 {
+        return out;
+    }
+    let mut palette = [a0, a1, 0, 0, 0, 0, 0, 0];
+    for (i, v) in palette.iter_mut().enumerate().skip(2) {
+        *v = ((8 - i as i32) * a0 + (i as i32 - 1) * a1) / 7;
+    }
+    let mut bits = 0u64;
+    for (i, p) in block.iter().enumerate() {
+        let a = p[3] as i32;
+        let idx = (0..8).min_by_key(|&k| (palette[k] - a).abs()).unwrap_or(0);
+        bits |= (idx as u64) << (3 * i);
+    }
+    out[2..].copy_from_slice(&bits.to_le_bytes()[..6]);
+    out
+}
+
+/// Quantize an RGBA endpoint to 7 bits per channel plus a shared p-bit.
+fn quantize_bc7_endpoint(e: [f32; 4]) -> ([u32; 4], u32) {
+    let mut best = ([0u32; 4], 0u32, f32::MAX);
+    for p in 0..2u32 {
+        let mut q = [0u32; 4];
+        let mut err = 0f32;
+        for c in 0..4 {
+            q[c] = ((e[c] - p as f32) / 2.0).round().clamp(0.0, 127.0) as u32;
+            err += (((q[c] << 1) | p) as f32 - e[c]).powi(2);
+        }
+        if err < best.2 {
+            best = (q, p, err);
+        }
+    }
+    (best.0, best.1)
+}
+
+/// BC7 mode 6: one subset, RGBA endpoints (7 bits + p-bit), 4-bit indices.
+fn encode_bc7_mode6(block: &Block) -> [u8; 16] {
+    let (e0, e1) = principal_endpoints(block, 4);
+    let (mut q0, mut p0) = quantize_bc7_endpoint(e0);
+    let (mut q1, mut p1) = quantize_bc7_endpoint(e1);
+    let unpack = |q: [u32; 4], p: u32| q.map(|v| (v << 1) | p);
+    let (c0, c1) = (unpack(q0, p0), unpack(q1, p1));
+    let mut indices = [0u32; 16];
+    for (i, p) in block.iter().enumerate() {
+        let mut best = (0u32, u32::MAX);
+        for (k, &w) in BC7_WEIGHTS4.iter().enumerate() {
+            let err: u32 = (0..4)
+                .map(|c| {
+                    let v = ((64 - w) * c0[c] + w * c1[c] + 32) >> 6;
+                    (v as i32 - p[c] as i32).pow(2) as u32
+                })
+                .sum();
+            if err < best.1 {
+                best = (k as u32, err);
+            }
+        }
+        indices[i] = best.0;
+    }
+    // The anchor index drops its top bit, so it must be < 8.
+    if indices[0] >= 8 {
+        std::mem::swap(&mut q0, &mut q1);
+        std::mem::swap(&mut p0, &mut p1);
+        for idx in indices.iter_mut() {
+            *idx = 15 - *idx;
+        }
+    }
+
+    let mut bits = 0u128;
+    let mut pos = 0u32;
+    let mut put = |value: u32, width: u32| {
+        bits |= (value as u128) << pos;
+        pos += width;
+    };
+    put(1 << 6, 7);
+    for c in 0..4 {
+        put(q0[c], 7);
+        put(q1[c], 7);
+    }
+    put(p0, 1);
+    put(p1, 1);
+    put(indices[0], 3);
+    for &idx in &indices[1..] {
+        put(idx, 4);
+    }
+    bits.to_le_bytes()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use image::Rgba;
+
+    fn decode_bc1(b: &[u8], four_color: bool) -> [[i32; 4]; 16] {
+        let c0 = u16::from_le_bytes([b[0], b[1]]);
+        let c1 = u16::from_le_bytes([b[2], b[3]]);
+        let (p0, p1) = (from_565(c0), from_565(c1));
+        let with_alpha = |c: [i32; 3], a| [c[0], c[1], c[2], a];
+        let palette = if four_color || c0 > c1 {
+            [
+                with_alpha(p0, 255),
+                with_alpha(p1, 255),
+                with_alpha([0, 1, 2].map(|c| (2 * p0[c] + p1[c]) / 3), 255),
+                with_alpha([0, 1, 2].map(|c| (p0[c] + 2 * p1[c]) / 3), 255),
+            ]
+        } else {
+            [
+                with_alpha(p0, 255),
+                with_alpha(p1, 255),
+                with_alpha([0, 1, 2].map(|c| (p0[c] + p1[c]) / 2), 255),
+                [0, 0, 0, 0],
+            ]
+        };
+        let idx = u32::from_le_bytes([b[4], b[5], b[6], b[7]]);
+        std::array::from_fn(|i| palette[((idx >> (2 * i)) & 3) as usize])
+    }
+
+    fn decode_alpha(b: &[u8]) -> [i32; 16] {
+        let (a0, a1) = (b[0] as i32, b[1] as i32);
+        let mut palette = [a0, a1, 0, 0, 0, 0, 0, 0];
+        for (i, v) in palette.iter_mut().enumerate().skip(2) {
+            *v = if a0 > a1 {
+                ((8 - i as i32) * a0 + (i as i32 - 1) * a1) / 7
+            } else if i < 6 {
+                ((6 - i as i32) * a0 + (i as i32 - 1) * a1) / 5
+            } else if i == 6 {
+                0
+            } else {
+                255
+            };
+        }
+        let mut raw = [0u8; 8];
+        raw[..6].copy_from_slice(&b[2..8]);
+        let bits = u64::from_le_bytes(raw);
+        std::array::from_fn(|i| palette[((bits >> (3 * i)) & 7) as usize])
+    }
+
+    fn decode_bc7_mode6(b: &[u8]) -> [[i32; 4]; 16] {
+        let bits = u128::from_le_bytes(b.try_into().unwrap());
+        let mut pos = 0u32;
+        let mut get = |width: u32| {
+            let v = ((bits >> pos) & ((1u128 << width) - 1)) as u32;
+            pos += width;
+            v
+        };
+        assert_eq!(get(7), 1 << 6, "mode 6");
+        // Endpoints interleave per channel: r0 r1 g0 g1 b0 b1 a0 a1
+        let (mut e0, mut e1) = ([0u32; 4], [0u32; 4]);
+        for (a, b) in e0.iter_mut().zip(e1.iter_mut()) {
+            *a = get(7);
+            *b = get(7);
+        }
+        let (p0, p1) = (get(1), get(1));
+        let c0 = e0.map(|v| (v << 1) | p0);
+        let c1 = e1.map(|v| (v << 1) | p1);
+        let mut out = [[0i32; 4]; 16];
+        for (i, texel) in out.iter_mut().enumerate() {
+            let w = BC7_WEIGHTS4[get(if i == 0 { 3 } else { 4 }) as usize];
+            for c in 0..4 {
+                texel[c] = (((64 - w) * c0[c] + w * c1[c] + 32) >> 6) as i32;
+            }
+        }
+        out
+    }
+
+    fn gradient_block() -> RgbaImage {
+        let mut img = RgbaImage::new(4, 4);
+        for (x, y, p) in img.enumerate_pixels_mut() {
+            let t = (x + y * 4) as u8;
+            *p = Rgba([t * 16, 255 - t * 16, 40 + t * 8, 128 + t * 8]);
+        }
+        img
+    }
+
+    fn max_error(img: &RgbaImage, decoded: &[[i32; 4]; 16], channels: usize) -> i32 {
+        img.pixels()
+            .zip(decoded.iter())
+            .flat_map(|(p, d)| (0..channels).map(move |c| (p.0[c] as i32 - d[c]).abs()))
+            .max()
+            .unwrap()
+    }
+
+    #[test]
+    fn solid_blocks_roundtrip() {
+        let img = RgbaImage::from_pixel(4, 4, Rgba([200, 100, 50, 255]));
+        let bc1 = compress_bcn(&img, BcnFormat::Bc1);
+        assert_eq!(bc1.len(), 8);
+        assert!(max_error(&img, &decode_bc1(&bc1, false), 3) <= 4);
+        let bc7 = compress_bcn(&img, BcnFormat::Bc7);
+        assert_eq!(bc7.len(), 16);
+        assert!(max_error(&img, &decode_bc7_mode6(&bc7), 4) <= 1);
+    }
+
+    #[test]
+    fn gradient_errors_are_bounded() {
+        let img = gradient_block();
+        let bc3 = compress_bcn(&img, BcnFormat::Bc3);
+        assert_eq!(bc3.len(), 16);
+        let alpha = decode_alpha(&bc3[..8]);
+        for (p, a) in img.pixels().zip(alpha) {
+            assert!((p.0[3] as i32 - a).abs() <= 9, "alpha {} vs {}", p.0[3], a);
+        }
+        // Four palette entries over a 16-step ramp: at most about half a palette step off.
+        assert!(max_error(&img, &decode_bc1(&bc3[8..], true), 3) <= 40);
+
+        let bc7 = compress_bcn(&img, BcnFormat::Bc7);
+        assert!(max_error(&img, &decode_bc7_mode6(&bc7), 4) <= 10);
+    }
+
+    #[test]
+    fn bc1_punch_through_alpha() {
+        let mut img = RgbaImage::from_pixel(4, 4, Rgba([10, 220, 30, 255]));
+        img.put_pixel(1, 2, Rgba([0, 0, 0, 0]));
+        let bc1 = compress_bcn(&img, BcnFormat::Bc1);
+        let decoded = decode_bc1(&bc1, false);
+        assert_eq!(decoded[2 * 4 + 1][3], 0);
+        assert!(decoded.iter().filter(|d| d[3] == 255).count() == 15);
+
+        let clear = RgbaImage::new(4, 4);
+        let decoded = decode_bc1(&compress_bcn(&clear, BcnFormat::Bc1), false);
+        assert!(decoded.iter().all(|d| d[3] == 0));
+    }
+
+    #[test]
+    fn partial_blocks_repeat_edges() {
+        let img = RgbaImage::from_pixel(5, 3, Rgba([1, 2, 3, 255]));
+        assert_eq!(compress_bcn(&img, BcnFormat::Bc7).len(), 2 * 16);
+        assert_eq!(compress_bcn(&img, BcnFormat::Bc1).len(), 2 * 8);
+    }
+}
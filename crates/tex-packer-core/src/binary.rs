@@ -0,0 +1,475 @@
+//! Compact binary atlas metadata with a zero-copy reader.
+//!
+//! Meant for shipping large atlases (thousands of frames) without parsing JSON at startup: the
+//! file is a header followed by fixed-size little-endian records, so [`AtlasView`] reads fields
+//! straight out of the byte slice and [`AtlasView::find`] binary-searches a key index.
+//!
+//! Layout (version 1; all integers little-endian, offsets from the start of the buffer):
+//!
+//! | Section      | Size                | Contents |
+//! |--------------|---------------------|----------|
+//! | header       | 24                  | magic `TPAT`, version `u16`, flags `u16` (0), page count `u32`, frame count `u32`, meta offset/length `u32` |
+//! | pages        | 28 per page         | id, width, height, image offset/length, first frame, frame count (`u32`) |
+//! | frames       | 104 per frame       | key offset/length, page index, flags, frame rect, source rect, source size (`u32`), pivot (`f64` x2), id, content hash (`u64`), nine-patch (`u32` x4) |
+//! | key index    | 4 per frame         | frame indices sorted by key (byte order) |
+//! | strings      | rest                | UTF-8 keys, page image names and the `Meta` JSON |
+//!
+//! Frames are stored page by page in atlas order. Frame flags: bit 0 rotated, 1 trimmed,
+//! 2 degenerate, 3 has content hash, 4 has nine-patch. Readers reject other versions; new fields
+//! will bump the version.
+
+use crate::error::{Result, TexPackerError};
+use crate::model::{Atlas, Frame, Meta, NinePatch, Page, Pivot, Rect};
+
+/// Format version written by [`to_binary_atlas`].
+pub const BINARY_ATLAS_VERSION: u16 = 1;
+
+const MAGIC: &[u8; 4] = b"TPAT";
+const HEADER: usize = 24;
+const PAGE: usize = 28;
+const FRAME: usize = 104;
+
+const ROTATED: u32 = 1;
+const TRIMMED: u32 = 1 << 1;
+const DEGENERATE: u32 = 1 << 2;
+const HAS_HASH: u32 = 1 << 3;
+const HAS_NINE_PATCH: u32 = 1 << 4;
+
+/// Serialize `atlas` to the binary format; `page_names` are the page texture names (empty when
+/// missing).
+pub fn to_binary_atlas<K: ToString>(atlas: &Atlas<K>, page_names: &[String]) -> Vec<u8> {
+    let keys: Vec<String> = atlas
+        .pages
+        .iter()
+        .flat_map(|p| p.frames.iter().map(|f| f.key.to_string()))
+        .collect();
+    let frame_count = keys.len();
+    let strings_start = HEADER + atlas.pages.len() * PAGE + frame_count * (FRAME + 4);
+    let mut strings = Vec::new();
+    let mut intern = |s: &str| {
+        let off = (strings_start + strings.len()) as u32;
+        strings.extend_from_slice(s.as_bytes());
+        (off, s.len() as u32)
+    };
+
+    let mut pages = Vec::with_capacity(atlas.pages.len() * PAGE);
+    let mut frames = Vec::with_capacity(frame_count * FRAME);
+    let mut first = 0u32;
+    let mut key_iter = keys.iter();
+    for (i, page) in atlas.pages.iter().enumerate() {
+        let (img_off, img_len) = intern(page_names.get(i).map_or("", String::as_str));
+        for v in [
+            page.id as u32,
+            page.width,
+            page.height,
+            img_off,
+            img_len,
+            first,
+            page.frames.len() as u32,
+        ] {
+            pages.extend_from_slice(&v.to_le_bytes());
+        }
+        first += page.frames.len() as u32;
+        for f in &page.frames {
+            let (key_off, key_len) = intern(key_iter.next().expect("one key per frame"));
+            let mut flags = 0;
+            for (set, bit) in [
+                (f.rotated, ROTATED),
+                (f.trimmed, TRIMMED),
+                (f.degenerate, DEGENERATE),
+                (f.content_hash.is_some(), HAS_HASH),
+                (f.nine_patch.is_some(), HAS_NINE_PATCH),
+            ] {
+                if set {
+                    flags |= bit;
+                }
+            }
+            let nine = f.nine_patch.unwrap_or_default();
+            for v in [
+                key_off,
+                key_len,
+                i as u32,
+                flags,
+                f.frame.x,
+                f.frame.y,
+                f.frame.w,
+                f.frame.h,
+                f.source.x,
+                f.source.y,
+                f.source.w,
+                f.source.h,
+                f.source_size.0,
+                f.source_size.1,
+            ] {
+                frames.extend_from_slice(&v.to_le_bytes());
+            }
+            frames.extend_from_slice(&f.pivot.x.to_le_bytes());
+            frames.extend_from_slice(&f.pivot.y.to_le_bytes());
+            frames.extend_from_slice(&f.id.to_le_bytes());
+            frames.extend_from_slice(&f.content_hash.unwrap_or(0).to_le_bytes());
+            for v in [nine.left, nine.top, nine.right, nine.bottom] {
+                frames.extend_from_slice(&v.to_le_bytes());
+            }
+        }
+    }
+    let meta_json = serde_json::to_string(&atlas.meta).expect("Meta serializes to JSON");
+    let (meta_off, meta_len) = intern(&meta_json);
+
+    let mut index: Vec<u32> = (0..frame_count as u32).collect();
+    index.sort_by(|&a, &b| keys[a as usize].cmp(&keys[b as usize]));
+
+    let mut out = Vec::with_capacity(strings_start + strings.len());
+    out.extend_from_slice(MAGIC);
+    out.extend_from_slice(&BINARY_ATLAS_VERSION.to_le_bytes());
+    out.extend_from_slice(&0u16.to_le_bytes());
+    for v in [
+        atlas.pages.len() as u32,
+        frame_count as u32,
+        meta_off,
+        meta_len,
+    ] {
+        out.extend_from_slice(&v.to_le_bytes());
+    }
+    out.extend_from_slice(&pages);
+    out.extend_from_slice(&frames);
+    for i in index {
+        out.extend_from_slice(&i.to_le_bytes());
+    }
+    out.extend_from_slice(&strings);
+    out
+}
+
+fn invalid(msg: impl Into<String>) -> TexPackerError {
+    TexPackerError::InvalidInput(format!("binary atlas: {}", msg.into()))
+}
+
+fn u32_at(b: &[u8], at: usize) -> u32 {
+    u32::from_le_bytes(b[at..at + 4].try_into().unwrap())
+}
+
+fn u64_at(b: &[u8], at: usize) -> u64 {
+    u64::from_le_bytes(b[at..at + 8].try_into().unwrap())
+}
+
+fn f64_at(b: &[u8], at: usize) -> f64 {
+    f64::from_le_bytes(b[at..at + 8].try_into().unwrap())
+}
+
+/// Borrowed view of a binary atlas. [`AtlasView::parse`] validates the whole buffer once, so the
+/// accessors never fail and never allocate.
+#[derive(Debug, Clone, Copy)]
+pub struct AtlasView<'a> {
+    bytes: &'a [u8],
+    pages: usize,
+    frames: usize,
+}
+
+impl<'a> AtlasView<'a> {
+    /// Checks the header, record bounds, string ranges (UTF-8) and page/frame references.
+    pub fn parse(bytes: &'a [u8]) -> Result<Self> {
+        if bytes.len() < HEADER || &bytes[..4] != MAGIC {
+            return Err(invalid("missing TPAT header"));
+        }
+        let version = u16::from_le_bytes([bytes[4], bytes[5]]);
+        if version != BINARY_ATLAS_VERSION {
+            return Err(invalid(format!(
+                "unsupported version {version} (expected {BINARY_ATLAS_VERSION})"
+            )));
+        }
+        let pages = u32_at(bytes, 8) as usize;
+        let frames = u32_at(bytes, 12) as usize;
+        let tables = pages
+            .checked_mul(PAGE)
+            .zip(frames.checked_mul(FRAME + 4))
+            .and_then(|(p, f)| HEADER.checked_add(p)?.checked_add(f));
+        if tables.is_none_or(|end| end > bytes.len()) {
+            return Err(invalid("truncated page/frame tables"));
+        }
+        let view = Self {
+            bytes,
+            pages,
+            frames,
+        };
+        view.check_str(16)?;
+        serde_json::from_str::<serde_json::Value>(view.str_at(16))
+            .map_err(|e| invalid(format!("meta: {e}")))?;
+        let mut next_first = 0;
+        for i in 0..pages {
+            let at = HEADER + i * PAGE;
+            view.check_str(at + 12)?;
+            let (first, count) = (
+                u32_at(bytes, at + 20) as usize,
+                u32_at(bytes, at + 24) as usize,
+            );
+            if first != next_first || first + count > frames {
+                return Err(invalid(format!("page {i} has a bad frame range")));
+            }
+            next_first = first + count;
+        }
+        if next_first != frames {
+            return Err(invalid("frames not covered by pages"));
+        }
+        for i in 0..frames {
+            let at = view.frame_at(i);
+            view.check_str(at)?;
+            if u32_at(bytes, at + 8) as usize >= pages {
+                return Err(invalid(format!("frame {i} references a missing page")));
+            }
+        }
+        let index = view.index_at();
+        for i in 0..frames {
+            if u32_at(bytes, index + i * 4) as usize >= frames {
+                return Err(invalid("key index out of range"));
+            }
+        }
+        Ok(view)
+    }
+
+    fn frame_at(&self, i: usize) -> usize {
+        HEADER + self.pages * PAGE + i * FRAME
+    }
+
+    fn index_at(&self) -> usize {
+        HEADER + self.pages * PAGE + self.frames * FRAME
+    }
+
+    /// Validates the `(offset, length)` string reference stored at `at`.
+    fn check_str(&self, at: usize) -> Result<()> {
+        let (off, len) = (
+            u32_at(self.bytes, at) as usize,
+            u32_at(self.bytes, at + 4) as usize,
+        );
+        let s = off
+            .checked_add(len)
+            .and_then(|end| self.bytes.get(off..end))
+            .ok_or_else(|| invalid("string out of bounds"))?;
+        std::str::from_utf8(s).map_err(|_| invalid("string is not UTF-8"))?;
+        Ok(())
+    }
+
+    /// String referenced at `at`; validated by `parse`.
+    fn str_at(&self, at: usize) -> &'a str {
+        let (off, len) = (
+            u32_at(self.bytes, at) as usize,
+            u32_at(self.bytes, at + 4) as usize,
+        );
+        std::str::from_utf8(&self.bytes[off..off + len]).unwrap_or_default()
+    }
+
+    /// Format version of the buffer.
+    pub fn version(&self) -> u16 {
+        u16::from_le_bytes([self.bytes[4], self.bytes[5]])
+    }
+
+    pub fn page_count(&self) -> usize {
+        self.pages
+    }
+
+    pub fn frame_count(&self) -> usize {
+        self.frames
+    }
+
+    pub fn page(&self, i: usize) -> Option<PageView<'a>> {
+        (i < self.pages).then(|| PageView {
+            view: *self,
+            at: HEADER + i * PAGE,
+        })
+    }
+
+    pub fn pages(&self) -> impl Iterator<Item = PageView<'a>> + '_ {
+        (0..self.pages).filter_map(|i| self.page(i))
+    }
+
+    /// Frame `i` in storage order (page by page).
+    pub fn frame(&self, i: usize) -> Option<FrameView<'a>> {
+        (i < self.frames).then(|| FrameView {
+            view: *self,
+            at: self.frame_at(i),
+        })
+    }
+
+    pub fn frames(&self) -> impl Iterator<Item = FrameView<'a>> + '_ {
+        (0..self.frames).filter_map(|i| self.frame(i))
+    }
+
+    /// Looks a frame up by key (binary search over the key index).
+    pub fn find(&self, key: &str) -> Option<FrameView<'a>> {
+        let index = self.index_at();
+        let slot = |i: usize| u32_at(self.bytes, index + i * 4) as usize;
+        let (mut lo, mut hi) = (0, self.frames);
+        while lo < hi {
+            let mid = (lo + hi) / 2;
+            let frame = self.frame(slot(mid))?;
+            match frame.key().cmp(key) {
+                std::cmp::Ordering::Equal => return Some(frame),
+                std::cmp::Ordering::Less => lo = mid + 1,
+                std::cmp::Ordering::Greater => hi = mid,
+            }
+        }
+        None
+    }
+
+    /// Raw `Meta` JSON.
+    pub fn meta_json(&self) -> &'a str {
+        self.str_at(16)
+    }
+
+    /// Parses the `Meta` JSON.
+    pub fn meta(&self) -> Result<Meta> {
+        serde_json::from_str(self.meta_json()).map_err(|e| invalid(format!("meta: {e}")))
+    }
+
+    /// Copies the whole atlas into the owned model.
+    pub fn to_atlas(&self) -> Result<Atlas> {
+        let pages = self
+            .pages()
+            .map(|p| Page {
+                id: p.id(),
+                width: p.width(),
+                height: p.height(),
+                frames: p.frames().map(|f| f.to_frame()).collect(),
+            })
+            .collect();
+        Ok(Atlas {
+            pages,
+            meta: self.meta()?,
+        })
+    }
+}
+
+/// Borrowed page record.
+#[derive(Debug, Clone, Copy)]
+pub struct PageView<'a> {
+    view: AtlasView<'a>,
+    at: usize,
+}
+
+impl<'a> PageView<'a> {
+    fn u32(&self, field: usize) -> u32 {
+        u32_at(self.view.bytes, self.at + field * 4)
+    }
+
+    pub fn id(&self) -> usize {
+        self.u32(0) as usize
+    }
+
+    pub fn width(&self) -> u32 {
+        self.u32(1)
+    }
+
+    pub fn height(&self) -> u32 {
+        self.u32(2)
+    }
+
+    /// Page texture name; empty if none was recorded.
+    pub fn image(&self) -> &'a str {
+        self.view.str_at(self.at + 12)
+    }
+
+    /// This page's frames, in atlas order.
+    pub fn frames(&self) -> impl Iterator<Item = FrameView<'a>> + 'a {
+        let view = self.view;
+        let first = self.u32(5) as usize;
+        (first..first + self.u32(6) as usize).filter_map(move |i| view.frame(i))
+    }
+}
+
+/// Borrowed frame record.
+#[derive(Debug, Clone, Copy)]
+pub struct FrameView<'a> {
+    view: AtlasView<'a>,
+    at: usize,
+}
+
+impl<'a> FrameView<'a> {
+    fn u32(&self, field: usize) -> u32 {
+        u32_at(self.view.bytes, self.at + field * 4)
+    }
+
+    fn flag(&self, bit: u32) -> bool {
+        self.u32(3) & bit != 0
+    }
+
+    pub fn key(&self) -> &'a str {
+        self.view.str_at(self.at)
+    }
+
+    /// Index of the page holding the frame.
+    pub fn page(&self) -> usize {
+        self.u32(2) as usize
+    }
+
+    pub fn rotated(&self) -> bool {
+        self.flag(ROTATED)
+    }
+
+    pub fn trimmed(&self) -> bool {
+        self.flag(TRIMMED)
+    }
+
+    pub fn degenerate(&self) -> bool {
+        self.flag(DEGENERATE)
+    }
+
+    /// Placed rectangle within the page (post-rotation size).
+    pub fn frame(&self) -> Rect {
+        Rect::new(self.u32(4), self.u32(5), self.u32(6), self.u32(7))
+    }
+
+    /// Trimmed rect within the original image.
+    pub fn source(&self) -> Rect {
+        Rect::new(self.u32(8), self.u32(9), self.u32(10), self.u32(11))
+    }
+
+    pub fn source_size(&self) -> (u32, u32) {
+        (self.u32(12), self.u32(13))
+    }
+
+    pub fn pivot(&self) -> Pivot {
+        let b = self.view.bytes;
+        Pivot::new(f64_at(b, self.at + 56), f64_at(b, self.at + 64))
+    }
+
+    pub fn id(&self) -> u64 {
+        u64_at(self.view.bytes, self.at + 72)
+    }
+
+    pub fn content_hash(&self) -> Option<u64> {
+        self.flag(HAS_HASH)
+            .then(|| u64_at(self.view.bytes, self.at + 80))
+    }
+
+    pub fn nine_patch(&self) -> Option<NinePatch> {
+        self.flag(HAS_NINE_PATCH).then(|| NinePatch {
+            left: self.u32(22),
+            top: self.u32(23),
+            right: self.u32(24),
+            bottom: self.u32(25),
+        })
+    }
+
+    /// Copies the record into the owned model.
+    pub fn to_frame(&self) -> Frame {
+        Frame {
+            key: self.key().to_string(),
+            frame: self.frame(),
+            rotated: self.rotated(),
+            trimmed: self.trimmed(),
+            source: self.source(),
+            source_size: self.source_size(),
+            degenerate: self.degenerate(),
+            id: self.id(),
+            content_hash: self.content_hash(),
+            pivot: self.pivot(),
+            nine_patch: self.nine_patch(),
+            scale: None,
+        }
+    }
+}
+
+impl Atlas {
+    /// Parses the binary format written by [`to_binary_atlas`] into the owned model.
+    pub fn from_binary(bytes: &[u8]) -> Result<Atlas> {
+        AtlasView::parse(bytes)?.to_atlas()
+    }
+}
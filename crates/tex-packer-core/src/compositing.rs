@@ -1,14 +1,17 @@
-use image::{Rgba, RgbaImage};
+use crate::config::{ExtrudeMode, RotationDirection};
+use image::{ImageBuffer, Pixel, Primitive, Rgba, Rgba32FImage, RgbaImage};
 
 /// Blit a sub-rectangle from `src` into `canvas` at destination (dx, dy),
-/// optionally rotated 90° clockwise, then apply pixel extrusion around the
+/// optionally rotated 90° either way, then apply pixel extrusion around the
 /// blitted content area and optional red outlines for debugging.
 ///
 /// - (sx, sy, sw, sh): source rectangle within `src`
 /// - (dx, dy): destination top-left in `canvas` where content area begins
-/// - rotated: if true, rotate 90° CW during blit
+/// - rotation: when set, rotate 90° in that direction during blit
 /// - extrude: number of pixels to extrude around the content
+/// - extrude_mode: which content pixels fill the extruded border
 /// - outlines: if true, draw a red 1px outline around the content area
+#[allow(clippy::too_many_arguments)]
 pub fn blit_rgba(
     src: &RgbaImage,
     canvas: &mut RgbaImage,
@@ -18,22 +21,78 @@ pub fn blit_rgba(
     sy: u32,
     sw: u32,
     sh: u32,
-    rotated: bool,
+    rotation: Option<RotationDirection>,
     extrude: u32,
+    extrude_mode: ExtrudeMode,
     outlines: bool,
 ) {
+    let red = outlines.then_some(Rgba([255, 0, 0, 255]));
+    blit(
+        src,
+        canvas,
+        (dx, dy),
+        (sx, sy, sw, sh),
+        rotation,
+        (extrude, extrude_mode),
+        red,
+    );
+}
+
+/// [`blit_rgba`] for 32-bit float pages (HDR); outlines are drawn in `(1, 0, 0, 1)`.
+#[allow(clippy::too_many_arguments)]
+pub fn blit_rgba32f(
+    src: &Rgba32FImage,
+    canvas: &mut Rgba32FImage,
+    dx: u32,
+    dy: u32,
+    sx: u32,
+    sy: u32,
+    sw: u32,
+    sh: u32,
+    rotation: Option<RotationDirection>,
+    extrude: u32,
+    extrude_mode: ExtrudeMode,
+    outlines: bool,
+) {
+    let red = outlines.then_some(Rgba([1.0, 0.0, 0.0, 1.0]));
+    blit(
+        src,
+        canvas,
+        (dx, dy),
+        (sx, sy, sw, sh),
+        rotation,
+        (extrude, extrude_mode),
+        red,
+    );
+}
+
+fn blit<T: Primitive>(
+    src: &ImageBuffer<Rgba<T>, Vec<T>>,
+    canvas: &mut ImageBuffer<Rgba<T>, Vec<T>>,
+    (dx, dy): (u32, u32),
+    (sx, sy, sw, sh): (u32, u32, u32, u32),
+    rotation: Option<RotationDirection>,
+    (extrude, mode): (u32, ExtrudeMode),
+    outline: Option<Rgba<T>>,
+) where
+    Rgba<T>: Pixel<Subpixel = T>,
+{
     let (cw, ch) = canvas.dimensions();
     // destination (rendered) size may differ when rotated
-    let (rw, rh) = if rotated { (sh, sw) } else { (sw, sh) };
+    let (rw, rh) = if rotation.is_some() {
+        (sh, sw)
+    } else {
+        (sw, sh)
+    };
 
     // main blit
     for yy in 0..rh {
         for xx in 0..rw {
-            let (ix, iy) = if rotated {
-                (sx + yy, sy + (sh - 1 - xx))
-            } else {
-                (sx + xx, sy + yy)
+            let (ox, oy) = match rotation {
+                Some(dir) => dir.source_of(xx, yy, sw, sh),
+                None => (xx, yy),
             };
+            let (ix, iy) = (sx + ox, sy + oy);
             if dx + xx < cw && dy + yy < ch {
                 let px = *src.get_pixel(ix, iy);
                 canvas.put_pixel(dx + xx, dy + yy, px);
@@ -41,9 +100,8 @@ pub fn blit_rgba(
         }
     }
 
-    if outlines {
+    if let Some(red) = outline {
         // red outline on frame bounds
-        let red = Rgba([255, 0, 0, 255]);
         for xx in 0..rw {
             if dx + xx < cw && dy < ch {
                 canvas.put_pixel(dx + xx, dy, red);
@@ -64,104 +122,140 @@ pub fn blit_rgba(
         }
     }
 
-    if extrude > 0 {
-        // edges
-        for e in 1..=extrude {
-            // top row
-            if dy >= e && dy < ch {
-                for xx in 0..rw {
-                    if dx + xx < cw {
-                        let p = *canvas.get_pixel(dx + xx, dy);
-                        if dy >= e {
-                            canvas.put_pixel(dx + xx, dy - e, p);
-                        }
-                    }
+    if extrude > 0 && rw > 0 && rh > 0 {
+        // Each border pixel copies the content pixel `mode` maps it to; content reads stay
+        // inside the blitted rect, so the order of writes does not matter.
+        let e = extrude as i64;
+        let (x0, y0) = (dx as i64, dy as i64);
+        let (w, h) = (rw as i64, rh as i64);
+        for j in -e..h + e {
+            for i in -e..w + e {
+                if (0..w).contains(&i) && (0..h).contains(&j) {
+                    continue;
                 }
-            }
-            // bottom row
-            if dy + rh - 1 < ch && dy + rh - 1 + e < ch {
-                for xx in 0..rw {
-                    if dx + xx < cw {
-                        let p = *canvas.get_pixel(dx + xx, dy + rh - 1);
-                        canvas.put_pixel(dx + xx, dy + rh - 1 + e, p);
-                    }
+                let (px, py) = (x0 + i, y0 + j);
+                if px < 0 || py < 0 || px >= cw as i64 || py >= ch as i64 {
+                    continue;
                 }
-            }
-            // left col
-            if dx >= e && dx < cw {
-                for yy in 0..rh {
-                    if dy + yy < ch {
-                        let p = *canvas.get_pixel(dx, dy + yy);
-                        canvas.put_pixel(dx - e, dy + yy, p);
-                    }
-                }
-            }
-            // right col
-            if dx + rw - 1 < cw && dx + rw - 1 + e < cw {
-                for yy in 0..rh {
-                    if dy + yy < ch {
-                        let p = *canvas.get_pixel(dx + rw - 1, dy + yy);
-                        canvas.put_pixel(dx + rw - 1 + e, dy + yy, p);
-                    }
+                let (sx, sy) = (x0 + mode.map(i, w), y0 + mode.map(j, h));
+                if sx < cw as i64 && sy < ch as i64 {
+                    let p = *canvas.get_pixel(sx as u32, sy as u32);
+                    canvas.put_pixel(px as u32, py as u32, p);
                 }
             }
         }
-        // corners (copy the corner pixel) with bounds guards
-        let c00 = if dx < cw && dy < ch {
-            *canvas.get_pixel(dx, dy)
-        } else {
-            Rgba([0, 0, 0, 0])
-        };
-        let c10 = if dx + rw > 0 && dx + rw - 1 < cw && dy < ch {
-            *canvas.get_pixel(dx + rw - 1, dy)
-        } else {
-            Rgba([0, 0, 0, 0])
-        };
-        let c01 = if dx < cw && dy + rh > 0 && dy + rh - 1 < ch {
-            *canvas.get_pixel(dx, dy + rh - 1)
-        } else {
-            Rgba([0, 0, 0, 0])
-        };
-        let c11 = if dx + rw > 0 && dx + rw - 1 < cw && dy + rh > 0 && dy + rh - 1 < ch {
-            *canvas.get_pixel(dx + rw - 1, dy + rh - 1)
-        } else {
-            Rgba([0, 0, 0, 0])
-        };
-        if dx >= 1 && dy >= 1 {
-            for ex in 1..=extrude {
-                for ey in 1..=extrude {
-                    if dx >= ex && dy >= ey {
-                        canvas.put_pixel(dx - ex, dy - ey, c00);
-                    }
-                }
+    }
+}
+
+impl ExtrudeMode {
+    /// Content coordinate (in `0..n`) sampled for offset `i` from the content's origin.
+    pub(crate) fn map(self, i: i64, n: i64) -> i64 {
+        match self {
+            ExtrudeMode::Clamp => i.clamp(0, n - 1),
+            ExtrudeMode::Wrap => i.rem_euclid(n),
+            ExtrudeMode::Mirror => {
+                let m = i.rem_euclid(2 * n);
+                if m < n { m } else { 2 * n - 1 - m }
             }
         }
-        if dy >= 1 && dx + rw - 1 < cw {
-            for ex in 1..=extrude {
-                for ey in 1..=extrude {
-                    if dy >= ey && dx + rw - 1 + ex < cw {
-                        canvas.put_pixel(dx + rw - 1 + ex, dy - ey, c10);
-                    }
+    }
+}
+
+/// Alpha bleed (color dilation) inside the rectangle (x, y, w, h) of `canvas`.
+///
+/// Fully transparent pixels take the average RGB of their colored 8-neighbors, growing
+/// outward one pixel per pass for up to `radius` passes. Alpha stays 0, so the image looks
+/// the same, but filtering no longer blends in the black of empty pixels.
+pub fn alpha_bleed(canvas: &mut RgbaImage, x: u32, y: u32, w: u32, h: u32, radius: u32) {
+    let (cw, ch) = canvas.dimensions();
+    let (x1, y1) = (x.saturating_add(w).min(cw), y.saturating_add(h).min(ch));
+    if radius == 0 || x >= x1 || y >= y1 {
+        return;
+    }
+    let (rw, rh) = ((x1 - x) as usize, (y1 - y) as usize);
+    let mut colored: Vec<bool> = (0..rw * rh)
+        .map(|i| canvas.get_pixel(x + (i % rw) as u32, y + (i / rw) as u32)[3] > 0)
+        .collect();
+    for _ in 0..radius {
+        let mut updates = Vec::new();
+        for j in 0..rh {
+            for i in 0..rw {
+                if colored[j * rw + i] {
+                    continue;
                 }
-            }
-        }
-        if dx >= 1 && dy + rh - 1 < ch {
-            for ex in 1..=extrude {
-                for ey in 1..=extrude {
-                    if dx >= ex && dy + rh - 1 + ey < ch {
-                        canvas.put_pixel(dx - ex, dy + rh - 1 + ey, c01);
+                let mut sum = [0u32; 3];
+                let mut n = 0u32;
+                for nj in j.saturating_sub(1)..(j + 2).min(rh) {
+                    for ni in i.saturating_sub(1)..(i + 2).min(rw) {
+                        if colored[nj * rw + ni] {
+                            let p = canvas.get_pixel(x + ni as u32, y + nj as u32);
+                            for c in 0..3 {
+                                sum[c] += p[c] as u32;
+                            }
+                            n += 1;
+                        }
                     }
                 }
-            }
-        }
-        if dx + rw - 1 < cw && dy + rh - 1 < ch {
-            for ex in 1..=extrude {
-                for ey in 1..=extrude {
-                    if dx + rw - 1 + ex < cw && dy + rh - 1 + ey < ch {
-                        canvas.put_pixel(dx + rw - 1 + ex, dy + rh - 1 + ey, c11);
-                    }
+                if n > 0 {
+                    updates.push((i, j, sum.map(|v| (v / n) as u8)));
                 }
             }
         }
+        if updates.is_empty() {
+            break;
+        }
+        for (i, j, [r, g, b]) in updates {
+            canvas.put_pixel(x + i as u32, y + j as u32, Rgba([r, g, b, 0]));
+            colored[j * rw + i] = true;
+        }
+    }
+}
+
+/// Composites every pixel of `canvas` over the solid `color` (straight alpha "over"), so empty
+/// areas take `color` and semi-transparent pixels blend into it.
+pub fn matte(canvas: &mut RgbaImage, color: [u8; 4]) {
+    for p in canvas.pixels_mut() {
+        *p = Rgba(matte_pixel(p.0, color));
+    }
+}
+
+/// [`matte`] for 32-bit float pages; `color` is scaled to 0.0-1.0 and HDR values above 1.0
+/// are kept.
+pub fn matte_rgba32f(canvas: &mut Rgba32FImage, color: [u8; 4]) {
+    let bg = color.map(|c| c as f32 / 255.0);
+    for p in canvas.pixels_mut() {
+        let sa = p[3].clamp(0.0, 1.0);
+        if sa >= 1.0 {
+            continue;
+        }
+        let ba = bg[3] * (1.0 - sa);
+        let oa = sa + ba;
+        if oa == 0.0 {
+            *p = Rgba([bg[0], bg[1], bg[2], 0.0]);
+            continue;
+        }
+        for c in 0..3 {
+            p[c] = (p[c] * sa + bg[c] * ba) / oa;
+        }
+        p[3] = oa;
+    }
+}
+
+fn matte_pixel(src: [u8; 4], bg: [u8; 4]) -> [u8; 4] {
+    let sa = src[3] as u32;
+    if sa == 255 {
+        return src;
+    }
+    // Alpha of the result, scaled by 255: sa + ba * (1 - sa)
+    let ba = bg[3] as u32 * (255 - sa) / 255;
+    let oa = sa + ba;
+    if oa == 0 {
+        return [bg[0], bg[1], bg[2], 0];
+    }
+    let mut out = [0u8; 4];
+    for c in 0..3 {
+        out[c] = ((src[c] as u32 * sa + bg[c] as u32 * ba + oa / 2) / oa) as u8;
     }
+    out[3] = oa as u8;
+    out
 }
@@ -1,13 +1,20 @@
+use crate::model::{Animation, NinePatch, Page, Pivot};
+use crate::packer::{Packer, PackerFactory};
+use image::RgbaImage;
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 use std::str::FromStr;
+use std::sync::Arc;
 
 /// Algorithm families and packing configuration.
 /// Key notes:
-///   - `family` selects Skyline/MaxRects/Guillotine/Auto
+///   - `family` selects Skyline/MaxRects/Guillotine/Shelf/Grid/Auto
 ///   - `mr_reference` toggles reference-accurate MaxRects split/prune (SplitFreeNode), improving packing on large sets at higher CPU cost
+///   - `mr_global` switches MaxRects to global best-first insertion (slower, denser on mixed sizes)
 ///   - `time_budget_ms` and `parallel` affect Auto portfolio evaluation
 ///     Top-level algorithm families.
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[serde(rename_all = "lowercase")]
 pub enum AlgorithmFamily {
     /// Skyline data structure (BL/MW; fast and good baseline). Optional waste-map recovery.
@@ -16,8 +23,18 @@ pub enum AlgorithmFamily {
     MaxRects,
     /// Guillotine splitting (flexible choice/split; competitive; useful in waste-map too).
     Guillotine,
+    /// Shelf/strip packing (next/first/best-height fit). Fastest; row-aligned layouts that suit
+    /// uniform-height sprites such as font glyphs, best with `SortOrder::HeightDesc`.
+    Shelf,
+    /// Uniform cells of `grid_cell_width` x `grid_cell_height` (auto-derived from the largest
+    /// sprite when 0), filled row-major. Tilesheet-style atlases with predictable UVs.
+    Grid,
     /// Try a small portfolio of candidates and pick the best result (pages, then total area).
     Auto,
+    /// A user-supplied [`Packer`](crate::packer::Packer) built by `PackerConfig::custom_packer`,
+    /// run through the regular pipeline (trimming, paging, compositing). Never part of the
+    /// `Auto` portfolio.
+    Custom,
 }
 
 impl FromStr for AlgorithmFamily {
@@ -27,7 +44,10 @@ impl FromStr for AlgorithmFamily {
             "skyline" => Ok(Self::Skyline),
             "maxrects" => Ok(Self::MaxRects),
             "guillotine" => Ok(Self::Guillotine),
+            "shelf" => Ok(Self::Shelf),
+            "grid" => Ok(Self::Grid),
             "auto" => Ok(Self::Auto),
+            "custom" => Ok(Self::Custom),
             _ => Err(()),
         }
     }
@@ -35,6 +55,7 @@ impl FromStr for AlgorithmFamily {
 
 /// MaxRects placement heuristics.
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[serde(rename_all = "lowercase")]
 pub enum MaxRectsHeuristic {
     BestAreaFit,
@@ -60,10 +81,32 @@ impl FromStr for MaxRectsHeuristic {
 
 /// Skyline placement heuristics.
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[serde(rename_all = "lowercase")]
 pub enum SkylineHeuristic {
     BottomLeft,
     MinWaste,
+    /// `BottomLeft` plus a waste map (Jylänki's BL+WM): gaps left under the skyline are
+    /// tracked as free rectangles and filled before the skyline is searched.
+    BottomLeftWm,
+    /// `MinWaste` plus a waste map (Jylänki's MW+WM).
+    MinWasteWm,
+}
+
+impl SkylineHeuristic {
+    /// Whether this variant keeps a waste map (`PackerConfig::use_waste_map` forces one for
+    /// every variant).
+    pub fn uses_waste_map(&self) -> bool {
+        matches!(self, Self::BottomLeftWm | Self::MinWasteWm)
+    }
+
+    /// Skyline placement rule without the waste map.
+    pub fn base(&self) -> Self {
+        match self {
+            Self::BottomLeft | Self::BottomLeftWm => Self::BottomLeft,
+            Self::MinWaste | Self::MinWasteWm => Self::MinWaste,
+        }
+    }
 }
 
 impl FromStr for SkylineHeuristic {
@@ -72,6 +115,34 @@ impl FromStr for SkylineHeuristic {
         match s.to_ascii_lowercase().as_str() {
             "bl" | "bottomleft" => Ok(Self::BottomLeft),
             "minwaste" | "mw" => Ok(Self::MinWaste),
+            "bl_wm" | "bl+wm" | "bottomleftwm" => Ok(Self::BottomLeftWm),
+            "minwaste_wm" | "mw_wm" | "mw+wm" | "minwastewm" => Ok(Self::MinWasteWm),
+            _ => Err(()),
+        }
+    }
+}
+
+/// Shelf selection for the `Shelf` family.
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[serde(rename_all = "snake_case")]
+pub enum ShelfHeuristic {
+    /// Only the newest shelf is open; a rect that does not fit closes it.
+    NextFit,
+    /// First shelf (top to bottom) with room.
+    #[default]
+    FirstFit,
+    /// Shelf whose height exceeds the rect's by the least.
+    BestHeightFit,
+}
+
+impl FromStr for ShelfHeuristic {
+    type Err = ();
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "nf" | "next_fit" | "nextfit" => Ok(Self::NextFit),
+            "ff" | "first_fit" | "firstfit" => Ok(Self::FirstFit),
+            "bhf" | "best_height_fit" | "bestheightfit" => Ok(Self::BestHeightFit),
             _ => Err(()),
         }
     }
@@ -79,6 +150,7 @@ impl FromStr for SkylineHeuristic {
 
 /// Guillotine free-rect choice heuristics.
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[serde(rename_all = "lowercase")]
 pub enum GuillotineChoice {
     BestAreaFit,
@@ -106,6 +178,7 @@ impl FromStr for GuillotineChoice {
 
 /// Guillotine split axis heuristics.
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[serde(rename_all = "lowercase")]
 pub enum GuillotineSplit {
     SplitShorterLeftoverAxis,
@@ -133,10 +206,16 @@ impl FromStr for GuillotineSplit {
 
 /// Auto presets.
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[serde(rename_all = "lowercase")]
 pub enum AutoMode {
     Fast,
     Quality,
+    /// Sweep every family x heuristic x sort order x rotation setting (within the time budget).
+    Exhaustive,
+    /// Simulated annealing over input order and per-item rotation for MaxRects, minimizing
+    /// total page area (bounded by `time_budget_ms` when set).
+    Anneal,
 }
 
 impl FromStr for AutoMode {
@@ -145,6 +224,8 @@ impl FromStr for AutoMode {
         match s.to_ascii_lowercase().as_str() {
             "fast" => Ok(Self::Fast),
             "quality" => Ok(Self::Quality),
+            "exhaustive" => Ok(Self::Exhaustive),
+            "anneal" => Ok(Self::Anneal),
             _ => Err(()),
         }
     }
@@ -152,6 +233,7 @@ impl FromStr for AutoMode {
 
 /// Sorting orders for deterministic packing.
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[serde(rename_all = "snake_case")]
 pub enum SortOrder {
     AreaDesc,
@@ -177,35 +259,241 @@ impl FromStr for SortOrder {
     }
 }
 
+/// One entry of a user-specified `Auto` portfolio ([`PackerConfig::auto_candidates`]): a concrete
+/// family plus optional overrides; unset fields keep the values of the packed config.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct AutoCandidate {
+    pub family: AlgorithmFamily,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub mr_heuristic: Option<MaxRectsHeuristic>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub mr_reference: Option<bool>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub mr_global: Option<bool>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub skyline_heuristic: Option<SkylineHeuristic>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub g_choice: Option<GuillotineChoice>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub g_split: Option<GuillotineSplit>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub shelf_heuristic: Option<ShelfHeuristic>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sort_order: Option<SortOrder>,
+    /// `Some(false)` packs without rotation; rotation is never enabled where the config
+    /// forbids it.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub allow_rotation: Option<bool>,
+}
+
+impl AutoCandidate {
+    /// A candidate of `family` that keeps every other setting.
+    pub fn new(family: AlgorithmFamily) -> Self {
+        Self {
+            family,
+            mr_heuristic: None,
+            mr_reference: None,
+            mr_global: None,
+            skyline_heuristic: None,
+            g_choice: None,
+            g_split: None,
+            shelf_heuristic: None,
+            sort_order: None,
+            allow_rotation: None,
+        }
+    }
+
+    /// `base` with this candidate's family and overrides applied.
+    pub fn apply(&self, base: &PackerConfig) -> PackerConfig {
+        let mut cfg = base.clone();
+        cfg.family = self.family.clone();
+        if let Some(v) = &self.mr_heuristic {
+            cfg.mr_heuristic = v.clone();
+        }
+        if let Some(v) = self.mr_reference {
+            cfg.mr_reference = v;
+        }
+        if let Some(v) = self.mr_global {
+            cfg.mr_global = v;
+        }
+        if let Some(v) = &self.skyline_heuristic {
+            cfg.skyline_heuristic = v.clone();
+        }
+        if let Some(v) = &self.g_choice {
+            cfg.g_choice = v.clone();
+        }
+        if let Some(v) = &self.g_split {
+            cfg.g_split = v.clone();
+        }
+        if let Some(v) = self.shelf_heuristic {
+            cfg.shelf_heuristic = v;
+        }
+        if let Some(v) = &self.sort_order {
+            cfg.sort_order = v.clone();
+        }
+        if let Some(v) = self.allow_rotation {
+            cfg.allow_rotation = cfg.allow_rotation && v;
+        }
+        cfg
+    }
+}
+
+impl FromStr for AutoCandidate {
+    type Err = ();
+    /// Accepts `family[:heuristic][@sort_order]`, e.g. `maxrects:bssf`, `skyline:minwaste`,
+    /// `guillotine:baf:slas` or `shelf:bhf@height_desc`. The heuristic is the family's own
+    /// (`mr_heuristic`, `skyline_heuristic`, `g_choice` then `g_split`, `shelf_heuristic`).
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (algo, sort) = match s.trim().split_once('@') {
+            Some((algo, sort)) => (algo, Some(sort.trim().parse()?)),
+            None => (s.trim(), None),
+        };
+        let mut parts = algo.split(':').map(str::trim);
+        let mut c = Self::new(parts.next().unwrap_or_default().parse()?);
+        c.sort_order = sort;
+        let (h, split) = (parts.next(), parts.next());
+        match (&c.family, h) {
+            (_, None) => {}
+            (AlgorithmFamily::MaxRects, Some(h)) => c.mr_heuristic = Some(h.parse()?),
+            (AlgorithmFamily::Skyline, Some(h)) => c.skyline_heuristic = Some(h.parse()?),
+            (AlgorithmFamily::Guillotine, Some(h)) => {
+                c.g_choice = Some(h.parse()?);
+                c.g_split = split.map(str::parse).transpose()?;
+            }
+            (AlgorithmFamily::Shelf, Some(h)) => c.shelf_heuristic = Some(h.parse()?),
+            _ => return Err(()),
+        }
+        if parts.next().is_some() || (split.is_some() && c.g_split.is_none()) {
+            return Err(());
+        }
+        Ok(c)
+    }
+}
+
+/// How `Auto` ranks candidate layouts ([`PackerConfig::auto_objective`]). Lower ranks win;
+/// ties fall back to fewer pages, then smaller total page area, then portfolio order.
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[serde(rename_all = "snake_case")]
+pub enum AutoObjective {
+    /// Fewest pages, then smallest total page area.
+    #[default]
+    Pages,
+    /// Smallest total page area (GPU memory), regardless of the page count.
+    Area,
+    /// Highest occupancy: frame area over total page area.
+    Occupancy,
+    /// Smallest largest page side, for targets with a texture size limit.
+    MaxDimension,
+    /// Weighted sum of normalized terms: pages, total area in units of `max_width * max_height`,
+    /// largest page side over the larger of `max_width`/`max_height`, minus occupancy (0..1).
+    Weighted {
+        pages: f64,
+        area: f64,
+        max_dimension: f64,
+        occupancy: f64,
+    },
+}
+
+impl FromStr for AutoObjective {
+    type Err = ();
+    /// Accepts a name or `weighted:PAGES,AREA,MAX_DIMENSION,OCCUPANCY`.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim().to_ascii_lowercase();
+        if let Some(weights) = s.strip_prefix("weighted:") {
+            let w: Vec<f64> = weights
+                .split(',')
+                .map(|p| p.trim().parse().map_err(|_| ()))
+                .collect::<Result<_, _>>()?;
+            let [pages, area, max_dimension, occupancy] = w[..] else {
+                return Err(());
+            };
+            return Ok(Self::Weighted {
+                pages,
+                area,
+                max_dimension,
+                occupancy,
+            });
+        }
+        match s.as_str() {
+            "pages" => Ok(Self::Pages),
+            "area" => Ok(Self::Area),
+            "occupancy" => Ok(Self::Occupancy),
+            "max_dimension" | "max-dimension" | "maxdim" => Ok(Self::MaxDimension),
+            _ => Err(()),
+        }
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[serde(remote = "Self")]
 pub struct PackerConfig {
     /// Maximum page width in pixels.
     pub max_width: u32,
     /// Maximum page height in pixels.
     pub max_height: u32,
+    /// Minimum page width in pixels (pages grow to at least this size). 0 disables.
+    #[serde(default)]
+    pub min_width: u32,
+    /// Minimum page height in pixels. 0 disables.
+    #[serde(default)]
+    pub min_height: u32,
     /// Allow 90° rotations for placements where beneficial.
     pub allow_rotation: bool,
-    /// Force final page dimensions to be exactly max_width/max_height.
-    pub force_max_dimensions: bool,
+    /// Which way rotated frames are turned on the page (clockwise by default). Recorded in
+    /// `Meta::rotation_direction`; exporters whose format fixes the direction reject the other.
+    #[serde(default)]
+    pub rotation_direction: RotationDirection,
 
     /// Pixels around entire page border.
     pub border_padding: u32,
     /// Pixels between frames.
     pub texture_padding: u32,
+    /// Per-edge spacing around each frame; overrides `texture_padding` when set (see
+    /// [`PackerConfig::sprite_padding`]).
+    #[serde(default)]
+    pub padding: Option<Padding>,
     /// Extrude edge pixels of each frame (for sampling safety).
     pub texture_extrusion: u32,
+    /// Which pixels fill the extruded border: repeated edges, or the opposite/mirrored edge for
+    /// tileable textures.
+    #[serde(default)]
+    pub extrude_mode: ExtrudeMode,
+    /// Place every frame at x/y multiples of this (e.g. 4 for block-compressed pages); 0 or 1
+    /// leaves positions unconstrained.
+    #[serde(default)]
+    pub align: u32,
+    /// With `align`, also round frame sizes up to multiples of it; frames grow right/down,
+    /// extending the sprite's canvas with transparent pixels where needed.
+    #[serde(default)]
+    pub align_size: bool,
 
     /// Trim transparent borders (alpha <= trim_threshold).
     pub trim: bool,
     pub trim_threshold: u8,
+    /// Margin, minimum size and alignment applied to trimmed rects.
+    #[serde(default)]
+    pub trim_options: TrimOptions,
     /// Draw red outlines on output pages (debug).
     pub texture_outlines: bool,
 
-    /// Resize output page to power-of-two.
-    pub power_of_two: bool,
     /// Force output page to be square (max(width,height)).
     pub square: bool,
-    /// Use waste map in Skyline to recover gaps
+    /// How page dimensions follow from the frames' extent (tight crop, power of two, fixed or an
+    /// allowed list). Configs written before this field existed spell it with the
+    /// `power_of_two`, `force_max_dimensions` and `allowed_sizes` keys, which deserialize into
+    /// the matching mode (see [`PageSizeMode::from_legacy_flags`]).
+    #[serde(default)]
+    pub page_size: PageSizeMode,
+    /// After a pack that fits on one page, binary-search smaller page sizes (honoring
+    /// `page_size`, `square` and the minimum size) and keep the smallest one everything still
+    /// fits on, re-packing per candidate. Ignored with `PageSizeMode::Fixed`.
+    #[serde(default)]
+    pub minimize_page_size: bool,
+    /// Use waste map in Skyline to recover gaps, whatever `skyline_heuristic` is (the `*Wm`
+    /// heuristics enable it on their own)
     pub use_waste_map: bool,
 
     // algorithm selection
@@ -219,8 +507,39 @@ pub struct PackerConfig {
     pub g_choice: GuillotineChoice,
     #[serde(default = "default_g_split")]
     pub g_split: GuillotineSplit,
+    /// Guillotine rectangle merge: re-join adjacent free rects after every split so late large
+    /// rects still find room. On by default; turning it off gives the plain split-only packer.
+    #[serde(default = "default_g_merge")]
+    pub g_merge: bool,
+    #[serde(default)]
+    pub shelf_heuristic: ShelfHeuristic,
+    /// Cell size of the `Grid` family, excluding texture padding/extrusion (0 = the largest
+    /// sprite's width/height). Sprites larger than a cell are rejected.
+    #[serde(default)]
+    pub grid_cell_width: u32,
+    #[serde(default)]
+    pub grid_cell_height: u32,
     #[serde(default = "default_auto_mode")]
     pub auto_mode: AutoMode,
+    /// Explicit `Auto` portfolio, evaluated in order like the presets (time budget, parallelism
+    /// and tie-breaking included). When non-empty it replaces the `auto_mode` candidates;
+    /// `pack_images_iter` uses the first entry.
+    #[serde(default)]
+    pub auto_candidates: Vec<AutoCandidate>,
+    /// After the `Auto` portfolio, spend the rest of `time_budget_ms` hill-climbing from the
+    /// winner: other sort orders, rotation on/off and, for MaxRects, global insertion and
+    /// reference splits; the best-ranked layout is kept. Without a budget (or with
+    /// `deterministic`) the search runs until no variant improves. Not used by `Anneal`.
+    #[serde(default)]
+    pub auto_refine: bool,
+    /// Ranking of `Auto` candidates (default: fewest pages, then smallest area).
+    #[serde(default)]
+    pub auto_objective: AutoObjective,
+    /// Custom `Auto` ranking that overrides `auto_objective`: called with each candidate's
+    /// pages, lower wins. Not serialized.
+    #[serde(skip)]
+    #[cfg_attr(feature = "schemars", schemars(skip))]
+    pub auto_score: Option<AutoScore>,
     #[serde(default = "default_sort_order")]
     pub sort_order: SortOrder,
 
@@ -228,14 +547,55 @@ pub struct PackerConfig {
     /// Optional time budget for auto portfolio (milliseconds). None or 0 disables.
     #[serde(default)]
     pub time_budget_ms: Option<u64>,
-    /// Enable parallel candidate evaluation when feature "parallel" is on.
+    /// Enable parallel input preparation, candidate evaluation and page compositing when feature
+    /// "parallel" is on.
     #[serde(default = "default_parallel")]
     pub parallel: bool,
+    /// Guarantee identical Auto results across runs and thread counts: every candidate is
+    /// evaluated regardless of `time_budget_ms` (Anneal runs its full iteration count) and ties
+    /// resolve to the earliest candidate in portfolio order.
+    #[serde(default)]
+    pub deterministic: bool,
+    /// Composite each page into a memory-mapped temporary file instead of RAM (see
+    /// `OutputPage::scratch`) and stream PNG rows when writing. Meant for 8192x8192+ pages.
+    #[serde(default)]
+    pub low_memory: bool,
+    /// Rewrites input keys (strip folders/extension, regex renames, case) before packing.
+    #[serde(default)]
+    pub key_transform: KeyTransform,
+    /// What to do when two inputs end up with the same key (after `key_transform`).
+    #[serde(default)]
+    pub duplicate_keys: DuplicateKeyPolicy,
+
+    /// How pivots are chosen for frames without an entry in `pivots`.
+    #[serde(default)]
+    pub pivot_mode: PivotMode,
+    /// Pivot for frames without an entry in `pivots` (`PivotMode::Manual`, and the fallback for
+    /// fully transparent sprites in the detecting modes).
+    #[serde(default)]
+    pub default_pivot: Pivot,
+    /// Per-frame pivots keyed by frame key (after `key_transform`).
+    #[serde(default)]
+    pub pivots: BTreeMap<String, Pivot>,
+
+    /// Per-sprite overrides keyed by frame key (after `key_transform`), e.g. from a manifest.
+    #[serde(default)]
+    pub sprites: BTreeMap<String, SpriteOptions>,
+
+    /// Frame sequences (e.g. expanded animated GIFs) copied to `Meta::animations`; keys are
+    /// frame keys after `key_transform`.
+    #[serde(default)]
+    pub animations: Vec<Animation>,
 
     /// Use reference-accurate MaxRects split/prune (SplitFreeNode + staged prune).
     /// When false, uses a simpler but correct split/prune that may create more intermediate free rects.
     #[serde(default)]
     pub mr_reference: bool,
+    /// Global MaxRects insertion: every step scores all remaining items against the free list
+    /// and places the best pair, instead of visiting items in `sort_order`. Denser on
+    /// heterogeneous sets, but quadratic in the item count.
+    #[serde(default)]
+    pub mr_global: bool,
 
     /// Auto-mode: enable mr_reference when time budget >= this (ms). None => use default heuristic.
     #[serde(default)]
@@ -247,6 +607,124 @@ pub struct PackerConfig {
     /// Policy for fully transparent images (effective when `trim=true`).
     #[serde(default = "default_transparent_policy")]
     pub transparent_policy: TransparentPolicy,
+
+    /// What `pack_images` / `pack_images_iter` and the layout-only APIs do with inputs too large
+    /// for an empty page. Anything but `Error` returns a partial result listing the affected
+    /// inputs (`PackOutput::skipped` / `PackOutput::scaled`, or the same lists of
+    /// `LayoutOutput`).
+    #[serde(default)]
+    pub on_unplaceable: OnUnplaceable,
+
+    /// Maximum number of pages; packing fails with `TooManyPages` when more would be needed.
+    #[serde(default)]
+    pub max_pages: Option<usize>,
+    /// How inputs are grouped onto pages. Groups never share a page, so each group occupies
+    /// a contiguous page range.
+    #[serde(default = "default_page_grouping")]
+    pub page_grouping: PageGrouping,
+    /// Maximum pages per group (`Some(1)` forces every group onto a single page).
+    #[serde(default)]
+    pub group_max_pages: Option<usize>,
+    /// How frames are spread over the pages of a multi-page group.
+    #[serde(default)]
+    pub page_balance: PageBalance,
+    /// Order of the frames within each page, as every exporter writes them. `Packing` (the
+    /// default) keeps placement order, which shifts with the algorithm and sort order.
+    #[serde(default)]
+    pub frame_order: FrameOrder,
+
+    /// Alpha bleed radius in pixels: fully transparent pixels within each frame (and its
+    /// extrusion) take the color of nearby opaque pixels, avoiding dark halos under
+    /// bilinear filtering and mipmapping. Alpha is unchanged. None or 0 disables.
+    #[serde(default)]
+    pub alpha_bleed: Option<u32>,
+    /// Mip levels the pages will be sampled with, level 0 included. Widens the gap between
+    /// frames to [`mip_safe_padding`](crate::mipmap::mip_safe_padding) so neither downsampling
+    /// nor bilinear filtering at the smallest level mixes neighbors. Recorded as `Meta::mip`.
+    #[serde(default)]
+    pub mip_levels: Option<u32>,
+    /// Solid page background (RGBA). Pages are filled with it and semi-transparent pixels are
+    /// matte-composited over it, for formats or engines without alpha. None keeps pages
+    /// transparent. Also reported as `Meta::background_color`.
+    #[serde(default)]
+    pub background_color: Option<[u8; 4]>,
+
+    /// Output page image format. Block-compressed formats (`Dds`) round page sizes up to multiples of 4.
+    #[serde(default = "default_page_format")]
+    pub page_format: PageFormat,
+    /// Block compression used when `page_format` is `Dds`.
+    #[serde(default = "default_bcn_format")]
+    pub bcn_format: BcnFormat,
+    /// Basis Universal codec used when `page_format` is `Ktx2`.
+    #[serde(default)]
+    pub basis_codec: BasisCodec,
+    /// Quality settings of `Jpeg`, `Webp` and `Avif` pages.
+    #[serde(default)]
+    pub page_encoding: PageEncoding,
+
+    /// Convert every input to a signed distance field before trimming and packing (font and
+    /// icon atlases). Applies to `pack_images` / `pack_images_iter`; requires the `sdf`
+    /// feature. Recorded as `Meta::sdf`.
+    #[serde(default)]
+    pub sdf: Option<SdfOptions>,
+
+    /// Called with every composited page of `pack_images`, `pack_images_iter` and
+    /// `pack_image_sets` (after `background_color`), e.g. to tint, add noise or watermark.
+    /// The page size must stay the same. Not serialized; HDR pages are not passed.
+    #[serde(skip)]
+    #[cfg_attr(feature = "schemars", schemars(skip))]
+    pub page_postprocess: Option<PagePostprocess>,
+    /// Builds the packer of every page when `family` is `AlgorithmFamily::Custom`. Not
+    /// serialized.
+    #[serde(skip)]
+    #[cfg_attr(feature = "schemars", schemars(skip))]
+    pub custom_packer: Option<PackerFactory>,
+}
+
+type PagePostprocessFn = dyn Fn(&mut RgbaImage, &Page) + Send + Sync;
+
+/// Page hook of [`PackerConfig::page_postprocess`]. Clones share the closure, which may run
+/// on rayon worker threads when pages are composited in parallel.
+#[derive(Clone)]
+pub struct PagePostprocess(Arc<PagePostprocessFn>);
+
+impl PagePostprocess {
+    pub fn new(f: impl Fn(&mut RgbaImage, &Page) + Send + Sync + 'static) -> Self {
+        Self(Arc::new(f))
+    }
+    /// Runs the hook on `rgba`, the pixels of `page`.
+    pub fn apply(&self, rgba: &mut RgbaImage, page: &Page) {
+        (self.0)(rgba, page)
+    }
+}
+
+impl std::fmt::Debug for PagePostprocess {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("PagePostprocess(..)")
+    }
+}
+
+type AutoScoreFn = dyn Fn(&[Page]) -> f64 + Send + Sync;
+
+/// Candidate score of [`PackerConfig::auto_score`]. Clones share the closure, which may run on
+/// rayon worker threads when candidates are evaluated in parallel.
+#[derive(Clone)]
+pub struct AutoScore(Arc<AutoScoreFn>);
+
+impl AutoScore {
+    pub fn new(f: impl Fn(&[Page]) -> f64 + Send + Sync + 'static) -> Self {
+        Self(Arc::new(f))
+    }
+    /// Score of a candidate layout; lower is better.
+    pub fn score(&self, pages: &[Page]) -> f64 {
+        (self.0)(pages)
+    }
+}
+
+impl std::fmt::Debug for AutoScore {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("AutoScore(..)")
+    }
 }
 
 impl Default for PackerConfig {
@@ -254,30 +732,72 @@ impl Default for PackerConfig {
         Self {
             max_width: 1024,
             max_height: 1024,
+            min_width: 0,
+            min_height: 0,
             allow_rotation: true,
-            force_max_dimensions: false,
+            rotation_direction: RotationDirection::default(),
             border_padding: 0,
             texture_padding: 2,
+            padding: None,
             texture_extrusion: 0,
             trim: true,
             trim_threshold: 0,
+            trim_options: TrimOptions::default(),
             texture_outlines: false,
-            power_of_two: false,
             square: false,
+            minimize_page_size: false,
             use_waste_map: false,
             family: default_family(),
             mr_heuristic: default_mr_heuristic(),
             skyline_heuristic: default_skyline_heuristic(),
             g_choice: default_g_choice(),
             g_split: default_g_split(),
+            g_merge: default_g_merge(),
+            shelf_heuristic: ShelfHeuristic::default(),
+            grid_cell_width: 0,
+            grid_cell_height: 0,
             auto_mode: default_auto_mode(),
+            auto_candidates: Vec::new(),
+            auto_refine: false,
+            page_size: PageSizeMode::default(),
+            auto_objective: AutoObjective::default(),
+            auto_score: None,
             sort_order: default_sort_order(),
             time_budget_ms: None,
             parallel: default_parallel(),
+            deterministic: false,
+            low_memory: false,
+            key_transform: KeyTransform::default(),
+            duplicate_keys: DuplicateKeyPolicy::default(),
+            extrude_mode: ExtrudeMode::default(),
+            align: 0,
+            align_size: false,
+            pivot_mode: PivotMode::default(),
+            default_pivot: Pivot::default(),
+            pivots: BTreeMap::new(),
+            sprites: BTreeMap::new(),
+            animations: Vec::new(),
             mr_reference: false,
+            mr_global: false,
             auto_mr_ref_time_ms_threshold: None,
             auto_mr_ref_input_threshold: None,
             transparent_policy: default_transparent_policy(),
+            on_unplaceable: OnUnplaceable::default(),
+            max_pages: None,
+            page_grouping: default_page_grouping(),
+            group_max_pages: None,
+            page_balance: PageBalance::default(),
+            frame_order: FrameOrder::default(),
+            alpha_bleed: None,
+            mip_levels: None,
+            background_color: None,
+            page_format: default_page_format(),
+            bcn_format: default_bcn_format(),
+            basis_codec: BasisCodec::default(),
+            page_encoding: PageEncoding::default(),
+            sdf: None,
+            page_postprocess: None,
+            custom_packer: None,
         }
     }
 }
@@ -302,8 +822,10 @@ impl PackerConfig {
 
         // Validate padding doesn't exceed available space
         let total_border = self.border_padding.saturating_mul(2);
-        let total_padding_per_texture = self
-            .texture_padding
+        let padding = self.sprite_padding();
+        let total_padding_per_texture = padding
+            .horizontal()
+            .max(padding.vertical())
             .saturating_add(self.texture_extrusion.saturating_mul(2));
 
         if total_border >= self.max_width || total_border >= self.max_height {
@@ -334,6 +856,127 @@ impl PackerConfig {
 
         // trim_threshold is u8, so it's always valid (0-255)
 
+        if self.align > usable_width || self.align > usable_height {
+            return Err(TexPackerError::InvalidConfig(format!(
+                "align ({}) exceeds the usable page area ({}x{})",
+                self.align, usable_width, usable_height
+            )));
+        }
+
+        let enc = &self.page_encoding;
+        if !(1..=100).contains(&enc.quality) || !(1..=10).contains(&enc.speed) {
+            return Err(TexPackerError::InvalidConfig(format!(
+                "page_encoding quality must be 1-100 and speed 1-10 (got {} and {})",
+                enc.quality, enc.speed
+            )));
+        }
+        if let Some(level) = enc.png_optimize
+            && level > 6
+        {
+            return Err(TexPackerError::InvalidConfig(format!(
+                "page_encoding png_optimize level must be 0-6 (got {level})"
+            )));
+        }
+        if enc.basis_quality == 0 {
+            return Err(TexPackerError::InvalidConfig(
+                "page_encoding basis_quality must be 1-255".into(),
+            ));
+        }
+
+        if self.min_width > self.max_width || self.min_height > self.max_height {
+            return Err(TexPackerError::InvalidConfig(format!(
+                "min page size ({}x{}) exceeds max page size ({}x{})",
+                self.min_width, self.min_height, self.max_width, self.max_height
+            )));
+        }
+        if let PageSizeMode::AllowedList { sizes } = &self.page_size
+            && (!sizes.contains(&self.max_width) || !sizes.contains(&self.max_height))
+        {
+            return Err(TexPackerError::InvalidConfig(format!(
+                "max page size ({}x{}) must be one of the allowed sizes {:?}",
+                self.max_width, self.max_height, sizes
+            )));
+        }
+
+        if self.max_pages == Some(0) || self.group_max_pages == Some(0) {
+            return Err(TexPackerError::InvalidConfig(
+                "max_pages and group_max_pages must be at least 1".into(),
+            ));
+        }
+
+        if let Some(levels) = self.mip_levels
+            && !(1..=16).contains(&levels)
+        {
+            return Err(TexPackerError::InvalidConfig(format!(
+                "mip_levels must be 1-16 (got {levels})"
+            )));
+        }
+
+        if let Some(sdf) = &self.sdf {
+            if cfg!(not(feature = "sdf")) {
+                return Err(TexPackerError::InvalidConfig(
+                    "sdf requires the `sdf` feature".into(),
+                ));
+            }
+            if sdf.range == 0 {
+                return Err(TexPackerError::InvalidConfig(
+                    "sdf range must be at least 1 pixel".into(),
+                ));
+            }
+        }
+
+        if let AutoObjective::Weighted {
+            pages,
+            area,
+            max_dimension,
+            occupancy,
+        } = self.auto_objective
+            && ![pages, area, max_dimension, occupancy]
+                .iter()
+                .all(|w| w.is_finite())
+        {
+            return Err(TexPackerError::InvalidConfig(
+                "auto_objective weights must be finite".into(),
+            ));
+        }
+        for c in &self.auto_candidates {
+            if c.family == AlgorithmFamily::Auto {
+                return Err(TexPackerError::InvalidConfig(
+                    "auto_candidates must name concrete families, not `auto`".into(),
+                ));
+            }
+            if c.family == AlgorithmFamily::Custom && self.custom_packer.is_none() {
+                return Err(TexPackerError::InvalidConfig(
+                    "auto candidate `custom` needs a packer factory (PackerConfig::custom_packer)"
+                        .into(),
+                ));
+            }
+        }
+        if self.family == AlgorithmFamily::Custom && self.custom_packer.is_none() {
+            return Err(TexPackerError::InvalidConfig(
+                "family `custom` needs a packer factory (PackerConfig::custom_packer)".into(),
+            ));
+        }
+
+        self.key_transform.compile()?;
+
+        // Block-compressed pages are padded to multiples of 4, which must still fit the maximum.
+        let block = self.page_format.block_size();
+        if !self.max_width.is_multiple_of(block) || !self.max_height.is_multiple_of(block) {
+            return Err(TexPackerError::InvalidConfig(format!(
+                "page_format {:?} requires max dimensions divisible by {} (got {}x{})",
+                self.page_format, block, self.max_width, self.max_height
+            )));
+        }
+        if let PageSizeMode::AllowedList { sizes } = &self.page_size
+            && let Some(s) = sizes.iter().find(|s| !s.is_multiple_of(block))
+        {
+            return Err(TexPackerError::InvalidConfig(format!(
+                "page_format {:?} requires allowed sizes divisible by {} (got {})",
+                self.page_format, block, s
+            )));
+        }
+
         Ok(())
     }
 }
@@ -353,6 +996,9 @@ fn default_g_choice() -> GuillotineChoice {
 fn default_g_split() -> GuillotineSplit {
     GuillotineSplit::SplitShorterLeftoverAxis
 }
+fn default_g_merge() -> bool {
+    true
+}
 fn default_auto_mode() -> AutoMode {
     AutoMode::Quality
 }
@@ -362,6 +1008,15 @@ fn default_sort_order() -> SortOrder {
 fn default_parallel() -> bool {
     false
 }
+fn default_page_grouping() -> PageGrouping {
+    PageGrouping::None
+}
+fn default_page_format() -> PageFormat {
+    PageFormat::Png
+}
+fn default_bcn_format() -> BcnFormat {
+    BcnFormat::Bc7
+}
 fn default_transparent_policy() -> TransparentPolicy {
     TransparentPolicy::Keep
 }
@@ -387,8 +1042,15 @@ impl PackerConfigBuilder {
         self.cfg.allow_rotation = v;
         self
     }
+    pub fn rotation_direction(mut self, v: RotationDirection) -> Self {
+        self.cfg.rotation_direction = v;
+        self
+    }
+    /// Pre-`page_size` flag: `true` selects [`PageSizeMode::Fixed`], `false` undoes it
+    /// (still winning over [`pow2`](Self::pow2), as before).
+    #[deprecated(note = "use `page_size(PageSizeMode::Fixed)`")]
     pub fn force_max_dimensions(mut self, v: bool) -> Self {
-        self.cfg.force_max_dimensions = v;
+        self.cfg.set_legacy_page_size(None, Some(v), None);
         self
     }
     pub fn border_padding(mut self, v: u32) -> Self {
@@ -399,6 +1061,10 @@ impl PackerConfigBuilder {
         self.cfg.texture_padding = v;
         self
     }
+    pub fn padding(mut self, v: Padding) -> Self {
+        self.cfg.padding = Some(v);
+        self
+    }
     pub fn texture_extrusion(mut self, v: u32) -> Self {
         self.cfg.texture_extrusion = v;
         self
@@ -411,18 +1077,29 @@ impl PackerConfigBuilder {
         self.cfg.trim_threshold = v;
         self
     }
+    pub fn trim_options(mut self, v: TrimOptions) -> Self {
+        self.cfg.trim_options = v;
+        self
+    }
     pub fn outlines(mut self, v: bool) -> Self {
         self.cfg.texture_outlines = v;
         self
     }
+    /// Pre-`page_size` flag: `true` selects [`PageSizeMode::Pow2`] unless
+    /// [`force_max_dimensions`](Self::force_max_dimensions) is set.
+    #[deprecated(note = "use `page_size(PageSizeMode::Pow2)`")]
     pub fn pow2(mut self, v: bool) -> Self {
-        self.cfg.power_of_two = v;
+        self.cfg.set_legacy_page_size(Some(v), None, None);
         self
     }
     pub fn square(mut self, v: bool) -> Self {
         self.cfg.square = v;
         self
     }
+    pub fn minimize_page_size(mut self, v: bool) -> Self {
+        self.cfg.minimize_page_size = v;
+        self
+    }
     pub fn family(mut self, v: AlgorithmFamily) -> Self {
         self.cfg.family = v;
         self
@@ -443,10 +1120,40 @@ impl PackerConfigBuilder {
         self.cfg.g_split = v;
         self
     }
+    pub fn g_merge(mut self, v: bool) -> Self {
+        self.cfg.g_merge = v;
+        self
+    }
+    pub fn shelf_heuristic(mut self, v: ShelfHeuristic) -> Self {
+        self.cfg.shelf_heuristic = v;
+        self
+    }
+    pub fn grid_cell(mut self, w: u32, h: u32) -> Self {
+        self.cfg.grid_cell_width = w;
+        self.cfg.grid_cell_height = h;
+        self
+    }
     pub fn auto_mode(mut self, v: AutoMode) -> Self {
         self.cfg.auto_mode = v;
         self
     }
+    pub fn auto_candidates(mut self, v: Vec<AutoCandidate>) -> Self {
+        self.cfg.auto_candidates = v;
+        self
+    }
+    pub fn auto_refine(mut self, v: bool) -> Self {
+        self.cfg.auto_refine = v;
+        self
+    }
+    pub fn auto_objective(mut self, v: AutoObjective) -> Self {
+        self.cfg.auto_objective = v;
+        self
+    }
+    /// Ranks `Auto` candidates by `f(pages)` (lower wins) instead of `auto_objective`.
+    pub fn auto_score(mut self, f: impl Fn(&[Page]) -> f64 + Send + Sync + 'static) -> Self {
+        self.cfg.auto_score = Some(AutoScore::new(f));
+        self
+    }
     pub fn sort_order(mut self, v: SortOrder) -> Self {
         self.cfg.sort_order = v;
         self
@@ -459,10 +1166,63 @@ impl PackerConfigBuilder {
         self.cfg.parallel = v;
         self
     }
+    pub fn deterministic(mut self, v: bool) -> Self {
+        self.cfg.deterministic = v;
+        self
+    }
+    pub fn low_memory(mut self, v: bool) -> Self {
+        self.cfg.low_memory = v;
+        self
+    }
+    pub fn key_transform(mut self, v: KeyTransform) -> Self {
+        self.cfg.key_transform = v;
+        self
+    }
+    pub fn align(mut self, v: u32) -> Self {
+        self.cfg.align = v;
+        self
+    }
+    pub fn align_size(mut self, v: bool) -> Self {
+        self.cfg.align_size = v;
+        self
+    }
+    pub fn extrude_mode(mut self, v: ExtrudeMode) -> Self {
+        self.cfg.extrude_mode = v;
+        self
+    }
+    pub fn duplicate_keys(mut self, v: DuplicateKeyPolicy) -> Self {
+        self.cfg.duplicate_keys = v;
+        self
+    }
+    pub fn pivot_mode(mut self, v: PivotMode) -> Self {
+        self.cfg.pivot_mode = v;
+        self
+    }
+    pub fn default_pivot(mut self, v: Pivot) -> Self {
+        self.cfg.default_pivot = v;
+        self
+    }
+    pub fn pivots(mut self, v: BTreeMap<String, Pivot>) -> Self {
+        self.cfg.pivots = v;
+        self
+    }
+    pub fn sprites(mut self, v: BTreeMap<String, SpriteOptions>) -> Self {
+        self.cfg.sprites = v;
+        self
+    }
+    /// Adds (or replaces) the overrides of one sprite.
+    pub fn sprite(mut self, key: impl Into<String>, v: SpriteOptions) -> Self {
+        self.cfg.sprites.insert(key.into(), v);
+        self
+    }
     pub fn mr_reference(mut self, v: bool) -> Self {
         self.cfg.mr_reference = v;
         self
     }
+    pub fn mr_global(mut self, v: bool) -> Self {
+        self.cfg.mr_global = v;
+        self
+    }
     pub fn auto_mr_ref_time_ms_threshold(mut self, v: Option<u64>) -> Self {
         self.cfg.auto_mr_ref_time_ms_threshold = v;
         self
@@ -479,6 +1239,89 @@ impl PackerConfigBuilder {
         self.cfg.transparent_policy = v;
         self
     }
+    pub fn with_min_dimensions(mut self, w: u32, h: u32) -> Self {
+        self.cfg.min_width = w;
+        self.cfg.min_height = h;
+        self
+    }
+    pub fn page_size(mut self, v: PageSizeMode) -> Self {
+        self.cfg.page_size = v;
+        self
+    }
+    pub fn max_pages(mut self, v: Option<usize>) -> Self {
+        self.cfg.max_pages = v;
+        self
+    }
+    pub fn page_grouping(mut self, v: PageGrouping) -> Self {
+        self.cfg.page_grouping = v;
+        self
+    }
+    pub fn group_max_pages(mut self, v: Option<usize>) -> Self {
+        self.cfg.group_max_pages = v;
+        self
+    }
+    pub fn on_unplaceable(mut self, v: OnUnplaceable) -> Self {
+        self.cfg.on_unplaceable = v;
+        self
+    }
+    pub fn page_balance(mut self, v: PageBalance) -> Self {
+        self.cfg.page_balance = v;
+        self
+    }
+    pub fn frame_order(mut self, v: FrameOrder) -> Self {
+        self.cfg.frame_order = v;
+        self
+    }
+    pub fn alpha_bleed(mut self, v: Option<u32>) -> Self {
+        self.cfg.alpha_bleed = v;
+        self
+    }
+    pub fn mip_levels(mut self, v: Option<u32>) -> Self {
+        self.cfg.mip_levels = v;
+        self
+    }
+    pub fn background_color(mut self, v: Option<[u8; 4]>) -> Self {
+        self.cfg.background_color = v;
+        self
+    }
+    pub fn page_format(mut self, v: PageFormat) -> Self {
+        self.cfg.page_format = v;
+        self
+    }
+    pub fn bcn_format(mut self, v: BcnFormat) -> Self {
+        self.cfg.bcn_format = v;
+        self
+    }
+    pub fn basis_codec(mut self, v: BasisCodec) -> Self {
+        self.cfg.basis_codec = v;
+        self
+    }
+    pub fn page_encoding(mut self, v: PageEncoding) -> Self {
+        self.cfg.page_encoding = v;
+        self
+    }
+    pub fn sdf(mut self, v: Option<SdfOptions>) -> Self {
+        self.cfg.sdf = v;
+        self
+    }
+    /// Sets [`PackerConfig::page_postprocess`].
+    pub fn page_postprocess(
+        mut self,
+        f: impl Fn(&mut RgbaImage, &Page) + Send + Sync + 'static,
+    ) -> Self {
+        self.cfg.page_postprocess = Some(PagePostprocess::new(f));
+        self
+    }
+    /// Packs with `f`'s packers: sets `family` to `AlgorithmFamily::Custom` and
+    /// [`PackerConfig::custom_packer`]. `f` receives the config of the page being opened.
+    pub fn custom_packer(
+        mut self,
+        f: impl Fn(&PackerConfig) -> Box<dyn Packer<String>> + Send + Sync + 'static,
+    ) -> Self {
+        self.cfg.family = AlgorithmFamily::Custom;
+        self.cfg.custom_packer = Some(PackerFactory::new(f));
+        self
+    }
     pub fn build(self) -> PackerConfig {
         self.cfg
     }
@@ -489,10 +1332,591 @@ impl PackerConfig {
     pub fn builder() -> PackerConfigBuilder {
         PackerConfigBuilder::new()
     }
-}
-/// Policy for fully transparent images when trimming is enabled and no opaque pixel is found.
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
-#[serde(rename_all = "snake_case")]
+
+    /// Whether every page comes out with power-of-two sides under `page_size` (recorded in
+    /// `Meta::power_of_two`).
+    pub fn power_of_two_pages(&self) -> bool {
+        match &self.page_size {
+            PageSizeMode::Pow2 => true,
+            PageSizeMode::Fixed => {
+                self.max_width.is_power_of_two() && self.max_height.is_power_of_two()
+            }
+            PageSizeMode::AllowedList { sizes } => sizes.iter().all(|s| s.is_power_of_two()),
+            PageSizeMode::TightCrop { .. } => false,
+        }
+    }
+
+    /// Spacing around frames: `padding`, else `texture_padding` on both axes.
+    pub fn sprite_padding(&self) -> Padding {
+        let pad = self
+            .padding
+            .unwrap_or(Padding::uniform(self.texture_padding));
+        let Some(levels) = self.mip_levels else {
+            return pad;
+        };
+        let mip = Padding::uniform(crate::mipmap::mip_safe_padding(
+            levels,
+            self.texture_extrusion,
+        ));
+        Padding {
+            left: pad.left.max(mip.left),
+            top: pad.top.max(mip.top),
+            right: pad.right.max(mip.right),
+            bottom: pad.bottom.max(mip.bottom),
+        }
+    }
+
+    /// `mip_levels` as recorded in `Meta::mip`.
+    pub(crate) fn mip_info(&self) -> Option<crate::model::MipInfo> {
+        self.mip_levels.map(|levels| crate::model::MipInfo {
+            levels,
+            uv_inset: crate::mipmap::mip_uv_inset(levels),
+        })
+    }
+
+    /// `align` as a step: 1 when positions are unconstrained.
+    pub fn align_step(&self) -> u32 {
+        self.align.max(1)
+    }
+
+    /// `align` as recorded in `Meta::align`.
+    pub(crate) fn frame_align(&self) -> Option<u32> {
+        (self.align > 1).then_some(self.align)
+    }
+
+    /// The single padding value recorded in `Meta::padding` (the narrower axis when uneven).
+    pub(crate) fn meta_padding(&self) -> u32 {
+        let pad = self.sprite_padding();
+        pad.horizontal().min(pad.vertical())
+    }
+
+    /// Overrides of the sprite with frame key `key`, if any.
+    pub fn sprite(&self, key: &str) -> Option<&SpriteOptions> {
+        self.sprites.get(key)
+    }
+
+    /// True if the sprite with `key` is excluded from packing.
+    pub fn is_excluded(&self, key: &str) -> bool {
+        self.sprite(key).is_some_and(|s| s.exclude)
+    }
+
+    /// Extrusion fill for the sprite with `key`: its `SpriteOptions::extrude_mode`, else
+    /// `extrude_mode`.
+    pub fn extrude_mode_for(&self, key: &str) -> ExtrudeMode {
+        self.sprite(key)
+            .and_then(|s| s.extrude_mode)
+            .unwrap_or(self.extrude_mode)
+    }
+
+    /// Whether the sprite with `key` is trimmed: its `SpriteOptions::trim`, else `trim`.
+    pub fn trim_for(&self, key: &str) -> bool {
+        self.sprite(key).and_then(|s| s.trim).unwrap_or(self.trim)
+    }
+
+    /// Page group of the sprite with `key`: its `SpriteOptions::group`, else `page_grouping`.
+    pub fn group_of(&self, key: &str) -> Option<String> {
+        match self.sprite(key).and_then(|s| s.group.clone()) {
+            Some(g) => Some(g),
+            None => self.page_grouping.group_of(key),
+        }
+    }
+
+    /// Pivot of the frame with `key`: its `SpriteOptions::pivot` or `pivots` entry, else the
+    /// `detected` pivot (see `pipeline::detect_pivot`), else the center (`PivotMode::Center`) or
+    /// `default_pivot`.
+    pub fn pivot_for(&self, key: &str, detected: Option<Pivot>) -> Pivot {
+        if let Some(p) = self.sprite(key).and_then(|s| s.pivot) {
+            return p;
+        }
+        if let Some(p) = self.pivots.get(key) {
+            return *p;
+        }
+        match self.pivot_mode {
+            PivotMode::Center => Pivot::CENTER,
+            _ => detected.unwrap_or(self.default_pivot),
+        }
+    }
+}
+
+impl PackerConfig {
+    /// Builds a config from `(field, value)` string pairs applied over the defaults in order
+    /// (see [`PackerConfig::set_str`]), e.g. `[("family", "maxrects"), ("texture_padding", "4")]`.
+    pub fn from_str_overrides<K: AsRef<str>, V: AsRef<str>>(
+        overrides: impl IntoIterator<Item = (K, V)>,
+    ) -> crate::error::Result<Self> {
+        let mut cfg = Self::default();
+        for (field, value) in overrides {
+            cfg.set_str(field.as_ref(), value.as_ref())?;
+        }
+        Ok(cfg)
+    }
+
+    /// Sets the field named `field` (its serde name) from a string: numbers, `true`/`false`,
+    /// enum names (see [`NamedOption`]), `none` for unset optional values and pivots as accepted
+    /// by `Pivot::from_str`. The pre-`page_size` keys `power_of_two`, `force_max_dimensions` and
+    /// `allowed_sizes` (comma-separated) still work and update `page_size`. Map-valued fields
+    /// (`key_transform`, `pivots`, `sprites`) cannot be set this way.
+    pub fn set_str(&mut self, field: &str, value: &str) -> crate::error::Result<()> {
+        use crate::error::TexPackerError;
+        let v = value.trim();
+        match field {
+            "max_width" => self.max_width = parse_value(field, v)?,
+            "max_height" => self.max_height = parse_value(field, v)?,
+            "min_width" => self.min_width = parse_value(field, v)?,
+            "min_height" => self.min_height = parse_value(field, v)?,
+            "allowed_sizes" => {
+                let sizes = v
+                    .split(',')
+                    .map(str::trim)
+                    .filter(|s| !s.is_empty())
+                    .map(|s| parse_value(field, s))
+                    .collect::<crate::error::Result<_>>()?;
+                self.set_legacy_page_size(None, None, Some(sizes))
+            }
+            "allow_rotation" => self.allow_rotation = parse_value(field, v)?,
+            "rotation_direction" => self.rotation_direction = parse_option(field, v)?,
+            "force_max_dimensions" => {
+                self.set_legacy_page_size(None, Some(parse_value(field, v)?), None)
+            }
+            "border_padding" => self.border_padding = parse_value(field, v)?,
+            "texture_padding" => self.texture_padding = parse_value(field, v)?,
+            "padding" => {
+                self.padding = if v.is_empty() || v.eq_ignore_ascii_case("none") {
+                    None
+                } else {
+                    Some(v.parse().map_err(|_| TexPackerError::InvalidOption {
+                        field: field.into(),
+                        value: v.into(),
+                        expected: "none, N, x,y or left,top,right,bottom".into(),
+                    })?)
+                }
+            }
+            "texture_extrusion" => self.texture_extrusion = parse_value(field, v)?,
+            "trim" => self.trim = parse_value(field, v)?,
+            "trim_threshold" => self.trim_threshold = parse_value(field, v)?,
+            "trim_options.margin" => {
+                self.trim_options.margin =
+                    parse_sides(v).ok_or_else(|| crate::error::TexPackerError::InvalidOption {
+                        field: field.into(),
+                        value: v.into(),
+                        expected: "N or left,top,right,bottom".into(),
+                    })?
+            }
+            "trim_options.min_size" => self.trim_options.min_size = parse_value(field, v)?,
+            "trim_options.multiple_of" => self.trim_options.multiple_of = parse_value(field, v)?,
+            "texture_outlines" => self.texture_outlines = parse_value(field, v)?,
+            "power_of_two" => self.set_legacy_page_size(Some(parse_value(field, v)?), None, None),
+            "page_size" => self.page_size = parse_option(field, v)?,
+            "square" => self.square = parse_value(field, v)?,
+            "minimize_page_size" => self.minimize_page_size = parse_value(field, v)?,
+            "use_waste_map" => self.use_waste_map = parse_value(field, v)?,
+            "family" => self.family = parse_option(field, v)?,
+            "mr_heuristic" => self.mr_heuristic = parse_option(field, v)?,
+            "skyline_heuristic" => self.skyline_heuristic = parse_option(field, v)?,
+            "g_choice" => self.g_choice = parse_option(field, v)?,
+            "g_split" => self.g_split = parse_option(field, v)?,
+            "g_merge" => self.g_merge = parse_value(field, v)?,
+            "shelf_heuristic" => self.shelf_heuristic = parse_option(field, v)?,
+            "grid_cell_width" => self.grid_cell_width = parse_value(field, v)?,
+            "grid_cell_height" => self.grid_cell_height = parse_value(field, v)?,
+            "auto_mode" => self.auto_mode = parse_option(field, v)?,
+            "auto_refine" => self.auto_refine = parse_value(field, v)?,
+            "auto_objective" => self.auto_objective = parse_option(field, v)?,
+            "auto_candidates" => {
+                self.auto_candidates = v
+                    .split(',')
+                    .map(str::trim)
+                    .filter(|s| !s.is_empty())
+                    .map(|s| {
+                        s.parse().map_err(|_| TexPackerError::InvalidOption {
+                            field: field.into(),
+                            value: s.into(),
+                            expected: "family[:heuristic][@sort_order], e.g. maxrects:baf".into(),
+                        })
+                    })
+                    .collect::<crate::error::Result<_>>()?
+            }
+            "sort_order" => self.sort_order = parse_option(field, v)?,
+            "time_budget_ms" => self.time_budget_ms = parse_optional(field, v)?,
+            "parallel" => self.parallel = parse_value(field, v)?,
+            "deterministic" => self.deterministic = parse_value(field, v)?,
+            "low_memory" => self.low_memory = parse_value(field, v)?,
+            "duplicate_keys" => self.duplicate_keys = parse_option(field, v)?,
+            "extrude_mode" => self.extrude_mode = parse_option(field, v)?,
+            "align" => self.align = parse_value(field, v)?,
+            "align_size" => self.align_size = parse_value(field, v)?,
+            "pivot_mode" => self.pivot_mode = parse_option(field, v)?,
+            "default_pivot" => {
+                self.default_pivot = v.parse().map_err(|_| TexPackerError::InvalidOption {
+                    field: field.into(),
+                    value: v.into(),
+                    expected: "x,y or a pivot name such as center, bottom-center, top-left".into(),
+                })?
+            }
+            "mr_reference" => self.mr_reference = parse_value(field, v)?,
+            "mr_global" => self.mr_global = parse_value(field, v)?,
+            "auto_mr_ref_time_ms_threshold" => {
+                self.auto_mr_ref_time_ms_threshold = parse_optional(field, v)?
+            }
+            "auto_mr_ref_input_threshold" => {
+                self.auto_mr_ref_input_threshold = parse_optional(field, v)?
+            }
+            "transparent_policy" => self.transparent_policy = parse_option(field, v)?,
+            "on_unplaceable" => self.on_unplaceable = parse_option(field, v)?,
+            "max_pages" => self.max_pages = parse_optional(field, v)?,
+            "page_grouping" => self.page_grouping = parse_option(field, v)?,
+            "group_max_pages" => self.group_max_pages = parse_optional(field, v)?,
+            "page_balance" => self.page_balance = parse_option(field, v)?,
+            "frame_order" => self.frame_order = parse_option(field, v)?,
+            "alpha_bleed" => self.alpha_bleed = parse_optional(field, v)?,
+            "mip_levels" => self.mip_levels = parse_optional(field, v)?,
+            "background_color" => self.background_color = parse_optional_color(field, v)?,
+            "page_format" => self.page_format = parse_option(field, v)?,
+            "bcn_format" => self.bcn_format = parse_option(field, v)?,
+            "basis_codec" => self.basis_codec = parse_option(field, v)?,
+            "page_encoding.quality" => self.page_encoding.quality = parse_value(field, v)?,
+            "page_encoding.lossless" => self.page_encoding.lossless = parse_value(field, v)?,
+            "page_encoding.speed" => self.page_encoding.speed = parse_value(field, v)?,
+            "page_encoding.png_optimize" => {
+                self.page_encoding.png_optimize = parse_optional(field, v)?
+            }
+            "page_encoding.basis_quality" => {
+                self.page_encoding.basis_quality = parse_value(field, v)?
+            }
+            "sdf" => {
+                self.sdf = if v.is_empty() || v.eq_ignore_ascii_case("none") {
+                    None
+                } else {
+                    let mode = parse_option(field, v)?;
+                    Some(SdfOptions {
+                        mode,
+                        ..self.sdf.unwrap_or_default()
+                    })
+                }
+            }
+            "sdf.mode" => self.sdf.get_or_insert_default().mode = parse_option(field, v)?,
+            "sdf.range" => self.sdf.get_or_insert_default().range = parse_value(field, v)?,
+            "sdf.threshold" => self.sdf.get_or_insert_default().threshold = parse_value(field, v)?,
+            "key_transform" | "pivots" | "sprites" => {
+                return Err(TexPackerError::InvalidConfig(format!(
+                    "`{field}` is a map and cannot be set from a string"
+                )));
+            }
+            "page_postprocess" | "custom_packer" | "auto_score" => {
+                return Err(TexPackerError::InvalidConfig(format!(
+                    "`{field}` is a callback and cannot be set from a string"
+                )));
+            }
+            _ => {
+                return Err(TexPackerError::InvalidConfig(format!(
+                    "unknown option `{field}`"
+                )));
+            }
+        }
+        Ok(())
+    }
+
+    /// Sets `field` from a JSON value, as passed by language bindings. Strings, numbers,
+    /// booleans and `null` (= `none`) go through [`PackerConfig::set_str`]; arrays and objects
+    /// replace the top-level field wholesale via serde (`key_transform`, `pivots`, ...).
+    pub fn set_json(&mut self, field: &str, value: &serde_json::Value) -> crate::error::Result<()> {
+        use crate::error::TexPackerError;
+        use serde_json::Value;
+        match value {
+            Value::Null => self.set_str(field, "none"),
+            Value::Bool(b) => self.set_str(field, &b.to_string()),
+            Value::Number(n) => self.set_str(field, &n.to_string()),
+            Value::String(s) => self.set_str(field, s),
+            Value::Array(sizes) if field == "allowed_sizes" => {
+                let list: Vec<String> = sizes.iter().map(|s| s.to_string()).collect();
+                self.set_str(field, &list.join(","))
+            }
+            Value::Array(_) | Value::Object(_) => {
+                let mut whole = serde_json::to_value(&*self)
+                    .map_err(|e| TexPackerError::InvalidConfig(e.to_string()))?;
+                let Some(slot) = whole.get_mut(field) else {
+                    return Err(TexPackerError::InvalidConfig(format!(
+                        "unknown option `{field}`"
+                    )));
+                };
+                *slot = value.clone();
+                let hook = self.page_postprocess.take();
+                let factory = self.custom_packer.take();
+                let score = self.auto_score.take();
+                *self = serde_json::from_value(whole)
+                    .map_err(|e| TexPackerError::InvalidConfig(format!("`{field}`: {e}")))?;
+                self.page_postprocess = hook;
+                self.custom_packer = factory;
+                self.auto_score = score;
+                Ok(())
+            }
+        }
+    }
+
+    /// Applies pre-`page_size` keys given one at a time: the flags the current mode stands for
+    /// are overridden by the given ones and mapped back onto a mode. The mode is left alone
+    /// (margin included) when the flags still map onto the same one.
+    fn set_legacy_page_size(
+        &mut self,
+        power_of_two: Option<bool>,
+        force_max_dimensions: Option<bool>,
+        allowed_sizes: Option<Vec<u32>>,
+    ) {
+        let (pow2, fixed, sizes) = match &self.page_size {
+            PageSizeMode::TightCrop { .. } => (false, false, Vec::new()),
+            PageSizeMode::Pow2 => (true, false, Vec::new()),
+            PageSizeMode::Fixed => (false, true, Vec::new()),
+            PageSizeMode::AllowedList { sizes } => (false, false, sizes.clone()),
+        };
+        let (w, h) = (self.max_width, self.max_height);
+        let before = PageSizeMode::from_legacy_flags(pow2, fixed, &sizes, w, h);
+        let after = PageSizeMode::from_legacy_flags(
+            power_of_two.unwrap_or(pow2),
+            force_max_dimensions.unwrap_or(fixed),
+            allowed_sizes.as_deref().unwrap_or(&sizes),
+            w,
+            h,
+        );
+        if after != before {
+            self.page_size = after;
+        }
+    }
+}
+
+impl Serialize for PackerConfig {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        PackerConfig::serialize(self, serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for PackerConfig {
+    /// Deserializes a config, mapping the pre-`page_size` keys `power_of_two`,
+    /// `force_max_dimensions` and `allowed_sizes` onto `page_size` when it is absent.
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        #[derive(Deserialize)]
+        struct WithLegacyKeys {
+            #[serde(flatten, deserialize_with = "PackerConfig::deserialize")]
+            cfg: PackerConfig,
+            page_size: Option<PageSizeMode>,
+            #[serde(default)]
+            power_of_two: bool,
+            #[serde(default)]
+            force_max_dimensions: bool,
+            #[serde(default)]
+            allowed_sizes: Vec<u32>,
+        }
+        let c = WithLegacyKeys::deserialize(deserializer)?;
+        let mut cfg = c.cfg;
+        cfg.page_size = c.page_size.unwrap_or_else(|| {
+            PageSizeMode::from_legacy_flags(
+                c.power_of_two,
+                c.force_max_dimensions,
+                &c.allowed_sizes,
+                cfg.max_width,
+                cfg.max_height,
+            )
+        });
+        Ok(cfg)
+    }
+}
+
+/// Config enums that parse from a name (CLI flags, YAML values, [`PackerConfig::set_str`]).
+pub trait NamedOption: FromStr {
+    /// Canonical names, listed in errors (aliases are accepted but not listed).
+    const NAMES: &'static [&'static str];
+}
+
+/// Parses `value` as `T`, failing with [`TexPackerError::InvalidOption`](crate::TexPackerError)
+/// that names `field` and the accepted names.
+pub fn parse_option<T: NamedOption>(field: &str, value: &str) -> crate::error::Result<T> {
+    value
+        .parse()
+        .map_err(|_| crate::error::TexPackerError::InvalidOption {
+            field: field.into(),
+            value: value.into(),
+            expected: format!("one of {}", T::NAMES.join(", ")),
+        })
+}
+
+/// Numbers and booleans of [`PackerConfig::set_str`].
+fn parse_value<T: FromStr>(field: &str, value: &str) -> crate::error::Result<T> {
+    value
+        .parse()
+        .map_err(|_| crate::error::TexPackerError::InvalidOption {
+            field: field.into(),
+            value: value.into(),
+            expected: match std::any::type_name::<T>() {
+                "bool" => "true or false",
+                _ => "a non-negative integer",
+            }
+            .into(),
+        })
+}
+
+/// Like [`parse_value`]; `none` (or empty) unsets the value.
+fn parse_optional<T: FromStr>(field: &str, value: &str) -> crate::error::Result<Option<T>> {
+    if value.is_empty() || value.eq_ignore_ascii_case("none") {
+        return Ok(None);
+    }
+    parse_value(field, value).map(Some)
+}
+
+/// Parses a color as `#rrggbb`, `#rrggbbaa` (the `#` is optional) or `r,g,b[,a]`; alpha
+/// defaults to 255.
+pub fn parse_color(s: &str) -> Option<[u8; 4]> {
+    let s = s.trim();
+    if s.contains(',') {
+        let parts: Vec<u8> = s
+            .split(',')
+            .map(|p| p.trim().parse().ok())
+            .collect::<Option<_>>()?;
+        return match parts[..] {
+            [r, g, b] => Some([r, g, b, 255]),
+            [r, g, b, a] => Some([r, g, b, a]),
+            _ => None,
+        };
+    }
+    let hex = s.strip_prefix('#').unwrap_or(s);
+    if !(hex.len() == 6 || hex.len() == 8) || !hex.is_ascii() {
+        return None;
+    }
+    let byte = |i: usize| u8::from_str_radix(&hex[i..i + 2], 16).ok();
+    let a = if hex.len() == 8 { byte(6)? } else { 255 };
+    Some([byte(0)?, byte(2)?, byte(4)?, a])
+}
+
+/// Parses per-side pixel counts as `N` (all sides) or `left,top,right,bottom`.
+pub fn parse_sides(s: &str) -> Option<[u32; 4]> {
+    let parts: Vec<u32> = s
+        .split(',')
+        .map(|p| p.trim().parse().ok())
+        .collect::<Option<_>>()?;
+    match parts[..] {
+        [n] => Some([n; 4]),
+        [l, t, r, b] => Some([l, t, r, b]),
+        _ => None,
+    }
+}
+
+/// [`parse_color`] for [`PackerConfig::set_str`]; `none` (or empty) unsets the value.
+fn parse_optional_color(field: &str, value: &str) -> crate::error::Result<Option<[u8; 4]>> {
+    if value.is_empty() || value.eq_ignore_ascii_case("none") {
+        return Ok(None);
+    }
+    parse_color(value)
+        .map(Some)
+        .ok_or_else(|| crate::error::TexPackerError::InvalidOption {
+            field: field.into(),
+            value: value.into(),
+            expected: "a color like #rrggbb, #rrggbbaa or r,g,b[,a]".into(),
+        })
+}
+
+impl NamedOption for AlgorithmFamily {
+    const NAMES: &'static [&'static str] = &[
+        "skyline",
+        "maxrects",
+        "guillotine",
+        "shelf",
+        "grid",
+        "auto",
+        "custom",
+    ];
+}
+impl NamedOption for MaxRectsHeuristic {
+    const NAMES: &'static [&'static str] = &["baf", "bssf", "blsf", "bl", "cp"];
+}
+impl NamedOption for SkylineHeuristic {
+    const NAMES: &'static [&'static str] = &["bl", "minwaste", "bl_wm", "minwaste_wm"];
+}
+impl NamedOption for ShelfHeuristic {
+    const NAMES: &'static [&'static str] = &["nf", "ff", "bhf"];
+}
+impl NamedOption for GuillotineChoice {
+    const NAMES: &'static [&'static str] = &["baf", "bssf", "blsf", "waf", "wssf", "wlsf"];
+}
+impl NamedOption for GuillotineSplit {
+    const NAMES: &'static [&'static str] = &["slas", "llas", "minas", "maxas", "sas", "las"];
+}
+impl NamedOption for AutoObjective {
+    const NAMES: &'static [&'static str] = &[
+        "pages",
+        "area",
+        "occupancy",
+        "max_dimension",
+        "weighted:PAGES,AREA,MAX_DIMENSION,OCCUPANCY",
+    ];
+}
+impl NamedOption for PageSizeMode {
+    const NAMES: &'static [&'static str] = &[
+        "tight_crop[:MARGIN]",
+        "pow2",
+        "fixed",
+        "allowed_list:SIZE,SIZE,...",
+    ];
+}
+impl NamedOption for AutoMode {
+    const NAMES: &'static [&'static str] = &["fast", "quality", "exhaustive", "anneal"];
+}
+impl NamedOption for SortOrder {
+    const NAMES: &'static [&'static str] = &[
+        "area_desc",
+        "max_side_desc",
+        "height_desc",
+        "width_desc",
+        "name_asc",
+        "none",
+    ];
+}
+impl NamedOption for TransparentPolicy {
+    const NAMES: &'static [&'static str] = &["keep", "one_by_one", "skip"];
+}
+impl NamedOption for DuplicateKeyPolicy {
+    const NAMES: &'static [&'static str] = &["error", "suffix", "overwrite"];
+}
+impl NamedOption for ExtrudeMode {
+    const NAMES: &'static [&'static str] = &["clamp", "wrap", "mirror"];
+}
+impl NamedOption for PivotMode {
+    const NAMES: &'static [&'static str] = &["manual", "center", "alpha_centroid", "bottom_center"];
+}
+impl NamedOption for PageGrouping {
+    const NAMES: &'static [&'static str] = &["none", "folder"];
+}
+impl NamedOption for PageBalance {
+    const NAMES: &'static [&'static str] = &["fill_first", "balanced", "multi_bin"];
+}
+impl NamedOption for RotationPolicy {
+    const NAMES: &'static [&'static str] = &["auto", "always", "never"];
+}
+impl NamedOption for RotationDirection {
+    const NAMES: &'static [&'static str] = &["clockwise", "counter_clockwise"];
+}
+impl NamedOption for OnUnplaceable {
+    const NAMES: &'static [&'static str] = &["error", "skip", "scale_down"];
+}
+impl NamedOption for SdfMode {
+    const NAMES: &'static [&'static str] = &["single", "multi"];
+}
+impl NamedOption for FrameOrder {
+    const NAMES: &'static [&'static str] = &["packing", "key", "input", "position"];
+}
+impl NamedOption for PageFormat {
+    const NAMES: &'static [&'static str] = &["png", "ktx2", "dds", "jpeg", "webp", "avif", "exr"];
+}
+impl NamedOption for BcnFormat {
+    const NAMES: &'static [&'static str] = &["bc1", "bc3", "bc7"];
+}
+impl NamedOption for BasisCodec {
+    const NAMES: &'static [&'static str] = &["etc1s", "uastc"];
+}
+impl NamedOption for KeyCase {
+    const NAMES: &'static [&'static str] = &["keep", "lower", "upper"];
+}
+/// Policy for fully transparent images when trimming is enabled and no opaque pixel is found.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[serde(rename_all = "snake_case")]
 pub enum TransparentPolicy {
     /// Keep original dimensions (status quo)
     Keep,
@@ -513,3 +1937,971 @@ impl FromStr for TransparentPolicy {
         }
     }
 }
+
+/// Handling of inputs that do not fit an empty page (their slot, with padding and extrusion,
+/// exceeds the page minus its border in every allowed orientation).
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[serde(rename_all = "snake_case")]
+pub enum OnUnplaceable {
+    /// Fail the whole run with `TexPackerError::OutOfSpace` / `OutOfSpaceGeneric`.
+    #[default]
+    Error,
+    /// Leave the input out and list it in `PackOutput::skipped`.
+    Skip,
+    /// Shrink the input (aspect ratio kept) until it fits and list it in `PackOutput::scaled`.
+    ScaleDown,
+}
+
+impl FromStr for OnUnplaceable {
+    type Err = ();
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "error" | "fail" => Ok(Self::Error),
+            "skip" => Ok(Self::Skip),
+            "scale_down" | "scale-down" | "scale" | "shrink" => Ok(Self::ScaleDown),
+            _ => Err(()),
+        }
+    }
+}
+
+/// Handling of inputs whose keys collide.
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[serde(rename_all = "snake_case")]
+pub enum DuplicateKeyPolicy {
+    /// Fail with `TexPackerError::DuplicateKey`.
+    #[default]
+    Error,
+    /// Rename later inputs by appending `_2`, `_3`, ... before the extension (`hero_2.png`).
+    Suffix,
+    /// The last input with a key replaces earlier ones (keeping the first one's position).
+    Overwrite,
+}
+
+impl FromStr for DuplicateKeyPolicy {
+    type Err = ();
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "error" => Ok(Self::Error),
+            "suffix" | "rename" => Ok(Self::Suffix),
+            "overwrite" | "replace" => Ok(Self::Overwrite),
+            _ => Err(()),
+        }
+    }
+}
+
+/// Source of the pixels written into a frame's extruded border.
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[serde(rename_all = "snake_case")]
+pub enum ExtrudeMode {
+    /// Repeat the nearest edge pixel.
+    #[default]
+    Clamp,
+    /// Continue with the opposite edge, so filtered samples of a tiling texture do not seam.
+    Wrap,
+    /// Reflect the pixels next to the edge (`GL_MIRRORED_REPEAT`).
+    Mirror,
+}
+
+impl FromStr for ExtrudeMode {
+    type Err = ();
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "clamp" | "edge" => Ok(Self::Clamp),
+            "wrap" | "repeat" | "tile" => Ok(Self::Wrap),
+            "mirror" | "reflect" => Ok(Self::Mirror),
+            _ => Err(()),
+        }
+    }
+}
+
+/// Per-sprite overrides merged over the global [`PackerConfig`] (see `PackerConfig::sprites`).
+///
+/// Padding and extrusion can only grow the spacing around a sprite: values below the global
+/// `texture_padding` / `texture_extrusion` are treated as the global value.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[serde(deny_unknown_fields)]
+pub struct SpriteOptions {
+    /// Spacing reserved around this sprite (instead of `texture_padding`).
+    #[serde(default, alias = "padding")]
+    pub texture_padding: Option<u32>,
+    /// Edge extrusion for this sprite (instead of `texture_extrusion`).
+    #[serde(default, alias = "extrusion")]
+    pub texture_extrusion: Option<u32>,
+    /// Fill of the extruded border (instead of `extrude_mode`), e.g. `wrap` for tiles.
+    #[serde(default)]
+    pub extrude_mode: Option<ExtrudeMode>,
+    /// Trim transparent borders of this sprite (instead of `trim`).
+    #[serde(default)]
+    pub trim: Option<bool>,
+    /// Pivot; wins over `PackerConfig::pivots` and detection.
+    #[serde(default)]
+    pub pivot: Option<Pivot>,
+    /// Rotation of this sprite: `always` places it rotated 90° (even with `allow_rotation` off),
+    /// `never` keeps it upright (nine-patch or tiling sprites); `auto` or unset follows
+    /// `allow_rotation`. Also accepts `true` / `false` for `always` / `never`.
+    #[serde(default)]
+    pub rotate: Option<RotationPolicy>,
+    /// Page group (sprites of different groups never share a page); overrides `page_grouping`.
+    #[serde(default)]
+    pub group: Option<String>,
+    /// Nine-patch insets, written to exporters that support them.
+    #[serde(default)]
+    pub nine_patch: Option<NinePatch>,
+    /// Leave this sprite out of the atlas.
+    #[serde(default)]
+    pub exclude: bool,
+}
+
+impl SpriteOptions {
+    /// Fills the fields left unset here from `parent` (e.g. options of an enclosing folder);
+    /// a sprite is excluded if either excludes it.
+    pub fn inherit(self, parent: &SpriteOptions) -> SpriteOptions {
+        SpriteOptions {
+            texture_padding: self.texture_padding.or(parent.texture_padding),
+            texture_extrusion: self.texture_extrusion.or(parent.texture_extrusion),
+            extrude_mode: self.extrude_mode.or(parent.extrude_mode),
+            trim: self.trim.or(parent.trim),
+            pivot: self.pivot.or(parent.pivot),
+            rotate: self.rotate.or(parent.rotate),
+            group: self.group.or_else(|| parent.group.clone()),
+            nine_patch: self.nine_patch.or(parent.nine_patch),
+            exclude: self.exclude || parent.exclude,
+        }
+    }
+}
+
+/// Direction of the 90° turn applied to rotated frames.
+///
+/// With `Clockwise` the source's top-left pixel lands at the frame's top-right corner (the
+/// TexturePacker, cocos2d, libGDX and Spine convention); with `CounterClockwise` it lands at the
+/// bottom-left.
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[serde(rename_all = "snake_case")]
+pub enum RotationDirection {
+    #[default]
+    Clockwise,
+    CounterClockwise,
+}
+
+impl RotationDirection {
+    /// Source pixel shown at `(x, y)` of a rotated frame whose source is `w`x`h`.
+    pub const fn source_of(self, x: u32, y: u32, w: u32, h: u32) -> (u32, u32) {
+        match self {
+            Self::Clockwise => (y, h - 1 - x),
+            Self::CounterClockwise => (w - 1 - y, x),
+        }
+    }
+}
+
+impl FromStr for RotationDirection {
+    type Err = ();
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "clockwise" | "cw" => Ok(Self::Clockwise),
+            "counter_clockwise" | "counter-clockwise" | "counterclockwise" | "ccw"
+            | "anticlockwise" => Ok(Self::CounterClockwise),
+            _ => Err(()),
+        }
+    }
+}
+
+/// Per-sprite rotation (`SpriteOptions::rotate`).
+#[derive(Debug, Clone, Copy, Default, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum RotationPolicy {
+    /// Rotate when `PackerConfig::allow_rotation` is on and the packer prefers it.
+    #[default]
+    Auto,
+    /// Always place the sprite rotated 90°.
+    Always,
+    /// Never rotate the sprite.
+    Never,
+}
+
+impl RotationPolicy {
+    /// `Some(rotated)` when the policy pins the orientation, `None` for `Auto`.
+    pub const fn forced(self) -> Option<bool> {
+        match self {
+            Self::Auto => None,
+            Self::Always => Some(true),
+            Self::Never => Some(false),
+        }
+    }
+}
+
+impl FromStr for RotationPolicy {
+    type Err = ();
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "auto" | "allow" => Ok(Self::Auto),
+            "always" | "force" | "true" => Ok(Self::Always),
+            "never" | "forbid" | "false" => Ok(Self::Never),
+            _ => Err(()),
+        }
+    }
+}
+
+impl From<bool> for RotationPolicy {
+    fn from(rotate: bool) -> Self {
+        if rotate { Self::Always } else { Self::Never }
+    }
+}
+
+impl<'de> Deserialize<'de> for RotationPolicy {
+    fn deserialize<D: serde::Deserializer<'de>>(d: D) -> Result<Self, D::Error> {
+        #[derive(Deserialize)]
+        #[serde(untagged)]
+        enum Repr {
+            Bool(bool),
+            Name(String),
+        }
+        match Repr::deserialize(d)? {
+            Repr::Bool(b) => Ok(b.into()),
+            Repr::Name(name) => name.parse().map_err(|_| {
+                serde::de::Error::custom(format!(
+                    "unknown rotation policy '{name}', expected one of: {}",
+                    Self::NAMES.join(", ")
+                ))
+            }),
+        }
+    }
+}
+
+#[cfg(feature = "schemars")]
+impl schemars::JsonSchema for RotationPolicy {
+    fn schema_name() -> std::borrow::Cow<'static, str> {
+        "RotationPolicy".into()
+    }
+
+    fn json_schema(_: &mut schemars::SchemaGenerator) -> schemars::Schema {
+        schemars::json_schema!({
+            "description": "Sprite rotation: auto (follow allow_rotation), always or never; true / false mean always / never.",
+            "oneOf": [
+                { "type": "string", "enum": ["auto", "always", "never"] },
+                { "type": "boolean" }
+            ]
+        })
+    }
+}
+
+/// How frame pivots are chosen; explicit `PackerConfig::pivots` entries always win.
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[serde(rename_all = "snake_case")]
+pub enum PivotMode {
+    /// `default_pivot` (center unless configured).
+    #[default]
+    Manual,
+    /// Center of the untrimmed image.
+    Center,
+    /// Centroid of the opaque pixels (alpha above `trim_threshold`).
+    AlphaCentroid,
+    /// Center of the lowest opaque row, on its bottom edge (a "feet" anchor for characters).
+    BottomCenter,
+}
+
+impl FromStr for PivotMode {
+    type Err = ();
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().replace('-', "_").as_str() {
+            "manual" => Ok(Self::Manual),
+            "center" => Ok(Self::Center),
+            "alpha_centroid" | "centroid" => Ok(Self::AlphaCentroid),
+            "bottom_center" | "feet" => Ok(Self::BottomCenter),
+            _ => Err(()),
+        }
+    }
+}
+
+/// Grouping of inputs onto pages.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[serde(rename_all = "snake_case")]
+pub enum PageGrouping {
+    /// No grouping: any input may share a page with any other.
+    None,
+    /// Group by the key's folder (everything before the last `/`); keys without a folder form
+    /// one group.
+    Folder,
+}
+
+impl PageGrouping {
+    /// Group name for `key`, or `None` when grouping is disabled.
+    pub fn group_of(&self, key: &str) -> Option<String> {
+        match self {
+            Self::None => None,
+            Self::Folder => Some(
+                key.rsplit_once('/')
+                    .map(|(dir, _)| dir)
+                    .unwrap_or("")
+                    .to_string(),
+            ),
+        }
+    }
+}
+
+impl FromStr for PageGrouping {
+    type Err = ();
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "none" => Ok(Self::None),
+            "folder" | "dir" => Ok(Self::Folder),
+            _ => Err(()),
+        }
+    }
+}
+
+/// How page dimensions follow from the extent of the frames on the page. `min_width`/`min_height`
+/// apply before, and block rounding for compressed `page_format`s and `square` after, every mode
+/// but `Fixed`.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[serde(rename_all = "snake_case")]
+pub enum PageSizeMode {
+    /// Crop to the frames (trailing padding, extrusion and border included) plus `margin` pixels
+    /// right and below, capped at the max size.
+    TightCrop {
+        #[serde(default)]
+        margin: u32,
+    },
+    /// Round each side up to a power of two.
+    Pow2,
+    /// Always exactly `max_width` x `max_height`.
+    Fixed,
+    /// Snap each side up to the next of `sizes`; `max_width`/`max_height` must be listed.
+    AllowedList { sizes: Vec<u32> },
+}
+
+impl Default for PageSizeMode {
+    fn default() -> Self {
+        Self::TightCrop { margin: 0 }
+    }
+}
+
+impl PageSizeMode {
+    /// The mode the pre-`page_size` config flags stood for: `force_max_dimensions` wins, then
+    /// `allowed_sizes` (only its powers of two and the max dimensions with `power_of_two`), then
+    /// `power_of_two`, then a plain tight crop.
+    pub fn from_legacy_flags(
+        power_of_two: bool,
+        force_max_dimensions: bool,
+        allowed_sizes: &[u32],
+        max_width: u32,
+        max_height: u32,
+    ) -> Self {
+        if force_max_dimensions {
+            Self::Fixed
+        } else if !allowed_sizes.is_empty() {
+            let mut sizes = allowed_sizes.to_vec();
+            if power_of_two {
+                sizes.retain(|&s| s.is_power_of_two() || s == max_width || s == max_height);
+            }
+            Self::AllowedList { sizes }
+        } else if power_of_two {
+            Self::Pow2
+        } else {
+            Self::default()
+        }
+    }
+}
+
+impl FromStr for PageSizeMode {
+    type Err = ();
+    /// Accepts `tight_crop[:MARGIN]`, `pow2`, `fixed` or `allowed_list:SIZE,SIZE,...`.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim().to_ascii_lowercase();
+        let (name, arg) = match s.split_once(':') {
+            Some((name, arg)) => (name, Some(arg)),
+            None => (s.as_str(), None),
+        };
+        match (name, arg) {
+            ("tight_crop" | "tight-crop" | "tight" | "crop", None) => Ok(Self::default()),
+            ("tight_crop" | "tight-crop" | "tight" | "crop", Some(m)) => Ok(Self::TightCrop {
+                margin: m.trim().parse().map_err(|_| ())?,
+            }),
+            ("pow2" | "power_of_two" | "power-of-two", None) => Ok(Self::Pow2),
+            ("fixed" | "max", None) => Ok(Self::Fixed),
+            ("allowed_list" | "allowed-list" | "allowed", Some(list)) => {
+                let sizes: Vec<u32> = list
+                    .split(',')
+                    .map(|v| v.trim().parse().map_err(|_| ()))
+                    .collect::<Result<_, _>>()?;
+                Ok(Self::AllowedList { sizes })
+            }
+            _ => Err(()),
+        }
+    }
+}
+
+/// Distribution of frames over the pages of a group that needs more than one page.
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[serde(rename_all = "snake_case")]
+pub enum PageBalance {
+    /// Fill each page before opening the next; the last page is often nearly empty.
+    #[default]
+    FillFirst,
+    /// Keep the page count of `FillFirst` but redistribute frames so pages have similar
+    /// occupancy (and smaller maximum page size). Falls back to re-splitting only the last two
+    /// pages, then to `FillFirst`, when a balanced split does not fit.
+    Balanced,
+    /// Keep every page of the group open and place each frame, in packing order, on the open
+    /// page that scores it best. Frames that would be stranded at the end of a full page can go
+    /// back to earlier pages. A second pass also opens a page early (up to the `FillFirst` page
+    /// count) whenever a frame would grow its best open page by more area than a fresh page for
+    /// it takes. The pass with fewer pages, then less total page area, is kept over the
+    /// `FillFirst` layout only when it beats it.
+    MultiBin,
+}
+
+impl FromStr for PageBalance {
+    type Err = ();
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "fill_first" | "fill-first" | "fill" => Ok(Self::FillFirst),
+            "balanced" | "balance" => Ok(Self::Balanced),
+            "multi_bin" | "multi-bin" | "multibin" | "lookahead" => Ok(Self::MultiBin),
+            _ => Err(()),
+        }
+    }
+}
+
+/// Order of the frames within each page of an atlas.
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[serde(rename_all = "snake_case")]
+pub enum FrameOrder {
+    /// The order the packer placed frames in.
+    #[default]
+    Packing,
+    /// By key (byte order).
+    Key,
+    /// The order the inputs were given in.
+    Input,
+    /// Top to bottom, then left to right.
+    Position,
+}
+
+impl FromStr for FrameOrder {
+    type Err = ();
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "packing" | "packed" => Ok(Self::Packing),
+            "key" | "name" => Ok(Self::Key),
+            "input" => Ok(Self::Input),
+            "position" | "pos" => Ok(Self::Position),
+            _ => Err(()),
+        }
+    }
+}
+
+/// Image format for written atlas pages.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[serde(rename_all = "snake_case")]
+pub enum PageFormat {
+    /// Lossless PNG (default).
+    Png,
+    /// KTX2 container with Basis Universal (ETC1S or UASTC) data, transcodable to the GPU
+    /// formats of the target device (requires the `ktx2` feature to encode).
+    Ktx2,
+    /// DDS container with BCn block compression (requires the `dds` feature to encode).
+    Dds,
+    /// Lossy JPEG (no alpha; pair with `background_color`). Quality from `page_encoding`.
+    Jpeg,
+    /// WebP: lossless, or near-lossless (quantized colors) when `page_encoding.lossless` is off.
+    Webp,
+    /// AVIF (AV1); quality and speed from `page_encoding`.
+    Avif,
+    /// OpenEXR with 32-bit float RGBA; full range for pages from `pack_images_hdr`.
+    Exr,
+}
+
+impl PageFormat {
+    /// File extension used for pages in this format.
+    pub fn extension(&self) -> &'static str {
+        match self {
+            Self::Png => "png",
+            Self::Ktx2 => "ktx2",
+            Self::Dds => "dds",
+            Self::Jpeg => "jpg",
+            Self::Webp => "webp",
+            Self::Avif => "avif",
+            Self::Exr => "exr",
+        }
+    }
+
+    /// Texel block size; page dimensions are rounded up to a multiple of it.
+    pub fn block_size(&self) -> u32 {
+        match self {
+            Self::Dds => 4,
+            Self::Png | Self::Ktx2 | Self::Jpeg | Self::Webp | Self::Avif | Self::Exr => 1,
+        }
+    }
+}
+
+impl FromStr for PageFormat {
+    type Err = ();
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "png" => Ok(Self::Png),
+            "ktx2" => Ok(Self::Ktx2),
+            "dds" => Ok(Self::Dds),
+            "jpeg" | "jpg" => Ok(Self::Jpeg),
+            "webp" => Ok(Self::Webp),
+            "avif" => Ok(Self::Avif),
+            "exr" => Ok(Self::Exr),
+            _ => Err(()),
+        }
+    }
+}
+
+/// Spacing reserved around each frame, per page-space edge (rotation does not swap it). Two
+/// neighbors are `right + left` apart horizontally and `bottom + top` vertically.
+///
+/// Deserializes from a number (the uniform gap, like `texture_padding`), `{x, y}` (horizontal
+/// and vertical gaps, each split around the frame) or `{left, top, right, bottom}`.
+#[derive(Debug, Clone, Copy, Default, Serialize, PartialEq, Eq)]
+pub struct Padding {
+    pub left: u32,
+    pub top: u32,
+    pub right: u32,
+    pub bottom: u32,
+}
+
+impl Padding {
+    /// A gap of `p` pixels between neighbors on both axes (`texture_padding = p`).
+    pub const fn uniform(p: u32) -> Self {
+        Self::xy(p, p)
+    }
+
+    /// Gaps of `x` pixels between horizontal and `y` between vertical neighbors.
+    pub const fn xy(x: u32, y: u32) -> Self {
+        Self {
+            left: x / 2,
+            top: y / 2,
+            right: x - x / 2,
+            bottom: y - y / 2,
+        }
+    }
+
+    /// Horizontal gap (`left + right`).
+    pub const fn horizontal(&self) -> u32 {
+        self.left + self.right
+    }
+
+    /// Vertical gap (`top + bottom`).
+    pub const fn vertical(&self) -> u32 {
+        self.top + self.bottom
+    }
+}
+
+impl<'de> Deserialize<'de> for Padding {
+    fn deserialize<D: serde::Deserializer<'de>>(d: D) -> Result<Self, D::Error> {
+        #[derive(Deserialize)]
+        #[serde(untagged)]
+        enum Repr {
+            Uniform(u32),
+            Xy {
+                x: u32,
+                y: u32,
+            },
+            Edges {
+                left: u32,
+                top: u32,
+                right: u32,
+                bottom: u32,
+            },
+        }
+        Ok(match Repr::deserialize(d)? {
+            Repr::Uniform(p) => Self::uniform(p),
+            Repr::Xy { x, y } => Self::xy(x, y),
+            Repr::Edges {
+                left,
+                top,
+                right,
+                bottom,
+            } => Self {
+                left,
+                top,
+                right,
+                bottom,
+            },
+        })
+    }
+}
+
+impl FromStr for Padding {
+    type Err = ();
+    /// Accepts `N`, `x,y` or `left,top,right,bottom`.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let parts: Vec<u32> = s
+            .split(',')
+            .map(|p| p.trim().parse().map_err(|_| ()))
+            .collect::<Result<_, _>>()?;
+        match parts[..] {
+            [p] => Ok(Self::uniform(p)),
+            [x, y] => Ok(Self::xy(x, y)),
+            [left, top, right, bottom] => Ok(Self {
+                left,
+                top,
+                right,
+                bottom,
+            }),
+            _ => Err(()),
+        }
+    }
+}
+
+#[cfg(feature = "schemars")]
+impl schemars::JsonSchema for Padding {
+    fn schema_name() -> std::borrow::Cow<'static, str> {
+        "Padding".into()
+    }
+
+    fn json_schema(_: &mut schemars::SchemaGenerator) -> schemars::Schema {
+        let side = schemars::json_schema!({ "type": "integer", "format": "uint32", "minimum": 0 });
+        schemars::json_schema!({
+            "description": "Spacing around each frame: a uniform gap, {x, y} gaps or {left, top, right, bottom} edges.",
+            "oneOf": [
+                side,
+                {
+                    "type": "object",
+                    "required": ["x", "y"],
+                    "properties": { "x": side, "y": side }
+                },
+                {
+                    "type": "object",
+                    "required": ["left", "top", "right", "bottom"],
+                    "properties": { "left": side, "top": side, "right": side, "bottom": side }
+                }
+            ]
+        })
+    }
+}
+
+/// Rules applied after the transparent border of a sprite is found, in this order: keep
+/// `margin` transparent pixels per side, grow to `min_size`, round up to `multiple_of`.
+/// Growth is centered on the content and never exceeds the untrimmed image.
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct TrimOptions {
+    /// Pixels of the trimmed border kept on each side: left, top, right, bottom.
+    #[serde(default)]
+    pub margin: [u32; 4],
+    /// Trimmed width and height never drop below this (0 = no floor).
+    #[serde(default)]
+    pub min_size: u32,
+    /// Round trimmed width and height up to a multiple of this (0 or 1 = off), e.g. 4 to keep
+    /// frames aligned to BCn blocks or a grid.
+    #[serde(default)]
+    pub multiple_of: u32,
+}
+
+/// Encoder settings for PNG, JPEG, WebP, AVIF and KTX2 pages.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct PageEncoding {
+    /// Quality 1-100 (JPEG, AVIF, near-lossless WebP).
+    #[serde(default = "default_page_quality")]
+    pub quality: u8,
+    /// WebP only: exact pixels. When off, colors are quantized according to `quality` before
+    /// lossless VP8L encoding (near-lossless), which shrinks files without block artifacts.
+    #[serde(default = "default_lossless")]
+    pub lossless: bool,
+    /// AVIF encoder speed, 1 (slowest, smallest) to 10 (fastest).
+    #[serde(default = "default_avif_speed")]
+    pub speed: u8,
+    /// PNG only: lossless oxipng recompression level 0-6 (higher is slower and smaller).
+    /// Requires the `oxipng` feature; `None` writes the encoder output as is.
+    #[serde(default)]
+    pub png_optimize: Option<u8>,
+    /// KTX2 only: Basis Universal quality 1-255. ETC1S uses it as its quality level; UASTC
+    /// maps it onto its five pack levels (fastest to very slow).
+    #[serde(default = "default_basis_quality")]
+    pub basis_quality: u8,
+}
+
+impl Default for PageEncoding {
+    fn default() -> Self {
+        Self {
+            quality: default_page_quality(),
+            lossless: true,
+            speed: default_avif_speed(),
+            png_optimize: None,
+            basis_quality: default_basis_quality(),
+        }
+    }
+}
+
+fn default_page_quality() -> u8 {
+    90
+}
+fn default_basis_quality() -> u8 {
+    128
+}
+fn default_lossless() -> bool {
+    true
+}
+fn default_avif_speed() -> u8 {
+    6
+}
+
+/// Signed distance field conversion of inputs (`PackerConfig::sdf`).
+///
+/// Each input grows by `range / 2` pixels (rounded up) per side, then every pixel stores
+/// `0.5 + d / range` (clamped to 0..1, scaled to 0-255) where `d` is its signed distance in
+/// pixels to the shape edge, positive inside. A shader recovers the edge at 0.5.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct SdfOptions {
+    #[serde(default)]
+    pub mode: SdfMode,
+    /// Width of the distance band in pixels: distances beyond `range / 2` from the edge clamp
+    /// to 0 or 255.
+    #[serde(default = "default_sdf_range")]
+    pub range: u32,
+    /// Channel values at or above this are inside the shape.
+    #[serde(default = "default_sdf_threshold")]
+    pub threshold: u8,
+}
+
+impl Default for SdfOptions {
+    fn default() -> Self {
+        Self {
+            mode: SdfMode::default(),
+            range: default_sdf_range(),
+            threshold: default_sdf_threshold(),
+        }
+    }
+}
+
+fn default_sdf_range() -> u32 {
+    8
+}
+fn default_sdf_threshold() -> u8 {
+    128
+}
+
+/// Channel layout of [`SdfOptions`].
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[serde(rename_all = "snake_case")]
+pub enum SdfMode {
+    /// One field of the alpha channel, written to all four channels.
+    #[default]
+    Single,
+    /// Independent fields of the red, green and blue channels plus the alpha field in alpha.
+    /// Inputs are expected to carry the edge-colored masks (e.g. from msdfgen's shape
+    /// coloring); a plain glyph yields three equal fields, as the corner-preserving coloring
+    /// needs vector outlines.
+    Multi,
+}
+
+impl FromStr for SdfMode {
+    type Err = ();
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "single" | "sdf" => Ok(Self::Single),
+            "multi" | "msdf" | "mtsdf" => Ok(Self::Multi),
+            _ => Err(()),
+        }
+    }
+}
+
+/// BCn block compression formats for DDS pages.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[serde(rename_all = "snake_case")]
+pub enum BcnFormat {
+    /// BC1 (DXT1): RGB with 1-bit alpha, 8 bytes per block.
+    Bc1,
+    /// BC3 (DXT5): RGB plus interpolated alpha, 16 bytes per block.
+    Bc3,
+    /// BC7: high-quality RGBA, 16 bytes per block.
+    Bc7,
+}
+
+impl FromStr for BcnFormat {
+    type Err = ();
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "bc1" | "dxt1" => Ok(Self::Bc1),
+            "bc3" | "dxt5" => Ok(Self::Bc3),
+            "bc7" => Ok(Self::Bc7),
+            _ => Err(()),
+        }
+    }
+}
+
+/// Basis Universal codecs for KTX2 pages.
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[serde(rename_all = "snake_case")]
+pub enum BasisCodec {
+    /// ETC1S with BasisLZ supercompression: small files, medium quality.
+    #[default]
+    Etc1s,
+    /// UASTC 4x4: near-BC7 quality, 8 bits per texel.
+    Uastc,
+}
+
+impl FromStr for BasisCodec {
+    type Err = ();
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "etc1s" => Ok(Self::Etc1s),
+            "uastc" => Ok(Self::Uastc),
+            _ => Err(()),
+        }
+    }
+}
+
+/// Case folding applied by [`KeyTransform`].
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[serde(rename_all = "snake_case")]
+pub enum KeyCase {
+    /// Leave the case unchanged.
+    #[default]
+    Keep,
+    Lower,
+    Upper,
+}
+
+impl FromStr for KeyCase {
+    type Err = ();
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "keep" | "none" => Ok(Self::Keep),
+            "lower" => Ok(Self::Lower),
+            "upper" => Ok(Self::Upper),
+            _ => Err(()),
+        }
+    }
+}
+
+/// Regex search/replace rule of a [`KeyTransform`]; `replacement` may use `$1` / `${name}`.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct KeyReplace {
+    pub pattern: String,
+    pub replacement: String,
+}
+
+/// Rules that rewrite input keys before packing; the result is the frame key in the atlas.
+///
+/// Steps run in field order: `strip_prefix`, `strip_directories`, `strip_extension`, each
+/// `replace` rule (all matches), then `case`. The default leaves keys unchanged.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct KeyTransform {
+    /// Literal prefix removed from keys that start with it (e.g. `"assets/sprites/"`).
+    #[serde(default)]
+    pub strip_prefix: Option<String>,
+    /// Keep only the file name (everything after the last `/` or `\`).
+    #[serde(default)]
+    pub strip_directories: bool,
+    /// Drop the file extension (`hero.png` -> `hero`; dotfiles are left alone).
+    #[serde(default)]
+    pub strip_extension: bool,
+    /// Regex search/replace rules, applied in order.
+    #[serde(default)]
+    pub replace: Vec<KeyReplace>,
+    #[serde(default)]
+    pub case: KeyCase,
+}
+
+impl KeyTransform {
+    /// File name without directories or extension (`ui/icons/hero.png` -> `hero`).
+    pub fn file_stem() -> Self {
+        Self {
+            strip_directories: true,
+            strip_extension: true,
+            ..Default::default()
+        }
+    }
+
+    /// File name without directories (`ui/icons/hero.png` -> `hero.png`).
+    pub fn file_name() -> Self {
+        Self {
+            strip_directories: true,
+            ..Default::default()
+        }
+    }
+
+    /// True if keys pass through unchanged.
+    pub fn is_identity(&self) -> bool {
+        *self == Self::default()
+    }
+
+    /// Rewrites one key. Fails only if a `replace` pattern is not a valid regex.
+    pub fn apply(&self, key: &str) -> crate::error::Result<String> {
+        Ok(self.compile()?.apply(key))
+    }
+
+    pub(crate) fn compile(&self) -> crate::error::Result<CompiledKeyTransform> {
+        let replace = self
+            .replace
+            .iter()
+            .map(|r| {
+                regex::Regex::new(&r.pattern)
+                    .map(|re| (re, r.replacement.clone()))
+                    .map_err(|e| {
+                        crate::error::TexPackerError::InvalidConfig(format!(
+                            "key_transform pattern '{}': {}",
+                            r.pattern, e
+                        ))
+                    })
+            })
+            .collect::<crate::error::Result<Vec<_>>>()?;
+        Ok(CompiledKeyTransform {
+            rules: self.clone(),
+            replace,
+        })
+    }
+}
+
+/// [`KeyTransform`] with its regexes compiled once per packing run.
+#[derive(Debug, Clone)]
+pub(crate) struct CompiledKeyTransform {
+    rules: KeyTransform,
+    replace: Vec<(regex::Regex, String)>,
+}
+
+impl CompiledKeyTransform {
+    pub(crate) fn apply(&self, key: &str) -> String {
+        if self.rules.is_identity() {
+            return key.to_string();
+        }
+        let mut k = key;
+        if let Some(p) = self.rules.strip_prefix.as_deref() {
+            k = k.strip_prefix(p).unwrap_or(k);
+        }
+        if self.rules.strip_directories {
+            k = k.rsplit(['/', '\\']).next().unwrap_or(k);
+        }
+        if self.rules.strip_extension {
+            let name_start = k.rfind(['/', '\\']).map_or(0, |i| i + 1);
+            if let Some(dot) = k[name_start..].rfind('.')
+                && dot > 0
+            {
+                k = &k[..name_start + dot];
+            }
+        }
+        let mut out = k.to_string();
+        for (re, rep) in &self.replace {
+            out = re.replace_all(&out, rep.as_str()).into_owned();
+        }
+        match self.rules.case {
+            KeyCase::Keep => out,
+            KeyCase::Lower => out.to_lowercase(),
+            KeyCase::Upper => out.to_uppercase(),
+        }
+    }
+}
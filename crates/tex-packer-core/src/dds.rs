@@ -0,0 +1,62 @@
+use crate::bcn::compress_bcn;
+use crate::config::BcnFormat;
+use crate::error::{Result, TexPackerError};
+use image::RgbaImage;
+
+const DDS_MAGIC: &[u8; 4] = b"DDS ";
+// DDS_HEADER.dwFlags: CAPS | HEIGHT | WIDTH | PIXELFORMAT | MIPMAPCOUNT | LINEARSIZE
+const DDSD_FLAGS: u32 = 0x1 | 0x2 | 0x4 | 0x1000 | 0x20000 | 0x80000;
+const DDPF_FOURCC: u32 = 0x4;
+const DDSCAPS_TEXTURE: u32 = 0x1000;
+const DXGI_FORMAT_BC7_UNORM: u32 = 98;
+const D3D10_RESOURCE_DIMENSION_TEXTURE2D: u32 = 3;
+
+/// Encode an RGBA page as a single-level, block-compressed DDS texture.
+///
+/// BC1/BC3 use the legacy `DXT1`/`DXT5` FourCC header; BC7 uses the DX10 extension header.
+/// The stored size is rounded up to multiples of 4 (edge texels repeat), so pages packed with
+/// `PageFormat::Dds` already have block-aligned dimensions.
+pub fn encode_dds(rgba: &RgbaImage, format: BcnFormat) -> Result<Vec<u8>> {
+    let (width, height) = rgba.dimensions();
+    if width == 0 || height == 0 {
+        return Err(TexPackerError::InvalidDimensions { width, height });
+    }
+    let (width, height) = (width.next_multiple_of(4), height.next_multiple_of(4));
+    let blocks = compress_bcn(rgba, format);
+    let four_cc = match format {
+        BcnFormat::Bc1 => b"DXT1",
+        BcnFormat::Bc3 => b"DXT5",
+        BcnFormat::Bc7 => b"DX10",
+    };
+
+    let mut out = Vec::with_capacity(4 + 124 + 20 + blocks.len());
+    out.extend_from_slice(DDS_MAGIC);
+    let header: [u32; 7] = [124, DDSD_FLAGS, height, width, blocks.len() as u32, 0, 1];
+    for v in header {
+        out.extend_from_slice(&v.to_le_bytes());
+    }
+    // dwReserved1[11]
+    out.resize(out.len() + 11 * 4, 0);
+    // DDS_PIXELFORMAT: size, flags, fourCC, then bit count and masks (unused)
+    out.extend_from_slice(&32u32.to_le_bytes());
+    out.extend_from_slice(&DDPF_FOURCC.to_le_bytes());
+    out.extend_from_slice(four_cc);
+    out.resize(out.len() + 5 * 4, 0);
+    // dwCaps, dwCaps2..4, dwReserved2
+    out.extend_from_slice(&DDSCAPS_TEXTURE.to_le_bytes());
+    out.resize(out.len() + 4 * 4, 0);
+    if format == BcnFormat::Bc7 {
+        // DDS_HEADER_DXT10: format, dimension, misc flags, array size, misc flags 2
+        for v in [
+            DXGI_FORMAT_BC7_UNORM,
+            D3D10_RESOURCE_DIMENSION_TEXTURE2D,
+            0,
+            1,
+            0,
+        ] {
+            out.extend_from_slice(&v.to_le_bytes());
+        }
+    }
+    out.extend_from_slice(&blocks);
+    Ok(out)
+}
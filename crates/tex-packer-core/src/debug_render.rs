@@ -0,0 +1,159 @@
+use crate::error::Result;
+use crate::model::{Frame, Meta, Page};
+use crate::pipeline::OutputPage;
+use image::{Rgba, RgbaImage};
+
+const FREE: Rgba<u8> = Rgba([40, 200, 80, 255]);
+const EMPTY: Rgba<u8> = Rgba([24, 24, 24, 255]);
+const BORDER: Rgba<u8> = Rgba([90, 90, 90, 255]);
+const CONTENT: Rgba<u8> = Rgba([70, 90, 140, 255]);
+const CONTENT_EDGE: Rgba<u8> = Rgba([30, 40, 70, 255]);
+const RESERVED: Rgba<u8> = Rgba([230, 180, 40, 255]);
+const PADDING: Rgba<u8> = Rgba([60, 200, 220, 255]);
+const EXTRUSION: Rgba<u8> = Rgba([220, 60, 200, 255]);
+
+/// Debug visualizations of one packed page.
+pub struct DebugRenders {
+    /// Free-space overlay: unused page area in green, frames in blue, padding/extrusion in amber.
+    pub free_space: RgbaImage,
+    /// Per-frame waste heat map: each frame slot colored green (no waste) to red (all waste).
+    pub waste_heatmap: RgbaImage,
+    /// Padding/extrusion visualization: page pixels with padding (cyan), extrusion (magenta)
+    /// and border padding (gray) bands drawn around them.
+    pub padding: RgbaImage,
+}
+
+/// Render all debug visualizations for a page (low-memory pages are loaded from scratch).
+pub fn render_debug(page: &OutputPage, meta: &Meta) -> Result<DebugRenders> {
+    let rgba = page.pixels()?;
+    Ok(DebugRenders {
+        free_space: render_free_space(&page.page, meta),
+        waste_heatmap: render_waste_heatmap(&page.page, &rgba, meta),
+        padding: render_padding(&page.page, &rgba, meta),
+    })
+}
+
+/// Free-space overlay. Needs only the layout, so it also works for layout-only atlases.
+pub fn render_free_space<K>(page: &Page<K>, meta: &Meta) -> RgbaImage {
+    let mut img = RgbaImage::from_pixel(page.width, page.height, FREE);
+    draw_border(&mut img, meta.padding.0);
+    for f in &page.frames {
+        let (x, y, w, h) = slot_rect(f, meta);
+        fill_rect(&mut img, x, y, w, h, RESERVED);
+        let r = f.frame;
+        fill_rect(&mut img, r.x, r.y, r.w, r.h, CONTENT);
+        outline_rect(&mut img, r.x, r.y, r.w, r.h, CONTENT_EDGE);
+    }
+    img
+}
+
+/// Per-frame waste heat map. A frame's waste is the share of its slot (content plus
+/// padding/extrusion) that carries no visible pixel (alpha == 0 in `rgba`).
+pub fn render_waste_heatmap<K>(page: &Page<K>, rgba: &RgbaImage, meta: &Meta) -> RgbaImage {
+    let mut img = RgbaImage::from_pixel(page.width, page.height, EMPTY);
+    draw_border(&mut img, meta.padding.0);
+    for f in &page.frames {
+        let (x, y, w, h) = slot_rect(f, meta);
+        let slot_area = w as u64 * h as u64;
+        let r = f.frame;
+        let mut visible = 0u64;
+        for py in r.y..(r.y + r.h).min(rgba.height()) {
+            for px in r.x..(r.x + r.w).min(rgba.width()) {
+                if rgba.get_pixel(px, py)[3] > 0 {
+                    visible += 1;
+                }
+            }
+        }
+        let waste = if slot_area > 0 {
+            1.0 - visible as f32 / slot_area as f32
+        } else {
+            0.0
+        };
+        fill_rect(&mut img, x, y, w, h, heat_color(waste));
+        outline_rect(&mut img, r.x, r.y, r.w, r.h, CONTENT_EDGE);
+    }
+    img
+}
+
+/// Padding/extrusion visualization over the composited page pixels.
+pub fn render_padding<K>(page: &Page<K>, rgba: &RgbaImage, meta: &Meta) -> RgbaImage {
+    let mut img = RgbaImage::from_pixel(page.width, page.height, EMPTY);
+    draw_border(&mut img, meta.padding.0);
+    let e = meta.extrude;
+    for f in &page.frames {
+        let (x, y, w, h) = slot_rect(f, meta);
+        fill_rect(&mut img, x, y, w, h, PADDING);
+        let r = f.frame;
+        fill_rect(
+            &mut img,
+            r.x.saturating_sub(e),
+            r.y.saturating_sub(e),
+            r.w + 2 * e,
+            r.h + 2 * e,
+            EXTRUSION,
+        );
+        // Content: page pixels composited over a mid gray so transparent areas stay visible.
+        for py in r.y..(r.y + r.h).min(rgba.height().min(img.height())) {
+            for px in r.x..(r.x + r.w).min(rgba.width().min(img.width())) {
+                let p = rgba.get_pixel(px, py);
+                let a = p[3] as u32;
+                let mix = |c: u8| ((c as u32 * a + 128 * (255 - a)) / 255) as u8;
+                img.put_pixel(px, py, Rgba([mix(p[0]), mix(p[1]), mix(p[2]), 255]));
+            }
+        }
+    }
+    img
+}
+
+/// Slot reserved by a frame: content plus extrusion and texture padding, offset like the packers
+/// (extrusion + half padding before the content, the rest after it).
+fn slot_rect<K>(f: &Frame<K>, meta: &Meta) -> (u32, u32, u32, u32) {
+    let pad = meta.padding.1;
+    let before = meta.extrude + pad / 2;
+    let after = meta.extrude + (pad - pad / 2);
+    let r = f.frame;
+    let x = r.x.saturating_sub(before);
+    let y = r.y.saturating_sub(before);
+    (x, y, r.x + r.w + after - x, r.y + r.h + after - y)
+}
+
+/// Green (0.0) through yellow to red (1.0).
+fn heat_color(t: f32) -> Rgba<u8> {
+    let t = t.clamp(0.0, 1.0);
+    let (r, g) = if t < 0.5 {
+        (t * 2.0, 1.0)
+    } else {
+        (1.0, (1.0 - t) * 2.0)
+    };
+    Rgba([(r * 255.0) as u8, (g * 200.0) as u8, 40, 255])
+}
+
+fn draw_border(img: &mut RgbaImage, border: u32) {
+    if border == 0 {
+        return;
+    }
+    let (w, h) = img.dimensions();
+    fill_rect(img, 0, 0, w, border, BORDER);
+    fill_rect(img, 0, h.saturating_sub(border), w, border, BORDER);
+    fill_rect(img, 0, 0, border, h, BORDER);
+    fill_rect(img, w.saturating_sub(border), 0, border, h, BORDER);
+}
+
+fn fill_rect(img: &mut RgbaImage, x: u32, y: u32, w: u32, h: u32, color: Rgba<u8>) {
+    let (iw, ih) = img.dimensions();
+    for py in y..y.saturating_add(h).min(ih) {
+        for px in x..x.saturating_add(w).min(iw) {
+            img.put_pixel(px, py, color);
+        }
+    }
+}
+
+fn outline_rect(img: &mut RgbaImage, x: u32, y: u32, w: u32, h: u32, color: Rgba<u8>) {
+    if w == 0 || h == 0 {
+        return;
+    }
+    fill_rect(img, x, y, w, 1, color);
+    fill_rect(img, x, y + h - 1, w, 1, color);
+    fill_rect(img, x, y, 1, h, color);
+    fill_rect(img, x + w - 1, y, 1, h, color);
+}
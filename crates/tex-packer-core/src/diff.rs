@@ -0,0 +1,168 @@
+//! Structural comparison of two atlases (e.g. before/after an asset change in CI).
+
+use crate::model::{Atlas, Frame, Rect};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// Where and how a frame sits in an atlas.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FramePlacement {
+    /// Page id (`Page::id`).
+    pub page: usize,
+    /// Placed rectangle within the page.
+    pub frame: Rect,
+    pub rotated: bool,
+    /// Trimmed source rectangle within the original image.
+    pub source: Rect,
+    /// Original (untrimmed) image size.
+    pub source_size: (u32, u32),
+}
+
+impl FramePlacement {
+    fn of<K>(page: usize, f: &Frame<K>) -> Self {
+        Self {
+            page,
+            frame: f.frame,
+            rotated: f.rotated,
+            source: f.source,
+            source_size: f.source_size,
+        }
+    }
+}
+
+/// A frame present in both atlases whose placement differs.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FrameChange<K = String> {
+    pub key: K,
+    pub old: FramePlacement,
+    pub new: FramePlacement,
+}
+
+/// Differences between two atlases, keyed by frame key. All frame lists are sorted by key.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AtlasDiff<K = String> {
+    /// Keys only in the new atlas.
+    pub added: Vec<K>,
+    /// Keys only in the old atlas.
+    pub removed: Vec<K>,
+    /// Same size, but a different page, position or rotation.
+    pub moved: Vec<FrameChange<K>>,
+    /// Trimmed or original size changed (the frame usually moved as well).
+    pub resized: Vec<FrameChange<K>>,
+    /// Same placement and size, but a different `content_hash` (only when both sides have one).
+    pub content_changed: Vec<K>,
+    pub old_pages: usize,
+    pub new_pages: usize,
+    pub old_page_area: u64,
+    pub new_page_area: u64,
+    /// Occupancy (0.0 to 1.0) of the old and new atlas.
+    pub old_occupancy: f64,
+    pub new_occupancy: f64,
+}
+
+impl<K> AtlasDiff<K> {
+    /// True if no frame was added, removed, moved, resized or changed and the page count and
+    /// area are the same.
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty()
+            && self.removed.is_empty()
+            && self.moved.is_empty()
+            && self.resized.is_empty()
+            && self.content_changed.is_empty()
+            && self.old_pages == self.new_pages
+            && self.old_page_area == self.new_page_area
+    }
+
+    /// New minus old page count.
+    pub fn page_delta(&self) -> i64 {
+        self.new_pages as i64 - self.old_pages as i64
+    }
+
+    /// New minus old occupancy (-1.0 to 1.0).
+    pub fn occupancy_delta(&self) -> f64 {
+        self.new_occupancy - self.old_occupancy
+    }
+
+    /// Returns a one-line human-readable summary.
+    pub fn summary(&self) -> String {
+        format!(
+            "Pages: {} -> {} ({:+}), Occupancy: {:.2}% -> {:.2}% ({:+.2}%), Added: {}, Removed: {}, Moved: {}, Resized: {}, Content changed: {}",
+            self.old_pages,
+            self.new_pages,
+            self.page_delta(),
+            self.old_occupancy * 100.0,
+            self.new_occupancy * 100.0,
+            self.occupancy_delta() * 100.0,
+            self.added.len(),
+            self.removed.len(),
+            self.moved.len(),
+            self.resized.len(),
+            self.content_changed.len(),
+        )
+    }
+}
+
+/// Compares `old` against `new` frame by frame (matched on key) and page by page.
+pub fn diff_atlases<K: Ord + Clone>(old: &Atlas<K>, new: &Atlas<K>) -> AtlasDiff<K> {
+    let index = |atlas: &Atlas<K>| -> BTreeMap<K, (FramePlacement, Option<u64>)> {
+        atlas
+            .pages
+            .iter()
+            .flat_map(|p| {
+                p.frames
+                    .iter()
+                    .map(move |f| (f.key.clone(), (FramePlacement::of(p.id, f), f.content_hash)))
+            })
+            .collect()
+    };
+    let a = index(old);
+    let b = index(new);
+
+    let mut added = Vec::new();
+    let mut removed = Vec::new();
+    let mut moved = Vec::new();
+    let mut resized = Vec::new();
+    let mut content_changed = Vec::new();
+    for (key, (pa, ha)) in &a {
+        let Some((pb, hb)) = b.get(key) else {
+            removed.push(key.clone());
+            continue;
+        };
+        let change = || FrameChange {
+            key: key.clone(),
+            old: *pa,
+            new: *pb,
+        };
+        if (pa.source.w, pa.source.h) != (pb.source.w, pb.source.h)
+            || pa.source_size != pb.source_size
+        {
+            resized.push(change());
+        } else if pa.page != pb.page
+            || (pa.frame.x, pa.frame.y) != (pb.frame.x, pb.frame.y)
+            || pa.rotated != pb.rotated
+        {
+            moved.push(change());
+        } else if let (Some(ha), Some(hb)) = (ha, hb)
+            && ha != hb
+        {
+            content_changed.push(key.clone());
+        }
+    }
+    added.extend(b.keys().filter(|k| !a.contains_key(k)).cloned());
+
+    let sa = old.stats();
+    let sb = new.stats();
+    AtlasDiff {
+        added,
+        removed,
+        moved,
+        resized,
+        content_changed,
+        old_pages: sa.num_pages,
+        new_pages: sb.num_pages,
+        old_page_area: sa.total_page_area,
+        new_page_area: sb.total_page_area,
+        old_occupancy: sa.occupancy,
+        new_occupancy: sb.occupancy,
+    }
+}
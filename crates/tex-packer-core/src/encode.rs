@@ -0,0 +1,129 @@
+use crate::config::{PageEncoding, PageFormat};
+use crate::error::{Result, TexPackerError};
+use image::codecs::avif::AvifEncoder;
+use image::codecs::jpeg::JpegEncoder;
+use image::codecs::openexr::OpenExrEncoder;
+use image::codecs::png::PngEncoder;
+use image::codecs::webp::WebPEncoder;
+use image::{DynamicImage, ExtendedColorType, ImageEncoder, Rgba32FImage, RgbaImage};
+
+/// Encode an RGBA page as PNG, JPEG, WebP, AVIF or OpenEXR with the settings in `enc`.
+///
+/// PNG output is recompressed with [`optimize_png`] when `enc.png_optimize` is set, which
+/// needs the `oxipng` feature.
+///
+/// JPEG has no alpha channel: transparent areas keep whatever color they hold (black unless
+/// `background_color` or alpha bleed filled them). KTX2 and DDS pages go through
+/// `encode_ktx2` / `encode_dds` instead.
+pub fn encode_page(rgba: &RgbaImage, format: PageFormat, enc: &PageEncoding) -> Result<Vec<u8>> {
+    let (width, height) = rgba.dimensions();
+    if width == 0 || height == 0 {
+        return Err(TexPackerError::InvalidDimensions { width, height });
+    }
+    let mut out = Vec::new();
+    match format {
+        PageFormat::Png => {
+            PngEncoder::new(&mut out).write_image(
+                rgba.as_raw(),
+                width,
+                height,
+                ExtendedColorType::Rgba8,
+            )?;
+            if let Some(level) = enc.png_optimize {
+                out = optimize_png(&out, level)?;
+            }
+        }
+        PageFormat::Jpeg => {
+            let rgb: Vec<u8> = rgba
+                .as_raw()
+                .chunks_exact(4)
+                .flat_map(|p| [p[0], p[1], p[2]])
+                .collect();
+            JpegEncoder::new_with_quality(&mut out, enc.quality).write_image(
+                &rgb,
+                width,
+                height,
+                ExtendedColorType::Rgb8,
+            )?;
+        }
+        PageFormat::Webp => {
+            let quantized;
+            let pixels = if enc.lossless {
+                rgba
+            } else {
+                quantized = near_lossless(rgba, enc.quality);
+                &quantized
+            };
+            WebPEncoder::new_lossless(&mut out).write_image(
+                pixels.as_raw(),
+                width,
+                height,
+                ExtendedColorType::Rgba8,
+            )?;
+        }
+        PageFormat::Avif => AvifEncoder::new_with_speed_quality(&mut out, enc.speed, enc.quality)
+            .write_image(rgba.as_raw(), width, height, ExtendedColorType::Rgba8)?,
+        PageFormat::Exr => {
+            out = encode_exr(&DynamicImage::ImageRgba8(rgba.clone()).into_rgba32f())?;
+        }
+        PageFormat::Ktx2 | PageFormat::Dds => {
+            return Err(TexPackerError::InvalidConfig(format!(
+                "{format:?} pages are encoded with encode_ktx2 / encode_dds"
+            )));
+        }
+    }
+    Ok(out)
+}
+
+/// Encode a 32-bit float RGBA page (e.g. from `pack_images_hdr`) as OpenEXR.
+pub fn encode_exr(rgba: &Rgba32FImage) -> Result<Vec<u8>> {
+    let (width, height) = rgba.dimensions();
+    if width == 0 || height == 0 {
+        return Err(TexPackerError::InvalidDimensions { width, height });
+    }
+    let mut out = std::io::Cursor::new(Vec::new());
+    let raw: Vec<u8> = rgba.as_raw().iter().flat_map(|v| v.to_ne_bytes()).collect();
+    OpenExrEncoder::new(&mut out).write_image(&raw, width, height, ExtendedColorType::Rgba32F)?;
+    Ok(out.into_inner())
+}
+
+/// Losslessly recompress an encoded PNG with oxipng at preset `level` (0-6; higher is slower
+/// and usually smaller). The result is never larger than the input.
+#[cfg(feature = "oxipng")]
+pub fn optimize_png(png: &[u8], level: u8) -> Result<Vec<u8>> {
+    let opts = oxipng::Options::from_preset(level.min(6));
+    let optimized = oxipng::optimize_from_memory(png, &opts)
+        .map_err(|e| TexPackerError::Encode(format!("oxipng: {e}")))?;
+    Ok(if optimized.len() < png.len() {
+        optimized
+    } else {
+        png.to_vec()
+    })
+}
+
+/// Always fails: this build lacks the `oxipng` feature.
+#[cfg(not(feature = "oxipng"))]
+pub fn optimize_png(_png: &[u8], _level: u8) -> Result<Vec<u8>> {
+    Err(TexPackerError::InvalidConfig(
+        "page_encoding.png_optimize requires the `oxipng` feature".into(),
+    ))
+}
+
+/// Rounds color channels to a grid that coarsens as `quality` drops (1 bit dropped per 25
+/// quality points below 100, up to 4), so the lossless encoder finds longer runs. Alpha is
+/// kept exact.
+fn near_lossless(rgba: &RgbaImage, quality: u8) -> RgbaImage {
+    let bits = (100u32.saturating_sub(quality as u32)).div_ceil(25).min(4);
+    let mut out = rgba.clone();
+    if bits == 0 {
+        return out;
+    }
+    let step = 1u32 << bits;
+    for p in out.pixels_mut() {
+        for c in &mut p.0[..3] {
+            let v = (*c as u32 + step / 2) / step * step;
+            *c = v.min(255) as u8;
+        }
+    }
+    out
+}
@@ -14,6 +14,13 @@ pub enum TexPackerError {
     #[error("Invalid configuration: {0}")]
     InvalidConfig(String),
 
+    #[error("Invalid value '{value}' for `{field}`: expected {expected}")]
+    InvalidOption {
+        field: String,
+        value: String,
+        expected: String,
+    },
+
     #[error(
         "Texture '{key}' ({width}x{height}) exceeds maximum atlas dimensions ({max_width}x{max_height})"
     )]
@@ -40,9 +47,25 @@ pub enum TexPackerError {
     )]
     OutOfSpaceGeneric { placed: usize, total: usize },
 
+    #[error(
+        "Too many pages: {scope} needs more than {max_pages} page(s) (placed {placed}/{total} textures)"
+    )]
+    TooManyPages {
+        scope: String,
+        max_pages: usize,
+        placed: usize,
+        total: usize,
+    },
+
+    #[error("Duplicate key '{0}': two inputs map to the same frame key")]
+    DuplicateKey(String),
+
     #[error("Nothing to pack: input list is empty")]
     Empty,
 
+    #[error("Packing was cancelled")]
+    Cancelled,
+
     #[error("Encoding error: {0}")]
     Encode(String),
 
@@ -1,10 +1,50 @@
-use crate::model::Atlas;
-use serde::Serialize;
+use crate::config::RotationDirection;
+use crate::error::{Result, TexPackerError};
+use crate::model::{Atlas, Frame, Meta, PackStats, Pivot};
+use serde::{Deserialize, Serialize};
 use serde_json::{Value, json};
 
+/// Optional extras of the `json-array` / `json-hash` exports.
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq)]
+#[serde(default)]
+pub struct JsonOptions {
+    /// Add `uv: {u0, v0, u1, v1}`, the frame rect normalized to its page (0..1, v down).
+    pub uvs: bool,
+    /// Texels to shrink each UV edge by; 0.5 keeps linear filtering from reaching neighbours.
+    pub uv_inset: f32,
+}
+
+impl JsonOptions {
+    /// Adds `uv` for `fr` on a `page_w`x`page_h` page when enabled.
+    fn insert_uv<K>(&self, v: &mut Value, fr: &Frame<K>, page_w: u32, page_h: u32) {
+        if !self.uvs || page_w == 0 || page_h == 0 {
+            return;
+        }
+        let r = &fr.frame;
+        // Never let the inset cross the frame's center
+        let ix = self.uv_inset.min(r.w as f32 / 2.0) as f64;
+        let iy = self.uv_inset.min(r.h as f32 / 2.0) as f64;
+        let (pw, ph) = (page_w as f64, page_h as f64);
+        v["uv"] = json!({
+            "u0": (r.x as f64 + ix) / pw,
+            "v0": (r.y as f64 + iy) / ph,
+            "u1": ((r.x + r.w) as f64 - ix) / pw,
+            "v1": ((r.y + r.h) as f64 - iy) / ph,
+        });
+    }
+}
+
 /// Serialize the whole `Atlas` as a JSON object `{ pages, meta }` (array-of-pages style).
 /// Suitable for generic tooling and simple consumption.
 pub fn to_json_array<K: ToString + Clone + Serialize>(atlas: &Atlas<K>) -> Value {
+    to_json_array_with(atlas, &JsonOptions::default())
+}
+
+/// [`to_json_array`] with `options` (e.g. normalized UVs per frame).
+pub fn to_json_array_with<K: ToString + Clone + Serialize>(
+    atlas: &Atlas<K>,
+    options: &JsonOptions,
+) -> Value {
     // Build array-of-pages with per-frame fields using camelCase for source metadata,
     // consistent with the hash schema naming.
     let pages_val = atlas
@@ -18,16 +58,23 @@ pub fn to_json_array<K: ToString + Clone + Serialize>(atlas: &Atlas<K>) -> Value
                     let frame = json!({"x": fr.frame.x, "y": fr.frame.y, "w": fr.frame.w, "h": fr.frame.h});
                     let sprite_source_size = json!({"x": fr.source.x, "y": fr.source.y, "w": fr.source.w, "h": fr.source.h});
                     let source_size = json!({"w": fr.source_size.0, "h": fr.source_size.1});
-                    let pivot = json!({"x": 0.5, "y": 0.5});
-                    json!({
+                    let pivot = json!({"x": fr.pivot.x, "y": fr.pivot.y});
+                    let mut v = json!({
                         "key": fr.key.to_string(),
+                        "id": fr.id,
                         "frame": frame,
                         "rotated": fr.rotated,
                         "trimmed": fr.trimmed,
                         "spriteSourceSize": sprite_source_size,
                         "sourceSize": source_size,
                         "pivot": pivot
-                    })
+                    });
+                    insert_degenerate(&mut v, fr);
+                    insert_content_hash(&mut v, fr);
+                    insert_nine_patch(&mut v, fr);
+                    insert_scale(&mut v, fr);
+                    options.insert_uv(&mut v, fr, p.width, p.height);
+                    v
                 })
                 .collect();
             json!({
@@ -45,6 +92,11 @@ pub fn to_json_array<K: ToString + Clone + Serialize>(atlas: &Atlas<K>) -> Value
 /// Shape: `{ frames: { name: { frame, rotated, trimmed, spriteSourceSize, sourceSize, pivot, page, pageSize } }, meta }`.
 /// Compatible with many engine pipelines expecting TexturePacker-like JSON hash.
 pub fn to_json_hash<K: ToString + Clone>(atlas: &Atlas<K>) -> Value {
+    to_json_hash_with(atlas, &JsonOptions::default())
+}
+
+/// [`to_json_hash`] with `options` (e.g. normalized UVs per frame).
+pub fn to_json_hash_with<K: ToString + Clone>(atlas: &Atlas<K>, options: &JsonOptions) -> Value {
     // Flatten frames keyed by name, include page info
     let mut frames = serde_json::Map::new();
     for page in &atlas.pages {
@@ -54,21 +106,597 @@ pub fn to_json_hash<K: ToString + Clone>(atlas: &Atlas<K>) -> Value {
             let sprite_source_size =
                 json!({"x": fr.source.x, "y": fr.source.y, "w": fr.source.w, "h": fr.source.h});
             let source_size = json!({"w": fr.source_size.0, "h": fr.source_size.1});
-            let pivot = json!({"x": 0.5, "y": 0.5});
-            frames.insert(
-                key,
-                json!({
-                    "frame": frame,
-                    "rotated": fr.rotated,
-                    "trimmed": fr.trimmed,
-                    "spriteSourceSize": sprite_source_size,
-                    "sourceSize": source_size,
-                    "pivot": pivot,
-                    "page": page.id,
-                    "pageSize": {"w": page.width, "h": page.height},
-                }),
-            );
+            let pivot = json!({"x": fr.pivot.x, "y": fr.pivot.y});
+            let mut v = json!({
+                "id": fr.id,
+                "frame": frame,
+                "rotated": fr.rotated,
+                "trimmed": fr.trimmed,
+                "spriteSourceSize": sprite_source_size,
+                "sourceSize": source_size,
+                "pivot": pivot,
+                "page": page.id,
+                "pageSize": {"w": page.width, "h": page.height},
+            });
+            insert_degenerate(&mut v, fr);
+            insert_content_hash(&mut v, fr);
+            insert_nine_patch(&mut v, fr);
+            insert_scale(&mut v, fr);
+            options.insert_uv(&mut v, fr, page.width, page.height);
+            frames.insert(key, v);
         }
     }
     json!({ "frames": frames, "meta": &atlas.meta })
 }
+
+/// Packing statistics as the stats JSON document the CLI (`--export-stats`) and GUI write.
+pub fn to_stats_json(stats: &PackStats) -> Value {
+    json!({
+        "pages": stats.num_pages,
+        "frames": stats.num_frames,
+        "used_area": stats.used_frame_area,
+        "total_area": stats.total_page_area,
+        "occupancy": stats.occupancy,
+        "lower_bound_area": stats.lower_bound_area,
+        "lower_bound_pages": stats.lower_bound_pages,
+        "bound_efficiency": stats.bound_efficiency,
+        "rotated": stats.num_rotated,
+        "trimmed": stats.num_trimmed,
+        "trimmed_pixels_saved": stats.trimmed_pixels_saved,
+        "padding_overhead": stats.padding_overhead,
+        "winning_config": stats.winning_config,
+        "auto_refinement": stats.auto_refinement,
+        "page_stats": stats.pages,
+        "timings_ms": stats.timings,
+    })
+}
+
+/// File names of one page of a multi-pack export: its texture and its data file.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub struct PackFile {
+    /// Page texture, written to `meta.image`.
+    pub image: String,
+    /// Data file (the JSON document itself), listed in the other pages' `meta.related_multi_packs`.
+    pub data: String,
+}
+
+/// Checks that `atlas` fits a `format` whose `rotated` flag always means a 90° clockwise turn
+/// (TexturePacker JSON, plist, libGDX, Spine, Aseprite). Fails with
+/// [`TexPackerError::InvalidInput`] naming the first frame rotated counter-clockwise; call it
+/// before those exporters, which write the flag as is.
+pub fn require_clockwise<K: ToString>(atlas: &Atlas<K>, format: &str) -> Result<()> {
+    if atlas.meta.rotation_direction == RotationDirection::Clockwise {
+        return Ok(());
+    }
+    match atlas
+        .pages
+        .iter()
+        .flat_map(|p| &p.frames)
+        .find(|f| f.rotated)
+    {
+        Some(f) => Err(TexPackerError::InvalidInput(format!(
+            "{format} only describes clockwise rotation, but frame '{}' was rotated counter-clockwise; pack with rotation_direction: clockwise",
+            f.key.to_string()
+        ))),
+        None => Ok(()),
+    }
+}
+
+/// TexturePacker-conformant JSON hash for one page (`files[page_index]`), as read by Pixi.js'
+/// `Spritesheet` and Phaser's `load.atlas`.
+///
+/// Unlike [`to_json_hash`], each page is its own document with `meta.image`, `meta.size` and
+/// `meta.scale`, fields follow TexturePacker's order, rotated frames record their unrotated
+/// size, and tex-packer extensions (`id`, `page`, `contentHash`, ...) are left out. With several
+/// pages, `meta.related_multi_packs` lists the other pages' data files so Pixi loads them too.
+///
+/// Returns the pretty-printed document: the field and frame order are part of the format and a
+/// [`Value`] would not keep them.
+pub fn to_json_hash_conformant<K: ToString>(
+    atlas: &Atlas<K>,
+    page_index: usize,
+    files: &[PackFile],
+) -> String {
+    let frames = FrameMap(
+        page_frames(atlas, page_index)
+            .map(|fr| (fr.key.to_string(), conformant_frame(fr, None)))
+            .collect(),
+    );
+    conformant_document(frames, atlas, page_index, files)
+}
+
+/// TexturePacker-conformant JSON array for one page: like [`to_json_hash_conformant`], with
+/// `frames` as an array of entries named by `filename`.
+pub fn to_json_array_conformant<K: ToString>(
+    atlas: &Atlas<K>,
+    page_index: usize,
+    files: &[PackFile],
+) -> String {
+    let frames: Vec<ConformantFrame> = page_frames(atlas, page_index)
+        .map(|fr| conformant_frame(fr, Some(fr.key.to_string())))
+        .collect();
+    conformant_document(frames, atlas, page_index, files)
+}
+
+fn page_frames<K>(atlas: &Atlas<K>, page_index: usize) -> impl Iterator<Item = &Frame<K>> {
+    atlas
+        .pages
+        .get(page_index)
+        .into_iter()
+        .flat_map(|p| &p.frames)
+}
+
+// The conformant documents are written through these structs rather than `json!` so their
+// fields keep TexturePacker's order (serde_json sorts `Value` object keys).
+
+#[derive(Serialize)]
+struct ConformantDocument<'a, F> {
+    frames: F,
+    meta: ConformantMeta<'a>,
+}
+
+/// Frames keyed by name, in packing order.
+struct FrameMap(Vec<(String, ConformantFrame)>);
+
+impl Serialize for FrameMap {
+    fn serialize<S: serde::Serializer>(
+        &self,
+        serializer: S,
+    ) -> std::result::Result<S::Ok, S::Error> {
+        serializer.collect_map(self.0.iter().map(|(k, v)| (k, v)))
+    }
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct ConformantFrame {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    filename: Option<String>,
+    frame: ConformantRect,
+    rotated: bool,
+    trimmed: bool,
+    sprite_source_size: ConformantRect,
+    source_size: ConformantSize,
+    pivot: crate::model::Pivot,
+}
+
+#[derive(Serialize)]
+struct ConformantRect {
+    x: u32,
+    y: u32,
+    w: u32,
+    h: u32,
+}
+
+#[derive(Serialize)]
+struct ConformantSize {
+    w: u32,
+    h: u32,
+}
+
+#[derive(Serialize)]
+struct ConformantMeta<'a> {
+    app: &'a str,
+    version: &'a str,
+    image: &'a str,
+    format: &'static str,
+    size: ConformantSize,
+    scale: String,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    related_multi_packs: Vec<&'a str>,
+}
+
+fn conformant_frame<K>(fr: &Frame<K>, filename: Option<String>) -> ConformantFrame {
+    // TexturePacker stores the sprite's own size; loaders swap it back for rotated frames
+    let (w, h) = if fr.rotated {
+        (fr.frame.h, fr.frame.w)
+    } else {
+        (fr.frame.w, fr.frame.h)
+    };
+    ConformantFrame {
+        filename,
+        frame: ConformantRect {
+            x: fr.frame.x,
+            y: fr.frame.y,
+            w,
+            h,
+        },
+        rotated: fr.rotated,
+        trimmed: fr.trimmed,
+        sprite_source_size: ConformantRect {
+            x: fr.source.x,
+            y: fr.source.y,
+            w: fr.source.w,
+            h: fr.source.h,
+        },
+        source_size: ConformantSize {
+            w: fr.source_size.0,
+            h: fr.source_size.1,
+        },
+        pivot: fr.pivot,
+    }
+}
+
+fn conformant_document<K, F: Serialize>(
+    frames: F,
+    atlas: &Atlas<K>,
+    page_index: usize,
+    files: &[PackFile],
+) -> String {
+    let (w, h) = atlas
+        .pages
+        .get(page_index)
+        .map_or((0, 0), |p| (p.width, p.height));
+    let related_multi_packs = if files.len() > 1 {
+        files
+            .iter()
+            .enumerate()
+            .filter(|(i, _)| *i != page_index)
+            .map(|(_, f)| f.data.as_str())
+            .collect()
+    } else {
+        Vec::new()
+    };
+    let doc = ConformantDocument {
+        frames,
+        meta: ConformantMeta {
+            app: &atlas.meta.app,
+            version: &atlas.meta.version,
+            image: files.get(page_index).map_or("", |f| f.image.as_str()),
+            format: "RGBA8888",
+            size: ConformantSize { w, h },
+            scale: atlas.meta.scale.to_string(),
+            related_multi_packs,
+        },
+    };
+    serde_json::to_string_pretty(&doc).expect("conformant JSON documents always serialize")
+}
+
+/// Adds `degenerate: true` to degenerate frames; other frames omit the key.
+fn insert_degenerate<K>(v: &mut Value, fr: &Frame<K>) {
+    if fr.degenerate {
+        v["degenerate"] = json!(true);
+    }
+}
+
+/// Adds `contentHash` (16 hex digits) when the frame carries one.
+fn insert_content_hash<K>(v: &mut Value, fr: &Frame<K>) {
+    if let Some(h) = fr.content_hash {
+        v["contentHash"] = json!(format!("{h:016x}"));
+    }
+}
+
+/// Adds `ninePatch: {left, top, right, bottom}` when the frame has nine-patch insets.
+fn insert_nine_patch<K>(v: &mut Value, fr: &Frame<K>) {
+    if let Some(n) = fr.nine_patch {
+        v["ninePatch"] =
+            json!({"left": n.left, "top": n.top, "right": n.right, "bottom": n.bottom});
+    }
+}
+
+/// Adds `scale` when the frame was downscaled to fit its page.
+fn insert_scale<K>(v: &mut Value, fr: &Frame<K>) {
+    if let Some(s) = fr.scale {
+        // Via the shortest f32 text, so 0.42 does not widen to 0.41999998688697815
+        v["scale"] = json!(s.to_string().parse::<f64>().unwrap_or(s as f64));
+    }
+}
+
+/// `split: left, right, top, bottom` line of libGDX/Spine atlases (nine-patch regions).
+pub(crate) fn atlas_split_line<K>(fr: &Frame<K>) -> Option<String> {
+    fr.nine_patch.map(|n| {
+        format!(
+            "  split: {}, {}, {}, {}\n",
+            n.left, n.right, n.top, n.bottom
+        )
+    })
+}
+
+/// Build a libGDX (`gdx-texturepacker`) compatible `.atlas` text file.
+/// `page_names` are the texture filenames written as page headers, one per page in order.
+/// Region names drop the file extension; a trailing `_N` suffix becomes the region `index`
+/// (as gdx-texturepacker does for animations). `offset` is measured from the bottom-left.
+/// `rotate: true` stands for a clockwise turn (see [`require_clockwise`]).
+/// Page and region names are sanitized for the line-based parser (surrounding whitespace
+/// trimmed, line breaks and `:` replaced with `_`).
+pub fn to_gdx_atlas<K: ToString>(atlas: &Atlas<K>, page_names: &[String]) -> String {
+    write_atlas_text(atlas, page_names, AtlasNaming::Sequence)
+}
+
+/// Region naming of the libGDX-style `.atlas` writers.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum AtlasNaming {
+    /// gdx-texturepacker: a trailing `_N` suffix becomes the region `index`.
+    Sequence,
+    /// Spine: the whole stem names the region, `index: -1`.
+    Plain,
+}
+
+/// Shared writer behind [`to_gdx_atlas`] and [`to_spine_atlas`](crate::to_spine_atlas).
+pub(crate) fn write_atlas_text<K: ToString>(
+    atlas: &Atlas<K>,
+    page_names: &[String],
+    naming: AtlasNaming,
+) -> String {
+    let size_sep = match naming {
+        AtlasNaming::Sequence => ", ",
+        AtlasNaming::Plain => ",",
+    };
+    let mut s = String::new();
+    for (i, page) in atlas.pages.iter().enumerate() {
+        let image = page_names
+            .get(i)
+            .cloned()
+            .unwrap_or_else(|| format!("page_{}.png", page.id));
+        s.push('\n');
+        s.push_str(&format!("{}\n", sanitize_atlas_line(&image)));
+        s.push_str(&format!("size: {}{size_sep}{}\n", page.width, page.height));
+        s.push_str("format: RGBA8888\n");
+        s.push_str("filter: Linear,Linear\n");
+        s.push_str("repeat: none\n");
+        for fr in &page.frames {
+            let key = fr.key.to_string();
+            let (name, index) = match naming {
+                AtlasNaming::Sequence => sequence_name(&key),
+                AtlasNaming::Plain => (strip_extension(&key).to_string(), None),
+            };
+            // `size` is the unrotated packed size; the page footprint is swapped when rotated.
+            let (w, h) = if fr.rotated {
+                (fr.frame.h, fr.frame.w)
+            } else {
+                (fr.frame.w, fr.frame.h)
+            };
+            let offset_x = fr.source.x;
+            let offset_y = fr.source_size.1.saturating_sub(fr.source.y + fr.source.h);
+            s.push_str(&format!("{}\n", sanitize_atlas_line(&name)));
+            s.push_str(&format!("  rotate: {}\n", fr.rotated));
+            s.push_str(&format!("  xy: {}, {}\n", fr.frame.x, fr.frame.y));
+            s.push_str(&format!("  size: {}, {}\n", w, h));
+            if let Some(split) = atlas_split_line(fr) {
+                s.push_str(&split);
+            }
+            s.push_str(&format!(
+                "  orig: {}, {}\n",
+                fr.source_size.0, fr.source_size.1
+            ));
+            s.push_str(&format!("  offset: {}, {}\n", offset_x, offset_y));
+            s.push_str(&format!("  index: {}\n", index.map_or(-1, i64::from)));
+        }
+    }
+    s
+}
+
+/// The atlas parser trims every line, ends a page on an empty line and splits fields on `:`.
+fn sanitize_atlas_line(s: &str) -> String {
+    let cleaned: String = s
+        .chars()
+        .map(|c| match c {
+            '\r' | '\n' | ':' => '_',
+            c => c,
+        })
+        .collect();
+    let trimmed = cleaned.trim();
+    if trimmed.is_empty() {
+        "_".to_string()
+    } else {
+        trimmed.to_string()
+    }
+}
+
+/// Export one page as Aseprite-compatible JSON (`{ frames: [...], meta }`, array style).
+/// Animations (`meta.animations`, else `_N` key sequences of two or more frames, as in the
+/// Godot export) keep their frames contiguous and in order, and each becomes a
+/// `meta.frameTags` entry; other frames sort by key. Frame durations come from the animations
+/// (100 ms for other frames). Frames with a nine-patch or a non-center pivot get a
+/// `meta.slices` entry over their untrimmed bounds.
+pub fn to_aseprite_json<K: ToString>(atlas: &Atlas<K>, page_index: usize, image: &str) -> Value {
+    let Some(page) = atlas.pages.get(page_index) else {
+        return json!({
+            "frames": [],
+            "meta": aseprite_meta(&atlas.meta, image, 0, 0, Vec::new(), Vec::new()),
+        });
+    };
+    let groups = animation_groups(atlas);
+    // Key -> (animation, position, duration); a key in several animations keeps the first
+    let mut grouped: std::collections::HashMap<&str, (usize, u32, u32)> = Default::default();
+    for (g, (_, frames)) in groups.iter().enumerate() {
+        for (pos, (key, ms)) in frames.iter().enumerate() {
+            grouped.entry(key.as_str()).or_insert((g, pos as u32, *ms));
+        }
+    }
+    let mut entries: Vec<AsepriteEntry<K>> = page
+        .frames
+        .iter()
+        .map(|frame| {
+            let key = frame.key.to_string();
+            let (sort_name, sort_index, animation) = match grouped.get(key.as_str()) {
+                Some(&(g, pos, _)) => (groups[g].0.clone(), Some(pos), Some(g)),
+                None => {
+                    let (stem, index) = sequence_name(&key);
+                    (stem, index, None)
+                }
+            };
+            AsepriteEntry {
+                sort_name,
+                sort_index,
+                animation,
+                key,
+                frame,
+            }
+        })
+        .collect();
+    entries.sort_by(|a, b| {
+        (&a.sort_name, a.sort_index, &a.key).cmp(&(&b.sort_name, b.sort_index, &b.key))
+    });
+
+    let frames: Vec<Value> = entries
+        .iter()
+        .map(|AsepriteEntry { key, frame: fr, .. }| {
+            let duration = grouped.get(key.as_str()).map_or(100, |g| g.2);
+            json!({
+                "filename": key,
+                "frame": {"x": fr.frame.x, "y": fr.frame.y, "w": fr.frame.w, "h": fr.frame.h},
+                "rotated": fr.rotated,
+                "trimmed": fr.trimmed,
+                "spriteSourceSize": {"x": fr.source.x, "y": fr.source.y, "w": fr.source.w, "h": fr.source.h},
+                "sourceSize": {"w": fr.source_size.0, "h": fr.source_size.1},
+                "duration": duration,
+            })
+        })
+        .collect();
+
+    let mut tags = Vec::new();
+    let mut start = 0;
+    while start < entries.len() {
+        let mut end = start;
+        while end + 1 < entries.len() && entries[end + 1].animation == entries[start].animation {
+            end += 1;
+        }
+        if let Some(g) = entries[start].animation {
+            tags.push(json!({
+                "name": groups[g].0,
+                "from": start,
+                "to": end,
+                "direction": "forward",
+            }));
+        }
+        start = end + 1;
+    }
+
+    let slices = entries
+        .iter()
+        .enumerate()
+        .filter_map(|(i, e)| aseprite_slice(i, &e.key, e.frame))
+        .collect();
+
+    json!({
+        "frames": frames,
+        "meta": aseprite_meta(&atlas.meta, image, page.width, page.height, tags, slices),
+    })
+}
+
+/// A frame of [`to_aseprite_json`] with its position in the frame order.
+struct AsepriteEntry<'a, K> {
+    /// Animation name, else the `_N` sequence stem.
+    sort_name: String,
+    /// Position in the animation, else the `_N` index.
+    sort_index: Option<u32>,
+    /// Index into the animation groups.
+    animation: Option<usize>,
+    key: String,
+    frame: &'a Frame<K>,
+}
+
+/// Slice of frame `index` carrying its nine-patch as `center` and its pivot in pixels, both
+/// relative to the untrimmed bounds (the inverse of what the Aseprite reader imports).
+fn aseprite_slice<K>(index: usize, key: &str, fr: &Frame<K>) -> Option<Value> {
+    let has_pivot = fr.pivot != Pivot::CENTER;
+    if fr.nine_patch.is_none() && !has_pivot {
+        return None;
+    }
+    let (w, h) = fr.source_size;
+    let mut slice_key = json!({
+        "frame": index,
+        "bounds": {"x": 0, "y": 0, "w": w, "h": h},
+    });
+    if let Some(np) = fr.nine_patch {
+        slice_key["center"] = json!({
+            "x": np.left,
+            "y": np.top,
+            "w": w.saturating_sub(np.left + np.right),
+            "h": h.saturating_sub(np.top + np.bottom),
+        });
+    }
+    if has_pivot {
+        slice_key["pivot"] = json!({
+            "x": (fr.pivot.x * w as f64).round() as i64,
+            "y": (fr.pivot.y * h as f64).round() as i64,
+        });
+    }
+    Some(json!({
+        "name": key,
+        "color": "#0000ffff",
+        "keys": [slice_key],
+    }))
+}
+
+fn aseprite_meta(
+    meta: &Meta,
+    image: &str,
+    w: u32,
+    h: u32,
+    frame_tags: Vec<Value>,
+    slices: Vec<Value>,
+) -> Value {
+    json!({
+        "app": meta.app,
+        "version": meta.version,
+        "image": image,
+        "format": "RGBA8888",
+        "size": {"w": w, "h": h},
+        "scale": "1",
+        "frameTags": frame_tags,
+        "layers": [],
+        "slices": slices,
+    })
+}
+
+/// `(name, [(key, duration_ms)])`: `meta.animations`, or `_N` sequences when there are none.
+pub(crate) fn animation_groups<K: ToString>(atlas: &Atlas<K>) -> Vec<(String, Vec<(String, u32)>)> {
+    if !atlas.meta.animations.is_empty() {
+        return atlas
+            .meta
+            .animations
+            .iter()
+            .map(|a| {
+                let frames = a
+                    .frames
+                    .iter()
+                    .map(|f| (f.key.clone(), f.duration_ms))
+                    .collect();
+                (a.name.clone(), frames)
+            })
+            .collect();
+    }
+    let mut sequences: std::collections::BTreeMap<String, Vec<(u32, String)>> = Default::default();
+    for fr in atlas.pages.iter().flat_map(|p| &p.frames) {
+        let key = fr.key.to_string();
+        if let (stem, Some(index)) = sequence_name(&key) {
+            sequences.entry(stem).or_default().push((index, key));
+        }
+    }
+    sequences
+        .into_iter()
+        .filter(|(_, frames)| frames.len() > 1)
+        .map(|(name, mut frames)| {
+            frames.sort();
+            (name, frames.into_iter().map(|(_, k)| (k, 100)).collect())
+        })
+        .collect()
+}
+
+/// Split a frame key into a sequence name (extension stripped) and trailing `_N` index, if any.
+pub(crate) fn sequence_name(key: &str) -> (String, Option<u32>) {
+    let file_start = key.rfind('/').map(|i| i + 1).unwrap_or(0);
+    let stem = strip_extension(key);
+    if let Some(us) = stem.rfind('_') {
+        let digits = &stem[us + 1..];
+        if us > file_start
+            && !digits.is_empty()
+            && digits.bytes().all(|b| b.is_ascii_digit())
+            && let Ok(index) = digits.parse::<u32>()
+        {
+            return (stem[..us].to_string(), Some(index));
+        }
+    }
+    (stem.to_string(), None)
+}
+
+/// The key without the file extension of its last path component.
+fn strip_extension(key: &str) -> &str {
+    let file_start = key.rfind('/').map(|i| i + 1).unwrap_or(0);
+    match key[file_start..].rfind('.') {
+        Some(dot) if dot > 0 => &key[..file_start + dot],
+        _ => key,
+    }
+}
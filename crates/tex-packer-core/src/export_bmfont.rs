@@ -0,0 +1,304 @@
+//! AngelCode BMFont (`.fnt`) export for atlases of per-glyph images.
+//!
+//! Glyph metrics (advance, offsets, line height, kerning) do not come from the images, so they
+//! are supplied as [`FontMetrics`], typically deserialized from a JSON/TOML/YAML file next to the
+//! glyph images. Each [`GlyphMetrics`] names the frame holding its bitmap; glyphs without one
+//! (spaces) are written with an empty rect. Trimmed frames fold their trim offset into
+//! `xoffset`/`yoffset`, so trimming glyph images is safe. BMFont cannot describe rotated glyphs,
+//! so pack with `allow_rotation: false`.
+
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+
+use crate::error::{Result, TexPackerError};
+use crate::model::{Atlas, Frame};
+
+/// Font-wide metrics plus per-glyph metrics and kerning pairs for [`to_bmfont_text`] and
+/// [`to_bmfont_binary`]. Field names follow the BMFont `info`/`common` tags.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[serde(default)]
+pub struct FontMetrics {
+    /// Font name (`info face`).
+    pub face: String,
+    /// Font size in pixels (`info size`); negative means the size matches the cell height.
+    pub size: i32,
+    pub bold: bool,
+    pub italic: bool,
+    /// Padding baked into every glyph image: up, right, down, left.
+    pub padding: [u32; 4],
+    /// Distance between two lines of text (`common lineHeight`).
+    pub line_height: u32,
+    /// Distance from the top of a line to the baseline (`common base`).
+    pub base: u32,
+    pub glyphs: Vec<GlyphMetrics>,
+    pub kernings: Vec<KerningPair>,
+}
+
+/// Metrics of one glyph. Offsets are relative to the untrimmed glyph image.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct GlyphMetrics {
+    /// Character code (Unicode scalar value).
+    pub id: u32,
+    /// Frame key of the glyph image: the exact key, or the file stem of exactly one frame
+    /// (`"A"` finds `glyphs/A.png`). None for glyphs without pixels.
+    #[serde(default)]
+    pub key: Option<String>,
+    #[serde(default)]
+    pub xoffset: i32,
+    #[serde(default)]
+    pub yoffset: i32,
+    pub xadvance: i32,
+}
+
+/// Horizontal adjustment between two characters.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct KerningPair {
+    pub first: u32,
+    pub second: u32,
+    pub amount: i32,
+}
+
+/// A glyph resolved against the atlas.
+struct Char {
+    id: u32,
+    x: u32,
+    y: u32,
+    w: u32,
+    h: u32,
+    xoffset: i32,
+    yoffset: i32,
+    xadvance: i32,
+    page: usize,
+}
+
+fn invalid(msg: impl std::fmt::Display) -> TexPackerError {
+    TexPackerError::InvalidInput(format!("bmfont: {msg}"))
+}
+
+fn stem(key: &str) -> &str {
+    let name = key.rsplit(['/', '\\']).next().unwrap_or(key);
+    name.rsplit_once('.').map_or(name, |(stem, _)| stem)
+}
+
+fn resolve<K: ToString>(atlas: &Atlas<K>, metrics: &FontMetrics) -> Result<Vec<Char>> {
+    let mut by_key: HashMap<String, (usize, &Frame<K>)> = HashMap::new();
+    let mut by_stem: HashMap<String, Option<(usize, &Frame<K>)>> = HashMap::new();
+    for (page, p) in atlas.pages.iter().enumerate() {
+        for f in &p.frames {
+            let key = f.key.to_string();
+            by_stem
+                .entry(stem(&key).to_string())
+                .and_modify(|e| *e = None)
+                .or_insert(Some((page, f)));
+            by_key.insert(key, (page, f));
+        }
+    }
+    let mut chars = Vec::with_capacity(metrics.glyphs.len());
+    for g in &metrics.glyphs {
+        let Some(key) = &g.key else {
+            chars.push(Char {
+                id: g.id,
+                x: 0,
+                y: 0,
+                w: 0,
+                h: 0,
+                xoffset: g.xoffset,
+                yoffset: g.yoffset,
+                xadvance: g.xadvance,
+                page: 0,
+            });
+            continue;
+        };
+        let found = by_key
+            .get(key)
+            .copied()
+            .or_else(|| by_stem.get(key.as_str()).copied().flatten());
+        let Some((page, f)) = found else {
+            return Err(invalid(format!(
+                "glyph {} refers to '{key}', which is not a (unique) frame of the atlas",
+                g.id
+            )));
+        };
+        if f.rotated {
+            return Err(invalid(format!(
+                "glyph {} ('{key}') is rotated; pack with allow_rotation: false",
+                g.id
+            )));
+        }
+        let (w, h) = if f.degenerate {
+            (0, 0)
+        } else {
+            (f.frame.w, f.frame.h)
+        };
+        chars.push(Char {
+            id: g.id,
+            x: f.frame.x,
+            y: f.frame.y,
+            w,
+            h,
+            xoffset: g.xoffset + f.source.x as i32,
+            yoffset: g.yoffset + f.source.y as i32,
+            xadvance: g.xadvance,
+            page,
+        });
+    }
+    Ok(chars)
+}
+
+/// `common scaleW/scaleH`: BMFont assumes equally sized pages, so the largest page size.
+fn scale<K>(atlas: &Atlas<K>) -> (u32, u32) {
+    let w = atlas.pages.iter().map(|p| p.width).max().unwrap_or(0);
+    let h = atlas.pages.iter().map(|p| p.height).max().unwrap_or(0);
+    (w, h)
+}
+
+fn page_name<K>(atlas: &Atlas<K>, page_names: &[String], i: usize) -> String {
+    page_names
+        .get(i)
+        .cloned()
+        .unwrap_or_else(|| format!("page_{}.png", atlas.pages[i].id))
+}
+
+/// Build a BMFont text `.fnt` for `atlas`, with `page_names` the page textures in order.
+///
+/// `info spacing` is the atlas texture padding; glyphs are written in `metrics` order with
+/// `chnl=15` (all channels), followed by the kerning pairs.
+pub fn to_bmfont_text<K: ToString>(
+    atlas: &Atlas<K>,
+    metrics: &FontMetrics,
+    page_names: &[String],
+) -> Result<String> {
+    let chars = resolve(atlas, metrics)?;
+    let [up, right, down, left] = metrics.padding;
+    let spacing = atlas.meta.padding.1;
+    let (scale_w, scale_h) = scale(atlas);
+    let mut s = String::new();
+    s.push_str(&format!(
+        "info face=\"{}\" size={} bold={} italic={} charset=\"\" unicode=1 stretchH=100 smooth=1 aa=1 padding={up},{right},{down},{left} spacing={spacing},{spacing} outline=0\n",
+        metrics.face.replace('"', "'"),
+        metrics.size,
+        metrics.bold as u8,
+        metrics.italic as u8,
+    ));
+    s.push_str(&format!(
+        "common lineHeight={} base={} scaleW={scale_w} scaleH={scale_h} pages={} packed=0 alphaChnl=0 redChnl=0 greenChnl=0 blueChnl=0\n",
+        metrics.line_height,
+        metrics.base,
+        atlas.pages.len()
+    ));
+    for i in 0..atlas.pages.len() {
+        s.push_str(&format!(
+            "page id={i} file=\"{}\"\n",
+            page_name(atlas, page_names, i)
+        ));
+    }
+    s.push_str(&format!("chars count={}\n", chars.len()));
+    for c in &chars {
+        s.push_str(&format!(
+            "char id={} x={} y={} width={} height={} xoffset={} yoffset={} xadvance={} page={} chnl=15\n",
+            c.id, c.x, c.y, c.w, c.h, c.xoffset, c.yoffset, c.xadvance, c.page
+        ));
+    }
+    if !metrics.kernings.is_empty() {
+        s.push_str(&format!("kernings count={}\n", metrics.kernings.len()));
+        for k in &metrics.kernings {
+            s.push_str(&format!(
+                "kerning first={} second={} amount={}\n",
+                k.first, k.second, k.amount
+            ));
+        }
+    }
+    Ok(s)
+}
+
+/// Build a binary (version 3) BMFont `.fnt`: the same content as [`to_bmfont_text`] in the
+/// little-endian block layout read by BMFont loaders. Values that overflow their binary field
+/// (e.g. pages above 65535 px or more than 256 pages) are an [`TexPackerError::InvalidInput`].
+pub fn to_bmfont_binary<K: ToString>(
+    atlas: &Atlas<K>,
+    metrics: &FontMetrics,
+    page_names: &[String],
+) -> Result<Vec<u8>> {
+    fn fit<T: TryFrom<i64>>(v: impl Into<i64>, what: &str) -> Result<T> {
+        let v = v.into();
+        T::try_from(v).map_err(|_| invalid(format!("{what} {v} does not fit the binary format")))
+    }
+    fn block(out: &mut Vec<u8>, kind: u8, body: &[u8]) {
+        out.push(kind);
+        out.extend_from_slice(&(body.len() as u32).to_le_bytes());
+        out.extend_from_slice(body);
+    }
+
+    let chars = resolve(atlas, metrics)?;
+    let mut out = b"BMF\x03".to_vec();
+
+    let mut info = Vec::new();
+    info.extend_from_slice(&fit::<i16>(metrics.size, "font size")?.to_le_bytes());
+    // smooth, unicode, italic, bold
+    let bits = 0b11 | (metrics.italic as u8) << 2 | (metrics.bold as u8) << 3;
+    info.extend_from_slice(&[bits, 0]);
+    info.extend_from_slice(&100u16.to_le_bytes());
+    info.push(1);
+    for v in metrics.padding {
+        info.push(fit(v, "padding")?);
+    }
+    let spacing: u8 = fit(atlas.meta.padding.1, "spacing")?;
+    info.extend_from_slice(&[spacing, spacing, 0]);
+    info.extend_from_slice(metrics.face.as_bytes());
+    info.push(0);
+    block(&mut out, 1, &info);
+
+    let (scale_w, scale_h) = scale(atlas);
+    let mut common = Vec::new();
+    for (v, what) in [
+        (metrics.line_height, "line height"),
+        (metrics.base, "base"),
+        (scale_w, "page width"),
+        (scale_h, "page height"),
+        (atlas.pages.len() as u32, "page count"),
+    ] {
+        common.extend_from_slice(&fit::<u16>(v, what)?.to_le_bytes());
+    }
+    common.extend_from_slice(&[0; 5]);
+    block(&mut out, 2, &common);
+
+    let mut pages = Vec::new();
+    for i in 0..atlas.pages.len() {
+        pages.extend_from_slice(page_name(atlas, page_names, i).as_bytes());
+        pages.push(0);
+    }
+    block(&mut out, 3, &pages);
+
+    let mut records = Vec::with_capacity(chars.len() * 20);
+    for c in &chars {
+        records.extend_from_slice(&c.id.to_le_bytes());
+        for (v, what) in [(c.x, "x"), (c.y, "y"), (c.w, "width"), (c.h, "height")] {
+            records.extend_from_slice(&fit::<u16>(v, what)?.to_le_bytes());
+        }
+        for (v, what) in [
+            (c.xoffset, "xoffset"),
+            (c.yoffset, "yoffset"),
+            (c.xadvance, "xadvance"),
+        ] {
+            records.extend_from_slice(&fit::<i16>(v, what)?.to_le_bytes());
+        }
+        records.push(fit(c.page as u32, "page")?);
+        records.push(15);
+    }
+    block(&mut out, 4, &records);
+
+    if !metrics.kernings.is_empty() {
+        let mut pairs = Vec::with_capacity(metrics.kernings.len() * 10);
+        for k in &metrics.kernings {
+            pairs.extend_from_slice(&k.first.to_le_bytes());
+            pairs.extend_from_slice(&k.second.to_le_bytes());
+            pairs.extend_from_slice(&fit::<i16>(k.amount, "kerning amount")?.to_le_bytes());
+        }
+        block(&mut out, 5, &pairs);
+    }
+    Ok(out)
+}
@@ -0,0 +1,139 @@
+use crate::export_rust::{key_words, page_images, sorted_frames, unique};
+use crate::model::Atlas;
+use std::collections::HashSet;
+use std::fmt::Write;
+
+/// Build a C header embedding the atlas layout as static tables.
+///
+/// `name` (e.g. the atlas name) prefixes every identifier, so several atlases can be included
+/// in one translation unit: `<name>_pages` / `<name>_frames` arrays (frames sorted by key),
+/// `<NAME>_PAGE_COUNT` / `<NAME>_FRAME_COUNT`, and one `<NAME>_FRAME_<KEY>` index define per
+/// frame (`ui/button_ok.png` -> `ATLAS_FRAME_UI_BUTTON_OK_PNG`). The shared
+/// `tex_packer_page` / `tex_packer_frame` structs are guarded by `TEX_PACKER_ATLAS_TYPES`.
+/// Plain C89; strings are escaped as C literals.
+pub fn to_c_header<K: ToString>(atlas: &Atlas<K>, page_names: &[String], name: &str) -> String {
+    let lower = snake_case(name, "atlas").to_ascii_lowercase();
+    let upper = lower.to_ascii_uppercase();
+    let frames = sorted_frames(atlas);
+
+    let mut s = String::new();
+    let _ = writeln!(
+        s,
+        "/* Generated by {} {}. Do not edit. */\n",
+        atlas.meta.app, atlas.meta.version
+    );
+    let _ = writeln!(s, "#ifndef {upper}_ATLAS_H\n#define {upper}_ATLAS_H\n");
+    s.push_str(TYPES);
+
+    let _ = writeln!(s, "\n#define {upper}_PAGE_COUNT {}", atlas.pages.len());
+    let _ = writeln!(s, "#define {upper}_FRAME_COUNT {}\n", frames.len());
+    let mut used = HashSet::new();
+    for (i, (key, _, _)) in frames.iter().enumerate() {
+        let id = unique(snake_case(key, "F").to_ascii_uppercase(), &mut used);
+        let _ = writeln!(s, "#define {upper}_FRAME_{id} {i}");
+    }
+
+    // Zero-length arrays are not valid C
+    if !atlas.pages.is_empty() {
+        let _ = writeln!(
+            s,
+            "\nstatic const tex_packer_page {lower}_pages[{upper}_PAGE_COUNT] = {{"
+        );
+        for (page, image) in atlas.pages.iter().zip(page_images(atlas, page_names)) {
+            let _ = writeln!(
+                s,
+                "    {{ {}, {}, {} }},",
+                c_string(&image),
+                page.width,
+                page.height
+            );
+        }
+        s.push_str("};\n");
+    }
+    if !frames.is_empty() {
+        let _ = writeln!(
+            s,
+            "\n/* key, page, x, y, w, h, rotated, trimmed, source x/y/w/h, source size, pivot */\n\
+             static const tex_packer_frame {lower}_frames[{upper}_FRAME_COUNT] = {{"
+        );
+        for (key, page, f) in &frames {
+            let _ = writeln!(
+                s,
+                "    {{ {}, {page}, {}, {}, {}, {}, {}, {}, {}, {}, {}, {}, {}, {}, {:?}f, {:?}f }},",
+                c_string(key),
+                f.frame.x,
+                f.frame.y,
+                f.frame.w,
+                f.frame.h,
+                f.rotated as u8,
+                f.trimmed as u8,
+                f.source.x,
+                f.source.y,
+                f.source.w,
+                f.source.h,
+                f.source_size.0,
+                f.source_size.1,
+                f.pivot.x as f32,
+                f.pivot.y as f32,
+            );
+        }
+        s.push_str("};\n");
+    }
+    let _ = writeln!(s, "\n#endif /* {upper}_ATLAS_H */");
+    s
+}
+
+const TYPES: &str = "#ifndef TEX_PACKER_ATLAS_TYPES
+#define TEX_PACKER_ATLAS_TYPES
+typedef struct tex_packer_page {
+    const char *image;
+    unsigned width;
+    unsigned height;
+} tex_packer_page;
+
+/* x/y/w/h: rect on the page (post-rotation size); source_*: trimmed rect within the
+   original source_width x source_height image */
+typedef struct tex_packer_frame {
+    const char *key;
+    unsigned page;
+    unsigned x, y, w, h;
+    unsigned char rotated;
+    unsigned char trimmed;
+    unsigned source_x, source_y, source_w, source_h;
+    unsigned source_width, source_height;
+    float pivot_x, pivot_y;
+} tex_packer_frame;
+#endif
+";
+
+/// `snake_case` identifier from the alphanumeric words of `s`, prefixed with `fallback` when
+/// empty or starting with a digit.
+fn snake_case(s: &str, fallback: &str) -> String {
+    let name = key_words(s).collect::<Vec<_>>().join("_");
+    if name.is_empty() {
+        fallback.to_string()
+    } else if name.starts_with(|c: char| c.is_ascii_digit()) {
+        format!("{fallback}_{name}")
+    } else {
+        name
+    }
+}
+
+/// C string literal; non-ASCII bytes and controls become octal escapes.
+fn c_string(s: &str) -> String {
+    let mut out = String::from("\"");
+    for b in s.bytes() {
+        match b {
+            b'"' => out.push_str("\\\""),
+            b'\\' => out.push_str("\\\\"),
+            // Avoid `??x` trigraphs
+            b'?' => out.push_str("\\?"),
+            0x20..=0x7e => out.push(b as char),
+            _ => {
+                let _ = write!(out, "\\{b:03o}");
+            }
+        }
+    }
+    out.push('"');
+    out
+}
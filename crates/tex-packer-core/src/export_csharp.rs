@@ -0,0 +1,174 @@
+use crate::export_rust::{page_images, pascal_case, sorted_frames, unique};
+use crate::model::Atlas;
+use std::collections::HashSet;
+use std::fmt::Write;
+
+/// Build a C# source file with a static class of the atlas layout.
+///
+/// The class is `name` in PascalCase (`atlas` -> `Atlas`) and holds `Pages`, `Frames` (sorted by
+/// key), a `FrameKey` enum with one member per frame (`ui/button_ok.png` -> `UiButtonOkPng`,
+/// unique with a numeric suffix), `Get(FrameKey)` and `TryFind(string, out AtlasFrame)`. The
+/// nested `AtlasPage` / `AtlasFrame` structs are read-only; the file has no `using` directives.
+pub fn to_csharp<K: ToString>(atlas: &Atlas<K>, page_names: &[String], name: &str) -> String {
+    let mut class = pascal_case(name);
+    // Members may not share the enclosing class' name
+    if RESERVED_MEMBERS.contains(&class.as_str()) {
+        class.push_str("Data");
+    }
+    let frames = sorted_frames(atlas);
+
+    let mut s = String::new();
+    let _ = writeln!(
+        s,
+        "// Generated by {} {}. Do not edit.\n",
+        atlas.meta.app, atlas.meta.version
+    );
+    let _ = writeln!(s, "public static class {class}\n{{");
+    s.push_str(TYPES);
+
+    s.push_str("\n    public static readonly AtlasPage[] Pages =\n    {\n");
+    for (page, image) in atlas.pages.iter().zip(page_images(atlas, page_names)) {
+        let _ = writeln!(
+            s,
+            "        new AtlasPage({}, {}, {}),",
+            cs_string(&image),
+            page.width,
+            page.height
+        );
+    }
+    s.push_str("    };\n");
+
+    s.push_str("\n    /// <summary>Frames sorted by key (ordinal).</summary>\n");
+    s.push_str("    public static readonly AtlasFrame[] Frames =\n    {\n");
+    for (key, page, f) in &frames {
+        let _ = writeln!(
+            s,
+            "        new AtlasFrame({}, {page}, {}, {}, {}, {}, {}, {}, {}, {}, {}, {}, {}, {}, {:?}f, {:?}f),",
+            cs_string(key),
+            f.frame.x,
+            f.frame.y,
+            f.frame.w,
+            f.frame.h,
+            f.rotated,
+            f.trimmed,
+            f.source.x,
+            f.source.y,
+            f.source.w,
+            f.source.h,
+            f.source_size.0,
+            f.source_size.1,
+            f.pivot.x as f32,
+            f.pivot.y as f32,
+        );
+    }
+    s.push_str("    };\n");
+
+    s.push_str("\n    public enum FrameKey\n    {\n");
+    let mut used = HashSet::new();
+    for (i, (key, _, _)) in frames.iter().enumerate() {
+        let _ = writeln!(s, "        {} = {i},", unique(pascal_case(key), &mut used));
+    }
+    s.push_str("    }\n");
+    s.push_str(LOOKUP);
+    s.push_str("}\n");
+    s
+}
+
+const RESERVED_MEMBERS: &[&str] = &[
+    "AtlasPage",
+    "AtlasFrame",
+    "Pages",
+    "Frames",
+    "FrameKey",
+    "Get",
+    "TryFind",
+];
+
+const TYPES: &str = "    public readonly struct AtlasPage
+    {
+        public readonly string Image;
+        public readonly int Width, Height;
+
+        public AtlasPage(string image, int width, int height)
+        {
+            Image = image;
+            Width = width;
+            Height = height;
+        }
+    }
+
+    /// <summary>X/Y/W/H: rect on the page (post-rotation size); Source*: trimmed rect within the
+    /// original SourceWidth x SourceHeight image.</summary>
+    public readonly struct AtlasFrame
+    {
+        public readonly string Key;
+        public readonly int Page;
+        public readonly int X, Y, W, H;
+        public readonly bool Rotated, Trimmed;
+        public readonly int SourceX, SourceY, SourceW, SourceH;
+        public readonly int SourceWidth, SourceHeight;
+        public readonly float PivotX, PivotY;
+
+        public AtlasFrame(string key, int page, int x, int y, int w, int h, bool rotated,
+            bool trimmed, int sourceX, int sourceY, int sourceW, int sourceH, int sourceWidth,
+            int sourceHeight, float pivotX, float pivotY)
+        {
+            Key = key;
+            Page = page;
+            X = x;
+            Y = y;
+            W = w;
+            H = h;
+            Rotated = rotated;
+            Trimmed = trimmed;
+            SourceX = sourceX;
+            SourceY = sourceY;
+            SourceW = sourceW;
+            SourceH = sourceH;
+            SourceWidth = sourceWidth;
+            SourceHeight = sourceHeight;
+            PivotX = pivotX;
+            PivotY = pivotY;
+        }
+    }
+";
+
+const LOOKUP: &str = "
+    public static AtlasFrame Get(FrameKey key) => Frames[(int)key];
+
+    /// <summary>Looks a frame up by key.</summary>
+    public static bool TryFind(string key, out AtlasFrame frame)
+    {
+        int lo = 0, hi = Frames.Length - 1;
+        while (lo <= hi)
+        {
+            int mid = (lo + hi) / 2;
+            int cmp = string.CompareOrdinal(Frames[mid].Key, key);
+            if (cmp == 0)
+            {
+                frame = Frames[mid];
+                return true;
+            }
+            if (cmp < 0) lo = mid + 1; else hi = mid - 1;
+        }
+        frame = default;
+        return false;
+    }
+";
+
+/// C# string literal; control characters become `\uXXXX` escapes.
+fn cs_string(s: &str) -> String {
+    let mut out = String::from("\"");
+    for c in s.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            c if (c as u32) < 0x20 || c as u32 == 0x7f => {
+                let _ = write!(out, "\\u{:04x}", c as u32);
+            }
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+    out
+}
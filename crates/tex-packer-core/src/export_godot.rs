@@ -0,0 +1,196 @@
+use crate::error::{Result, TexPackerError};
+use crate::export::animation_groups;
+use crate::model::{Atlas, Frame};
+use serde::{Deserialize, Serialize};
+
+/// Options of [`to_godot_resources`].
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(default)]
+pub struct GodotOptions {
+    /// Godot resource directory the files are written to, e.g. `res://sprites/atlas`; page
+    /// textures are expected in the same directory.
+    pub res_dir: String,
+    /// File name of a `SpriteFrames` resource holding the animations, if one should be written.
+    pub sprite_frames: Option<String>,
+}
+
+impl Default for GodotOptions {
+    fn default() -> Self {
+        Self {
+            res_dir: "res://".into(),
+            sprite_frames: None,
+        }
+    }
+}
+
+/// A Godot text resource: `path` is relative to `GodotOptions::res_dir`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GodotResource {
+    pub path: String,
+    pub contents: String,
+}
+
+/// Build Godot 4 `.tres` resources: one `AtlasTexture` per frame and, with
+/// `GodotOptions::sprite_frames`, a `SpriteFrames` resource of the atlas' animations.
+///
+/// - Each `AtlasTexture` is named after its key (extension dropped, directories kept) and points
+///   at its page texture from `page_names`; `region` is the packed rect and `margin` restores the
+///   trimmed-away border (`size` is the total extra width/height), so the texture reports the
+///   original image size.
+/// - Animations come from `meta.animations`, or else from `_N` key sequences of two or more
+///   frames (100 ms per frame); frames play at `speed = 1` with `duration` in seconds.
+/// - `AtlasTexture` cannot sample rotated regions, so rotated frames are an
+///   [`TexPackerError::InvalidInput`]; pack with `allow_rotation: false`.
+pub fn to_godot_resources<K: ToString>(
+    atlas: &Atlas<K>,
+    page_names: &[String],
+    options: &GodotOptions,
+) -> Result<Vec<GodotResource>> {
+    let mut out = Vec::new();
+    let mut paths = std::collections::HashMap::new();
+    for (i, page) in atlas.pages.iter().enumerate() {
+        let image = page_names
+            .get(i)
+            .cloned()
+            .unwrap_or_else(|| format!("page_{}.png", page.id));
+        for fr in &page.frames {
+            if fr.rotated {
+                return Err(TexPackerError::InvalidInput(format!(
+                    "Godot AtlasTexture cannot hold rotated frame '{}'; disable allow_rotation",
+                    fr.key.to_string()
+                )));
+            }
+            let key = fr.key.to_string();
+            let path = format!("{}.tres", resource_stem(&key));
+            out.push(GodotResource {
+                contents: atlas_texture(fr, &res_path(&options.res_dir, &image)),
+                path: path.clone(),
+            });
+            paths.insert(key, path);
+        }
+    }
+    if let Some(name) = &options.sprite_frames {
+        let contents = sprite_frames(atlas, &paths, &options.res_dir);
+        out.push(GodotResource {
+            path: name.clone(),
+            contents,
+        });
+    }
+    Ok(out)
+}
+
+fn atlas_texture<K>(fr: &Frame<K>, texture: &str) -> String {
+    let r = &fr.frame;
+    let (src_w, src_h) = fr.source_size;
+    format!(
+        "[gd_resource type=\"AtlasTexture\" load_steps=2 format=3]\n\n\
+         [ext_resource type=\"Texture2D\" path=\"{}\" id=\"1\"]\n\n\
+         [resource]\n\
+         atlas = ExtResource(\"1\")\n\
+         region = Rect2({}, {}, {}, {})\n\
+         margin = Rect2({}, {}, {}, {})\n\
+         filter_clip = true\n",
+        escape(texture),
+        r.x,
+        r.y,
+        r.w,
+        r.h,
+        fr.source.x,
+        fr.source.y,
+        src_w.saturating_sub(r.w),
+        src_h.saturating_sub(r.h),
+    )
+}
+
+/// `SpriteFrames` referencing the frame resources in `paths` (key -> relative path).
+fn sprite_frames<K: ToString>(
+    atlas: &Atlas<K>,
+    paths: &std::collections::HashMap<String, String>,
+    res_dir: &str,
+) -> String {
+    let animations = animation_groups(atlas);
+    let mut ids: Vec<&str> = Vec::new();
+    let mut body = Vec::new();
+    for (name, frames) in &animations {
+        let entries: Vec<String> = frames
+            .iter()
+            .filter_map(|(key, ms)| {
+                let path = paths.get(key)?;
+                let id = match ids.iter().position(|p| p == path) {
+                    Some(i) => i + 1,
+                    None => {
+                        ids.push(path);
+                        ids.len()
+                    }
+                };
+                Some(format!(
+                    "{{\n\"duration\": {:?},\n\"texture\": ExtResource(\"{id}\")\n}}",
+                    *ms as f64 / 1000.0
+                ))
+            })
+            .collect();
+        body.push(format!(
+            "{{\n\"frames\": [{}],\n\"loop\": true,\n\"name\": &\"{}\",\n\"speed\": 1.0\n}}",
+            entries.join(", "),
+            escape(name)
+        ));
+    }
+    let mut s = format!(
+        "[gd_resource type=\"SpriteFrames\" load_steps={} format=3]\n\n",
+        ids.len() + 1
+    );
+    for (i, path) in ids.iter().enumerate() {
+        s.push_str(&format!(
+            "[ext_resource type=\"Texture2D\" path=\"{}\" id=\"{}\"]\n",
+            escape(&res_path(res_dir, path)),
+            i + 1
+        ));
+    }
+    if !ids.is_empty() {
+        s.push('\n');
+    }
+    s.push_str(&format!("[resource]\nanimations = [{}]\n", body.join(", ")));
+    s
+}
+
+fn res_path(res_dir: &str, file: &str) -> String {
+    if res_dir.is_empty() || res_dir.ends_with('/') {
+        format!("{res_dir}{file}")
+    } else {
+        format!("{res_dir}/{file}")
+    }
+}
+
+/// Relative resource path for a key: extension dropped, characters invalid in file names
+/// replaced with `_`.
+fn resource_stem(key: &str) -> String {
+    let file_start = key.rfind('/').map(|i| i + 1).unwrap_or(0);
+    let stem = match key[file_start..].rfind('.') {
+        Some(dot) if dot > 0 => &key[..file_start + dot],
+        _ => key,
+    };
+    // Keep the key's directories, but never above `res_dir`
+    let path = stem
+        .split('/')
+        .filter(|seg| !seg.is_empty() && *seg != ".")
+        .map(|seg| {
+            if seg == ".." {
+                return "_".to_string();
+            }
+            seg.chars()
+                .map(|c| match c {
+                    '\\' | ':' | '*' | '?' | '"' | '<' | '>' | '|' => '_',
+                    c if c.is_control() => '_',
+                    c => c,
+                })
+                .collect()
+        })
+        .collect::<Vec<String>>()
+        .join("/");
+    if path.is_empty() { "_".into() } else { path }
+}
+
+/// Escapes a string for a quoted `.tres` value.
+fn escape(s: &str) -> String {
+    s.replace('\\', "\\\\").replace('"', "\\\"")
+}
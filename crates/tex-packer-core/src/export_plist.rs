@@ -4,6 +4,7 @@ use serde::Serialize;
 /// Build a basic Apple plist (XML) with frames in a dict keyed by name.
 /// Multi-page atlases include page id and size fields for each frame.
 /// Use `to_plist_hash_with_pages` to inject texture filenames into meta.
+/// Rotated frames are marked for a clockwise turn (see [`require_clockwise`](crate::require_clockwise)).
 pub fn to_plist_hash<K: ToString + Clone + Serialize>(atlas: &Atlas<K>) -> String {
     // Very basic Apple plist (XML) with frames in a dict keyed by name. Multi-page adds page id and size fields.
     let mut s = String::new();
@@ -26,7 +27,7 @@ pub fn to_plist_hash<K: ToString + Clone + Serialize>(atlas: &Atlas<K>) -> Strin
                 fr.source.x, fr.source.y, fr.source.w, fr.source.h
             );
             s.push_str(&format!(
-                "    <key>{}</key>\n    <dict>\n      <key>page</key><integer>{}</integer>\n      <key>pageSize</key><string>{{{}, {}}}</string>\n      <key>frame</key><string>{}</string>\n      <key>rotated</key><{} />\n      <key>trimmed</key><{} />\n      <key>spriteSourceSize</key><string>{}</string>\n      <key>sourceSize</key><string>{{{}, {}}}</string>\n      <key>pivot</key><string>{{{:.2}, {:.2}}}</string>\n    </dict>\n",
+                "    <key>{}</key>\n    <dict>\n      <key>page</key><integer>{}</integer>\n      <key>pageSize</key><string>{{{}, {}}}</string>\n      <key>frame</key><string>{}</string>\n      <key>rotated</key><{} />\n      <key>trimmed</key><{} />\n      <key>spriteSourceSize</key><string>{}</string>\n      <key>sourceSize</key><string>{{{}, {}}}</string>\n      <key>pivot</key><string>{{{}, {}}}</string>\n    </dict>\n",
                 xml_escape(&name),
                 page.id,
                 page.width, page.height,
@@ -35,7 +36,7 @@ pub fn to_plist_hash<K: ToString + Clone + Serialize>(atlas: &Atlas<K>) -> Strin
                 if fr.trimmed { "true" } else { "false" },
                 source,
                 fr.source_size.0, fr.source_size.1,
-                0.5, 0.5,
+                fr.pivot.x, fr.pivot.y,
             ));
         }
     }
@@ -88,7 +89,7 @@ pub fn to_plist_hash_with_pages<K: ToString + Clone + Serialize>(
                 fr.source.x, fr.source.y, fr.source.w, fr.source.h
             );
             s.push_str(&format!(
-                "    <key>{}</key>\n    <dict>\n      <key>page</key><integer>{}</integer>\n      <key>pageSize</key><string>{{{}, {}}}</string>\n      <key>frame</key><string>{}</string>\n      <key>rotated</key><{} />\n      <key>trimmed</key><{} />\n      <key>spriteSourceSize</key><string>{}</string>\n      <key>sourceSize</key><string>{{{}, {}}}</string>\n      <key>pivot</key><string>{{{:.2}, {:.2}}}</string>\n    </dict>\n",
+                "    <key>{}</key>\n    <dict>\n      <key>page</key><integer>{}</integer>\n      <key>pageSize</key><string>{{{}, {}}}</string>\n      <key>frame</key><string>{}</string>\n      <key>rotated</key><{} />\n      <key>trimmed</key><{} />\n      <key>spriteSourceSize</key><string>{}</string>\n      <key>sourceSize</key><string>{{{}, {}}}</string>\n      <key>pivot</key><string>{{{}, {}}}</string>\n    </dict>\n",
                 xml_escape(&name),
                 page.id,
                 page.width, page.height,
@@ -97,7 +98,7 @@ pub fn to_plist_hash_with_pages<K: ToString + Clone + Serialize>(
                 if fr.trimmed { "true" } else { "false" },
                 source,
                 fr.source_size.0, fr.source_size.1,
-                0.5, 0.5,
+                fr.pivot.x, fr.pivot.y,
             ));
         }
     }
@@ -0,0 +1,188 @@
+use crate::model::{Atlas, Frame};
+use std::collections::HashSet;
+use std::fmt::Write;
+
+/// Build a Rust source file embedding the atlas layout as constants.
+///
+/// The file defines `AtlasPage` / `AtlasFrame`, `PAGES` (texture names from `page_names`),
+/// `FRAMES` (sorted by key) and `FrameKey`, an enum with one variant per frame whose
+/// `frame()` indexes `FRAMES` directly; `find(key)` looks a frame up by its key string.
+/// Variant names are the keys in PascalCase (`ui/button_ok.png` -> `UiButtonOkPng`), made unique
+/// with a numeric suffix. Include it with `include!` or as a module; it has no dependencies.
+pub fn to_rust_code<K: ToString>(atlas: &Atlas<K>, page_names: &[String]) -> String {
+    let frames = sorted_frames(atlas);
+
+    let mut s = String::new();
+    let _ = writeln!(
+        s,
+        "// Generated by {} {}. Do not edit.\n",
+        atlas.meta.app, atlas.meta.version
+    );
+    s.push_str(PREAMBLE);
+
+    s.push_str("\npub const PAGES: &[AtlasPage] = &[\n");
+    for (page, image) in atlas.pages.iter().zip(page_images(atlas, page_names)) {
+        let _ = writeln!(
+            s,
+            "    AtlasPage {{ image: {:?}, width: {}, height: {} }},",
+            image, page.width, page.height
+        );
+    }
+    s.push_str("];\n");
+
+    s.push_str("\n/// Frames sorted by key.\npub const FRAMES: &[AtlasFrame] = &[\n");
+    for (key, page, f) in &frames {
+        let _ = writeln!(
+            s,
+            "    AtlasFrame {{ key: {:?}, page: {}, x: {}, y: {}, w: {}, h: {}, rotated: {}, trimmed: {}, source_x: {}, source_y: {}, source_w: {}, source_h: {}, source_width: {}, source_height: {}, pivot_x: {:?}, pivot_y: {:?} }},",
+            key,
+            page,
+            f.frame.x,
+            f.frame.y,
+            f.frame.w,
+            f.frame.h,
+            f.rotated,
+            f.trimmed,
+            f.source.x,
+            f.source.y,
+            f.source.w,
+            f.source.h,
+            f.source_size.0,
+            f.source_size.1,
+            f.pivot.x as f32,
+            f.pivot.y as f32,
+        );
+    }
+    s.push_str("];\n");
+
+    s.push_str(
+        "\n/// One variant per frame, in `FRAMES` order.\n\
+         #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]\n\
+         #[repr(usize)]\n\
+         pub enum FrameKey {\n",
+    );
+    let mut used = HashSet::new();
+    for (i, (key, _, _)) in frames.iter().enumerate() {
+        let _ = writeln!(s, "    {} = {i},", variant_name(key, &mut used));
+    }
+    s.push_str("}\n");
+    s.push_str(FRAME_KEY_IMPL);
+    s
+}
+
+const PREAMBLE: &str = "/// A page texture.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AtlasPage {
+    pub image: &'static str,
+    pub width: u32,
+    pub height: u32,
+}
+
+/// A packed frame: `x`/`y`/`w`/`h` on page `page` (post-rotation size), `source_*` the trimmed
+/// rect within the original `source_width` x `source_height` image.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct AtlasFrame {
+    pub key: &'static str,
+    pub page: usize,
+    pub x: u32,
+    pub y: u32,
+    pub w: u32,
+    pub h: u32,
+    pub rotated: bool,
+    pub trimmed: bool,
+    pub source_x: u32,
+    pub source_y: u32,
+    pub source_w: u32,
+    pub source_h: u32,
+    pub source_width: u32,
+    pub source_height: u32,
+    pub pivot_x: f32,
+    pub pivot_y: f32,
+}
+";
+
+const FRAME_KEY_IMPL: &str = "
+impl FrameKey {
+    pub const fn frame(self) -> &'static AtlasFrame {
+        &FRAMES[self as usize]
+    }
+}
+
+/// Looks a frame up by key.
+pub fn find(key: &str) -> Option<&'static AtlasFrame> {
+    FRAMES
+        .binary_search_by(|f| f.key.cmp(key))
+        .ok()
+        .map(|i| &FRAMES[i])
+}
+";
+
+/// `(key, page index, frame)` of every frame, sorted by key; shared by the code generators.
+pub(crate) fn sorted_frames<K: ToString>(atlas: &Atlas<K>) -> Vec<(String, usize, &Frame<K>)> {
+    let mut frames: Vec<(String, usize, &Frame<K>)> = atlas
+        .pages
+        .iter()
+        .enumerate()
+        .flat_map(|(i, p)| p.frames.iter().map(move |f| (f.key.to_string(), i, f)))
+        .collect();
+    frames.sort_by(|a, b| a.0.cmp(&b.0));
+    frames
+}
+
+/// Page texture names, falling back to `page_<id>.png`.
+pub(crate) fn page_images<K>(atlas: &Atlas<K>, page_names: &[String]) -> Vec<String> {
+    atlas
+        .pages
+        .iter()
+        .enumerate()
+        .map(|(i, page)| {
+            page_names
+                .get(i)
+                .cloned()
+                .unwrap_or_else(|| format!("page_{}.png", page.id))
+        })
+        .collect()
+}
+
+/// Alphanumeric runs of `key`, the words identifiers are built from.
+pub(crate) fn key_words(key: &str) -> impl Iterator<Item = &str> {
+    key.split(|c: char| !c.is_ascii_alphanumeric())
+        .filter(|w| !w.is_empty())
+}
+
+/// `name`, or `name2`, `name3`, ... if already in `used`.
+pub(crate) fn unique(name: String, used: &mut HashSet<String>) -> String {
+    let mut candidate = name.clone();
+    let mut n = 2;
+    while !used.insert(candidate.clone()) {
+        candidate = format!("{name}{n}");
+        n += 1;
+    }
+    candidate
+}
+
+/// PascalCase identifier for `key` (prefixed with `F` if it would start with a digit).
+pub(crate) fn pascal_case(key: &str) -> String {
+    let mut name = String::new();
+    for word in key_words(key) {
+        let mut chars = word.chars();
+        if let Some(first) = chars.next() {
+            name.push(first.to_ascii_uppercase());
+            name.push_str(chars.as_str());
+        }
+    }
+    if name.is_empty() || name.starts_with(|c: char| c.is_ascii_digit()) {
+        name.insert(0, 'F');
+    }
+    name
+}
+
+/// PascalCase identifier for `key`, unique within `used`.
+fn variant_name(key: &str, used: &mut HashSet<String>) -> String {
+    let mut name = pascal_case(key);
+    // `Self` is the only reserved word a PascalCase identifier can hit
+    if name == "Self" {
+        name.push('_');
+    }
+    unique(name, used)
+}
@@ -0,0 +1,210 @@
+use crate::export::{AtlasNaming, write_atlas_text};
+use crate::model::Atlas;
+
+/// Build a Spine `.atlas` text file (the libgdx-style layout read by Spine runtimes).
+///
+/// - Each page starts with a blank line, its image name from `page_names`, then the page header.
+/// - `size` is the unrotated region size; rotated regions are emitted as `rotate: true` (90° CW;
+///   see [`require_clockwise`](crate::require_clockwise)).
+/// - `orig`/`offset` describe trimming; `offset` is measured from the bottom-left of the original image.
+/// - Region names drop the file extension and are sanitized for the line-based parser
+///   (surrounding whitespace trimmed, line breaks and `:` replaced with `_`).
+pub fn to_spine_atlas<K: ToString>(atlas: &Atlas<K>, page_names: &[String]) -> String {
+    write_atlas_text(atlas, page_names, AtlasNaming::Plain)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::{Frame, Meta, Page, Rect};
+    use std::collections::HashMap;
+
+    struct ParsedRegion {
+        name: String,
+        fields: HashMap<String, String>,
+    }
+
+    struct ParsedPage {
+        image: String,
+        header: HashMap<String, String>,
+        regions: Vec<ParsedRegion>,
+    }
+
+    /// Minimal reader following libgdx `TextureAtlasData` rules: lines are trimmed, an empty
+    /// line ends the current page, `key: value` lines are fields and anything else is a name.
+    fn parse(text: &str) -> Vec<ParsedPage> {
+        let mut pages: Vec<ParsedPage> = Vec::new();
+        let mut in_page = false;
+        let mut in_header = false;
+        for raw in text.lines() {
+            let line = raw.trim();
+            if line.is_empty() {
+                in_page = false;
+                continue;
+            }
+            let entry = line
+                .split_once(':')
+                .map(|(k, v)| (k.trim().to_string(), v.trim().to_string()));
+            if !in_page {
+                pages.push(ParsedPage {
+                    image: line.to_string(),
+                    header: HashMap::new(),
+                    regions: Vec::new(),
+                });
+                in_page = true;
+                in_header = true;
+                continue;
+            }
+            let page = pages.last_mut().unwrap();
+            match entry {
+                Some((k, v)) if in_header => {
+                    page.header.insert(k, v);
+                }
+                Some((k, v)) => {
+                    page.regions.last_mut().unwrap().fields.insert(k, v);
+                }
+                None => {
+                    in_header = false;
+                    page.regions.push(ParsedRegion {
+                        name: line.to_string(),
+                        fields: HashMap::new(),
+                    });
+                }
+            }
+        }
+        pages
+    }
+
+    fn frame(key: &str, frame: Rect, rotated: bool, source: Rect, size: (u32, u32)) -> Frame {
+        Frame {
+            key: key.into(),
+            frame,
+            rotated,
+            trimmed: source.w != size.0 || source.h != size.1,
+            source,
+            source_size: size,
+            degenerate: false,
+            id: 0,
+            content_hash: None,
+            pivot: Default::default(),
+            nine_patch: None,
+            scale: None,
+        }
+    }
+
+    fn atlas(pages: Vec<Page>) -> Atlas {
+        Atlas {
+            pages,
+            meta: Meta {
+                schema_version: "1".into(),
+                app: "tex-packer".into(),
+                version: "test".into(),
+                format: "RGBA8888".into(),
+                scale: 1.0,
+                power_of_two: false,
+                square: false,
+                max_dim: (256, 256),
+                padding: (0, 0),
+                extrude: 0,
+                allow_rotation: true,
+                rotation_direction: Default::default(),
+                trim_mode: "trim".into(),
+                background_color: None,
+                packer: None,
+                page_minimization: None,
+                auto_refinement: None,
+                align: None,
+                animations: Vec::new(),
+                sdf: None,
+                mip: None,
+            },
+        }
+    }
+
+    #[test]
+    fn multi_page_headers_and_regions() {
+        let a = atlas(vec![
+            Page {
+                id: 0,
+                width: 64,
+                height: 32,
+                frames: vec![
+                    frame(
+                        "hero/head.png",
+                        Rect::new(0, 0, 10, 20),
+                        true,
+                        Rect::new(2, 1, 20, 10),
+                        (24, 16),
+                    ),
+                    frame(
+                        "tail.png",
+                        Rect::new(12, 0, 8, 8),
+                        false,
+                        Rect::new(0, 0, 8, 8),
+                        (8, 8),
+                    ),
+                ],
+            },
+            Page {
+                id: 1,
+                width: 16,
+                height: 16,
+                frames: vec![frame(
+                    "wing",
+                    Rect::new(0, 0, 4, 4),
+                    false,
+                    Rect::new(0, 0, 4, 4),
+                    (4, 4),
+                )],
+            },
+        ]);
+        let names = vec!["skel_0.png".to_string(), "skel_1.png".to_string()];
+        let pages = parse(&to_spine_atlas(&a, &names));
+
+        assert_eq!(pages.len(), 2);
+        assert_eq!(pages[0].image, "skel_0.png");
+        assert_eq!(pages[1].image, "skel_1.png");
+        assert_eq!(pages[0].header["size"], "64,32");
+        assert_eq!(pages[0].header["filter"], "Linear,Linear");
+        assert_eq!(pages[0].regions.len(), 2);
+        assert_eq!(pages[1].regions.len(), 1);
+
+        // Rotated + trimmed: size is unrotated, offset measured from the bottom.
+        let head = &pages[0].regions[0];
+        assert_eq!(head.name, "hero/head");
+        assert_eq!(head.fields["rotate"], "true");
+        assert_eq!(head.fields["xy"], "0, 0");
+        assert_eq!(head.fields["size"], "20, 10");
+        assert_eq!(head.fields["orig"], "24, 16");
+        assert_eq!(head.fields["offset"], "2, 5");
+        assert_eq!(head.fields["index"], "-1");
+
+        let tail = &pages[0].regions[1];
+        assert_eq!(tail.name, "tail");
+        assert_eq!(tail.fields["rotate"], "false");
+        assert_eq!(pages[1].regions[0].name, "wing");
+    }
+
+    #[test]
+    fn names_are_sanitized_for_the_parser() {
+        let a = atlas(vec![Page {
+            id: 0,
+            width: 8,
+            height: 8,
+            frames: vec![frame(
+                "  odd:name\nx.png ",
+                Rect::new(0, 0, 2, 2),
+                false,
+                Rect::new(0, 0, 2, 2),
+                (2, 2),
+            )],
+        }]);
+        let text = to_spine_atlas(&a, &["p.png".to_string()]);
+        let pages = parse(&text);
+        assert_eq!(pages.len(), 1);
+        assert_eq!(pages[0].regions.len(), 1);
+        assert_eq!(pages[0].regions[0].name, "odd_name_x");
+        // No region line may carry trailing whitespace.
+        assert!(text.lines().all(|l| l == l.trim_end()));
+    }
+}
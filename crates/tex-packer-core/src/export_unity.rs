@@ -0,0 +1,104 @@
+use crate::error::{Result, TexPackerError};
+use crate::model::{Atlas, Frame};
+
+/// Build a Unity `.tpsheet` (sprite sheet data read by the TexturePacker Importer package) for one
+/// page, referencing `image`.
+///
+/// - Rects use Unity's bottom-left texture origin; names drop the file extension.
+/// - Pivots are normalized to the trimmed rect (y up), so trimmed sprites keep their anchor.
+/// - Borders come from the frame's nine-patch insets, measured from the trimmed rect's edges.
+/// - Vertices/triangles describe the sprite rect as a quad (two triangles), in pixels relative to
+///   its bottom-left corner.
+/// - Unity sprites cannot be rotated in the texture; rotated frames are an
+///   [`TexPackerError::InvalidInput`].
+pub fn to_unity_tpsheet<K: ToString>(
+    atlas: &Atlas<K>,
+    page_index: usize,
+    image: &str,
+) -> Result<String> {
+    let (w, h, frames) = match atlas.pages.get(page_index) {
+        Some(p) => (p.width, p.height, p.frames.as_slice()),
+        None => (0, 0, &[][..]),
+    };
+    let mut s = String::new();
+    s.push_str("#\n# Sprite sheet data for Unity.\n#\n");
+    s.push_str("# Import with the TexturePacker Importer package:\n");
+    s.push_str("# https://www.codeandweb.com/texturepacker/unity\n#\n");
+    s.push_str(":format=40300\n");
+    s.push_str(&format!(":texture={}\n", sanitize(image)));
+    s.push_str(&format!(":size={w}x{h}\n"));
+    s.push_str(":pivotpoints=enabled\n");
+    s.push_str(":borders=enabled\n");
+    s.push_str(":alphahandling=ClearTransparentPixels\n\n");
+    s.push_str("# Sprite|Rect|Pivot|Borders|Vertices|Triangles\n");
+    for fr in frames {
+        if fr.rotated {
+            return Err(TexPackerError::InvalidInput(format!(
+                "Unity sprites cannot be rotated: frame '{}'; disable allow_rotation",
+                fr.key.to_string()
+            )));
+        }
+        s.push_str(&sprite_line(fr, h));
+        s.push('\n');
+    }
+    Ok(s)
+}
+
+fn sprite_line<K: ToString>(fr: &Frame<K>, page_h: u32) -> String {
+    let r = &fr.frame;
+    let y = page_h.saturating_sub(r.y + r.h);
+    let (src_w, src_h) = (fr.source_size.0 as f64, fr.source_size.1 as f64);
+    let (tw, th) = (r.w.max(1) as f64, r.h.max(1) as f64);
+    // Pivot in untrimmed pixels (y down) -> fraction of the trimmed rect (y up)
+    let px = (fr.pivot.x * src_w - fr.source.x as f64) / tw;
+    let py = 1.0 - (fr.pivot.y * src_h - fr.source.y as f64) / th;
+    let (bl, br, bt, bb) = match fr.nine_patch {
+        Some(n) => {
+            let right_trim = fr.source_size.0.saturating_sub(fr.source.x + fr.source.w);
+            let bottom_trim = fr.source_size.1.saturating_sub(fr.source.y + fr.source.h);
+            (
+                n.left.saturating_sub(fr.source.x),
+                n.right.saturating_sub(right_trim),
+                n.top.saturating_sub(fr.source.y),
+                n.bottom.saturating_sub(bottom_trim),
+            )
+        }
+        None => (0, 0, 0, 0),
+    };
+    format!(
+        "{};{};{};{};{}; {};{}; {};{};{};{}; 4;0;0;{w};0;{w};{h};0;{h}; 2;0;1;2;0;2;3",
+        sprite_name(&fr.key.to_string()),
+        r.x,
+        y,
+        r.w,
+        r.h,
+        px,
+        py,
+        bl,
+        br,
+        bt,
+        bb,
+        w = r.w,
+        h = r.h,
+    )
+}
+
+/// Sprite name for a frame key: extension stripped, then sanitized.
+fn sprite_name(key: &str) -> String {
+    let file_start = key.rfind('/').map(|i| i + 1).unwrap_or(0);
+    let stem = match key[file_start..].rfind('.') {
+        Some(dot) if dot > 0 => &key[..file_start + dot],
+        _ => key,
+    };
+    sanitize(stem)
+}
+
+/// The importer splits lines on `;` and reads one sprite per line.
+fn sanitize(s: &str) -> String {
+    s.chars()
+        .map(|c| match c {
+            '\r' | '\n' | ';' => '_',
+            c => c,
+        })
+        .collect()
+}
@@ -0,0 +1,80 @@
+//! Reverse of packing: cut sprites back out of atlas pages.
+
+use crate::config::RotationDirection;
+use crate::error::{Result, TexPackerError};
+use crate::model::{Atlas, Frame};
+use image::RgbaImage;
+use image::imageops;
+
+/// Reconstructs every frame of `atlas` as a standalone image, in page/frame order.
+///
+/// `pages[i]` is the pixel data of `atlas.pages[i]`. Rotated frames are turned back against
+/// `meta.rotation_direction` and trimmed frames are placed at their `source` offset on a
+/// transparent canvas of the original `source_size`. Degenerate frames come back as fully
+/// transparent images of their original size.
+pub fn extract_frames<K: Clone>(
+    atlas: &Atlas<K>,
+    pages: &[RgbaImage],
+) -> Result<Vec<(K, RgbaImage)>> {
+    if pages.len() != atlas.pages.len() {
+        return Err(TexPackerError::InvalidInput(format!(
+            "atlas has {} page(s) but {} page image(s) were given",
+            atlas.pages.len(),
+            pages.len()
+        )));
+    }
+    let mut out = Vec::with_capacity(atlas.pages.iter().map(|p| p.frames.len()).sum());
+    for (page, rgba) in atlas.pages.iter().zip(pages) {
+        for f in &page.frames {
+            let sprite = extract_frame_with(f, rgba, atlas.meta.rotation_direction)?;
+            out.push((f.key.clone(), sprite));
+        }
+    }
+    Ok(out)
+}
+
+/// Reconstructs one frame from its page image, assuming rotated frames were turned clockwise.
+pub fn extract_frame<K>(f: &Frame<K>, page: &RgbaImage) -> Result<RgbaImage> {
+    extract_frame_with(f, page, RotationDirection::Clockwise)
+}
+
+/// [`extract_frame`] for an atlas whose rotated frames were turned `direction`.
+pub fn extract_frame_with<K>(
+    f: &Frame<K>,
+    page: &RgbaImage,
+    direction: RotationDirection,
+) -> Result<RgbaImage> {
+    let (w, h) = f.source_size;
+    let mut sprite = RgbaImage::new(w, h);
+    if f.degenerate || f.source.w == 0 || f.source.h == 0 {
+        return Ok(sprite);
+    }
+    let r = f.frame;
+    if r.x + r.w > page.width() || r.y + r.h > page.height() {
+        return Err(TexPackerError::InvalidInput(format!(
+            "frame {}x{} at ({}, {}) lies outside its {}x{} page",
+            r.w,
+            r.h,
+            r.x,
+            r.y,
+            page.width(),
+            page.height()
+        )));
+    }
+    let placed = imageops::crop_imm(page, r.x, r.y, r.w, r.h).to_image();
+    let trimmed = if f.rotated {
+        unrotate(&placed, direction)
+    } else {
+        placed
+    };
+    imageops::replace(&mut sprite, &trimmed, f.source.x as i64, f.source.y as i64);
+    Ok(sprite)
+}
+
+/// Turns a frame placed rotated in `direction` back upright.
+pub(crate) fn unrotate(placed: &RgbaImage, direction: RotationDirection) -> RgbaImage {
+    match direction {
+        RotationDirection::Clockwise => imageops::rotate270(placed),
+        RotationDirection::CounterClockwise => imageops::rotate90(placed),
+    }
+}
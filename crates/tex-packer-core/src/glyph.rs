@@ -0,0 +1,426 @@
+//! Glyph cache on top of [`RuntimeAtlas`]: rasterize glyphs on first use, keep them on shared
+//! pages and report the dirty page areas to upload once per frame.
+//!
+//! Rasterizing is left to a [`GlyphRasterizer`] (any `FnMut(&GlyphKey) -> Option<RasterizedGlyph>`
+//! works); the `ab_glyph` feature adds [`AbGlyphRasterizer`].
+
+use crate::config::PackerConfig;
+use crate::error::{Result, TexPackerError};
+use crate::model::Rect;
+use crate::runtime::{RuntimeBudget, RuntimeStats, RuntimeStrategy};
+use crate::runtime_atlas::{RuntimeAtlas, RuntimePixelFormat, UpdateRegion};
+use image::Rgba;
+use std::collections::HashMap;
+
+/// Identifies one rasterization of a glyph.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct GlyphKey {
+    /// Caller-defined font identifier (e.g. index into a font list).
+    pub font_id: u32,
+    pub glyph_id: u32,
+    /// Pixel size in 1/64 px units (26.6 fixed point), so fractional sizes stay hashable.
+    pub size: u32,
+    /// Horizontal subpixel bin, `0` for glyphs snapped to whole pixels.
+    pub subpixel: u8,
+}
+
+impl GlyphKey {
+    pub fn new(font_id: u32, glyph_id: u32, size_px: f32) -> Self {
+        Self {
+            font_id,
+            glyph_id,
+            size: (size_px * 64.0).round().max(0.0) as u32,
+            subpixel: 0,
+        }
+    }
+
+    pub fn with_subpixel(mut self, bin: u8) -> Self {
+        self.subpixel = bin;
+        self
+    }
+
+    /// Pixel size as a float.
+    pub fn size_px(&self) -> f32 {
+        self.size as f32 / 64.0
+    }
+
+    fn session_key(&self) -> String {
+        format!(
+            "{}:{}:{}:{}",
+            self.font_id, self.glyph_id, self.size, self.subpixel
+        )
+    }
+}
+
+/// Pixel layout of glyph bitmaps and of the data returned by [`GlyphAtlas::region_pixels`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum GlyphFormat {
+    /// One coverage byte per pixel.
+    #[default]
+    A8,
+    /// Straight RGBA8, e.g. for color emoji.
+    Rgba8,
+}
+
+impl GlyphFormat {
+    pub fn bytes_per_pixel(self) -> usize {
+        match self {
+            GlyphFormat::A8 => 1,
+            GlyphFormat::Rgba8 => 4,
+        }
+    }
+}
+
+/// A glyph bitmap produced by a rasterizer.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RasterizedGlyph {
+    pub width: u32,
+    pub height: u32,
+    /// Offset from the pen position to the bitmap's left edge.
+    pub left: i32,
+    /// Offset from the baseline to the bitmap's top edge, positive upwards.
+    pub top: i32,
+    /// Horizontal advance in pixels.
+    pub advance: f32,
+    pub format: GlyphFormat,
+    /// `width * height * format.bytes_per_pixel()` bytes, rows top to bottom.
+    pub data: Vec<u8>,
+}
+
+/// Placement of a cached glyph.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct GlyphInfo {
+    /// Page holding the bitmap; meaningless for empty glyphs.
+    pub page: usize,
+    /// Bitmap position on the page; zero-sized for empty glyphs such as spaces.
+    pub rect: Rect,
+    pub left: i32,
+    pub top: i32,
+    pub advance: f32,
+}
+
+/// Produces glyph bitmaps for [`GlyphAtlas::get_or_rasterize`]. Returning `None` means the glyph
+/// cannot be rendered (missing font or glyph).
+pub trait GlyphRasterizer {
+    fn rasterize(&mut self, key: &GlyphKey) -> Option<RasterizedGlyph>;
+}
+
+impl<F: FnMut(&GlyphKey) -> Option<RasterizedGlyph>> GlyphRasterizer for F {
+    fn rasterize(&mut self, key: &GlyphKey) -> Option<RasterizedGlyph> {
+        self(key)
+    }
+}
+
+/// Runtime glyph atlas.
+///
+/// Glyphs are never rotated. Pages are stored in the glyph format itself (one byte per pixel
+/// for [`GlyphFormat::A8`]), so [`region_pixels`](Self::region_pixels) can be uploaded as is.
+pub struct GlyphAtlas {
+    atlas: RuntimeAtlas,
+    format: GlyphFormat,
+    glyphs: HashMap<GlyphKey, GlyphInfo>,
+    by_session_key: HashMap<String, GlyphKey>,
+    /// Union of changed areas per page since the last `take_dirty`.
+    dirty: HashMap<usize, Rect>,
+    evicted: Vec<GlyphKey>,
+}
+
+impl GlyphAtlas {
+    /// Pages are `cfg.max_width` x `cfg.max_height`; padding and extrusion come from `cfg` too
+    /// (padding of 1 or more avoids sampling neighbours with bilinear filtering).
+    pub fn new(mut cfg: PackerConfig, strategy: RuntimeStrategy, format: GlyphFormat) -> Self {
+        cfg.allow_rotation = false;
+        cfg.alpha_bleed = None;
+        let pixel_format = match format {
+            GlyphFormat::A8 => RuntimePixelFormat::A8,
+            GlyphFormat::Rgba8 => RuntimePixelFormat::Rgba8,
+        };
+        let atlas = RuntimeAtlas::new(cfg, strategy)
+            .with_background_color(Rgba([0, 0, 0, 0]))
+            .with_pixel_format(pixel_format);
+        Self {
+            atlas,
+            format,
+            glyphs: HashMap::new(),
+            by_session_key: HashMap::new(),
+            dirty: HashMap::new(),
+            evicted: Vec::new(),
+        }
+    }
+
+    /// Caps the cache at a page/pixel budget; least-recently-used glyphs are evicted to make
+    /// room (see [`RuntimeBudget`]). Lookups through [`get`](Self::get) and
+    /// [`get_or_rasterize`](Self::get_or_rasterize) count as uses.
+    pub fn with_budget(mut self, budget: RuntimeBudget) -> Self {
+        self.atlas = self.atlas.with_budget(budget);
+        self
+    }
+
+    pub fn format(&self) -> GlyphFormat {
+        self.format
+    }
+
+    /// Cached glyph, marking it as recently used.
+    pub fn get(&mut self, key: &GlyphKey) -> Option<GlyphInfo> {
+        let info = *self.glyphs.get(key)?;
+        self.atlas.touch(&key.session_key());
+        Some(info)
+    }
+
+    /// Cached glyph without touching the LRU order.
+    pub fn peek(&self, key: &GlyphKey) -> Option<&GlyphInfo> {
+        self.glyphs.get(key)
+    }
+
+    pub fn contains(&self, key: &GlyphKey) -> bool {
+        self.glyphs.contains_key(key)
+    }
+
+    /// Returns the cached glyph, rasterizing and inserting it first if needed. `Ok(None)` when
+    /// the rasterizer cannot produce the glyph.
+    pub fn get_or_rasterize(
+        &mut self,
+        key: GlyphKey,
+        rasterizer: &mut impl GlyphRasterizer,
+    ) -> Result<Option<GlyphInfo>> {
+        if let Some(info) = self.get(&key) {
+            return Ok(Some(info));
+        }
+        match rasterizer.rasterize(&key) {
+            Some(glyph) => self.insert(key, &glyph).map(Some),
+            None => Ok(None),
+        }
+    }
+
+    /// Inserts (or replaces) a glyph bitmap. Bitmaps in the other [`GlyphFormat`] are converted:
+    /// A8 becomes white with alpha, RGBA keeps only its alpha.
+    pub fn insert(&mut self, key: GlyphKey, glyph: &RasterizedGlyph) -> Result<GlyphInfo> {
+        let expected =
+            (glyph.width as usize) * (glyph.height as usize) * glyph.format.bytes_per_pixel();
+        if glyph.data.len() != expected {
+            return Err(TexPackerError::InvalidInput(format!(
+                "glyph {key:?}: {} data bytes, expected {expected} for {}x{} {:?}",
+                glyph.data.len(),
+                glyph.width,
+                glyph.height,
+                glyph.format
+            )));
+        }
+        self.remove(&key);
+        let session_key = key.session_key();
+        let (page, rect) = if glyph.width == 0 || glyph.height == 0 {
+            (0, Rect::new(0, 0, 0, 0))
+        } else {
+            let pixels = self.convert(glyph);
+            let placed = self.atlas.append_with_pixels(
+                session_key.clone(),
+                glyph.width,
+                glyph.height,
+                &pixels,
+            );
+            // Evictions may have happened even if the glyph did not fit in the end
+            self.sync_evicted();
+            let (page, frame, region) = placed?;
+            self.mark_dirty(region);
+            (page, frame.frame)
+        };
+        let info = GlyphInfo {
+            page,
+            rect,
+            left: glyph.left,
+            top: glyph.top,
+            advance: glyph.advance,
+        };
+        self.glyphs.insert(key, info);
+        self.by_session_key.insert(session_key, key);
+        Ok(info)
+    }
+
+    /// Drops a glyph and clears its pixels. Returns false if it was not cached.
+    pub fn remove(&mut self, key: &GlyphKey) -> bool {
+        let Some(info) = self.glyphs.remove(key) else {
+            return false;
+        };
+        let session_key = key.session_key();
+        self.by_session_key.remove(&session_key);
+        if info.rect.w > 0
+            && let Some(region) = self.atlas.evict_by_key_with_clear(&session_key, true)
+        {
+            self.mark_dirty(region);
+        }
+        true
+    }
+
+    /// Glyphs evicted by the budget since the last call.
+    pub fn drain_evicted(&mut self) -> Vec<GlyphKey> {
+        std::mem::take(&mut self.evicted)
+    }
+
+    /// Changed areas since the last call, one region per page covering all of that page's
+    /// changes, sorted by page. Upload each with [`region_pixels`](Self::region_pixels).
+    pub fn take_dirty(&mut self) -> Vec<UpdateRegion> {
+        let mut regions: Vec<UpdateRegion> = self
+            .dirty
+            .drain()
+            .map(|(page_id, r)| UpdateRegion {
+                page_id,
+                x: r.x,
+                y: r.y,
+                width: r.w,
+                height: r.h,
+            })
+            .collect();
+        regions.sort_by_key(|r| r.page_id);
+        regions
+    }
+
+    /// Tightly packed pixels of `region` in the atlas format, ready for a texture sub-upload.
+    pub fn region_pixels(&self, region: &UpdateRegion) -> Option<Vec<u8>> {
+        self.atlas.region_bytes(region)
+    }
+
+    /// Whole page buffer in the atlas format; rows are `page_width * bytes_per_pixel` bytes.
+    pub fn page_pixels(&self, page_id: usize) -> Option<&[u8]> {
+        self.atlas.page_bytes(page_id)
+    }
+
+    pub fn num_pages(&self) -> usize {
+        self.atlas.num_pages()
+    }
+
+    pub fn len(&self) -> usize {
+        self.glyphs.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.glyphs.is_empty()
+    }
+
+    pub fn stats(&self) -> RuntimeStats {
+        self.atlas.stats()
+    }
+
+    /// Glyph data in the atlas format.
+    fn convert(&self, glyph: &RasterizedGlyph) -> Vec<u8> {
+        match (glyph.format, self.format) {
+            (GlyphFormat::A8, GlyphFormat::Rgba8) => glyph
+                .data
+                .iter()
+                .flat_map(|&a| [255, 255, 255, a])
+                .collect(),
+            (GlyphFormat::Rgba8, GlyphFormat::A8) => {
+                glyph.data.chunks_exact(4).map(|px| px[3]).collect()
+            }
+            _ => glyph.data.clone(),
+        }
+    }
+
+    fn mark_dirty(&mut self, region: UpdateRegion) {
+        if region.is_empty() {
+            return;
+        }
+        let r = Rect::new(region.x, region.y, region.width, region.height);
+        self.dirty
+            .entry(region.page_id)
+            .and_modify(|d| {
+                let x1 = (d.x + d.w).max(r.x + r.w);
+                let y1 = (d.y + d.h).max(r.y + r.h);
+                d.x = d.x.min(r.x);
+                d.y = d.y.min(r.y);
+                d.w = x1 - d.x;
+                d.h = y1 - d.y;
+            })
+            .or_insert(r);
+    }
+
+    fn sync_evicted(&mut self) {
+        for e in self.atlas.drain_evicted() {
+            self.mark_dirty(UpdateRegion {
+                page_id: e.page_id,
+                x: e.slot.x,
+                y: e.slot.y,
+                width: e.slot.w,
+                height: e.slot.h,
+            });
+            if let Some(key) = self.by_session_key.remove(&e.key) {
+                self.glyphs.remove(&key);
+                self.evicted.push(key);
+            }
+        }
+    }
+}
+
+#[cfg(feature = "ab_glyph")]
+pub use ab::AbGlyphRasterizer;
+/// Re-exported so fonts can be loaded with the same `ab_glyph` version.
+#[cfg(feature = "ab_glyph")]
+pub use ab_glyph;
+
+#[cfg(feature = "ab_glyph")]
+mod ab {
+    use super::{GlyphFormat, GlyphKey, GlyphRasterizer, RasterizedGlyph};
+    use ab_glyph::{Font, FontArc, GlyphId, PxScale, ScaleFont, point};
+
+    /// [`GlyphRasterizer`] for `ab_glyph` fonts; `GlyphKey::font_id` indexes `fonts`.
+    ///
+    /// Subpixel bin `b` shifts the glyph right by `b / subpixel_bins` of a pixel.
+    pub struct AbGlyphRasterizer {
+        pub fonts: Vec<FontArc>,
+        pub subpixel_bins: u8,
+    }
+
+    impl AbGlyphRasterizer {
+        pub fn new(fonts: Vec<FontArc>) -> Self {
+            Self {
+                fonts,
+                subpixel_bins: 1,
+            }
+        }
+
+        pub fn with_subpixel_bins(mut self, bins: u8) -> Self {
+            self.subpixel_bins = bins.max(1);
+            self
+        }
+    }
+
+    impl GlyphRasterizer for AbGlyphRasterizer {
+        fn rasterize(&mut self, key: &GlyphKey) -> Option<RasterizedGlyph> {
+            let font = self.fonts.get(key.font_id as usize)?;
+            let scale = PxScale::from(key.size_px());
+            let id = GlyphId(u16::try_from(key.glyph_id).ok()?);
+            let advance = font.as_scaled(scale).h_advance(id);
+            let offset = f32::from(key.subpixel % self.subpixel_bins.max(1))
+                / f32::from(self.subpixel_bins.max(1));
+            let glyph = id.with_scale_and_position(scale, point(offset, 0.0));
+            let Some(outlined) = font.outline_glyph(glyph) else {
+                // No outline (e.g. space): cache the metrics only
+                return Some(RasterizedGlyph {
+                    width: 0,
+                    height: 0,
+                    left: 0,
+                    top: 0,
+                    advance,
+                    format: GlyphFormat::A8,
+                    data: Vec::new(),
+                });
+            };
+            let bounds = outlined.px_bounds();
+            let (width, height) = (bounds.width() as u32, bounds.height() as u32);
+            let mut data = vec![0u8; (width * height) as usize];
+            outlined.draw(|x, y, c| {
+                if x < width && y < height {
+                    data[(y * width + x) as usize] = (c.clamp(0.0, 1.0) * 255.0).round() as u8;
+                }
+            });
+            Some(RasterizedGlyph {
+                width,
+                height,
+                left: bounds.min.x as i32,
+                top: -(bounds.min.y as i32),
+                advance,
+                format: GlyphFormat::A8,
+                data,
+            })
+        }
+    }
+}
@@ -0,0 +1,171 @@
+//! XXH64 hashing for frame content hashes and stable frame IDs.
+
+use crate::model::Rect;
+use image::{Rgba32FImage, RgbaImage};
+
+const P1: u64 = 0x9E37_79B1_85EB_CA87;
+const P2: u64 = 0xC2B2_AE3D_27D4_EB4F;
+const P3: u64 = 0x1656_67B1_9E37_79F9;
+const P4: u64 = 0x85EB_CA77_C2B2_AE63;
+const P5: u64 = 0x27D4_EB2F_1656_67C5;
+
+/// Largest integer a JSON (f64) number represents exactly.
+const JSON_SAFE_MASK: u64 = (1 << 53) - 1;
+
+/// Streaming XXH64 hasher.
+#[derive(Debug, Clone)]
+pub struct Xxh64 {
+    seed: u64,
+    acc: [u64; 4],
+    buf: [u8; 32],
+    buf_len: usize,
+    total_len: u64,
+}
+
+impl Xxh64 {
+    pub fn new(seed: u64) -> Self {
+        Self {
+            seed,
+            acc: [
+                seed.wrapping_add(P1).wrapping_add(P2),
+                seed.wrapping_add(P2),
+                seed,
+                seed.wrapping_sub(P1),
+            ],
+            buf: [0; 32],
+            buf_len: 0,
+            total_len: 0,
+        }
+    }
+
+    pub fn update(&mut self, mut data: &[u8]) {
+        self.total_len += data.len() as u64;
+        if self.buf_len > 0 {
+            let take = (32 - self.buf_len).min(data.len());
+            self.buf[self.buf_len..self.buf_len + take].copy_from_slice(&data[..take]);
+            self.buf_len += take;
+            data = &data[take..];
+            if self.buf_len < 32 {
+                return;
+            }
+            let stripe = self.buf;
+            self.consume(&stripe);
+            self.buf_len = 0;
+        }
+        let mut stripes = data.chunks_exact(32);
+        for stripe in &mut stripes {
+            self.consume(stripe);
+        }
+        let rest = stripes.remainder();
+        self.buf[..rest.len()].copy_from_slice(rest);
+        self.buf_len = rest.len();
+    }
+
+    pub fn digest(&self) -> u64 {
+        let mut h = if self.total_len >= 32 {
+            let [v1, v2, v3, v4] = self.acc;
+            let mut h = v1
+                .rotate_left(1)
+                .wrapping_add(v2.rotate_left(7))
+                .wrapping_add(v3.rotate_left(12))
+                .wrapping_add(v4.rotate_left(18));
+            for v in self.acc {
+                h = (h ^ round(0, v)).wrapping_mul(P1).wrapping_add(P4);
+            }
+            h
+        } else {
+            self.seed.wrapping_add(P5)
+        };
+        h = h.wrapping_add(self.total_len);
+
+        let mut tail = &self.buf[..self.buf_len];
+        while tail.len() >= 8 {
+            h ^= round(0, read_u64(tail));
+            h = h.rotate_left(27).wrapping_mul(P1).wrapping_add(P4);
+            tail = &tail[8..];
+        }
+        if tail.len() >= 4 {
+            h ^= (read_u32(tail) as u64).wrapping_mul(P1);
+            h = h.rotate_left(23).wrapping_mul(P2).wrapping_add(P3);
+            tail = &tail[4..];
+        }
+        for &b in tail {
+            h ^= (b as u64).wrapping_mul(P5);
+            h = h.rotate_left(11).wrapping_mul(P1);
+        }
+
+        h ^= h >> 33;
+        h = h.wrapping_mul(P2);
+        h ^= h >> 29;
+        h = h.wrapping_mul(P3);
+        h ^ (h >> 32)
+    }
+
+    fn consume(&mut self, stripe: &[u8]) {
+        for (i, acc) in self.acc.iter_mut().enumerate() {
+            *acc = round(*acc, read_u64(&stripe[i * 8..]));
+        }
+    }
+}
+
+/// One-shot XXH64 of `data`.
+pub fn xxh64(data: &[u8], seed: u64) -> u64 {
+    let mut h = Xxh64::new(seed);
+    h.update(data);
+    h.digest()
+}
+
+/// Stable numeric frame ID derived from the key: XXH64 truncated to 53 bits so it survives
+/// JSON number parsing. Identical across runs, packs and platforms.
+pub fn stable_frame_id(key: &str) -> u64 {
+    xxh64(key.as_bytes(), 0) & JSON_SAFE_MASK
+}
+
+/// Content hash of the `source` sub-rectangle of `rgba` (its size, then RGBA8 rows).
+pub fn content_hash(rgba: &RgbaImage, source: &Rect) -> u64 {
+    let mut h = Xxh64::new(0);
+    h.update(&source.w.to_le_bytes());
+    h.update(&source.h.to_le_bytes());
+    let stride = rgba.width() as usize * 4;
+    let raw = rgba.as_raw();
+    for y in source.y..source.y + source.h {
+        let start = y as usize * stride + source.x as usize * 4;
+        h.update(&raw[start..start + source.w as usize * 4]);
+    }
+    h.digest()
+}
+
+/// [`content_hash`] of a 32-bit float image (its size, then little-endian `f32` rows).
+pub fn content_hash_rgba32f(rgba: &Rgba32FImage, source: &Rect) -> u64 {
+    let mut h = Xxh64::new(0);
+    h.update(&source.w.to_le_bytes());
+    h.update(&source.h.to_le_bytes());
+    let stride = rgba.width() as usize * 4;
+    let raw = rgba.as_raw();
+    let mut row = Vec::with_capacity(source.w as usize * 16);
+    for y in source.y..source.y + source.h {
+        let start = y as usize * stride + source.x as usize * 4;
+        row.clear();
+        row.extend(
+            raw[start..start + source.w as usize * 4]
+                .iter()
+                .flat_map(|v| v.to_le_bytes()),
+        );
+        h.update(&row);
+    }
+    h.digest()
+}
+
+fn round(acc: u64, input: u64) -> u64 {
+    acc.wrapping_add(input.wrapping_mul(P2))
+        .rotate_left(31)
+        .wrapping_mul(P1)
+}
+
+fn read_u64(b: &[u8]) -> u64 {
+    u64::from_le_bytes(b[..8].try_into().unwrap())
+}
+
+fn read_u32(b: &[u8]) -> u32 {
+    u32::from_le_bytes(b[..4].try_into().unwrap())
+}
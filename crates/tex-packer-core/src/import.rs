@@ -0,0 +1,799 @@
+//! Parsers for atlas metadata written by this crate or by other tools.
+//!
+//! Supported inputs:
+//! - JSON: this crate's json-array / json-hash, TexturePacker JSON (Hash / Array) and the
+//!   TexturePacker multipack `textures` layout
+//! - Apple plist: this crate's plist and Cocos2d plist formats 2 and 3
+//! - libGDX `.atlas`: legacy (`xy`/`size`/`orig`/`offset`) and 1.9.12+ (`bounds`/`offsets`)
+//!
+//! Frames are normalized to the crate's conventions: `frame` is the footprint on the page
+//! (width/height swapped for rotated frames), `source` is the trimmed rect within the original
+//! image (top-left origin) and `source_size` the original size. Keys keep the names used by the
+//! file; missing frame IDs are derived from the key.
+
+use crate::error::{Result, TexPackerError};
+use crate::hash::stable_frame_id;
+use crate::model::{Atlas, Frame, Meta, NinePatch, Page, Pivot, Rect};
+use serde_json::Value;
+
+/// An imported atlas plus the texture filename of each page, when the format records one.
+#[derive(Debug, Clone)]
+pub struct ImportedAtlas {
+    pub atlas: Atlas,
+    /// One entry per page (same order as `atlas.pages`); paths are as written in the file,
+    /// usually relative to the metadata file.
+    pub page_images: Vec<Option<String>>,
+}
+
+impl Atlas {
+    /// Parses JSON atlas metadata (json-array or json-hash, this crate's or TexturePacker's).
+    pub fn from_json_array(text: &str) -> Result<Atlas> {
+        Ok(import_json(text)?.atlas)
+    }
+
+    /// Alias of [`Atlas::from_json_array`]; the JSON layout is detected from the content.
+    pub fn from_json_hash(text: &str) -> Result<Atlas> {
+        Ok(import_json(text)?.atlas)
+    }
+
+    /// Parses an Apple plist atlas (this crate's or Cocos2d format 2/3).
+    pub fn from_plist(text: &str) -> Result<Atlas> {
+        Ok(import_plist(text)?.atlas)
+    }
+
+    /// Parses a libGDX `.atlas` text file.
+    pub fn from_gdx(text: &str) -> Result<Atlas> {
+        Ok(import_gdx(text)?.atlas)
+    }
+}
+
+/// Detects the format from the content (`{` JSON, `<` plist, otherwise libGDX) and parses it.
+pub fn import_atlas(text: &str) -> Result<ImportedAtlas> {
+    match text.trim_start().chars().next() {
+        Some('{') => import_json(text),
+        Some('<') => import_plist(text),
+        _ => import_gdx(text),
+    }
+}
+
+fn invalid(msg: impl Into<String>) -> TexPackerError {
+    TexPackerError::InvalidInput(msg.into())
+}
+
+/// Builds a frame from its page position and unrotated packed size (`packed`), deriving the
+/// page footprint.
+fn make_frame(
+    key: String,
+    packed: Rect,
+    rotated: bool,
+    source: Rect,
+    source_size: (u32, u32),
+) -> Frame {
+    let Rect { x, y, w, h } = packed;
+    let (fw, fh) = if rotated { (h, w) } else { (w, h) };
+    Frame {
+        id: stable_frame_id(&key),
+        key,
+        frame: Rect::new(x, y, fw, fh),
+        rotated,
+        trimmed: source.x != 0 || source.y != 0 || (source.w, source.h) != source_size,
+        source,
+        source_size,
+        degenerate: false,
+        content_hash: None,
+        pivot: Default::default(),
+        nine_patch: None,
+        scale: None,
+    }
+}
+
+/// Meta for atlases that do not carry this crate's `Meta`, inferred from the pages.
+fn inferred_meta(
+    pages: &[Page],
+    app: Option<&str>,
+    version: Option<&str>,
+    format: Option<&str>,
+    scale: f32,
+) -> Meta {
+    let frames = || pages.iter().flat_map(|p| &p.frames);
+    let max_w = pages.iter().map(|p| p.width).max().unwrap_or(0);
+    let max_h = pages.iter().map(|p| p.height).max().unwrap_or(0);
+    Meta {
+        schema_version: "1".into(),
+        app: app.unwrap_or("unknown").into(),
+        version: version.unwrap_or("").into(),
+        format: format.unwrap_or("RGBA8888").into(),
+        scale,
+        power_of_two: max_w.is_power_of_two() && max_h.is_power_of_two(),
+        square: pages.iter().all(|p| p.width == p.height),
+        max_dim: (max_w, max_h),
+        padding: (0, 0),
+        extrude: 0,
+        allow_rotation: frames().any(|f| f.rotated),
+        rotation_direction: Default::default(),
+        trim_mode: if frames().any(|f| f.trimmed) {
+            "trim"
+        } else {
+            "none"
+        }
+        .into(),
+        background_color: None,
+        packer: None,
+        page_minimization: None,
+        auto_refinement: None,
+        align: None,
+        animations: Vec::new(),
+        sdf: None,
+        mip: None,
+    }
+}
+
+/// Grows pages whose size is unknown (0) to the extent of their frames.
+fn fill_page_sizes(pages: &mut [Page]) {
+    for p in pages {
+        if p.width == 0 || p.height == 0 {
+            p.width = p
+                .frames
+                .iter()
+                .map(|f| f.frame.x + f.frame.w)
+                .max()
+                .unwrap_or(0);
+            p.height = p
+                .frames
+                .iter()
+                .map(|f| f.frame.y + f.frame.h)
+                .max()
+                .unwrap_or(0);
+        }
+    }
+}
+
+// ---------------------------------------------------------------------------------------------
+// JSON
+
+/// Parses JSON atlas metadata (see the module docs for the accepted layouts).
+pub fn import_json(text: &str) -> Result<ImportedAtlas> {
+    let v: Value = serde_json::from_str(text).map_err(|e| invalid(format!("JSON: {e}")))?;
+    let mut pages: Vec<Page> = Vec::new();
+    let mut page_images: Vec<Option<String>> = Vec::new();
+    let meta_v = &v["meta"];
+    let meta_size = json_size(&meta_v["size"]);
+
+    if let Some(arr) = v["pages"].as_array() {
+        // tex-packer json-array
+        for (i, p) in arr.iter().enumerate() {
+            let frames = json_frames(&p["frames"])?;
+            pages.push(Page {
+                id: p["id"].as_u64().map_or(i, |id| id as usize),
+                width: json_u32(p, "width"),
+                height: json_u32(p, "height"),
+                frames,
+            });
+            page_images.push(p["image"].as_str().map(str::to_string));
+        }
+    } else if let Some(arr) = v["textures"].as_array() {
+        // TexturePacker multipack
+        for (i, t) in arr.iter().enumerate() {
+            let (w, h) = json_size(&t["size"]);
+            pages.push(Page {
+                id: i,
+                width: w,
+                height: h,
+                frames: json_frames(&t["frames"])?,
+            });
+            page_images.push(t["image"].as_str().map(str::to_string));
+        }
+    } else if v["frames"].is_array() || v["frames"].is_object() {
+        // json-hash (per-frame `page`/`pageSize` when written by tex-packer) or TexturePacker
+        // JSON Array/Hash (single page sized by `meta.size`)
+        let entries: Vec<(Option<&str>, &Value)> = match &v["frames"] {
+            Value::Array(a) => a.iter().map(|f| (None, f)).collect(),
+            Value::Object(m) => m.iter().map(|(k, f)| (Some(k.as_str()), f)).collect(),
+            _ => unreachable!(),
+        };
+        for (key, f) in entries {
+            let page_id = f["page"].as_u64().unwrap_or(0) as usize;
+            let idx = match pages.iter().position(|p| p.id == page_id) {
+                Some(i) => i,
+                None => {
+                    let (w, h) = if f["pageSize"].is_object() {
+                        json_size(&f["pageSize"])
+                    } else {
+                        meta_size
+                    };
+                    pages.push(Page {
+                        id: page_id,
+                        width: w,
+                        height: h,
+                        frames: Vec::new(),
+                    });
+                    pages.len() - 1
+                }
+            };
+            pages[idx].frames.push(json_frame(key, f)?);
+        }
+        pages.sort_by_key(|p| p.id);
+        let image = meta_v["image"].as_str().map(str::to_string);
+        page_images = pages.iter().map(|_| image.clone()).collect();
+        if pages.len() > 1 {
+            page_images.iter_mut().for_each(|i| *i = None);
+        }
+    } else {
+        return Err(invalid(
+            "JSON: expected `pages`, `textures` or `frames` at the top level",
+        ));
+    }
+
+    fill_page_sizes(&mut pages);
+    let meta = match serde_json::from_value::<Meta>(meta_v.clone()) {
+        Ok(m) => m,
+        Err(_) => {
+            let scale = match &meta_v["scale"] {
+                Value::String(s) => s.parse().unwrap_or(1.0),
+                Value::Number(n) => n.as_f64().unwrap_or(1.0) as f32,
+                _ => 1.0,
+            };
+            inferred_meta(
+                &pages,
+                meta_v["app"].as_str(),
+                meta_v["version"].as_str(),
+                meta_v["format"].as_str(),
+                scale,
+            )
+        }
+    };
+    Ok(ImportedAtlas {
+        atlas: Atlas { pages, meta },
+        page_images,
+    })
+}
+
+fn json_u32(v: &Value, k: &str) -> u32 {
+    v[k].as_u64().unwrap_or(0) as u32
+}
+
+fn json_rect(v: &Value) -> Option<Rect> {
+    v.is_object().then(|| {
+        Rect::new(
+            json_u32(v, "x"),
+            json_u32(v, "y"),
+            json_u32(v, "w"),
+            json_u32(v, "h"),
+        )
+    })
+}
+
+fn json_size(v: &Value) -> (u32, u32) {
+    (json_u32(v, "w"), json_u32(v, "h"))
+}
+
+fn json_frames(v: &Value) -> Result<Vec<Frame>> {
+    match v {
+        Value::Array(a) => a.iter().map(|f| json_frame(None, f)).collect(),
+        Value::Object(m) => m.iter().map(|(k, f)| json_frame(Some(k), f)).collect(),
+        Value::Null => Ok(Vec::new()),
+        _ => Err(invalid("JSON: `frames` must be an array or object")),
+    }
+}
+
+fn json_frame(key: Option<&str>, f: &Value) -> Result<Frame> {
+    let key = key
+        .or_else(|| f["key"].as_str())
+        .or_else(|| f["filename"].as_str())
+        .ok_or_else(|| invalid("JSON: frame without `key` or `filename`"))?
+        .to_string();
+    let frame = json_rect(&f["frame"])
+        .ok_or_else(|| invalid(format!("JSON: frame `{key}` has no `frame` rect")))?;
+    let rotated = f["rotated"].as_bool().unwrap_or(false);
+    let source = json_rect(&f["spriteSourceSize"]).unwrap_or(Rect::new(0, 0, frame.w, frame.h));
+    let source_size = if f["sourceSize"].is_object() {
+        json_size(&f["sourceSize"])
+    } else {
+        (source.w, source.h)
+    };
+    // tex-packer writes the page footprint; TexturePacker writes the unrotated size.
+    let (w, h) = if rotated && (frame.w, frame.h) != (source.w, source.h) {
+        (frame.h, frame.w)
+    } else {
+        (frame.w, frame.h)
+    };
+    let mut out = make_frame(
+        key,
+        Rect::new(frame.x, frame.y, w, h),
+        rotated,
+        source,
+        source_size,
+    );
+    if let Some(t) = f["trimmed"].as_bool() {
+        out.trimmed = t;
+    }
+    out.degenerate = f["degenerate"].as_bool().unwrap_or(false);
+    if let Some(id) = f["id"].as_u64() {
+        out.id = id;
+    }
+    out.content_hash = f["contentHash"]
+        .as_str()
+        .and_then(|h| u64::from_str_radix(h, 16).ok());
+    if let (Some(x), Some(y)) = (f["pivot"]["x"].as_f64(), f["pivot"]["y"].as_f64()) {
+        out.pivot = Pivot::new(x, y);
+    }
+    out.scale = f["scale"].as_f64().map(|s| s as f32);
+    if f["ninePatch"].is_object() {
+        let n = &f["ninePatch"];
+        out.nine_patch = Some(NinePatch {
+            left: json_u32(n, "left"),
+            top: json_u32(n, "top"),
+            right: json_u32(n, "right"),
+            bottom: json_u32(n, "bottom"),
+        });
+    }
+    Ok(out)
+}
+
+// ---------------------------------------------------------------------------------------------
+// plist
+
+#[derive(Debug, Clone)]
+enum PlistValue {
+    Dict(Vec<(String, PlistValue)>),
+    Array(Vec<PlistValue>),
+    String(String),
+    Integer(i64),
+    Real(f64),
+    Bool(bool),
+}
+
+impl PlistValue {
+    fn get(&self, key: &str) -> Option<&PlistValue> {
+        match self {
+            PlistValue::Dict(entries) => entries.iter().find(|(k, _)| k == key).map(|(_, v)| v),
+            _ => None,
+        }
+    }
+
+    fn as_str(&self) -> Option<&str> {
+        match self {
+            PlistValue::String(s) => Some(s),
+            _ => None,
+        }
+    }
+
+    fn as_bool(&self) -> Option<bool> {
+        match self {
+            PlistValue::Bool(b) => Some(*b),
+            PlistValue::Integer(i) => Some(*i != 0),
+            _ => None,
+        }
+    }
+
+    fn as_f64(&self) -> Option<f64> {
+        match self {
+            PlistValue::Integer(i) => Some(*i as f64),
+            PlistValue::Real(r) => Some(*r),
+            PlistValue::String(s) => s.trim().parse().ok(),
+            _ => None,
+        }
+    }
+}
+
+/// Minimal XML plist reader (dict/array/key/string/integer/real/true/false).
+struct PlistReader<'a> {
+    s: &'a str,
+    pos: usize,
+}
+
+impl<'a> PlistReader<'a> {
+    /// Next tag as (name, is_closing, is_self_closing), skipping prolog, doctype and comments.
+    fn next_tag(&mut self) -> Result<Option<(&'a str, bool, bool)>> {
+        loop {
+            let Some(start) = self.s[self.pos..].find('<').map(|i| self.pos + i) else {
+                return Ok(None);
+            };
+            if self.s[start..].starts_with("<!--") {
+                let end = self.s[start..]
+                    .find("-->")
+                    .ok_or_else(|| invalid("plist: unterminated comment"))?;
+                self.pos = start + end + 3;
+                continue;
+            }
+            let end = self.s[start..]
+                .find('>')
+                .map(|i| start + i)
+                .ok_or_else(|| invalid("plist: unterminated tag"))?;
+            self.pos = end + 1;
+            let inner = &self.s[start + 1..end];
+            if inner.starts_with('?') || inner.starts_with('!') {
+                continue;
+            }
+            let closing = inner.starts_with('/');
+            let self_closing = inner.ends_with('/');
+            let name = inner
+                .trim_start_matches('/')
+                .trim_end_matches('/')
+                .split_whitespace()
+                .next()
+                .unwrap_or("");
+            return Ok(Some((name, closing, self_closing)));
+        }
+    }
+
+    /// Text up to the closing `</name>`.
+    fn text_until(&mut self, name: &str) -> Result<String> {
+        let close = format!("</{name}>");
+        let end = self.s[self.pos..]
+            .find(&close)
+            .map(|i| self.pos + i)
+            .ok_or_else(|| invalid(format!("plist: missing {close}")))?;
+        let raw = &self.s[self.pos..end];
+        self.pos = end + close.len();
+        Ok(raw
+            .replace("&lt;", "<")
+            .replace("&gt;", ">")
+            .replace("&quot;", "\"")
+            .replace("&apos;", "'")
+            .replace("&amp;", "&"))
+    }
+
+    /// Parses the value whose opening tag was just read.
+    fn value(&mut self, name: &str, self_closing: bool) -> Result<PlistValue> {
+        match name {
+            "true" | "false" => {
+                if !self_closing {
+                    self.text_until(name)?;
+                }
+                Ok(PlistValue::Bool(name == "true"))
+            }
+            "string" | "key" | "date" | "data" if self_closing => {
+                Ok(PlistValue::String(String::new()))
+            }
+            "string" | "date" | "data" => Ok(PlistValue::String(self.text_until(name)?)),
+            "integer" => {
+                let t = self.text_until(name)?;
+                t.trim()
+                    .parse()
+                    .map(PlistValue::Integer)
+                    .map_err(|_| invalid(format!("plist: bad integer `{t}`")))
+            }
+            "real" => {
+                let t = self.text_until(name)?;
+                t.trim()
+                    .parse()
+                    .map(PlistValue::Real)
+                    .map_err(|_| invalid(format!("plist: bad real `{t}`")))
+            }
+            "array" => {
+                let mut items = Vec::new();
+                if self_closing {
+                    return Ok(PlistValue::Array(items));
+                }
+                loop {
+                    match self.next_tag()? {
+                        Some(("array", true, _)) => return Ok(PlistValue::Array(items)),
+                        Some((n, false, sc)) => items.push(self.value(n, sc)?),
+                        _ => return Err(invalid("plist: malformed array")),
+                    }
+                }
+            }
+            "dict" => {
+                let mut entries = Vec::new();
+                if self_closing {
+                    return Ok(PlistValue::Dict(entries));
+                }
+                loop {
+                    match self.next_tag()? {
+                        Some(("dict", true, _)) => return Ok(PlistValue::Dict(entries)),
+                        Some(("key", false, sc)) => {
+                            let key = if sc {
+                                String::new()
+                            } else {
+                                self.text_until("key")?
+                            };
+                            match self.next_tag()? {
+                                Some((n, false, sc)) => entries.push((key, self.value(n, sc)?)),
+                                _ => {
+                                    return Err(invalid(format!(
+                                        "plist: key `{key}` has no value"
+                                    )));
+                                }
+                            }
+                        }
+                        _ => return Err(invalid("plist: malformed dict")),
+                    }
+                }
+            }
+            other => Err(invalid(format!("plist: unsupported element <{other}>"))),
+        }
+    }
+}
+
+fn parse_plist(text: &str) -> Result<PlistValue> {
+    let mut r = PlistReader { s: text, pos: 0 };
+    loop {
+        match r.next_tag()? {
+            Some(("plist", false, _)) => continue,
+            Some((name, false, sc)) => return r.value(name, sc),
+            Some(_) => continue,
+            None => return Err(invalid("plist: no root value")),
+        }
+    }
+}
+
+/// Numbers in a plist geometry string such as `{{1,2},{3,4}}` or `{5, 6}`.
+fn plist_numbers(s: &str) -> Vec<f64> {
+    s.split(['{', '}', ','])
+        .filter_map(|t| t.trim().parse().ok())
+        .collect()
+}
+
+fn plist_rect(v: Option<&PlistValue>) -> Option<Rect> {
+    match plist_numbers(v?.as_str()?).as_slice() {
+        [x, y, w, h] => Some(Rect::new(*x as u32, *y as u32, *w as u32, *h as u32)),
+        _ => None,
+    }
+}
+
+fn plist_pair(v: Option<&PlistValue>) -> Option<(f64, f64)> {
+    match plist_numbers(v?.as_str()?).as_slice() {
+        [a, b] => Some((*a, *b)),
+        _ => None,
+    }
+}
+
+/// Parses an Apple plist atlas (this crate's or Cocos2d format 2/3).
+pub fn import_plist(text: &str) -> Result<ImportedAtlas> {
+    let root = parse_plist(text)?;
+    let Some(PlistValue::Dict(frames)) = root.get("frames") else {
+        return Err(invalid("plist: missing `frames` dict"));
+    };
+    let meta = root.get("metadata").or_else(|| root.get("meta"));
+    let meta_get = |k: &str| meta.and_then(|m| m.get(k));
+    let meta_size = plist_pair(meta_get("size")).map(|(w, h)| (w as u32, h as u32));
+    let page_sizes: Vec<(u32, u32)> = match meta_get("pages") {
+        Some(PlistValue::Array(a)) => a
+            .iter()
+            .filter_map(|p| plist_pair(Some(p)).map(|(w, h)| (w as u32, h as u32)))
+            .collect(),
+        _ => Vec::new(),
+    };
+
+    let mut pages: Vec<Page> = Vec::new();
+    for (key, f) in frames {
+        // format 3 (`textureRect`) or format 2 / tex-packer (`frame`)
+        let rect = plist_rect(f.get("textureRect").or_else(|| f.get("frame")))
+            .ok_or_else(|| invalid(format!("plist: frame `{key}` has no frame rect")))?;
+        let rotated = f
+            .get("textureRotated")
+            .or_else(|| f.get("rotated"))
+            .and_then(PlistValue::as_bool)
+            .unwrap_or(false);
+        let source_size = plist_pair(
+            f.get("spriteSourceSize")
+                .filter(|v| v.as_str().is_some_and(|s| plist_numbers(s).len() == 2)),
+        )
+        .or_else(|| plist_pair(f.get("sourceSize")))
+        .map(|(w, h)| (w as u32, h as u32));
+        let source = if let Some(r) =
+            plist_rect(f.get("sourceColorRect")).or_else(|| plist_rect(f.get("spriteSourceSize")))
+        {
+            r
+        } else {
+            // format 3 without a color rect: derive from the centre offset (y up)
+            let (w, h) = plist_pair(f.get("spriteSize"))
+                .map_or((rect.w, rect.h), |(w, h)| (w as u32, h as u32));
+            let (ow, oh) = source_size.unwrap_or((w, h));
+            let (dx, dy) =
+                plist_pair(f.get("spriteOffset").or_else(|| f.get("offset"))).unwrap_or((0.0, 0.0));
+            let x = (ow as f64 - w as f64) / 2.0 + dx;
+            let y = (oh as f64 - h as f64) / 2.0 - dy;
+            Rect::new(x.max(0.0).round() as u32, y.max(0.0).round() as u32, w, h)
+        };
+        let source_size = source_size.unwrap_or((source.w, source.h));
+        // tex-packer writes the page footprint; Cocos writes the unrotated size.
+        let (w, h) = if rotated && (rect.w, rect.h) != (source.w, source.h) {
+            (rect.h, rect.w)
+        } else {
+            (rect.w, rect.h)
+        };
+        let mut frame = make_frame(
+            key.clone(),
+            Rect::new(rect.x, rect.y, w, h),
+            rotated,
+            source,
+            source_size,
+        );
+        if let Some(t) = f.get("trimmed").and_then(PlistValue::as_bool) {
+            frame.trimmed = t;
+        }
+        if let Some((x, y)) = plist_pair(f.get("pivot")).or_else(|| plist_pair(f.get("anchor"))) {
+            frame.pivot = Pivot::new(x, y);
+        }
+
+        let page_id = f
+            .get("page")
+            .and_then(PlistValue::as_f64)
+            .map_or(0, |p| p as usize);
+        let idx = match pages.iter().position(|p| p.id == page_id) {
+            Some(i) => i,
+            None => {
+                let (pw, ph) = plist_pair(f.get("pageSize"))
+                    .map(|(w, h)| (w as u32, h as u32))
+                    .or_else(|| page_sizes.get(page_id).copied())
+                    .or(meta_size)
+                    .unwrap_or((0, 0));
+                pages.push(Page {
+                    id: page_id,
+                    width: pw,
+                    height: ph,
+                    frames: Vec::new(),
+                });
+                pages.len() - 1
+            }
+        };
+        pages[idx].frames.push(frame);
+    }
+    pages.sort_by_key(|p| p.id);
+    fill_page_sizes(&mut pages);
+
+    let names: Vec<String> = match meta_get("textureFileNames") {
+        Some(PlistValue::Array(a)) => a
+            .iter()
+            .filter_map(|v| v.as_str().map(str::to_string))
+            .collect(),
+        _ => meta_get("textureFileName")
+            .or_else(|| meta_get("realTextureFileName"))
+            .and_then(PlistValue::as_str)
+            .map(|s| vec![s.to_string()])
+            .unwrap_or_default(),
+    };
+    let page_images = pages
+        .iter()
+        .enumerate()
+        .map(|(i, _)| names.get(i).cloned())
+        .collect();
+    let scale = meta_get("scale")
+        .and_then(PlistValue::as_f64)
+        .unwrap_or(1.0) as f32;
+    let mut meta = inferred_meta(
+        &pages,
+        meta_get("app").and_then(PlistValue::as_str),
+        meta_get("version").and_then(PlistValue::as_str),
+        meta_get("format")
+            .and_then(PlistValue::as_str)
+            .or(meta_get("pixelFormat").and_then(PlistValue::as_str)),
+        scale,
+    );
+    if let Some(b) = meta_get("allowRotation").and_then(PlistValue::as_bool) {
+        meta.allow_rotation = b;
+    }
+    Ok(ImportedAtlas {
+        atlas: Atlas { pages, meta },
+        page_images,
+    })
+}
+
+// ---------------------------------------------------------------------------------------------
+// libGDX
+
+/// Parses a libGDX `.atlas` file. Region keys are the region name, with `_<index>` appended
+/// when the region has a non-negative `index` (the inverse of `to_gdx_atlas`).
+pub fn import_gdx(text: &str) -> Result<ImportedAtlas> {
+    struct Region {
+        name: String,
+        fields: Vec<(String, String)>,
+    }
+    type GdxPage = (String, Vec<(String, String)>, Vec<Region>);
+    let mut pages: Vec<GdxPage> = Vec::new();
+    let mut expect_page = true;
+    for line in text.lines() {
+        let t = line.trim();
+        if t.is_empty() {
+            expect_page = true;
+            continue;
+        }
+        if expect_page {
+            pages.push((t.to_string(), Vec::new(), Vec::new()));
+            expect_page = false;
+            continue;
+        }
+        let page = pages.last_mut().expect("page header precedes fields");
+        match t.split_once(':') {
+            Some((k, v)) => {
+                let field = (k.trim().to_string(), v.trim().to_string());
+                match page.2.last_mut() {
+                    Some(r) => r.fields.push(field),
+                    None => page.1.push(field),
+                }
+            }
+            None => page.2.push(Region {
+                name: t.to_string(),
+                fields: Vec::new(),
+            }),
+        }
+    }
+    if pages.is_empty() {
+        return Err(invalid("libGDX atlas: no pages"));
+    }
+
+    let ints =
+        |s: &str| -> Vec<i64> { s.split(',').filter_map(|t| t.trim().parse().ok()).collect() };
+    let mut out_pages = Vec::with_capacity(pages.len());
+    let mut page_images = Vec::with_capacity(pages.len());
+    for (id, (image, fields, regions)) in pages.into_iter().enumerate() {
+        let field = |fs: &[(String, String)], k: &str| {
+            fs.iter().find(|(n, _)| n == k).map(|(_, v)| v.clone())
+        };
+        let (pw, ph) = match field(&fields, "size").map(|s| ints(&s)).as_deref() {
+            Some([w, h]) => (*w as u32, *h as u32),
+            _ => (0, 0),
+        };
+        let mut frames = Vec::with_capacity(regions.len());
+        for r in regions {
+            let f = |k: &str| field(&r.fields, k).map(|s| ints(&s));
+            let (x, y, w, h) = match (f("bounds"), f("xy"), f("size")) {
+                (Some(b), _, _) if b.len() == 4 => (b[0], b[1], b[2], b[3]),
+                (_, Some(xy), Some(sz)) if xy.len() == 2 && sz.len() == 2 => {
+                    (xy[0], xy[1], sz[0], sz[1])
+                }
+                _ => {
+                    return Err(invalid(format!(
+                        "libGDX atlas: region `{}` has no bounds",
+                        r.name
+                    )));
+                }
+            };
+            let (ox, oy, ow, oh) = match (f("offsets"), f("offset"), f("orig")) {
+                (Some(o), _, _) if o.len() == 4 => (o[0], o[1], o[2], o[3]),
+                (_, Some(o), Some(orig)) if o.len() == 2 && orig.len() == 2 => {
+                    (o[0], o[1], orig[0], orig[1])
+                }
+                _ => (0, 0, w, h),
+            };
+            let rotated = field(&r.fields, "rotate")
+                .is_some_and(|v| v == "true" || v.parse::<i64>().is_ok_and(|d| d != 0));
+            let index = field(&r.fields, "index").and_then(|v| v.parse::<i64>().ok());
+            let key = match index {
+                Some(i) if i >= 0 => format!("{}_{}", r.name, i),
+                _ => r.name,
+            };
+            // gdx offsets are measured from the bottom-left of the original image
+            let source = Rect::new(
+                ox.max(0) as u32,
+                (oh - oy - h).max(0) as u32,
+                w as u32,
+                h as u32,
+            );
+            let mut frame = make_frame(
+                key,
+                Rect::new(x as u32, y as u32, w as u32, h as u32),
+                rotated,
+                source,
+                (ow as u32, oh as u32),
+            );
+            if let Some([l, r, t, b]) = f("split").as_deref() {
+                frame.nine_patch = Some(NinePatch {
+                    left: *l as u32,
+                    top: *t as u32,
+                    right: *r as u32,
+                    bottom: *b as u32,
+                });
+            }
+            frames.push(frame);
+        }
+        out_pages.push(Page {
+            id,
+            width: pw,
+            height: ph,
+            frames,
+        });
+        page_images.push(Some(image));
+    }
+    fill_page_sizes(&mut out_pages);
+    let meta = inferred_meta(&out_pages, Some("libgdx"), None, None, 1.0);
+    Ok(ImportedAtlas {
+        atlas: Atlas {
+            pages: out_pages,
+            meta,
+        },
+        page_images,
+    })
+}
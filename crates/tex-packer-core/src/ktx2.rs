@@ -0,0 +1,321 @@
+use crate::config::{BasisCodec, PageEncoding};
+use crate::error::{Result, TexPackerError};
+use basis_universal::{BasisTextureFormat, ColorSpace, Compressor, CompressorParams, sys};
+use image::RgbaImage;
+use std::ops::Range;
+
+/// KTX2 file identifier: `«KTX 20»\r\n\x1A\n`.
+const KTX2_IDENTIFIER: [u8; 12] = [
+    0xAB, 0x4B, 0x54, 0x58, 0x20, 0x32, 0x30, 0xBB, 0x0D, 0x0A, 0x1A, 0x0A,
+];
+const SUPERCOMPRESSION_NONE: u32 = 0;
+const SUPERCOMPRESSION_BASIS_LZ: u32 = 1;
+const KHR_DF_MODEL_ETC1S: u32 = 163;
+const KHR_DF_MODEL_UASTC: u32 = 166;
+const UASTC_BLOCK_BYTES: u32 = 16;
+
+// `.basis` layout (basisu_file_headers.h): a packed header, then one packed descriptor per slice.
+const BASIS_SIGNATURE: u32 = 0x4273;
+const BASIS_HEADER_SIZE: usize = 77;
+const BASIS_SLICE_DESC_SIZE: usize = 23;
+const BASIS_TEX_FORMAT_UASTC: u32 = 1;
+const BASIS_SLICE_HAS_ALPHA: u32 = 1;
+
+/// Encode an RGBA page as a single-level KTX2 texture holding Basis Universal data.
+///
+/// `Etc1s` stores BasisLZ-supercompressed ETC1S (RGB slice plus an alpha slice when the page
+/// has transparency); `Uastc` stores UASTC 4x4 blocks without supercompression. Quality comes
+/// from `enc.basis_quality`. Textures are tagged sRGB; loaders transcode them to the BCn, ETC
+/// or ASTC format the device supports.
+pub fn encode_ktx2(rgba: &RgbaImage, codec: BasisCodec, enc: &PageEncoding) -> Result<Vec<u8>> {
+    encode_ktx2_with_mips(rgba, &[], codec, enc)
+}
+
+/// Like [`encode_ktx2`], storing `mips` (levels 1.., e.g. from [`crate::mip_chain`]) as the
+/// texture's further mip levels. Each level must halve the previous one (rounding down, at
+/// least 1x1).
+pub fn encode_ktx2_with_mips(
+    rgba: &RgbaImage,
+    mips: &[RgbaImage],
+    codec: BasisCodec,
+    enc: &PageEncoding,
+) -> Result<Vec<u8>> {
+    let (width, height) = rgba.dimensions();
+    if width == 0 || height == 0 {
+        return Err(TexPackerError::InvalidDimensions { width, height });
+    }
+    let mut prev = (width, height);
+    for mip in mips {
+        let expected = ((prev.0 / 2).max(1), (prev.1 / 2).max(1));
+        if prev == (1, 1) || mip.dimensions() != expected {
+            let (width, height) = mip.dimensions();
+            return Err(TexPackerError::InvalidDimensions { width, height });
+        }
+        prev = expected;
+    }
+    let basis = compress_basis(rgba, mips, codec, enc.basis_quality)?;
+    basis_to_ktx2(&basis)
+}
+
+/// Runs the Basis Universal encoder and returns the `.basis` file.
+fn compress_basis(
+    rgba: &RgbaImage,
+    mips: &[RgbaImage],
+    codec: BasisCodec,
+    quality: u8,
+) -> Result<Vec<u8>> {
+    let quality = u32::from(quality.max(1));
+    let mut params = CompressorParams::new();
+    params.set_print_status_to_stdout(false);
+    params.set_color_space(ColorSpace::Srgb);
+    match codec {
+        BasisCodec::Etc1s => {
+            params.set_basis_format(BasisTextureFormat::ETC1S);
+            params.set_etc1s_quality_level(quality);
+        }
+        BasisCodec::Uastc => {
+            params.set_basis_format(BasisTextureFormat::UASTC4x4);
+            // 1-255 onto the pack levels 0 (fastest) - 4 (very slow). `set_uastc_quality_level`
+            // ORs into the default level, so the level bits are replaced here instead.
+            let level = (quality * 5 / 256) as sys::UastcPackFlags;
+            // SAFETY: `params.0` is the live parameter object owned by `params`.
+            unsafe {
+                let flags = sys::compressor_params_get_pack_uastc_flags(params.0)
+                    & !sys::UastcPackFlags_PackUASTCLevelMask;
+                sys::compressor_params_set_pack_uastc_flags(params.0, flags | level);
+            }
+        }
+    }
+    params
+        .source_image_mut(0)
+        .init(rgba.as_raw(), rgba.width(), rgba.height(), 4);
+    for (i, mip) in mips.iter().enumerate() {
+        params.source_mipmap_image_mut(0, i as u32).init(
+            mip.as_raw(),
+            mip.width(),
+            mip.height(),
+            4,
+        );
+    }
+
+    let threads = std::thread::available_parallelism().map_or(1, |n| n.get() as u32);
+    let mut compressor = Compressor::new(threads);
+    // SAFETY: the parameters hold one RGBA8 image (plus its mip chain) and in-range quality
+    // settings.
+    unsafe {
+        if !compressor.init(&params) {
+            return Err(TexPackerError::Encode(
+                "basis: could not initialize the encoder".into(),
+            ));
+        }
+        compressor
+            .process()
+            .map_err(|e| TexPackerError::Encode(format!("basis: {e:?}")))?;
+    }
+    Ok(compressor.basis_file().to_vec())
+}
+
+/// One compressed slice of a `.basis` file.
+struct BasisSlice {
+    level: usize,
+    alpha: bool,
+    width: u32,
+    height: u32,
+    data: Range<usize>,
+}
+
+/// Reads `len` little-endian bytes at `offset`.
+fn read_le(bytes: &[u8], offset: usize, len: usize) -> Result<u32> {
+    let field = bytes
+        .get(offset..offset + len)
+        .ok_or_else(|| TexPackerError::Encode("basis: truncated file".into()))?;
+    Ok(field.iter().rev().fold(0, |v, &b| (v << 8) | u32::from(b)))
+}
+
+/// Repackages a `.basis` file as KTX2, following basisu's own KTX2 writer: slices of a level
+/// are concatenated, ETC1S codebooks and Huffman tables become the BasisLZ global data.
+fn basis_to_ktx2(basis: &[u8]) -> Result<Vec<u8>> {
+    let field = |offset, len| read_le(basis, offset, len);
+    if field(0, 2)? != BASIS_SIGNATURE || field(4, 2)? as usize != BASIS_HEADER_SIZE {
+        return Err(TexPackerError::Encode(
+            "basis: unexpected file header".into(),
+        ));
+    }
+    let total_slices = field(14, 3)? as usize;
+    let uastc = field(20, 1)? == BASIS_TEX_FORMAT_UASTC;
+    let range = |offset, size| -> Result<Range<usize>> {
+        let start = field(offset, 4)? as usize;
+        let end = start + field(offset + 4, size)? as usize;
+        if end > basis.len() {
+            return Err(TexPackerError::Encode("basis: truncated file".into()));
+        }
+        Ok(start..end)
+    };
+    let endpoints = range(41, 3)?;
+    let selectors = range(50, 3)?;
+    let tables = range(57, 4)?;
+    let slice_descs = field(65, 4)? as usize;
+
+    let mut slices = Vec::with_capacity(total_slices);
+    for i in 0..total_slices {
+        let desc = slice_descs + i * BASIS_SLICE_DESC_SIZE;
+        let start = field(desc + 13, 4)? as usize;
+        let end = start + field(desc + 17, 4)? as usize;
+        if end > basis.len() {
+            return Err(TexPackerError::Encode("basis: truncated file".into()));
+        }
+        slices.push(BasisSlice {
+            level: field(desc + 3, 1)? as usize,
+            alpha: field(desc + 4, 1)? & BASIS_SLICE_HAS_ALPHA != 0,
+            width: field(desc + 5, 2)?,
+            height: field(desc + 7, 2)?,
+            data: start..end,
+        });
+    }
+    let Some(base) = slices.iter().find(|s| s.level == 0) else {
+        return Err(TexPackerError::Encode("basis: file has no slices".into()));
+    };
+    let (width, height) = (base.width, base.height);
+    let has_alpha = slices.iter().any(|s| s.alpha);
+    let level_count = slices.iter().map(|s| s.level + 1).max().unwrap_or(1);
+
+    // Level data plus, for ETC1S, one image descriptor per level: flags, then offset/length of
+    // the RGB and alpha slices within the level.
+    let mut levels = vec![Vec::new(); level_count];
+    let mut image_descs = vec![[0u32; 5]; level_count];
+    for slice in &slices {
+        let level = &mut levels[slice.level];
+        let desc = &mut image_descs[slice.level];
+        let at = if slice.alpha { 3 } else { 1 };
+        desc[at] = level.len() as u32;
+        desc[at + 1] = slice.data.len() as u32;
+        level.extend_from_slice(&basis[slice.data.clone()]);
+    }
+
+    let mut sgd = Vec::new();
+    if !uastc {
+        sgd.extend_from_slice(&(field(39, 2)? as u16).to_le_bytes());
+        sgd.extend_from_slice(&(field(48, 2)? as u16).to_le_bytes());
+        for len in [endpoints.len(), selectors.len(), tables.len(), 0] {
+            sgd.extend_from_slice(&(len as u32).to_le_bytes());
+        }
+        for v in image_descs.iter().flatten() {
+            sgd.extend_from_slice(&v.to_le_bytes());
+        }
+        for part in [endpoints, selectors, tables] {
+            sgd.extend_from_slice(&basis[part]);
+        }
+    }
+    let scheme = if uastc {
+        SUPERCOMPRESSION_NONE
+    } else {
+        SUPERCOMPRESSION_BASIS_LZ
+    };
+
+    let dfd = data_format_descriptor(uastc, has_alpha);
+    let kvd = key_value_data();
+
+    // identifier + header (9 x u32) + index (4 x u32, 2 x u64) + level index (3 x u64 per level)
+    let dfd_offset = 12 + 9 * 4 + 4 * 4 + 2 * 8 + level_count * 3 * 8;
+    let kvd_offset = dfd_offset + dfd.len();
+    let sgd_offset = if sgd.is_empty() {
+        0
+    } else {
+        (kvd_offset + kvd.len()).next_multiple_of(8)
+    };
+    let data_start = (kvd_offset + kvd.len()).max(sgd_offset + sgd.len());
+    // Levels are stored smallest first. UASTC levels align to the 16-byte block; BasisLZ
+    // levels need no alignment.
+    let align = if uastc { UASTC_BLOCK_BYTES as usize } else { 1 };
+    let mut offsets = vec![0usize; level_count];
+    let mut end = data_start;
+    for (i, data) in levels.iter().enumerate().rev() {
+        offsets[i] = end.next_multiple_of(align);
+        end = offsets[i] + data.len();
+    }
+
+    let mut out = Vec::with_capacity(end);
+    out.extend_from_slice(&KTX2_IDENTIFIER);
+    // vkFormat is VK_FORMAT_UNDEFINED: the DFD color model names the Basis codec
+    for v in [0, 1, width, height, 0, 0, 1, level_count as u32, scheme] {
+        out.extend_from_slice(&v.to_le_bytes());
+    }
+    for v in [
+        dfd_offset as u32,
+        dfd.len() as u32,
+        kvd_offset as u32,
+        kvd.len() as u32,
+    ] {
+        out.extend_from_slice(&v.to_le_bytes());
+    }
+    out.extend_from_slice(&(sgd_offset as u64).to_le_bytes());
+    out.extend_from_slice(&(sgd.len() as u64).to_le_bytes());
+    for (offset, data) in offsets.iter().zip(&levels) {
+        // BasisLZ levels have no meaningful uncompressed length
+        let uncompressed = if uastc { data.len() } else { 0 };
+        for v in [*offset, data.len(), uncompressed] {
+            out.extend_from_slice(&(v as u64).to_le_bytes());
+        }
+    }
+    out.extend_from_slice(&dfd);
+    out.extend_from_slice(&kvd);
+    if !sgd.is_empty() {
+        out.resize(sgd_offset, 0);
+        out.extend_from_slice(&sgd);
+    }
+    for (offset, data) in offsets.iter().zip(&levels).rev() {
+        out.resize(*offset, 0);
+        out.extend_from_slice(data);
+    }
+    Ok(out)
+}
+
+/// Basic data format descriptor for ETC1S or UASTC (Khronos Data Format 1.3, sRGB, 4x4 blocks).
+///
+/// ETC1S has an RGB sample plus an alpha sample when alpha slices exist; UASTC has one 128-bit
+/// sample whose channel id says whether the blocks carry alpha.
+fn data_format_descriptor(uastc: bool, has_alpha: bool) -> Vec<u8> {
+    // (bit offset, bit length - 1, channel id)
+    let samples: &[(u32, u32, u32)] = match (uastc, has_alpha) {
+        (true, false) => &[(0, 127, 0)],
+        (true, true) => &[(0, 127, 3)],
+        (false, false) => &[(0, 63, 0)],
+        (false, true) => &[(0, 63, 0), (64, 63, 15)],
+    };
+    let block_size = 24 + 16 * samples.len() as u32;
+    let (model, bytes_plane0) = if uastc {
+        (KHR_DF_MODEL_UASTC, UASTC_BLOCK_BYTES)
+    } else {
+        // Supercompressed data has no fixed plane size
+        (KHR_DF_MODEL_ETC1S, 0)
+    };
+    let mut words: Vec<u32> = vec![
+        4 + block_size,         // dfdTotalSize
+        0,                      // vendorId = Khronos, descriptorType = basic
+        2 | (block_size << 16), // versionNumber = 2, descriptorBlockSize
+        // colorPrimaries = BT709, transfer = sRGB, flags = straight alpha
+        model | (1 << 8) | (2 << 16),
+        3 | (3 << 8), // texelBlockDimension 4x4x1x1
+        bytes_plane0,
+        0, // bytesPlane4..7
+    ];
+    for &(offset, length, channel) in samples {
+        words.push(offset | (length << 16) | (channel << 24));
+        words.push(0); // sample position
+        words.push(0); // sampleLower
+        words.push(u32::MAX); // sampleUpper
+    }
+    words.iter().flat_map(|w| w.to_le_bytes()).collect()
+}
+
+/// Key/value data carrying the recommended `KTXwriter` entry.
+fn key_value_data() -> Vec<u8> {
+    let mut entry = Vec::new();
+    entry.extend_from_slice(b"KTXwriter\0");
+    entry.extend_from_slice(format!("tex-packer {}\0", env!("CARGO_PKG_VERSION")).as_bytes());
+    let mut out = Vec::new();
+    out.extend_from_slice(&(entry.len() as u32).to_le_bytes());
+    out.extend_from_slice(&entry);
+    out.resize(out.len().next_multiple_of(4), 0);
+    out
+}
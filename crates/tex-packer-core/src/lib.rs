@@ -2,6 +2,7 @@
 //!
 //! - Algorithms: Skyline (BL/MW + optional Waste Map), MaxRects (BAF/BSSF/BLSF/BL/CP), Guillotine (choice + split)
 //! - Pipeline: `pack_images` takes in-memory images and returns pages + metadata
+//! - Linked sets: `pack_image_sets` gives diffuse/normal/emissive maps one shared layout
 //! - Data model is serde-serializable; exporters are provided in helpers and the CLI crate.
 //!
 //! Quick example:
@@ -21,37 +22,125 @@
 //! # Ok(()) }
 //! ```
 
+#[cfg(feature = "aseprite")]
+pub mod aseprite;
+#[cfg(feature = "dds")]
+pub mod bcn;
+pub mod binary;
 pub mod compositing;
 pub mod config;
+#[cfg(feature = "dds")]
+pub mod dds;
+pub mod debug_render;
+pub mod diff;
+pub mod encode;
 pub mod error;
 pub mod export;
+pub mod export_bmfont;
+pub mod export_c;
+pub mod export_csharp;
+pub mod export_godot;
 pub mod export_plist;
+pub mod export_rust;
+pub mod export_spine;
+pub mod export_unity;
+pub mod extract;
+#[cfg(feature = "glyph")]
+pub mod glyph;
+pub mod hash;
+pub mod import;
+#[cfg(feature = "ktx2")]
+pub mod ktx2;
+pub mod merge;
+pub mod mipmap;
 pub mod model;
+pub mod optimizer;
 pub mod packer;
 pub mod pipeline;
+pub mod progress;
+#[cfg(feature = "psd")]
+pub mod psd;
 pub mod runtime;
 pub mod runtime_atlas;
+#[cfg(feature = "schemars")]
+pub mod schema;
+pub mod scratch;
+#[cfg(feature = "sdf")]
+pub mod sdf;
+#[cfg(not(target_arch = "wasm32"))]
+pub mod task;
+#[cfg(feature = "templates")]
+pub mod templates;
+pub mod verify;
 
+#[cfg(feature = "aseprite")]
+pub use aseprite::*;
+#[cfg(feature = "dds")]
+pub use bcn::*;
+pub use binary::*;
 pub use config::*;
+#[cfg(feature = "dds")]
+pub use dds::*;
+pub use diff::*;
+pub use encode::*;
 pub use error::*;
 pub use export::*;
+pub use export_bmfont::*;
+pub use export_c::*;
+pub use export_csharp::*;
+pub use export_godot::*;
 pub use export_plist::*;
+pub use export_rust::*;
+pub use export_spine::*;
+pub use export_unity::*;
+pub use extract::*;
+#[cfg(feature = "glyph")]
+pub use glyph::*;
+pub use import::*;
+#[cfg(feature = "ktx2")]
+pub use ktx2::*;
+pub use merge::*;
+pub use mipmap::*;
 pub use model::*;
 pub use packer::*;
 pub use pipeline::*;
+pub use progress::*;
+#[cfg(feature = "psd")]
+pub use psd::*;
+pub use scratch::ScratchImage;
+#[cfg(feature = "sdf")]
+pub use sdf::*;
+#[cfg(not(target_arch = "wasm32"))]
+pub use task::{PackHandle, spawn_pack, spawn_pack_with};
+#[cfg(feature = "templates")]
+pub use templates::*;
+pub use verify::*;
 
 /// Convenience prelude for common types and functions.
 /// Importing `tex_packer_core::prelude::*` brings the primary APIs into scope.
 pub mod prelude {
     pub use crate::config::{
-        AlgorithmFamily, AutoMode, GuillotineChoice, GuillotineSplit, MaxRectsHeuristic,
-        PackerConfig, PackerConfigBuilder, SkylineHeuristic, SortOrder,
+        AlgorithmFamily, AutoCandidate, AutoMode, BasisCodec, BcnFormat, ExtrudeMode,
+        GuillotineChoice, GuillotineSplit, KeyTransform, MaxRectsHeuristic, OnUnplaceable,
+        PackerConfig, PackerConfigBuilder, PageFormat, PageSizeMode, PivotMode, RotationDirection,
+        RotationPolicy, ShelfHeuristic, SkylineHeuristic, SortOrder, SpriteOptions,
     };
-    pub use crate::model::{Atlas, Frame, Meta, PackStats, Page, Rect};
-    pub use crate::pipeline::LayoutItem;
-    pub use crate::runtime::{AtlasSession, RuntimeStats, RuntimeStrategy, ShelfPolicy};
-    pub use crate::runtime_atlas::{RuntimeAtlas, UpdateRegion};
+    pub use crate::model::{
+        Atlas, Frame, Meta, NinePatch, PackStats, PackTimings, Page, PageStats, Pivot, Rect,
+        ScaledItem, SkipReason, SkippedItem,
+    };
+    pub use crate::pipeline::{LayoutItem, LayoutOutput};
+    pub use crate::progress::{CancellationToken, PackControl, PackPhase, PackProgress};
+    pub use crate::runtime::{
+        AtlasSession, EvictedEntry, FrameMove, RuntimeBudget, RuntimeStats, RuntimeStrategy,
+        ShelfPolicy,
+    };
+    pub use crate::runtime_atlas::{RuntimeAtlas, RuntimePixelFormat, UpdateRegion};
+    #[cfg(not(target_arch = "wasm32"))]
+    pub use crate::task::{PackHandle, spawn_pack};
     pub use crate::{
-        InputImage, OutputPage, PackOutput, pack_images, pack_layout, pack_layout_items,
+        ImageSetOutput, InputImage, InputImageSet, OutputPage, PackOutput, PageStream,
+        pack_image_sets, pack_images, pack_images_iter, pack_images_with, pack_layout,
+        pack_layout_items, pack_layout_items_with,
     };
 }
@@ -0,0 +1,70 @@
+//! Re-packing several atlases (or groups of images) into one set of pages.
+
+use crate::config::PackerConfig;
+use crate::error::Result;
+use crate::extract::extract_frames;
+use crate::pipeline::{InputImage, PackOutput, pack_images};
+use image::DynamicImage;
+
+impl PackOutput {
+    /// Cuts every frame back out of the pages (see [`extract_frames`]) as packer inputs.
+    pub fn into_inputs(self) -> Result<Vec<InputImage>> {
+        let pages = self
+            .pages
+            .iter()
+            .map(|p| p.pixels().map(|c| c.into_owned()))
+            .collect::<Result<Vec<_>>>()?;
+        Ok(extract_frames(&self.atlas, &pages)?
+            .into_iter()
+            .map(|(key, rgba)| InputImage {
+                key,
+                image: DynamicImage::ImageRgba8(rgba),
+            })
+            .collect())
+    }
+}
+
+/// Merges existing atlases into one, re-packed with `cfg`. Keys are kept as they are, so a key
+/// present in more than one atlas is handled by `cfg.duplicate_keys` (an error by default; use
+/// [`merge_atlases_prefixed`] to namespace them).
+pub fn merge_atlases(outputs: Vec<PackOutput>, cfg: PackerConfig) -> Result<PackOutput> {
+    merge_atlases_prefixed(
+        outputs.into_iter().map(|o| (String::new(), o)).collect(),
+        cfg,
+    )
+}
+
+/// Merges existing atlases into one, prepending each atlas' prefix (e.g. `"ui/"`) to its keys.
+pub fn merge_atlases_prefixed(
+    sources: Vec<(String, PackOutput)>,
+    cfg: PackerConfig,
+) -> Result<PackOutput> {
+    let groups = sources
+        .into_iter()
+        .map(|(prefix, out)| Ok((prefix, out.into_inputs()?)))
+        .collect::<Result<Vec<_>>>()?;
+    merge_inputs(groups, cfg)
+}
+
+/// Packs groups of images as one atlas, prepending each group's prefix to its keys.
+/// Keys that still collide are handled by `cfg.duplicate_keys`.
+pub fn merge_inputs(
+    groups: Vec<(String, Vec<InputImage>)>,
+    cfg: PackerConfig,
+) -> Result<PackOutput> {
+    pack_images(namespaced_inputs(groups), cfg)
+}
+
+/// Flattens groups of images, prepending each group's prefix to its keys.
+pub fn namespaced_inputs(groups: Vec<(String, Vec<InputImage>)>) -> Vec<InputImage> {
+    let mut inputs = Vec::with_capacity(groups.iter().map(|(_, g)| g.len()).sum());
+    for (prefix, group) in groups {
+        for mut input in group {
+            if !prefix.is_empty() {
+                input.key = format!("{prefix}{}", input.key);
+            }
+            inputs.push(input);
+        }
+    }
+    inputs
+}
@@ -0,0 +1,66 @@
+//! Mipmap support: the frame spacing a mip chain needs (`PackerConfig::mip_levels`) and
+//! pre-generated mip levels for pages.
+
+use image::{Rgba, RgbaImage};
+
+/// Gap between neighboring frames (padding plus both frames' extrusion) that keeps `levels`
+/// mip levels, level 0 included, from mixing them: twice a texel of the smallest level, as
+/// bilinear filtering there reads one texel beyond the 2x2-averaged block holding an edge.
+/// Returns the padding still needed on top of `extrusion` pixels per side.
+pub fn mip_safe_padding(levels: u32, extrusion: u32) -> u32 {
+    if levels <= 1 {
+        return 0;
+    }
+    let gap = 1u32 << levels.min(31);
+    gap.saturating_sub(extrusion.saturating_mul(2))
+}
+
+/// Pixels to shrink every frame's UV rect by on each side so bilinear samples at the smallest
+/// of `levels` mip levels stay within the frame: half a texel of that level.
+pub fn mip_uv_inset(levels: u32) -> f32 {
+    0.5 * (1u64 << (levels.clamp(1, 32) - 1)) as f32
+}
+
+/// Number of levels of a full chain for a `width`x`height` image, down to 1x1.
+pub fn max_mip_levels(width: u32, height: u32) -> u32 {
+    32 - width.max(height).max(1).leading_zeros()
+}
+
+/// Levels 1.. of the mip chain of `page`, halving each time, for a chain of `levels` levels
+/// including `page` itself (capped at [`max_mip_levels`]).
+///
+/// Each texel averages its 2x2 block (edge texels of odd sizes repeat the last row/column).
+/// Colors are weighted by alpha so transparent pixels do not darken edges.
+pub fn mip_chain(page: &RgbaImage, levels: u32) -> Vec<RgbaImage> {
+    let n = levels.min(max_mip_levels(page.width(), page.height()));
+    let mut chain: Vec<RgbaImage> = Vec::with_capacity(n.saturating_sub(1) as usize);
+    for _ in 1..n {
+        let next = downsample(chain.last().unwrap_or(page));
+        chain.push(next);
+    }
+    chain
+}
+
+/// Halves `img` (rounding down, at least 1x1) with an alpha-weighted 2x2 box filter.
+pub fn downsample(img: &RgbaImage) -> RgbaImage {
+    let (w, h) = img.dimensions();
+    let (nw, nh) = ((w / 2).max(1), (h / 2).max(1));
+    RgbaImage::from_fn(nw, nh, |x, y| {
+        let (x0, y0) = ((2 * x).min(w - 1), (2 * y).min(h - 1));
+        let (x1, y1) = ((2 * x + 1).min(w - 1), (2 * y + 1).min(h - 1));
+        let mut color = [0u32; 3];
+        let mut alpha = 0u32;
+        for (sx, sy) in [(x0, y0), (x1, y0), (x0, y1), (x1, y1)] {
+            let p = img.get_pixel(sx, sy);
+            for (c, v) in color.iter_mut().zip(p.0) {
+                *c += v as u32 * p[3] as u32;
+            }
+            alpha += p[3] as u32;
+        }
+        if alpha == 0 {
+            return Rgba([0; 4]);
+        }
+        let [r, g, b] = color.map(|c| ((c + alpha / 2) / alpha) as u8);
+        Rgba([r, g, b, ((alpha + 2) / 4) as u8])
+    })
+}
@@ -1,7 +1,9 @@
+use crate::config::{AlgorithmFamily, PackerConfig, RotationDirection, SdfOptions, SortOrder};
 use serde::{Deserialize, Serialize};
 
 /// Axis-aligned rectangle (pixels). `x,y` is top-left; `w,h` are sizes.
 #[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct Rect {
     pub x: u32,
     pub y: u32,
@@ -27,6 +29,111 @@ impl Rect {
     }
 }
 
+/// Pivot (anchor) point, normalized to the untrimmed source size: `(0, 0)` is the top-left
+/// corner and `(1, 1)` the bottom-right. Values outside `0..=1` are allowed.
+///
+/// Deserializes from `{x, y}`, `[x, y]`, or a string accepted by `FromStr` (`"bottom-center"`,
+/// `"0.5,1"`).
+#[derive(Debug, Clone, Copy, Serialize, PartialEq)]
+pub struct Pivot {
+    pub x: f64,
+    pub y: f64,
+}
+
+impl Pivot {
+    pub const CENTER: Self = Self::new(0.5, 0.5);
+    pub const TOP_LEFT: Self = Self::new(0.0, 0.0);
+    pub const BOTTOM_CENTER: Self = Self::new(0.5, 1.0);
+
+    pub const fn new(x: f64, y: f64) -> Self {
+        Self { x, y }
+    }
+}
+
+impl Default for Pivot {
+    fn default() -> Self {
+        Self::CENTER
+    }
+}
+
+impl<'de> Deserialize<'de> for Pivot {
+    fn deserialize<D: serde::Deserializer<'de>>(d: D) -> Result<Self, D::Error> {
+        #[derive(Deserialize)]
+        #[serde(untagged)]
+        enum Repr {
+            Map { x: f64, y: f64 },
+            Pair(f64, f64),
+            Name(String),
+        }
+        match Repr::deserialize(d)? {
+            Repr::Map { x, y } | Repr::Pair(x, y) => Ok(Self::new(x, y)),
+            Repr::Name(s) => s
+                .parse()
+                .map_err(|_| serde::de::Error::custom(format!("invalid pivot '{s}'"))),
+        }
+    }
+}
+
+impl std::str::FromStr for Pivot {
+    type Err = ();
+    /// Accepts `x,y` (e.g. `0.5,1`) or a named anchor such as `center`, `bottom-center`,
+    /// `top-left` (`-`, `_` and spaces are interchangeable; `top`/`bottom`/`left`/`right` mean
+    /// the edge centers).
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if let Some((x, y)) = s.split_once(',') {
+            let x = x.trim().parse().map_err(|_| ())?;
+            let y = y.trim().parse().map_err(|_| ())?;
+            return Ok(Self::new(x, y));
+        }
+        let name = s.trim().to_ascii_lowercase().replace(['_', ' '], "-");
+        let (x, y) = match name.as_str() {
+            "center" | "centre" | "center-center" => (0.5, 0.5),
+            "top-left" => (0.0, 0.0),
+            "top" | "top-center" => (0.5, 0.0),
+            "top-right" => (1.0, 0.0),
+            "left" | "center-left" => (0.0, 0.5),
+            "right" | "center-right" => (1.0, 0.5),
+            "bottom-left" => (0.0, 1.0),
+            "bottom" | "bottom-center" => (0.5, 1.0),
+            "bottom-right" => (1.0, 1.0),
+            _ => return Err(()),
+        };
+        Ok(Self::new(x, y))
+    }
+}
+
+#[cfg(feature = "schemars")]
+impl schemars::JsonSchema for Pivot {
+    fn schema_name() -> std::borrow::Cow<'static, str> {
+        "Pivot".into()
+    }
+
+    fn json_schema(_: &mut schemars::SchemaGenerator) -> schemars::Schema {
+        schemars::json_schema!({
+            "description": "Pivot normalized to the untrimmed size (y down): {x, y}, [x, y], \"x,y\" or a name such as \"bottom-center\".",
+            "oneOf": [
+                {
+                    "type": "object",
+                    "required": ["x", "y"],
+                    "properties": { "x": { "type": "number" }, "y": { "type": "number" } }
+                },
+                { "type": "array", "items": { "type": "number" }, "minItems": 2, "maxItems": 2 },
+                { "type": "string" }
+            ]
+        })
+    }
+}
+
+/// Nine-patch (9-slice) insets in source pixels, measured from each edge of the untrimmed image.
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct NinePatch {
+    pub left: u32,
+    pub top: u32,
+    pub right: u32,
+    pub bottom: u32,
+}
+
 /// A placed frame within a page.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Frame<K = String> {
@@ -42,6 +149,48 @@ pub struct Frame<K = String> {
     pub source: Rect,
     /// Original (untrimmed) image size.
     pub source_size: (u32, u32),
+    /// True if the frame has no meaningful content: a zero-sized input (placed as a 1x1
+    /// placeholder) or a fully transparent input collapsed to 1x1 by `TransparentPolicy::OneByOne`.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub degenerate: bool,
+    /// Stable ID derived from the key (see `hash::stable_frame_id`); same key, same ID.
+    #[serde(default)]
+    pub id: u64,
+    /// XXH64 of the trimmed source pixels, serialized as 16 hex digits. Changes exactly when the
+    /// sprite's visible content (or trim size) changes; `None` for layout-only frames.
+    #[serde(default, skip_serializing_if = "Option::is_none", with = "hex_hash")]
+    pub content_hash: Option<u64>,
+    /// Pivot point (`SpriteOptions::pivot`, `PackerConfig::pivots` / `pivot_mode`); center unless
+    /// configured.
+    #[serde(default)]
+    pub pivot: Pivot,
+    /// Nine-patch insets (`SpriteOptions::nine_patch`), if any.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub nine_patch: Option<NinePatch>,
+    /// Factor the input image was downscaled by to fit a page (`OnUnplaceable::ScaleDown`);
+    /// `source` and `source_size` describe the scaled image. Multiply by `1 / scale` to draw the
+    /// sprite at its authored size.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub scale: Option<f32>,
+}
+
+/// Serializes `Option<u64>` content hashes as 16-digit hex strings (JSON numbers lose precision
+/// above 2^53).
+mod hex_hash {
+    use serde::{Deserialize, Deserializer, Serializer, de::Error};
+
+    pub fn serialize<S: Serializer>(v: &Option<u64>, s: S) -> Result<S::Ok, S::Error> {
+        match v {
+            Some(h) => s.serialize_str(&format!("{h:016x}")),
+            None => s.serialize_none(),
+        }
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(d: D) -> Result<Option<u64>, D::Error> {
+        Option::<String>::deserialize(d)?
+            .map(|s| u64::from_str_radix(&s, 16).map_err(D::Error::custom))
+            .transpose()
+    }
 }
 
 /// A single atlas page (logical record).
@@ -53,8 +202,52 @@ pub struct Page<K = String> {
     pub frames: Vec<Frame<K>>,
 }
 
+/// Algorithm configuration that produced an atlas; recorded by `Auto` runs for the winner.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct PackerChoice {
+    pub family: AlgorithmFamily,
+    /// Family-specific heuristic, e.g. "BestAreaFit" or "BestAreaFit+SplitShorterLeftoverAxis"
+    /// ("+Global" marks global MaxRects insertion).
+    pub heuristic: String,
+    pub sort_order: SortOrder,
+    pub allow_rotation: bool,
+    pub mr_reference: bool,
+}
+
+impl PackerChoice {
+    /// Describes the algorithm settings of a concrete (non-Auto) config.
+    pub fn from_config(cfg: &PackerConfig) -> Self {
+        let heuristic = match cfg.family {
+            AlgorithmFamily::Skyline
+                if cfg.use_waste_map && !cfg.skyline_heuristic.uses_waste_map() =>
+            {
+                format!("{:?}Wm", cfg.skyline_heuristic)
+            }
+            AlgorithmFamily::Skyline => format!("{:?}", cfg.skyline_heuristic),
+            AlgorithmFamily::MaxRects if cfg.mr_global => format!("{:?}+Global", cfg.mr_heuristic),
+            AlgorithmFamily::MaxRects => format!("{:?}", cfg.mr_heuristic),
+            AlgorithmFamily::Guillotine if !cfg.g_merge => {
+                format!("{:?}+{:?}+NoMerge", cfg.g_choice, cfg.g_split)
+            }
+            AlgorithmFamily::Guillotine => format!("{:?}+{:?}", cfg.g_choice, cfg.g_split),
+            AlgorithmFamily::Shelf => format!("{:?}", cfg.shelf_heuristic),
+            AlgorithmFamily::Grid => format!("{}x{}", cfg.grid_cell_width, cfg.grid_cell_height),
+            AlgorithmFamily::Auto | AlgorithmFamily::Custom => String::new(),
+        };
+        Self {
+            family: cfg.family.clone(),
+            heuristic,
+            sort_order: cfg.sort_order.clone(),
+            allow_rotation: cfg.allow_rotation,
+            mr_reference: cfg.family == AlgorithmFamily::MaxRects && cfg.mr_reference,
+        }
+    }
+}
+
 /// Atlas-level metadata (common fields used by exporters/templates).
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct Meta {
     /// Schema version for JSON metadata formats (e.g., json-array/json-hash).
     /// Allows downstream tooling to handle future additive changes.
@@ -70,8 +263,84 @@ pub struct Meta {
     pub padding: (u32, u32),
     pub extrude: u32,
     pub allow_rotation: bool,
+    /// Direction rotated frames are turned on the pages (`PackerConfig::rotation_direction`).
+    #[serde(default)]
+    pub rotation_direction: RotationDirection,
     pub trim_mode: String,
     pub background_color: Option<[u8; 4]>,
+    /// Winning algorithm configuration when the atlas was produced by `AlgorithmFamily::Auto`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub packer: Option<PackerChoice>,
+    /// Page-size search of `PackerConfig::minimize_page_size`, when it ran.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub page_minimization: Option<PageMinimization>,
+    /// Search of `PackerConfig::auto_refine` after the `Auto` portfolio, when it ran.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub auto_refinement: Option<AutoRefinement>,
+    /// Grid every frame position sits on (`PackerConfig::align`), when above 1.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub align: Option<u32>,
+    /// Frame sequences from `PackerConfig::animations`.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub animations: Vec<Animation>,
+    /// Distance field settings (`PackerConfig::sdf`) when the pages hold signed distance fields.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sdf: Option<SdfOptions>,
+    /// Mip chain the frame spacing accounts for (`PackerConfig::mip_levels`).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub mip: Option<MipInfo>,
+}
+
+/// Mip levels an atlas was laid out for, with the UV inset that keeps samples inside frames.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct MipInfo {
+    /// Levels, level 0 included.
+    pub levels: u32,
+    /// Pixels (at level 0) to shrink each frame's UV rect by on every side before sampling the
+    /// smallest level; divide by the page size for UV units.
+    pub uv_inset: f32,
+}
+
+/// A named frame sequence, such as the frames of an animated GIF or APNG.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct Animation {
+    pub name: String,
+    pub frames: Vec<AnimationFrame>,
+}
+
+/// One frame of an [`Animation`].
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct AnimationFrame {
+    /// Frame key in the atlas.
+    pub key: String,
+    pub duration_ms: u32,
+}
+
+/// Outcome of `PackerConfig::minimize_page_size` for a single-page atlas.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct PageMinimization {
+    /// Re-packs tried at smaller candidate sizes.
+    pub attempts: u32,
+    /// Page size before shrinking.
+    pub initial_size: (u32, u32),
+}
+
+/// How `PackerConfig::auto_refine` spent the time left after the `Auto` portfolio.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct AutoRefinement {
+    /// Portfolio winner the search started from (`Meta::packer` holds the final one).
+    pub initial: PackerChoice,
+    /// Variants of the winner laid out.
+    pub variants: u32,
+    /// Variants that beat the best layout so far.
+    pub improvements: u32,
+    /// The time budget (or cancellation) ended the search before it converged.
+    pub budget_exhausted: bool,
 }
 
 /// Atlas of pages and metadata.
@@ -82,7 +351,7 @@ pub struct Atlas<K = String> {
 }
 
 /// Statistics about atlas packing efficiency.
-#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct PackStats {
     /// Total number of pages in the atlas.
     pub num_pages: usize,
@@ -105,9 +374,201 @@ pub struct PackStats {
     pub num_rotated: usize,
     /// Number of trimmed frames.
     pub num_trimmed: usize,
+    /// Theoretical lower bound on page area: frame areas plus the mandatory per-frame
+    /// padding/extrusion reserve. No layout of these frames can use less.
+    #[serde(default)]
+    pub lower_bound_area: u64,
+    /// Fewest pages that could hold `lower_bound_area` given `meta.max_dim` and border padding.
+    #[serde(default)]
+    pub lower_bound_pages: usize,
+    /// Achieved efficiency vs the bound: lower_bound_area / total_page_area (0.0 to 1.0).
+    /// Close to 1.0 means no algorithm could do much better.
+    #[serde(default)]
+    pub bound_efficiency: f64,
+    /// Winning algorithm configuration of an `Auto` run (from `meta.packer`).
+    #[serde(default)]
+    pub winning_config: Option<PackerChoice>,
+    /// Re-packs tried by `minimize_page_size` (0 when it did not run).
+    #[serde(default)]
+    pub page_size_attempts: u32,
+    /// Refinement of the `Auto` winner (from `meta.auto_refinement`).
+    #[serde(default)]
+    pub auto_refinement: Option<AutoRefinement>,
+    /// Pixels trimming removed: source size area minus trimmed area, summed over trimmed frames.
+    #[serde(default)]
+    pub trimmed_pixels_saved: u64,
+    /// Area reserved around frames for texture padding and extrusion
+    /// (`lower_bound_area - used_frame_area`).
+    #[serde(default)]
+    pub padding_overhead: u64,
+    /// Per-page breakdown, in page order.
+    #[serde(default)]
+    pub pages: Vec<PageStats>,
+    /// Wall-clock time per phase; only set by entry points that measure it
+    /// (e.g. [`PackOutput::stats`](crate::PackOutput::stats)).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub timings: Option<PackTimings>,
+}
+
+/// Wall-clock milliseconds spent in each phase of a packing run.
+/// Phases a caller did not run (or does not measure) stay 0.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PackTimings {
+    /// Trimming, extrusion and hashing of the inputs.
+    pub prepare_ms: u64,
+    /// Layout, including `Auto` evaluation and page minimization.
+    pub pack_ms: u64,
+    /// Part of `pack_ms` spent by `PackerConfig::auto_refine` (not added again by `total_ms`).
+    #[serde(default)]
+    pub refine_ms: u64,
+    /// Blitting frames into page canvases.
+    pub composite_ms: u64,
+    /// Encoding and writing pages (filled in by the caller that writes them).
+    pub encode_ms: u64,
+}
+
+impl PackTimings {
+    /// Sum of all phases.
+    pub fn total_ms(&self) -> u64 {
+        self.prepare_ms + self.pack_ms + self.composite_ms + self.encode_ms
+    }
+}
+
+/// An input left out of a partial result ([`PackOutput::skipped`](crate::PackOutput::skipped)).
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SkippedItem {
+    /// Frame key (after `key_transform`); `#<n>` for the n-th (0-based) input of
+    /// `pack_images_iter` that failed before a key was known.
+    pub key: String,
+    #[serde(flatten)]
+    pub reason: SkipReason,
+}
+
+/// Why an input is missing from the atlas.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "reason", rename_all = "snake_case")]
+pub enum SkipReason {
+    /// Its slot (trimmed size plus padding and extrusion) exceeds an empty page under
+    /// `OnUnplaceable::Skip`, or cannot be scaled down far enough under `ScaleDown`.
+    TooLarge { width: u32, height: u32 },
+    /// Fully transparent or zero-sized under `TransparentPolicy::Skip`.
+    Transparent,
+    /// Reading or decoding the input failed (`pack_images_iter` with a non-`Error` policy).
+    Decode { error: String },
+}
+
+/// An input shrunk to fit a page under `OnUnplaceable::ScaleDown`. Its frame describes the
+/// scaled image.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ScaledItem {
+    pub key: String,
+    /// Input image size.
+    pub original: (u32, u32),
+    /// Image size after scaling (before trimming).
+    pub scaled: (u32, u32),
+    /// Factor applied (also recorded as `Frame::scale`).
+    pub scale: f32,
+}
+
+/// Per-page breakdown of packing efficiency.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct PageStats {
+    /// Page id (matches `Page::id`).
+    pub id: usize,
+    pub width: u32,
+    pub height: u32,
+    /// Number of frames on this page.
+    pub num_frames: usize,
+    /// Number of rotated frames on this page.
+    pub num_rotated: usize,
+    /// Page area (width * height).
+    pub page_area: u64,
+    /// Area used by frames on this page.
+    pub used_frame_area: u64,
+    /// Occupancy ratio: used_frame_area / page_area (0.0 to 1.0).
+    pub occupancy: f64,
+}
+
+impl PageStats {
+    /// Returns wasted space on this page in pixels.
+    pub fn wasted_area(&self) -> u64 {
+        self.page_area.saturating_sub(self.used_frame_area)
+    }
+
+    /// Returns the share of rotated frames (0.0 to 1.0).
+    pub fn rotated_ratio(&self) -> f64 {
+        if self.num_frames > 0 {
+            self.num_rotated as f64 / self.num_frames as f64
+        } else {
+            0.0
+        }
+    }
+}
+
+impl<K> Page<K> {
+    /// Computes efficiency statistics for this page.
+    pub fn stats(&self) -> PageStats {
+        let page_area = (self.width as u64) * (self.height as u64);
+        let used_frame_area: u64 = self
+            .frames
+            .iter()
+            .map(|f| (f.frame.w as u64) * (f.frame.h as u64))
+            .sum();
+        let occupancy = if page_area > 0 {
+            used_frame_area as f64 / page_area as f64
+        } else {
+            0.0
+        };
+        PageStats {
+            id: self.id,
+            width: self.width,
+            height: self.height,
+            num_frames: self.frames.len(),
+            num_rotated: self.frames.iter().filter(|f| f.rotated).count(),
+            page_area,
+            used_frame_area,
+            occupancy,
+        }
+    }
 }
 
 impl<K> Atlas<K> {
+    /// Computes per-page statistics, in page order.
+    pub fn page_stats(&self) -> Vec<PageStats> {
+        self.pages.iter().map(|p| p.stats()).collect()
+    }
+
+    /// Keys of all frames flagged as degenerate, in page order.
+    pub fn degenerate_keys(&self) -> Vec<&K> {
+        self.pages
+            .iter()
+            .flat_map(|p| p.frames.iter())
+            .filter(|f| f.degenerate)
+            .map(|f| &f.key)
+            .collect()
+    }
+
+    /// Returns a copy of the atlas with degenerate frames removed (e.g. to skip them on export).
+    /// Page sizes and the remaining placements are unchanged.
+    pub fn without_degenerate(&self) -> Atlas<K>
+    where
+        K: Clone,
+    {
+        Atlas {
+            pages: self
+                .pages
+                .iter()
+                .map(|p| Page {
+                    id: p.id,
+                    width: p.width,
+                    height: p.height,
+                    frames: p.frames.iter().filter(|f| !f.degenerate).cloned().collect(),
+                })
+                .collect(),
+            meta: self.meta.clone(),
+        }
+    }
+
     /// Computes packing statistics for this atlas.
     pub fn stats(&self) -> PackStats {
         let num_pages = self.pages.len();
@@ -118,6 +579,10 @@ impl<K> Atlas<K> {
         let mut max_page_height = 0u32;
         let mut num_rotated = 0;
         let mut num_trimmed = 0;
+        let mut lower_bound_area = 0u64;
+        let mut trimmed_pixels_saved = 0u64;
+        // Each frame reserves its size plus texture padding and extrusion on both sides.
+        let reserve = self.meta.padding.1 as u64 + 2 * self.meta.extrude as u64;
 
         for page in &self.pages {
             let page_area = (page.width as u64) * (page.height as u64);
@@ -129,12 +594,17 @@ impl<K> Atlas<K> {
                 num_frames += 1;
                 let frame_area = (frame.frame.w as u64) * (frame.frame.h as u64);
                 used_frame_area += frame_area;
+                lower_bound_area +=
+                    (frame.frame.w as u64 + reserve) * (frame.frame.h as u64 + reserve);
 
                 if frame.rotated {
                     num_rotated += 1;
                 }
                 if frame.trimmed {
                     num_trimmed += 1;
+                    let full = (frame.source_size.0 as u64) * (frame.source_size.1 as u64);
+                    let kept = (frame.source.w as u64) * (frame.source.h as u64);
+                    trimmed_pixels_saved += full.saturating_sub(kept);
                 }
             }
         }
@@ -156,6 +626,22 @@ impl<K> Atlas<K> {
             (0.0, 0.0)
         };
 
+        let border = 2 * self.meta.padding.0 as u64;
+        let usable_page_area = (self.meta.max_dim.0 as u64).saturating_sub(border)
+            * (self.meta.max_dim.1 as u64).saturating_sub(border);
+        let lower_bound_pages = if num_frames == 0 {
+            0
+        } else if usable_page_area > 0 {
+            lower_bound_area.div_ceil(usable_page_area).max(1) as usize
+        } else {
+            1
+        };
+        let bound_efficiency = if total_page_area > 0 {
+            (lower_bound_area as f64 / total_page_area as f64).min(1.0)
+        } else {
+            0.0
+        };
+
         PackStats {
             num_pages,
             num_frames,
@@ -168,6 +654,16 @@ impl<K> Atlas<K> {
             max_page_height,
             num_rotated,
             num_trimmed,
+            lower_bound_area,
+            lower_bound_pages,
+            bound_efficiency,
+            winning_config: self.meta.packer.clone(),
+            page_size_attempts: self.meta.page_minimization.map_or(0, |m| m.attempts),
+            auto_refinement: self.meta.auto_refinement.clone(),
+            trimmed_pixels_saved,
+            padding_overhead: lower_bound_area.saturating_sub(used_frame_area),
+            pages: self.page_stats(),
+            timings: None,
         }
     }
 }
@@ -176,10 +672,12 @@ impl PackStats {
     /// Returns a human-readable summary of the statistics.
     pub fn summary(&self) -> String {
         format!(
-            "Pages: {}, Frames: {}, Occupancy: {:.2}%, Total Area: {} px², Used Area: {} px², Rotated: {}, Trimmed: {}",
+            "Pages: {} (bound {}), Frames: {}, Occupancy: {:.2}%, Bound Efficiency: {:.2}%, Total Area: {} px², Used Area: {} px², Rotated: {}, Trimmed: {}",
             self.num_pages,
+            self.lower_bound_pages,
             self.num_frames,
             self.occupancy * 100.0,
+            self.bound_efficiency * 100.0,
             self.total_page_area,
             self.used_frame_area,
             self.num_rotated,
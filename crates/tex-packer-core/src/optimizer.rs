@@ -0,0 +1,154 @@
+//! Metaheuristic search over packing order and per-item rotation.
+//!
+//! The optimizer is independent of any packer: it mutates an [`Arrangement`] and asks a cost
+//! function to score it. `AutoMode::Anneal` drives it with MaxRects layouts scored by total page
+//! area, but callers can plug in any cost.
+
+#[cfg(not(target_arch = "wasm32"))]
+use std::time::Instant;
+#[cfg(target_arch = "wasm32")]
+use web_time::Instant;
+
+/// Simulated-annealing settings.
+#[derive(Debug, Clone)]
+pub struct AnnealSettings {
+    /// Maximum number of neighbours evaluated.
+    pub max_iterations: usize,
+    /// Wall-clock budget in milliseconds; 0 means iterations only (fully deterministic).
+    pub time_budget_ms: u64,
+    /// Starting temperature, as a fraction of the initial cost.
+    pub initial_temperature: f64,
+    /// Temperature reached at the last iteration, as a fraction of the initial cost.
+    pub final_temperature: f64,
+    /// Seed of the internal PRNG.
+    pub seed: u64,
+}
+
+impl Default for AnnealSettings {
+    fn default() -> Self {
+        Self {
+            max_iterations: 1000,
+            time_budget_ms: 0,
+            initial_temperature: 0.02,
+            final_temperature: 0.0005,
+            seed: 0x7E58_AC4E_2D1B_0F93,
+        }
+    }
+}
+
+/// A packing candidate: the order items are offered to the packer, and whether each item
+/// (indexed like the inputs, not like `order`) is placed rotated.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Arrangement {
+    pub order: Vec<usize>,
+    pub rotated: Vec<bool>,
+}
+
+/// Outcome of [`anneal`].
+#[derive(Debug, Clone)]
+pub struct AnnealResult {
+    /// Lowest-cost arrangement seen (the initial one if nothing beat it).
+    pub best: Arrangement,
+    pub cost: u64,
+    /// Neighbours evaluated.
+    pub iterations: usize,
+    /// Times the best cost improved.
+    pub improvements: usize,
+}
+
+/// Simulated annealing over `initial`.
+///
+/// Moves swap two positions, move one item to another position, or flip the rotation of an
+/// item whose `rotatable` flag is set. `cost` returns `None` for infeasible arrangements, which
+/// are rejected. Returns `None` when the initial arrangement itself is infeasible.
+pub fn anneal<F>(
+    initial: Arrangement,
+    rotatable: &[bool],
+    settings: &AnnealSettings,
+    mut cost: F,
+) -> Option<AnnealResult>
+where
+    F: FnMut(&Arrangement) -> Option<u64>,
+{
+    let initial_cost = cost(&initial)?;
+    let mut result = AnnealResult {
+        best: initial.clone(),
+        cost: initial_cost,
+        iterations: 0,
+        improvements: 0,
+    };
+    let flippable: Vec<usize> = (0..rotatable.len()).filter(|&i| rotatable[i]).collect();
+    let n = initial.order.len();
+    if n < 2 && flippable.is_empty() {
+        return Some(result);
+    }
+
+    let start = Instant::now();
+    let mut rng = SplitMix64(settings.seed);
+    let scale = initial_cost.max(1) as f64;
+    let t0 = settings.initial_temperature * scale;
+    let t1 = settings.final_temperature * scale;
+    let mut current = initial;
+    let mut current_cost = initial_cost;
+
+    for k in 0..settings.max_iterations {
+        if settings.time_budget_ms > 0
+            && start.elapsed().as_millis() as u64 > settings.time_budget_ms
+        {
+            break;
+        }
+        let mut next = current.clone();
+        let roll = rng.next_f64();
+        if !flippable.is_empty() && (n < 2 || roll < 0.3) {
+            let i = flippable[rng.below(flippable.len())];
+            next.rotated[i] = !next.rotated[i];
+        } else if roll < 0.65 {
+            let (a, b) = (rng.below(n), rng.below(n));
+            next.order.swap(a, b);
+        } else {
+            let item = next.order.remove(rng.below(n));
+            next.order.insert(rng.below(n), item);
+        }
+        result.iterations += 1;
+
+        let Some(next_cost) = cost(&next) else {
+            continue;
+        };
+        let progress = k as f64 / settings.max_iterations.max(1) as f64;
+        let temperature = t0 * (t1 / t0).powf(progress);
+        let accept = next_cost <= current_cost
+            || (temperature > 0.0
+                && rng.next_f64() < (-((next_cost - current_cost) as f64) / temperature).exp());
+        if accept {
+            if next_cost < result.cost {
+                result.best = next.clone();
+                result.cost = next_cost;
+                result.improvements += 1;
+            }
+            current = next;
+            current_cost = next_cost;
+        }
+    }
+    Some(result)
+}
+
+/// Small deterministic PRNG (SplitMix64); keeps runs reproducible without a `rand` dependency.
+struct SplitMix64(u64);
+
+impl SplitMix64 {
+    fn next_u64(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+
+    fn next_f64(&mut self) -> f64 {
+        (self.next_u64() >> 11) as f64 / (1u64 << 53) as f64
+    }
+
+    fn below(&mut self, n: usize) -> usize {
+        (self.next_u64() % n as u64) as usize
+    }
+}
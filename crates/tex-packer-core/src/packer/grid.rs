@@ -0,0 +1,98 @@
+use super::{Packer, Slot};
+use crate::config::PackerConfig;
+use crate::model::{Frame, Rect};
+
+/// Grid packer: every rect takes one uniform cell, filled row-major from the top-left.
+///
+/// A cell spans `cell_w` x `cell_h` content pixels plus texture padding/extrusion, so cell `i`
+/// always starts at the same offset and a sprite's UVs follow from its index alone. Sprites
+/// smaller than a cell sit at the cell's top-left corner.
+pub struct GridPacker {
+    config: PackerConfig,
+    border: Rect,
+    /// Cell pitch, including texture padding and extrusion.
+    stride: (u32, u32),
+    cols: u32,
+    rows: u32,
+    next: u32,
+}
+
+impl GridPacker {
+    pub fn new(config: PackerConfig, cell_w: u32, cell_h: u32) -> Self {
+        let pad = config.border_padding;
+        let w = config.max_width.saturating_sub(pad.saturating_mul(2));
+        let h = config.max_height.saturating_sub(pad.saturating_mul(2));
+        let cell = Slot::new(&Rect::new(0, 0, cell_w.max(1), cell_h.max(1)), &config);
+        let stride = (cell.w, cell.h);
+        Self {
+            cols: w / stride.0,
+            rows: h / stride.1,
+            config,
+            border: Rect::new(pad, pad, w, h),
+            stride,
+            next: 0,
+        }
+    }
+
+    /// Top-left corner of cell `i` (row-major).
+    pub fn cell_origin(&self, i: u32) -> (u32, u32) {
+        let (c, r) = (i % self.cols.max(1), i / self.cols.max(1));
+        (
+            self.border.x + c * self.stride.0,
+            self.border.y + r * self.stride.1,
+        )
+    }
+
+    /// Number of cells on a page.
+    pub fn capacity(&self) -> u32 {
+        self.cols * self.rows
+    }
+
+    /// Whether `slot` goes into a cell, and if so rotated.
+    fn orientation(&self, slot: Slot) -> Option<bool> {
+        if self.next >= self.capacity() {
+            return None;
+        }
+        let fits = |w: u32, h: u32| w <= self.stride.0 && h <= self.stride.1;
+        if fits(slot.w, slot.h) {
+            Some(false)
+        } else if self.config.allow_rotation && fits(slot.rw, slot.rh) {
+            Some(true)
+        } else {
+            None
+        }
+    }
+}
+
+impl<K: Clone> Packer<K> for GridPacker {
+    fn can_pack(&self, rect: &Rect) -> bool {
+        let slot = Slot::new(rect, &self.config);
+        self.orientation(slot).is_some()
+    }
+
+    fn set_allow_rotation(&mut self, allow: bool) {
+        self.config.allow_rotation = allow;
+    }
+
+    fn pack(&mut self, key: K, rect: &Rect) -> Option<Frame<K>> {
+        let slot = Slot::new(rect, &self.config);
+        let rotated = self.orientation(slot)?;
+        let (x, y) = self.cell_origin(self.next);
+        self.next += 1;
+        let frame = Slot::content(&Rect::new(x, y, 0, 0), rect, rotated, &self.config);
+        Some(Frame {
+            key,
+            frame,
+            rotated,
+            trimmed: false,
+            source: *rect,
+            source_size: (rect.w, rect.h),
+            degenerate: false,
+            id: 0,
+            content_hash: None,
+            pivot: Default::default(),
+            nine_patch: None,
+            scale: None,
+        })
+    }
+}
@@ -1,4 +1,4 @@
-use super::Packer;
+use super::{Packer, Slot};
 use crate::config::{GuillotineChoice, GuillotineSplit, PackerConfig};
 use crate::model::{Frame, Rect};
 
@@ -41,7 +41,8 @@ impl GuillotinePacker {
         }
     }
 
-    fn choose(&self, w: u32, h: u32) -> Option<(usize, Rect, bool)> {
+    fn choose(&self, slot: Slot) -> Option<(usize, Rect, bool)> {
+        let Slot { w, h, rw, rh } = slot;
         let mut best_idx = None;
         let mut best_score = i32::MAX;
         let mut best_rect = Rect::new(0, 0, 0, 0);
@@ -56,12 +57,12 @@ impl GuillotinePacker {
                     best_rot = false;
                 }
             }
-            if self.config.allow_rotation && fr.w >= h && fr.h >= w {
-                let s = Self::score(&self.choice, fr, h, w);
+            if self.config.allow_rotation && fr.w >= rw && fr.h >= rh {
+                let s = Self::score(&self.choice, fr, rw, rh);
                 if s < best_score {
                     best_score = s;
                     best_idx = Some(i);
-                    best_rect = Rect::new(fr.x, fr.y, h, w);
+                    best_rect = Rect::new(fr.x, fr.y, rw, rh);
                     best_rot = true;
                 }
             }
@@ -118,7 +119,9 @@ impl GuillotinePacker {
             self.free.push(r);
         }
         self.prune_free_list();
-        self.merge_free_list();
+        if self.config.g_merge {
+            self.merge_free_list();
+        }
         self.used.push(*placed);
     }
 
@@ -152,6 +155,7 @@ impl GuillotinePacker {
         }
     }
 
+    /// RectMerge: joins free rects sharing a full edge (same y and height, or same x and width).
     fn merge_free_list(&mut self) {
         let mut merged = true;
         while merged {
@@ -196,29 +200,23 @@ impl GuillotinePacker {
 
 impl<K: Clone> Packer<K> for GuillotinePacker {
     fn can_pack(&self, rect: &Rect) -> bool {
-        let w = rect.w + self.config.texture_padding + self.config.texture_extrusion * 2;
-        let h = rect.h + self.config.texture_padding + self.config.texture_extrusion * 2;
-        self.choose(w, h).is_some()
+        let slot = Slot::new(rect, &self.config);
+        self.choose(slot).is_some()
+    }
+
+    fn set_allow_rotation(&mut self, allow: bool) {
+        self.config.allow_rotation = allow;
+    }
+
+    fn free_list_len(&self) -> Option<usize> {
+        Some(self.free.len())
     }
 
     fn pack(&mut self, key: K, rect: &Rect) -> Option<Frame<K>> {
-        let w = rect.w + self.config.texture_padding + self.config.texture_extrusion * 2;
-        let h = rect.h + self.config.texture_padding + self.config.texture_extrusion * 2;
-        if let Some((idx, place, rotated)) = self.choose(w, h) {
+        let slot = Slot::new(rect, &self.config);
+        if let Some((idx, place, rotated)) = self.choose(slot) {
             self.place(idx, &place);
-            let pad_half = self.config.texture_padding / 2;
-            let off = self.config.texture_extrusion + pad_half;
-            let (fw, fh) = if rotated {
-                (rect.h, rect.w)
-            } else {
-                (rect.w, rect.h)
-            };
-            let frame_rect = Rect::new(
-                place.x.saturating_add(off),
-                place.y.saturating_add(off),
-                fw,
-                fh,
-            );
+            let frame_rect = Slot::content(&place, rect, rotated, &self.config);
             Some(Frame {
                 key,
                 frame: frame_rect,
@@ -226,6 +224,12 @@ impl<K: Clone> Packer<K> for GuillotinePacker {
                 trimmed: false,
                 source: *rect,
                 source_size: (rect.w, rect.h),
+                degenerate: false,
+                id: 0,
+                content_hash: None,
+                pivot: Default::default(),
+                nine_patch: None,
+                scale: None,
             })
         } else {
             None
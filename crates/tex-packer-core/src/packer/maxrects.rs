@@ -1,4 +1,4 @@
-use super::Packer;
+use super::{Packer, Slot};
 use crate::config::{MaxRectsHeuristic, PackerConfig};
 use crate::model::{Frame, Rect};
 
@@ -244,7 +244,9 @@ impl MaxRectsPacker {
         }
     }
 
-    fn find_position(&self, w: u32, h: u32) -> Option<(Rect, bool)> {
+    /// Best position for `slot`: the rect, whether it is rotated, and its score.
+    fn find_position(&self, slot: Slot) -> Option<(Rect, bool, (i32, i32))> {
+        let Slot { w, h, rw, rh } = slot;
         let mut best_score1 = i32::MAX;
         let mut best_score2 = i32::MAX;
         let mut best_rect = Rect::new(0, 0, 0, 0);
@@ -272,13 +274,13 @@ impl MaxRectsPacker {
                 }
                 // perfect fit early-out
                 if fr.w == w && fr.h == h {
-                    return Some((Rect::new(fr.x, fr.y, w, h), false));
+                    return Some((Rect::new(fr.x, fr.y, w, h), false, (s1, s2)));
                 }
             }
             // rotated
-            if self.config.allow_rotation && fr.w >= h && fr.h >= w {
-                let (s1, s2) = self.score(fr, h, w);
-                let top = fr.y.saturating_add(w);
+            if self.config.allow_rotation && fr.w >= rw && fr.h >= rh {
+                let (s1, s2) = self.score(fr, rw, rh);
+                let top = fr.y.saturating_add(rh);
                 if s1 < best_score1
                     || (s1 == best_score1
                         && (s2 < best_score2
@@ -289,12 +291,12 @@ impl MaxRectsPacker {
                     best_score2 = s2;
                     best_top = top;
                     best_left = fr.x;
-                    best_rect = Rect::new(fr.x, fr.y, h, w);
+                    best_rect = Rect::new(fr.x, fr.y, rw, rh);
                     best_rot = true;
                 }
                 // perfect fit early-out (rotated)
-                if fr.w == h && fr.h == w {
-                    return Some((Rect::new(fr.x, fr.y, h, w), true));
+                if fr.w == rw && fr.h == rh {
+                    return Some((Rect::new(fr.x, fr.y, rw, rh), true, (s1, s2)));
                 }
             }
         }
@@ -302,7 +304,7 @@ impl MaxRectsPacker {
         if best_rect.w == 0 || best_rect.h == 0 {
             None
         } else {
-            Some((best_rect, best_rot))
+            Some((best_rect, best_rot, (best_score1, best_score2)))
         }
     }
 
@@ -354,31 +356,28 @@ fn overlap_1d(a1: u32, a2: u32, b1: u32, b2: u32) -> u32 {
 
 impl<K: Clone> Packer<K> for MaxRectsPacker {
     fn can_pack(&self, rect: &Rect) -> bool {
-        let w = rect.w + self.config.texture_padding + self.config.texture_extrusion * 2;
-        let h = rect.h + self.config.texture_padding + self.config.texture_extrusion * 2;
-        self.find_position(w, h).is_some()
+        let slot = Slot::new(rect, &self.config);
+        self.find_position(slot).is_some()
+    }
+
+    fn set_allow_rotation(&mut self, allow: bool) {
+        self.config.allow_rotation = allow;
+    }
+
+    fn free_list_len(&self) -> Option<usize> {
+        Some(self.free.len())
+    }
+
+    fn score(&self, rect: &Rect) -> Option<(i32, i32)> {
+        let slot = Slot::new(rect, &self.config);
+        self.find_position(slot).map(|(_, _, score)| score)
     }
 
     fn pack(&mut self, key: K, rect: &Rect) -> Option<Frame<K>> {
-        let w = rect.w + self.config.texture_padding + self.config.texture_extrusion * 2;
-        let h = rect.h + self.config.texture_padding + self.config.texture_extrusion * 2;
-        if let Some((place, rotated)) = self.find_position(w, h) {
+        let slot = Slot::new(rect, &self.config);
+        if let Some((place, rotated, _)) = self.find_position(slot) {
             self.place_rect(&place);
-            // Report atlas frame rectangle in stored orientation (post-rotation dimensions),
-            // and offset content inside reserved slot by extrude + half padding (symmetric)
-            let (fw, fh) = if rotated {
-                (rect.h, rect.w)
-            } else {
-                (rect.w, rect.h)
-            };
-            let pad_half = self.config.texture_padding / 2;
-            let off = self.config.texture_extrusion + pad_half;
-            let frame = Rect::new(
-                place.x.saturating_add(off),
-                place.y.saturating_add(off),
-                fw,
-                fh,
-            );
+            let frame = Slot::content(&place, rect, rotated, &self.config);
             Some(Frame {
                 key,
                 frame,
@@ -386,6 +385,12 @@ impl<K: Clone> Packer<K> for MaxRectsPacker {
                 trimmed: false,
                 source: *rect,
                 source_size: (rect.w, rect.h),
+                degenerate: false,
+                id: 0,
+                content_hash: None,
+                pivot: Default::default(),
+                nine_patch: None,
+                scale: None,
             })
         } else {
             None
@@ -1,7 +1,11 @@
+use crate::config::PackerConfig;
 use crate::model::{Frame, Rect};
+use std::sync::Arc;
 
+pub mod grid;
 pub mod guillotine;
 pub mod maxrects;
+pub mod shelf;
 pub mod skyline;
 
 /// A packer places rectangles into a page.
@@ -11,4 +15,137 @@ pub mod skyline;
 pub trait Packer<K> {
     fn can_pack(&self, rect: &Rect) -> bool;
     fn pack(&mut self, key: K, rect: &Rect) -> Option<Frame<K>>;
+    /// Overrides the configured `allow_rotation` for subsequent `pack` calls (used for
+    /// per-sprite rotation overrides).
+    fn set_allow_rotation(&mut self, allow: bool);
+    /// Score of the best position for `rect` (lower is better), or `None` if it does not fit.
+    /// Drives global insertion, which places the best-scoring item first; packers without a
+    /// meaningful score rate every fitting rect the same.
+    fn score(&self, rect: &Rect) -> Option<(i32, i32)> {
+        self.can_pack(rect).then_some((0, 0))
+    }
+    /// Waste-map counters, for packers that keep one.
+    fn waste_map_stats(&self) -> Option<WasteMapStats> {
+        None
+    }
+    /// Current size of the packer's free-space structure (free rects, skyline segments or
+    /// shelves); reported on page spans when tracing.
+    fn free_list_len(&self) -> Option<usize> {
+        None
+    }
+}
+
+type PackerFactoryFn = dyn Fn(&PackerConfig) -> Box<dyn Packer<String>> + Send + Sync;
+
+/// Builds the packers of `AlgorithmFamily::Custom` (`PackerConfig::custom_packer`), one per
+/// page. Clones share the closure.
+///
+/// Custom packers receive content sizes like the built-in ones and should reserve
+/// [`Slot::new`] around each (padding, extrusion, `align`), returning the content rect from
+/// [`Slot::content`]; pages start inside `border_padding`.
+#[derive(Clone)]
+pub struct PackerFactory(Arc<PackerFactoryFn>);
+
+impl PackerFactory {
+    pub fn new(
+        f: impl Fn(&PackerConfig) -> Box<dyn Packer<String>> + Send + Sync + 'static,
+    ) -> Self {
+        Self(Arc::new(f))
+    }
+    /// A fresh packer for one page of `cfg`.
+    pub fn build(&self, cfg: &PackerConfig) -> Box<dyn Packer<String>> {
+        (self.0)(cfg)
+    }
+}
+
+impl std::fmt::Debug for PackerFactory {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("PackerFactory(..)")
+    }
+}
+
+/// Page-space footprint a packer reserves for a `rect`-sized item: content plus extrusion and
+/// padding, for both orientations (they differ when horizontal and vertical padding differ).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Slot {
+    pub w: u32,
+    pub h: u32,
+    /// Size when the content is placed rotated.
+    pub rw: u32,
+    pub rh: u32,
+}
+
+impl Slot {
+    /// Slot sizes are multiples of `cfg.align`, so slots packed from the page border keep
+    /// their origins on that grid (see [`Slot::lead`]).
+    pub fn new(rect: &Rect, cfg: &PackerConfig) -> Self {
+        let pad = cfg.sprite_padding();
+        let step = cfg.align_step();
+        let (lead_x, lead_y) = Self::lead(cfg);
+        let (trail_x, trail_y) = (
+            cfg.texture_extrusion + pad.right,
+            cfg.texture_extrusion + pad.bottom,
+        );
+        let w = |len: u32| (lead_x + len + trail_x).next_multiple_of(step);
+        let h = |len: u32| (lead_y + len + trail_y).next_multiple_of(step);
+        Self {
+            w: w(rect.w),
+            h: h(rect.h),
+            rw: w(rect.h),
+            rh: h(rect.w),
+        }
+    }
+
+    /// Offset of the content from its slot's origin: extrusion plus leading padding, rounded up
+    /// so the content lands on a multiple of `cfg.align` in page space.
+    pub fn lead(cfg: &PackerConfig) -> (u32, u32) {
+        let pad = cfg.sprite_padding();
+        let border = cfg.border_padding;
+        let at = |edge: u32| {
+            (border + cfg.texture_extrusion + edge).next_multiple_of(cfg.align_step()) - border
+        };
+        (at(pad.left), at(pad.top))
+    }
+
+    /// Page rect of the content of a `rect`-sized item whose slot was placed at `place`.
+    pub fn content(place: &Rect, rect: &Rect, rotated: bool, cfg: &PackerConfig) -> Rect {
+        let (lead_x, lead_y) = Self::lead(cfg);
+        let (w, h) = if rotated {
+            (rect.h, rect.w)
+        } else {
+            (rect.w, rect.h)
+        };
+        Rect::new(
+            place.x.saturating_add(lead_x),
+            place.y.saturating_add(lead_y),
+            w,
+            h,
+        )
+    }
+}
+
+/// Debug counters of a Skyline waste map.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct WasteMapStats {
+    /// Free rectangles currently tracked.
+    pub free_rects: usize,
+    /// Area of the tracked free rectangles.
+    pub free_area: u64,
+    /// Total gap area ever added to the map.
+    pub recovered_area: u64,
+    /// Rectangles placed into the map instead of on the skyline.
+    pub placements: usize,
+    /// Area of those placements.
+    pub placed_area: u64,
+}
+
+impl WasteMapStats {
+    /// Share of the recovered gap area that was reused by placements (0.0 to 1.0).
+    pub fn occupancy(&self) -> f64 {
+        if self.recovered_area > 0 {
+            self.placed_area as f64 / self.recovered_area as f64
+        } else {
+            0.0
+        }
+    }
 }
@@ -0,0 +1,137 @@
+use super::{Packer, Slot};
+use crate::config::{PackerConfig, ShelfHeuristic};
+use crate::model::{Frame, Rect};
+
+/// A horizontal strip; its height is fixed by the first rect placed on it.
+#[derive(Clone, Copy, Debug)]
+struct Shelf {
+    y: u32,
+    h: u32,
+    /// Width already used, measured from the border's left edge.
+    used: u32,
+}
+
+/// Shelf (strip) packer: rects are laid left to right on horizontal shelves stacked top to
+/// bottom. Best with inputs sorted by decreasing height (e.g. `SortOrder::HeightDesc`), where
+/// it yields row-aligned, cache-friendly layouts for uniform-height sprites such as glyphs.
+pub struct ShelfPacker {
+    config: PackerConfig,
+    border: Rect,
+    shelves: Vec<Shelf>,
+    heuristic: ShelfHeuristic,
+}
+
+impl ShelfPacker {
+    pub fn new(config: PackerConfig, heuristic: ShelfHeuristic) -> Self {
+        let pad = config.border_padding;
+        let w = config.max_width.saturating_sub(pad.saturating_mul(2));
+        let h = config.max_height.saturating_sub(pad.saturating_mul(2));
+        Self {
+            config,
+            border: Rect::new(pad, pad, w, h),
+            shelves: Vec::new(),
+            heuristic,
+        }
+    }
+
+    /// Top of the next shelf to open.
+    fn next_y(&self) -> u32 {
+        self.shelves.last().map_or(self.border.y, |s| s.y + s.h)
+    }
+
+    fn fits_on(&self, s: &Shelf, w: u32, h: u32) -> bool {
+        h <= s.h && s.used + w <= self.border.w
+    }
+
+    /// Shelf index for a `w`x`h` slot among the open shelves, per heuristic.
+    fn choose_shelf(&self, w: u32, h: u32) -> Option<(usize, u32)> {
+        match self.heuristic {
+            // Only the newest shelf stays open.
+            ShelfHeuristic::NextFit => {
+                let i = self.shelves.len().checked_sub(1)?;
+                self.fits_on(&self.shelves[i], w, h).then_some((i, 0))
+            }
+            ShelfHeuristic::FirstFit => self
+                .shelves
+                .iter()
+                .position(|s| self.fits_on(s, w, h))
+                .map(|i| (i, 0)),
+            // Tightest shelf height, then the earliest shelf.
+            ShelfHeuristic::BestHeightFit => self
+                .shelves
+                .iter()
+                .enumerate()
+                .filter(|(_, s)| self.fits_on(s, w, h))
+                .map(|(i, s)| (i, s.h - h))
+                .min_by_key(|&(i, waste)| (waste, i)),
+        }
+    }
+
+    /// Where `slot` goes: `(shelf index or None for a new shelf, rotated, rect)`.
+    fn find_position(&self, slot: Slot) -> Option<(Option<usize>, bool, Rect)> {
+        let mut orientations = vec![(slot.w, slot.h, false)];
+        if self.config.allow_rotation && (slot.rw, slot.rh) != (slot.w, slot.h) {
+            orientations.push((slot.rw, slot.rh, true));
+        }
+        // An open shelf first (unrotated wins ties), then a new shelf.
+        let on_shelf = orientations
+            .iter()
+            .filter_map(|&(ow, oh, rot)| {
+                self.choose_shelf(ow, oh)
+                    .map(|(i, waste)| ((waste, i, rot), ow, oh))
+            })
+            .min_by_key(|c| c.0);
+        if let Some(((_, i, rot), ow, oh)) = on_shelf {
+            let s = &self.shelves[i];
+            return Some((Some(i), rot, Rect::new(self.border.x + s.used, s.y, ow, oh)));
+        }
+        let y = self.next_y();
+        orientations.iter().find_map(|&(ow, oh, rot)| {
+            let fits = ow <= self.border.w && y + oh <= self.border.y + self.border.h;
+            fits.then_some((None, rot, Rect::new(self.border.x, y, ow, oh)))
+        })
+    }
+}
+
+impl<K: Clone> Packer<K> for ShelfPacker {
+    fn can_pack(&self, rect: &Rect) -> bool {
+        let slot = Slot::new(rect, &self.config);
+        self.find_position(slot).is_some()
+    }
+
+    fn set_allow_rotation(&mut self, allow: bool) {
+        self.config.allow_rotation = allow;
+    }
+
+    fn free_list_len(&self) -> Option<usize> {
+        Some(self.shelves.len())
+    }
+
+    fn pack(&mut self, key: K, rect: &Rect) -> Option<Frame<K>> {
+        let slot = Slot::new(rect, &self.config);
+        let (shelf, rotated, place) = self.find_position(slot)?;
+        match shelf {
+            Some(i) => self.shelves[i].used += place.w,
+            None => self.shelves.push(Shelf {
+                y: place.y,
+                h: place.h,
+                used: place.w,
+            }),
+        }
+        let frame = Slot::content(&place, rect, rotated, &self.config);
+        Some(Frame {
+            key,
+            frame,
+            rotated,
+            trimmed: false,
+            source: *rect,
+            source_size: (rect.w, rect.h),
+            degenerate: false,
+            id: 0,
+            content_hash: None,
+            pivot: Default::default(),
+            nine_patch: None,
+            scale: None,
+        })
+    }
+}
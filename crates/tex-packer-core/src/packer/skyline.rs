@@ -1,4 +1,4 @@
-use super::Packer;
+use super::{Packer, Slot, WasteMapStats};
 use crate::config::{GuillotineChoice, GuillotineSplit, PackerConfig, SkylineHeuristic};
 use crate::model::{Frame, Rect};
 
@@ -38,7 +38,7 @@ impl SkylinePacker {
             border: Rect::new(pad, pad, w, h),
             skylines: vec![SkylineNode { x: pad, y: pad, w }],
             heuristic: config.skyline_heuristic.clone(),
-            waste: if config.use_waste_map {
+            waste: if config.use_waste_map || config.skyline_heuristic.uses_waste_map() {
                 Some(WasteMap::new(
                     Rect::new(pad, pad, w, h),
                     config.allow_rotation,
@@ -70,14 +70,15 @@ impl SkylinePacker {
         }
     }
 
-    fn find_skyline(&self, w: u32, h: u32) -> Option<(usize, Rect, bool)> {
-        match self.heuristic {
-            SkylineHeuristic::BottomLeft => self.find_bottom_left(w, h),
-            SkylineHeuristic::MinWaste => self.find_min_waste(w, h),
+    fn find_skyline(&self, slot: Slot) -> Option<(usize, Rect, bool)> {
+        match self.heuristic.base() {
+            SkylineHeuristic::MinWaste => self.find_min_waste(slot),
+            _ => self.find_bottom_left(slot),
         }
     }
 
-    fn find_bottom_left(&self, w: u32, h: u32) -> Option<(usize, Rect, bool)> {
+    fn find_bottom_left(&self, slot: Slot) -> Option<(usize, Rect, bool)> {
+        let Slot { w, h, rw, rh } = slot;
         let mut best_bottom = u32::MAX;
         let mut best_width = u32::MAX;
         let mut best_index: Option<usize> = None;
@@ -97,7 +98,7 @@ impl SkylinePacker {
                 }
             }
             if self.config.allow_rotation {
-                if let Some(r) = self.can_put(i, h, w) {
+                if let Some(r) = self.can_put(i, rw, rh) {
                     if r.bottom() < best_bottom
                         || (r.bottom() == best_bottom && self.skylines[i].w < best_width)
                     {
@@ -130,7 +131,8 @@ impl SkylinePacker {
         area
     }
 
-    fn find_min_waste(&self, w: u32, h: u32) -> Option<(usize, Rect, bool)> {
+    fn find_min_waste(&self, slot: Slot) -> Option<(usize, Rect, bool)> {
+        let Slot { w, h, rw, rh } = slot;
         let mut best_waste = u32::MAX;
         let mut best_bottom = u32::MAX;
         let mut best_index: Option<usize> = None;
@@ -148,7 +150,7 @@ impl SkylinePacker {
                 }
             }
             if self.config.allow_rotation {
-                if let Some(r) = self.can_put(i, h, w) {
+                if let Some(r) = self.can_put(i, rw, rh) {
                     let waste = self.wasted_area_for(i, &r);
                     if waste < best_waste || (waste == best_waste && r.bottom() < best_bottom) {
                         best_waste = waste;
@@ -164,20 +166,17 @@ impl SkylinePacker {
     }
 
     fn split(&mut self, index: usize, rect: &Rect) {
-        // Clamp the new skyline y to border.bottom() to avoid going past the page bottom when the
-        // placed rectangle touches the bottom edge.
-        let mut new_y = rect.bottom().saturating_add(1);
-        if new_y > self.border.bottom() {
-            new_y = self.border.bottom();
-        }
+        // A rect touching the bottom edge leaves its segment one past `border.bottom()`; `can_put`
+        // rejects anything there. Clamping it back onto the last row would open a phantom
+        // one-pixel band on top of the placed rect.
         let skyline = SkylineNode {
             x: rect.x,
-            y: new_y,
+            y: rect.bottom().saturating_add(1),
             w: rect.w,
         };
         // ensure within border
         debug_assert!(skyline.right() <= self.border.right());
-        debug_assert!(skyline.y <= self.border.bottom());
+        debug_assert!(skyline.y <= self.border.bottom() + 1);
 
         self.skylines.insert(index, skyline);
 
@@ -260,36 +259,37 @@ mod tests {
 
 impl<K: Clone> Packer<K> for SkylinePacker {
     fn can_pack(&self, rect: &Rect) -> bool {
-        let w = rect.w + self.config.texture_padding + self.config.texture_extrusion * 2;
-        let h = rect.h + self.config.texture_padding + self.config.texture_extrusion * 2;
+        let slot = Slot::new(rect, &self.config);
         if let Some(wm) = &self.waste {
-            if wm.can_fit(w, h) {
+            if wm.can_fit(slot) {
                 return true;
             }
         }
-        self.find_skyline(w, h).is_some()
+        self.find_skyline(slot).is_some()
+    }
+
+    fn set_allow_rotation(&mut self, allow: bool) {
+        self.config.allow_rotation = allow;
+        if let Some(w) = &mut self.waste {
+            w.allow_rotation = allow;
+        }
+    }
+
+    fn waste_map_stats(&self) -> Option<WasteMapStats> {
+        self.waste.as_ref().map(|wm| wm.stats)
+    }
+
+    fn free_list_len(&self) -> Option<usize> {
+        Some(self.skylines.len() + self.waste.as_ref().map_or(0, |wm| wm.free.len()))
     }
 
     fn pack(&mut self, key: K, rect: &Rect) -> Option<Frame<K>> {
-        let w = rect.w + self.config.texture_padding + self.config.texture_extrusion * 2;
-        let h = rect.h + self.config.texture_padding + self.config.texture_extrusion * 2;
+        let slot = Slot::new(rect, &self.config);
 
         // Try waste map first
         if let Some(wm) = &mut self.waste {
-            if let Some((place, rotated)) = wm.try_pack(w, h) {
-                let (fw, fh) = if rotated {
-                    (rect.h, rect.w)
-                } else {
-                    (rect.w, rect.h)
-                };
-                let pad_half = self.config.texture_padding / 2;
-                let off = self.config.texture_extrusion + pad_half;
-                let frame = Rect::new(
-                    place.x.saturating_add(off),
-                    place.y.saturating_add(off),
-                    fw,
-                    fh,
-                );
+            if let Some((place, rotated)) = wm.try_pack(slot) {
+                let frame = Slot::content(&place, rect, rotated, &self.config);
                 return Some(Frame {
                     key,
                     frame,
@@ -297,30 +297,24 @@ impl<K: Clone> Packer<K> for SkylinePacker {
                     trimmed: false,
                     source: *rect,
                     source_size: (rect.w, rect.h),
+                    degenerate: false,
+                    id: 0,
+                    content_hash: None,
+                    pivot: Default::default(),
+                    nine_patch: None,
+                    scale: None,
                 });
             }
         }
 
-        if let Some((i, place, rotated)) = self.find_skyline(w, h) {
+        if let Some((i, place, rotated)) = self.find_skyline(slot) {
+            // Gaps are measured against the skyline the rect rests on, so record them before
+            // `split` replaces those segments.
+            self.add_waste_areas(i, &place);
             self.split(i, &place);
             self.merge();
-            self.add_waste_areas(i, &place);
 
-            // Compute content frame size (post-rotation)
-            let (fw, fh) = if rotated {
-                (rect.h, rect.w)
-            } else {
-                (rect.w, rect.h)
-            };
-            // Offset content inside the reserved slot by extrude + half padding (symmetric)
-            let pad_half = self.config.texture_padding / 2;
-            let off = self.config.texture_extrusion + pad_half;
-            let frame = Rect::new(
-                place.x.saturating_add(off),
-                place.y.saturating_add(off),
-                fw,
-                fh,
-            );
+            let frame = Slot::content(&place, rect, rotated, &self.config);
 
             Some(Frame {
                 key,
@@ -329,6 +323,12 @@ impl<K: Clone> Packer<K> for SkylinePacker {
                 trimmed: false,
                 source: *rect,
                 source_size: (rect.w, rect.h),
+                degenerate: false,
+                id: 0,
+                content_hash: None,
+                pivot: Default::default(),
+                nine_patch: None,
+                scale: None,
             })
         } else {
             None
@@ -379,6 +379,7 @@ struct WasteMap {
     free: Vec<Rect>,
     allow_rotation: bool,
     choice: GuillotineChoice,
+    stats: WasteMapStats,
 }
 
 impl WasteMap {
@@ -393,54 +394,57 @@ impl WasteMap {
             free: Vec::new(),
             allow_rotation,
             choice,
+            stats: WasteMapStats::default(),
         }
     }
-    fn can_fit(&self, w: u32, h: u32) -> bool {
-        self.choose(w, h).is_some()
+    fn can_fit(&self, slot: Slot) -> bool {
+        self.choose(slot).is_some()
     }
-    fn try_pack(&mut self, w: u32, h: u32) -> Option<(Rect, bool)> {
-        if let Some((idx, r, rot)) = self.choose(w, h) {
-            self.place(idx, &r);
+    fn try_pack(&mut self, slot: Slot) -> Option<(Rect, bool)> {
+        if let Some((r, rot)) = self.choose(slot) {
+            self.place(&r);
+            self.stats.placements += 1;
+            self.stats.placed_area += r.w as u64 * r.h as u64;
+            self.refresh_free_stats();
             Some((r, rot))
         } else {
             None
         }
     }
-    fn choose(&self, w: u32, h: u32) -> Option<(usize, Rect, bool)> {
-        let mut best_idx = None;
+    fn choose(&self, slot: Slot) -> Option<(Rect, bool)> {
+        let Slot { w, h, rw, rh } = slot;
+        let mut found = false;
         let mut best_s = i32::MAX;
         let mut best_s2 = i32::MAX;
         let mut best = Rect::new(0, 0, 0, 0);
         let mut best_rot = false;
-        for (i, fr) in self.free.iter().enumerate() {
+        for fr in &self.free {
             if fr.w >= w && fr.h >= h {
                 let (s1, s2) = score_choice(&self.choice, fr, w, h);
                 if s1 < best_s || (s1 == best_s && s2 < best_s2) {
                     best_s = s1;
                     best_s2 = s2;
-                    best_idx = Some(i);
+                    found = true;
                     best = Rect::new(fr.x, fr.y, w, h);
                     best_rot = false;
                 }
             }
-            if self.allow_rotation && fr.w >= h && fr.h >= w {
-                let (s1, s2) = score_choice(&self.choice, fr, h, w);
+            if self.allow_rotation && fr.w >= rw && fr.h >= rh {
+                let (s1, s2) = score_choice(&self.choice, fr, rw, rh);
                 if s1 < best_s || (s1 == best_s && s2 < best_s2) {
                     best_s = s1;
                     best_s2 = s2;
-                    best_idx = Some(i);
-                    best = Rect::new(fr.x, fr.y, h, w);
+                    found = true;
+                    best = Rect::new(fr.x, fr.y, rw, rh);
                     best_rot = true;
                 }
             }
         }
-        best_idx.map(|i| (i, best, best_rot))
+        found.then_some((best, best_rot))
     }
-    fn place(&mut self, idx: usize, node: &Rect) {
-        // Remove chosen free rectangle
-        self.free.swap_remove(idx);
-
-        // Subtract the placed node from all existing free rectangles to keep the list disjoint.
+    fn place(&mut self, node: &Rect) {
+        // Subtract the placed node from all free rectangles (the chosen one included, so its
+        // leftover stays available) to keep the list disjoint.
         let mut new_free: Vec<Rect> = Vec::with_capacity(self.free.len() + 2);
         for fr in self.free.drain(..) {
             if !intersects(&fr, node) {
@@ -492,9 +496,15 @@ impl WasteMap {
         self.merge();
     }
     fn add_area(&mut self, r: Rect) {
+        self.stats.recovered_area += r.w as u64 * r.h as u64;
         self.push(r);
         self.prune();
         self.merge();
+        self.refresh_free_stats();
+    }
+    fn refresh_free_stats(&mut self) {
+        self.stats.free_rects = self.free.len();
+        self.stats.free_area = self.free.iter().map(|r| r.w as u64 * r.h as u64).sum();
     }
     fn push(&mut self, r: Rect) {
         if r.w > 0 && r.h > 0 {
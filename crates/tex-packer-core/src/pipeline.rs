@@ -1,16 +1,34 @@
 use crate::config::PackerConfig;
-use crate::config::{AlgorithmFamily, AutoMode, SortOrder};
+use crate::config::{
+    AlgorithmFamily, AutoMode, AutoObjective, CompiledKeyTransform, DuplicateKeyPolicy, FrameOrder,
+    MaxRectsHeuristic, OnUnplaceable, PageBalance, PageGrouping, PageSizeMode, PivotMode,
+    RotationPolicy, SortOrder, TrimOptions,
+};
 use crate::error::{Result, TexPackerError};
-use crate::model::{Atlas, Frame, Meta, Page, Rect};
+use crate::hash::{content_hash, content_hash_rgba32f, stable_frame_id};
+use crate::model::{
+    Atlas, AutoRefinement, Frame, Meta, PackTimings, PackerChoice, Page, PageMinimization, Pivot,
+    Rect, ScaledItem, SkipReason, SkippedItem,
+};
 use crate::packer::{
-    Packer, guillotine::GuillotinePacker, maxrects::MaxRectsPacker, skyline::SkylinePacker,
+    Packer, Slot, grid::GridPacker, guillotine::GuillotinePacker, maxrects::MaxRectsPacker,
+    shelf::ShelfPacker, skyline::SkylinePacker,
 };
-use image::{DynamicImage, RgbaImage};
-use std::collections::{HashMap, HashSet};
+use crate::progress::{PackControl, PackPhase};
+use crate::scratch::ScratchImage;
+use image::{DynamicImage, Rgba32FImage, RgbaImage};
+use std::borrow::Cow;
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::io::Write;
+use std::sync::atomic::{AtomicUsize, Ordering as AtomicOrdering};
+#[cfg(not(target_arch = "wasm32"))]
 use std::time::Instant;
+use tracing::field::Empty;
 use tracing::instrument;
+#[cfg(target_arch = "wasm32")]
+use web_time::Instant;
 
-#[cfg(feature = "parallel")]
+#[cfg(all(feature = "parallel", not(target_arch = "wasm32")))]
 use rayon::prelude::*;
 
 /// In-memory image to pack (key + decoded image).
@@ -22,24 +40,67 @@ pub struct InputImage {
 /// Output RGBA page and its logical page record.
 pub struct OutputPage {
     pub page: Page,
+    /// Page pixels; empty (0x0) when the page lives in `scratch`.
     pub rgba: RgbaImage,
+    /// File-backed pixels of pages composited with `PackerConfig::low_memory`.
+    pub scratch: Option<ScratchImage>,
+}
+
+impl OutputPage {
+    /// Page pixels in memory, loading them from the scratch file for low-memory pages.
+    pub fn pixels(&self) -> Result<Cow<'_, RgbaImage>> {
+        match &self.scratch {
+            Some(s) => Ok(Cow::Owned(s.to_rgba()?)),
+            None => Ok(Cow::Borrowed(&self.rgba)),
+        }
+    }
+
+    /// Encodes the page as PNG; low-memory pages are streamed row by row.
+    pub fn write_png<W: Write>(&self, out: W) -> Result<()> {
+        match &self.scratch {
+            Some(s) => s.write_png(out),
+            None => {
+                let stride = self.rgba.width() as usize * 4;
+                let raw = self.rgba.as_raw();
+                crate::scratch::write_png_rows(
+                    out,
+                    self.rgba.width(),
+                    self.rgba.height(),
+                    |y, buf| {
+                        let start = y as usize * stride;
+                        buf.write_all(&raw[start..start + stride])?;
+                        Ok(())
+                    },
+                )
+            }
+        }
+    }
 }
 
 /// Output of a packing run: atlas metadata and RGBA pages.
 pub struct PackOutput {
     pub atlas: Atlas,
     pub pages: Vec<OutputPage>,
+    /// Time spent per phase; `encode_ms` is left for the caller that writes the pages.
+    pub timings: PackTimings,
+    /// Inputs that are not in the atlas: transparent ones under `TransparentPolicy::Skip`, and
+    /// with `PackerConfig::on_unplaceable` other than `Error`, those too large for a page (and,
+    /// for `pack_images_iter`, those that failed to decode).
+    pub skipped: Vec<SkippedItem>,
+    /// Inputs shrunk under `OnUnplaceable::ScaleDown`.
+    pub scaled: Vec<ScaledItem>,
 }
 
 impl PackOutput {
-    /// Computes packing statistics for this output.
-    /// This is a convenience method that delegates to `atlas.stats()`.
+    /// Computes packing statistics for this output: `atlas.stats()` plus the phase timings.
     pub fn stats(&self) -> crate::model::PackStats {
-        self.atlas.stats()
+        crate::model::PackStats {
+            timings: Some(self.timings),
+            ..self.atlas.stats()
+        }
     }
 }
 
-#[instrument(skip_all)]
 /// Packs `inputs` into atlas pages using configuration `cfg` and returns metadata and RGBA pages.
 ///
 /// Notes:
@@ -47,6 +108,22 @@ impl PackOutput {
 /// - When `family` is `Auto`, a small portfolio is tried and the best result is chosen (pages first, then total area).
 /// - `time_budget_ms` can limit Auto evaluation time; `parallel` may evaluate in parallel when enabled.
 pub fn pack_images(inputs: Vec<InputImage>, cfg: PackerConfig) -> Result<PackOutput> {
+    pack_images_with(inputs, cfg, &PackControl::default())
+}
+
+/// [`pack_images`] reporting progress to `ctl` and stopping with
+/// [`TexPackerError::Cancelled`] once its cancellation token fires. Cancellation is checked
+/// between inputs, `Auto` candidates, anneal steps and pages.
+///
+/// Runs in a `pack_images` tracing span with `prepare`, `auto` / `auto_candidate`, `page` and
+/// `composite` / `composite_page` spans below it, so a `tracing` subscriber (e.g.
+/// `tracing-subscriber` or tracy) shows where time goes.
+#[instrument(name = "pack_images", skip_all, fields(inputs = inputs.len(), family = ?cfg.family))]
+pub fn pack_images_with(
+    inputs: Vec<InputImage>,
+    cfg: PackerConfig,
+    ctl: &PackControl,
+) -> Result<PackOutput> {
     // Validate configuration first
     cfg.validate()?;
 
@@ -55,17 +132,615 @@ pub fn pack_images(inputs: Vec<InputImage>, cfg: PackerConfig) -> Result<PackOut
     }
 
     // Preprocess once
-    let prepared = prepare_inputs(&inputs, &cfg);
+    let started = Instant::now();
+    let (prepared, mut skipped) = prepare_inputs(&inputs, &cfg, ctl)?;
+    let (prepared, scaled) = apply_unplaceable(prepared, &cfg, &mut skipped);
+    warn_unplaceable(&skipped, &scaled);
+    let mut cfg = cfg;
+    resolve_grid_cell(
+        &mut cfg,
+        prepared
+            .iter()
+            .map(|p| (p.key.as_str(), p.rect.w, p.rect.h)),
+    )?;
+    warn_degenerate(
+        prepared
+            .iter()
+            .filter(|p| p.degenerate)
+            .map(|p| p.key.as_str()),
+    );
+    let prepare_ms = started.elapsed().as_millis() as u64;
+
+    let started = Instant::now();
+    let plan = plan_layout(&prepared, cfg, ctl)?;
+    let pack_ms = started.elapsed().as_millis() as u64;
+
+    let started = Instant::now();
+    let mut out = plan.compose(&prepared, ctl)?;
+    out.timings = PackTimings {
+        prepare_ms,
+        pack_ms,
+        refine_ms: plan.refine_ms,
+        composite_ms: started.elapsed().as_millis() as u64,
+        encode_ms: 0,
+    };
+    out.skipped = skipped;
+    out.scaled = scaled;
+    Ok(out)
+}
+
+/// A sprite whose maps (e.g. diffuse, normal, emissive) share one slot across parallel atlases.
+/// `layers[i]` is composited into page set `i`.
+pub struct InputImageSet {
+    pub key: String,
+    pub layers: Vec<DynamicImage>,
+}
+
+/// Output of [`pack_image_sets`]: one layout and its pages for every layer.
+pub struct ImageSetOutput {
+    pub atlas: Atlas,
+    /// `layers[i][p]` is page `p` composited from layer `i` of every set.
+    pub layers: Vec<Vec<OutputPage>>,
+}
+
+impl ImageSetOutput {
+    /// Computes packing statistics of the shared layout.
+    pub fn stats(&self) -> crate::model::PackStats {
+        self.atlas.stats()
+    }
+}
+
+/// Packs linked sprite sets: each set gets one slot, and each layer is composited into its own
+/// page set, so `hero.png`, `hero_n.png` and `hero_e.png` land at identical positions on the
+/// diffuse, normal and emissive pages.
+///
+/// Every set needs the same number of layers, and all layers of a set the same dimensions.
+/// Layout runs on layer 0 with the highest alpha of any layer, so trimming keeps the union of
+/// the layers' opaque areas.
+pub fn pack_image_sets(sets: Vec<InputImageSet>, cfg: PackerConfig) -> Result<ImageSetOutput> {
+    cfg.validate()?;
+    if cfg.sdf.is_some() {
+        return Err(TexPackerError::InvalidConfig(
+            "sdf is not supported by pack_image_sets".into(),
+        ));
+    }
+    let Some(first) = sets.first() else {
+        return Err(TexPackerError::Empty);
+    };
+    let n_layers = first.layers.len();
+    let mut layers: Vec<Vec<RgbaImage>> = Vec::with_capacity(sets.len());
+    for set in &sets {
+        if set.layers.len() != n_layers || n_layers == 0 {
+            return Err(TexPackerError::InvalidInput(format!(
+                "image set '{}' has {} layer(s), expected {}",
+                set.key,
+                set.layers.len(),
+                n_layers.max(1)
+            )));
+        }
+        let rgba: Vec<RgbaImage> = set.layers.iter().map(|l| l.to_rgba8()).collect();
+        let dims = rgba[0].dimensions();
+        if let Some((l, img)) = rgba
+            .iter()
+            .enumerate()
+            .find(|(_, l)| l.dimensions() != dims)
+        {
+            return Err(TexPackerError::InvalidInput(format!(
+                "layer {l} of image set '{}' is {}x{}, expected {}x{}",
+                set.key,
+                img.width(),
+                img.height(),
+                dims.0,
+                dims.1
+            )));
+        }
+        layers.push(rgba);
+    }
+
+    let merged = sets
+        .iter()
+        .zip(&layers)
+        .map(|(set, rgba)| (set.key.as_str(), merge_alpha(rgba)));
+    let (prepared, origin): (Vec<Prep>, Vec<usize>) =
+        prepare_indexed(merged, &cfg)?.into_iter().unzip();
+    warn_degenerate(
+        prepared
+            .iter()
+            .filter(|p| p.degenerate)
+            .map(|p| p.key.as_str()),
+    );
+    let mut cfg = cfg;
+    resolve_grid_cell(
+        &mut cfg,
+        prepared
+            .iter()
+            .map(|p| (p.key.as_str(), p.rect.w, p.rect.h)),
+    )?;
+    let ctl = PackControl::default();
+    let plan = plan_layout(&prepared, cfg, &ctl)?;
+
+    // Hand each layer's pixels to the compositor in turn
+    let mut per_set: Vec<_> = layers.into_iter().map(Vec::into_iter).collect();
+    let mut atlas = None;
+    let mut pages = Vec::with_capacity(n_layers);
+    for _ in 0..n_layers {
+        let mut current: Vec<RgbaImage> = per_set
+            .iter_mut()
+            .map(|it| it.next().unwrap_or_default())
+            .collect();
+        let layer: Vec<Prep> = prepared
+            .iter()
+            .zip(&origin)
+            .map(|(p, &i)| Prep {
+                key: p.key.clone(),
+                rgba: std::mem::take(&mut current[i]),
+                ..*p
+            })
+            .collect();
+        let out = plan.compose(&layer, &ctl)?;
+        atlas.get_or_insert(out.atlas);
+        pages.push(out.pages);
+    }
+    Ok(ImageSetOutput {
+        atlas: atlas.expect("at least one layer"),
+        layers: pages,
+    })
+}
+
+/// `layers[0]` with each pixel's alpha raised to the highest alpha of any layer.
+fn merge_alpha(layers: &[RgbaImage]) -> RgbaImage {
+    let mut merged = layers[0].clone();
+    for layer in &layers[1..] {
+        for (m, p) in merged.pixels_mut().zip(layer.pixels()) {
+            m[3] = m[3].max(p[3]);
+        }
+    }
+    merged
+}
+
+/// Output 32-bit float RGBA page of [`pack_images_hdr`] and its logical page record.
+pub struct HdrOutputPage {
+    pub page: Page,
+    pub rgba: Rgba32FImage,
+}
+
+impl HdrOutputPage {
+    /// Encodes the page as OpenEXR (32-bit float RGBA).
+    pub fn to_exr(&self) -> Result<Vec<u8>> {
+        crate::encode::encode_exr(&self.rgba)
+    }
+}
+
+/// Output of [`pack_images_hdr`].
+pub struct HdrPackOutput {
+    pub atlas: Atlas,
+    pub pages: Vec<HdrOutputPage>,
+}
+
+impl HdrPackOutput {
+    /// Computes packing statistics for this output.
+    pub fn stats(&self) -> crate::model::PackStats {
+        self.atlas.stats()
+    }
+}
+
+/// Packs high-dynamic-range inputs (e.g. `.exr` / `.hdr` lightmaps) into 32-bit float pages,
+/// keeping values above 1.0 and negative values intact.
+///
+/// Layout is identical to [`pack_images`]: trimming, pivot detection and every placement option
+/// work on an 8-bit copy of each input, so the alpha threshold still applies to alpha scaled to
+/// 0-255. Pixels are then copied from the float inputs, with extrusion, outlines and
+/// `background_color`. `alpha_bleed` and `low_memory` do not apply. Content hashes cover the
+/// float pixels. Write pages with [`HdrOutputPage::to_exr`].
+pub fn pack_images_hdr(inputs: Vec<InputImage>, cfg: PackerConfig) -> Result<HdrPackOutput> {
+    cfg.validate()?;
+    if cfg.sdf.is_some() {
+        return Err(TexPackerError::InvalidConfig(
+            "sdf is not supported by pack_images_hdr".into(),
+        ));
+    }
+    if inputs.is_empty() {
+        return Err(TexPackerError::Empty);
+    }
+    let hdr: Vec<Rgba32FImage> = inputs.iter().map(|i| i.image.to_rgba32f()).collect();
+    let ldr = inputs.iter().map(|i| (i.key.as_str(), i.image.to_rgba8()));
+    let (mut prepared, origin): (Vec<Prep>, Vec<usize>) =
+        prepare_indexed(ldr, &cfg)?.into_iter().unzip();
+    drop(inputs);
+    for (p, &i) in prepared.iter_mut().zip(&origin) {
+        // Only the float pixels are composited
+        p.rgba = RgbaImage::new(0, 0);
+        p.content_hash = Some(content_hash_rgba32f(&hdr[i], &p.source));
+    }
+    warn_degenerate(
+        prepared
+            .iter()
+            .filter(|p| p.degenerate)
+            .map(|p| p.key.as_str()),
+    );
+    let mut cfg = cfg;
+    resolve_grid_cell(
+        &mut cfg,
+        prepared
+            .iter()
+            .map(|p| (p.key.as_str(), p.rect.w, p.rect.h)),
+    )?;
+    let plan = plan_layout(&prepared, cfg, &PackControl::default())?;
+    let cfg = &plan.cfg;
+
+    let by_key: HashMap<&str, (&Prep, &Rgba32FImage)> = prepared
+        .iter()
+        .zip(&origin)
+        .map(|(p, &i)| (p.key.as_str(), (p, &hdr[i])))
+        .collect();
+    let pages = plan
+        .pages
+        .iter()
+        .map(|page| {
+            let mut canvas = Rgba32FImage::new(page.width, page.height);
+            for f in &page.frames {
+                let Some(&(prep, src)) = by_key.get(f.key.as_str()) else {
+                    continue;
+                };
+                if prep.source.w == 0 || prep.source.h == 0 {
+                    continue;
+                }
+                crate::compositing::blit_rgba32f(
+                    src,
+                    &mut canvas,
+                    f.frame.x,
+                    f.frame.y,
+                    prep.source.x,
+                    prep.source.y,
+                    prep.source.w,
+                    prep.source.h,
+                    f.rotated.then_some(cfg.rotation_direction),
+                    prep.extrusion(cfg),
+                    cfg.extrude_mode_for(&prep.key),
+                    cfg.texture_outlines,
+                );
+            }
+            if let Some(bg) = cfg.background_color {
+                crate::compositing::matte_rgba32f(&mut canvas, bg);
+            }
+            HdrOutputPage {
+                page: page.clone(),
+                rgba: canvas,
+            }
+        })
+        .collect();
+    let mut meta = image_meta(cfg);
+    meta.format = "RGBA32F".into();
+    meta.packer = plan.packer.clone();
+    meta.page_minimization = plan.minimization;
+    meta.auto_refinement = plan.refinement.clone();
+    Ok(HdrPackOutput {
+        atlas: Atlas {
+            pages: plan.pages,
+            meta,
+        },
+        pages,
+    })
+}
+
+/// Streams `inputs` into atlas pages with bounded memory: each image is decoded, trimmed and
+/// composited as it arrives, and only the page being filled is kept in memory. Pages are yielded
+/// as soon as they are full.
+///
+/// Items are placed online in arrival order, so `sort_order` does not apply and pages are usually
+/// less dense than with [`pack_images`]. `Auto` resolves to MaxRects (BestAreaFit), since a
+/// portfolio needs every input up front. Page grouping and `DuplicateKeyPolicy::Overwrite` are
+/// not supported.
+pub fn pack_images_iter<I>(inputs: I, cfg: PackerConfig) -> Result<PageStream<I::IntoIter>>
+where
+    I: IntoIterator<Item = Result<InputImage>>,
+{
+    cfg.validate()?;
+    if cfg.page_grouping != PageGrouping::None {
+        return Err(TexPackerError::InvalidConfig(
+            "page grouping is not supported by pack_images_iter".into(),
+        ));
+    }
+    if cfg.sprites.values().any(|s| s.group.is_some()) {
+        return Err(TexPackerError::InvalidConfig(
+            "per-sprite page groups are not supported by pack_images_iter".into(),
+        ));
+    }
+    if cfg.duplicate_keys == DuplicateKeyPolicy::Overwrite {
+        return Err(TexPackerError::InvalidConfig(
+            "duplicate_keys = overwrite is not supported by pack_images_iter".into(),
+        ));
+    }
+    if cfg.family == AlgorithmFamily::Grid
+        && (cfg.grid_cell_width == 0 || cfg.grid_cell_height == 0)
+    {
+        return Err(TexPackerError::InvalidConfig(
+            "pack_images_iter needs an explicit grid cell size".into(),
+        ));
+    }
+    let mut cfg = cfg;
+    if let (AlgorithmFamily::Auto, Some(first)) = (&cfg.family, cfg.auto_candidates.first()) {
+        cfg = first.apply(&cfg);
+    } else if cfg.family == AlgorithmFamily::Auto {
+        cfg.family = AlgorithmFamily::MaxRects;
+        cfg.mr_heuristic = MaxRectsHeuristic::BestAreaFit;
+    }
+    Ok(PageStream {
+        inputs: inputs.into_iter(),
+        keys: cfg.key_transform.compile()?,
+        taken: HashSet::new(),
+        cfg,
+        open: None,
+        next_id: 0,
+        placed: 0,
+        seen: 0,
+        read: 0,
+        degenerate: Vec::new(),
+        skipped: Vec::new(),
+        scaled: Vec::new(),
+        done: false,
+    })
+}
+
+/// Pages produced by [`pack_images_iter`], in page id order.
+pub struct PageStream<I> {
+    inputs: I,
+    keys: CompiledKeyTransform,
+    taken: HashSet<String>,
+    cfg: PackerConfig,
+    open: Option<OpenPage>,
+    next_id: usize,
+    placed: usize,
+    seen: usize,
+    /// Items pulled from `inputs`, failed ones included.
+    read: usize,
+    degenerate: Vec<String>,
+    skipped: Vec<SkippedItem>,
+    scaled: Vec<ScaledItem>,
+    done: bool,
+}
+
+/// Page currently being filled; the canvas spans the maximum page size until it is closed.
+struct OpenPage {
+    packer: Box<dyn Packer<String>>,
+    frames: Vec<Frame>,
+    /// Page extent the placed slots need (see `slot_extent`).
+    extent: (u32, u32),
+    canvas: RgbaImage,
+}
+
+impl<I: Iterator<Item = Result<InputImage>>> PageStream<I> {
+    /// Atlas metadata shared by all pages of this stream.
+    pub fn meta(&self) -> Meta {
+        image_meta(&self.cfg)
+    }
+
+    /// Inputs left out so far (see [`PackOutput::skipped`]).
+    pub fn skipped(&self) -> &[SkippedItem] {
+        &self.skipped
+    }
+
+    /// Inputs shrunk so far (see [`PackOutput::scaled`]).
+    pub fn scaled(&self) -> &[ScaledItem] {
+        &self.scaled
+    }
+
+    /// Drains the stream into a regular [`PackOutput`] (keeps every page in memory).
+    pub fn into_output(mut self) -> Result<PackOutput> {
+        let meta = self.meta();
+        let pages = self.by_ref().collect::<Result<Vec<OutputPage>>>()?;
+        let atlas = Atlas {
+            pages: pages.iter().map(|p| p.page.clone()).collect(),
+            meta,
+        };
+        Ok(PackOutput {
+            atlas,
+            pages,
+            timings: PackTimings::default(),
+            skipped: self.skipped,
+            scaled: self.scaled,
+        })
+    }
+
+    fn open_page(&self) -> Result<OpenPage> {
+        check_page_limits(
+            &self.cfg,
+            None,
+            self.next_id,
+            self.next_id,
+            self.placed,
+            self.seen,
+        )?;
+        Ok(OpenPage {
+            packer: new_packer(&self.cfg),
+            frames: Vec::new(),
+            extent: (0, 0),
+            canvas: RgbaImage::new(self.cfg.max_width, self.cfg.max_height),
+        })
+    }
+
+    fn close_page(&mut self, open: OpenPage) -> Result<OutputPage> {
+        let _span = tracing::debug_span!(
+            "page",
+            page = self.next_id,
+            frames = open.frames.len(),
+            free_rects = open.packer.free_list_len(),
+        )
+        .entered();
+        let (w, h) = page_size_for_extent(open.extent, &self.cfg);
+        let mut rgba = if open.canvas.dimensions() == (w, h) {
+            open.canvas
+        } else {
+            image::imageops::crop_imm(&open.canvas, 0, 0, w, h).to_image()
+        };
+        if let Some(bg) = self.cfg.background_color {
+            crate::compositing::matte(&mut rgba, bg);
+        }
+        let mut page = Page {
+            id: self.next_id,
+            width: w,
+            height: h,
+            frames: open.frames,
+        };
+        // Frames arrive in input order, which no ranks keep
+        order_frames(
+            std::slice::from_mut(&mut page),
+            self.cfg.frame_order,
+            &HashMap::new(),
+        );
+        self.next_id += 1;
+        let mut out = OutputPage {
+            page,
+            rgba,
+            scratch: None,
+        };
+        postprocess_page(&mut out, &self.cfg)?;
+        Ok(out)
+    }
 
-    // Auto portfolio
-    if matches!(cfg.family, AlgorithmFamily::Auto) {
-        return pack_auto(&prepared, cfg);
+    /// Places one item on the open page, closing it (and returning it) when the item needs a
+    /// fresh page.
+    fn place(&mut self, prep: Prep) -> Result<Option<OutputPage>> {
+        let mut closed = None;
+        loop {
+            let mut open = match self.open.take() {
+                Some(open) => open,
+                None => self.open_page()?,
+            };
+            let placed = place_item(
+                open.packer.as_mut(),
+                &prep.key,
+                prep.rect,
+                prep.placement,
+                &self.cfg,
+                false,
+            );
+            if let Some((mut f, extent)) = placed {
+                f.trimmed = prep.trimmed;
+                f.source = prep.source;
+                f.source_size = prep.orig_size;
+                f.degenerate = prep.degenerate;
+                f.id = stable_frame_id(&prep.key);
+                f.pivot = self.cfg.pivot_for(&prep.key, prep.detected_pivot);
+                f.nine_patch = self.cfg.sprite(&prep.key).and_then(|s| s.nine_patch);
+                f.scale = prep.scale;
+                f.content_hash = prep.content_hash;
+                open.extent = (open.extent.0.max(extent.0), open.extent.1.max(extent.1));
+                blit_frame(&mut open.canvas, &prep, &f, &self.cfg);
+                open.frames.push(f);
+                self.open = Some(open);
+                self.placed += 1;
+                return Ok(closed);
+            }
+            if open.frames.is_empty() {
+                // Does not fit even on an empty page
+                return Err(TexPackerError::OutOfSpace {
+                    key: prep.key,
+                    width: prep.rect.w,
+                    height: prep.rect.h,
+                    pages_attempted: self.next_id + 1,
+                });
+            }
+            closed = Some(self.close_page(open)?);
+        }
     }
+}
+
+impl<I: Iterator<Item = Result<InputImage>>> Iterator for PageStream<I> {
+    type Item = Result<OutputPage>;
 
-    pack_prepared(&prepared, &cfg)
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+        loop {
+            let next = self.inputs.next();
+            self.read += next.is_some() as usize;
+            let inp = match next {
+                Some(Ok(inp)) => inp,
+                Some(Err(e)) if self.cfg.on_unplaceable != OnUnplaceable::Error => {
+                    self.skipped.push(SkippedItem {
+                        key: format!("#{}", self.read - 1),
+                        reason: SkipReason::Decode {
+                            error: e.to_string(),
+                        },
+                    });
+                    continue;
+                }
+                Some(Err(e)) => {
+                    self.done = true;
+                    return Some(Err(e));
+                }
+                None => {
+                    self.done = true;
+                    warn_degenerate(self.degenerate.iter().map(String::as_str));
+                    warn_unplaceable(&self.skipped, &self.scaled);
+                    return match self.open.take() {
+                        Some(open) => Some(self.close_page(open)),
+                        // Everything skipped is an empty partial result, not an error
+                        None if self.next_id == 0 && self.skipped.is_empty() => {
+                            Some(Err(TexPackerError::Empty))
+                        }
+                        None => None,
+                    };
+                }
+            };
+            self.seen += 1;
+            let mut key = self.keys.apply(&inp.key);
+            if self.taken.contains(&key) {
+                if self.cfg.duplicate_keys == DuplicateKeyPolicy::Error {
+                    self.done = true;
+                    return Some(Err(TexPackerError::DuplicateKey(key)));
+                }
+                key = suffixed_key(&key, |c| self.taken.contains(c));
+            }
+            self.taken.insert(key.clone());
+            if self.cfg.is_excluded(&key) {
+                continue;
+            }
+            let Some(mut prep) = prepare_one(
+                key.clone(),
+                distance_field(inp.image.into_rgba8(), &self.cfg),
+                &self.cfg,
+            ) else {
+                self.skipped.push(SkippedItem {
+                    key,
+                    reason: SkipReason::Transparent,
+                });
+                continue;
+            };
+            prep.placement = Placement::for_key(&self.cfg, &prep.key);
+            let Some(prep) = fit_unplaceable(prep, &self.cfg, &mut self.skipped, &mut self.scaled)
+            else {
+                continue;
+            };
+            if prep.degenerate {
+                self.degenerate.push(prep.key.clone());
+            }
+            match self.place(prep) {
+                Ok(Some(page)) => return Some(Ok(page)),
+                Ok(None) => {}
+                Err(e) => {
+                    self.done = true;
+                    return Some(Err(e));
+                }
+            }
+        }
+    }
 }
 
+/// Finds the content of `rgba` (alpha above `threshold`). Returns the trimmed size at the
+/// origin and its source rect in the image, or `(None, full rect)` when nothing is opaque.
 pub fn compute_trim_rect(rgba: &RgbaImage, threshold: u8) -> (Option<Rect>, Rect) {
+    compute_trim_rect_with(rgba, threshold, &TrimOptions::default())
+}
+
+/// [`compute_trim_rect`] with margin, minimum size and alignment rules from `opts`.
+pub fn compute_trim_rect_with(
+    rgba: &RgbaImage,
+    threshold: u8,
+    opts: &TrimOptions,
+) -> (Option<Rect>, Rect) {
     let (w, h) = rgba.dimensions();
     let mut x1 = 0;
     let mut y1 = 0;
@@ -134,9 +809,76 @@ pub fn compute_trim_rect(rgba: &RgbaImage, threshold: u8) -> (Option<Rect>, Rect
             break;
         }
     }
-    let tw = x2 - x1 + 1;
-    let th = y2 - y1 + 1;
-    (Some(Rect::new(0, 0, tw, th)), Rect::new(x1, y1, tw, th))
+    let [ml, mt, mr, mb] = opts.margin;
+    let x1 = x1.saturating_sub(ml);
+    let y1 = y1.saturating_sub(mt);
+    let x2 = x2.saturating_add(mr).min(w - 1);
+    let y2 = y2.saturating_add(mb).min(h - 1);
+    let (x, tw) = grow_trim_span(x1, x2 - x1 + 1, w, opts);
+    let (y, th) = grow_trim_span(y1, y2 - y1 + 1, h, opts);
+    (Some(Rect::new(0, 0, tw, th)), Rect::new(x, y, tw, th))
+}
+
+/// With `cfg.align_size`, grows `source` right/down to multiples of `cfg.align`, enlarging the
+/// `size` canvas of the sprite when the grown rect no longer fits it.
+fn align_source(cfg: &PackerConfig, source: Rect, size: (u32, u32)) -> (Rect, (u32, u32)) {
+    let step = cfg.align_step();
+    if !cfg.align_size || step == 1 {
+        return (source, size);
+    }
+    let grown = Rect::new(
+        source.x,
+        source.y,
+        source.w.next_multiple_of(step),
+        source.h.next_multiple_of(step),
+    );
+    let size = (size.0.max(grown.x + grown.w), size.1.max(grown.y + grown.h));
+    (grown, size)
+}
+
+/// Widens `start..start+len` to `min_size` and then to a multiple of `multiple_of`, centered
+/// and clamped to `0..full`.
+fn grow_trim_span(start: u32, len: u32, full: u32, opts: &TrimOptions) -> (u32, u32) {
+    let mut target = len.max(opts.min_size);
+    if opts.multiple_of > 1 {
+        target = target.div_ceil(opts.multiple_of) * opts.multiple_of;
+    }
+    let target = target.min(full);
+    if target <= len {
+        return (start, len);
+    }
+    let extra = target - len;
+    (start.saturating_sub(extra / 2).min(full - target), target)
+}
+
+/// Pivot detected from the pixels for `mode` (normalized to the full image, y down), or `None`
+/// when the mode does not look at pixels or nothing is above `threshold` alpha.
+pub fn detect_pivot(rgba: &RgbaImage, mode: PivotMode, threshold: u8) -> Option<Pivot> {
+    let (w, h) = rgba.dimensions();
+    if w == 0 || h == 0 {
+        return None;
+    }
+    match mode {
+        PivotMode::Manual | PivotMode::Center => None,
+        PivotMode::AlphaCentroid => {
+            let (mut sx, mut sy, mut n) = (0f64, 0f64, 0u64);
+            for (x, y, px) in rgba.enumerate_pixels() {
+                if px[3] > threshold {
+                    sx += x as f64 + 0.5;
+                    sy += y as f64 + 0.5;
+                    n += 1;
+                }
+            }
+            (n > 0).then(|| Pivot::new(sx / n as f64 / w as f64, sy / n as f64 / h as f64))
+        }
+        PivotMode::BottomCenter => (0..h).rev().find_map(|y| {
+            let opaque = |x: &u32| rgba.get_pixel(*x, y)[3] > threshold;
+            let first = (0..w).find(opaque)?;
+            let last = (first..w).rev().find(opaque).unwrap_or(first);
+            let cx = (first + last + 1) as f64 / 2.0;
+            Some(Pivot::new(cx / w as f64, (y + 1) as f64 / h as f64))
+        }),
+    }
 }
 
 fn next_pow2(mut v: u32) -> u32 {
@@ -164,197 +906,1464 @@ struct Prep {
     trimmed: bool,
     source: Rect,
     orig_size: (u32, u32),
+    degenerate: bool,
+    /// Hash of the pixels; `None` for layout-only items.
+    content_hash: Option<u64>,
+    /// Pivot found by `cfg.pivot_mode` detection, if any.
+    detected_pivot: Option<Pivot>,
+    placement: Placement,
+    /// Factor the input was shrunk by under `OnUnplaceable::ScaleDown`.
+    scale: Option<f32>,
 }
 
-fn prepare_inputs(inputs: &[InputImage], cfg: &PackerConfig) -> Vec<Prep> {
-    let mut out = Vec::with_capacity(inputs.len());
-    for inp in inputs.iter() {
-        let rgba = inp.image.to_rgba8();
-        let (iw, ih) = rgba.dimensions();
-        let mut push_entry = true;
-        let (rect, trimmed, source) = if cfg.trim {
-            let (trim_rect_opt, src_rect) = compute_trim_rect(&rgba, cfg.trim_threshold);
-            match trim_rect_opt {
-                Some(r) => (Rect::new(0, 0, r.w, r.h), true, src_rect),
-                None => match cfg.transparent_policy {
-                    crate::config::TransparentPolicy::Keep => {
-                        (Rect::new(0, 0, iw, ih), false, Rect::new(0, 0, iw, ih))
-                    }
-                    crate::config::TransparentPolicy::OneByOne => {
-                        (Rect::new(0, 0, 1, 1), true, Rect::new(0, 0, 1, 1))
-                    }
-                    crate::config::TransparentPolicy::Skip => {
-                        push_entry = false;
-                        (Rect::new(0, 0, 0, 0), false, Rect::new(0, 0, 0, 0))
-                    }
-                },
-            }
-        } else {
-            (Rect::new(0, 0, iw, ih), false, Rect::new(0, 0, iw, ih))
+impl Prep {
+    /// Whether this item comes from the layout-only APIs and carries no pixels.
+    fn is_layout_only(&self) -> bool {
+        self.content_hash.is_none()
+    }
+
+    /// Extrusion of this item (global plus its per-sprite extra).
+    fn extrusion(&self, cfg: &PackerConfig) -> u32 {
+        cfg.texture_extrusion + self.placement.extra_extrusion
+    }
+}
+
+/// Placement overrides of one sprite, resolved from `cfg.sprites`.
+#[derive(Debug, Clone, Copy, Default)]
+struct Placement {
+    /// Extrusion / padding beyond the global values.
+    extra_extrusion: u32,
+    extra_padding: u32,
+    rotate: Option<bool>,
+}
+
+impl Placement {
+    fn for_key(cfg: &PackerConfig, key: &str) -> Self {
+        let Some(s) = cfg.sprite(key) else {
+            return Self::default();
         };
-        if !push_entry {
-            continue;
+        let pad = cfg.sprite_padding();
+        Self {
+            extra_extrusion: s
+                .texture_extrusion
+                .map_or(0, |e| e.saturating_sub(cfg.texture_extrusion)),
+            extra_padding: s.texture_padding.map_or(0, |p| {
+                p.saturating_sub(pad.horizontal().min(pad.vertical()))
+            }),
+            rotate: s.rotate.and_then(RotationPolicy::forced),
         }
-        out.push(Prep {
-            key: inp.key.clone(),
-            rgba,
-            rect,
-            trimmed,
-            source,
-            orig_size: (iw, ih),
-        });
     }
-    // stable sort per config
-    match cfg.sort_order {
-        SortOrder::None => {}
-        SortOrder::NameAsc => {
-            out.sort_by(|a, b| a.key.cmp(&b.key));
-        }
-        SortOrder::AreaDesc => {
-            out.sort_by(|a, b| {
-                (b.rect.w * b.rect.h)
-                    .cmp(&(a.rect.w * a.rect.h))
-                    .then_with(|| a.key.cmp(&b.key))
-            });
-        }
-        SortOrder::MaxSideDesc => {
-            out.sort_by(|a, b| {
-                b.rect
-                    .w
-                    .max(b.rect.h)
-                    .cmp(&a.rect.w.max(a.rect.h))
-                    .then_with(|| a.key.cmp(&b.key))
-            });
-        }
-        SortOrder::HeightDesc => {
-            out.sort_by(|a, b| b.rect.h.cmp(&a.rect.h).then_with(|| a.key.cmp(&b.key)));
-        }
-        SortOrder::WidthDesc => {
-            out.sort_by(|a, b| b.rect.w.cmp(&a.rect.w).then_with(|| a.key.cmp(&b.key)));
-        }
+}
+
+impl Placement {
+    /// Offset of the frame inside its grown slot: the extra extrusion and padding, kept on
+    /// the `cfg.align` grid.
+    fn inset(&self, cfg: &PackerConfig) -> u32 {
+        (self.extra_extrusion + self.extra_padding).next_multiple_of(cfg.align_step())
     }
-    out
 }
 
-fn pack_prepared(prepared: &[Prep], cfg: &PackerConfig) -> Result<PackOutput> {
-    let mut pages: Vec<OutputPage> = Vec::new();
-    let mut atlas_pages: Vec<Page> = Vec::new();
+/// The slot a `rect`-sized item asks the packer for, whether it is rotated up front, and whether
+/// its orientation is pinned (decided here, not by the packer).
+fn item_slot(
+    rect: Rect,
+    placement: Placement,
+    cfg: &PackerConfig,
+    force_rotation: bool,
+) -> (Rect, bool, bool) {
+    // The sprite's own policy wins over a rotation replayed from another layout
+    let rotate = placement.rotate.unwrap_or(force_rotation);
+    let grow = 2 * placement.inset(cfg);
+    let (w, h) = if rotate {
+        (rect.h, rect.w)
+    } else {
+        (rect.w, rect.h)
+    };
+    let slot = Rect::new(rect.x, rect.y, w + grow, h + grow);
+    (slot, rotate, rotate || placement.rotate.is_some())
+}
 
-    // Map for quick lookup during compositing
-    let prep_map: HashMap<String, &Prep> = prepared.iter().map(|p| (p.key.clone(), p)).collect();
+/// Runs `f` with packer-side rotation disabled when the orientation is `pinned`.
+fn with_pinned<T>(
+    packer: &mut dyn Packer<String>,
+    pinned: bool,
+    cfg: &PackerConfig,
+    f: impl FnOnce(&mut dyn Packer<String>) -> T,
+) -> T {
+    if pinned {
+        packer.set_allow_rotation(false);
+    }
+    let out = f(&mut *packer);
+    if pinned {
+        packer.set_allow_rotation(cfg.allow_rotation);
+    }
+    out
+}
 
-    // Remaining indices to place (in sorted order)
-    let mut remaining: Vec<usize> = (0..prepared.len()).collect();
-    let mut page_id = 0usize;
+/// [`Packer::score`] of the slot [`place_item`] would pack.
+fn score_item(
+    packer: &mut dyn Packer<String>,
+    rect: Rect,
+    placement: Placement,
+    cfg: &PackerConfig,
+    force_rotation: bool,
+) -> Option<(i32, i32)> {
+    let (slot, _, pinned) = item_slot(rect, placement, cfg, force_rotation);
+    with_pinned(packer, pinned, cfg, |p| p.score(&slot))
+}
 
-    while !remaining.is_empty() {
-        let mut packer: Box<dyn Packer<String>> = match cfg.family {
-            AlgorithmFamily::Skyline => Box::new(SkylinePacker::new(cfg.clone())),
-            AlgorithmFamily::MaxRects => {
-                Box::new(MaxRectsPacker::new(cfg.clone(), cfg.mr_heuristic.clone()))
-            }
-            AlgorithmFamily::Guillotine => Box::new(GuillotinePacker::new(
-                cfg.clone(),
-                cfg.g_choice.clone(),
-                cfg.g_split.clone(),
-            )),
-            AlgorithmFamily::Auto => unreachable!(),
+/// Packs a `rect`-sized item honoring its `placement` (`force_rotation` also rotates it).
+///
+/// Extra padding/extrusion is reserved by packing a grown slot and insetting the frame within
+/// it. Returns the frame and the page extent the slot needs, or `None` if it does not fit.
+fn place_item(
+    packer: &mut dyn Packer<String>,
+    key: &str,
+    rect: Rect,
+    placement: Placement,
+    cfg: &PackerConfig,
+    force_rotation: bool,
+) -> Option<(Frame, (u32, u32))> {
+    let (slot, rotate, pinned) = item_slot(rect, placement, cfg, force_rotation);
+    let packed = with_pinned(packer, pinned, cfg, |p| {
+        if p.can_pack(&slot) {
+            p.pack(key.to_string(), &slot)
+        } else {
+            None
+        }
+    });
+    let mut f = packed?;
+    f.rotated |= rotate;
+    let extent = slot_extent(&f.frame, cfg);
+    let inset = placement.inset(cfg);
+    if inset > 0 {
+        let (fw, fh) = if f.rotated {
+            (rect.h, rect.w)
+        } else {
+            (rect.w, rect.h)
         };
-        let mut frames: Vec<Frame> = Vec::new();
+        f.frame = Rect::new(f.frame.x + inset, f.frame.y + inset, fw, fh);
+    }
+    Some((f, extent))
+}
 
-        loop {
-            let mut placed_any = false;
-            let mut remove_set: HashSet<usize> = HashSet::new();
-            for &idx in &remaining {
-                let p = &prepared[idx];
-                if !packer.can_pack(&p.rect) {
-                    continue;
-                }
-                if let Some(mut f) = packer.pack(p.key.clone(), &p.rect) {
-                    f.trimmed = p.trimmed;
-                    f.source = p.source;
-                    f.source_size = p.orig_size;
-                    frames.push(f);
-                    remove_set.insert(idx);
-                    placed_any = true;
-                }
-            }
-            if !placed_any {
-                break;
-            }
-            // Retain only indices not placed
-            if !remove_set.is_empty() {
-                remaining.retain(|i| !remove_set.contains(i));
-            }
-        }
+/// Logs a warning enumerating degenerate inputs (zero-sized, or transparent collapsed to 1x1).
+fn warn_degenerate<'a>(keys: impl Iterator<Item = &'a str>) {
+    let keys: Vec<&str> = keys.collect();
+    if !keys.is_empty() {
+        tracing::warn!(
+            count = keys.len(),
+            keys = ?keys,
+            "degenerate frames (zero-sized or fully transparent inputs)"
+        );
+    }
+}
 
-        if frames.is_empty() {
-            // No textures could be placed on this page - likely first texture is too large
-            let placed = prepared.len() - remaining.len();
-            return Err(TexPackerError::OutOfSpaceGeneric {
-                placed,
-                total: prepared.len(),
+/// Applies `cfg.key_transform`, `cfg.duplicate_keys` and `cfg.sprites`, and trims every input.
+/// Also returns the inputs `cfg.transparent_policy` skipped.
+fn prepare_inputs(
+    inputs: &[InputImage],
+    cfg: &PackerConfig,
+    ctl: &PackControl,
+) -> Result<(Vec<Prep>, Vec<SkippedItem>)> {
+    let span = tracing::debug_span!("prepare", inputs = inputs.len(), kept = Empty).entered();
+    let keys = cfg.key_transform.compile()?;
+    let done = AtomicUsize::new(0);
+    let prepare = |(i, inp): (usize, &InputImage)| -> Result<Option<(Prep, usize)>> {
+        ctl.check()?;
+        let prep = prepare_one(
+            keys.apply(&inp.key),
+            distance_field(inp.image.to_rgba8(), cfg),
+            cfg,
+        );
+        ctl.report(
+            PackPhase::Prepare,
+            done.fetch_add(1, AtomicOrdering::Relaxed) + 1,
+            inputs.len(),
+        );
+        Ok(prep.map(|p| (p, i)))
+    };
+    // RGBA conversion and trimming are per-input and dominate large batches; rayon's ordered
+    // collect keeps the input order, so duplicate resolution below stays deterministic.
+    #[cfg(all(feature = "parallel", not(target_arch = "wasm32")))]
+    let prepared: Vec<Option<(Prep, usize)>> = if cfg.parallel {
+        inputs
+            .par_iter()
+            .enumerate()
+            .map(prepare)
+            .collect::<Result<_>>()?
+    } else {
+        inputs
+            .iter()
+            .enumerate()
+            .map(prepare)
+            .collect::<Result<_>>()?
+    };
+    #[cfg(not(all(feature = "parallel", not(target_arch = "wasm32"))))]
+    let prepared: Vec<Option<(Prep, usize)>> = inputs
+        .iter()
+        .enumerate()
+        .map(prepare)
+        .collect::<Result<_>>()?;
+    let mut skipped = Vec::new();
+    for (inp, prep) in inputs.iter().zip(&prepared) {
+        let key = keys.apply(&inp.key);
+        if prep.is_none() && !cfg.is_excluded(&key) {
+            skipped.push(SkippedItem {
+                key,
+                reason: SkipReason::Transparent,
             });
         }
+    }
+    let prepared: Vec<Prep> = finish_prepared(prepared.into_iter().flatten().collect(), cfg)?
+        .into_iter()
+        .map(|(p, _)| p)
+        .collect();
+    span.record("kept", prepared.len());
+    Ok((prepared, skipped))
+}
 
-        // Compute final page size via helper to keep logic consistent across APIs
-        let (page_w, page_h) = compute_page_size(&frames, cfg);
+/// Applies `cfg.on_unplaceable` to every item (see [`fit_unplaceable`]), returning the items
+/// to pack and the ones that were scaled down.
+fn apply_unplaceable(
+    prepared: Vec<Prep>,
+    cfg: &PackerConfig,
+    skipped: &mut Vec<SkippedItem>,
+) -> (Vec<Prep>, Vec<ScaledItem>) {
+    let mut scaled = Vec::new();
+    let kept = prepared
+        .into_iter()
+        .filter_map(|p| fit_unplaceable(p, cfg, skipped, &mut scaled))
+        .collect();
+    (kept, scaled)
+}
 
-        let mut canvas = RgbaImage::new(page_w, page_h);
-        for f in &frames {
-            if let Some(prep) = prep_map.get(&f.key) {
-                crate::compositing::blit_rgba(
-                    &prep.rgba,
-                    &mut canvas,
-                    f.frame.x,
-                    f.frame.y,
-                    prep.source.x,
-                    prep.source.y,
-                    prep.source.w,
-                    prep.source.h,
-                    f.rotated,
-                    cfg.texture_extrusion,
-                    cfg.texture_outlines,
-                );
-            }
-        }
-        let page = Page {
-            id: page_id,
-            width: page_w,
-            height: page_h,
-            frames: frames.clone(),
-        };
-        pages.push(OutputPage {
-            page: page.clone(),
-            rgba: canvas,
+/// The item to pack in place of `p`: `p` itself when it fits an empty page (or under
+/// `OnUnplaceable::Error`, where layout reports it), a shrunk copy under `ScaleDown`, or `None`
+/// after recording it in `skipped`.
+fn fit_unplaceable(
+    p: Prep,
+    cfg: &PackerConfig,
+    skipped: &mut Vec<SkippedItem>,
+    scaled: &mut Vec<ScaledItem>,
+) -> Option<Prep> {
+    if cfg.on_unplaceable == OnUnplaceable::Error || fits_empty_page(&p, cfg) {
+        return Some(p);
+    }
+    if cfg.on_unplaceable == OnUnplaceable::ScaleDown
+        && let Some(small) = scale_to_fit(&p, cfg)
+    {
+        scaled.push(ScaledItem {
+            key: p.key,
+            original: p.orig_size,
+            scaled: small.orig_size,
+            scale: small.scale.unwrap_or(1.0),
         });
-        atlas_pages.push(page);
-        page_id += 1;
+        return Some(small);
     }
+    skipped.push(SkippedItem {
+        key: p.key,
+        reason: SkipReason::TooLarge {
+            width: p.rect.w,
+            height: p.rect.h,
+        },
+    });
+    None
+}
 
-    let meta = Meta {
+/// Page area inside the border.
+fn page_room(cfg: &PackerConfig) -> (u32, u32) {
+    (
+        cfg.max_width.saturating_sub(2 * cfg.border_padding),
+        cfg.max_height.saturating_sub(2 * cfg.border_padding),
+    )
+}
+
+/// Whether the slot of `p` fits an empty page in an orientation it may be placed in.
+fn fits_empty_page(p: &Prep, cfg: &PackerConfig) -> bool {
+    let (rect, _, pinned) = item_slot(p.rect, p.placement, cfg, false);
+    let slot = Slot::new(&rect, cfg);
+    let (w, h) = page_room(cfg);
+    (slot.w <= w && slot.h <= h) || (cfg.allow_rotation && !pinned && slot.rw <= w && slot.rh <= h)
+}
+
+/// Shrinks the whole image of `p` (so trimming is redone at the new size) by the smallest factor
+/// that lets its slot fit an empty page; `None` when padding and extrusion alone fill the page.
+fn scale_to_fit(p: &Prep, cfg: &PackerConfig) -> Option<Prep> {
+    let (rect, _, pinned) = item_slot(p.rect, p.placement, cfg, false);
+    let slot = Slot::new(&rect, cfg);
+    let (room_w, room_h) = page_room(cfg);
+    // Largest ratio that fits the content into the room the padding and extrusion leave
+    let ratio = |w: u32, h: u32, slot_w: u32, slot_h: u32| {
+        let free_w = room_w.checked_sub(slot_w - w)?;
+        let free_h = room_h.checked_sub(slot_h - h)?;
+        Some((free_w as f64 / w as f64).min(free_h as f64 / h as f64))
+    };
+    let upright = ratio(p.rect.w, p.rect.h, slot.w, slot.h);
+    let rotated = (cfg.allow_rotation && !pinned)
+        .then(|| ratio(p.rect.h, p.rect.w, slot.rw, slot.rh))
+        .flatten();
+    let mut scale = upright
+        .into_iter()
+        .chain(rotated)
+        .fold(0.0f64, f64::max)
+        .min(1.0);
+    if scale <= 0.0 {
+        return None;
+    }
+    let (iw, ih) = p.orig_size;
+    // Resampling and re-trimming can round up, so step down a pixel at a time until it fits
+    for _ in 0..64 {
+        let w = ((iw as f64 * scale).floor() as u32).max(1);
+        let h = ((ih as f64 * scale).floor() as u32).max(1);
+        let mut small = if p.is_layout_only() {
+            scaled_layout_prep(p, scale, (w, h), cfg)
+        } else {
+            let rgba =
+                image::imageops::resize(&p.rgba, w, h, image::imageops::FilterType::Triangle);
+            prepare_one(p.key.clone(), rgba, cfg)?
+        };
+        small.placement = p.placement;
+        small.scale = Some(scale as f32);
+        if fits_empty_page(&small, cfg) {
+            return Some(small);
+        }
+        if (w, h) == (1, 1) {
+            return None;
+        }
+        let long = w.max(h) as f64;
+        scale *= (long - 1.0) / long;
+    }
+    None
+}
+
+/// Logs a warning per partial-result list that is not empty.
+fn warn_unplaceable(skipped: &[SkippedItem], scaled: &[ScaledItem]) {
+    if !skipped.is_empty() {
+        let keys: Vec<&str> = skipped.iter().map(|s| s.key.as_str()).collect();
+        tracing::warn!(count = keys.len(), keys = ?keys, "inputs skipped");
+    }
+    if !scaled.is_empty() {
+        let keys: Vec<&str> = scaled.iter().map(|s| s.key.as_str()).collect();
+        tracing::warn!(count = keys.len(), keys = ?keys, "inputs scaled down to fit");
+    }
+}
+
+/// Prepares `(key, pixels)` items like [`prepare_inputs`], pairing every kept item with its
+/// index.
+fn prepare_indexed<'a>(
+    items: impl Iterator<Item = (&'a str, RgbaImage)>,
+    cfg: &PackerConfig,
+) -> Result<Vec<(Prep, usize)>> {
+    let keys = cfg.key_transform.compile()?;
+    let prepared = items
+        .enumerate()
+        .filter_map(|(i, (key, rgba))| prepare_one(keys.apply(key), rgba, cfg).map(|p| (p, i)))
+        .collect();
+    finish_prepared(prepared, cfg)
+}
+
+/// Resolves duplicate keys, drops excluded sprites and attaches per-sprite placement.
+fn finish_prepared(prepared: Vec<(Prep, usize)>, cfg: &PackerConfig) -> Result<Vec<(Prep, usize)>> {
+    let mut prepared = resolve_duplicate_keys(prepared, cfg.duplicate_keys, |(p, _)| &mut p.key)?;
+    prepared.retain(|(p, _)| !cfg.is_excluded(&p.key));
+    for (p, _) in &mut prepared {
+        p.placement = Placement::for_key(cfg, &p.key);
+    }
+    Ok(prepared)
+}
+
+/// Applies `policy` to items sharing a key, keeping first-occurrence order.
+fn resolve_duplicate_keys<T>(
+    items: Vec<T>,
+    policy: DuplicateKeyPolicy,
+    key: fn(&mut T) -> &mut String,
+) -> Result<Vec<T>> {
+    let mut index: HashMap<String, usize> = HashMap::with_capacity(items.len());
+    let mut out: Vec<T> = Vec::with_capacity(items.len());
+    for mut item in items {
+        let k = key(&mut item).clone();
+        let Some(&i) = index.get(&k) else {
+            index.insert(k, out.len());
+            out.push(item);
+            continue;
+        };
+        match policy {
+            DuplicateKeyPolicy::Error => return Err(TexPackerError::DuplicateKey(k)),
+            DuplicateKeyPolicy::Overwrite => out[i] = item,
+            DuplicateKeyPolicy::Suffix => {
+                let unique = suffixed_key(&k, |c| index.contains_key(c));
+                *key(&mut item) = unique.clone();
+                index.insert(unique, out.len());
+                out.push(item);
+            }
+        }
+    }
+    Ok(out)
+}
+
+/// First of `key_2`, `key_3`, ... (suffix placed before the extension) not yet `taken`.
+fn suffixed_key(key: &str, taken: impl Fn(&str) -> bool) -> String {
+    let name_start = key.rfind('/').map_or(0, |i| i + 1);
+    let (base, ext) = match key[name_start..].rfind('.') {
+        Some(dot) if dot > 0 => key.split_at(name_start + dot),
+        _ => (key, ""),
+    };
+    (2..)
+        .map(|n| format!("{base}_{n}{ext}"))
+        .find(|c| !taken(c))
+        .expect("unbounded suffix search")
+}
+
+/// Converts a decoded input to a signed distance field when `cfg.sdf` is set.
+fn distance_field(rgba: RgbaImage, cfg: &PackerConfig) -> RgbaImage {
+    match &cfg.sdf {
+        #[cfg(feature = "sdf")]
+        Some(opts) => crate::sdf::generate_sdf(&rgba, opts),
+        _ => rgba,
+    }
+}
+
+/// Trims one decoded image per `cfg`; `None` when the transparent policy drops it.
+fn prepare_one(key: String, rgba: RgbaImage, cfg: &PackerConfig) -> Option<Prep> {
+    let (iw, ih) = rgba.dimensions();
+    let mut push_entry = true;
+    let mut degenerate = false;
+    let (rect, trimmed, source) = if iw == 0 || ih == 0 {
+        // Zero-sized input: nothing to composite. Treated like a fully transparent image:
+        // dropped under `Skip`, otherwise reserved as a 1x1 placeholder slot.
+        degenerate = true;
+        push_entry = cfg.transparent_policy != crate::config::TransparentPolicy::Skip;
+        (Rect::new(0, 0, 1, 1), false, Rect::new(0, 0, iw, ih))
+    } else if cfg.trim_for(&key) {
+        let (trim_rect_opt, src_rect) =
+            compute_trim_rect_with(&rgba, cfg.trim_threshold, &cfg.trim_options);
+        match trim_rect_opt {
+            Some(r) => (Rect::new(0, 0, r.w, r.h), true, src_rect),
+            None => match cfg.transparent_policy {
+                crate::config::TransparentPolicy::Keep => {
+                    (Rect::new(0, 0, iw, ih), false, Rect::new(0, 0, iw, ih))
+                }
+                crate::config::TransparentPolicy::OneByOne => {
+                    degenerate = true;
+                    (Rect::new(0, 0, 1, 1), true, Rect::new(0, 0, 1, 1))
+                }
+                crate::config::TransparentPolicy::Skip => {
+                    push_entry = false;
+                    (Rect::new(0, 0, 0, 0), false, Rect::new(0, 0, 0, 0))
+                }
+            },
+        }
+    } else {
+        (Rect::new(0, 0, iw, ih), false, Rect::new(0, 0, iw, ih))
+    };
+    if !push_entry {
+        return None;
+    }
+    let (rgba, rect, source) = if degenerate {
+        (rgba, rect, source)
+    } else {
+        let (source, size) = align_source(cfg, source, (iw, ih));
+        let rgba = if size == (iw, ih) {
+            rgba
+        } else {
+            let mut canvas = RgbaImage::new(size.0, size.1);
+            image::imageops::replace(&mut canvas, &rgba, 0, 0);
+            canvas
+        };
+        (rgba, Rect::new(0, 0, source.w, source.h), source)
+    };
+    let (iw, ih) = rgba.dimensions();
+    let content_hash = Some(content_hash(&rgba, &source));
+    let detected_pivot = detect_pivot(&rgba, cfg.pivot_mode, cfg.trim_threshold);
+    Some(Prep {
+        placement: Placement::default(),
+        key,
+        rgba,
+        rect,
+        trimmed,
+        source,
+        orig_size: (iw, ih),
+        degenerate,
+        content_hash,
+        detected_pivot,
+        scale: None,
+    })
+}
+
+/// Indices of `prepared` in packing order for `order` (stable, ties broken by key).
+fn packing_order(prepared: &[Prep], order: &SortOrder) -> Vec<usize> {
+    let mut idx: Vec<usize> = (0..prepared.len()).collect();
+    let item = |i: &usize| &prepared[*i];
+    match order {
+        SortOrder::None => {}
+        SortOrder::NameAsc => idx.sort_by(|a, b| item(a).key.cmp(&item(b).key)),
+        SortOrder::AreaDesc => idx.sort_by(|a, b| {
+            let (a, b) = (item(a), item(b));
+            (b.rect.w * b.rect.h)
+                .cmp(&(a.rect.w * a.rect.h))
+                .then_with(|| a.key.cmp(&b.key))
+        }),
+        SortOrder::MaxSideDesc => idx.sort_by(|a, b| {
+            let (a, b) = (item(a), item(b));
+            b.rect
+                .w
+                .max(b.rect.h)
+                .cmp(&a.rect.w.max(a.rect.h))
+                .then_with(|| a.key.cmp(&b.key))
+        }),
+        SortOrder::HeightDesc => idx.sort_by(|a, b| {
+            let (a, b) = (item(a), item(b));
+            b.rect.h.cmp(&a.rect.h).then_with(|| a.key.cmp(&b.key))
+        }),
+        SortOrder::WidthDesc => idx.sort_by(|a, b| {
+            let (a, b) = (item(a), item(b));
+            b.rect.w.cmp(&a.rect.w).then_with(|| a.key.cmp(&b.key))
+        }),
+    }
+    idx
+}
+
+/// Partitions input indices (already in packing order) into page groups. Without grouping
+/// there is a single unnamed group; otherwise groups are ordered by name and never share a page.
+fn page_groups<'a>(
+    keys: impl Iterator<Item = &'a str>,
+    cfg: &PackerConfig,
+) -> Vec<(Option<String>, Vec<usize>)> {
+    let mut groups: BTreeMap<Option<String>, Vec<usize>> = BTreeMap::new();
+    for (i, key) in keys.enumerate() {
+        groups.entry(cfg.group_of(key)).or_default().push(i);
+    }
+    groups.into_iter().collect()
+}
+
+/// Fresh single-page packer for a concrete (non-`Auto`) family.
+fn new_packer(cfg: &PackerConfig) -> Box<dyn Packer<String>> {
+    match cfg.family {
+        AlgorithmFamily::Skyline => Box::new(SkylinePacker::new(cfg.clone())),
+        AlgorithmFamily::MaxRects => {
+            Box::new(MaxRectsPacker::new(cfg.clone(), cfg.mr_heuristic.clone()))
+        }
+        AlgorithmFamily::Guillotine => Box::new(GuillotinePacker::new(
+            cfg.clone(),
+            cfg.g_choice.clone(),
+            cfg.g_split.clone(),
+        )),
+        AlgorithmFamily::Shelf => Box::new(ShelfPacker::new(cfg.clone(), cfg.shelf_heuristic)),
+        AlgorithmFamily::Grid => Box::new(GridPacker::new(
+            cfg.clone(),
+            cfg.grid_cell_width,
+            cfg.grid_cell_height,
+        )),
+        AlgorithmFamily::Custom => cfg
+            .custom_packer
+            .as_ref()
+            .expect("validated: custom family has a factory")
+            .build(cfg),
+        AlgorithmFamily::Auto => unreachable!(),
+    }
+}
+
+/// For the `Grid` family, derives an auto (0) cell width/height from the largest of `sizes`
+/// (`(key, w, h)`) and rejects sprites that fit a cell in neither orientation.
+fn resolve_grid_cell<'a>(
+    cfg: &mut PackerConfig,
+    sizes: impl Iterator<Item = (&'a str, u32, u32)> + Clone,
+) -> Result<()> {
+    if cfg.family != AlgorithmFamily::Grid {
+        return Ok(());
+    }
+    if cfg.grid_cell_width == 0 {
+        cfg.grid_cell_width = sizes.clone().map(|(_, w, _)| w).max().unwrap_or(1);
+    }
+    if cfg.grid_cell_height == 0 {
+        cfg.grid_cell_height = sizes.clone().map(|(_, _, h)| h).max().unwrap_or(1);
+    }
+    let (cw, ch) = (cfg.grid_cell_width, cfg.grid_cell_height);
+    for (key, w, h) in sizes {
+        let fits = (w <= cw && h <= ch) || (cfg.allow_rotation && h <= cw && w <= ch);
+        if !fits {
+            return Err(TexPackerError::InvalidInput(format!(
+                "sprite '{key}' ({w}x{h}) does not fit the {cw}x{ch} grid cell"
+            )));
+        }
+    }
+    Ok(())
+}
+
+/// Enforces `max_pages` and `group_max_pages` before another page is opened.
+fn check_page_limits(
+    cfg: &PackerConfig,
+    group: Option<&str>,
+    group_pages: usize,
+    total_pages: usize,
+    placed: usize,
+    total: usize,
+) -> Result<()> {
+    if let Some(max) = cfg.max_pages
+        && total_pages >= max
+    {
+        return Err(TexPackerError::TooManyPages {
+            scope: "atlas".into(),
+            max_pages: max,
+            placed,
+            total,
+        });
+    }
+    if let (Some(name), Some(max)) = (group, cfg.group_max_pages)
+        && group_pages >= max
+    {
+        return Err(TexPackerError::TooManyPages {
+            scope: format!("group '{name}'"),
+            max_pages: max,
+            placed,
+            total,
+        });
+    }
+    Ok(())
+}
+
+/// Final page layout of a packing run, before any pixels are composited.
+struct Plan {
+    pages: Vec<Page>,
+    /// Config the pages were laid out with (the winning candidate under `Auto`).
+    cfg: PackerConfig,
+    /// Algorithm that won an `Auto` run.
+    packer: Option<PackerChoice>,
+    minimization: Option<PageMinimization>,
+    refinement: Option<AutoRefinement>,
+    refine_ms: u64,
+}
+
+impl Plan {
+    fn compose(&self, prepared: &[Prep], ctl: &PackControl) -> Result<PackOutput> {
+        let mut out = compose_pages(prepared, &self.cfg, self.pages.clone(), ctl)?;
+        out.atlas.meta.packer = self.packer.clone();
+        out.atlas.meta.page_minimization = self.minimization;
+        out.atlas.meta.auto_refinement = self.refinement.clone();
+        Ok(out)
+    }
+}
+
+/// Lays out `prepared`, running the portfolio when `cfg.family` is `Auto`.
+fn plan_layout(prepared: &[Prep], cfg: PackerConfig, ctl: &PackControl) -> Result<Plan> {
+    ctl.check()?;
+    let mut plan = if cfg.family == AlgorithmFamily::Auto {
+        plan_auto(prepared, cfg, ctl)?
+    } else {
+        plan_prepared(prepared, cfg, ctl)?
+    };
+    // `prepared` is in input order
+    let ranks = input_ranks(
+        plan.cfg.frame_order,
+        prepared.iter().map(|p| p.key.as_str()),
+    );
+    order_frames(&mut plan.pages, plan.cfg.frame_order, &ranks);
+    Ok(plan)
+}
+
+/// Input position of each key for `FrameOrder::Input` (empty for the other orders).
+fn input_ranks<'a>(
+    order: FrameOrder,
+    keys: impl Iterator<Item = &'a str>,
+) -> HashMap<String, usize> {
+    if order != FrameOrder::Input {
+        return HashMap::new();
+    }
+    keys.enumerate().map(|(i, k)| (k.to_string(), i)).collect()
+}
+
+/// Reorders the frames of each page for `PackerConfig::frame_order`.
+fn order_frames(pages: &mut [Page], order: FrameOrder, ranks: &HashMap<String, usize>) {
+    for page in pages {
+        match order {
+            FrameOrder::Packing => {}
+            FrameOrder::Key => page.frames.sort_by(|a, b| a.key.cmp(&b.key)),
+            // Keys without a rank keep their relative order
+            FrameOrder::Input => page
+                .frames
+                .sort_by_key(|f| ranks.get(&f.key).copied().unwrap_or(usize::MAX)),
+            FrameOrder::Position => page.frames.sort_by_key(|f| (f.frame.y, f.frame.x)),
+        }
+    }
+}
+
+fn plan_prepared(prepared: &[Prep], cfg: PackerConfig, ctl: &PackControl) -> Result<Plan> {
+    let order = packing_order(prepared, &cfg.sort_order);
+    let pages = layout_prepared(prepared, &cfg, &order, None)?;
+    ctl.report(PackPhase::Layout, 1, 1);
+    let (pages, minimization) = minimize_page(prepared, &cfg, &order, None, pages);
+    Ok(Plan {
+        pages,
+        cfg,
+        packer: None,
+        minimization,
+        refinement: None,
+        refine_ms: 0,
+    })
+}
+
+fn layout_sorted(prepared: &[Prep], cfg: &PackerConfig) -> Result<Vec<Page>> {
+    let order = packing_order(prepared, &cfg.sort_order);
+    layout_prepared(prepared, cfg, &order, None)
+}
+
+/// Places `prepared` (visited in `order`) onto pages without touching pixels.
+///
+/// With `forced_rotation`, item `i` is placed rotated exactly when `forced_rotation[i]` is set;
+/// `cfg.allow_rotation` should then be off so the packer does not rotate on its own.
+fn layout_prepared(
+    prepared: &[Prep],
+    cfg: &PackerConfig,
+    order: &[usize],
+    forced_rotation: Option<&[bool]>,
+) -> Result<Vec<Page>> {
+    let mut atlas_pages: Vec<Page> = Vec::new();
+
+    // Indices to place (in packing order), split into page groups
+    let mut page_id = 0usize;
+    let groups = page_groups(order.iter().map(|&i| prepared[i].key.as_str()), cfg);
+    for (group, positions) in groups {
+        let mut remaining: Vec<usize> = positions.into_iter().map(|p| order[p]).collect();
+        let items = (cfg.page_balance == PageBalance::MultiBin).then(|| remaining.clone());
+        let first_page = page_id;
+        while !remaining.is_empty() {
+            check_page_limits(
+                cfg,
+                group.as_deref(),
+                page_id - first_page,
+                page_id,
+                atlas_pages.iter().map(|p| p.frames.len()).sum(),
+                prepared.len(),
+            )?;
+            let page = fill_page(prepared, cfg, &mut remaining, forced_rotation, page_id);
+            if page.frames.is_empty() {
+                // No textures could be placed on this page - likely first texture is too large
+                let placed = atlas_pages.iter().map(|p| p.frames.len()).sum();
+                return Err(TexPackerError::OutOfSpaceGeneric {
+                    placed,
+                    total: prepared.len(),
+                });
+            }
+            atlas_pages.push(page);
+            page_id += 1;
+        }
+        if let Some(items) = items
+            && page_id - first_page > 1
+        {
+            let fill_first = &atlas_pages[first_page..];
+            let key = |pages: &[Page]| (pages.len(), pages_area(pages));
+            // Opening pages early can beat packing the open ones tightly; keep the better one
+            let multi = [false, true]
+                .into_iter()
+                .filter_map(|early_open| {
+                    multi_bin_pages(
+                        prepared,
+                        cfg,
+                        forced_rotation,
+                        &items,
+                        first_page,
+                        fill_first.len(),
+                        early_open,
+                    )
+                })
+                .min_by_key(|pages| key(pages));
+            if let Some(multi) = multi
+                && key(&multi) < key(fill_first)
+            {
+                tracing::debug!(
+                    from = fill_first.len(),
+                    to = multi.len(),
+                    "multi-bin layout replaces fill-first"
+                );
+                atlas_pages.truncate(first_page);
+                page_id = first_page + multi.len();
+                atlas_pages.extend(multi);
+            }
+        }
+        if cfg.page_balance == PageBalance::Balanced && page_id - first_page > 1 {
+            balance_pages(
+                prepared,
+                cfg,
+                forced_rotation,
+                &mut atlas_pages[first_page..],
+            );
+        }
+    }
+    Ok(atlas_pages)
+}
+
+/// Places as many of `remaining` (in order) as fit on one new page with id `page_id`, removing
+/// them from `remaining`.
+fn fill_page(
+    prepared: &[Prep],
+    cfg: &PackerConfig,
+    remaining: &mut Vec<usize>,
+    forced_rotation: Option<&[bool]>,
+    page_id: usize,
+) -> Page {
+    let span = tracing::debug_span!(
+        "page",
+        page = page_id,
+        remaining = remaining.len(),
+        frames = Empty,
+        free_rects = Empty,
+        occupancy = Empty,
+    )
+    .entered();
+    let mut packer = new_packer(cfg);
+    let mut frames: Vec<Frame> = Vec::new();
+    let mut extent = (0, 0);
+    let force = |idx: usize| forced_rotation.is_some_and(|r| r[idx]);
+    let mut place = |packer: &mut dyn Packer<String>, idx: usize| {
+        let p = &prepared[idx];
+        let placed = place_item(packer, &p.key, p.rect, p.placement, cfg, force(idx));
+        let Some((f, slot)) = placed else {
+            return false;
+        };
+        extent = (extent.0.max(slot.0), extent.1.max(slot.1));
+        frames.push(finish_frame(f, p, cfg));
+        true
+    };
+
+    if cfg.mr_global && cfg.family == AlgorithmFamily::MaxRects {
+        // Global best-first: place the best-scoring (item, position) pair each step; ties go to
+        // the item earlier in packing order.
+        loop {
+            let best = remaining
+                .iter()
+                .enumerate()
+                .filter_map(|(pos, &idx)| {
+                    let p = &prepared[idx];
+                    score_item(packer.as_mut(), p.rect, p.placement, cfg, force(idx))
+                        .map(|score| (score, pos))
+                })
+                .min();
+            let Some((_, pos)) = best else {
+                break;
+            };
+            if !place(packer.as_mut(), remaining[pos]) {
+                break;
+            }
+            remaining.remove(pos);
+        }
+    } else {
+        loop {
+            let mut placed_any = false;
+            let mut remove_set: HashSet<usize> = HashSet::new();
+            for &idx in remaining.iter() {
+                if place(packer.as_mut(), idx) {
+                    remove_set.insert(idx);
+                    placed_any = true;
+                }
+            }
+            if !placed_any {
+                break;
+            }
+            // Retain only indices not placed
+            if !remove_set.is_empty() {
+                remaining.retain(|i| !remove_set.contains(i));
+            }
+        }
+    }
+
+    if let Some(wm) = packer.waste_map_stats() {
+        tracing::debug!(
+            page = page_id,
+            free_rects = wm.free_rects,
+            recovered_area = wm.recovered_area,
+            placements = wm.placements,
+            occupancy = wm.occupancy(),
+            "skyline waste map"
+        );
+    }
+
+    // Compute final page size via helper to keep logic consistent across APIs
+    let (width, height) = page_size_for_extent(extent, cfg);
+    let page = Page {
+        id: page_id,
+        width,
+        height,
+        frames,
+    };
+    span.record("frames", page.frames.len());
+    if let Some(n) = packer.free_list_len() {
+        span.record("free_rects", n);
+    }
+    span.record("occupancy", page.stats().occupancy);
+    debug_verify_page(&page, cfg);
+    page
+}
+
+/// Copies the metadata of `p` onto its freshly packed frame.
+fn finish_frame(mut f: Frame, p: &Prep, cfg: &PackerConfig) -> Frame {
+    f.trimmed = p.trimmed;
+    f.source = p.source;
+    f.source_size = p.orig_size;
+    f.degenerate = p.degenerate;
+    f.id = stable_frame_id(&p.key);
+    f.pivot = cfg.pivot_for(&p.key, p.detected_pivot);
+    f.nine_patch = cfg.sprite(&p.key).and_then(|s| s.nine_patch);
+    f.scale = p.scale;
+    f.content_hash = p.content_hash;
+    f
+}
+
+fn debug_verify_page(page: &Page, cfg: &PackerConfig) {
+    if cfg!(debug_assertions) {
+        let issues = crate::verify::page_issues(page, cfg);
+        debug_assert!(
+            issues.is_empty(),
+            "page {} fails validation: {issues:?}",
+            page.id
+        );
+    }
+}
+
+/// `PageBalance::MultiBin`: places `items` (in packing order) on the open page whose packer
+/// scores each best, ties to the earliest page, opening a page only when none fits. With
+/// `early_open` an item also gets a fresh page while fewer than `max_pages` are open, when its
+/// best open page would grow by more area than that fresh page takes. `None` when an item fits
+/// no fresh page or the layout needs more than `max_pages` pages.
+fn multi_bin_pages(
+    prepared: &[Prep],
+    cfg: &PackerConfig,
+    forced_rotation: Option<&[bool]>,
+    items: &[usize],
+    first_id: usize,
+    max_pages: usize,
+    early_open: bool,
+) -> Option<Vec<Page>> {
+    let _span = tracing::debug_span!("multi_bin", items = items.len(), early_open).entered();
+    struct Bin {
+        packer: Box<dyn Packer<String>>,
+        items: Vec<usize>,
+        frames: Vec<Frame>,
+        extent: (u32, u32),
+    }
+    let force = |idx: usize| forced_rotation.is_some_and(|r| r[idx]);
+    let open = || Bin {
+        packer: new_packer(cfg),
+        items: Vec::new(),
+        frames: Vec::new(),
+        extent: (0, 0),
+    };
+    let put = |bin: &mut Bin, idx: usize| -> bool {
+        let p = &prepared[idx];
+        let Some((f, slot)) = place_item(
+            bin.packer.as_mut(),
+            &p.key,
+            p.rect,
+            p.placement,
+            cfg,
+            force(idx),
+        ) else {
+            return false;
+        };
+        bin.extent = (bin.extent.0.max(slot.0), bin.extent.1.max(slot.1));
+        bin.items.push(idx);
+        bin.frames.push(finish_frame(f, p, cfg));
+        true
+    };
+    let page_area = |extent: (u32, u32)| {
+        let (w, h) = page_size_for_extent(extent, cfg);
+        w as u64 * h as u64
+    };
+    let mut bins: Vec<Bin> = Vec::new();
+    for &idx in items {
+        let p = &prepared[idx];
+        let mut ranked: Vec<((i32, i32), usize)> = bins
+            .iter_mut()
+            .enumerate()
+            .filter_map(|(b, bin)| {
+                score_item(bin.packer.as_mut(), p.rect, p.placement, cfg, force(idx))
+                    .map(|score| (score, b))
+            })
+            .collect();
+        ranked.sort_unstable();
+        let mut placed = None;
+        for (_, b) in ranked {
+            let before = bins[b].extent;
+            if put(&mut bins[b], idx) {
+                placed = Some((b, before));
+                break;
+            }
+        }
+        let Some((b, before)) = placed else {
+            let mut bin = open();
+            if bins.len() == max_pages || !put(&mut bin, idx) {
+                return None;
+            }
+            bins.push(bin);
+            continue;
+        };
+        if early_open && bins.len() < max_pages {
+            let mut fresh = open();
+            if put(&mut fresh, idx)
+                && page_area(bins[b].extent).saturating_sub(page_area(before))
+                    > page_area(fresh.extent)
+            {
+                // Packers cannot undo a placement; replay the page without this item
+                let mut rebuilt = open();
+                let kept = &bins[b].items[..bins[b].items.len() - 1];
+                if kept.iter().all(|&i| put(&mut rebuilt, i)) {
+                    bins[b] = rebuilt;
+                    bins.push(fresh);
+                }
+            }
+        }
+    }
+    let pages: Vec<Page> = bins
+        .into_iter()
+        .enumerate()
+        .map(|(b, bin)| {
+            let (width, height) = page_size_for_extent(bin.extent, cfg);
+            Page {
+                id: first_id + b,
+                width,
+                height,
+                frames: bin.frames,
+            }
+        })
+        .collect();
+    pages.iter().for_each(|p| debug_verify_page(p, cfg));
+    Some(pages)
+}
+
+/// `PageBalance::Balanced`: redistributes the frames of one group's fill-first `pages` over the
+/// same number of pages. Frames are dealt largest-first to the page with the least slot area;
+/// when a dealt page overflows, only the last two pages are re-split evenly. Pages are replaced
+/// only when the result fits and its largest page is no bigger than before.
+fn balance_pages(
+    prepared: &[Prep],
+    cfg: &PackerConfig,
+    forced_rotation: Option<&[bool]>,
+    pages: &mut [Page],
+) {
+    let index: HashMap<&str, usize> = prepared
+        .iter()
+        .enumerate()
+        .map(|(i, p)| (p.key.as_str(), i))
+        .collect();
+    let items_of = |pages: &[Page]| -> Vec<usize> {
+        pages
+            .iter()
+            .flat_map(|p| p.frames.iter().map(|f| index[f.key.as_str()]))
+            .collect()
+    };
+    let first_id = pages[0].id;
+    let max_area = |pages: &[Page]| {
+        pages
+            .iter()
+            .map(|p| p.width as u64 * p.height as u64)
+            .max()
+            .unwrap_or(0)
+    };
+
+    let all = items_of(pages);
+    if let Some(balanced) = split_pages(prepared, cfg, forced_rotation, &all, pages.len(), first_id)
+        && max_area(&balanced) <= max_area(pages)
+    {
+        tracing::debug!(pages = pages.len(), "balanced page occupancy");
+        pages.clone_from_slice(&balanced);
+        return;
+    }
+
+    let n = pages.len();
+    let tail = items_of(&pages[n - 2..]);
+    if let Some(split) = split_pages(prepared, cfg, forced_rotation, &tail, 2, pages[n - 2].id)
+        && max_area(&split) <= max_area(&pages[n - 2..])
+    {
+        tracing::debug!("balanced last two pages");
+        pages[n - 2..].clone_from_slice(&split);
+    }
+}
+
+/// Deals `items` into `bins` pages (largest slot area first, each to the emptiest bin) and packs
+/// each bin on its own page; `None` when a bin does not fit on one page.
+fn split_pages(
+    prepared: &[Prep],
+    cfg: &PackerConfig,
+    forced_rotation: Option<&[bool]>,
+    items: &[usize],
+    bins: usize,
+    first_id: usize,
+) -> Option<Vec<Page>> {
+    let slot_area = |i: usize| {
+        let p = &prepared[i];
+        let pad = cfg.sprite_padding();
+        let grow = 2 * (p.placement.extra_padding + p.placement.extra_extrusion)
+            + 2 * cfg.texture_extrusion;
+        (p.rect.w + grow + pad.horizontal()) as u64 * (p.rect.h + grow + pad.vertical()) as u64
+    };
+    let mut by_size: Vec<(usize, usize)> = items.iter().copied().enumerate().collect();
+    by_size.sort_by_key(|&(pos, i)| (std::cmp::Reverse(slot_area(i)), pos));
+
+    let mut dealt: Vec<Vec<(usize, usize)>> = vec![Vec::new(); bins];
+    let mut load = vec![0u64; bins];
+    for (pos, i) in by_size {
+        let bin = (0..bins).min_by_key(|&b| (load[b], b)).unwrap_or(0);
+        load[bin] += slot_area(i);
+        dealt[bin].push((pos, i));
+    }
+
+    dealt
+        .into_iter()
+        .enumerate()
+        .map(|(b, mut bin)| {
+            // Keep the configured packing order within each page.
+            bin.sort_unstable();
+            let mut remaining: Vec<usize> = bin.into_iter().map(|(_, i)| i).collect();
+            let page = fill_page(prepared, cfg, &mut remaining, forced_rotation, first_id + b);
+            (remaining.is_empty() && !page.frames.is_empty()).then_some(page)
+        })
+        .collect()
+}
+
+/// `minimize_page_size`: shrinks a single-page layout by binary-searching smaller page widths,
+/// then heights (one side when `square`), re-packing in `order` at every candidate size.
+/// Returns the smallest layout found and a record of the search (`None` when it did not run).
+fn minimize_page(
+    prepared: &[Prep],
+    cfg: &PackerConfig,
+    order: &[usize],
+    forced_rotation: Option<&[bool]>,
+    pages: Vec<Page>,
+) -> (Vec<Page>, Option<PageMinimization>) {
+    if !cfg.minimize_page_size || cfg.page_size == PageSizeMode::Fixed || pages.len() != 1 {
+        return (pages, None);
+    }
+    let initial_size = (pages[0].width, pages[0].height);
+    let mut best = pages;
+    let mut attempts = 0u32;
+    let mut fits = |w: u32, h: u32| {
+        attempts += 1;
+        let mut trial = cfg.clone();
+        trial.max_width = w;
+        trial.max_height = h;
+        trial.max_pages = Some(1);
+        match layout_prepared(prepared, &trial, order, forced_rotation) {
+            Ok(pages) if pages.len() == 1 && pages[0].width <= w && pages[0].height <= h => {
+                best = pages;
+                true
+            }
+            _ => false,
+        }
+    };
+    let (w0, h0) = initial_size;
+    if cfg.square {
+        let min = cfg.min_width.max(cfg.min_height);
+        smallest_fitting(&page_size_candidates(cfg, min, w0.max(h0)), |s| fits(s, s));
+    } else {
+        let w = smallest_fitting(&page_size_candidates(cfg, cfg.min_width, w0), |w| {
+            fits(w, h0)
+        });
+        smallest_fitting(&page_size_candidates(cfg, cfg.min_height, h0), |h| {
+            fits(w, h)
+        });
+    }
+    tracing::debug!(
+        attempts,
+        from = ?initial_size,
+        to = ?(best[0].width, best[0].height),
+        "minimized page size"
+    );
+    (
+        best,
+        Some(PageMinimization {
+            attempts,
+            initial_size,
+        }),
+    )
+}
+
+/// Page sizes in `min..current` a page can take under `cfg` (allowed sizes, power of two or block
+/// multiples), ascending, followed by `current`.
+fn page_size_candidates(cfg: &PackerConfig, min: u32, current: u32) -> Vec<u32> {
+    let min = min.max(1);
+    let mut sizes: Vec<u32> = match &cfg.page_size {
+        PageSizeMode::AllowedList { sizes } => {
+            let mut s: Vec<u32> = sizes
+                .iter()
+                .copied()
+                .filter(|&v| v >= min && v < current)
+                .collect();
+            s.sort_unstable();
+            s.dedup();
+            s
+        }
+        PageSizeMode::Pow2 => (0..32)
+            .map(|i| 1u32 << i)
+            .filter(|&v| v >= min && v < current)
+            .collect(),
+        PageSizeMode::TightCrop { .. } | PageSizeMode::Fixed => {
+            let block = cfg.page_format.block_size();
+            (min..current).filter(|v| v % block == 0).collect()
+        }
+    };
+    sizes.push(current);
+    sizes
+}
+
+/// Smallest of the ascending `candidates` accepted by `fits`; the last one is known to fit.
+fn smallest_fitting(candidates: &[u32], mut fits: impl FnMut(u32) -> bool) -> u32 {
+    let (mut lo, mut hi) = (0, candidates.len() - 1);
+    while lo < hi {
+        let mid = (lo + hi) / 2;
+        if fits(candidates[mid]) {
+            hi = mid;
+        } else {
+            lo = mid + 1;
+        }
+    }
+    candidates[hi]
+}
+
+/// Composites laid-out pages into RGBA canvases and assembles the output.
+fn compose_pages(
+    prepared: &[Prep],
+    cfg: &PackerConfig,
+    atlas_pages: Vec<Page>,
+    ctl: &PackControl,
+) -> Result<PackOutput> {
+    let _span = tracing::debug_span!("composite", pages = atlas_pages.len()).entered();
+    // Map for quick lookup during compositing
+    let prep_map: HashMap<&str, &Prep> = prepared.iter().map(|p| (p.key.as_str(), p)).collect();
+    let done = AtomicUsize::new(0);
+    // Rayon workers do not inherit the current span, so page spans name their parent
+    let parent = tracing::Span::current();
+    let compose = |page: &Page| -> Result<OutputPage> {
+        ctl.check()?;
+        let _span = tracing::debug_span!(
+            parent: &parent,
+            "composite_page",
+            page = page.id,
+            frames = page.frames.len(),
+            size = ?(page.width, page.height),
+        )
+        .entered();
+        let out = compose_page(page, &prep_map, cfg)?;
+        let n = done.fetch_add(1, AtomicOrdering::Relaxed) + 1;
+        ctl.report(PackPhase::Compose, n, atlas_pages.len());
+        Ok(out)
+    };
+
+    // Pages are independent, so they composite in parallel when enabled
+    #[cfg(all(feature = "parallel", not(target_arch = "wasm32")))]
+    let pages: Vec<OutputPage> = if cfg.parallel {
+        atlas_pages.par_iter().map(compose).collect::<Result<_>>()?
+    } else {
+        atlas_pages.iter().map(compose).collect::<Result<_>>()?
+    };
+    #[cfg(not(all(feature = "parallel", not(target_arch = "wasm32"))))]
+    let pages: Vec<OutputPage> = atlas_pages.iter().map(compose).collect::<Result<_>>()?;
+
+    let atlas = Atlas {
+        pages: atlas_pages,
+        meta: image_meta(cfg),
+    };
+    Ok(PackOutput {
+        atlas,
+        pages,
+        timings: PackTimings::default(),
+        skipped: Vec::new(),
+        scaled: Vec::new(),
+    })
+}
+
+/// Pages with at least this many frames also blit their frames in parallel.
+#[cfg(all(feature = "parallel", not(target_arch = "wasm32")))]
+const PARALLEL_BLIT_MIN_FRAMES: usize = 64;
+
+fn compose_page(
+    page: &Page,
+    prep_map: &HashMap<&str, &Prep>,
+    cfg: &PackerConfig,
+) -> Result<OutputPage> {
+    let mut out = composite_page(page, prep_map, cfg)?;
+    postprocess_page(&mut out, cfg)?;
+    Ok(out)
+}
+
+fn composite_page(
+    page: &Page,
+    prep_map: &HashMap<&str, &Prep>,
+    cfg: &PackerConfig,
+) -> Result<OutputPage> {
+    let frames: Vec<(&Frame, &Prep)> = page
+        .frames
+        .iter()
+        .filter_map(|f| prep_map.get(f.key.as_str()).map(|prep| (f, *prep)))
+        .collect();
+    let (rgba, scratch) = if cfg.low_memory {
+        // One frame-sized tile in RAM at a time (per thread); the page itself lives in a
+        // memory-mapped scratch file.
+        let scratch = ScratchImage::new(page.width, page.height)?;
+        if let Some(bg) = cfg.background_color {
+            scratch.fill(bg)?;
+        }
+        let put = |f: &Frame, prep: &Prep| {
+            let (x, y, mut tile) = frame_tile(prep, f, cfg);
+            if let Some(bg) = cfg.background_color {
+                crate::compositing::matte(&mut tile, bg);
+            }
+            scratch.write_tile(x, y, &tile)
+        };
+        #[cfg(all(feature = "parallel", not(target_arch = "wasm32")))]
+        if cfg.parallel && frames.len() >= PARALLEL_BLIT_MIN_FRAMES {
+            frames.par_iter().try_for_each(|(f, prep)| put(f, prep))?;
+        } else {
+            for (f, prep) in &frames {
+                put(f, prep)?;
+            }
+        }
+        #[cfg(not(all(feature = "parallel", not(target_arch = "wasm32"))))]
+        for (f, prep) in &frames {
+            put(f, prep)?;
+        }
+        (RgbaImage::new(0, 0), Some(scratch))
+    } else {
+        let mut canvas = RgbaImage::new(page.width, page.height);
+        #[cfg(all(feature = "parallel", not(target_arch = "wasm32")))]
+        if cfg.parallel && frames.len() >= PARALLEL_BLIT_MIN_FRAMES {
+            let tiles: Vec<(u32, u32, RgbaImage)> = frames
+                .par_iter()
+                .map(|(f, prep)| frame_tile(prep, f, cfg))
+                .collect();
+            for (x, y, tile) in tiles {
+                image::imageops::replace(&mut canvas, &tile, x as i64, y as i64);
+            }
+            if let Some(bg) = cfg.background_color {
+                crate::compositing::matte(&mut canvas, bg);
+            }
+            return Ok(OutputPage {
+                page: page.clone(),
+                rgba: canvas,
+                scratch: None,
+            });
+        }
+        for (f, prep) in frames {
+            blit_frame(&mut canvas, prep, f, cfg);
+        }
+        if let Some(bg) = cfg.background_color {
+            crate::compositing::matte(&mut canvas, bg);
+        }
+        (canvas, None)
+    };
+    Ok(OutputPage {
+        page: page.clone(),
+        rgba,
+        scratch,
+    })
+}
+
+/// Runs `cfg.page_postprocess` on a composited page, in memory or in its scratch file.
+fn postprocess_page(out: &mut OutputPage, cfg: &PackerConfig) -> Result<()> {
+    let Some(hook) = &cfg.page_postprocess else {
+        return Ok(());
+    };
+    let size = (out.page.width, out.page.height);
+    let mut scratch_pixels = match &out.scratch {
+        Some(s) => Some(s.to_rgba()?),
+        None => None,
+    };
+    let rgba = scratch_pixels.as_mut().unwrap_or(&mut out.rgba);
+    hook.apply(rgba, &out.page);
+    if rgba.dimensions() != size {
+        return Err(TexPackerError::InvalidConfig(format!(
+            "page_postprocess resized page {} from {}x{} to {}x{}",
+            out.page.id,
+            size.0,
+            size.1,
+            rgba.width(),
+            rgba.height()
+        )));
+    }
+    if let (Some(s), Some(rgba)) = (&out.scratch, &scratch_pixels) {
+        s.write_tile(0, 0, rgba)?;
+    }
+    Ok(())
+}
+
+/// Composites one frame (with extrusion and alpha bleed) into its own tile, returning the
+/// tile's page position. Slots never overlap, so copying tiles onto a page gives exactly the
+/// pixels of blitting every frame onto a shared canvas.
+fn frame_tile(prep: &Prep, f: &Frame, cfg: &PackerConfig) -> (u32, u32, RgbaImage) {
+    let e = prep.extrusion(cfg);
+    let (x0, y0) = (f.frame.x.saturating_sub(e), f.frame.y.saturating_sub(e));
+    let mut tile = RgbaImage::new(
+        f.frame.x + f.frame.w + e - x0,
+        f.frame.y + f.frame.h + e - y0,
+    );
+    let local = Frame {
+        frame: Rect::new(f.frame.x - x0, f.frame.y - y0, f.frame.w, f.frame.h),
+        ..f.clone()
+    };
+    blit_frame(&mut tile, prep, &local, cfg);
+    (x0, y0, tile)
+}
+
+/// Copies one placed frame's pixels (plus extrusion and alpha bleed) onto its page canvas.
+fn blit_frame(canvas: &mut RgbaImage, prep: &Prep, f: &Frame, cfg: &PackerConfig) {
+    // Zero-sized sources only reserve a placeholder slot; there are no pixels to copy.
+    if prep.source.w == 0 || prep.source.h == 0 {
+        return;
+    }
+    crate::compositing::blit_rgba(
+        &prep.rgba,
+        canvas,
+        f.frame.x,
+        f.frame.y,
+        prep.source.x,
+        prep.source.y,
+        prep.source.w,
+        prep.source.h,
+        f.rotated.then_some(cfg.rotation_direction),
+        prep.extrusion(cfg),
+        cfg.extrude_mode_for(&prep.key),
+        cfg.texture_outlines,
+    );
+    if let Some(radius) = cfg.alpha_bleed {
+        let e = prep.extrusion(cfg);
+        crate::compositing::alpha_bleed(
+            canvas,
+            f.frame.x.saturating_sub(e),
+            f.frame.y.saturating_sub(e),
+            f.frame.w + 2 * e,
+            f.frame.h + 2 * e,
+            radius,
+        );
+    }
+}
+
+/// Metadata for image-producing packs.
+fn image_meta(cfg: &PackerConfig) -> Meta {
+    Meta {
         schema_version: "1".into(),
         app: "tex-packer".into(),
         version: env!("CARGO_PKG_VERSION").into(),
         format: "RGBA8888".into(),
         scale: 1.0,
-        power_of_two: cfg.power_of_two,
+        power_of_two: cfg.power_of_two_pages(),
         square: cfg.square,
         max_dim: (cfg.max_width, cfg.max_height),
-        padding: (cfg.border_padding, cfg.texture_padding),
+        padding: (cfg.border_padding, cfg.meta_padding()),
         extrude: cfg.texture_extrusion,
         allow_rotation: cfg.allow_rotation,
+        rotation_direction: cfg.rotation_direction,
         trim_mode: if cfg.trim { "trim" } else { "none" }.into(),
-        background_color: None,
-    };
-    let atlas = Atlas {
-        pages: atlas_pages,
-        meta,
-    };
-    Ok(PackOutput { atlas, pages })
+        background_color: cfg.background_color,
+        packer: None,
+        page_minimization: None,
+        auto_refinement: None,
+        align: cfg.frame_align(),
+        animations: cfg.animations.clone(),
+        sdf: cfg.sdf,
+        mip: cfg.mip_info(),
+    }
 }
 
-fn pack_auto(prepared: &[Prep], base: PackerConfig) -> Result<PackOutput> {
+fn plan_auto(prepared: &[Prep], base: PackerConfig, ctl: &PackControl) -> Result<Plan> {
     let mut candidates: Vec<PackerConfig> = Vec::new();
     let n_inputs = prepared.len();
     let budget_ms = base.time_budget_ms.unwrap_or(0);
@@ -363,6 +2372,13 @@ fn pack_auto(prepared: &[Prep], base: PackerConfig) -> Result<PackOutput> {
     let enable_mr_ref = matches!(base.auto_mode, AutoMode::Quality)
         && (budget_ms >= thr_time || n_inputs >= thr_inputs);
     match base.auto_mode {
+        _ if !base.auto_candidates.is_empty() => {
+            candidates = base
+                .auto_candidates
+                .iter()
+                .map(|c| c.apply(&base))
+                .collect();
+        }
         AutoMode::Fast => {
             let mut s_bl = base.clone();
             s_bl.family = AlgorithmFamily::Skyline;
@@ -379,6 +2395,10 @@ fn pack_auto(prepared: &[Prep], base: PackerConfig) -> Result<PackOutput> {
             s_mw.family = AlgorithmFamily::Skyline;
             s_mw.skyline_heuristic = crate::config::SkylineHeuristic::MinWaste;
             candidates.push(s_mw);
+            let mut s_mw_wm = base.clone();
+            s_mw_wm.family = AlgorithmFamily::Skyline;
+            s_mw_wm.skyline_heuristic = crate::config::SkylineHeuristic::MinWasteWm;
+            candidates.push(s_mw_wm);
             let mut mr_baf = base.clone();
             mr_baf.family = AlgorithmFamily::MaxRects;
             mr_baf.mr_heuristic = crate::config::MaxRectsHeuristic::BestAreaFit;
@@ -398,215 +2418,479 @@ fn pack_auto(prepared: &[Prep], base: PackerConfig) -> Result<PackOutput> {
             g.family = AlgorithmFamily::Guillotine;
             g.g_choice = crate::config::GuillotineChoice::BestAreaFit;
             g.g_split = crate::config::GuillotineSplit::SplitShorterLeftoverAxis;
+            g.g_merge = true;
             candidates.push(g);
+            // Decreasing-height shelves: cheap, and hard to beat on uniform-height sets.
+            let mut shelf = base.clone();
+            shelf.family = AlgorithmFamily::Shelf;
+            shelf.shelf_heuristic = crate::config::ShelfHeuristic::BestHeightFit;
+            shelf.sort_order = SortOrder::HeightDesc;
+            candidates.push(shelf);
         }
+        AutoMode::Exhaustive => candidates = exhaustive_candidates(&base),
+        AutoMode::Anneal => return plan_anneal(prepared, &base, ctl),
     }
+    let span = tracing::debug_span!(
+        "auto",
+        mode = ?base.auto_mode,
+        candidates = candidates.len(),
+        winner = Empty,
+    );
+    let _entered = span.enter();
     let start = Instant::now();
+    // `deterministic` trades the time budget for reproducible results; cancellation stops
+    // further candidates the same way and is reported once the started ones finish.
+    let over_budget = || {
+        ctl.is_cancelled()
+            || (!base.deterministic
+                && budget_ms > 0
+                && start.elapsed().as_millis() as u64 > budget_ms)
+    };
+    let done = AtomicUsize::new(0);
+    let evaluate = |idx: usize, cand: &PackerConfig| {
+        let choice = PackerChoice::from_config(cand);
+        let cand_span = tracing::debug_span!(
+            parent: &span,
+            "auto_candidate",
+            idx,
+            family = ?choice.family,
+            heuristic = %choice.heuristic,
+            sort_order = ?choice.sort_order,
+            pages = Empty,
+            area = Empty,
+        )
+        .entered();
+        let outcome = layout_sorted(prepared, cand);
+        match &outcome {
+            Ok(pages) => {
+                cand_span.record("pages", pages.len());
+                cand_span.record("area", pages_area(pages));
+            }
+            Err(e) => tracing::debug!(error = %e, "candidate failed"),
+        }
+        let n = done.fetch_add(1, AtomicOrdering::Relaxed) + 1;
+        ctl.report(PackPhase::Layout, n, candidates.len());
+        outcome
+    };
+    // Surfaced when every candidate fails (e.g. `TooManyPages`)
+    let mut last_err = None;
+    // Selection key: `auto_rank`, then portfolio position. Outcomes are always considered in
+    // candidate order, so sequential and parallel runs pick the same winner.
+    // Candidates are only laid out; the winner is composited once at the end.
+    let mut best: Option<(Rank, usize, Vec<Page>)> = None;
+    let mut consider = |idx: usize, outcome: Result<Vec<Page>>| match outcome {
+        Ok(pages) => {
+            let rank = auto_rank(&pages, &base);
+            if best
+                .as_ref()
+                .is_none_or(|(b, _, _)| rank_cmp(&rank, b).is_lt())
+            {
+                best = Some((rank, idx, pages));
+            }
+        }
+        Err(e) => last_err = Some(e),
+    };
 
-    // Parallel path (optional)
-    #[cfg(feature = "parallel")]
-    {
-        if base.parallel {
-            let results: Vec<(PackOutput, u64, u32)> = candidates
-                .par_iter()
-                .filter_map(|cand| pack_prepared(prepared, cand).ok())
-                .map(|out| {
-                    let pages = out.atlas.pages.len() as u32;
-                    let total_area: u64 = out
-                        .atlas
-                        .pages
-                        .iter()
-                        .map(|p| (p.width as u64) * (p.height as u64))
-                        .sum();
-                    (out, total_area, pages)
-                })
-                .collect();
-            let best = results.into_iter().min_by(|a, b| match a.2.cmp(&b.2) {
-                // pages asc
-                std::cmp::Ordering::Equal => a.1.cmp(&b.1),
-                other => other,
-            });
-            return best.map(|x| x.0).ok_or(TexPackerError::OutOfSpaceGeneric {
-                placed: 0,
-                total: prepared.len(),
-            });
+    // Parallel path (optional). Candidates that would start after the budget is spent are skipped.
+    #[cfg(all(feature = "parallel", not(target_arch = "wasm32")))]
+    let evaluated_in_parallel = if base.parallel {
+        let outcomes: Vec<Option<Result<Vec<Page>>>> = candidates
+            .par_iter()
+            .enumerate()
+            .map(|(idx, cand)| (!over_budget()).then(|| evaluate(idx, cand)))
+            .collect();
+        for (idx, outcome) in outcomes.into_iter().enumerate() {
+            if let Some(outcome) = outcome {
+                consider(idx, outcome);
+            }
         }
-    }
+        true
+    } else {
+        false
+    };
+    #[cfg(not(all(feature = "parallel", not(target_arch = "wasm32"))))]
+    let evaluated_in_parallel = false;
 
     // Sequential path with optional time budget
-    let mut best: Option<(PackOutput, u64, u32)> = None; // (output, total_area, pages)
-    for cand in candidates.into_iter() {
-        if budget_ms > 0 && start.elapsed().as_millis() as u64 > budget_ms {
-            break;
-        }
-        if let Ok(out) = pack_prepared(prepared, &cand) {
-            let pages = out.atlas.pages.len() as u32;
-            let total_area: u64 = out
-                .atlas
-                .pages
-                .iter()
-                .map(|p| (p.width as u64) * (p.height as u64))
-                .sum();
-            match &mut best {
-                None => best = Some((out, total_area, pages)),
-                Some((bo, barea, bpages)) => {
-                    if pages < *bpages || (pages == *bpages && total_area < *barea) {
-                        *bo = out;
-                        *barea = total_area;
-                        *bpages = pages;
-                    }
-                }
+    if !evaluated_in_parallel {
+        for (idx, cand) in candidates.iter().enumerate() {
+            if over_budget() {
+                break;
             }
+            consider(idx, evaluate(idx, cand));
         }
     }
-    best.map(|x| x.0).ok_or(TexPackerError::OutOfSpaceGeneric {
-        placed: 0,
-        total: prepared.len(),
-    })
-}
+    ctl.check()?;
 
-// ---------------- Layout-only API ----------------
+    match best {
+        Some((rank, idx, pages)) => {
+            span.record("winner", idx);
+            let refine_start = Instant::now();
+            let (winner, pages, refinement) = if base.auto_refine {
+                let (winner, pages, refinement) = refine_winner(
+                    prepared,
+                    &base,
+                    candidates[idx].clone(),
+                    pages,
+                    rank,
+                    &over_budget,
+                );
+                ctl.check()?;
+                (winner, pages, Some(refinement))
+            } else {
+                (candidates[idx].clone(), pages, None)
+            };
+            let refine_ms = refine_start.elapsed().as_millis() as u64;
+            let order = packing_order(prepared, &winner.sort_order);
+            let (pages, minimization) = minimize_page(prepared, &winner, &order, None, pages);
+            Ok(Plan {
+                pages,
+                packer: Some(PackerChoice::from_config(&winner)),
+                cfg: winner,
+                minimization,
+                refinement,
+                refine_ms,
+            })
+        }
+        None => Err(last_err.unwrap_or(TexPackerError::OutOfSpaceGeneric {
+            placed: 0,
+            total: prepared.len(),
+        })),
+    }
+}
 
-/// Packs sizes into pages without compositing pixel data.
-/// Inputs are (key, width, height). Returns an Atlas with pages and frames; no RGBA pages.
-pub fn pack_layout<K: Into<String>>(
-    inputs: Vec<(K, u32, u32)>,
-    cfg: PackerConfig,
-) -> Result<Atlas<String>> {
-    // Validate configuration first
-    cfg.validate()?;
+/// `PackerConfig::auto_refine`: first-improvement hill climbing from the portfolio winner over
+/// [`refine_variants`], ranked like the portfolio. Stops when no variant of the current best
+/// improves on it or `over_budget` trips.
+fn refine_winner(
+    prepared: &[Prep],
+    base: &PackerConfig,
+    mut best: PackerConfig,
+    mut pages: Vec<Page>,
+    mut rank: Rank,
+    over_budget: &dyn Fn() -> bool,
+) -> (PackerConfig, Vec<Page>, AutoRefinement) {
+    let span = tracing::debug_span!("auto_refine", variants = Empty, improvements = Empty);
+    let _entered = span.enter();
+    let mut report = AutoRefinement {
+        initial: PackerChoice::from_config(&best),
+        variants: 0,
+        improvements: 0,
+        budget_exhausted: false,
+    };
+    let mut seen = vec![report.initial.clone()];
+    'climb: loop {
+        for cand in refine_variants(&best, base) {
+            let choice = PackerChoice::from_config(&cand);
+            if seen.contains(&choice) {
+                continue;
+            }
+            if over_budget() {
+                report.budget_exhausted = true;
+                break 'climb;
+            }
+            seen.push(choice);
+            report.variants += 1;
+            let Ok(candidate_pages) = layout_sorted(prepared, &cand) else {
+                continue;
+            };
+            let candidate_rank = auto_rank(&candidate_pages, base);
+            if rank_cmp(&candidate_rank, &rank).is_lt() {
+                (best, pages, rank) = (cand, candidate_pages, candidate_rank);
+                report.improvements += 1;
+                continue 'climb;
+            }
+        }
+        break;
+    }
+    span.record("variants", report.variants);
+    span.record("improvements", report.improvements);
+    (best, pages, report)
+}
 
-    if inputs.is_empty() {
-        return Err(TexPackerError::Empty);
+/// Neighbours of `cfg` tried by [`refine_winner`]: every other sort order, rotation toggled
+/// (only when `base` allows it) and, for MaxRects, global insertion and reference splits toggled.
+fn refine_variants(cfg: &PackerConfig, base: &PackerConfig) -> Vec<PackerConfig> {
+    let mut out = Vec::new();
+    for sort in [
+        SortOrder::AreaDesc,
+        SortOrder::MaxSideDesc,
+        SortOrder::HeightDesc,
+        SortOrder::WidthDesc,
+        SortOrder::NameAsc,
+        SortOrder::None,
+    ] {
+        if sort != cfg.sort_order {
+            let mut c = cfg.clone();
+            c.sort_order = sort;
+            out.push(c);
+        }
     }
-    // Build lightweight preps
-    struct PrepL {
-        key: String,
-        rect: Rect,
-        trimmed: bool,
-        source: Rect,
-        orig_size: (u32, u32),
+    if base.allow_rotation {
+        let mut c = cfg.clone();
+        c.allow_rotation = !c.allow_rotation;
+        out.push(c);
     }
-    let mut prepared: Vec<PrepL> = inputs
-        .into_iter()
-        .map(|(k, w, h)| {
-            let key = k.into();
-            let rect = Rect::new(0, 0, w, h);
-            let source = Rect::new(0, 0, w, h);
-            PrepL {
-                key,
-                rect,
-                trimmed: false,
-                source,
-                orig_size: (w, h),
-            }
-        })
+    if cfg.family == AlgorithmFamily::MaxRects {
+        let mut c = cfg.clone();
+        c.mr_global = !c.mr_global;
+        out.push(c);
+        let mut c = cfg.clone();
+        c.mr_reference = !c.mr_reference;
+        out.push(c);
+    }
+    out
+}
+
+/// `AutoMode::Anneal`: simulated annealing over packing order and per-item rotation for
+/// MaxRects, minimizing total page area. The search starts from the plain MaxRects layout
+/// (rotations as the packer chose them), so the result is never worse than that layout.
+fn plan_anneal(prepared: &[Prep], base: &PackerConfig, ctl: &PackControl) -> Result<Plan> {
+    use crate::optimizer::{AnnealSettings, Arrangement, anneal};
+
+    let _span = tracing::debug_span!("anneal", items = prepared.len()).entered();
+    let mut cfg = base.clone();
+    cfg.family = AlgorithmFamily::MaxRects;
+    cfg.mr_reference = true;
+    let order = packing_order(prepared, &cfg.sort_order);
+    let seed_pages = layout_prepared(prepared, &cfg, &order, None)?;
+
+    let index: HashMap<&str, usize> = prepared
+        .iter()
+        .enumerate()
+        .map(|(i, p)| (p.key.as_str(), i))
         .collect();
-    // Sort like pack_images
-    match cfg.sort_order {
-        SortOrder::None => {}
-        SortOrder::NameAsc => prepared.sort_by(|a, b| a.key.cmp(&b.key)),
-        SortOrder::AreaDesc => prepared.sort_by(|a, b| {
-            (b.rect.w * b.rect.h)
-                .cmp(&(a.rect.w * a.rect.h))
-                .then_with(|| a.key.cmp(&b.key))
-        }),
-        SortOrder::MaxSideDesc => prepared.sort_by(|a, b| {
-            b.rect
-                .w
-                .max(b.rect.h)
-                .cmp(&a.rect.w.max(a.rect.h))
-                .then_with(|| a.key.cmp(&b.key))
-        }),
-        SortOrder::HeightDesc => {
-            prepared.sort_by(|a, b| b.rect.h.cmp(&a.rect.h).then_with(|| a.key.cmp(&b.key)))
-        }
-        SortOrder::WidthDesc => {
-            prepared.sort_by(|a, b| b.rect.w.cmp(&a.rect.w).then_with(|| a.key.cmp(&b.key)))
+    let mut rotated = vec![false; prepared.len()];
+    for f in seed_pages.iter().flat_map(|p| &p.frames) {
+        if f.rotated {
+            rotated[index[f.key.as_str()]] = true;
         }
     }
+    let rotatable: Vec<bool> = prepared
+        .iter()
+        .map(|p| base.allow_rotation && p.rect.w != p.rect.h)
+        .collect();
 
-    let mut remaining: Vec<usize> = (0..prepared.len()).collect();
-    let mut atlas_pages: Vec<Page> = Vec::new();
-    let mut page_id = 0usize;
-    while !remaining.is_empty() {
-        let mut packer: Box<dyn Packer<String>> = match cfg.family {
-            AlgorithmFamily::Skyline => Box::new(SkylinePacker::new(cfg.clone())),
-            AlgorithmFamily::MaxRects => {
-                Box::new(MaxRectsPacker::new(cfg.clone(), cfg.mr_heuristic.clone()))
-            }
-            AlgorithmFamily::Guillotine => Box::new(GuillotinePacker::new(
-                cfg.clone(),
-                cfg.g_choice.clone(),
-                cfg.g_split.clone(),
-            )),
-            AlgorithmFamily::Auto => unreachable!(),
-        };
-        let mut frames: Vec<Frame> = Vec::new();
-        loop {
-            let mut placed_any = false;
-            let mut remove_set: HashSet<usize> = HashSet::new();
-            for &idx in &remaining {
-                let p = &prepared[idx];
-                if !packer.can_pack(&p.rect) {
-                    continue;
-                }
-                if let Some(mut f) = packer.pack(p.key.clone(), &p.rect) {
-                    f.trimmed = p.trimmed;
-                    f.source = p.source;
-                    f.source_size = p.orig_size;
-                    frames.push(f);
-                    remove_set.insert(idx);
-                    placed_any = true;
-                }
-            }
-            if !placed_any {
-                break;
-            }
-            if !remove_set.is_empty() {
-                remaining.retain(|i| !remove_set.contains(i));
+    // Rotation is decided by the arrangement, not by the packer
+    let mut fixed = cfg.clone();
+    fixed.allow_rotation = false;
+    let settings = AnnealSettings {
+        time_budget_ms: if base.deterministic {
+            0
+        } else {
+            base.time_budget_ms.unwrap_or(0)
+        },
+        ..Default::default()
+    };
+    let seed_area = pages_area(&seed_pages);
+    let result = anneal(
+        Arrangement {
+            order: order.clone(),
+            rotated,
+        },
+        &rotatable,
+        &settings,
+        |a| {
+            // Once cancelled, remaining steps are rejected without laying anything out.
+            if ctl.is_cancelled() {
+                return None;
             }
+            layout_prepared(prepared, &fixed, &a.order, Some(&a.rotated))
+                .ok()
+                .map(|pages| pages_area(&pages))
+        },
+    );
+    ctl.check()?;
+    ctl.report(PackPhase::Layout, 1, 1);
+    let (pages, minimization) = match result {
+        Some(r) if r.cost < seed_area => {
+            tracing::debug!(
+                iterations = r.iterations,
+                improvements = r.improvements,
+                from = seed_area,
+                to = r.cost,
+                "anneal improved total page area"
+            );
+            let rotated = Some(r.best.rotated.as_slice());
+            let pages = layout_prepared(prepared, &fixed, &r.best.order, rotated)?;
+            minimize_page(prepared, &fixed, &r.best.order, rotated, pages)
         }
-        if frames.is_empty() {
-            let placed = prepared.len() - remaining.len();
-            return Err(TexPackerError::OutOfSpaceGeneric {
-                placed,
-                total: prepared.len(),
-            });
-        }
+        _ => minimize_page(prepared, &cfg, &order, None, seed_pages),
+    };
+
+    let mut choice = PackerChoice::from_config(&cfg);
+    choice.heuristic.push_str("+anneal");
+    Ok(Plan {
+        pages,
+        cfg,
+        packer: Some(choice),
+        minimization,
+        refinement: None,
+        refine_ms: 0,
+    })
+}
 
-        // Compute page size same as pack_prepared
-        let (page_w, page_h) = compute_page_size(&frames, &cfg);
+type Rank = [f64; 3];
 
-        let page = Page {
-            id: page_id,
-            width: page_w,
-            height: page_h,
-            frames: frames.clone(),
-        };
-        atlas_pages.push(page);
-        page_id += 1;
+/// Rank of an `Auto` candidate under `cfg.auto_score` or `cfg.auto_objective`, compared
+/// lexicographically (lower wins); the objective comes first, pages and area break ties.
+fn auto_rank(pages: &[Page], cfg: &PackerConfig) -> Rank {
+    let n = pages.len() as f64;
+    let area = pages_area(pages) as f64;
+    if let Some(score) = &cfg.auto_score {
+        return [score.score(pages), n, area];
+    }
+    let used: u64 = pages.iter().map(|p| p.stats().used_frame_area).sum();
+    let occupancy = if area > 0.0 { used as f64 / area } else { 0.0 };
+    let max_side = pages
+        .iter()
+        .map(|p| p.width.max(p.height))
+        .max()
+        .unwrap_or(0) as f64;
+    match cfg.auto_objective {
+        AutoObjective::Pages => [n, area, 0.0],
+        AutoObjective::Area => [area, n, 0.0],
+        AutoObjective::Occupancy => [-occupancy, n, area],
+        AutoObjective::MaxDimension => [max_side, n, area],
+        AutoObjective::Weighted {
+            pages: wp,
+            area: wa,
+            max_dimension: wd,
+            occupancy: wo,
+        } => {
+            let page_area = cfg.max_width as f64 * cfg.max_height as f64;
+            let limit = cfg.max_width.max(cfg.max_height) as f64;
+            let score = wp * n + wa * area / page_area + wd * max_side / limit - wo * occupancy;
+            [score, n, area]
+        }
     }
+}
 
-    let meta = Meta {
-        schema_version: "1".into(),
-        app: "tex-packer".into(),
-        version: env!("CARGO_PKG_VERSION").into(),
-        format: "RGBA8888".into(),
-        scale: 1.0,
-        power_of_two: cfg.power_of_two,
-        square: cfg.square,
-        max_dim: (cfg.max_width, cfg.max_height),
-        padding: (cfg.border_padding, cfg.texture_padding),
-        extrude: cfg.texture_extrusion,
-        allow_rotation: cfg.allow_rotation,
-        trim_mode: if cfg.trim { "trim" } else { "none" }.into(),
-        background_color: None,
+fn rank_cmp(a: &Rank, b: &Rank) -> std::cmp::Ordering {
+    a.iter()
+        .zip(b)
+        .map(|(x, y)| x.total_cmp(y))
+        .find(|o| o.is_ne())
+        .unwrap_or(std::cmp::Ordering::Equal)
+}
+
+fn pages_area(pages: &[Page]) -> u64 {
+    pages
+        .iter()
+        .map(|p| (p.width as u64) * (p.height as u64))
+        .sum()
+}
+
+/// Exhaustive portfolio: every family x heuristic x sort order, with rotation on and off
+/// (only off when the base config forbids rotation). Likely-strong candidates come first so a
+/// time budget still covers them.
+fn exhaustive_candidates(base: &PackerConfig) -> Vec<PackerConfig> {
+    use crate::config::{
+        GuillotineChoice as GC, GuillotineSplit as GS, MaxRectsHeuristic as MR,
+        ShelfHeuristic as SF, SkylineHeuristic as SH,
     };
-    Ok(Atlas {
-        pages: atlas_pages,
-        meta,
-    })
+    let sorts = [
+        SortOrder::AreaDesc,
+        SortOrder::MaxSideDesc,
+        SortOrder::HeightDesc,
+        SortOrder::WidthDesc,
+        SortOrder::NameAsc,
+        SortOrder::None,
+    ];
+    let rotations: &[bool] = if base.allow_rotation {
+        &[true, false]
+    } else {
+        &[false]
+    };
+    let mut algos: Vec<PackerConfig> = Vec::new();
+    for h in [
+        MR::BestAreaFit,
+        MR::BestShortSideFit,
+        MR::BestLongSideFit,
+        MR::BottomLeft,
+        MR::ContactPoint,
+    ] {
+        let mut c = base.clone();
+        c.family = AlgorithmFamily::MaxRects;
+        c.mr_heuristic = h;
+        c.mr_reference = true;
+        algos.push(c);
+    }
+    for h in [
+        SH::MinWaste,
+        SH::BottomLeft,
+        SH::MinWasteWm,
+        SH::BottomLeftWm,
+    ] {
+        let mut c = base.clone();
+        c.family = AlgorithmFamily::Skyline;
+        c.skyline_heuristic = h;
+        algos.push(c);
+    }
+    for h in [SF::NextFit, SF::FirstFit, SF::BestHeightFit] {
+        let mut c = base.clone();
+        c.family = AlgorithmFamily::Shelf;
+        c.shelf_heuristic = h;
+        algos.push(c);
+    }
+    for choice in [
+        GC::BestAreaFit,
+        GC::BestShortSideFit,
+        GC::BestLongSideFit,
+        GC::WorstAreaFit,
+        GC::WorstShortSideFit,
+        GC::WorstLongSideFit,
+    ] {
+        for split in [
+            GS::SplitShorterLeftoverAxis,
+            GS::SplitLongerLeftoverAxis,
+            GS::SplitMinimizeArea,
+            GS::SplitMaximizeArea,
+            GS::SplitShorterAxis,
+            GS::SplitLongerAxis,
+        ] {
+            let mut c = base.clone();
+            c.family = AlgorithmFamily::Guillotine;
+            c.g_choice = choice.clone();
+            c.g_split = split;
+            algos.push(c);
+        }
+    }
+    let mut out = Vec::with_capacity(sorts.len() * rotations.len() * algos.len());
+    for sort in &sorts {
+        for &rot in rotations {
+            for a in &algos {
+                let mut c = a.clone();
+                c.sort_order = sort.clone();
+                c.allow_rotation = rot;
+                out.push(c);
+            }
+        }
+    }
+    out
+}
+
+// ---------------- Layout-only API ----------------
+
+/// Packs sizes into pages without compositing pixel data.
+/// Inputs are (key, width, height). Returns an Atlas with pages and frames; no RGBA pages.
+#[instrument(skip_all, fields(inputs = inputs.len(), family = ?cfg.family))]
+pub fn pack_layout<K: Into<String>>(
+    inputs: Vec<(K, u32, u32)>,
+    cfg: PackerConfig,
+) -> Result<Atlas<String>> {
+    let items = inputs
+        .into_iter()
+        .map(|(key, w, h)| LayoutItem {
+            key,
+            w,
+            h,
+            source: None,
+            source_size: None,
+            trimmed: false,
+        })
+        .collect();
+    pack_layout_items(items, cfg)
 }
 
 /// Layout-only item with optional source/source_size to propagate trimming metadata.
@@ -620,164 +2904,194 @@ pub struct LayoutItem<K = String> {
     pub trimmed: bool,
 }
 
+/// Result of [`pack_layout_items_with`]: the atlas, plus the partial-result lists of
+/// [`PackOutput`].
+#[derive(Debug, Clone)]
+pub struct LayoutOutput {
+    pub atlas: Atlas<String>,
+    /// Items left out under `OnUnplaceable::Skip`.
+    pub skipped: Vec<SkippedItem>,
+    /// Items shrunk under `OnUnplaceable::ScaleDown`.
+    pub scaled: Vec<ScaledItem>,
+}
+
 /// Packs layout-only items (with optional source/source_size metadata) into pages.
 pub fn pack_layout_items<K: Into<String>>(
     items: Vec<LayoutItem<K>>,
     cfg: PackerConfig,
 ) -> Result<Atlas<String>> {
+    Ok(pack_layout_items_with(items, cfg, &PackControl::default())?.atlas)
+}
+
+/// [`pack_layout_items`] reporting progress to `ctl`, returning the items `on_unplaceable`
+/// skipped or scaled down.
+///
+/// Items take the same layout path as [`pack_images`] (`Auto`, `mr_global`, `page_balance`,
+/// `minimize_page_size`, `on_unplaceable`), so both produce the same frames for the same
+/// sizes. `ScaleDown` scales the item's size and trim rect instead of resampling pixels.
+#[instrument(name = "pack_layout_items", skip_all, fields(items = items.len(), family = ?cfg.family))]
+pub fn pack_layout_items_with<K: Into<String>>(
+    items: Vec<LayoutItem<K>>,
+    cfg: PackerConfig,
+    ctl: &PackControl,
+) -> Result<LayoutOutput> {
     // Validate configuration first
     cfg.validate()?;
 
     if items.is_empty() {
         return Err(TexPackerError::Empty);
     }
-    struct PrepL {
-        key: String,
-        rect: Rect,
-        trimmed: bool,
-        source: Rect,
-        orig_size: (u32, u32),
-    }
-    let mut prepared: Vec<PrepL> = items
+    let prepared = items
         .into_iter()
-        .map(|it| {
-            let key = it.key.into();
-            let rect = Rect::new(0, 0, it.w, it.h);
-            let source = it.source.unwrap_or(Rect::new(0, 0, it.w, it.h));
-            let orig = it.source_size.unwrap_or((it.w, it.h));
-            PrepL {
-                key,
-                rect,
-                trimmed: it.trimmed,
-                source,
-                orig_size: orig,
-            }
-        })
+        .enumerate()
+        .map(|(i, it)| (layout_prep(it, &cfg), i))
         .collect();
-    match cfg.sort_order {
-        SortOrder::None => {}
-        SortOrder::NameAsc => prepared.sort_by(|a, b| a.key.cmp(&b.key)),
-        SortOrder::AreaDesc => prepared.sort_by(|a, b| {
-            (b.rect.w * b.rect.h)
-                .cmp(&(a.rect.w * a.rect.h))
-                .then_with(|| a.key.cmp(&b.key))
-        }),
-        SortOrder::MaxSideDesc => prepared.sort_by(|a, b| {
-            b.rect
-                .w
-                .max(b.rect.h)
-                .cmp(&a.rect.w.max(a.rect.h))
-                .then_with(|| a.key.cmp(&b.key))
-        }),
-        SortOrder::HeightDesc => {
-            prepared.sort_by(|a, b| b.rect.h.cmp(&a.rect.h).then_with(|| a.key.cmp(&b.key)))
-        }
-        SortOrder::WidthDesc => {
-            prepared.sort_by(|a, b| b.rect.w.cmp(&a.rect.w).then_with(|| a.key.cmp(&b.key)))
-        }
-    }
-
-    let mut remaining: Vec<usize> = (0..prepared.len()).collect();
-    let mut atlas_pages: Vec<Page> = Vec::new();
-    let mut page_id = 0usize;
-    while !remaining.is_empty() {
-        let mut packer: Box<dyn Packer<String>> = match cfg.family {
-            AlgorithmFamily::Skyline => Box::new(SkylinePacker::new(cfg.clone())),
-            AlgorithmFamily::MaxRects => {
-                Box::new(MaxRectsPacker::new(cfg.clone(), cfg.mr_heuristic.clone()))
-            }
-            AlgorithmFamily::Guillotine => Box::new(GuillotinePacker::new(
-                cfg.clone(),
-                cfg.g_choice.clone(),
-                cfg.g_split.clone(),
-            )),
-            AlgorithmFamily::Auto => unreachable!(),
-        };
-        let mut frames: Vec<Frame> = Vec::new();
-        loop {
-            let mut placed_any = false;
-            let mut remove_set: HashSet<usize> = HashSet::new();
-            for &idx in &remaining {
-                let p = &prepared[idx];
-                if !packer.can_pack(&p.rect) {
-                    continue;
-                }
-                if let Some(mut f) = packer.pack(p.key.clone(), &p.rect) {
-                    f.trimmed = p.trimmed;
-                    f.source = p.source;
-                    f.source_size = p.orig_size;
-                    frames.push(f);
-                    remove_set.insert(idx);
-                    placed_any = true;
-                }
-            }
-            if !placed_any {
-                break;
-            }
-            if !remove_set.is_empty() {
-                remaining.retain(|i| !remove_set.contains(i));
-            }
-        }
-        if frames.is_empty() {
-            let placed = prepared.len() - remaining.len();
-            return Err(TexPackerError::OutOfSpaceGeneric {
-                placed,
-                total: prepared.len(),
-            });
-        }
+    let prepared: Vec<Prep> = finish_prepared(prepared, &cfg)?
+        .into_iter()
+        .map(|(p, _)| p)
+        .collect();
+    let mut skipped = Vec::new();
+    let (prepared, scaled) = apply_unplaceable(prepared, &cfg, &mut skipped);
+    warn_unplaceable(&skipped, &scaled);
+    warn_degenerate(
+        prepared
+            .iter()
+            .filter(|p| p.degenerate)
+            .map(|p| p.key.as_str()),
+    );
+    let mut cfg = cfg;
+    resolve_grid_cell(
+        &mut cfg,
+        prepared
+            .iter()
+            .map(|p| (p.key.as_str(), p.rect.w, p.rect.h)),
+    )?;
 
-        let (page_w, page_h) = compute_page_size(&frames, &cfg);
+    let plan = plan_layout(&prepared, cfg, ctl)?;
+    let meta = Meta {
+        packer: plan.packer,
+        page_minimization: plan.minimization,
+        auto_refinement: plan.refinement,
+        sdf: None,
+        ..image_meta(&plan.cfg)
+    };
+    Ok(LayoutOutput {
+        atlas: Atlas {
+            pages: plan.pages,
+            meta,
+        },
+        skipped,
+        scaled,
+    })
+}
 
-        let page = Page {
-            id: page_id,
-            width: page_w,
-            height: page_h,
-            frames: frames.clone(),
-        };
-        atlas_pages.push(page);
-        page_id += 1;
+/// Pixel-less [`Prep`] of a layout-only item.
+fn layout_prep<K: Into<String>>(it: LayoutItem<K>, cfg: &PackerConfig) -> Prep {
+    let degenerate = it.w == 0 || it.h == 0;
+    let source = it.source.unwrap_or(Rect::new(0, 0, it.w, it.h));
+    let orig = it.source_size.unwrap_or((it.w, it.h));
+    let (source, orig) = if degenerate {
+        (source, orig)
+    } else {
+        align_source(cfg, source, orig)
+    };
+    let rect = if degenerate {
+        Rect::new(0, 0, 1, 1)
+    } else {
+        let step = if cfg.align_size { cfg.align_step() } else { 1 };
+        Rect::new(
+            0,
+            0,
+            it.w.next_multiple_of(step),
+            it.h.next_multiple_of(step),
+        )
+    };
+    Prep {
+        key: it.key.into(),
+        rgba: RgbaImage::new(0, 0),
+        rect,
+        trimmed: it.trimmed,
+        source,
+        orig_size: orig,
+        degenerate,
+        content_hash: None,
+        detected_pivot: None,
+        placement: Placement::default(),
+        scale: None,
     }
+}
 
-    let meta = Meta {
-        schema_version: "1".into(),
-        app: "tex-packer".into(),
-        version: env!("CARGO_PKG_VERSION").into(),
-        format: "RGBA8888".into(),
-        scale: 1.0,
-        power_of_two: cfg.power_of_two,
-        square: cfg.square,
-        max_dim: (cfg.max_width, cfg.max_height),
-        padding: (cfg.border_padding, cfg.texture_padding),
-        extrude: cfg.texture_extrusion,
-        allow_rotation: cfg.allow_rotation,
-        trim_mode: if cfg.trim { "trim" } else { "none" }.into(),
-        background_color: None,
+/// `OnUnplaceable::ScaleDown` for a layout-only item: its source size becomes `size` and its
+/// trim rect shrinks by `scale`.
+fn scaled_layout_prep(p: &Prep, scale: f64, size: (u32, u32), cfg: &PackerConfig) -> Prep {
+    let s = |v: u32| (v as f64 * scale).floor() as u32;
+    let x = s(p.source.x).min(size.0 - 1);
+    let y = s(p.source.y).min(size.1 - 1);
+    let source = Rect::new(
+        x,
+        y,
+        s(p.source.w).clamp(1, size.0 - x),
+        s(p.source.h).clamp(1, size.1 - y),
+    );
+    let item = LayoutItem {
+        key: p.key.clone(),
+        w: source.w,
+        h: source.h,
+        source: Some(source),
+        source_size: Some(size),
+        trimmed: p.trimmed,
     };
-    Ok(Atlas {
-        pages: atlas_pages,
-        meta,
-    })
+    layout_prep(item, cfg)
 }
 
-/// Compute final page dimensions given placed frames and config.
-fn compute_page_size(frames: &[Frame], cfg: &PackerConfig) -> (u32, u32) {
-    if cfg.force_max_dimensions {
-        // When forced, return exactly the configured dimensions, ignoring pow2/square adjustments.
-        return (cfg.max_width, cfg.max_height);
-    }
-    let pad_half = cfg.texture_padding / 2;
-    let pad_rem = cfg.texture_padding - pad_half;
-    let right_extra = cfg.texture_extrusion + pad_rem;
-    let bottom_extra = cfg.texture_extrusion + pad_rem;
-    let mut page_w = 0u32;
-    let mut page_h = 0u32;
-    for f in frames {
-        page_w = page_w.max(f.frame.right() + 1 + right_extra + cfg.border_padding);
-        page_h = page_h.max(f.frame.bottom() + 1 + bottom_extra + cfg.border_padding);
-    }
-    if cfg.power_of_two {
-        page_w = next_pow2(page_w.max(1));
-        page_h = next_pow2(page_h.max(1));
+/// Smallest allowed size >= `v` (falls back to `max`, which validation requires to be allowed).
+fn snap_to_allowed(v: u32, max: u32, allowed: &[u32]) -> u32 {
+    allowed
+        .iter()
+        .copied()
+        .filter(|&s| s >= v && s <= max)
+        .min()
+        .unwrap_or(max)
+}
+
+/// Right/bottom page edge needed by a frame packed at `packed` (trailing extrusion, padding and
+/// border included).
+fn slot_extent(packed: &Rect, cfg: &PackerConfig) -> (u32, u32) {
+    let pad = cfg.sprite_padding();
+    let extra = cfg.texture_extrusion + cfg.border_padding;
+    (
+        packed.right() + 1 + extra + pad.right,
+        packed.bottom() + 1 + extra + pad.bottom,
+    )
+}
+
+/// Final page dimensions for frames whose slots reach `extent` (see `slot_extent`).
+fn page_size_for_extent(extent: (u32, u32), cfg: &PackerConfig) -> (u32, u32) {
+    let mode = &cfg.page_size;
+    let margin = match *mode {
+        // Exactly the configured dimensions, ignoring min size, block and square adjustments.
+        PageSizeMode::Fixed => return (cfg.max_width, cfg.max_height),
+        PageSizeMode::TightCrop { margin } => margin,
+        _ => 0,
+    };
+    let crop = |e: u32, max: u32| e.saturating_add(margin).min(max).max(e);
+    let mut page_w = crop(extent.0, cfg.max_width).max(cfg.min_width);
+    let mut page_h = crop(extent.1, cfg.max_height).max(cfg.min_height);
+    match mode {
+        PageSizeMode::Pow2 => {
+            page_w = next_pow2(page_w.max(1));
+            page_h = next_pow2(page_h.max(1));
+        }
+        PageSizeMode::AllowedList { sizes } => {
+            page_w = snap_to_allowed(page_w, cfg.max_width, sizes);
+            page_h = snap_to_allowed(page_h, cfg.max_height, sizes);
+        }
+        PageSizeMode::TightCrop { .. } | PageSizeMode::Fixed => {}
+    }
+    let block = cfg.page_format.block_size();
+    if block > 1 {
+        page_w = page_w.max(1).next_multiple_of(block);
+        page_h = page_h.max(1).next_multiple_of(block);
     }
     if cfg.square {
         let m = page_w.max(page_h);
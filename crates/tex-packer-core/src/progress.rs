@@ -0,0 +1,107 @@
+use crate::error::{Result, TexPackerError};
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+/// Stage of a packing run reported through [`PackControl`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PackPhase {
+    /// Converting inputs to RGBA and trimming; counts inputs.
+    Prepare,
+    /// Laying out frames; counts `Auto` portfolio candidates (1 for a single algorithm).
+    Layout,
+    /// Compositing page pixels; counts pages.
+    Compose,
+}
+
+/// One progress report: `done` of `total` units of `phase` are finished.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PackProgress {
+    pub phase: PackPhase,
+    pub done: usize,
+    pub total: usize,
+}
+
+impl PackProgress {
+    /// Completed share of the current phase, 0.0..=1.0.
+    pub fn fraction(&self) -> f32 {
+        if self.total == 0 {
+            1.0
+        } else {
+            self.done as f32 / self.total as f32
+        }
+    }
+}
+
+/// Shared flag that stops a running pack; clones observe the same flag, so one can be moved
+/// to a worker thread and the other kept by the UI.
+#[derive(Debug, Clone, Default)]
+pub struct CancellationToken(Arc<AtomicBool>);
+
+impl CancellationToken {
+    pub fn new() -> Self {
+        Self::default()
+    }
+    /// Requests cancellation; the pack returns [`TexPackerError::Cancelled`] at its next check.
+    pub fn cancel(&self) {
+        self.0.store(true, Ordering::Relaxed);
+    }
+    pub fn is_cancelled(&self) -> bool {
+        self.0.load(Ordering::Relaxed)
+    }
+}
+
+type ProgressFn = dyn Fn(PackProgress) + Send + Sync;
+
+/// Progress observer and cancellation for [`pack_images_with`](crate::pack_images_with).
+///
+/// The callback may run on rayon worker threads when the `parallel` feature is on, so reports
+/// of one phase can arrive out of order; `done` never exceeds `total`.
+#[derive(Clone, Default)]
+pub struct PackControl {
+    on_progress: Vec<Arc<ProgressFn>>,
+    cancel: Vec<CancellationToken>,
+}
+
+impl std::fmt::Debug for PackControl {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("PackControl")
+            .field("on_progress", &self.on_progress.len())
+            .field("cancel", &self.cancel)
+            .finish()
+    }
+}
+
+impl PackControl {
+    pub fn new() -> Self {
+        Self::default()
+    }
+    /// Adds a progress observer; every observer receives each report.
+    pub fn on_progress(mut self, f: impl Fn(PackProgress) + Send + Sync + 'static) -> Self {
+        self.on_progress.push(Arc::new(f));
+        self
+    }
+    /// Adds a cancellation token; the pack stops when any of them is cancelled.
+    pub fn cancel_token(mut self, token: CancellationToken) -> Self {
+        self.cancel.push(token);
+        self
+    }
+
+    pub(crate) fn report(&self, phase: PackPhase, done: usize, total: usize) {
+        for f in &self.on_progress {
+            f(PackProgress { phase, done, total });
+        }
+    }
+
+    pub(crate) fn is_cancelled(&self) -> bool {
+        self.cancel.iter().any(CancellationToken::is_cancelled)
+    }
+
+    /// `Err(Cancelled)` once the token has been cancelled.
+    pub(crate) fn check(&self) -> Result<()> {
+        if self.is_cancelled() {
+            Err(TexPackerError::Cancelled)
+        } else {
+            Ok(())
+        }
+    }
+}
@@ -0,0 +1,164 @@
+//! Flattened Photoshop (`.psd`) reader.
+//!
+//! Only the merged composite stored at the end of the file is read (Photoshop writes it unless
+//! "Maximize Compatibility" is off); layers are not composited. RGB and grayscale documents
+//! with 8 or 16 bits per channel are supported.
+
+use crate::error::{Result, TexPackerError};
+use image::RgbaImage;
+
+fn invalid(msg: impl Into<String>) -> TexPackerError {
+    TexPackerError::InvalidInput(format!("psd: {}", msg.into()))
+}
+
+/// Big-endian cursor over a byte slice.
+struct Reader<'a> {
+    data: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> Reader<'a> {
+    fn bytes(&mut self, n: usize) -> Result<&'a [u8]> {
+        let end = self
+            .pos
+            .checked_add(n)
+            .filter(|&e| e <= self.data.len())
+            .ok_or_else(|| invalid("unexpected end of file"))?;
+        let b = &self.data[self.pos..end];
+        self.pos = end;
+        Ok(b)
+    }
+
+    fn u16(&mut self) -> Result<u16> {
+        Ok(u16::from_be_bytes(self.bytes(2)?.try_into().unwrap()))
+    }
+
+    fn u32(&mut self) -> Result<u32> {
+        Ok(u32::from_be_bytes(self.bytes(4)?.try_into().unwrap()))
+    }
+
+    /// Skips a section prefixed by its u32 length.
+    fn skip_section(&mut self) -> Result<()> {
+        let len = self.u32()? as usize;
+        self.bytes(len).map(drop)
+    }
+}
+
+/// Decodes the merged image of a PSD file.
+///
+/// A fourth (RGB) or second (grayscale) channel is read as transparency; Photoshop mattes the
+/// composite against white in that case, which is undone here.
+pub fn decode_psd(bytes: &[u8]) -> Result<RgbaImage> {
+    let mut r = Reader {
+        data: bytes,
+        pos: 0,
+    };
+    if r.bytes(4)? != b"8BPS" {
+        return Err(invalid("not a Photoshop file (bad signature)"));
+    }
+    if r.u16()? != 1 {
+        return Err(invalid("only PSD version 1 is supported (not PSB)"));
+    }
+    r.bytes(6)?;
+    let channels = r.u16()? as usize;
+    let height = r.u32()?;
+    let width = r.u32()?;
+    let depth = r.u16()?;
+    let mode = r.u16()?;
+    let color_channels = match mode {
+        1 => 1,
+        3 => 3,
+        _ => {
+            return Err(invalid(format!(
+                "unsupported color mode {mode} (RGB or grayscale only)"
+            )));
+        }
+    };
+    if channels < color_channels {
+        return Err(invalid("fewer channels than the color mode needs"));
+    }
+    if depth != 8 && depth != 16 {
+        return Err(invalid(format!("unsupported bit depth {depth}")));
+    }
+    r.skip_section()?; // color mode data
+    r.skip_section()?; // image resources
+    r.skip_section()?; // layer and mask information
+
+    let row_len = width as usize * (depth as usize / 8);
+    let rows = channels * height as usize;
+    let compression = r.u16()?;
+    let planes: Vec<u8> = match compression {
+        0 => r.bytes(rows * row_len)?.to_vec(),
+        1 => {
+            let counts = (0..rows).map(|_| r.u16()).collect::<Result<Vec<_>>>()?;
+            let mut out = Vec::with_capacity(rows * row_len);
+            for n in counts {
+                unpack_bits(r.bytes(n as usize)?, row_len, &mut out)?;
+            }
+            out
+        }
+        c => return Err(invalid(format!("unsupported compression {c}"))),
+    };
+
+    let plane_len = height as usize * row_len;
+    let has_alpha = channels > color_channels;
+    let sample = |channel: usize, i: usize| -> u8 {
+        // 16-bit samples are big-endian: keep the high byte
+        planes[channel * plane_len + i * (depth as usize / 8)]
+    };
+    let mut out = Vec::with_capacity(width as usize * height as usize * 4);
+    for i in 0..width as usize * height as usize {
+        let (mut rgb, a) = (
+            if color_channels == 3 {
+                [sample(0, i), sample(1, i), sample(2, i)]
+            } else {
+                [sample(0, i); 3]
+            },
+            if has_alpha {
+                sample(color_channels, i)
+            } else {
+                255
+            },
+        );
+        if has_alpha && a < 255 {
+            for c in &mut rgb {
+                *c = if a == 0 {
+                    0
+                } else {
+                    let v = (*c as i32 - (255 - a as i32)).max(0) * 255 / a as i32;
+                    v.min(255) as u8
+                };
+            }
+        }
+        out.extend_from_slice(&[rgb[0], rgb[1], rgb[2], a]);
+    }
+    RgbaImage::from_raw(width, height, out).ok_or_else(|| invalid("image size mismatch"))
+}
+
+/// Appends one PackBits-compressed row of `row_len` bytes.
+fn unpack_bits(mut src: &[u8], row_len: usize, out: &mut Vec<u8>) -> Result<()> {
+    let start = out.len();
+    while out.len() - start < row_len {
+        let (&n, rest) = src
+            .split_first()
+            .ok_or_else(|| invalid("RLE row is truncated"))?;
+        src = rest;
+        let n = n as i8;
+        if n >= 0 {
+            let len = n as usize + 1;
+            if src.len() < len {
+                return Err(invalid("RLE row is truncated"));
+            }
+            out.extend_from_slice(&src[..len]);
+            src = &src[len..];
+        } else if n != -128 {
+            let (&b, rest) = src
+                .split_first()
+                .ok_or_else(|| invalid("RLE row is truncated"))?;
+            src = rest;
+            out.extend(std::iter::repeat_n(b, (1 - n as isize) as usize));
+        }
+    }
+    out.truncate(start + row_len);
+    Ok(())
+}
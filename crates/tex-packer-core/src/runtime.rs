@@ -1,16 +1,23 @@
-use crate::config::{GuillotineChoice, GuillotineSplit, PackerConfig, SkylineHeuristic};
+use crate::config::{
+    GuillotineChoice, GuillotineSplit, MaxRectsHeuristic, PackerConfig, SkylineHeuristic,
+};
 use crate::error::{Result, TexPackerError};
 use crate::model::{Atlas, Frame, Meta, Page, Rect};
-use std::collections::HashMap;
+use crate::packer::Slot;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum RuntimeStrategy {
     Guillotine,
     Shelf(ShelfPolicy),
     Skyline(SkylineHeuristic),
+    /// Maximal free rectangles; evicted slots merge back into the surrounding free space, which
+    /// suits churn-heavy caches (e.g. glyphs) that leave Guillotine/Shelf fragmented.
+    MaxRects(MaxRectsHeuristic),
 }
 
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
 pub enum ShelfPolicy {
     NextFit,
     FirstFit,
@@ -70,13 +77,68 @@ impl RuntimeStats {
     }
 }
 
+/// A live entry relocated by [`AtlasSession::compact`] / [`AtlasSession::compact_all`].
+///
+/// Rects are reserved slots (frame plus padding and extrusion). Orientation never changes, so
+/// the pixels of `old_rect` on `old_page` can be copied verbatim to `new_rect` on `new_page`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FrameMove {
+    pub key: String,
+    pub old_page: usize,
+    pub old_rect: Rect,
+    pub new_page: usize,
+    pub new_rect: Rect,
+}
+
+/// Limits of a budgeted [`AtlasSession`]: once `append` would need more pages than allowed,
+/// least-recently-used entries are evicted until the new entry fits.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RuntimeBudget {
+    /// Maximum number of pages.
+    pub max_pages: Option<usize>,
+    /// Maximum total page pixels (`max_width * max_height` per page).
+    pub max_pixels: Option<u64>,
+}
+
+/// An entry evicted automatically by a budgeted session.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct EvictedEntry {
+    pub key: String,
+    pub page_id: usize,
+    /// Reserved slot the entry occupied.
+    pub slot: Rect,
+}
+
+/// A live entry taken out of a page for re-packing.
+struct LiveEntry {
+    page: usize,
+    key: String,
+    slot: Rect,
+    rotated: bool,
+    frame: Frame<String>,
+}
+
+/// Incremental atlas layout: append and evict entries one at a time.
+///
+/// The session is `Serialize`/`Deserialize`. Its state includes free lists, shelves, skylines,
+/// used slots and LRU order, so a restored session continues exactly where it left off without
+/// repacking.
+#[derive(Serialize, Deserialize)]
 pub struct AtlasSession {
     pub(crate) cfg: PackerConfig,
+    #[serde(rename = "strategy")]
     _strategy: RuntimeStrategy,
     pages: Vec<RtPage>,
     next_id: usize,
+    budget: Option<RuntimeBudget>,
+    /// Use clock; `lru` orders keys by their last append/touch.
+    clock: u64,
+    last_used: HashMap<String, u64>,
+    lru: BTreeMap<u64, String>,
+    evicted: Vec<EvictedEntry>,
 }
 
+#[derive(Serialize, Deserialize)]
 struct RtPage {
     id: usize,
     width: u32,
@@ -87,6 +149,7 @@ struct RtPage {
     mode: RtMode,
 }
 
+#[derive(Serialize, Deserialize)]
 enum RtMode {
     Guillotine {
         free: Vec<Rect>,
@@ -104,16 +167,22 @@ enum RtMode {
         heuristic: SkylineHeuristic,
         skylines: Vec<SkylineNode>,
     },
+    MaxRects {
+        border: Rect,
+        heuristic: MaxRectsHeuristic,
+        /// Maximal free rectangles; they may overlap each other.
+        free: Vec<Rect>,
+    },
 }
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Serialize, Deserialize)]
 struct Shelf {
     y: u32,
     h: u32,
     segs: Vec<(u32, u32)>,
 }
 
-#[derive(Clone, Copy, Debug)]
+#[derive(Clone, Copy, Debug, Serialize, Deserialize)]
 struct SkylineNode {
     x: u32,
     y: u32,
@@ -127,12 +196,73 @@ impl AtlasSession {
             _strategy: strategy,
             pages: Vec::new(),
             next_id: 0,
+            budget: None,
+            clock: 0,
+            last_used: HashMap::new(),
+            lru: BTreeMap::new(),
+            evicted: Vec::new(),
+        }
+    }
+
+    /// Enforces `budget`: instead of failing or growing past it, `append` evicts the
+    /// least-recently-used entries (see [`touch`](Self::touch)) until the new entry fits.
+    /// Collect what was evicted with [`drain_evicted`](Self::drain_evicted).
+    ///
+    /// Skyline pages never reuse evicted space, so pair a budget with `MaxRects`, `Guillotine`
+    /// or `Shelf` (or call [`compact`](Self::compact) after evictions).
+    pub fn with_budget(mut self, budget: RuntimeBudget) -> Self {
+        self.budget = Some(budget);
+        self
+    }
+
+    /// Marks `key` as just used, making it the last candidate for budget eviction. Returns
+    /// false if the key is not in the session.
+    pub fn touch(&mut self, key: &str) -> bool {
+        if !self.contains(key) {
+            return false;
+        }
+        self.mark_used(key);
+        true
+    }
+
+    /// Entries evicted by the budget since the last call, oldest first.
+    pub fn drain_evicted(&mut self) -> Vec<EvictedEntry> {
+        std::mem::take(&mut self.evicted)
+    }
+
+    fn mark_used(&mut self, key: &str) {
+        if let Some(old) = self.last_used.remove(key) {
+            self.lru.remove(&old);
+        }
+        self.clock += 1;
+        self.last_used.insert(key.to_string(), self.clock);
+        self.lru.insert(self.clock, key.to_string());
+    }
+
+    fn forget(&mut self, key: &str) {
+        if let Some(stamp) = self.last_used.remove(key) {
+            self.lru.remove(&stamp);
+        }
+    }
+
+    /// Page count allowed by the budget, `None` when unbounded.
+    fn page_limit(&self) -> Option<usize> {
+        let budget = self.budget?;
+        let page_px = (self.cfg.max_width as u64) * (self.cfg.max_height as u64);
+        let by_pixels = budget.max_pixels.map(|px| (px / page_px.max(1)) as usize);
+        match (budget.max_pages, by_pixels) {
+            (Some(a), Some(b)) => Some(a.min(b)),
+            (a, b) => a.or(b),
         }
     }
 
     fn new_page(&mut self) -> RtPage {
         let id = self.next_id;
         self.next_id += 1;
+        self.blank_page(id)
+    }
+
+    fn blank_page(&self, id: usize) -> RtPage {
         let pad = self.cfg.border_padding;
         let w = self.cfg.max_width.saturating_sub(pad.saturating_mul(2));
         let h = self.cfg.max_height.saturating_sub(pad.saturating_mul(2));
@@ -153,6 +283,11 @@ impl AtlasSession {
                 heuristic: heuristic.clone(),
                 skylines: vec![SkylineNode { x: pad, y: pad, w }],
             },
+            RuntimeStrategy::MaxRects(heuristic) => RtMode::MaxRects {
+                border: Rect::new(pad, pad, w, h),
+                heuristic: heuristic.clone(),
+                free: vec![Rect::new(pad, pad, w, h)],
+            },
         };
         RtPage {
             id,
@@ -165,53 +300,179 @@ impl AtlasSession {
     }
 
     pub fn append(&mut self, key: String, w: u32, h: u32) -> Result<(usize, Frame<String>)> {
-        let reserve_w = w + self.cfg.texture_extrusion * 2 + self.cfg.texture_padding;
-        let reserve_h = h + self.cfg.texture_extrusion * 2 + self.cfg.texture_padding;
-        // Try existing pages
-        for idx in 0..self.pages.len() {
-            let (slot, rotated, id);
-            {
+        let reserve = Slot::new(&Rect::new(0, 0, w, h), &self.cfg);
+        let out_of_space = |pages: usize| TexPackerError::OutOfSpace {
+            key: key.clone(),
+            width: w,
+            height: h,
+            pages_attempted: pages,
+        };
+        // Never evict for an entry that cannot fit even on an empty page.
+        if self.blank_page(0).choose(reserve).is_none() {
+            return Err(out_of_space(self.pages.len() + 1));
+        }
+        loop {
+            // Try existing pages
+            for idx in 0..self.pages.len() {
                 let p = &self.pages[idx];
-                if let Some((s, r)) = p.choose(reserve_w, reserve_h) {
-                    slot = s;
-                    rotated = r;
-                    id = p.id;
-                } else {
+                let Some((slot, rotated)) = p.choose(reserve) else {
                     continue;
-                }
+                };
+                let id = p.id;
+                let frame = self.make_frame(&key, w, h, &slot, rotated);
+                self.pages[idx].place(&key, &slot, &frame, rotated);
+                self.mark_used(&key);
+                return Ok((id, frame));
             }
-            let frame = self.make_frame(&key, w, h, &slot, rotated);
-            let p = &mut self.pages[idx];
-            p.place(&key, &slot, &frame, rotated);
-            return Ok((id, frame));
-        }
-        // Grow: add a new page and place
-        let mut page = self.new_page();
-        if let Some((slot, rotated)) = page.choose(reserve_w, reserve_h) {
-            let frame = self.make_frame(&key, w, h, &slot, rotated);
-            page.place(&key, &slot, &frame, rotated);
-            let id = page.id;
-            self.pages.push(page);
-            return Ok((id, frame));
-        }
-        Err(TexPackerError::OutOfSpace {
-            key,
-            width: w,
-            height: h,
-            pages_attempted: self.pages.len() + 1,
-        })
+            // Grow: add a new page and place
+            if self
+                .page_limit()
+                .is_none_or(|limit| self.pages.len() < limit)
+            {
+                let mut page = self.new_page();
+                let Some((slot, rotated)) = page.choose(reserve) else {
+                    return Err(out_of_space(self.pages.len() + 1));
+                };
+                let frame = self.make_frame(&key, w, h, &slot, rotated);
+                page.place(&key, &slot, &frame, rotated);
+                let id = page.id;
+                self.pages.push(page);
+                self.mark_used(&key);
+                return Ok((id, frame));
+            }
+            // At the budget: evict the least-recently-used entry and retry
+            let Some((_, victim)) = self.lru.pop_first() else {
+                return Err(out_of_space(self.pages.len()));
+            };
+            self.last_used.remove(&victim);
+            if let Some((page_id, slot)) = self.get_reserved_slot(&victim) {
+                self.evict(page_id, &victim);
+                self.evicted.push(EvictedEntry {
+                    key: victim,
+                    page_id,
+                    slot,
+                });
+            }
+        }
     }
 
     pub fn evict(&mut self, page_id: usize, key: &str) -> bool {
         if let Some(p) = self.pages.iter_mut().find(|p| p.id == page_id) {
             if let Some((slot, _rot, _frame)) = p.used.remove(key) {
                 p.add_free(slot);
+                self.forget(key);
                 return true;
             }
         }
         false
     }
 
+    /// Re-packs the live entries of page `page_id` from scratch to undo fragmentation left by
+    /// evictions. Returns the entries that moved; empty when nothing moved, the page does not
+    /// exist, or the entries would not fit again in a fresh layout (the page is then unchanged).
+    pub fn compact(&mut self, page_id: usize) -> Vec<FrameMove> {
+        let Some(idx) = self.pages.iter().position(|p| p.id == page_id) else {
+            return Vec::new();
+        };
+        let entries = self.live_entries(&[idx]);
+        match self.repack(entries, &[page_id]) {
+            Some((mut pages, moves)) => {
+                self.pages[idx] = pages.remove(0);
+                moves
+            }
+            None => Vec::new(),
+        }
+    }
+
+    /// [`compact`](Self::compact) across the whole session: live entries are re-packed into the
+    /// lowest pages first, so later pages may end up empty (they stay allocated and keep their
+    /// ids). Returns the entries that moved, or nothing if the entries would not fit again.
+    pub fn compact_all(&mut self) -> Vec<FrameMove> {
+        let all: Vec<usize> = (0..self.pages.len()).collect();
+        let ids: Vec<usize> = self.pages.iter().map(|p| p.id).collect();
+        let entries = self.live_entries(&all);
+        match self.repack(entries, &ids) {
+            Some((pages, moves)) => {
+                self.pages = pages;
+                moves
+            }
+            None => Vec::new(),
+        }
+    }
+
+    /// Entries of the pages at `indices`, largest slots first (height, then width, then key)
+    /// so the re-pack is deterministic and shelf/skyline friendly.
+    fn live_entries(&self, indices: &[usize]) -> Vec<LiveEntry> {
+        let mut entries: Vec<LiveEntry> = indices
+            .iter()
+            .flat_map(|&i| {
+                let page = &self.pages[i];
+                page.used
+                    .iter()
+                    .map(|(key, (slot, rotated, frame))| LiveEntry {
+                        page: page.id,
+                        key: key.clone(),
+                        slot: *slot,
+                        rotated: *rotated,
+                        frame: frame.clone(),
+                    })
+            })
+            .collect();
+        entries.sort_by(|a, b| {
+            (b.slot.h, b.slot.w)
+                .cmp(&(a.slot.h, a.slot.w))
+                .then_with(|| a.key.cmp(&b.key))
+        });
+        entries
+    }
+
+    /// Places `entries` on fresh pages with `ids` (first fit, orientation kept). `None` if an
+    /// entry finds no room.
+    fn repack(
+        &self,
+        entries: Vec<LiveEntry>,
+        ids: &[usize],
+    ) -> Option<(Vec<RtPage>, Vec<FrameMove>)> {
+        let mut pages: Vec<RtPage> = ids
+            .iter()
+            .map(|&id| {
+                let mut page = self.blank_page(id);
+                page.allow_rotation = false;
+                page
+            })
+            .collect();
+        let mut moves = Vec::new();
+        for e in entries {
+            // Rotation is off, so only the reserved slot's own size matters
+            let reserve = Slot {
+                w: e.slot.w,
+                h: e.slot.h,
+                rw: e.slot.h,
+                rh: e.slot.w,
+            };
+            let (page, slot) = pages
+                .iter_mut()
+                .find_map(|p| p.choose(reserve).map(|(slot, _)| (p, slot)))?;
+            let mut frame = e.frame;
+            frame.frame.x = slot.x + (frame.frame.x - e.slot.x);
+            frame.frame.y = slot.y + (frame.frame.y - e.slot.y);
+            page.place(&e.key, &slot, &frame, e.rotated);
+            if page.id != e.page || slot != e.slot {
+                moves.push(FrameMove {
+                    key: e.key,
+                    old_page: e.page,
+                    old_rect: e.slot,
+                    new_page: page.id,
+                    new_rect: slot,
+                });
+            }
+        }
+        for page in &mut pages {
+            page.allow_rotation = self.cfg.allow_rotation;
+        }
+        Some((pages, moves))
+    }
+
     pub fn snapshot_atlas(&self) -> Atlas<String> {
         let mut pages: Vec<Page<String>> = Vec::new();
         for p in &self.pages {
@@ -232,14 +493,22 @@ impl AtlasSession {
             version: env!("CARGO_PKG_VERSION").into(),
             format: "RGBA8888".into(),
             scale: 1.0,
-            power_of_two: self.cfg.power_of_two,
+            power_of_two: self.cfg.power_of_two_pages(),
             square: self.cfg.square,
             max_dim: (self.cfg.max_width, self.cfg.max_height),
-            padding: (self.cfg.border_padding, self.cfg.texture_padding),
+            padding: (self.cfg.border_padding, self.cfg.meta_padding()),
             extrude: self.cfg.texture_extrusion,
             allow_rotation: self.cfg.allow_rotation,
+            rotation_direction: self.cfg.rotation_direction,
             trim_mode: if self.cfg.trim { "trim" } else { "none" }.into(),
-            background_color: None,
+            background_color: self.cfg.background_color,
+            packer: None,
+            page_minimization: None,
+            auto_refinement: None,
+            align: self.cfg.frame_align(),
+            animations: self.cfg.animations.clone(),
+            sdf: None,
+            mip: self.cfg.mip_info(),
         };
         Atlas { pages, meta }
     }
@@ -271,6 +540,7 @@ impl AtlasSession {
         for page in &mut self.pages {
             if let Some((slot, _rot, _frame)) = page.used.remove(key) {
                 page.add_free(slot);
+                self.forget(key);
                 return true;
             }
         }
@@ -326,6 +596,16 @@ impl AtlasSession {
                         }
                     }
                 }
+                RtMode::MaxRects { border, free, .. } => {
+                    // Free rects overlap, so count the uncovered border area instead
+                    num_free_rects += free.len();
+                    let used: u64 = page
+                        .used
+                        .values()
+                        .map(|(slot, _, _)| (slot.w as u64) * (slot.h as u64))
+                        .sum();
+                    total_free_area += ((border.w as u64) * (border.h as u64)).saturating_sub(used);
+                }
                 RtMode::Skyline {
                     border, skylines, ..
                 } => {
@@ -364,10 +644,8 @@ impl AtlasSession {
     }
 
     fn make_frame(&self, key: &str, w: u32, h: u32, slot: &Rect, rotated: bool) -> Frame<String> {
-        let pad_half = self.cfg.texture_padding / 2;
-        let off = self.cfg.texture_extrusion + pad_half;
-        let (fw, fh) = (w, h);
-        let frame = Rect::new(slot.x + off, slot.y + off, fw, fh);
+        let (lead_x, lead_y) = Slot::lead(&self.cfg);
+        let frame = Rect::new(slot.x + lead_x, slot.y + lead_y, w, h);
         let source = Rect::new(0, 0, w, h);
         Frame {
             key: key.to_string(),
@@ -376,12 +654,19 @@ impl AtlasSession {
             trimmed: false,
             source,
             source_size: (w, h),
+            degenerate: false,
+            id: crate::hash::stable_frame_id(key),
+            content_hash: None,
+            pivot: Default::default(),
+            nine_patch: None,
+            scale: None,
         }
     }
 }
 
 impl RtPage {
-    fn choose(&self, w: u32, h: u32) -> Option<(Rect, bool)> {
+    fn choose(&self, slot: Slot) -> Option<(Rect, bool)> {
+        let (w, h) = (slot.w, slot.h);
         match &self.mode {
             RtMode::Guillotine { free, choice, .. } => {
                 let mut best_idx = None;
@@ -400,13 +685,13 @@ impl RtPage {
                             best_rot = false;
                         }
                     }
-                    if self.allow_rotation && fr.w >= h && fr.h >= w {
-                        let (s1, s2) = score_choice(choice, fr, h, w);
+                    if self.allow_rotation && fr.w >= slot.rw && fr.h >= slot.rh {
+                        let (s1, s2) = score_choice(choice, fr, slot.rw, slot.rh);
                         if s1 < best_s || (s1 == best_s && s2 < best_s2) {
                             best_s = s1;
                             best_s2 = s2;
                             best_idx = Some(i);
-                            best = Rect::new(fr.x, fr.y, h, w);
+                            best = Rect::new(fr.x, fr.y, slot.rw, slot.rh);
                             best_rot = true;
                         }
                     }
@@ -418,12 +703,20 @@ impl RtPage {
                 policy,
                 shelves,
                 next_y,
-            } => choose_shelf(self.allow_rotation, border, *policy, shelves, *next_y, w, h),
+            } => choose_shelf(self.allow_rotation, border, *policy, shelves, *next_y, slot),
             RtMode::Skyline {
                 border,
                 heuristic,
                 skylines,
-            } => choose_skyline(self.allow_rotation, border, heuristic, skylines, w, h),
+            } => choose_skyline(self.allow_rotation, border, heuristic, skylines, slot),
+            RtMode::MaxRects {
+                border,
+                heuristic,
+                free,
+            } => {
+                let used: Vec<Rect> = self.used.values().map(|(slot, _, _)| *slot).collect();
+                choose_maxrects(self.allow_rotation, border, heuristic, free, &used, slot)
+            }
         }
     }
 
@@ -476,6 +769,9 @@ impl RtPage {
             RtMode::Skyline { skylines, .. } => {
                 place_skyline(skylines, slot);
             }
+            RtMode::MaxRects { free, .. } => {
+                split_maxrects(free, slot);
+            }
         }
         self.used
             .insert(key.to_string(), (*slot, rotated, frame.clone()));
@@ -489,7 +785,9 @@ impl RtPage {
                 merge_free_list(free);
             }
             RtMode::Shelf { shelves, .. } => {
-                if let Some(sh) = shelves.iter_mut().find(|s| s.y == r.y && s.h == r.h) {
+                // Slots shorter than their shelf return to it too (matched like `place` does);
+                // an exact-height match would open a second, overlapping shelf at the same y
+                if let Some(sh) = shelves.iter_mut().find(|s| s.y == r.y && s.h >= r.h) {
                     sh.segs.push((r.x, r.w));
                     merge_shelf_segments(sh);
                 } else {
@@ -503,6 +801,19 @@ impl RtPage {
             RtMode::Skyline { .. } => {
                 // Skyline doesn't support add_free (eviction not optimized)
             }
+            RtMode::MaxRects { border, free, .. } => {
+                // The freed slot can join several free rects into larger maximal ones, so the
+                // list is rebuilt from the remaining slots (`r` was already removed from `used`).
+                // Slots are carved in position order: `used` iterates in a per-process order,
+                // and the free list order decides score ties. The rebuild costs
+                // O(live slots x free rects) per eviction.
+                let mut slots: Vec<Rect> = self.used.values().map(|(slot, _, _)| *slot).collect();
+                slots.sort_by_key(|s| (s.y, s.x, s.h, s.w));
+                *free = vec![*border];
+                for slot in &slots {
+                    split_maxrects(free, slot);
+                }
+            }
         }
     }
 
@@ -636,9 +947,9 @@ fn choose_shelf(
     policy: ShelfPolicy,
     shelves: &Vec<Shelf>,
     next_y: u32,
-    w: u32,
-    h: u32,
+    slot: Slot,
 ) -> Option<(Rect, bool)> {
+    let (w, h) = (slot.w, slot.h);
     let try_in = |rw: u32, rh: u32| -> Option<Rect> {
         match policy {
             ShelfPolicy::FirstFit => {
@@ -675,7 +986,7 @@ fn choose_shelf(
         return Some((r, false));
     }
     if allow_rot {
-        if let Some(r) = try_in(h, w) {
+        if let Some(r) = try_in(slot.rw, slot.rh) {
             return Some((r, true));
         }
     }
@@ -690,7 +1001,7 @@ fn choose_shelf(
         return Some((r, false));
     }
     if allow_rot {
-        if let Some(r) = try_new(h, w) {
+        if let Some(r) = try_new(slot.rw, slot.rh) {
             return Some((r, true));
         }
     }
@@ -737,22 +1048,113 @@ fn merge_shelf_segments(sh: &mut Shelf) {
     sh.segs = out;
 }
 
+// MaxRects helper functions
+fn choose_maxrects(
+    allow_rotation: bool,
+    border: &Rect,
+    heuristic: &MaxRectsHeuristic,
+    free: &[Rect],
+    used: &[Rect],
+    slot: Slot,
+) -> Option<(Rect, bool)> {
+    let (w, h) = (slot.w, slot.h);
+    let score = |fr: &Rect, rw: u32, rh: u32| -> (i64, i64) {
+        let leftover_h = fr.w as i64 - rw as i64;
+        let leftover_v = fr.h as i64 - rh as i64;
+        let short_fit = leftover_h.min(leftover_v);
+        let long_fit = leftover_h.max(leftover_v);
+        let area_fit = (fr.w as i64) * (fr.h as i64) - (rw as i64) * (rh as i64);
+        match heuristic {
+            MaxRectsHeuristic::BestAreaFit => (area_fit, short_fit),
+            MaxRectsHeuristic::BestShortSideFit => (short_fit, long_fit),
+            MaxRectsHeuristic::BestLongSideFit => (long_fit, short_fit),
+            MaxRectsHeuristic::BottomLeft => ((fr.y + rh) as i64, fr.x as i64),
+            MaxRectsHeuristic::ContactPoint => {
+                let node = Rect::new(fr.x, fr.y, rw, rh);
+                (-(contact_score(border, used, &node) as i64), area_fit)
+            }
+        }
+    };
+    let mut best: Option<((i64, i64), Rect, bool)> = None;
+    for fr in free {
+        let mut consider = |rw: u32, rh: u32, rotated: bool| {
+            if fr.w >= rw && fr.h >= rh {
+                let s = score(fr, rw, rh);
+                if best.as_ref().is_none_or(|(b, _, _)| s < *b) {
+                    best = Some((s, Rect::new(fr.x, fr.y, rw, rh), rotated));
+                }
+            }
+        };
+        consider(w, h, false);
+        if allow_rotation && (slot.rw, slot.rh) != (w, h) {
+            consider(slot.rw, slot.rh, true);
+        }
+    }
+    best.map(|(_, r, rot)| (r, rot))
+}
+
+/// Length of `node`'s edges touching the border or used slots.
+fn contact_score(border: &Rect, used: &[Rect], node: &Rect) -> u32 {
+    let overlap = |a1: u32, a2: u32, b1: u32, b2: u32| a2.min(b2).saturating_sub(a1.max(b1));
+    let mut score = 0;
+    if node.x == border.x || node.x + node.w == border.x + border.w {
+        score += node.h;
+    }
+    if node.y == border.y || node.y + node.h == border.y + border.h {
+        score += node.w;
+    }
+    for u in used {
+        if node.x == u.x + u.w || u.x == node.x + node.w {
+            score += overlap(node.y, node.y + node.h, u.y, u.y + u.h);
+        }
+        if node.y == u.y + u.h || u.y == node.y + node.h {
+            score += overlap(node.x, node.x + node.w, u.x, u.x + u.w);
+        }
+    }
+    score
+}
+
+/// Carves `node` out of every free rect it overlaps, keeping the maximal remainders.
+fn split_maxrects(free: &mut Vec<Rect>, node: &Rect) {
+    let (nx2, ny2) = (node.x + node.w, node.y + node.h);
+    let mut out = Vec::with_capacity(free.len() + 4);
+    for fr in free.drain(..) {
+        let (fx2, fy2) = (fr.x + fr.w, fr.y + fr.h);
+        if node.x >= fx2 || fr.x >= nx2 || node.y >= fy2 || fr.y >= ny2 {
+            out.push(fr);
+            continue;
+        }
+        if node.x > fr.x {
+            out.push(Rect::new(fr.x, fr.y, node.x - fr.x, fr.h));
+        }
+        if nx2 < fx2 {
+            out.push(Rect::new(nx2, fr.y, fx2 - nx2, fr.h));
+        }
+        if node.y > fr.y {
+            out.push(Rect::new(fr.x, fr.y, fr.w, node.y - fr.y));
+        }
+        if ny2 < fy2 {
+            out.push(Rect::new(fr.x, ny2, fr.w, fy2 - ny2));
+        }
+    }
+    *free = out;
+    prune_free_list(free);
+}
+
 // Skyline helper functions
 fn choose_skyline(
     allow_rotation: bool,
     border: &Rect,
     heuristic: &SkylineHeuristic,
     skylines: &[SkylineNode],
-    w: u32,
-    h: u32,
+    slot: Slot,
 ) -> Option<(Rect, bool)> {
-    match heuristic {
-        SkylineHeuristic::BottomLeft => {
-            find_skyline_bottom_left(allow_rotation, border, skylines, w, h)
-        }
+    // The runtime skyline keeps no waste map; `*Wm` variants use their base rule.
+    match heuristic.base() {
         SkylineHeuristic::MinWaste => {
-            find_skyline_min_waste(allow_rotation, border, skylines, w, h)
+            find_skyline_min_waste(allow_rotation, border, skylines, slot)
         }
+        _ => find_skyline_bottom_left(allow_rotation, border, skylines, slot),
     }
 }
 
@@ -788,9 +1190,9 @@ fn find_skyline_bottom_left(
     allow_rotation: bool,
     border: &Rect,
     skylines: &[SkylineNode],
-    w: u32,
-    h: u32,
+    slot: Slot,
 ) -> Option<(Rect, bool)> {
+    let (w, h) = (slot.w, slot.h);
     let mut best_bottom = u32::MAX;
     let mut best_width = u32::MAX;
     let mut best_index: Option<usize> = None;
@@ -809,7 +1211,7 @@ fn find_skyline_bottom_left(
             }
         }
         if allow_rotation {
-            if let Some(r) = can_put_skyline(skylines, border, i, h, w) {
+            if let Some(r) = can_put_skyline(skylines, border, i, slot.rw, slot.rh) {
                 if r.bottom() < best_bottom
                     || (r.bottom() == best_bottom && skylines[i].w < best_width)
                 {
@@ -830,9 +1232,9 @@ fn find_skyline_min_waste(
     allow_rotation: bool,
     border: &Rect,
     skylines: &[SkylineNode],
-    w: u32,
-    h: u32,
+    slot: Slot,
 ) -> Option<(Rect, bool)> {
+    let (w, h) = (slot.w, slot.h);
     let mut best_waste = i64::MAX;
     let mut best_bottom = u32::MAX;
     let mut best_index: Option<usize> = None;
@@ -851,7 +1253,7 @@ fn find_skyline_min_waste(
             }
         }
         if allow_rotation {
-            if let Some(r) = can_put_skyline(skylines, border, i, h, w) {
+            if let Some(r) = can_put_skyline(skylines, border, i, slot.rw, slot.rh) {
                 let waste = compute_waste(skylines, i, &r);
                 if waste < best_waste || (waste == best_waste && r.bottom() < best_bottom) {
                     best_waste = waste;
@@ -885,34 +1287,49 @@ fn compute_waste(skylines: &[SkylineNode], start_idx: usize, rect: &Rect) -> i64
 }
 
 fn place_skyline(skylines: &mut Vec<SkylineNode>, slot: &Rect) {
-    // Find nodes that intersect with the placed rectangle
-    let mut first_idx = None;
-    let mut last_idx = None;
-
-    for (i, node) in skylines.iter().enumerate() {
-        if node.x < slot.x + slot.w && node.x + node.w > slot.x {
-            if first_idx.is_none() {
-                first_idx = Some(i);
+    // New node starts just below the placed rectangle; nodes it only partly covers keep their
+    // uncovered left/right parts.
+    let (x0, x1) = (slot.x, slot.x + slot.w);
+    let placed = SkylineNode {
+        x: slot.x,
+        y: slot.y + slot.h,
+        w: slot.w,
+    };
+    let mut out = Vec::with_capacity(skylines.len() + 2);
+    let mut inserted = false;
+    for node in skylines.drain(..) {
+        let (nx0, nx1) = (node.x, node.x + node.w);
+        if nx1 <= x0 || nx0 >= x1 {
+            if nx0 >= x1 && !inserted {
+                out.push(placed);
+                inserted = true;
             }
-            last_idx = Some(i);
+            out.push(node);
+            continue;
+        }
+        if nx0 < x0 {
+            out.push(SkylineNode {
+                w: x0 - nx0,
+                ..node
+            });
+        }
+        if !inserted {
+            out.push(placed);
+            inserted = true;
+        }
+        if nx1 > x1 {
+            out.push(SkylineNode {
+                x: x1,
+                w: nx1 - x1,
+                ..node
+            });
         }
     }
-
-    if let (Some(first), Some(last)) = (first_idx, last_idx) {
-        // Create new node at the placed rectangle's top
-        let new_node = SkylineNode {
-            x: slot.x,
-            y: slot.bottom(),
-            w: slot.w,
-        };
-
-        // Remove intersecting nodes and insert new node
-        skylines.drain(first..=last);
-        skylines.insert(first, new_node);
-
-        // Merge adjacent nodes with same height
-        merge_skyline_nodes(skylines);
+    if !inserted {
+        out.push(placed);
     }
+    *skylines = out;
+    merge_skyline_nodes(skylines);
 }
 
 fn merge_skyline_nodes(skylines: &mut Vec<SkylineNode>) {
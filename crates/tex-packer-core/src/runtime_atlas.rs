@@ -1,7 +1,9 @@
 use crate::config::PackerConfig;
 use crate::error::{Result, TexPackerError};
-use crate::model::Frame;
-use crate::runtime::{AtlasSession, RuntimeStats, RuntimeStrategy};
+use crate::model::{Frame, Rect};
+use crate::runtime::{
+    AtlasSession, EvictedEntry, FrameMove, RuntimeBudget, RuntimeStats, RuntimeStrategy,
+};
 use image::{Rgba, RgbaImage};
 
 /// Region that needs to be updated on GPU texture.
@@ -40,6 +42,134 @@ impl UpdateRegion {
     pub fn area(&self) -> u64 {
         (self.width as u64) * (self.height as u64)
     }
+
+    /// Bytes in one row of the region.
+    pub fn row_bytes(&self, format: RuntimePixelFormat) -> usize {
+        self.width as usize * format.bytes_per_pixel()
+    }
+
+    /// Offset of the region's first byte in a page buffer `page_width` pixels wide; upload
+    /// straight from [`RuntimeAtlas::page_bytes`] with this offset and a row stride of
+    /// `page_width * bytes_per_pixel`.
+    pub fn byte_offset(&self, page_width: u32, format: RuntimePixelFormat) -> usize {
+        (self.y as usize * page_width as usize + self.x as usize) * format.bytes_per_pixel()
+    }
+}
+
+/// Pixel layout of [`RuntimeAtlas`] page buffers.
+///
+/// Non-RGBA formats store only what single-channel (glyph, SDF) or two-channel data needs;
+/// `Rgba16F` holds little-endian IEEE half floats, e.g. for lightmaps.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum RuntimePixelFormat {
+    #[default]
+    Rgba8,
+    A8,
+    Rg8,
+    Rgba16F,
+}
+
+impl RuntimePixelFormat {
+    pub fn bytes_per_pixel(self) -> usize {
+        match self {
+            RuntimePixelFormat::Rgba8 => 4,
+            RuntimePixelFormat::A8 => 1,
+            RuntimePixelFormat::Rg8 => 2,
+            RuntimePixelFormat::Rgba16F => 8,
+        }
+    }
+
+    /// Converts an RGBA8 pixel: `A8` keeps alpha, `Rg8` red and green, `Rgba16F` maps each
+    /// channel to 0.0..=1.0.
+    fn encode(self, px: Rgba<u8>, out: &mut [u8]) {
+        let [r, g, b, a] = px.0;
+        match self {
+            RuntimePixelFormat::Rgba8 => out.copy_from_slice(&px.0),
+            RuntimePixelFormat::A8 => out[0] = a,
+            RuntimePixelFormat::Rg8 => out.copy_from_slice(&[r, g]),
+            RuntimePixelFormat::Rgba16F => {
+                for (i, c) in [r, g, b, a].into_iter().enumerate() {
+                    let h = f32_to_f16_bits(c as f32 / 255.0);
+                    out[i * 2..i * 2 + 2].copy_from_slice(&h.to_le_bytes());
+                }
+            }
+        }
+    }
+
+    fn encode_image(self, image: &RgbaImage) -> Vec<u8> {
+        let bpp = self.bytes_per_pixel();
+        let mut out = vec![0u8; image.width() as usize * image.height() as usize * bpp];
+        for (px, dst) in image.pixels().zip(out.chunks_exact_mut(bpp)) {
+            self.encode(*px, dst);
+        }
+        out
+    }
+}
+
+/// Round-to-nearest-even f32 -> IEEE 754 half conversion.
+fn f32_to_f16_bits(v: f32) -> u16 {
+    let x = v.to_bits();
+    let sign = ((x >> 16) & 0x8000) as u16;
+    let exp = ((x >> 23) & 0xff) as i32;
+    let mant = x & 0x7f_ffff;
+    if exp == 0xff {
+        return sign | 0x7c00 | if mant != 0 { 0x200 } else { 0 };
+    }
+    let e = exp - 127 + 15;
+    if e >= 0x1f {
+        return sign | 0x7c00;
+    }
+    let round = |v: u32, rem: u32, halfway: u32| {
+        if rem > halfway || (rem == halfway && v & 1 == 1) {
+            v + 1
+        } else {
+            v
+        }
+    };
+    if e <= 0 {
+        // Subnormal half (or zero)
+        if e < -10 {
+            return sign;
+        }
+        let m = mant | 0x80_0000;
+        let shift = (14 - e) as u32;
+        return sign | round(m >> shift, m & ((1 << shift) - 1), 1 << (shift - 1)) as u16;
+    }
+    // A carry out of the mantissa correctly bumps the exponent (up to infinity)
+    sign | round(((e as u32) << 10) | (mant >> 13), mant & 0x1fff, 0x1000) as u16
+}
+
+/// Pixels of one page: an `RgbaImage` for `Rgba8`, raw bytes otherwise.
+enum PageBuffer {
+    Rgba(RgbaImage),
+    Raw {
+        width: u32,
+        height: u32,
+        data: Vec<u8>,
+    },
+}
+
+impl PageBuffer {
+    fn dimensions(&self) -> (u32, u32) {
+        match self {
+            PageBuffer::Rgba(img) => img.dimensions(),
+            PageBuffer::Raw { width, height, .. } => (*width, *height),
+        }
+    }
+
+    fn bytes(&self) -> &[u8] {
+        match self {
+            PageBuffer::Rgba(img) => img.as_raw(),
+            PageBuffer::Raw { data, .. } => data,
+        }
+    }
+
+    fn bytes_mut(&mut self) -> &mut [u8] {
+        match self {
+            PageBuffer::Rgba(img) => img,
+            PageBuffer::Raw { data, .. } => data,
+        }
+    }
 }
 
 /// Runtime atlas with pixel data management.
@@ -48,18 +178,97 @@ impl UpdateRegion {
 /// Useful for game engines that need to dynamically update GPU textures.
 pub struct RuntimeAtlas {
     session: AtlasSession,
-    pages: Vec<RgbaImage>,
+    pages: Vec<PageBuffer>,
+    format: RuntimePixelFormat,
     background_color: Rgba<u8>,
+    evicted: Vec<EvictedEntry>,
 }
 
 impl RuntimeAtlas {
     /// Create a new runtime atlas with pixel data management.
     pub fn new(cfg: PackerConfig, strategy: RuntimeStrategy) -> Self {
+        // `cfg.background_color`, transparent by default
+        let background_color = Rgba(cfg.background_color.unwrap_or([0, 0, 0, 0]));
         Self {
             session: AtlasSession::new(cfg, strategy),
             pages: Vec::new(),
-            background_color: Rgba([0, 0, 0, 0]), // Transparent by default
+            format: RuntimePixelFormat::Rgba8,
+            background_color,
+            evicted: Vec::new(),
+        }
+    }
+
+    /// Enforces a page/pixel budget with LRU eviction (see [`AtlasSession::with_budget`]).
+    /// Slots of evicted entries are cleared to the background color before the new image is
+    /// drawn; re-upload them from [`drain_evicted`](Self::drain_evicted).
+    pub fn with_budget(mut self, budget: RuntimeBudget) -> Self {
+        self.session = self.session.with_budget(budget);
+        self
+    }
+
+    /// Marks `key` as just used for budget eviction. Returns false if the key is unknown.
+    pub fn touch(&mut self, key: &str) -> bool {
+        self.session.touch(key)
+    }
+
+    /// Entries evicted by the budget since the last call, oldest first.
+    pub fn drain_evicted(&mut self) -> Vec<EvictedEntry> {
+        std::mem::take(&mut self.evicted)
+    }
+
+    /// Rebuilds an atlas from a saved [`AtlasSession`] and its page buffers in `format`, as
+    /// returned by [`page_bytes`](Self::page_bytes) for each page. Fails unless there is one
+    /// buffer of the right size per session page.
+    pub fn restore(
+        session: AtlasSession,
+        format: RuntimePixelFormat,
+        pages: Vec<Vec<u8>>,
+    ) -> Result<Self> {
+        let num_pages = session.stats().num_pages;
+        if pages.len() != num_pages {
+            return Err(TexPackerError::InvalidInput(format!(
+                "{} page buffers for a session with {num_pages} pages",
+                pages.len()
+            )));
         }
+        let (width, height) = (session.cfg.max_width, session.cfg.max_height);
+        let expected = width as usize * height as usize * format.bytes_per_pixel();
+        let pages = pages
+            .into_iter()
+            .enumerate()
+            .map(|(i, data)| {
+                if data.len() != expected {
+                    return Err(TexPackerError::InvalidInput(format!(
+                        "page {i}: {} bytes, expected {expected} for {width}x{height} {format:?}",
+                        data.len()
+                    )));
+                }
+                Ok(match format {
+                    RuntimePixelFormat::Rgba8 => PageBuffer::Rgba(
+                        RgbaImage::from_raw(width, height, data).expect("length checked above"),
+                    ),
+                    _ => PageBuffer::Raw {
+                        width,
+                        height,
+                        data,
+                    },
+                })
+            })
+            .collect::<Result<Vec<_>>>()?;
+        let background_color = Rgba(session.cfg.background_color.unwrap_or([0, 0, 0, 0]));
+        Ok(Self {
+            session,
+            pages,
+            format,
+            background_color,
+            evicted: Vec::new(),
+        })
+    }
+
+    /// The underlying session; persist it (it is `Serialize`) together with the
+    /// [`page_bytes`](Self::page_bytes) of every page and reload with [`restore`](Self::restore).
+    pub fn session(&self) -> &AtlasSession {
+        &self.session
     }
 
     /// Set the background color for new pages.
@@ -68,6 +277,27 @@ impl RuntimeAtlas {
         self
     }
 
+    /// Stores pages in `format` instead of RGBA8. `texture_outlines` and `alpha_bleed` only
+    /// apply to `Rgba8`.
+    ///
+    /// # Panics
+    /// If a page already exists and `format` differs from the current one: existing page buffers
+    /// are not converted. Pick the format before appending, or pass it to
+    /// [`restore`](Self::restore).
+    pub fn with_pixel_format(mut self, format: RuntimePixelFormat) -> Self {
+        assert!(
+            self.pages.is_empty() || format == self.format,
+            "with_pixel_format({format:?}) after pages were created in {:?}",
+            self.format
+        );
+        self.format = format;
+        self
+    }
+
+    pub fn pixel_format(&self) -> RuntimePixelFormat {
+        self.format
+    }
+
     /// Append a texture with its pixel data.
     /// Returns (page_id, frame, update_region).
     pub fn append_with_image(
@@ -77,6 +307,7 @@ impl RuntimeAtlas {
     ) -> Result<(usize, Frame<String>, UpdateRegion)> {
         let (w, h) = image.dimensions();
         let (page_id, frame) = self.session.append(key, w, h)?;
+        self.clear_evicted();
 
         // Ensure page exists
         self.ensure_page(page_id);
@@ -87,10 +318,56 @@ impl RuntimeAtlas {
         Ok((page_id, frame, update_region))
     }
 
+    /// Append a texture whose pixels are already in the atlas [`pixel_format`](Self::pixel_format):
+    /// `w * h * bytes_per_pixel` bytes, rows top to bottom.
+    /// Returns (page_id, frame, update_region).
+    pub fn append_with_pixels(
+        &mut self,
+        key: String,
+        w: u32,
+        h: u32,
+        data: &[u8],
+    ) -> Result<(usize, Frame<String>, UpdateRegion)> {
+        let expected = w as usize * h as usize * self.format.bytes_per_pixel();
+        if data.len() != expected {
+            return Err(TexPackerError::InvalidInput(format!(
+                "'{key}': {} bytes of pixel data, expected {expected} for {w}x{h} {:?}",
+                data.len(),
+                self.format
+            )));
+        }
+        if self.format == RuntimePixelFormat::Rgba8 {
+            let image =
+                RgbaImage::from_raw(w, h, data.to_vec()).expect("pixel data length checked above");
+            return self.append_with_image(key, &image);
+        }
+        let (page_id, frame) = self.session.append(key, w, h)?;
+        self.clear_evicted();
+        self.ensure_page(page_id);
+        let update_region = self.blit_raw(page_id, &frame, data, w, h);
+        Ok((page_id, frame, update_region))
+    }
+
     /// Append a texture by dimensions only (no pixel data).
     /// Returns (page_id, frame).
     pub fn append(&mut self, key: String, w: u32, h: u32) -> Result<(usize, Frame<String>)> {
-        self.session.append(key, w, h)
+        let placed = self.session.append(key, w, h)?;
+        self.clear_evicted();
+        Ok(placed)
+    }
+
+    /// Clears the slots of entries the budget just evicted and queues them for `drain_evicted`.
+    fn clear_evicted(&mut self) {
+        for e in self.session.drain_evicted() {
+            self.clear_region(UpdateRegion {
+                page_id: e.page_id,
+                x: e.slot.x,
+                y: e.slot.y,
+                width: e.slot.w,
+                height: e.slot.h,
+            });
+            self.evicted.push(e);
+        }
     }
 
     /// Evict a texture and optionally clear its region.
@@ -161,14 +438,89 @@ impl RuntimeAtlas {
         }
     }
 
-    /// Get a reference to the pixel data of a page.
+    /// Compacts page `page_id` (see [`AtlasSession::compact`]) and moves the pixels along.
+    /// Vacated areas are cleared to the background color; re-upload each move's `old_rect`
+    /// on `old_page` and `new_rect` on `new_page`.
+    pub fn compact(&mut self, page_id: usize) -> Vec<FrameMove> {
+        let moves = self.session.compact(page_id);
+        self.apply_moves(&moves);
+        moves
+    }
+
+    /// Compacts the whole session (see [`AtlasSession::compact_all`]) and moves the pixels along.
+    pub fn compact_all(&mut self) -> Vec<FrameMove> {
+        let moves = self.session.compact_all();
+        self.apply_moves(&moves);
+        moves
+    }
+
+    /// Copies moved slots from a snapshot of their old pages, after clearing the old slots.
+    fn apply_moves(&mut self, moves: &[FrameMove]) {
+        let region = |page_id: usize, r: &Rect| UpdateRegion {
+            page_id,
+            x: r.x,
+            y: r.y,
+            width: r.w,
+            height: r.h,
+        };
+        let sources: Vec<Option<Vec<u8>>> = moves
+            .iter()
+            .map(|m| self.region_bytes(&region(m.old_page, &m.old_rect)))
+            .collect();
+        for m in moves {
+            self.clear_region(region(m.old_page, &m.old_rect));
+        }
+        for (m, src) in moves.iter().zip(sources) {
+            self.ensure_page(m.new_page);
+            if let Some(src) = src {
+                self.write_region(&region(m.new_page, &m.new_rect), &src);
+            }
+        }
+    }
+
+    /// Pixels of `region` in the atlas format, tightly packed (`region.row_bytes` per row).
+    pub fn region_bytes(&self, region: &UpdateRegion) -> Option<Vec<u8>> {
+        let page = self.pages.get(region.page_id)?;
+        let (pw, ph) = page.dimensions();
+        if region.x + region.width > pw || region.y + region.height > ph {
+            return None;
+        }
+        let stride = pw as usize * self.format.bytes_per_pixel();
+        let row = region.row_bytes(self.format);
+        let bytes = page.bytes();
+        let mut out = Vec::with_capacity(row * region.height as usize);
+        for y in 0..region.height as usize {
+            let start = region.byte_offset(pw, self.format) + y * stride;
+            out.extend_from_slice(&bytes[start..start + row]);
+        }
+        Some(out)
+    }
+
+    /// Whole page buffer in the atlas format; rows are `page_width * bytes_per_pixel` bytes.
+    pub fn page_bytes(&self, page_id: usize) -> Option<&[u8]> {
+        self.pages.get(page_id).map(PageBuffer::bytes)
+    }
+
+    /// Mutable page buffer in the atlas format.
+    pub fn page_bytes_mut(&mut self, page_id: usize) -> Option<&mut [u8]> {
+        self.pages.get_mut(page_id).map(PageBuffer::bytes_mut)
+    }
+
+    /// Get a reference to the pixel data of a page; `None` for non-`Rgba8` atlases (use
+    /// [`page_bytes`](Self::page_bytes)).
     pub fn get_page_image(&self, page_id: usize) -> Option<&RgbaImage> {
-        self.pages.get(page_id)
+        match self.pages.get(page_id)? {
+            PageBuffer::Rgba(img) => Some(img),
+            PageBuffer::Raw { .. } => None,
+        }
     }
 
-    /// Get a mutable reference to the pixel data of a page.
+    /// Get a mutable reference to the pixel data of a page; `None` for non-`Rgba8` atlases.
     pub fn get_page_image_mut(&mut self, page_id: usize) -> Option<&mut RgbaImage> {
-        self.pages.get_mut(page_id)
+        match self.pages.get_mut(page_id)? {
+            PageBuffer::Rgba(img) => Some(img),
+            PageBuffer::Raw { .. } => None,
+        }
     }
 
     /// Get the number of pages with pixel data.
@@ -203,13 +555,20 @@ impl RuntimeAtlas {
 
     /// Ensure a page exists, creating it if necessary.
     fn ensure_page(&mut self, page_id: usize) {
+        let (width, height) = (self.session.cfg.max_width, self.session.cfg.max_height);
         while self.pages.len() <= page_id {
-            let page_img = RgbaImage::from_pixel(
-                self.session.cfg.max_width,
-                self.session.cfg.max_height,
-                self.background_color,
-            );
-            self.pages.push(page_img);
+            let page = if self.format == RuntimePixelFormat::Rgba8 {
+                PageBuffer::Rgba(RgbaImage::from_pixel(width, height, self.background_color))
+            } else {
+                PageBuffer::Raw {
+                    width,
+                    height,
+                    data: self
+                        .background_bytes()
+                        .repeat(width as usize * height as usize),
+                }
+            };
+            self.pages.push(page);
         }
     }
 
@@ -220,10 +579,15 @@ impl RuntimeAtlas {
         frame: &Frame<String>,
         image: &RgbaImage,
     ) -> Result<UpdateRegion> {
-        let page = self
-            .pages
-            .get_mut(page_id)
-            .ok_or_else(|| TexPackerError::InvalidConfig("Page not found".into()))?;
+        let page = match self.pages.get_mut(page_id) {
+            Some(PageBuffer::Rgba(img)) => img,
+            Some(PageBuffer::Raw { .. }) => {
+                let data = self.format.encode_image(image);
+                let (w, h) = image.dimensions();
+                return Ok(self.blit_raw(page_id, frame, &data, w, h));
+            }
+            None => return Err(TexPackerError::InvalidConfig("Page not found".into())),
+        };
 
         let (src_w, src_h) = image.dimensions();
         let dst_x = frame.frame.x;
@@ -231,6 +595,7 @@ impl RuntimeAtlas {
 
         // Reuse core compositing (with extrusion and optional outlines)
         let extrude = self.session.cfg.texture_extrusion;
+        let extrude_mode = self.session.cfg.extrude_mode_for(&frame.key);
         let outlines = self.session.cfg.texture_outlines;
         crate::compositing::blit_rgba(
             image,
@@ -241,8 +606,9 @@ impl RuntimeAtlas {
             0,
             src_w,
             src_h,
-            frame.rotated,
+            frame.rotated.then_some(self.session.cfg.rotation_direction),
             extrude,
+            extrude_mode,
             outlines,
         );
 
@@ -258,6 +624,9 @@ impl RuntimeAtlas {
         if start_y + height > page.height() {
             height = page.height() - start_y;
         }
+        if let Some(radius) = self.session.cfg.alpha_bleed {
+            crate::compositing::alpha_bleed(page, start_x, start_y, width, height, radius);
+        }
 
         Ok(UpdateRegion {
             page_id,
@@ -268,12 +637,101 @@ impl RuntimeAtlas {
         })
     }
 
+    /// Blit raw pixels in the atlas format to a page, with rotation and edge extrusion.
+    fn blit_raw(
+        &mut self,
+        page_id: usize,
+        frame: &Frame<String>,
+        data: &[u8],
+        src_w: u32,
+        src_h: u32,
+    ) -> UpdateRegion {
+        let bpp = self.format.bytes_per_pixel();
+        let extrude = self.session.cfg.texture_extrusion;
+        let mode = self.session.cfg.extrude_mode_for(&frame.key);
+        let direction = self.session.cfg.rotation_direction;
+        let Some(page) = self.pages.get_mut(page_id) else {
+            return UpdateRegion::empty();
+        };
+        if frame.frame.w == 0 || frame.frame.h == 0 {
+            return UpdateRegion::empty();
+        }
+        let (pw, ph) = page.dimensions();
+        // Runtime frames keep the source size; rotated content is drawn transposed
+        let (fw, fh) = if frame.rotated {
+            (src_h, src_w)
+        } else {
+            (src_w, src_h)
+        };
+        let (fx, fy) = (frame.frame.x, frame.frame.y);
+        let start_x = fx.saturating_sub(extrude);
+        let start_y = fy.saturating_sub(extrude);
+        let end_x = (fx + fw + extrude).min(pw);
+        let end_y = (fy + fh + extrude).min(ph);
+        let bytes = page.bytes_mut();
+        for y in start_y..end_y {
+            // Extruded pixels sample the content as `mode` maps them
+            let yy = mode.map(y as i64 - fy as i64, fh as i64) as u32;
+            for x in start_x..end_x {
+                let xx = mode.map(x as i64 - fx as i64, fw as i64) as u32;
+                // Same mapping as `compositing::blit_rgba`
+                let (sx, sy) = if frame.rotated {
+                    direction.source_of(xx, yy, src_w, src_h)
+                } else {
+                    (xx, yy)
+                };
+                let src = (sy as usize * src_w as usize + sx as usize) * bpp;
+                let dst = (y as usize * pw as usize + x as usize) * bpp;
+                bytes[dst..dst + bpp].copy_from_slice(&data[src..src + bpp]);
+            }
+        }
+        UpdateRegion {
+            page_id,
+            x: start_x,
+            y: start_y,
+            width: end_x - start_x,
+            height: end_y - start_y,
+        }
+    }
+
+    /// Writes tightly packed pixels in the atlas format into `region`.
+    fn write_region(&mut self, region: &UpdateRegion, data: &[u8]) {
+        let format = self.format;
+        let Some(page) = self.pages.get_mut(region.page_id) else {
+            return;
+        };
+        let (pw, ph) = page.dimensions();
+        if region.x + region.width > pw || region.y + region.height > ph {
+            return;
+        }
+        let stride = pw as usize * format.bytes_per_pixel();
+        let row = region.row_bytes(format);
+        let offset = region.byte_offset(pw, format);
+        let bytes = page.bytes_mut();
+        for (y, src) in data.chunks_exact(row.max(1)).enumerate() {
+            let start = offset + y * stride;
+            bytes[start..start + row].copy_from_slice(src);
+        }
+    }
+
+    fn background_bytes(&self) -> Vec<u8> {
+        let mut px = vec![0u8; self.format.bytes_per_pixel()];
+        self.format.encode(self.background_color, &mut px);
+        px
+    }
+
     /// Clear a region on a page.
     fn clear_region(&mut self, region: UpdateRegion) {
+        let format = self.format;
+        let bg = self.background_bytes();
         if let Some(page) = self.pages.get_mut(region.page_id) {
-            for y in region.y..(region.y + region.height).min(page.height()) {
-                for x in region.x..(region.x + region.width).min(page.width()) {
-                    page.put_pixel(x, y, self.background_color);
+            let (pw, ph) = page.dimensions();
+            let bpp = format.bytes_per_pixel();
+            let bytes = page.bytes_mut();
+            for y in region.y..(region.y + region.height).min(ph) {
+                for x in region.x..(region.x + region.width).min(pw) {
+                    let at = (y as usize * pw as usize + x as usize) * bpp;
+                    bytes[at..at + bpp].copy_from_slice(&bg);
                 }
             }
         }
@@ -0,0 +1,117 @@
+//! JSON Schemas (draft 2020-12) for `PackerConfig` and the exported atlas JSON formats.
+
+use crate::config::PackerConfig;
+use serde_json::Value;
+
+/// Schema of a serialized [`PackerConfig`] (JSON or YAML config files).
+pub fn config_schema() -> Value {
+    schemars::schema_for!(PackerConfig).into()
+}
+
+/// Schema of the `json-hash` export ([`crate::to_json_hash`]).
+pub fn atlas_hash_schema() -> Value {
+    schemars::schema_for!(formats::HashAtlas).into()
+}
+
+/// Schema of the `json-array` export ([`crate::to_json_array`]).
+pub fn atlas_array_schema() -> Value {
+    schemars::schema_for!(formats::ArrayAtlas).into()
+}
+
+/// Schema-only mirrors of the documents built by the JSON exporters.
+#[allow(dead_code)]
+mod formats {
+    use crate::model::{Meta, NinePatch, Rect};
+    use schemars::JsonSchema;
+    use std::collections::BTreeMap;
+
+    #[derive(JsonSchema)]
+    pub struct Size {
+        w: u32,
+        h: u32,
+    }
+
+    #[derive(JsonSchema)]
+    pub struct Vec2 {
+        x: f64,
+        y: f64,
+    }
+
+    /// Frame rect normalized to the page (`JsonOptions::uvs`), optionally inset.
+    #[derive(JsonSchema)]
+    pub struct Uv {
+        u0: f64,
+        v0: f64,
+        u1: f64,
+        v1: f64,
+    }
+
+    /// tex-packer atlas, `json-hash` layout: frames keyed by name.
+    #[derive(JsonSchema)]
+    pub struct HashAtlas {
+        frames: BTreeMap<String, HashFrame>,
+        meta: Meta,
+    }
+
+    #[derive(JsonSchema)]
+    #[serde(rename_all = "camelCase")]
+    pub struct HashFrame {
+        id: u64,
+        frame: Rect,
+        rotated: bool,
+        trimmed: bool,
+        /// Only present (and `true`) on degenerate frames.
+        #[serde(default)]
+        degenerate: bool,
+        sprite_source_size: Rect,
+        source_size: Size,
+        pivot: Vec2,
+        page: usize,
+        page_size: Size,
+        /// XXH64 of the trimmed pixels, 16 hex digits.
+        #[schemars(regex(pattern = "^[0-9a-f]{16}$"))]
+        content_hash: Option<String>,
+        nine_patch: Option<NinePatch>,
+        /// Factor the input was downscaled by to fit its page.
+        scale: Option<f32>,
+        uv: Option<Uv>,
+    }
+
+    /// tex-packer atlas, `json-array` layout: pages with their frames.
+    #[derive(JsonSchema)]
+    pub struct ArrayAtlas {
+        pages: Vec<ArrayPage>,
+        meta: Meta,
+    }
+
+    #[derive(JsonSchema)]
+    pub struct ArrayPage {
+        id: usize,
+        width: u32,
+        height: u32,
+        frames: Vec<ArrayFrame>,
+    }
+
+    #[derive(JsonSchema)]
+    #[serde(rename_all = "camelCase")]
+    pub struct ArrayFrame {
+        key: String,
+        id: u64,
+        frame: Rect,
+        rotated: bool,
+        trimmed: bool,
+        /// Only present (and `true`) on degenerate frames.
+        #[serde(default)]
+        degenerate: bool,
+        sprite_source_size: Rect,
+        source_size: Size,
+        pivot: Vec2,
+        /// XXH64 of the trimmed pixels, 16 hex digits.
+        #[schemars(regex(pattern = "^[0-9a-f]{16}$"))]
+        content_hash: Option<String>,
+        nine_patch: Option<NinePatch>,
+        /// Factor the input was downscaled by to fit its page.
+        scale: Option<f32>,
+        uv: Option<Uv>,
+    }
+}
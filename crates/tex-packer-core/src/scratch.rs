@@ -0,0 +1,197 @@
+use crate::error::{Result, TexPackerError};
+use image::RgbaImage;
+use memmap2::{MmapMut, MmapOptions};
+use std::fs::OpenOptions;
+use std::io::Write;
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Mutex, MutexGuard};
+
+static NEXT_ID: AtomicU64 = AtomicU64::new(0);
+
+/// Rows per independently locked mapping.
+const BAND_ROWS: u32 = 64;
+
+/// RGBA8 page kept in a memory-mapped temporary file instead of RAM (`PackerConfig::low_memory`).
+///
+/// The page is mapped in bands of [`BAND_ROWS`] rows, each behind its own lock, so frames in
+/// different bands are copied in concurrently with plain `memcpy`s; the OS pages the file in
+/// and out, so resident memory stays bounded by what it keeps cached. The file is deleted on
+/// drop.
+pub struct ScratchImage {
+    bands: Vec<Mutex<MmapMut>>,
+    path: PathBuf,
+    width: u32,
+    height: u32,
+}
+
+impl ScratchImage {
+    /// Creates a fully transparent `width` x `height` page in the system temp directory.
+    pub fn new(width: u32, height: u32) -> Result<Self> {
+        if width == 0 || height == 0 {
+            return Err(TexPackerError::InvalidDimensions { width, height });
+        }
+        if cfg!(target_arch = "wasm32") {
+            return Err(TexPackerError::InvalidConfig(
+                "`low_memory` needs a file system, which wasm32 does not have".into(),
+            ));
+        }
+        let path = std::env::temp_dir().join(format!(
+            "tex-packer-{}-{}.rgba",
+            std::process::id(),
+            NEXT_ID.fetch_add(1, Ordering::Relaxed)
+        ));
+        let file = OpenOptions::new()
+            .read(true)
+            .write(true)
+            .create_new(true)
+            .open(&path)?;
+        let row_bytes = width as u64 * 4;
+        // Sparse zero fill: transparent black, like `RgbaImage::new`
+        file.set_len(row_bytes * height as u64)?;
+        let bands = (0..height)
+            .step_by(BAND_ROWS as usize)
+            .map(|y| {
+                let rows = BAND_ROWS.min(height - y) as u64;
+                // SAFETY: the file is private to this page (created with `create_new`, never
+                // resized after `set_len`), so nothing else writes or truncates it while the
+                // maps are alive.
+                let map = unsafe {
+                    MmapOptions::new()
+                        .offset(y as u64 * row_bytes)
+                        .len((rows * row_bytes) as usize)
+                        .map_mut(&file)?
+                };
+                Ok(Mutex::new(map))
+            })
+            .collect::<Result<Vec<_>>>();
+        let bands = match bands {
+            Ok(bands) => bands,
+            Err(e) => {
+                let _ = std::fs::remove_file(&path);
+                return Err(e);
+            }
+        };
+        Ok(Self {
+            bands,
+            path,
+            width,
+            height,
+        })
+    }
+
+    pub fn width(&self) -> u32 {
+        self.width
+    }
+
+    pub fn height(&self) -> u32 {
+        self.height
+    }
+
+    pub fn dimensions(&self) -> (u32, u32) {
+        (self.width, self.height)
+    }
+
+    /// Copies `tile` to (x, y), clipped to the page. Only the bands the tile covers are locked.
+    pub fn write_tile(&self, x: u32, y: u32, tile: &RgbaImage) -> Result<()> {
+        if x >= self.width || y >= self.height {
+            return Ok(());
+        }
+        let cols = tile.width().min(self.width - x) as usize * 4;
+        let rows = tile.height().min(self.height - y);
+        if rows == 0 {
+            return Ok(());
+        }
+        let stride = tile.width() as usize * 4;
+        let raw = tile.as_raw();
+        let first = (y / BAND_ROWS) as usize;
+        let mut bands = self.lock_bands(first..=((y + rows - 1) / BAND_ROWS) as usize);
+        for row in 0..rows {
+            let (band, offset) = self.locate(x, y + row);
+            let start = row as usize * stride;
+            bands[band - first][offset..offset + cols].copy_from_slice(&raw[start..start + cols]);
+        }
+        Ok(())
+    }
+
+    /// Fills the whole page with `color`.
+    pub fn fill(&self, color: [u8; 4]) -> Result<()> {
+        for band in &self.bands {
+            let mut band = lock(band);
+            for px in band.chunks_exact_mut(4) {
+                px.copy_from_slice(&color);
+            }
+        }
+        Ok(())
+    }
+
+    /// Reads row `y` into `buf` (`width * 4` bytes).
+    pub fn read_row(&self, y: u32, buf: &mut [u8]) -> Result<()> {
+        let (band, offset) = self.locate(0, y);
+        let len = self.width as usize * 4;
+        buf[..len].copy_from_slice(&lock(&self.bands[band])[offset..offset + len]);
+        Ok(())
+    }
+
+    /// Loads the whole page into memory.
+    pub fn to_rgba(&self) -> Result<RgbaImage> {
+        let mut raw = Vec::with_capacity(self.width as usize * self.height as usize * 4);
+        for band in &self.bands {
+            raw.extend_from_slice(&lock(band));
+        }
+        Ok(RgbaImage::from_raw(self.width, self.height, raw).expect("buffer matches dimensions"))
+    }
+
+    /// Encodes the page as PNG, streaming one row at a time.
+    pub fn write_png<W: Write>(&self, out: W) -> Result<()> {
+        let mut row = vec![0u8; self.width as usize * 4];
+        write_png_rows(out, self.width, self.height, |y, buf| {
+            self.read_row(y, &mut row)?;
+            buf.write_all(&row)?;
+            Ok(())
+        })
+    }
+
+    /// Band holding row `y` and the byte offset of (x, y) within it.
+    fn locate(&self, x: u32, y: u32) -> (usize, usize) {
+        let band = (y / BAND_ROWS) as usize;
+        let offset = ((y % BAND_ROWS) as usize * self.width as usize + x as usize) * 4;
+        (band, offset)
+    }
+
+    /// Locks `range` in ascending order, so concurrent writers cannot deadlock.
+    fn lock_bands(&self, range: std::ops::RangeInclusive<usize>) -> Vec<MutexGuard<'_, MmapMut>> {
+        self.bands[range].iter().map(lock).collect()
+    }
+}
+
+fn lock(band: &Mutex<MmapMut>) -> MutexGuard<'_, MmapMut> {
+    band.lock().unwrap_or_else(|e| e.into_inner())
+}
+
+impl Drop for ScratchImage {
+    fn drop(&mut self) {
+        // Unmap first: some platforms refuse to delete a mapped file
+        self.bands.clear();
+        let _ = std::fs::remove_file(&self.path);
+    }
+}
+
+/// Streams an RGBA8 PNG; `row(y, sink)` writes the bytes of row `y`.
+pub(crate) fn write_png_rows<W: Write>(
+    out: W,
+    width: u32,
+    height: u32,
+    mut row: impl FnMut(u32, &mut dyn Write) -> Result<()>,
+) -> Result<()> {
+    let png_err = |e: png::EncodingError| TexPackerError::Encode(e.to_string());
+    let mut encoder = png::Encoder::new(out, width, height);
+    encoder.set_color(png::ColorType::Rgba);
+    encoder.set_depth(png::BitDepth::Eight);
+    let mut writer = encoder.write_header().map_err(png_err)?;
+    let mut stream = writer.stream_writer().map_err(png_err)?;
+    for y in 0..height {
+        row(y, &mut stream)?;
+    }
+    stream.finish().map_err(png_err)
+}
@@ -0,0 +1,130 @@
+//! Signed distance field generation for [`PackerConfig::sdf`](crate::config::PackerConfig).
+//!
+//! Inputs are binarized at [`SdfOptions::threshold`], then every pixel gets the exact Euclidean
+//! distance between pixel centers to the nearest pixel on the other side of the edge
+//! (Felzenszwalb-Huttenlocher distance transform, linear in the pixel count). The edge is taken
+//! to lie halfway between the two pixels.
+
+use image::{Rgba, RgbaImage};
+
+use crate::config::{SdfMode, SdfOptions};
+
+/// Pixels added on each side of an input so the distance band fits: `range / 2`, rounded up.
+pub fn sdf_margin(opts: &SdfOptions) -> u32 {
+    opts.range.div_ceil(2)
+}
+
+/// Converts `img` to a distance field per `opts`, [`sdf_margin`] pixels larger on each side.
+///
+/// In [`SdfMode::Multi`] a color channel counts as inside only where alpha does too, so
+/// straight-alpha inputs with colored transparent pixels work. Empty images are returned as is.
+pub fn generate_sdf(img: &RgbaImage, opts: &SdfOptions) -> RgbaImage {
+    let (w, h) = img.dimensions();
+    if w == 0 || h == 0 {
+        return img.clone();
+    }
+    let m = sdf_margin(opts);
+    let (ow, oh) = (w + 2 * m, h + 2 * m);
+    let inside = |px: &Rgba<u8>, c: usize| px[c] >= opts.threshold && px[3] >= opts.threshold;
+    let mask = |c: usize| -> Vec<bool> {
+        (0..oh)
+            .flat_map(|y| (0..ow).map(move |x| (x, y)))
+            .map(|(x, y)| {
+                let (sx, sy) = (x.wrapping_sub(m), y.wrapping_sub(m));
+                sx < w && sy < h && inside(img.get_pixel(sx, sy), c)
+            })
+            .collect()
+    };
+    let field = |c: usize| signed_field(&mask(c), ow as usize, oh as usize, opts.range);
+    let alpha = field(3);
+    let data = match opts.mode {
+        SdfMode::Single => alpha.iter().flat_map(|&a| [a; 4]).collect(),
+        SdfMode::Multi => {
+            let (r, g, b) = (field(0), field(1), field(2));
+            (0..alpha.len())
+                .flat_map(|i| [r[i], g[i], b[i], alpha[i]])
+                .collect()
+        }
+    };
+    RgbaImage::from_raw(ow, oh, data).expect("buffer matches the padded size")
+}
+
+/// Encodes the signed distance of every cell to the edge of `inside` as `0.5 + d / range`,
+/// clamped and scaled to 0-255.
+fn signed_field(inside: &[bool], w: usize, h: usize, range: u32) -> Vec<u8> {
+    let to_inside = squared_distances(inside, w, h, true);
+    let to_outside = squared_distances(inside, w, h, false);
+    inside
+        .iter()
+        .zip(to_inside.iter().zip(&to_outside))
+        .map(|(&is_in, (&d_in, &d_out))| {
+            let d = if is_in {
+                d_out.sqrt() - 0.5
+            } else {
+                0.5 - d_in.sqrt()
+            };
+            ((0.5 + d / range as f64).clamp(0.0, 1.0) * 255.0).round() as u8
+        })
+        .collect()
+}
+
+const FAR: f64 = 1e20;
+
+/// Squared distance from every cell to the nearest cell whose `mask` value equals `seed`.
+fn squared_distances(mask: &[bool], w: usize, h: usize, seed: bool) -> Vec<f64> {
+    let mut grid: Vec<f64> = mask
+        .iter()
+        .map(|&m| if m == seed { 0.0 } else { FAR })
+        .collect();
+    let n = w.max(h);
+    let mut f = vec![0.0; n];
+    let mut d = vec![0.0; n];
+    let mut v = vec![0usize; n];
+    let mut z = vec![0.0; n + 1];
+    for x in 0..w {
+        for y in 0..h {
+            f[y] = grid[y * w + x];
+        }
+        transform_1d(&f[..h], &mut d[..h], &mut v, &mut z);
+        for y in 0..h {
+            grid[y * w + x] = d[y];
+        }
+    }
+    for row in grid.chunks_exact_mut(w) {
+        f[..w].copy_from_slice(row);
+        transform_1d(&f[..w], &mut d[..w], &mut v, &mut z);
+        row.copy_from_slice(&d[..w]);
+    }
+    grid
+}
+
+/// One-dimensional squared distance transform of `f` (lower envelope of parabolas).
+fn transform_1d(f: &[f64], d: &mut [f64], v: &mut [usize], z: &mut [f64]) {
+    let intersect = |q: usize, p: usize| {
+        let (qf, pf) = (q as f64, p as f64);
+        ((f[q] + qf * qf) - (f[p] + pf * pf)) / (2.0 * (qf - pf))
+    };
+    let mut k = 0;
+    v[0] = 0;
+    z[0] = -FAR;
+    z[1] = FAR;
+    for q in 1..f.len() {
+        let mut s = intersect(q, v[k]);
+        while s <= z[k] {
+            k -= 1;
+            s = intersect(q, v[k]);
+        }
+        k += 1;
+        v[k] = q;
+        z[k] = s;
+        z[k + 1] = FAR;
+    }
+    k = 0;
+    for (q, out) in d.iter_mut().enumerate() {
+        while z[k + 1] < q as f64 {
+            k += 1;
+        }
+        let dq = q as f64 - v[k] as f64;
+        *out = dq * dq + f[v[k]];
+    }
+}
@@ -0,0 +1,94 @@
+//! Off-thread packing for UIs: [`spawn_pack`] runs [`pack_images_with`] on a worker thread
+//! and returns a [`PackHandle`] to poll for progress and the result, or to cancel the run.
+
+use crate::config::PackerConfig;
+use crate::error::Result;
+use crate::pipeline::{InputImage, PackOutput, pack_images_with};
+use crate::progress::{CancellationToken, PackControl, PackProgress};
+use std::sync::{Arc, Mutex};
+use std::thread::JoinHandle;
+
+/// A packing run on a worker thread.
+///
+/// Dropping the handle cancels the run and detaches the thread.
+pub struct PackHandle {
+    thread: Option<JoinHandle<Result<PackOutput>>>,
+    cancel: CancellationToken,
+    progress: Arc<Mutex<Option<PackProgress>>>,
+}
+
+/// Packs `inputs` with `cfg` on a new thread.
+pub fn spawn_pack(inputs: Vec<InputImage>, cfg: PackerConfig) -> PackHandle {
+    spawn_pack_with(inputs, cfg, PackControl::new())
+}
+
+/// [`spawn_pack`] that also forwards progress to `ctl` (e.g. to request a repaint) and honours
+/// its cancellation tokens in addition to [`PackHandle::cancel`].
+pub fn spawn_pack_with(inputs: Vec<InputImage>, cfg: PackerConfig, ctl: PackControl) -> PackHandle {
+    let cancel = CancellationToken::new();
+    let progress = Arc::new(Mutex::new(None));
+    let latest = Arc::clone(&progress);
+    let ctl = ctl
+        .on_progress(move |p| *latest.lock().unwrap() = Some(p))
+        .cancel_token(cancel.clone());
+    let thread = std::thread::spawn(move || pack_images_with(inputs, cfg, &ctl));
+    PackHandle {
+        thread: Some(thread),
+        cancel,
+        progress,
+    }
+}
+
+impl PackHandle {
+    /// Latest progress report, `None` until the first one arrives.
+    pub fn progress(&self) -> Option<PackProgress> {
+        *self.progress.lock().unwrap()
+    }
+
+    /// Asks the worker to stop; the result becomes `Err(TexPackerError::Cancelled)` unless the
+    /// run already finished.
+    pub fn cancel(&self) {
+        self.cancel.cancel();
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.cancel.is_cancelled()
+    }
+
+    /// Whether the result is ready (or was already taken).
+    pub fn is_finished(&self) -> bool {
+        self.thread.as_ref().is_none_or(JoinHandle::is_finished)
+    }
+
+    /// The result once the worker is done, without blocking. Returns `None` while running and
+    /// after the result has been taken.
+    pub fn try_result(&mut self) -> Option<Result<PackOutput>> {
+        if !self.thread.as_ref()?.is_finished() {
+            return None;
+        }
+        self.thread.take().map(join)
+    }
+
+    /// Blocks until the worker is done.
+    ///
+    /// # Panics
+    /// If the result was already taken with [`try_result`](Self::try_result), or the worker
+    /// panicked (the panic is resumed on this thread).
+    pub fn wait(mut self) -> Result<PackOutput> {
+        join(self.thread.take().expect("pack result already taken"))
+    }
+}
+
+impl Drop for PackHandle {
+    fn drop(&mut self) {
+        if self.thread.is_some() {
+            self.cancel.cancel();
+        }
+    }
+}
+
+fn join(thread: JoinHandle<Result<PackOutput>>) -> Result<PackOutput> {
+    thread
+        .join()
+        .unwrap_or_else(|panic| std::panic::resume_unwind(panic))
+}
@@ -0,0 +1,482 @@
+//! Handlebars templates for arbitrary engine formats.
+//!
+//! A template renders against this context (see [`TemplateContext`]):
+//!
+//! - `name`: atlas name passed to [`AtlasTemplate::render`]
+//! - `meta`: the atlas [`Meta`](crate::model::Meta)
+//! - `pages[]`: `index`, `image`, `size {w, h}` and `sprites[]` with `name`, `id`,
+//!   `content_hash` (hex), `frame`, `rotated`, `trimmed`, `sprite_source_size`, `source_size`,
+//!   `pivot`, `nine_patch` and `scale` (only for frames downscaled to fit their page)
+//!
+//! Helpers on top of the handlebars built-ins:
+//!
+//! - `uv rect size [component]`: normalized `{u0, v0, u1, v1}` of `rect` on a page of `size`, or
+//!   one of its components (`{{uv frame ../size "u0"}}`)
+//! - `basename path` / `stem path`: last path segment, without its extension for `stem`
+//! - `json value`: compact JSON (use triple braces, `{{{json name}}}`, to skip HTML escaping)
+//! - `add`, `sub`, `mul`, `div`, `min`, `max` (two or more numbers), `floor`, `ceil`, `round`
+//!
+//! A template may start with a manifest header, a handlebars comment whose first line is
+//! `tex-packer`, to write one file per page or per frame instead of a single file:
+//!
+//! ```text
+//! {{!-- tex-packer
+//! each: frame
+//! path: sprites/{{stem sprite.name}}.json
+//! --}}
+//! ```
+//!
+//! `each` is `atlas` (default), `page` or `frame`; `path` is itself a template and is required
+//! for `page` and `frame`. A per-page render sees `name`, `meta`, `page_count` and `page`; a
+//! per-frame render additionally sees `sprite`, and its `page` has no `sprites`.
+
+use crate::error::{Result, TexPackerError};
+use crate::model::{Atlas, Meta, NinePatch, Pivot, Rect};
+use handlebars::{
+    Context, Handlebars, Helper, HelperDef, RenderContext, RenderError, RenderErrorReason,
+    ScopedJson,
+};
+use serde::Serialize;
+use serde_json::Value;
+use std::collections::HashSet;
+use std::path::{Component, Path};
+
+/// Width and height of a page or source image.
+#[derive(Debug, Clone, Copy, Serialize)]
+pub struct TemplateSize {
+    pub w: u32,
+    pub h: u32,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct TemplateSprite {
+    pub name: String,
+    pub id: u64,
+    /// Content hash as 16 hex digits.
+    pub content_hash: Option<String>,
+    pub frame: Rect,
+    pub rotated: bool,
+    pub trimmed: bool,
+    pub sprite_source_size: Rect,
+    pub source_size: TemplateSize,
+    pub pivot: Pivot,
+    pub nine_patch: Option<NinePatch>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub scale: Option<f32>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct TemplatePage {
+    pub index: usize,
+    pub image: String,
+    pub size: TemplateSize,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sprites: Option<Vec<TemplateSprite>>,
+}
+
+/// Data a template renders against.
+#[derive(Debug, Clone, Serialize)]
+pub struct TemplateContext {
+    pub name: String,
+    pub meta: Meta,
+    pub pages: Vec<TemplatePage>,
+}
+
+impl TemplateContext {
+    /// `page_names` are the page texture names, falling back to `page_<id>.png`.
+    pub fn new<K: ToString>(atlas: &Atlas<K>, page_names: &[String], name: &str) -> Self {
+        let pages = atlas
+            .pages
+            .iter()
+            .enumerate()
+            .map(|(index, page)| TemplatePage {
+                index,
+                image: page_names
+                    .get(index)
+                    .cloned()
+                    .unwrap_or_else(|| format!("page_{}.png", page.id)),
+                size: TemplateSize {
+                    w: page.width,
+                    h: page.height,
+                },
+                sprites: Some(
+                    page.frames
+                        .iter()
+                        .map(|f| TemplateSprite {
+                            name: f.key.to_string(),
+                            id: f.id,
+                            content_hash: f.content_hash.map(|h| format!("{h:016x}")),
+                            frame: f.frame,
+                            rotated: f.rotated,
+                            trimmed: f.trimmed,
+                            sprite_source_size: f.source,
+                            source_size: TemplateSize {
+                                w: f.source_size.0,
+                                h: f.source_size.1,
+                            },
+                            pivot: f.pivot,
+                            nine_patch: f.nine_patch,
+                            scale: f.scale,
+                        })
+                        .collect(),
+                ),
+            })
+            .collect();
+        Self {
+            name: name.to_string(),
+            meta: atlas.meta.clone(),
+            pages,
+        }
+    }
+}
+
+/// What a template renders once per output file.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum TemplateScope {
+    /// One file for the whole atlas.
+    #[default]
+    Atlas,
+    /// One file per page.
+    Page,
+    /// One file per frame.
+    Frame,
+}
+
+/// A rendered file; `path` is `None` for an atlas-scope template without a `path`, leaving the
+/// file name to the caller.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TemplateFile {
+    pub path: Option<String>,
+    pub contents: String,
+}
+
+/// A parsed template: manifest header plus body.
+#[derive(Debug, Clone)]
+pub struct AtlasTemplate {
+    pub scope: TemplateScope,
+    pub path: Option<String>,
+    body: String,
+}
+
+/// Built-in engine templates: `unity`, `godot`, `phaser3` (multi-atlas), `phaser3_single`,
+/// `cocos` and `unreal`.
+pub fn builtin_template(engine: &str) -> Option<&'static str> {
+    Some(match engine.to_ascii_lowercase().as_str() {
+        "unity" => include_str!("templates/unity.hbs"),
+        "godot" => include_str!("templates/godot.hbs"),
+        "phaser3" => include_str!("templates/phaser3_multiatlas.hbs"),
+        "phaser3_single" => include_str!("templates/phaser3_singleatlas.hbs"),
+        "cocos" => include_str!("templates/cocos.hbs"),
+        "unreal" => include_str!("templates/unreal.hbs"),
+        _ => return None,
+    })
+}
+
+fn invalid(msg: impl std::fmt::Display) -> TexPackerError {
+    TexPackerError::InvalidInput(format!("template: {msg}"))
+}
+
+impl AtlasTemplate {
+    /// Splits off and parses the manifest header, if any, and checks the template syntax.
+    pub fn parse(source: &str) -> Result<Self> {
+        let trimmed = source.trim_start();
+        let mut template = Self {
+            scope: TemplateScope::Atlas,
+            path: None,
+            body: source.to_string(),
+        };
+        if let Some(rest) = trimmed.strip_prefix("{{!--")
+            && rest
+                .trim_start_matches([' ', '\t'])
+                .starts_with("tex-packer")
+        {
+            let end = rest
+                .find("--}}")
+                .ok_or_else(|| invalid("unterminated manifest header"))?;
+            let mut lines = rest[..end].lines();
+            lines.next();
+            for line in lines.map(str::trim).filter(|l| !l.is_empty()) {
+                let (key, value) = line.split_once(':').ok_or_else(|| {
+                    invalid(format!("manifest line `{line}` is not `key: value`"))
+                })?;
+                match (key.trim(), value.trim()) {
+                    ("each", "atlas") => template.scope = TemplateScope::Atlas,
+                    ("each", "page") => template.scope = TemplateScope::Page,
+                    ("each", "frame") => template.scope = TemplateScope::Frame,
+                    ("each", other) => {
+                        return Err(invalid(format!(
+                            "manifest `each: {other}`; expected atlas, page or frame"
+                        )));
+                    }
+                    ("path", path) => template.path = Some(path.to_string()),
+                    (other, _) => return Err(invalid(format!("unknown manifest key `{other}`"))),
+                }
+            }
+            // Drop the header and the line break after it
+            let body = &rest[end + 4..];
+            template.body = body
+                .strip_prefix("\r\n")
+                .or_else(|| body.strip_prefix('\n'))
+                .unwrap_or(body)
+                .to_string();
+        }
+        if template.scope != TemplateScope::Atlas && template.path.is_none() {
+            return Err(invalid("manifest `each: page|frame` needs a `path`"));
+        }
+        template.registries()?;
+        Ok(template)
+    }
+
+    /// Renders the template against `atlas`, one [`TemplateFile`] per output file.
+    ///
+    /// Rendered paths must be relative and stay below the output directory; two files may not
+    /// share a path.
+    pub fn render<K: ToString>(
+        &self,
+        atlas: &Atlas<K>,
+        page_names: &[String],
+        name: &str,
+    ) -> Result<Vec<TemplateFile>> {
+        let mut ctx = TemplateContext::new(atlas, page_names, name);
+        let (body, path) = self.registries()?;
+        let render = |value: &Value| -> Result<TemplateFile> {
+            let ctx = Context::wraps(value).map_err(invalid)?;
+            let contents = body.render_with_context("body", &ctx).map_err(invalid)?;
+            let path = match self.path {
+                Some(_) => Some(check_path(
+                    path.render_with_context("path", &ctx).map_err(invalid)?,
+                )?),
+                None => None,
+            };
+            Ok(TemplateFile { path, contents })
+        };
+
+        let mut files = Vec::new();
+        match self.scope {
+            TemplateScope::Atlas => files.push(render(&context_value(&ctx)?)?),
+            TemplateScope::Page => {
+                let page_count = ctx.pages.len();
+                for page in &ctx.pages {
+                    files.push(render(&context_value(&PageScope {
+                        name: &ctx.name,
+                        meta: &ctx.meta,
+                        page_count,
+                        page,
+                    })?)?);
+                }
+            }
+            TemplateScope::Frame => {
+                let page_count = ctx.pages.len();
+                for page in &mut ctx.pages {
+                    let sprites = page.sprites.take().unwrap_or_default();
+                    for sprite in &sprites {
+                        files.push(render(&context_value(&FrameScope {
+                            name: &ctx.name,
+                            meta: &ctx.meta,
+                            page_count,
+                            page,
+                            sprite,
+                        })?)?);
+                    }
+                }
+            }
+        }
+        let mut seen = HashSet::new();
+        for path in files.iter().filter_map(|f| f.path.as_deref()) {
+            if !seen.insert(path) {
+                return Err(invalid(format!("two files render to `{path}`")));
+            }
+        }
+        Ok(files)
+    }
+
+    /// Body registry (HTML escaping, as handlebars does by default) and path registry (none).
+    fn registries(&self) -> Result<(Handlebars<'static>, Handlebars<'static>)> {
+        let mut body = registry();
+        body.register_template_string("body", &self.body)
+            .map_err(invalid)?;
+        let mut path = registry();
+        path.register_escape_fn(handlebars::no_escape);
+        path.register_template_string("path", self.path.as_deref().unwrap_or_default())
+            .map_err(invalid)?;
+        Ok((body, path))
+    }
+}
+
+fn context_value<T: Serialize>(value: &T) -> Result<Value> {
+    serde_json::to_value(value).map_err(invalid)
+}
+
+#[derive(Serialize)]
+struct PageScope<'a> {
+    name: &'a str,
+    meta: &'a Meta,
+    page_count: usize,
+    page: &'a TemplatePage,
+}
+
+#[derive(Serialize)]
+struct FrameScope<'a> {
+    name: &'a str,
+    meta: &'a Meta,
+    page_count: usize,
+    page: &'a TemplatePage,
+    sprite: &'a TemplateSprite,
+}
+
+fn check_path(path: String) -> Result<String> {
+    let p = Path::new(&path);
+    if path.trim().is_empty()
+        || p.is_absolute()
+        || p.components()
+            .any(|c| !matches!(c, Component::Normal(_) | Component::CurDir))
+    {
+        return Err(invalid(format!(
+            "output path `{path}` must be relative and stay in the output directory"
+        )));
+    }
+    Ok(path)
+}
+
+fn registry() -> Handlebars<'static> {
+    let mut reg = Handlebars::new();
+    reg.set_strict_mode(true);
+    for (name, f) in [
+        ("uv", uv as HelperFn),
+        ("basename", basename),
+        ("stem", stem),
+        ("json", json),
+        ("add", |a| fold(a, |x, y| x + y)),
+        ("sub", |a| fold(a, |x, y| x - y)),
+        ("mul", |a| fold(a, |x, y| x * y)),
+        ("div", |a| fold(a, |x, y| x / y)),
+        ("min", |a| fold(a, f64::min)),
+        ("max", |a| fold(a, f64::max)),
+        ("floor", |a| unary(a, f64::floor)),
+        ("ceil", |a| unary(a, f64::ceil)),
+        ("round", |a| unary(a, f64::round)),
+    ] {
+        reg.register_helper(name, Box::new(ValueHelper(f)));
+    }
+    reg
+}
+
+type HelperFn = fn(&[&Value]) -> std::result::Result<Value, String>;
+
+/// A helper computing a value from its parameters, usable in subexpressions.
+struct ValueHelper(HelperFn);
+
+impl HelperDef for ValueHelper {
+    fn call_inner<'reg: 'rc, 'rc>(
+        &self,
+        h: &Helper<'rc>,
+        _: &'reg Handlebars<'reg>,
+        _: &'rc Context,
+        _: &mut RenderContext<'reg, 'rc>,
+    ) -> std::result::Result<ScopedJson<'rc>, RenderError> {
+        let args: Vec<&Value> = h.params().iter().map(|p| p.value()).collect();
+        (self.0)(&args)
+            .map(ScopedJson::from)
+            .map_err(|e| RenderErrorReason::Other(format!("`{}`: {e}", h.name())).into())
+    }
+}
+
+fn number(v: &Value) -> std::result::Result<f64, String> {
+    v.as_f64()
+        .ok_or_else(|| format!("expected a number, got {v}"))
+}
+
+/// Integral results render without a fractional part.
+fn to_value(x: f64) -> std::result::Result<Value, String> {
+    if !x.is_finite() {
+        Err(format!("result {x} is not finite"))
+    } else if x.fract() == 0.0 && x.abs() < 9_007_199_254_740_992.0 {
+        Ok(Value::from(x as i64))
+    } else {
+        Ok(Value::from(x))
+    }
+}
+
+fn fold(args: &[&Value], op: fn(f64, f64) -> f64) -> std::result::Result<Value, String> {
+    if args.len() < 2 {
+        return Err("expected at least two numbers".into());
+    }
+    let mut acc = number(args[0])?;
+    for v in &args[1..] {
+        acc = op(acc, number(v)?);
+    }
+    to_value(acc)
+}
+
+fn unary(args: &[&Value], op: fn(f64) -> f64) -> std::result::Result<Value, String> {
+    match args {
+        [v] => to_value(op(number(v)?)),
+        _ => Err("expected one number".into()),
+    }
+}
+
+fn string_arg<'a>(args: &[&'a Value]) -> std::result::Result<&'a str, String> {
+    match args {
+        [Value::String(s)] => Ok(s),
+        _ => Err("expected one string".into()),
+    }
+}
+
+fn basename(args: &[&Value]) -> std::result::Result<Value, String> {
+    let s = string_arg(args)?;
+    Ok(s.rsplit(['/', '\\']).next().unwrap_or(s).into())
+}
+
+fn stem(args: &[&Value]) -> std::result::Result<Value, String> {
+    let base = basename(args)?;
+    let base = base.as_str().unwrap_or_default();
+    Ok(match base.rfind('.') {
+        Some(i) if i > 0 => &base[..i],
+        _ => base,
+    }
+    .into())
+}
+
+fn json(args: &[&Value]) -> std::result::Result<Value, String> {
+    match args {
+        [v] => serde_json::to_string(v)
+            .map(Value::from)
+            .map_err(|e| e.to_string()),
+        _ => Err("expected one value".into()),
+    }
+}
+
+fn uv(args: &[&Value]) -> std::result::Result<Value, String> {
+    let (rect, size, component) = match args {
+        [rect, size] => (rect, size, None),
+        [rect, size, Value::String(c)] => (rect, size, Some(c.as_str())),
+        _ => return Err("expected `uv rect size [component]`".into()),
+    };
+    let field = |v: &Value, k: &str| number(v.get(k).unwrap_or(&Value::Null));
+    let (x, y, w, h) = (
+        field(rect, "x")?,
+        field(rect, "y")?,
+        field(rect, "w")?,
+        field(rect, "h")?,
+    );
+    let (pw, ph) = (field(size, "w")?, field(size, "h")?);
+    if pw <= 0.0 || ph <= 0.0 {
+        return Err("page size must be positive".into());
+    }
+    let uv = [
+        ("u0", x / pw),
+        ("v0", y / ph),
+        ("u1", (x + w) / pw),
+        ("v1", (y + h) / ph),
+    ];
+    match component {
+        Some(c) => uv
+            .iter()
+            .find(|(k, _)| *k == c)
+            .map(|&(_, v)| Value::from(v))
+            .ok_or_else(|| format!("unknown component `{c}`; expected u0, v0, u1 or v1")),
+        None => Ok(Value::Object(
+            uv.iter().map(|&(k, v)| (k.to_string(), v.into())).collect(),
+        )),
+    }
+}
@@ -0,0 +1,460 @@
+//! Consistency checks for existing atlases (hand-edited or produced by other tools).
+
+use std::collections::HashSet;
+use std::fmt;
+use std::hash::Hash;
+
+use crate::config::{PackerConfig, RotationDirection};
+use crate::extract::unrotate;
+use crate::hash::content_hash;
+use crate::model::{Atlas, Frame, Page, Rect};
+use image::{RgbaImage, imageops};
+use serde::{Deserialize, Serialize};
+
+/// Options for [`verify_atlas`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct VerifyOptions {
+    /// Minimum gap between frames; `None` uses the texture padding recorded in `meta.padding`.
+    pub padding: Option<u32>,
+    /// Minimum gap between frames and page edges; `None` uses `meta.padding` as well.
+    pub border: Option<u32>,
+}
+
+/// What is wrong with a frame or page.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum IssueKind<K = String> {
+    /// The key appears more than once in the atlas.
+    DuplicateKey,
+    /// The frame extends past its page (or lies closer to an edge than the border padding).
+    OutOfBounds { page_size: (u32, u32), border: u32 },
+    /// The frame shares pixels with `other`.
+    Overlap { other: K },
+    /// The frame is closer than the padding to `other`.
+    TooClose { other: K, gap: u32, padding: u32 },
+    /// `source` does not fit inside `source_size`.
+    SourceOutOfBounds,
+    /// The placed size is neither the trimmed size nor (for rotated frames) its transpose.
+    SizeMismatch,
+    /// The rotation flag is wrong: the frame only matches its source (shape or content hash)
+    /// the other way around.
+    RotationMismatch,
+    /// The page pixels under the frame do not hash to `content_hash` (hex strings in JSON, like
+    /// `contentHash`).
+    ContentHashMismatch {
+        #[serde(with = "hex")]
+        expected: u64,
+        #[serde(with = "hex")]
+        actual: u64,
+    },
+    /// The page image has a different size than the metadata says.
+    PageSizeMismatch {
+        expected: (u32, u32),
+        actual: (u32, u32),
+    },
+    /// The extrusion or padding reserved around the frame reaches past the page border
+    /// ([`Atlas::validate`]).
+    ReservationOutOfBounds { page_size: (u32, u32), border: u32 },
+    /// The extrusion or padding reserved around the frame overlaps that of `other`
+    /// ([`Atlas::validate`]).
+    ReservationOverlap { other: K },
+    /// The frame is not trimmed but its source rect is not the whole source image.
+    UntrimmedSource,
+    /// The frame's `rotated` flag contradicts its sprite's `SpriteOptions::rotate` policy
+    /// ([`Atlas::validate`]).
+    RotationPolicy { expected: bool },
+}
+
+/// A problem found by [`verify_atlas`]. `key` is `None` for page-level issues.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AtlasIssue<K = String> {
+    /// Page id (`Page::id`).
+    pub page: usize,
+    pub key: Option<K>,
+    #[serde(flatten)]
+    pub kind: IssueKind<K>,
+}
+
+impl<K: fmt::Display> fmt::Display for AtlasIssue<K> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "page {}", self.page)?;
+        if let Some(key) = &self.key {
+            write!(f, ", frame '{key}'")?;
+        }
+        f.write_str(": ")?;
+        match &self.kind {
+            IssueKind::DuplicateKey => f.write_str("duplicate key"),
+            IssueKind::OutOfBounds { page_size, border } => {
+                write!(f, "outside the {}x{} page", page_size.0, page_size.1)?;
+                if *border > 0 {
+                    write!(f, " (border padding {border})")?;
+                }
+                Ok(())
+            }
+            IssueKind::Overlap { other } => write!(f, "overlaps '{other}'"),
+            IssueKind::TooClose {
+                other,
+                gap,
+                padding,
+            } => write!(f, "{gap}px from '{other}', padding is {padding}px"),
+            IssueKind::SourceOutOfBounds => f.write_str("source rect lies outside the source size"),
+            IssueKind::SizeMismatch => f.write_str("placed size does not match the source rect"),
+            IssueKind::RotationMismatch => f.write_str("rotation flag does not match the content"),
+            IssueKind::ContentHashMismatch { expected, actual } => {
+                write!(
+                    f,
+                    "content hash {actual:016x} does not match {expected:016x}"
+                )
+            }
+            IssueKind::PageSizeMismatch { expected, actual } => write!(
+                f,
+                "page image is {}x{}, metadata says {}x{}",
+                actual.0, actual.1, expected.0, expected.1
+            ),
+            IssueKind::ReservationOutOfBounds { page_size, border } => {
+                write!(
+                    f,
+                    "extrusion/padding reaches outside the {}x{} page",
+                    page_size.0, page_size.1
+                )?;
+                if *border > 0 {
+                    write!(f, " (border padding {border})")?;
+                }
+                Ok(())
+            }
+            IssueKind::ReservationOverlap { other } => {
+                write!(f, "extrusion/padding overlaps that of '{other}'")
+            }
+            IssueKind::UntrimmedSource => {
+                f.write_str("untrimmed frame does not cover its whole source image")
+            }
+            IssueKind::RotationPolicy { expected } => {
+                let want = if *expected { "rotated" } else { "upright" };
+                write!(f, "rotation policy requires the frame {want}")
+            }
+        }
+    }
+}
+
+/// Checks `atlas` for internal consistency and returns every problem found, in page order.
+///
+/// Frames must lie inside their page (minus the border padding), must not overlap or come
+/// closer to each other than the padding, and their placed size must match the trimmed
+/// source (transposed when rotated). With `pages` (pixel data of `atlas.pages[i]`), page sizes
+/// are compared and frames that carry a `content_hash` are re-hashed; a frame that only hashes
+/// correctly with the opposite rotation (and a possibly transposed rect), or turned the other way
+/// than `meta.rotation_direction`, is reported as [`IssueKind::RotationMismatch`].
+/// Degenerate frames are only checked for duplicates and bounds.
+pub fn verify_atlas<K: Clone + Eq + Hash>(
+    atlas: &Atlas<K>,
+    pages: Option<&[RgbaImage]>,
+    opts: VerifyOptions,
+) -> Vec<AtlasIssue<K>> {
+    let padding = opts.padding.unwrap_or(atlas.meta.padding.1);
+    let border = opts.border.unwrap_or(atlas.meta.padding.0);
+    let mut issues = Vec::new();
+    let mut seen = HashSet::new();
+    for (i, page) in atlas.pages.iter().enumerate() {
+        let image = pages.and_then(|p| p.get(i));
+        let direction = atlas.meta.rotation_direction;
+        verify_page(
+            page,
+            image,
+            padding,
+            border,
+            direction,
+            &mut seen,
+            &mut issues,
+        );
+    }
+    issues
+}
+
+/// [`verify_atlas`] checks of one page; keys already `seen` on earlier pages are duplicates.
+fn verify_page<'a, K: Clone + Eq + Hash>(
+    page: &'a Page<K>,
+    image: Option<&RgbaImage>,
+    padding: u32,
+    border: u32,
+    direction: RotationDirection,
+    seen: &mut HashSet<&'a K>,
+    issues: &mut Vec<AtlasIssue<K>>,
+) {
+    let issue = |key: Option<&K>, kind| AtlasIssue {
+        page: page.id,
+        key: key.cloned(),
+        kind,
+    };
+    let image_ok = match image {
+        Some(img) if img.dimensions() != (page.width, page.height) => {
+            issues.push(issue(
+                None,
+                IssueKind::PageSizeMismatch {
+                    expected: (page.width, page.height),
+                    actual: img.dimensions(),
+                },
+            ));
+            false
+        }
+        _ => true,
+    };
+
+    for f in &page.frames {
+        if !seen.insert(&f.key) {
+            issues.push(issue(Some(&f.key), IssueKind::DuplicateKey));
+        }
+        let r = f.frame;
+        let inside = r.x >= border
+            && r.y >= border
+            && r.x as u64 + r.w as u64 + border as u64 <= page.width as u64
+            && r.y as u64 + r.h as u64 + border as u64 <= page.height as u64;
+        if !inside {
+            issues.push(issue(
+                Some(&f.key),
+                IssueKind::OutOfBounds {
+                    page_size: (page.width, page.height),
+                    border,
+                },
+            ));
+        }
+        if f.degenerate {
+            continue;
+        }
+        let s = f.source;
+        if s.x as u64 + s.w as u64 > f.source_size.0 as u64
+            || s.y as u64 + s.h as u64 > f.source_size.1 as u64
+        {
+            issues.push(issue(Some(&f.key), IssueKind::SourceOutOfBounds));
+            continue;
+        }
+        let fits = |rotated: bool| {
+            let (w, h) = if rotated { (s.h, s.w) } else { (s.w, s.h) };
+            (r.w, r.h) == (w, h)
+        };
+        if !fits(f.rotated) {
+            let kind = if fits(!f.rotated) {
+                IssueKind::RotationMismatch
+            } else {
+                IssueKind::SizeMismatch
+            };
+            issues.push(issue(Some(&f.key), kind));
+            continue;
+        }
+        if let (Some(img), Some(expected), true, true) = (image, f.content_hash, image_ok, inside) {
+            let read = |r: &Rect, rotated: bool| frame_hash(r, rotated.then_some(direction), img);
+            let actual = read(&r, f.rotated);
+            if actual != expected {
+                // Formats that store unrotated frame sizes derive the placed size from the
+                // flag, so a wrong flag also transposes the rect
+                let flipped = Rect::new(r.x, r.y, r.h, r.w);
+                let flipped_fits = flipped.x as u64 + flipped.w as u64 <= img.width() as u64
+                    && flipped.y as u64 + flipped.h as u64 <= img.height() as u64;
+                let other_way = match direction {
+                    RotationDirection::Clockwise => RotationDirection::CounterClockwise,
+                    RotationDirection::CounterClockwise => RotationDirection::Clockwise,
+                };
+                let kind = if read(&r, !f.rotated) == expected
+                    || (flipped_fits && read(&flipped, !f.rotated) == expected)
+                    || (f.rotated && frame_hash(&r, Some(other_way), img) == expected)
+                {
+                    IssueKind::RotationMismatch
+                } else {
+                    IssueKind::ContentHashMismatch { expected, actual }
+                };
+                issues.push(issue(Some(&f.key), kind));
+            }
+        }
+    }
+
+    // Sweep by x: once a frame starts `padding` past the end of `a`, no later one can touch it
+    let mut order: Vec<&Frame<K>> = page.frames.iter().filter(|f| !f.degenerate).collect();
+    order.sort_by_key(|f| (f.frame.x, f.frame.y));
+    for (n, a) in order.iter().enumerate() {
+        let a_end = a.frame.x as u64 + a.frame.w as u64;
+        for b in &order[n + 1..] {
+            if b.frame.x as u64 >= a_end + padding as u64 {
+                break;
+            }
+            match gap(&a.frame, &b.frame) {
+                None => issues.push(issue(
+                    Some(&a.key),
+                    IssueKind::Overlap {
+                        other: b.key.clone(),
+                    },
+                )),
+                Some(g) if g < padding => issues.push(issue(
+                    Some(&a.key),
+                    IssueKind::TooClose {
+                        other: b.key.clone(),
+                        gap: g,
+                        padding,
+                    },
+                )),
+                Some(_) => {}
+            }
+        }
+    }
+}
+
+impl<K: Clone + Eq + Hash + AsRef<str>> Atlas<K> {
+    /// Checks a layout against the `cfg` it was packed with and returns every problem found, in
+    /// page order.
+    ///
+    /// Runs the metadata checks of [`verify_atlas`] with `cfg.border_padding` as the border,
+    /// then checks the space each frame reserves: its extrusion and sprite padding (per edge,
+    /// per-sprite overrides included) must stay inside the border and clear of every other
+    /// frame's reservation. Untrimmed frames must cover their whole source image. Debug builds
+    /// run this on every page `pack_images` lays out.
+    pub fn validate(&self, cfg: &PackerConfig) -> Vec<AtlasIssue<K>> {
+        let mut issues = Vec::new();
+        let mut seen = HashSet::new();
+        for page in &self.pages {
+            validate_page(page, cfg, &mut seen, &mut issues);
+        }
+        issues
+    }
+}
+
+/// [`Atlas::validate`] for a single page.
+pub(crate) fn page_issues<K: Clone + Eq + Hash + AsRef<str>>(
+    page: &Page<K>,
+    cfg: &PackerConfig,
+) -> Vec<AtlasIssue<K>> {
+    let mut issues = Vec::new();
+    validate_page(page, cfg, &mut HashSet::new(), &mut issues);
+    issues
+}
+
+fn validate_page<'a, K: Clone + Eq + Hash + AsRef<str>>(
+    page: &'a Page<K>,
+    cfg: &PackerConfig,
+    seen: &mut HashSet<&'a K>,
+    issues: &mut Vec<AtlasIssue<K>>,
+) {
+    let border = cfg.border_padding as i64;
+    let direction = cfg.rotation_direction;
+    verify_page(page, None, 0, cfg.border_padding, direction, seen, issues);
+    let issue = |key: &K, kind| AtlasIssue {
+        page: page.id,
+        key: Some(key.clone()),
+        kind,
+    };
+    // Reserved rects as [x0, y0, x1, y1); signed so margins may cross the page origin
+    let mut reserved: Vec<(&Frame<K>, [i64; 4])> = Vec::with_capacity(page.frames.len());
+    for f in page.frames.iter().filter(|f| !f.degenerate) {
+        if !f.trimmed && f.source != Rect::new(0, 0, f.source_size.0, f.source_size.1) {
+            issues.push(issue(&f.key, IssueKind::UntrimmedSource));
+        }
+        let policy = cfg.sprite(f.key.as_ref()).and_then(|s| s.rotate?.forced());
+        if let Some(expected) = policy.filter(|&e| e != f.rotated) {
+            issues.push(issue(&f.key, IssueKind::RotationPolicy { expected }));
+        }
+        let (left, top, right, bottom) = reservation(f.key.as_ref(), cfg);
+        let r = f.frame;
+        let rect = [
+            r.x as i64 - left as i64,
+            r.y as i64 - top as i64,
+            r.x as i64 + r.w as i64 + right as i64,
+            r.y as i64 + r.h as i64 + bottom as i64,
+        ];
+        if rect[0] < border
+            || rect[1] < border
+            || rect[2] > page.width as i64 - border
+            || rect[3] > page.height as i64 - border
+        {
+            issues.push(issue(
+                &f.key,
+                IssueKind::ReservationOutOfBounds {
+                    page_size: (page.width, page.height),
+                    border: cfg.border_padding,
+                },
+            ));
+        }
+        reserved.push((f, rect));
+    }
+    reserved.sort_by_key(|(_, r)| (r[0], r[1]));
+    for (n, (a, ra)) in reserved.iter().enumerate() {
+        for (b, rb) in &reserved[n + 1..] {
+            if rb[0] >= ra[2] {
+                break;
+            }
+            // Frames whose pixels overlap were already reported as `Overlap`
+            let apart = ra[3] <= rb[1] || rb[3] <= ra[1];
+            if !apart && gap(&a.frame, &b.frame).is_some() {
+                issues.push(issue(
+                    &a.key,
+                    IssueKind::ReservationOverlap {
+                        other: b.key.clone(),
+                    },
+                ));
+            }
+        }
+    }
+}
+
+/// Extrusion plus sprite padding `cfg` reserves on each edge of `key`'s frame:
+/// `(left, top, right, bottom)`.
+fn reservation(key: &str, cfg: &PackerConfig) -> (u32, u32, u32, u32) {
+    let pad = cfg.sprite_padding();
+    let sprite = cfg.sprite(key);
+    let e = cfg
+        .texture_extrusion
+        .max(sprite.and_then(|s| s.texture_extrusion).unwrap_or(0));
+    // A per-sprite padding above the global one is added on every edge
+    let extra = sprite
+        .and_then(|s| s.texture_padding)
+        .map_or(0, |p| p.saturating_sub(cfg.meta_padding()));
+    (
+        e + pad.left + extra,
+        e + pad.top + extra,
+        e + pad.right + extra,
+        e + pad.bottom + extra,
+    )
+}
+
+/// Hash of the frame's pixels read with the given rotation, as computed at pack time.
+fn frame_hash(r: &Rect, rotation: Option<RotationDirection>, page: &RgbaImage) -> u64 {
+    let placed = imageops::crop_imm(page, r.x, r.y, r.w, r.h).to_image();
+    let trimmed = match rotation {
+        Some(direction) => unrotate(&placed, direction),
+        None => placed,
+    };
+    let (w, h) = trimmed.dimensions();
+    content_hash(&trimmed, &Rect::new(0, 0, w, h))
+}
+
+/// Distance between two rectangles along the axis that separates them (`Some(0)` when they
+/// touch), or `None` when they overlap.
+fn gap(a: &Rect, b: &Rect) -> Option<u32> {
+    let gx = axis_gap(a.x, a.w, b.x, b.w);
+    let gy = axis_gap(a.y, a.h, b.y, b.h);
+    match (gx, gy) {
+        (None, None) => None,
+        (Some(x), Some(y)) => Some(x.max(y)),
+        (Some(g), None) | (None, Some(g)) => Some(g),
+    }
+}
+
+fn axis_gap(a: u32, a_len: u32, b: u32, b_len: u32) -> Option<u32> {
+    let (a, a_end) = (a as u64, a as u64 + a_len as u64);
+    let (b, b_end) = (b as u64, b as u64 + b_len as u64);
+    if a_end <= b {
+        Some((b - a_end) as u32)
+    } else if b_end <= a {
+        Some((a - b_end) as u32)
+    } else {
+        None
+    }
+}
+
+mod hex {
+    use serde::{Deserialize, Deserializer, Serializer, de::Error};
+
+    pub fn serialize<S: Serializer>(v: &u64, s: S) -> Result<S::Ok, S::Error> {
+        s.serialize_str(&format!("{v:016x}"))
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(d: D) -> Result<u64, D::Error> {
+        u64::from_str_radix(&String::deserialize(d)?, 16).map_err(D::Error::custom)
+    }
+}
@@ -0,0 +1,126 @@
+mod common;
+
+use tex_packer_core::config::{AlgorithmFamily, Padding, SkylineHeuristic, SpriteOptions};
+use tex_packer_core::runtime::{AtlasSession, RuntimeStrategy, ShelfPolicy};
+use tex_packer_core::{PackerConfig, TexPackerError, pack_images, pack_layout};
+
+fn sizes() -> Vec<(String, u32, u32)> {
+    (0..30)
+        .map(|i| (format!("s{i}"), 3 + (i * 7) % 19, 2 + (i * 5) % 13))
+        .collect()
+}
+
+fn cfg(family: AlgorithmFamily, align: u32) -> PackerConfig {
+    PackerConfig {
+        family,
+        max_width: 256,
+        max_height: 256,
+        border_padding: 3,
+        padding: Some(Padding {
+            left: 1,
+            top: 2,
+            right: 1,
+            bottom: 0,
+        }),
+        texture_extrusion: 1,
+        allow_rotation: true,
+        trim: false,
+        align,
+        ..Default::default()
+    }
+}
+
+#[test]
+fn every_family_places_frames_on_the_grid() {
+    for family in [
+        AlgorithmFamily::Skyline,
+        AlgorithmFamily::MaxRects,
+        AlgorithmFamily::Guillotine,
+        AlgorithmFamily::Shelf,
+        AlgorithmFamily::Grid,
+    ] {
+        for align in [4, 8] {
+            let atlas = pack_layout(sizes(), cfg(family.clone(), align)).unwrap();
+            assert_eq!(atlas.meta.align, Some(align));
+            let frames: Vec<_> = atlas.pages.iter().flat_map(|p| &p.frames).collect();
+            assert_eq!(frames.len(), 30);
+            for f in frames {
+                assert!(
+                    f.frame.x % align == 0 && f.frame.y % align == 0,
+                    "{family:?} align {align}: {} at {:?}",
+                    f.key,
+                    f.frame
+                );
+            }
+        }
+    }
+}
+
+#[test]
+fn auto_winner_is_aligned() {
+    let inputs = sizes()
+        .into_iter()
+        .map(|(key, w, h)| common::solid(key, w, h, [9, 9, 9, 255]))
+        .collect();
+    let out = pack_images(inputs, cfg(AlgorithmFamily::Auto, 4)).unwrap();
+    for f in out.atlas.pages.iter().flat_map(|p| &p.frames) {
+        assert_eq!((f.frame.x % 4, f.frame.y % 4), (0, 0), "{}", f.key);
+    }
+}
+
+#[test]
+fn per_sprite_spacing_keeps_alignment() {
+    let mut c = cfg(AlgorithmFamily::MaxRects, 4);
+    c.sprites.insert(
+        "s3".into(),
+        SpriteOptions {
+            texture_padding: Some(7),
+            ..Default::default()
+        },
+    );
+    let atlas = pack_layout(sizes(), c).unwrap();
+    for f in atlas.pages.iter().flat_map(|p| &p.frames) {
+        assert_eq!((f.frame.x % 4, f.frame.y % 4), (0, 0), "{}", f.key);
+    }
+}
+
+#[test]
+fn align_size_rounds_frames_and_extends_the_canvas() {
+    let mut c = cfg(AlgorithmFamily::Skyline, 4);
+    c.align_size = true;
+    c.allow_rotation = false;
+    let out = pack_images(vec![common::solid("a", 5, 3, [255, 0, 0, 255])], c).unwrap();
+    let page = &out.pages[0];
+    let f = &page.page.frames[0];
+    assert_eq!((f.frame.w, f.frame.h), (8, 4));
+    assert_eq!(f.source_size, (8, 4));
+    // The added columns/rows are transparent
+    assert_eq!(page.rgba.get_pixel(f.frame.x + 4, f.frame.y)[0], 255);
+    assert_eq!(page.rgba.get_pixel(f.frame.x + 5, f.frame.y)[3], 0);
+    assert_eq!(page.rgba.get_pixel(f.frame.x, f.frame.y + 3)[3], 0);
+}
+
+#[test]
+fn unaligned_atlases_omit_the_field() {
+    let atlas = pack_layout(sizes(), cfg(AlgorithmFamily::Skyline, 1)).unwrap();
+    assert_eq!(atlas.meta.align, None);
+    assert!(matches!(
+        pack_layout(sizes(), cfg(AlgorithmFamily::Skyline, 512)),
+        Err(TexPackerError::InvalidConfig(_))
+    ));
+}
+
+#[test]
+fn runtime_sessions_align_appended_frames() {
+    for strategy in [
+        RuntimeStrategy::Guillotine,
+        RuntimeStrategy::Shelf(ShelfPolicy::FirstFit),
+        RuntimeStrategy::Skyline(SkylineHeuristic::BottomLeft),
+    ] {
+        let mut session = AtlasSession::new(cfg(AlgorithmFamily::Skyline, 4), strategy);
+        for (key, w, h) in sizes() {
+            let (_, f) = session.append(key, w, h).unwrap();
+            assert_eq!((f.frame.x % 4, f.frame.y % 4), (0, 0), "{}", f.key);
+        }
+    }
+}
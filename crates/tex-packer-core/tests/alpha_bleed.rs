@@ -0,0 +1,65 @@
+mod common;
+
+use image::{Rgba, RgbaImage};
+use tex_packer_core::compositing::alpha_bleed;
+use tex_packer_core::{PackerConfig, pack_images};
+
+/// 8x8 image: a 2x2 opaque red square at (3,3), everything else fully transparent.
+fn sprite() -> RgbaImage {
+    let mut img = RgbaImage::new(8, 8);
+    for y in 3..5 {
+        for x in 3..5 {
+            img.put_pixel(x, y, Rgba([200, 10, 10, 255]));
+        }
+    }
+    img
+}
+
+#[test]
+fn bleed_fills_within_radius_only() {
+    let mut img = sprite();
+    alpha_bleed(&mut img, 0, 0, 8, 8, 2);
+    // One and two pixels away take the neighbor color but stay transparent.
+    assert_eq!(*img.get_pixel(2, 3), Rgba([200, 10, 10, 0]));
+    assert_eq!(*img.get_pixel(1, 4), Rgba([200, 10, 10, 0]));
+    assert_eq!(*img.get_pixel(5, 5), Rgba([200, 10, 10, 0]));
+    // Three pixels away is beyond the radius.
+    assert_eq!(*img.get_pixel(0, 3), Rgba([0, 0, 0, 0]));
+    // Opaque pixels are untouched.
+    assert_eq!(*img.get_pixel(3, 3), Rgba([200, 10, 10, 255]));
+}
+
+#[test]
+fn bleed_stays_inside_rect() {
+    let mut img = sprite();
+    alpha_bleed(&mut img, 2, 2, 4, 4, 8);
+    assert_eq!(img.get_pixel(2, 2)[0], 200);
+    assert_eq!(*img.get_pixel(1, 1), Rgba([0, 0, 0, 0]));
+    assert_eq!(*img.get_pixel(6, 6), Rgba([0, 0, 0, 0]));
+}
+
+#[test]
+fn pipeline_applies_alpha_bleed() {
+    let run = |bleed: Option<u32>| {
+        let inputs = vec![common::input("s", sprite())];
+        let cfg = PackerConfig {
+            max_width: 64,
+            max_height: 64,
+            trim: false,
+            texture_padding: 0,
+            alpha_bleed: bleed,
+            ..Default::default()
+        };
+        let out = pack_images(inputs, cfg).expect("pack");
+        let f = out.atlas.pages[0].frames[0].frame;
+        let page = &out.pages[0].rgba;
+        (*page.get_pixel(f.x + 2, f.y + 3), *page.get_pixel(f.x, f.y))
+    };
+    let (near, far) = run(None);
+    assert_eq!(near, Rgba([0, 0, 0, 0]));
+    assert_eq!(far, Rgba([0, 0, 0, 0]));
+
+    let (near, far) = run(Some(1));
+    assert_eq!(near, Rgba([200, 10, 10, 0]));
+    assert_eq!(far, Rgba([0, 0, 0, 0]));
+}
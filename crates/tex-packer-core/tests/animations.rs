@@ -0,0 +1,70 @@
+mod common;
+
+use tex_packer_core::{
+    Animation, AnimationFrame, InputImage, KeyTransform, PackerConfig, pack_images,
+    to_aseprite_json, to_json_hash,
+};
+
+fn frames() -> Vec<InputImage> {
+    (0..3)
+        .map(|i| common::solid(format!("anim/spin#{i}.png"), 8, 8, [i * 80, 0, 0, 255]))
+        .collect()
+}
+
+#[test]
+fn animations_are_recorded_in_metadata() {
+    let animation = Animation {
+        name: "anim/spin".into(),
+        frames: [100, 200, 50]
+            .iter()
+            .enumerate()
+            .map(|(i, &ms)| AnimationFrame {
+                key: format!("anim/spin#{i}"),
+                duration_ms: ms,
+            })
+            .collect(),
+    };
+    let cfg = PackerConfig {
+        key_transform: KeyTransform {
+            strip_extension: true,
+            ..Default::default()
+        },
+        animations: vec![animation.clone()],
+        ..Default::default()
+    };
+    let out = pack_images(frames(), cfg).unwrap();
+    assert_eq!(out.atlas.meta.animations, [animation]);
+
+    let hash = to_json_hash(&out.atlas);
+    assert_eq!(
+        hash["meta"]["animations"][0]["frames"][1]["duration_ms"],
+        200
+    );
+
+    let ase = to_aseprite_json(&out.atlas, 0, "atlas.png");
+    let durations: Vec<(&str, u64)> = ase["frames"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|f| {
+            (
+                f["filename"].as_str().unwrap(),
+                f["duration"].as_u64().unwrap(),
+            )
+        })
+        .collect();
+    assert_eq!(
+        durations,
+        [
+            ("anim/spin#0", 100),
+            ("anim/spin#1", 200),
+            ("anim/spin#2", 50)
+        ]
+    );
+}
+
+#[test]
+fn metadata_without_animations_omits_the_field() {
+    let out = pack_images(frames(), PackerConfig::default()).unwrap();
+    assert!(to_json_hash(&out.atlas)["meta"].get("animations").is_none());
+}
@@ -0,0 +1,131 @@
+mod common;
+
+use tex_packer_core::config::{AlgorithmFamily, AutoMode};
+use tex_packer_core::optimizer::{AnnealSettings, Arrangement, anneal};
+use tex_packer_core::{InputImage, PackOutput, PackerConfig, pack_images};
+
+fn inputs() -> Vec<InputImage> {
+    let sizes = [
+        (30, 9),
+        (9, 30),
+        (22, 14),
+        (14, 22),
+        (17, 17),
+        (26, 6),
+        (6, 26),
+        (12, 11),
+        (20, 5),
+        (7, 19),
+        (10, 10),
+        (15, 8),
+    ];
+    sizes
+        .iter()
+        .enumerate()
+        .map(|(i, &(w, h))| common::solid(format!("img{i:02}"), w, h, [80, 160, 240, 255]))
+        .collect()
+}
+
+fn cfg(family: AlgorithmFamily, allow_rotation: bool) -> PackerConfig {
+    PackerConfig {
+        max_width: 64,
+        max_height: 64,
+        texture_padding: 0,
+        trim: false,
+        allow_rotation,
+        family,
+        auto_mode: AutoMode::Anneal,
+        mr_reference: true,
+        mr_global: false,
+        ..Default::default()
+    }
+}
+
+fn total_area(out: &PackOutput) -> u64 {
+    common::page_area(&out.atlas.pages)
+}
+
+fn assert_disjoint(out: &PackOutput) {
+    out.atlas.pages.iter().for_each(common::assert_disjoint);
+}
+
+#[test]
+fn anneal_is_no_worse_than_maxrects() {
+    let plain = pack_images(inputs(), cfg(AlgorithmFamily::MaxRects, true)).expect("maxrects");
+    let annealed = pack_images(inputs(), cfg(AlgorithmFamily::Auto, true)).expect("anneal");
+    assert!(total_area(&annealed) <= total_area(&plain));
+    let placed: usize = annealed.pages.iter().map(|p| p.page.frames.len()).sum();
+    assert_eq!(placed, 12);
+    assert_disjoint(&annealed);
+
+    let winner = annealed
+        .stats()
+        .winning_config
+        .expect("anneal records its config");
+    assert_eq!(winner.family, AlgorithmFamily::MaxRects);
+    assert!(winner.heuristic.ends_with("+anneal"));
+}
+
+#[test]
+fn anneal_rotated_frames_have_swapped_dimensions() {
+    let out = pack_images(inputs(), cfg(AlgorithmFamily::Auto, true)).expect("anneal");
+    for f in out.pages.iter().flat_map(|p| &p.page.frames) {
+        let (sw, sh) = f.source_size;
+        let expected = if f.rotated { (sh, sw) } else { (sw, sh) };
+        assert_eq!((f.frame.w, f.frame.h), expected, "{}", f.key);
+    }
+}
+
+#[test]
+fn anneal_without_rotation_never_rotates() {
+    let out = pack_images(inputs(), cfg(AlgorithmFamily::Auto, false)).expect("anneal");
+    assert!(
+        out.pages
+            .iter()
+            .all(|p| p.page.frames.iter().all(|f| !f.rotated))
+    );
+    assert_disjoint(&out);
+}
+
+#[test]
+fn anneal_is_deterministic_without_time_budget() {
+    let a = pack_images(inputs(), cfg(AlgorithmFamily::Auto, true)).expect("first");
+    let b = pack_images(inputs(), cfg(AlgorithmFamily::Auto, true)).expect("second");
+    let frames = |o: &PackOutput| {
+        o.pages
+            .iter()
+            .flat_map(|p| {
+                p.page
+                    .frames
+                    .iter()
+                    .map(|f| (f.key.clone(), f.frame, f.rotated))
+            })
+            .collect::<Vec<_>>()
+    };
+    assert_eq!(frames(&a), frames(&b));
+}
+
+#[test]
+fn optimizer_minimizes_custom_cost() {
+    // Cost = number of inversions; the optimum is the identity order.
+    let initial = Arrangement {
+        order: vec![5, 3, 7, 0, 6, 1, 4, 2],
+        rotated: vec![false; 8],
+    };
+    let settings = AnnealSettings {
+        max_iterations: 5000,
+        ..Default::default()
+    };
+    let result = anneal(initial, &[false; 8], &settings, |a| {
+        let o = &a.order;
+        let inv = (0..o.len())
+            .flat_map(|i| (i + 1..o.len()).map(move |j| (i, j)))
+            .filter(|&(i, j)| o[i] > o[j])
+            .count();
+        Some(inv as u64)
+    })
+    .expect("initial is feasible");
+    assert_eq!(result.cost, 0);
+    assert_eq!(result.best.order, (0..8).collect::<Vec<_>>());
+    assert!(result.improvements > 0);
+}
@@ -0,0 +1,226 @@
+#![cfg(feature = "aseprite")]
+
+use std::io::Write;
+
+use image::Rgba;
+use tex_packer_core::{AsepriteFile, AsepriteSplit, NinePatch, Pivot};
+
+fn string(out: &mut Vec<u8>, s: &str) {
+    out.extend((s.len() as u16).to_le_bytes());
+    out.extend(s.as_bytes());
+}
+
+fn chunk(kind: u16, body: &[u8]) -> Vec<u8> {
+    let mut c = Vec::new();
+    c.extend((body.len() as u32 + 6).to_le_bytes());
+    c.extend(kind.to_le_bytes());
+    c.extend(body);
+    c
+}
+
+fn layer(flags: u16, kind: u16, level: u16, opacity: u8, name: &str) -> Vec<u8> {
+    let mut b = Vec::new();
+    for v in [flags, kind, level, 0, 0, 0] {
+        b.extend(v.to_le_bytes());
+    }
+    b.extend([opacity, 0, 0, 0]);
+    string(&mut b, name);
+    chunk(0x2004, &b)
+}
+
+/// A `w`x`h` cel of one color; zlib-compressed when `zlib` is set.
+fn cel(layer: u16, x: i16, y: i16, w: u16, h: u16, rgba: [u8; 4], zlib: bool) -> Vec<u8> {
+    let mut b = Vec::new();
+    b.extend(layer.to_le_bytes());
+    b.extend(x.to_le_bytes());
+    b.extend(y.to_le_bytes());
+    b.push(255);
+    b.extend((if zlib { 2u16 } else { 0 }).to_le_bytes());
+    b.extend([0u8; 7]);
+    b.extend(w.to_le_bytes());
+    b.extend(h.to_le_bytes());
+    let pixels: Vec<u8> = (0..w as usize * h as usize).flat_map(|_| rgba).collect();
+    if zlib {
+        let mut z = flate2::write::ZlibEncoder::new(Vec::new(), flate2::Compression::default());
+        z.write_all(&pixels).unwrap();
+        b.extend(z.finish().unwrap());
+    } else {
+        b.extend(pixels);
+    }
+    chunk(0x2005, &b)
+}
+
+fn linked_cel(layer: u16, frame: u16) -> Vec<u8> {
+    let mut b = Vec::new();
+    b.extend(layer.to_le_bytes());
+    b.extend([0u8; 4]);
+    b.push(255);
+    b.extend(1u16.to_le_bytes());
+    b.extend([0u8; 7]);
+    b.extend(frame.to_le_bytes());
+    chunk(0x2005, &b)
+}
+
+fn frame(duration: u16, chunks: &[Vec<u8>]) -> Vec<u8> {
+    let body: Vec<u8> = chunks.concat();
+    let mut f = Vec::new();
+    f.extend((body.len() as u32 + 16).to_le_bytes());
+    f.extend(0xF1FAu16.to_le_bytes());
+    f.extend((chunks.len() as u16).to_le_bytes());
+    f.extend(duration.to_le_bytes());
+    f.extend([0u8; 2]);
+    f.extend((chunks.len() as u32).to_le_bytes());
+    f.extend(body);
+    f
+}
+
+fn file(w: u16, h: u16, frames: &[Vec<u8>]) -> Vec<u8> {
+    let body: Vec<u8> = frames.concat();
+    let mut f = Vec::new();
+    f.extend((body.len() as u32 + 128).to_le_bytes());
+    f.extend(0xA5E0u16.to_le_bytes());
+    f.extend((frames.len() as u16).to_le_bytes());
+    f.extend(w.to_le_bytes());
+    f.extend(h.to_le_bytes());
+    f.extend(32u16.to_le_bytes());
+    f.extend(1u32.to_le_bytes()); // layer opacity is valid
+    f.resize(128, 0);
+    f.extend(body);
+    f
+}
+
+/// 8x8, three frames tagged `walk` (0-1), layers `body` and `fx/glow` (in a group) plus a
+/// hidden `guide`, and a slice `hand` with a nine-patch center and pivot.
+fn sample() -> Vec<u8> {
+    const RED: [u8; 4] = [255, 0, 0, 255];
+    const BLUE: [u8; 4] = [0, 0, 255, 255];
+    let mut tags = Vec::new();
+    tags.extend(1u16.to_le_bytes());
+    tags.extend([0u8; 8]);
+    tags.extend(0u16.to_le_bytes());
+    tags.extend(1u16.to_le_bytes());
+    tags.extend([0u8; 13]);
+    string(&mut tags, "walk");
+
+    let mut slice = Vec::new();
+    slice.extend(1u32.to_le_bytes());
+    slice.extend(3u32.to_le_bytes()); // nine-patch + pivot
+    slice.extend(0u32.to_le_bytes());
+    string(&mut slice, "hand");
+    slice.extend(0u32.to_le_bytes());
+    for v in [2i32, 2] {
+        slice.extend(v.to_le_bytes());
+    }
+    for v in [4u32, 4, 1, 1, 2, 2] {
+        slice.extend(v.to_le_bytes());
+    }
+    for v in [2i32, 4] {
+        slice.extend(v.to_le_bytes());
+    }
+
+    file(
+        8,
+        8,
+        &[
+            frame(
+                100,
+                &[
+                    layer(1, 0, 0, 255, "body"),
+                    layer(1, 1, 0, 255, "fx"),
+                    layer(1, 0, 1, 128, "glow"),
+                    layer(0, 0, 0, 255, "guide"),
+                    chunk(0x2018, &tags),
+                    chunk(0x2022, &slice),
+                    cel(0, 0, 0, 8, 8, RED, false),
+                    cel(2, 4, 4, 4, 4, BLUE, true),
+                    cel(3, 0, 0, 8, 8, BLUE, false),
+                ],
+            ),
+            frame(150, &[cel(0, 1, 0, 2, 2, BLUE, true)]),
+            frame(80, &[linked_cel(0, 0)]),
+        ],
+    )
+}
+
+#[test]
+fn parses_layers_tags_and_slices() {
+    let ase = AsepriteFile::parse(&sample()).unwrap();
+    assert_eq!((ase.width, ase.height, ase.frames.len()), (8, 8, 3));
+    let paths: Vec<(&str, bool)> = ase
+        .layers
+        .iter()
+        .map(|l| (l.path.as_str(), l.visible))
+        .collect();
+    assert_eq!(
+        paths,
+        [
+            ("body", true),
+            ("fx", true),
+            ("fx/glow", true),
+            ("guide", false)
+        ]
+    );
+    assert_eq!(ase.tags[0].name, "walk");
+    assert_eq!(ase.frames[1].duration_ms, 150);
+
+    // Glow is blended at half opacity over the red body; the hidden guide is skipped
+    let flat = ase.render_frame(0);
+    assert_eq!(*flat.get_pixel(0, 0), Rgba([255, 0, 0, 255]));
+    let p = flat.get_pixel(6, 6);
+    assert!(
+        p[0] > 100 && p[0] < 150 && p[2] > 100 && p[3] == 255,
+        "{p:?}"
+    );
+    // The linked cel repeats frame 0's body
+    assert_eq!(ase.render_layer(2, 0), ase.render_layer(0, 0));
+}
+
+#[test]
+fn splits_into_named_sprites() {
+    let ase = AsepriteFile::parse(&sample()).unwrap();
+
+    let frames = ase.sprites(AsepriteSplit::Frames);
+    let names: Vec<&str> = frames.iter().map(|s| s.name.as_str()).collect();
+    assert_eq!(names, ["walk_0", "walk_1", "2"]);
+    assert_eq!(frames[1].duration_ms, Some(150));
+    assert_eq!(*frames[1].image.get_pixel(1, 0), Rgba([0, 0, 255, 255]));
+    assert_eq!(frames[1].image.get_pixel(0, 0)[3], 0);
+
+    let layers = ase.sprites(AsepriteSplit::Layers);
+    let names: Vec<&str> = layers.iter().map(|s| s.name.as_str()).collect();
+    assert_eq!(
+        names,
+        [
+            "body_0",
+            "body_1",
+            "body_2",
+            "fx/glow_0",
+            "fx/glow_1",
+            "fx/glow_2"
+        ]
+    );
+    assert_eq!(layers[3].image.get_pixel(0, 0)[3], 0);
+
+    let slices = ase.sprites(AsepriteSplit::Slices);
+    assert_eq!(slices.len(), 3);
+    assert_eq!(slices[0].name, "hand_0");
+    assert_eq!(slices[0].image.dimensions(), (4, 4));
+    assert_eq!(slices[0].pivot, Some(Pivot::new(0.5, 1.0)));
+    assert_eq!(
+        slices[0].nine_patch,
+        Some(NinePatch {
+            left: 1,
+            top: 1,
+            right: 1,
+            bottom: 1
+        })
+    );
+}
+
+#[test]
+fn rejects_other_files() {
+    assert!(AsepriteFile::parse(b"\x89PNG\r\n\x1a\n").is_err());
+    let mut truncated = sample();
+    truncated.truncate(200);
+    assert!(AsepriteFile::parse(&truncated).is_err());
+}
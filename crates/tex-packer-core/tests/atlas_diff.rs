@@ -0,0 +1,94 @@
+mod common;
+
+use tex_packer_core::{PackerConfig, diff_atlases, pack_images};
+
+fn cfg() -> PackerConfig {
+    PackerConfig {
+        max_width: 128,
+        max_height: 128,
+        allow_rotation: false,
+        trim: false,
+        ..Default::default()
+    }
+}
+
+#[test]
+fn identical_atlases_have_empty_diff() {
+    let inputs = || {
+        vec![
+            common::solid("a", 20, 10, [1, 0, 0, 255]),
+            common::solid("b", 16, 16, [2, 0, 0, 255]),
+        ]
+    };
+    let a = pack_images(inputs(), cfg()).unwrap();
+    let b = pack_images(inputs(), cfg()).unwrap();
+    let diff = diff_atlases(&a.atlas, &b.atlas);
+    assert!(diff.is_empty(), "{}", diff.summary());
+    assert_eq!(diff.page_delta(), 0);
+}
+
+#[test]
+fn reports_added_removed_resized_and_recolored_frames() {
+    let old = pack_images(
+        vec![
+            common::solid("keep", 16, 16, [1, 0, 0, 255]),
+            common::solid("gone", 8, 8, [2, 0, 0, 255]),
+            common::solid("grow", 10, 10, [3, 0, 0, 255]),
+        ],
+        cfg(),
+    )
+    .unwrap();
+    let new = pack_images(
+        vec![
+            common::solid("keep", 16, 16, [9, 0, 0, 255]),
+            common::solid("grow", 24, 10, [3, 0, 0, 255]),
+            common::solid("fresh", 4, 4, [4, 0, 0, 255]),
+        ],
+        cfg(),
+    )
+    .unwrap();
+    let diff = diff_atlases(&old.atlas, &new.atlas);
+    assert_eq!(diff.added, vec!["fresh".to_string()]);
+    assert_eq!(diff.removed, vec!["gone".to_string()]);
+    assert_eq!(diff.resized.len(), 1);
+    assert_eq!(diff.resized[0].key, "grow");
+    assert_eq!(diff.resized[0].old.source_size, (10, 10));
+    assert_eq!(diff.resized[0].new.source_size, (24, 10));
+    // "keep" has the same size; it either moved or only its pixels changed.
+    let keep_moved = diff.moved.iter().any(|c| c.key == "keep");
+    let keep_recolored = diff.content_changed.iter().any(|k| k == "keep");
+    assert!(keep_moved ^ keep_recolored);
+    assert!(!diff.is_empty());
+}
+
+#[test]
+fn reports_moves_and_page_deltas() {
+    let mut old = pack_images(
+        vec![
+            common::solid("a", 60, 60, [1, 0, 0, 255]),
+            common::solid("b", 60, 60, [2, 0, 0, 255]),
+        ],
+        cfg(),
+    )
+    .unwrap();
+    let new = old.atlas.clone();
+    // Simulate the same sprites spread across two pages.
+    let mut moved = old.atlas.pages[0].clone();
+    let frame = moved.frames.remove(1);
+    let mut second = moved.clone();
+    second.id = 1;
+    second.frames = vec![frame];
+    second.frames[0].frame.x = 0;
+    second.frames[0].frame.y = 0;
+    old.atlas.pages = vec![moved, second];
+
+    let diff = diff_atlases(&old.atlas, &new);
+    assert_eq!(diff.old_pages, 2);
+    assert_eq!(diff.new_pages, 1);
+    assert_eq!(diff.page_delta(), -1);
+    assert!(diff.occupancy_delta() > 0.0);
+    assert_eq!(diff.moved.len(), 1);
+    assert_eq!(diff.moved[0].old.page, 1);
+    assert_eq!(diff.moved[0].new.page, 0);
+    assert!(diff.added.is_empty() && diff.removed.is_empty() && diff.resized.is_empty());
+}
@@ -0,0 +1,160 @@
+mod common;
+
+use tex_packer_core::config::{Padding, SpriteOptions};
+use tex_packer_core::{Atlas, IssueKind, PackerConfig, Rect, pack_images, pack_layout};
+
+fn cfg() -> PackerConfig {
+    let mut cfg = PackerConfig {
+        max_width: 96,
+        max_height: 96,
+        allow_rotation: true,
+        border_padding: 2,
+        padding: Some(Padding {
+            left: 1,
+            top: 0,
+            right: 3,
+            bottom: 2,
+        }),
+        texture_extrusion: 1,
+        ..Default::default()
+    };
+    cfg.sprites.insert(
+        "b".into(),
+        SpriteOptions {
+            texture_padding: Some(6),
+            texture_extrusion: Some(2),
+            ..Default::default()
+        },
+    );
+    cfg
+}
+
+fn packed() -> Atlas {
+    let sizes = [("a", 20, 12), ("b", 9, 30), ("c", 16, 16), ("d", 30, 6)]
+        .into_iter()
+        .map(|(k, w, h)| (k.to_string(), w, h))
+        .collect();
+    pack_layout(sizes, cfg()).unwrap()
+}
+
+fn frame<'a>(atlas: &'a mut Atlas, key: &str) -> &'a mut Rect {
+    &mut atlas.pages[0]
+        .frames
+        .iter_mut()
+        .find(|f| f.key == key)
+        .unwrap()
+        .frame
+}
+
+fn kinds(atlas: &Atlas) -> Vec<(String, IssueKind)> {
+    atlas
+        .validate(&cfg())
+        .into_iter()
+        .map(|i| (i.key.unwrap_or_default(), i.kind))
+        .collect()
+}
+
+#[test]
+fn packed_layouts_validate_clean() {
+    assert_eq!(kinds(&packed()), []);
+
+    let inputs = (0..12u8)
+        .map(|i| common::solid(format!("s{i}"), 5 + i as u32, 14 - i as u32, [i, 0, 0, 255]))
+        .collect();
+    let out = pack_images(inputs, cfg()).unwrap();
+    assert!(out.atlas.validate(&cfg()).is_empty());
+}
+
+#[test]
+fn reports_reservation_overlaps_and_bounds() {
+    let clean = packed();
+
+    // 1px right of "a": the pixels are apart, the padding and extrusion are not
+    let mut atlas = clean.clone();
+    let a = *frame(&mut atlas, "a");
+    let c = frame(&mut atlas, "c");
+    *c = Rect::new(a.x + a.w + 1, a.y, c.w, c.h);
+    let found = kinds(&atlas);
+    assert!(
+        found.iter().any(|(key, k)| matches!(
+            k,
+            IssueKind::ReservationOverlap { other } if [key.as_str(), other.as_str()].contains(&"c")
+        )),
+        "{found:?}"
+    );
+    assert!(
+        !found
+            .iter()
+            .any(|(_, k)| matches!(k, IssueKind::Overlap { .. }))
+    );
+
+    // Inside the border, but the leading extrusion and padding are not
+    let mut atlas = clean.clone();
+    frame(&mut atlas, "d").x = 3;
+    let found = kinds(&atlas);
+    assert!(
+        found.contains(&(
+            "d".into(),
+            IssueKind::ReservationOutOfBounds {
+                page_size: (clean.pages[0].width, clean.pages[0].height),
+                border: 2,
+            }
+        )),
+        "{found:?}"
+    );
+    assert!(
+        !found
+            .iter()
+            .any(|(_, k)| matches!(k, IssueKind::OutOfBounds { .. }))
+    );
+
+    // Per-sprite padding and extrusion widen "b"'s reservation only
+    let mut atlas = clean.clone();
+    let b = *frame(&mut atlas, "b");
+    let c = frame(&mut atlas, "c");
+    *c = Rect::new(b.x + b.w + 6, b.y, c.w, c.h);
+    assert!(
+        kinds(&atlas)
+            .iter()
+            .any(|(_, k)| matches!(k, IssueKind::ReservationOverlap { .. }))
+    );
+}
+
+#[test]
+fn reports_source_and_duplicate_problems() {
+    let mut atlas = packed();
+    let f = &mut atlas.pages[0].frames[0];
+    let key = f.key.clone();
+    f.source = Rect::new(1, 0, f.source.w, f.source.h);
+    f.source_size.0 += 1;
+    assert_eq!(kinds(&atlas), [(key.clone(), IssueKind::UntrimmedSource)]);
+
+    let mut atlas = packed();
+    atlas.pages[0].frames[0].source.w += 1;
+    assert!(kinds(&atlas).contains(&(key, IssueKind::SourceOutOfBounds)));
+
+    let issue = &packed_with_dup().validate(&cfg())[0];
+    assert_eq!(issue.kind, IssueKind::DuplicateKey);
+    assert!(issue.to_string().contains("duplicate key"), "{issue}");
+}
+
+fn packed_with_dup() -> Atlas {
+    let mut atlas = packed();
+    let copy = atlas.pages[0].frames[0].clone();
+    atlas.pages[0].frames.push(copy);
+    atlas
+}
+
+#[test]
+fn issues_display() {
+    let mut atlas = packed();
+    frame(&mut atlas, "d").x = 3;
+    let issue = atlas.validate(&cfg()).into_iter().find(|i| {
+        i.key.as_deref() == Some("d") && matches!(i.kind, IssueKind::ReservationOutOfBounds { .. })
+    });
+    let text = issue.unwrap().to_string();
+    assert!(
+        text.contains("frame 'd'") && text.contains("extrusion/padding reaches outside"),
+        "{text}"
+    );
+}
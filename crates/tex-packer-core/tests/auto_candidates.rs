@@ -0,0 +1,121 @@
+mod common;
+
+use tex_packer_core::config::{
+    AlgorithmFamily, AutoCandidate, GuillotineChoice, GuillotineSplit, MaxRectsHeuristic,
+    ShelfHeuristic, SkylineHeuristic, SortOrder,
+};
+use tex_packer_core::{InputImage, PackerConfig, pack_images, pack_images_iter};
+
+fn inputs() -> Vec<InputImage> {
+    [(40, 12), (12, 40), (24, 24), (30, 10), (16, 16), (20, 8)]
+        .iter()
+        .enumerate()
+        .map(|(i, &(w, h))| common::solid(format!("img{i}"), w, h, [9; 4]))
+        .collect()
+}
+
+fn cfg(candidates: Vec<AutoCandidate>) -> PackerConfig {
+    PackerConfig::builder()
+        .with_max_dimensions(64, 64)
+        .texture_padding(0)
+        .trim(false)
+        .family(AlgorithmFamily::Auto)
+        .auto_candidates(candidates)
+        .build()
+}
+
+#[test]
+fn candidates_replace_the_preset_portfolio() {
+    let only = AutoCandidate {
+        mr_heuristic: Some(MaxRectsHeuristic::ContactPoint),
+        sort_order: Some(SortOrder::NameAsc),
+        allow_rotation: Some(false),
+        ..AutoCandidate::new(AlgorithmFamily::MaxRects)
+    };
+    let out = pack_images(inputs(), cfg(vec![only])).unwrap();
+    let winner = out.stats().winning_config.unwrap();
+    assert_eq!(winner.family, AlgorithmFamily::MaxRects);
+    assert_eq!(winner.heuristic, "ContactPoint");
+    assert_eq!(winner.sort_order, SortOrder::NameAsc);
+    assert!(!winner.allow_rotation);
+
+    // The winner is picked among the listed candidates only
+    let two = vec![
+        "shelf:bhf@height_desc".parse().unwrap(),
+        "skyline:bl".parse().unwrap(),
+    ];
+    let winner = pack_images(inputs(), cfg(two))
+        .unwrap()
+        .stats()
+        .winning_config
+        .unwrap();
+    assert!(matches!(
+        winner.family,
+        AlgorithmFamily::Shelf | AlgorithmFamily::Skyline
+    ));
+
+    // Streaming cannot compare candidates and packs with the first one
+    let stream = pack_images_iter(inputs().into_iter().map(Ok), cfg(vec![only_shelf()])).unwrap();
+    for page in stream {
+        assert!(!page.unwrap().page.frames.is_empty());
+    }
+}
+
+fn only_shelf() -> AutoCandidate {
+    AutoCandidate::new(AlgorithmFamily::Shelf)
+}
+
+#[test]
+fn candidate_strings_parse_per_family() {
+    let c: AutoCandidate = "guillotine:baf:slas@area_desc".parse().unwrap();
+    assert_eq!(c.g_choice, Some(GuillotineChoice::BestAreaFit));
+    assert_eq!(c.g_split, Some(GuillotineSplit::SplitShorterLeftoverAxis));
+    assert_eq!(c.sort_order, Some(SortOrder::AreaDesc));
+    let c: AutoCandidate = "skyline:minwaste".parse().unwrap();
+    assert_eq!(c.skyline_heuristic, Some(SkylineHeuristic::MinWaste));
+    let c: AutoCandidate = "shelf".parse().unwrap();
+    assert_eq!(c, AutoCandidate::new(AlgorithmFamily::Shelf));
+    assert_eq!(c.shelf_heuristic, None::<ShelfHeuristic>);
+    for bad in [
+        "grid:baf",
+        "maxrects:baf:slas",
+        "maxrects:nope",
+        "shelf@tallest",
+        "",
+    ] {
+        assert!(bad.parse::<AutoCandidate>().is_err(), "{bad}");
+    }
+
+    let mut c = PackerConfig::default();
+    c.set_str("auto_candidates", "maxrects:bssf, skyline:bl")
+        .unwrap();
+    assert_eq!(c.auto_candidates.len(), 2);
+    let err = c.set_str("auto_candidates", "maxrects:x").unwrap_err();
+    assert!(err.to_string().contains("maxrects:x"), "{err}");
+    c.set_str("auto_candidates", "").unwrap();
+    assert!(c.auto_candidates.is_empty());
+    c.set_json(
+        "auto_candidates",
+        &serde_json::json!([{ "family": "maxrects", "mr_heuristic": "contactpoint" }]),
+    )
+    .unwrap();
+    assert_eq!(
+        c.auto_candidates[0].mr_heuristic,
+        Some(MaxRectsHeuristic::ContactPoint)
+    );
+}
+
+#[test]
+fn auto_and_factoryless_custom_candidates_are_rejected() {
+    for family in [AlgorithmFamily::Auto, AlgorithmFamily::Custom] {
+        let Err(err) = pack_images(inputs(), cfg(vec![AutoCandidate::new(family.clone())])) else {
+            panic!("{family:?} candidate accepted");
+        };
+        assert!(err.to_string().contains("auto"), "{err}");
+    }
+    let json = serde_json::to_value(cfg(vec![only_shelf()])).unwrap();
+    assert_eq!(
+        json["auto_candidates"],
+        serde_json::json!([{ "family": "shelf" }])
+    );
+}
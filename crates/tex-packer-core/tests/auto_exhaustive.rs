@@ -0,0 +1,86 @@
+mod common;
+
+use tex_packer_core::config::{AlgorithmFamily, AutoMode};
+use tex_packer_core::{InputImage, PackOutput, PackerConfig, pack_images};
+
+fn inputs() -> Vec<InputImage> {
+    let sizes = [
+        (40, 12),
+        (12, 40),
+        (24, 24),
+        (30, 10),
+        (10, 30),
+        (16, 16),
+        (20, 8),
+        (8, 20),
+        (14, 6),
+        (6, 14),
+    ];
+    sizes
+        .iter()
+        .enumerate()
+        .map(|(i, &(w, h))| common::solid(format!("img{i}"), w, h, [200, 100, 50, 255]))
+        .collect()
+}
+
+fn auto_cfg(mode: AutoMode) -> PackerConfig {
+    PackerConfig {
+        max_width: 64,
+        max_height: 64,
+        texture_padding: 0,
+        trim: false,
+        family: AlgorithmFamily::Auto,
+        auto_mode: mode,
+        ..Default::default()
+    }
+}
+
+fn score(out: &PackOutput) -> (usize, u64) {
+    let area = out
+        .pages
+        .iter()
+        .map(|p| p.page.width as u64 * p.page.height as u64)
+        .sum();
+    (out.pages.len(), area)
+}
+
+#[test]
+fn exhaustive_is_no_worse_than_quality() {
+    let quality = pack_images(inputs(), auto_cfg(AutoMode::Quality)).expect("quality");
+    let exhaustive = pack_images(inputs(), auto_cfg(AutoMode::Exhaustive)).expect("exhaustive");
+    assert!(score(&exhaustive) <= score(&quality));
+}
+
+#[test]
+fn auto_reports_winning_config() {
+    let out = pack_images(inputs(), auto_cfg(AutoMode::Exhaustive)).expect("pack");
+    let winner = out.stats().winning_config.expect("auto records its winner");
+    assert_ne!(winner.family, AlgorithmFamily::Auto);
+    assert!(!winner.heuristic.is_empty());
+    assert_eq!(out.atlas.meta.packer.as_ref(), Some(&winner));
+}
+
+#[test]
+fn exhaustive_respects_disabled_rotation() {
+    let cfg = PackerConfig {
+        allow_rotation: false,
+        ..auto_cfg(AutoMode::Exhaustive)
+    };
+    let out = pack_images(inputs(), cfg).expect("pack");
+    assert!(!out.stats().winning_config.unwrap().allow_rotation);
+    assert!(
+        out.pages
+            .iter()
+            .all(|p| p.page.frames.iter().all(|f| !f.rotated))
+    );
+}
+
+#[test]
+fn fixed_family_has_no_winning_config() {
+    let cfg = PackerConfig {
+        family: AlgorithmFamily::Skyline,
+        ..auto_cfg(AutoMode::Quality)
+    };
+    let out = pack_images(inputs(), cfg).expect("pack");
+    assert!(out.stats().winning_config.is_none());
+}
@@ -0,0 +1,141 @@
+mod common;
+
+use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use tex_packer_core::config::{AlgorithmFamily, AutoCandidate, AutoObjective};
+use tex_packer_core::{InputImage, PackOutput, PackerConfig, pack_images};
+
+fn inputs() -> Vec<InputImage> {
+    [
+        (40, 12),
+        (12, 40),
+        (24, 24),
+        (30, 10),
+        (16, 16),
+        (20, 8),
+        (50, 6),
+    ]
+    .iter()
+    .enumerate()
+    .map(|(i, &(w, h))| common::solid(format!("img{i}"), w, h, [9; 4]))
+    .collect()
+}
+
+fn candidates() -> Vec<AutoCandidate> {
+    [
+        "maxrects:baf",
+        "skyline:bl",
+        "shelf:nf@name_asc",
+        "guillotine:baf:slas",
+    ]
+    .iter()
+    .map(|s| s.parse().unwrap())
+    .collect()
+}
+
+fn cfg(objective: AutoObjective) -> PackerConfig {
+    PackerConfig::builder()
+        .with_max_dimensions(64, 64)
+        .texture_padding(0)
+        .trim(false)
+        .family(AlgorithmFamily::Auto)
+        .auto_candidates(candidates())
+        .auto_objective(objective)
+        .build()
+}
+
+/// (pages, total area, -occupancy, largest side) of a packed atlas.
+fn metrics(out: &PackOutput) -> (usize, u64, f64, u32) {
+    let stats = out.stats();
+    let side = out
+        .pages
+        .iter()
+        .map(|p| p.page.width.max(p.page.height))
+        .max()
+        .unwrap();
+    (
+        stats.num_pages,
+        stats.total_page_area,
+        -stats.occupancy,
+        side,
+    )
+}
+
+#[test]
+fn winner_is_best_under_each_objective() {
+    let singles: Vec<_> = candidates()
+        .iter()
+        .map(|c| {
+            let mut concrete = c.apply(&cfg(AutoObjective::Pages));
+            concrete.minimize_page_size = false;
+            metrics(&pack_images(inputs(), concrete).unwrap())
+        })
+        .collect();
+    let best_area = singles.iter().map(|m| m.1).min().unwrap();
+    let best_occupancy = singles.iter().map(|m| m.2).fold(f64::INFINITY, f64::min);
+    let best_side = singles.iter().map(|m| m.3).min().unwrap();
+
+    let area = metrics(&pack_images(inputs(), cfg(AutoObjective::Area)).unwrap());
+    assert_eq!(area.1, best_area);
+    let occupancy = metrics(&pack_images(inputs(), cfg(AutoObjective::Occupancy)).unwrap());
+    assert_eq!(occupancy.2, best_occupancy);
+    let side = metrics(&pack_images(inputs(), cfg(AutoObjective::MaxDimension)).unwrap());
+    assert_eq!(side.3, best_side);
+}
+
+#[test]
+fn user_score_overrides_the_objective() {
+    let calls = Arc::new(AtomicUsize::new(0));
+    let counter = calls.clone();
+    // Prefer the layout with the most frames on its first page
+    let cfg = PackerConfig::builder()
+        .with_max_dimensions(64, 64)
+        .texture_padding(0)
+        .trim(false)
+        .family(AlgorithmFamily::Auto)
+        .auto_candidates(candidates())
+        .auto_score(move |pages| {
+            counter.fetch_add(1, Ordering::Relaxed);
+            -(pages[0].frames.len() as f64)
+        })
+        .build();
+    let out = pack_images(inputs(), cfg.clone()).unwrap();
+    assert_eq!(calls.load(Ordering::Relaxed), candidates().len());
+    let first = out.pages[0].page.frames.len();
+    for c in candidates() {
+        let single = pack_images(inputs(), c.apply(&cfg)).unwrap();
+        assert!(single.pages[0].page.frames.len() <= first);
+    }
+}
+
+#[test]
+fn objectives_parse_and_validate() {
+    assert_eq!("area".parse(), Ok(AutoObjective::Area));
+    assert_eq!("max-dimension".parse(), Ok(AutoObjective::MaxDimension));
+    assert_eq!(
+        "weighted:1,0.5,0,2".parse(),
+        Ok(AutoObjective::Weighted {
+            pages: 1.0,
+            area: 0.5,
+            max_dimension: 0.0,
+            occupancy: 2.0,
+        })
+    );
+    assert!("weighted:1,2".parse::<AutoObjective>().is_err());
+
+    let mut c = PackerConfig::default();
+    c.set_str("auto_objective", "occupancy").unwrap();
+    assert_eq!(c.auto_objective, AutoObjective::Occupancy);
+    assert!(c.set_str("auto_objective", "smallest").is_err());
+    assert!(c.set_str("auto_score", "x").is_err());
+
+    let weighted = cfg(AutoObjective::Weighted {
+        pages: f64::NAN,
+        area: 1.0,
+        max_dimension: 0.0,
+        occupancy: 0.0,
+    });
+    assert!(weighted.validate().is_err());
+    let json = serde_json::to_value(cfg(AutoObjective::MaxDimension)).unwrap();
+    assert_eq!(json["auto_objective"], "max_dimension");
+}
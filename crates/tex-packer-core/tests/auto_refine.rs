@@ -0,0 +1,102 @@
+mod common;
+
+use tex_packer_core::config::{AlgorithmFamily, AutoCandidate, AutoMode, SortOrder};
+use tex_packer_core::{InputImage, PackOutput, PackerConfig, pack_images};
+
+fn inputs() -> Vec<InputImage> {
+    [
+        (40, 12),
+        (12, 40),
+        (24, 24),
+        (30, 10),
+        (10, 30),
+        (16, 16),
+        (20, 8),
+        (50, 6),
+        (6, 50),
+        (14, 14),
+    ]
+    .iter()
+    .enumerate()
+    .map(|(i, &(w, h))| common::solid(format!("img{i}"), w, h, [7; 4]))
+    .collect()
+}
+
+/// A single unsorted shelf candidate: a weak start that refinement can improve on.
+fn cfg(refine: bool) -> PackerConfig {
+    PackerConfig::builder()
+        .with_max_dimensions(64, 64)
+        .texture_padding(0)
+        .trim(false)
+        .family(AlgorithmFamily::Auto)
+        .auto_candidates(vec!["shelf:nf@none".parse().unwrap()])
+        .auto_refine(refine)
+        .build()
+}
+
+fn score(out: &PackOutput) -> (usize, u64) {
+    let stats = out.stats();
+    (stats.num_pages, stats.total_page_area)
+}
+
+#[test]
+fn refinement_is_never_worse_and_reports_its_search() {
+    let plain = pack_images(inputs(), cfg(false)).unwrap();
+    assert!(plain.atlas.meta.auto_refinement.is_none());
+    assert_eq!(plain.timings.refine_ms, 0);
+
+    let refined = pack_images(inputs(), cfg(true)).unwrap();
+    assert!(score(&refined) <= score(&plain));
+    let r = refined.stats().auto_refinement.unwrap();
+    assert_eq!(r.initial.sort_order, SortOrder::None);
+    assert!(!r.budget_exhausted);
+    // The five other sort orders plus rotation toggled, at least once
+    assert!(r.variants >= 6, "{r:?}");
+    if r.improvements > 0 {
+        assert_ne!(refined.atlas.meta.packer.as_ref(), Some(&r.initial));
+        assert!(score(&refined) < score(&plain));
+    }
+    assert!(refined.timings.refine_ms <= refined.timings.pack_ms);
+}
+
+#[test]
+fn refinement_is_deterministic_and_covers_maxrects_variants() {
+    let maxrects = PackerConfig {
+        auto_candidates: vec![AutoCandidate::new(AlgorithmFamily::MaxRects)],
+        deterministic: true,
+        time_budget_ms: Some(1),
+        ..cfg(true)
+    };
+    let a = pack_images(inputs(), maxrects.clone()).unwrap();
+    let b = pack_images(inputs(), maxrects).unwrap();
+    assert_eq!(a.atlas.meta.packer, b.atlas.meta.packer);
+    assert_eq!(a.atlas.meta.auto_refinement, b.atlas.meta.auto_refinement);
+    // Five other sort orders, rotation, global insertion and reference splits
+    assert!(a.atlas.meta.auto_refinement.unwrap().variants >= 8);
+}
+
+#[test]
+fn refinement_respects_rotation_and_skips_anneal() {
+    let fixed = PackerConfig {
+        allow_rotation: false,
+        ..cfg(true)
+    };
+    let out = pack_images(inputs(), fixed).unwrap();
+    assert!(!out.atlas.meta.packer.unwrap().allow_rotation);
+    assert!(
+        out.atlas
+            .pages
+            .iter()
+            .flat_map(|p| &p.frames)
+            .all(|f| !f.rotated)
+    );
+
+    let anneal = PackerConfig {
+        auto_mode: AutoMode::Anneal,
+        auto_candidates: Vec::new(),
+        time_budget_ms: Some(20),
+        ..cfg(true)
+    };
+    let out = pack_images(inputs(), anneal).unwrap();
+    assert!(out.atlas.meta.auto_refinement.is_none());
+}
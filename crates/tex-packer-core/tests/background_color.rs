@@ -0,0 +1,137 @@
+mod common;
+
+use image::{Rgba, RgbaImage};
+use tex_packer_core::compositing::matte;
+use tex_packer_core::config::parse_color;
+use tex_packer_core::{InputImage, PackOutput, PackerConfig, pack_images, pack_images_iter};
+
+const BG: [u8; 4] = [255, 255, 255, 255];
+
+fn inputs() -> Vec<InputImage> {
+    (0..6u32)
+        .map(|i| {
+            let img = RgbaImage::from_fn(8 + i, 6, |x, _| {
+                // Opaque red, half-transparent red, then fully transparent columns
+                match x % 3 {
+                    0 => Rgba([255, 0, 0, 255]),
+                    1 => Rgba([255, 0, 0, 128]),
+                    _ => Rgba([0, 0, 0, 0]),
+                }
+            });
+            common::input(format!("f{i}"), img)
+        })
+        .collect()
+}
+
+fn cfg() -> PackerConfig {
+    PackerConfig {
+        max_width: 64,
+        max_height: 64,
+        border_padding: 2,
+        texture_padding: 2,
+        texture_extrusion: 1,
+        trim: false,
+        background_color: Some(BG),
+        ..Default::default()
+    }
+}
+
+fn page_pixels(out: &PackOutput) -> Vec<RgbaImage> {
+    out.pages
+        .iter()
+        .map(|p| p.pixels().expect("readable").into_owned())
+        .collect()
+}
+
+#[test]
+fn matte_blends_over_the_background() {
+    let mut img =
+        RgbaImage::from_vec(3, 1, vec![255, 0, 0, 255, 255, 0, 0, 128, 10, 20, 30, 0]).unwrap();
+    matte(&mut img, BG);
+    assert_eq!(img.get_pixel(0, 0).0, [255, 0, 0, 255]);
+    assert_eq!(img.get_pixel(1, 0).0, [255, 127, 127, 255]);
+    assert_eq!(img.get_pixel(2, 0).0, BG);
+
+    // A translucent background keeps partial alpha.
+    let mut img = RgbaImage::from_pixel(1, 1, Rgba([0, 0, 0, 0]));
+    matte(&mut img, [0, 0, 255, 64]);
+    assert_eq!(img.get_pixel(0, 0).0, [0, 0, 255, 64]);
+}
+
+#[test]
+fn pages_are_opaque_and_meta_reports_the_color() {
+    let out = pack_images(inputs(), cfg()).unwrap();
+    assert_eq!(out.atlas.meta.background_color, Some(BG));
+    for page in page_pixels(&out) {
+        assert!(page.pixels().all(|p| p[3] == 255));
+        // Border padding is untouched by frames
+        assert_eq!(page.get_pixel(0, 0).0, BG);
+    }
+    for (page, f) in out
+        .pages
+        .iter()
+        .flat_map(|p| p.page.frames.iter().map(move |f| (&p.rgba, f)))
+    {
+        if f.rotated {
+            continue;
+        }
+        assert_eq!(page.get_pixel(f.frame.x, f.frame.y).0, [255, 0, 0, 255]);
+        assert_eq!(
+            page.get_pixel(f.frame.x + 1, f.frame.y).0,
+            [255, 127, 127, 255]
+        );
+        assert_eq!(page.get_pixel(f.frame.x + 2, f.frame.y).0, BG);
+    }
+
+    let plain = pack_images(
+        inputs(),
+        PackerConfig {
+            background_color: None,
+            ..cfg()
+        },
+    )
+    .unwrap();
+    assert_eq!(plain.atlas.meta.background_color, None);
+    assert_eq!(plain.pages[0].rgba.get_pixel(0, 0).0, [0, 0, 0, 0]);
+}
+
+#[test]
+fn every_compositing_path_agrees() {
+    let base = page_pixels(&pack_images(inputs(), cfg()).unwrap());
+    let low = pack_images(
+        inputs(),
+        PackerConfig {
+            low_memory: true,
+            ..cfg()
+        },
+    )
+    .unwrap();
+    assert_eq!(page_pixels(&low), base);
+
+    let streamed = pack_images_iter(inputs().into_iter().map(Ok), cfg())
+        .unwrap()
+        .into_output()
+        .unwrap();
+    assert_eq!(streamed.atlas.meta.background_color, Some(BG));
+    for page in page_pixels(&streamed) {
+        assert!(page.pixels().all(|p| p[3] == 255));
+    }
+}
+
+#[test]
+fn colors_parse_from_strings() {
+    assert_eq!(parse_color("#ff8000"), Some([255, 128, 0, 255]));
+    assert_eq!(parse_color("00000080"), Some([0, 0, 0, 128]));
+    assert_eq!(parse_color("1, 2, 3"), Some([1, 2, 3, 255]));
+    assert_eq!(parse_color("1,2,3,4"), Some([1, 2, 3, 4]));
+    assert_eq!(parse_color("#fff"), None);
+    assert_eq!(parse_color("1,2"), None);
+    assert_eq!(parse_color("256,0,0"), None);
+
+    let mut c = PackerConfig::default();
+    c.set_str("background_color", "#102030").unwrap();
+    assert_eq!(c.background_color, Some([16, 32, 48, 255]));
+    c.set_str("background_color", "none").unwrap();
+    assert_eq!(c.background_color, None);
+    assert!(c.set_str("background_color", "blue").is_err());
+}
@@ -0,0 +1,103 @@
+use tex_packer_core::{
+    Atlas, AtlasView, BINARY_ATLAS_VERSION, NinePatch, PackerConfig, Pivot, pack_layout,
+    to_binary_atlas,
+};
+
+fn atlas() -> Atlas {
+    let sizes = (0..40)
+        .map(|i| (format!("sprites/frame_{i:02}.png"), 8 + i % 7, 6 + i % 5))
+        .chain([("hé/\"x\".png".to_string(), 3, 4)])
+        .collect::<Vec<_>>();
+    let cfg = PackerConfig {
+        max_width: 48,
+        max_height: 48,
+        ..Default::default()
+    };
+    let mut atlas = pack_layout(sizes, cfg).unwrap();
+    let f = &mut atlas.pages[0].frames[0];
+    f.pivot = Pivot::new(0.25, 1.0);
+    f.nine_patch = Some(NinePatch {
+        left: 1,
+        top: 2,
+        right: 3,
+        bottom: 4,
+    });
+    f.content_hash = Some(u64::MAX - 7);
+    atlas
+}
+
+fn names(atlas: &Atlas) -> Vec<String> {
+    (0..atlas.pages.len())
+        .map(|i| format!("sheet_{i}.png"))
+        .collect()
+}
+
+#[test]
+fn round_trips_through_owned_model() {
+    let atlas = atlas();
+    assert!(atlas.pages.len() > 1);
+    let bytes = to_binary_atlas(&atlas, &names(&atlas));
+    let back = Atlas::from_binary(&bytes).unwrap();
+    assert_eq!(
+        serde_json::to_value(&back).unwrap(),
+        serde_json::to_value(&atlas).unwrap()
+    );
+}
+
+#[test]
+fn view_reads_without_copying() {
+    let atlas = atlas();
+    let bytes = to_binary_atlas(&atlas, &names(&atlas));
+    let view = AtlasView::parse(&bytes).unwrap();
+    assert_eq!(view.version(), BINARY_ATLAS_VERSION);
+    assert_eq!(view.page_count(), atlas.pages.len());
+    let total: usize = atlas.pages.iter().map(|p| p.frames.len()).sum();
+    assert_eq!(view.frame_count(), total);
+    for (i, page) in atlas.pages.iter().enumerate() {
+        let pv = view.page(i).unwrap();
+        assert_eq!(pv.image(), format!("sheet_{i}.png"));
+        assert_eq!((pv.width(), pv.height()), (page.width, page.height));
+        let keys: Vec<&str> = pv.frames().map(|f| f.key()).collect();
+        let expected: Vec<&str> = page.frames.iter().map(|f| f.key.as_str()).collect();
+        assert_eq!(keys, expected);
+    }
+    for f in atlas.pages.iter().flat_map(|p| &p.frames) {
+        let fv = view.find(&f.key).unwrap();
+        assert_eq!(fv.key(), f.key);
+        assert_eq!(fv.frame(), f.frame);
+        assert_eq!(fv.source(), f.source);
+        assert_eq!(fv.rotated(), f.rotated);
+        assert_eq!(fv.pivot(), f.pivot);
+        assert_eq!(fv.nine_patch(), f.nine_patch);
+        assert_eq!(fv.content_hash(), f.content_hash);
+    }
+    assert!(view.find("missing.png").is_none());
+    assert_eq!(view.meta().unwrap().app, atlas.meta.app);
+}
+
+#[test]
+fn rejects_corrupt_input() {
+    let atlas = atlas();
+    let bytes = to_binary_atlas(&atlas, &names(&atlas));
+    assert!(AtlasView::parse(&bytes[..10]).is_err());
+    assert!(AtlasView::parse(&bytes[..bytes.len() - 1]).is_err());
+    let mut bad = bytes.clone();
+    bad[0] = b'X';
+    assert!(AtlasView::parse(&bad).is_err());
+    let mut bad = bytes.clone();
+    bad[4] = 99;
+    assert!(AtlasView::parse(&bad).is_err());
+    // Frame count larger than the tables
+    let mut bad = bytes.clone();
+    bad[12..16].copy_from_slice(&u32::MAX.to_le_bytes());
+    assert!(AtlasView::parse(&bad).is_err());
+
+    let empty: Atlas = Atlas {
+        pages: vec![],
+        meta: atlas.meta.clone(),
+    };
+    let view_bytes = to_binary_atlas(&empty, &[]);
+    let view = AtlasView::parse(&view_bytes).unwrap();
+    assert_eq!((view.page_count(), view.frame_count()), (0, 0));
+    assert!(view.find("a").is_none());
+}
@@ -0,0 +1,94 @@
+//! Fixtures shared by the integration tests. Each test binary compiles its own copy and uses only
+//! some of them.
+#![allow(dead_code)]
+
+use image::{DynamicImage, Rgba, RgbaImage};
+use tex_packer_core::prelude::*;
+
+/// Wraps `img` as an input named `key`.
+pub fn input(key: impl Into<String>, img: RgbaImage) -> InputImage {
+    InputImage {
+        key: key.into(),
+        image: DynamicImage::ImageRgba8(img),
+    }
+}
+
+/// A `w`x`h` input filled with `rgba`.
+pub fn solid(key: impl Into<String>, w: u32, h: u32, rgba: [u8; 4]) -> InputImage {
+    input(key, RgbaImage::from_pixel(w, h, Rgba(rgba)))
+}
+
+/// A `w`x`h` block of `rgba` inside a transparent `margin` on every side, so trimming recovers
+/// exactly the block.
+pub fn framed(key: impl Into<String>, w: u32, h: u32, margin: u32, rgba: [u8; 4]) -> InputImage {
+    let img = RgbaImage::from_fn(w + 2 * margin, h + 2 * margin, |x, y| {
+        let inside = x >= margin && y >= margin && x < w + margin && y < h + margin;
+        Rgba(if inside { rgba } else { [0, 0, 0, 0] })
+    });
+    input(key, img)
+}
+
+/// `max_width`x`max_height` pages without padding, extrusion, trimming or rotation, so frames
+/// are exactly the input sizes where the packer puts them.
+pub fn tight(max_width: u32, max_height: u32) -> PackerConfig {
+    PackerConfig {
+        max_width,
+        max_height,
+        border_padding: 0,
+        texture_padding: 0,
+        texture_extrusion: 0,
+        trim: false,
+        allow_rotation: false,
+        ..Default::default()
+    }
+}
+
+/// (page id, key, frame, rotated) of every frame, page by page.
+pub fn placements(pages: &[Page<String>]) -> Vec<(usize, String, Rect, bool)> {
+    pages
+        .iter()
+        .flat_map(|p| {
+            p.frames
+                .iter()
+                .map(|f| (p.id, f.key.clone(), f.frame, f.rotated))
+        })
+        .collect()
+}
+
+/// Summed area of `pages`.
+pub fn page_area(pages: &[Page<String>]) -> u64 {
+    pages.iter().map(|p| p.width as u64 * p.height as u64).sum()
+}
+
+/// Panics if two frames of `page` overlap.
+pub fn assert_disjoint(page: &Page<String>) {
+    for (i, a) in page.frames.iter().enumerate() {
+        for b in &page.frames[i + 1..] {
+            let (a, b) = (a.frame, b.frame);
+            let disjoint =
+                a.x + a.w <= b.x || b.x + b.w <= a.x || a.y + a.h <= b.y || b.y + b.h <= a.y;
+            assert!(disjoint, "{a:?} overlaps {b:?}");
+        }
+    }
+}
+
+/// Panics if two reserved slots (frame plus padding) of a runtime session overlap or leave
+/// their page.
+pub fn assert_slots_disjoint(s: &AtlasSession) {
+    let atlas = s.snapshot_atlas();
+    for page in &atlas.pages {
+        let rects: Vec<Rect> = page
+            .frames
+            .iter()
+            .map(|f| s.get_reserved_slot(&f.key).unwrap().1)
+            .collect();
+        for (i, a) in rects.iter().enumerate() {
+            assert!(a.x + a.w <= page.width && a.y + a.h <= page.height);
+            for b in &rects[i + 1..] {
+                let overlap =
+                    a.x < b.x + b.w && b.x < a.x + a.w && a.y < b.y + b.h && b.y < a.y + a.h;
+                assert!(!overlap, "{a:?} overlaps {b:?}");
+            }
+        }
+    }
+}
@@ -0,0 +1,115 @@
+use tex_packer_core::config::{
+    AlgorithmFamily, MaxRectsHeuristic, PageGrouping, PageSizeMode, PivotMode, SortOrder,
+    parse_option,
+};
+use tex_packer_core::{PackerConfig, Pivot, TexPackerError};
+
+#[test]
+fn overrides_apply_in_order_over_defaults() {
+    let cfg = PackerConfig::from_str_overrides([
+        ("family", "maxrects"),
+        ("mr_heuristic", "bssf"),
+        ("texture_padding", "4"),
+        ("trim", "false"),
+        ("sort_order", "name_asc"),
+        ("pivot_mode", "alpha-centroid"),
+        ("default_pivot", "bottom-center"),
+        ("allowed_sizes", "256, 512,1024"),
+        ("max_pages", "3"),
+        ("max_pages", "none"),
+        ("page_grouping", "folder"),
+    ])
+    .unwrap();
+    assert_eq!(cfg.family, AlgorithmFamily::MaxRects);
+    assert_eq!(cfg.mr_heuristic, MaxRectsHeuristic::BestShortSideFit);
+    assert_eq!(cfg.texture_padding, 4);
+    assert!(!cfg.trim);
+    assert_eq!(cfg.sort_order, SortOrder::NameAsc);
+    assert_eq!(cfg.pivot_mode, PivotMode::AlphaCentroid);
+    assert_eq!(cfg.default_pivot, Pivot::BOTTOM_CENTER);
+    assert_eq!(
+        cfg.page_size,
+        PageSizeMode::AllowedList {
+            sizes: vec![256, 512, 1024]
+        }
+    );
+    assert_eq!(cfg.max_pages, None);
+    assert_eq!(cfg.page_grouping, PageGrouping::Folder);
+    assert_eq!(cfg.max_width, PackerConfig::default().max_width);
+}
+
+#[test]
+fn invalid_enum_names_the_field_value_and_alternatives() {
+    let err = PackerConfig::from_str_overrides([("family", "maxrect")]).unwrap_err();
+    match &err {
+        TexPackerError::InvalidOption {
+            field,
+            value,
+            expected,
+        } => {
+            assert_eq!(field, "family");
+            assert_eq!(value, "maxrect");
+            assert_eq!(
+                expected,
+                "one of skyline, maxrects, guillotine, shelf, grid, auto, custom"
+            );
+        }
+        other => panic!("unexpected error {other:?}"),
+    }
+    assert_eq!(
+        err.to_string(),
+        "Invalid value 'maxrect' for `family`: expected one of skyline, maxrects, guillotine, shelf, grid, auto, custom"
+    );
+}
+
+#[test]
+fn invalid_scalars_and_unknown_fields_are_errors() {
+    let err = PackerConfig::from_str_overrides([("texture_padding", "-1")]).unwrap_err();
+    assert!(
+        matches!(err, TexPackerError::InvalidOption { ref expected, .. } if expected == "a non-negative integer")
+    );
+    let err = PackerConfig::from_str_overrides([("trim", "maybe")]).unwrap_err();
+    assert!(
+        matches!(err, TexPackerError::InvalidOption { ref expected, .. } if expected == "true or false")
+    );
+    let err = PackerConfig::from_str_overrides([("texture_paddng", "2")]).unwrap_err();
+    assert!(matches!(err, TexPackerError::InvalidConfig(ref m) if m.contains("texture_paddng")));
+    let err = PackerConfig::from_str_overrides([("sprites", "x")]).unwrap_err();
+    assert!(matches!(err, TexPackerError::InvalidConfig(_)));
+}
+
+#[test]
+fn parse_option_accepts_aliases() {
+    let h: MaxRectsHeuristic = parse_option("heuristic", "BestAreaFit").unwrap();
+    assert_eq!(h, MaxRectsHeuristic::BestAreaFit);
+    assert!(parse_option::<SortOrder>("sort_order", "random").is_err());
+}
+
+#[test]
+fn set_json_handles_scalars_and_map_fields() {
+    let mut cfg = PackerConfig::default();
+    cfg.set_json("family", &serde_json::json!("maxrects"))
+        .unwrap();
+    cfg.set_json("texture_padding", &serde_json::json!(5))
+        .unwrap();
+    cfg.set_json("trim", &serde_json::json!(false)).unwrap();
+    cfg.set_json(
+        "key_transform",
+        &serde_json::json!({"strip_directories": true}),
+    )
+    .unwrap();
+    assert_eq!(cfg.family, AlgorithmFamily::MaxRects);
+    assert_eq!(cfg.texture_padding, 5);
+    assert!(!cfg.trim);
+    assert!(cfg.key_transform.strip_directories);
+
+    assert!(cfg.set_json("nope", &serde_json::json!({})).is_err());
+    assert!(
+        cfg.set_json(
+            "key_transform",
+            &serde_json::json!({"strip_directories": "x"})
+        )
+        .is_err()
+    );
+    assert!(cfg.key_transform.strip_directories);
+}
@@ -0,0 +1,126 @@
+mod common;
+
+use image::{Rgba, RgbaImage};
+use tex_packer_core::hash::{Xxh64, stable_frame_id, xxh64};
+use tex_packer_core::{
+    Frame, InputImage, PackOutput, PackerConfig, pack_images, pack_layout, to_json_array,
+    to_json_hash,
+};
+
+fn sprite(key: &str, w: u32, h: u32, margin: u32, color: [u8; 4]) -> InputImage {
+    let img = RgbaImage::from_fn(w + 2 * margin, h + 2 * margin, |x, y| {
+        let inside = x >= margin && y >= margin && x < w + margin && y < h + margin;
+        if inside {
+            Rgba([
+                color[0],
+                color[1] ^ (x - margin) as u8,
+                color[2] ^ (y - margin) as u8,
+                color[3],
+            ])
+        } else {
+            Rgba([0, 0, 0, 0])
+        }
+    });
+    common::input(key, img)
+}
+
+fn frame<'a>(out: &'a PackOutput, key: &str) -> &'a Frame {
+    out.atlas
+        .pages
+        .iter()
+        .flat_map(|p| &p.frames)
+        .find(|f| f.key == key)
+        .expect("frame present")
+}
+
+#[test]
+fn xxh64_matches_reference_vectors() {
+    assert_eq!(xxh64(b"", 0), 0xEF46_DB37_51D8_E999);
+    assert_eq!(xxh64(b"abc", 0), 0x44BC_2CF5_AD77_0999);
+    let data: Vec<u8> = (0..=255u8).cycle().take(1000).collect();
+    let mut streamed = Xxh64::new(7);
+    for chunk in data.chunks(13) {
+        streamed.update(chunk);
+    }
+    assert_eq!(streamed.digest(), xxh64(&data, 7));
+}
+
+#[test]
+fn content_hash_tracks_visible_pixels_only() {
+    let cfg = PackerConfig {
+        trim: true,
+        ..Default::default()
+    };
+    let a = pack_images(
+        vec![
+            sprite("hero", 10, 8, 0, [10, 20, 30, 255]),
+            sprite("other", 30, 30, 0, [1, 2, 3, 255]),
+        ],
+        cfg.clone(),
+    )
+    .unwrap();
+    // Same content with a transparent margin (trimmed away), packed next to different sprites.
+    let b = pack_images(
+        vec![
+            sprite("hero", 10, 8, 3, [10, 20, 30, 255]),
+            sprite("extra", 40, 12, 0, [9, 9, 9, 255]),
+        ],
+        cfg.clone(),
+    )
+    .unwrap();
+    // One channel changed
+    let c = pack_images(vec![sprite("hero", 10, 8, 0, [11, 20, 30, 255])], cfg).unwrap();
+
+    let (ha, hb, hc) = (
+        frame(&a, "hero").content_hash.expect("hashed"),
+        frame(&b, "hero").content_hash.expect("hashed"),
+        frame(&c, "hero").content_hash.expect("hashed"),
+    );
+    assert_eq!(ha, hb);
+    assert_ne!(ha, hc);
+    assert_ne!(ha, frame(&a, "other").content_hash.unwrap());
+}
+
+#[test]
+fn frame_ids_are_stable_and_json_safe() {
+    let out = pack_images(
+        vec![
+            sprite("ui/button", 8, 8, 0, [1, 1, 1, 255]),
+            sprite("ui/panel", 16, 8, 0, [2, 2, 2, 255]),
+        ],
+        PackerConfig::default(),
+    )
+    .unwrap();
+    let layout = pack_layout(vec![("ui/button", 8, 8)], PackerConfig::default()).unwrap();
+    let button = frame(&out, "ui/button");
+    assert_eq!(button.id, stable_frame_id("ui/button"));
+    assert_eq!(layout.pages[0].frames[0].id, button.id);
+    assert_ne!(button.id, frame(&out, "ui/panel").id);
+    assert!(button.id < 1 << 53);
+    // Layout-only frames have no pixels to hash
+    assert!(layout.pages[0].frames[0].content_hash.is_none());
+}
+
+#[test]
+fn exporters_emit_id_and_hex_content_hash() {
+    let out = pack_images(
+        vec![sprite("a", 4, 4, 0, [5, 6, 7, 255])],
+        PackerConfig::default(),
+    )
+    .unwrap();
+    let f = frame(&out, "a");
+    let hex = format!("{:016x}", f.content_hash.unwrap());
+
+    let hash = to_json_hash(&out.atlas);
+    assert_eq!(hash["frames"]["a"]["id"], f.id);
+    assert_eq!(hash["frames"]["a"]["contentHash"], hex);
+    let array = to_json_array(&out.atlas);
+    assert_eq!(array["pages"][0]["frames"][0]["contentHash"], hex);
+
+    // Serde round trip keeps the full 64-bit hash
+    let json = serde_json::to_string(f).unwrap();
+    assert!(json.contains(&format!("\"content_hash\":\"{hex}\"")));
+    let back: Frame = serde_json::from_str(&json).unwrap();
+    assert_eq!(back.content_hash, f.content_hash);
+    assert_eq!(back.id, f.id);
+}
@@ -0,0 +1,111 @@
+mod common;
+
+use tex_packer_core::{
+    AlgorithmFamily, Frame, InputImage, Packer, PackerConfig, Rect, Slot, pack_images,
+    pack_images_iter, pack_layout,
+};
+
+/// Stacks slots top to bottom in a single column along the left border.
+struct Column {
+    cfg: PackerConfig,
+    y: u32,
+}
+
+impl Column {
+    fn new(cfg: &PackerConfig) -> Self {
+        Self {
+            cfg: cfg.clone(),
+            y: cfg.border_padding,
+        }
+    }
+}
+
+impl Packer<String> for Column {
+    fn can_pack(&self, rect: &Rect) -> bool {
+        let slot = Slot::new(rect, &self.cfg);
+        self.cfg.border_padding * 2 + slot.w <= self.cfg.max_width
+            && self.y + slot.h + self.cfg.border_padding <= self.cfg.max_height
+    }
+
+    fn pack(&mut self, key: String, rect: &Rect) -> Option<Frame<String>> {
+        if !self.can_pack(rect) {
+            return None;
+        }
+        let slot = Slot::new(rect, &self.cfg);
+        let place = Rect::new(self.cfg.border_padding, self.y, slot.w, slot.h);
+        self.y += slot.h;
+        Some(Frame {
+            key,
+            frame: Slot::content(&place, rect, false, &self.cfg),
+            rotated: false,
+            trimmed: false,
+            source: *rect,
+            source_size: (rect.w, rect.h),
+            degenerate: false,
+            id: 0,
+            content_hash: None,
+            pivot: Default::default(),
+            nine_patch: None,
+            scale: None,
+        })
+    }
+
+    fn set_allow_rotation(&mut self, _allow: bool) {}
+}
+
+fn cfg() -> PackerConfig {
+    PackerConfig::builder()
+        .with_max_dimensions(32, 64)
+        .texture_padding(2)
+        .border_padding(1)
+        .trim(false)
+        .custom_packer(|cfg| Box::new(Column::new(cfg)))
+        .build()
+}
+
+fn inputs() -> Vec<InputImage> {
+    (0..5)
+        .map(|i| common::solid(format!("s{i}"), 10, 16, [200; 4]))
+        .collect()
+}
+
+#[test]
+fn custom_packer_runs_through_the_pipeline() {
+    let out = pack_images(inputs(), cfg()).unwrap();
+    // 18px slots (16 + 2 padding, half leading) from the 1px border: three per 64px page
+    assert_eq!(out.pages.len(), 2);
+    let frames = &out.pages[0].page.frames;
+    assert_eq!(frames.len(), 3);
+    for (i, f) in frames.iter().enumerate() {
+        assert_eq!((f.frame.x, f.frame.y), (2, 2 + 18 * i as u32));
+        assert_eq!(out.pages[0].rgba.get_pixel(f.frame.x, f.frame.y)[0], 200);
+    }
+
+    let items = (0..5).map(|i| (format!("s{i}"), 10, 16)).collect();
+    let layout = pack_layout(items, cfg()).unwrap();
+    assert_eq!(layout.pages.len(), 2);
+    assert_eq!(layout.pages[1].frames[1].frame.y, 20);
+
+    let stream = pack_images_iter(inputs().into_iter().map(Ok), cfg()).unwrap();
+    let pages: Vec<_> = stream.map(Result::unwrap).collect();
+    assert_eq!(pages.len(), 2);
+}
+
+#[test]
+fn custom_family_needs_a_factory() {
+    let bad = PackerConfig {
+        family: AlgorithmFamily::Custom,
+        ..Default::default()
+    };
+    let Err(err) = pack_images(inputs(), bad) else {
+        panic!("custom family without a factory packed");
+    };
+    assert!(err.to_string().contains("custom_packer"), "{err}");
+
+    // The factory is not serialized but survives string overrides
+    let mut cfg = cfg();
+    assert!(cfg.set_str("custom_packer", "x").is_err());
+    cfg.set_json("max_height", &serde_json::json!(40)).unwrap();
+    assert!(format!("{cfg:?}").contains("PackerFactory(..)"));
+    assert_eq!(pack_images(inputs(), cfg).unwrap().pages.len(), 3);
+}
@@ -0,0 +1,83 @@
+#![cfg(feature = "dds")]
+mod common;
+
+use image::{Rgba, RgbaImage};
+use tex_packer_core::{BcnFormat, PackerConfig, PageFormat, encode_dds, pack_images};
+
+fn page(w: u32, h: u32) -> RgbaImage {
+    let mut img = RgbaImage::new(w, h);
+    for (x, y, p) in img.enumerate_pixels_mut() {
+        *p = Rgba([x as u8 * 20, y as u8 * 30, 90, 255]);
+    }
+    img
+}
+
+#[test]
+fn dds_headers_match_format() {
+    let img = page(8, 4);
+    for (format, block_bytes) in [
+        (BcnFormat::Bc1, 8),
+        (BcnFormat::Bc3, 16),
+        (BcnFormat::Bc7, 16),
+    ] {
+        let bytes = encode_dds(&img, format).expect("encode");
+        let dds = ddsfile::Dds::read(&bytes[..]).expect("valid dds");
+        assert_eq!((dds.get_width(), dds.get_height()), (8, 4));
+        assert_eq!(dds.get_num_mipmap_levels(), 1);
+        assert_eq!(dds.data.len(), 2 * block_bytes);
+        match format {
+            BcnFormat::Bc1 => assert_eq!(dds.get_d3d_format(), Some(ddsfile::D3DFormat::DXT1)),
+            BcnFormat::Bc3 => assert_eq!(dds.get_d3d_format(), Some(ddsfile::D3DFormat::DXT5)),
+            BcnFormat::Bc7 => {
+                assert_eq!(dds.get_dxgi_format(), Some(ddsfile::DxgiFormat::BC7_UNorm))
+            }
+        }
+    }
+}
+
+#[test]
+fn dds_pads_unaligned_pages() {
+    let bytes = encode_dds(&page(5, 3), BcnFormat::Bc7).expect("encode");
+    let dds = ddsfile::Dds::read(&bytes[..]).expect("valid dds");
+    assert_eq!((dds.get_width(), dds.get_height()), (8, 4));
+    assert_eq!(dds.data.len(), 2 * 16);
+    assert!(encode_dds(&RgbaImage::new(0, 4), BcnFormat::Bc1).is_err());
+}
+
+#[test]
+fn block_formats_align_page_sizes() {
+    let inputs = vec![
+        common::input("a", page(13, 7)),
+        common::input("b", page(5, 9)),
+    ];
+    let cfg = PackerConfig {
+        max_width: 64,
+        max_height: 64,
+        trim: false,
+        page_format: PageFormat::Dds,
+        ..Default::default()
+    };
+    let out = pack_images(inputs, cfg).expect("pack");
+    for p in &out.pages {
+        assert_eq!(p.rgba.width() % 4, 0);
+        assert_eq!(p.rgba.height() % 4, 0);
+        assert_eq!(p.rgba.width(), p.page.width);
+        assert!(encode_dds(&p.rgba, BcnFormat::Bc3).is_ok());
+    }
+}
+
+#[test]
+fn block_formats_require_aligned_max_dimensions() {
+    let cfg = PackerConfig {
+        max_width: 1022,
+        max_height: 1024,
+        page_format: PageFormat::Dds,
+        ..Default::default()
+    };
+    assert!(cfg.validate().is_err());
+    let cfg = PackerConfig {
+        page_format: PageFormat::Png,
+        ..cfg
+    };
+    assert!(cfg.validate().is_ok());
+}
@@ -0,0 +1,66 @@
+mod common;
+
+use image::{Rgba, RgbaImage};
+use tex_packer_core::debug_render::render_debug;
+use tex_packer_core::{InputImage, PackerConfig, pack_images};
+
+fn input(key: &str, w: u32, h: u32) -> InputImage {
+    // Left half opaque, right half transparent.
+    let mut img = RgbaImage::new(w, h);
+    for (x, _, p) in img.enumerate_pixels_mut() {
+        if x < w / 2 {
+            *p = Rgba([255, 255, 255, 255]);
+        }
+    }
+    common::input(key, img)
+}
+
+#[test]
+fn debug_renders_match_page_and_classify_pixels() {
+    let cfg = PackerConfig {
+        max_width: 64,
+        max_height: 64,
+        trim: false,
+        allow_rotation: false,
+        border_padding: 1,
+        texture_padding: 2,
+        texture_extrusion: 1,
+        ..Default::default()
+    };
+    let out = pack_images(vec![input("a", 10, 10), input("b", 6, 12)], cfg).expect("pack");
+    let page = &out.pages[0];
+    let renders = render_debug(page, &out.atlas.meta).expect("render");
+    for img in [
+        &renders.free_space,
+        &renders.waste_heatmap,
+        &renders.padding,
+    ] {
+        assert_eq!(img.dimensions(), page.rgba.dimensions());
+    }
+
+    // Border padding is gray in every render.
+    assert_eq!(*renders.free_space.get_pixel(0, 0), Rgba([90, 90, 90, 255]));
+    assert_eq!(*renders.padding.get_pixel(0, 0), Rgba([90, 90, 90, 255]));
+
+    for f in &page.page.frames {
+        let r = f.frame;
+        // Content interior is blue in the free-space overlay.
+        assert_eq!(
+            *renders.free_space.get_pixel(r.x + 1, r.y + 1),
+            Rgba([70, 90, 140, 255])
+        );
+        // The extrusion ring is magenta; the padding beyond it is cyan.
+        assert_eq!(
+            *renders.padding.get_pixel(r.x - 1, r.y + 1),
+            Rgba([220, 60, 200, 255])
+        );
+        assert_eq!(
+            *renders.padding.get_pixel(r.x - 2, r.y + 1),
+            Rgba([60, 200, 220, 255])
+        );
+        // Half-transparent frames waste over half their slot: the heat color leans red.
+        let heat = renders.waste_heatmap.get_pixel(r.x - 1, r.y + 1);
+        assert_eq!(heat[0], 255);
+        assert!(heat[1] < 200);
+    }
+}
@@ -0,0 +1,87 @@
+mod common;
+
+use image::{Rgba, RgbaImage};
+use tex_packer_core::TransparentPolicy;
+use tex_packer_core::prelude::*;
+
+#[test]
+fn zero_sized_input_gets_placeholder_slot() {
+    let inputs = vec![
+        common::input("empty.png", RgbaImage::new(0, 5)),
+        common::input(
+            "solid.png",
+            RgbaImage::from_pixel(8, 8, Rgba([255, 0, 0, 255])),
+        ),
+    ];
+    // Extrusion used to underflow when compositing a zero-sized source.
+    let cfg = PackerConfig::builder()
+        .with_max_dimensions(64, 64)
+        .texture_extrusion(2)
+        .build();
+
+    let out = pack_images(inputs, cfg).expect("pack");
+    let frames = &out.atlas.pages[0].frames;
+    let empty = frames.iter().find(|f| f.key == "empty.png").unwrap();
+    assert!(empty.degenerate);
+    assert_eq!((empty.frame.w, empty.frame.h), (1, 1));
+    assert_eq!(empty.source_size, (0, 5));
+    let solid = frames.iter().find(|f| f.key == "solid.png").unwrap();
+    assert!(!solid.degenerate);
+    assert_eq!(out.atlas.degenerate_keys(), vec!["empty.png"]);
+}
+
+#[test]
+fn one_by_one_transparent_is_degenerate() {
+    let inputs = vec![
+        common::input("clear.png", RgbaImage::from_pixel(8, 8, Rgba([0, 0, 0, 0]))),
+        common::input(
+            "solid.png",
+            RgbaImage::from_pixel(4, 4, Rgba([0, 255, 0, 255])),
+        ),
+    ];
+    let cfg = PackerConfig::builder()
+        .with_max_dimensions(64, 64)
+        .trim(true)
+        .transparent_policy(TransparentPolicy::OneByOne)
+        .build();
+
+    let out = pack_images(inputs, cfg).expect("pack");
+    assert_eq!(out.atlas.degenerate_keys(), vec!["clear.png"]);
+
+    // Exporters can skip degenerate frames; json output flags them otherwise.
+    let skipped = out.atlas.without_degenerate();
+    assert_eq!(skipped.pages[0].frames.len(), 1);
+    assert_eq!(skipped.pages[0].frames[0].key, "solid.png");
+    let hash = tex_packer_core::to_json_hash(&out.atlas);
+    assert_eq!(hash["frames"]["clear.png"]["degenerate"], true);
+    assert!(hash["frames"]["solid.png"].get("degenerate").is_none());
+}
+
+#[test]
+fn zero_sized_input_skipped_under_skip_policy() {
+    let inputs = vec![
+        common::input("empty.png", RgbaImage::new(3, 0)),
+        common::input(
+            "solid.png",
+            RgbaImage::from_pixel(4, 4, Rgba([0, 0, 255, 255])),
+        ),
+    ];
+    let cfg = PackerConfig::builder()
+        .with_max_dimensions(64, 64)
+        .transparent_policy(TransparentPolicy::Skip)
+        .build();
+
+    let out = pack_images(inputs, cfg).expect("pack");
+    assert_eq!(out.atlas.pages[0].frames.len(), 1);
+    assert!(out.atlas.degenerate_keys().is_empty());
+}
+
+#[test]
+fn zero_sized_layout_items_are_flagged() {
+    let cfg = PackerConfig::builder().with_max_dimensions(64, 64).build();
+    let atlas = pack_layout(vec![("a", 0, 0), ("b", 10, 10)], cfg).expect("pack");
+    let a = atlas.pages[0].frames.iter().find(|f| f.key == "a").unwrap();
+    assert!(a.degenerate);
+    assert_eq!((a.frame.w, a.frame.h), (1, 1));
+    assert_eq!(a.source_size, (0, 0));
+}
@@ -0,0 +1,92 @@
+mod common;
+
+use tex_packer_core::config::{AlgorithmFamily, AutoMode};
+use tex_packer_core::{InputImage, PackOutput, PackerConfig, pack_images};
+
+fn inputs() -> Vec<InputImage> {
+    // Few distinct sizes, so many candidates tie on pages and area.
+    (0..24)
+        .map(|i| {
+            common::solid(
+                format!("tile{i:02}"),
+                8 + (i % 3) * 4,
+                8 + (i % 2) * 6,
+                [i as u8 * 10, 90, 200 - i as u8 * 5, 255],
+            )
+        })
+        .collect()
+}
+
+fn cfg(mode: AutoMode) -> PackerConfig {
+    PackerConfig {
+        max_width: 64,
+        max_height: 64,
+        trim: false,
+        family: AlgorithmFamily::Auto,
+        auto_mode: mode,
+        deterministic: true,
+        // Ignored in deterministic mode; would otherwise cut the sweep short.
+        time_budget_ms: Some(1),
+        ..Default::default()
+    }
+}
+
+fn fingerprint(out: &PackOutput) -> (String, Vec<Vec<u8>>) {
+    let frames = serde_json::to_string(&out.atlas.pages).unwrap();
+    let pixels = out.pages.iter().map(|p| p.rgba.as_raw().clone()).collect();
+    (frames, pixels)
+}
+
+#[test]
+fn deterministic_ignores_time_budget() {
+    let budgeted = pack_images(inputs(), cfg(AutoMode::Exhaustive)).expect("budgeted");
+    let unbounded = pack_images(
+        inputs(),
+        PackerConfig {
+            time_budget_ms: None,
+            ..cfg(AutoMode::Exhaustive)
+        },
+    )
+    .expect("unbounded");
+    assert_eq!(fingerprint(&budgeted), fingerprint(&unbounded));
+    assert_eq!(
+        budgeted.atlas.meta.packer, unbounded.atlas.meta.packer,
+        "same winning candidate"
+    );
+}
+
+#[test]
+fn deterministic_anneal_repeats() {
+    let a = pack_images(inputs(), cfg(AutoMode::Anneal)).expect("first");
+    let b = pack_images(inputs(), cfg(AutoMode::Anneal)).expect("second");
+    assert_eq!(fingerprint(&a), fingerprint(&b));
+}
+
+#[cfg(feature = "parallel")]
+#[test]
+fn parallel_matches_sequential_across_thread_counts() {
+    let sequential = pack_images(inputs(), cfg(AutoMode::Exhaustive)).expect("sequential");
+    for threads in [1, 2, 4] {
+        let pool = rayon::ThreadPoolBuilder::new()
+            .num_threads(threads)
+            .build()
+            .unwrap();
+        let parallel = pool
+            .install(|| {
+                pack_images(
+                    inputs(),
+                    PackerConfig {
+                        parallel: true,
+                        ..cfg(AutoMode::Exhaustive)
+                    },
+                )
+            })
+            .expect("parallel");
+        assert_eq!(
+            fingerprint(&parallel),
+            fingerprint(&sequential),
+            "{threads} threads"
+        );
+        assert_eq!(parallel.atlas.meta.packer, sequential.atlas.meta.packer);
+    }
+}
@@ -0,0 +1,152 @@
+mod common;
+
+use tex_packer_core::{
+    DuplicateKeyPolicy, KeyTransform, LayoutItem, PackerConfig, TexPackerError, pack_images,
+    pack_images_iter, pack_layout_items,
+};
+
+const RED: [u8; 4] = [255, 0, 0, 255];
+
+fn cfg(policy: DuplicateKeyPolicy) -> PackerConfig {
+    PackerConfig::builder()
+        .with_max_dimensions(256, 256)
+        .trim(false)
+        .allow_rotation(false)
+        .duplicate_keys(policy)
+        .build()
+}
+
+fn frames(out: &tex_packer_core::PackOutput) -> Vec<(String, u32)> {
+    let mut v: Vec<_> = out
+        .atlas
+        .pages
+        .iter()
+        .flat_map(|p| p.frames.iter().map(|f| (f.key.clone(), f.source_size.0)))
+        .collect();
+    v.sort();
+    v
+}
+
+#[test]
+fn duplicates_are_an_error_by_default() {
+    let err = pack_images(
+        vec![
+            common::solid("a.png", 4, 4, RED),
+            common::solid("a.png", 8, 8, RED),
+        ],
+        PackerConfig::default(),
+    )
+    .err()
+    .unwrap();
+    assert!(matches!(err, TexPackerError::DuplicateKey(ref k) if k == "a.png"));
+}
+
+#[test]
+fn collisions_created_by_key_transform_are_detected() {
+    let cfg = PackerConfig::builder()
+        .key_transform(KeyTransform::file_stem())
+        .build();
+    let err = pack_images(
+        vec![
+            common::solid("ui/a.png", 4, 4, RED),
+            common::solid("hud/a.png", 8, 8, RED),
+        ],
+        cfg,
+    )
+    .err()
+    .unwrap();
+    assert!(matches!(err, TexPackerError::DuplicateKey(ref k) if k == "a"));
+}
+
+#[test]
+fn suffix_renames_later_duplicates() {
+    let out = pack_images(
+        vec![
+            common::solid("a.png", 4, 4, RED),
+            common::solid("a.png", 8, 8, RED),
+            common::solid("a_2.png", 6, 6, RED),
+            common::solid("b", 5, 5, RED),
+            common::solid("b", 7, 7, RED),
+        ],
+        cfg(DuplicateKeyPolicy::Suffix),
+    )
+    .unwrap();
+    assert_eq!(
+        frames(&out),
+        [
+            ("a.png".to_string(), 4),
+            ("a_2.png".to_string(), 8),
+            ("a_2_2.png".to_string(), 6),
+            ("b".to_string(), 5),
+            ("b_2".to_string(), 7),
+        ]
+    );
+}
+
+#[test]
+fn overwrite_keeps_the_last_input() {
+    let out = pack_images(
+        vec![
+            common::solid("a", 4, 4, RED),
+            common::solid("b", 5, 5, RED),
+            common::solid("a", 8, 8, RED),
+        ],
+        cfg(DuplicateKeyPolicy::Overwrite),
+    )
+    .unwrap();
+    assert_eq!(frames(&out), [("a".to_string(), 8), ("b".to_string(), 5)]);
+}
+
+#[test]
+fn layout_items_follow_the_policy() {
+    let items = || {
+        vec![
+            LayoutItem {
+                key: "a",
+                w: 4,
+                h: 4,
+                source: None,
+                source_size: None,
+                trimmed: false,
+            };
+            2
+        ]
+    };
+    let err = pack_layout_items(items(), cfg(DuplicateKeyPolicy::Error)).unwrap_err();
+    assert!(matches!(err, TexPackerError::DuplicateKey(_)));
+    let atlas = pack_layout_items(items(), cfg(DuplicateKeyPolicy::Suffix)).unwrap();
+    let mut keys: Vec<_> = atlas.pages[0]
+        .frames
+        .iter()
+        .map(|f| f.key.as_str())
+        .collect();
+    keys.sort();
+    assert_eq!(keys, ["a", "a_2"]);
+}
+
+#[test]
+fn streaming_detects_and_suffixes_duplicates() {
+    let inputs = || {
+        vec![
+            Ok(common::solid("a", 4, 4, RED)),
+            Ok(common::solid("a", 8, 8, RED)),
+        ]
+    };
+    let err = pack_images_iter(inputs(), cfg(DuplicateKeyPolicy::Error))
+        .unwrap()
+        .into_output()
+        .err()
+        .unwrap();
+    assert!(matches!(err, TexPackerError::DuplicateKey(_)));
+
+    let out = pack_images_iter(inputs(), cfg(DuplicateKeyPolicy::Suffix))
+        .unwrap()
+        .into_output()
+        .unwrap();
+    assert_eq!(frames(&out), [("a".to_string(), 4), ("a_2".to_string(), 8)]);
+
+    assert!(matches!(
+        pack_images_iter(inputs(), cfg(DuplicateKeyPolicy::Overwrite)).err(),
+        Some(TexPackerError::InvalidConfig(_))
+    ));
+}
@@ -0,0 +1,140 @@
+use tex_packer_core::prelude::*;
+use tex_packer_core::{Animation, AnimationFrame};
+
+#[test]
+fn aseprite_json_frames_and_tags() {
+    let cfg = PackerConfig::builder()
+        .with_max_dimensions(256, 256)
+        .allow_rotation(false)
+        .build();
+    let items = vec![
+        ("run_2.png", 16, 16),
+        ("idle.png", 20, 20),
+        ("run_0.png", 16, 16),
+        ("run_1.png", 16, 16),
+    ];
+    let atlas = tex_packer_core::pack_layout(items, cfg).expect("pack");
+    assert_eq!(atlas.pages.len(), 1);
+
+    let v = tex_packer_core::to_aseprite_json(&atlas, 0, "atlas.png");
+    let frames = v["frames"].as_array().expect("frames array");
+    assert_eq!(frames.len(), 4);
+    let names: Vec<&str> = frames
+        .iter()
+        .map(|f| f["filename"].as_str().unwrap())
+        .collect();
+    assert_eq!(names, ["idle.png", "run_0.png", "run_1.png", "run_2.png"]);
+    assert_eq!(frames[0]["duration"], 100);
+
+    let meta = &v["meta"];
+    assert_eq!(meta["image"], "atlas.png");
+    assert_eq!(meta["size"]["w"], atlas.pages[0].width);
+    assert!(meta["slices"].as_array().unwrap().is_empty());
+
+    let tags = meta["frameTags"].as_array().expect("frameTags");
+    assert_eq!(tags.len(), 1);
+    assert_eq!(tags[0]["name"], "run");
+    assert_eq!(tags[0]["from"], 1);
+    assert_eq!(tags[0]["to"], 3);
+    assert_eq!(tags[0]["direction"], "forward");
+}
+
+#[test]
+fn aseprite_json_tags_follow_animations() {
+    let mut cfg = PackerConfig::builder()
+        .with_max_dimensions(256, 256)
+        .allow_rotation(false)
+        .build();
+    // GIF-style keys without `_N` suffixes, played out of key order
+    cfg.animations = vec![Animation {
+        name: "spin".into(),
+        frames: [(2, 40), (0, 60), (1, 80)]
+            .iter()
+            .map(|&(i, ms)| AnimationFrame {
+                key: format!("fx/spin#{i}"),
+                duration_ms: ms,
+            })
+            .collect(),
+    }];
+    let items = vec![
+        ("fx/spin#0", 8, 8),
+        ("fx/spin#1", 8, 8),
+        ("fx/spin#2", 8, 8),
+        ("a.png", 8, 8),
+        ("z.png", 8, 8),
+    ];
+    let atlas = tex_packer_core::pack_layout(items, cfg).expect("pack");
+
+    let v = tex_packer_core::to_aseprite_json(&atlas, 0, "atlas.png");
+    let frames = v["frames"].as_array().unwrap();
+    let names: Vec<&str> = frames
+        .iter()
+        .map(|f| f["filename"].as_str().unwrap())
+        .collect();
+    assert_eq!(
+        names,
+        ["a.png", "fx/spin#2", "fx/spin#0", "fx/spin#1", "z.png"]
+    );
+    let durations: Vec<u64> = frames
+        .iter()
+        .map(|f| f["duration"].as_u64().unwrap())
+        .collect();
+    assert_eq!(durations, [100, 40, 60, 80, 100]);
+    let tags = v["meta"]["frameTags"].as_array().unwrap();
+    assert_eq!(tags.len(), 1);
+    assert_eq!(tags[0]["name"], "spin");
+    assert_eq!(tags[0]["from"], 1);
+    assert_eq!(tags[0]["to"], 3);
+}
+
+#[test]
+fn aseprite_json_slices_carry_nine_patch_and_pivot() {
+    let mut cfg = PackerConfig::builder()
+        .with_max_dimensions(256, 256)
+        .allow_rotation(false)
+        .build();
+    cfg.sprites.insert(
+        "panel.png".into(),
+        SpriteOptions {
+            nine_patch: Some(NinePatch {
+                left: 4,
+                top: 3,
+                right: 6,
+                bottom: 5,
+            }),
+            ..Default::default()
+        },
+    );
+    cfg.pivots.insert("hero.png".into(), Pivot::BOTTOM_CENTER);
+    let items = vec![
+        ("hero.png", 20, 30),
+        ("panel.png", 32, 24),
+        ("plain.png", 8, 8),
+    ];
+    let atlas = tex_packer_core::pack_layout(items, cfg).expect("pack");
+
+    let v = tex_packer_core::to_aseprite_json(&atlas, 0, "atlas.png");
+    let slices = v["meta"]["slices"].as_array().unwrap();
+    assert_eq!(slices.len(), 2, "{slices:?}");
+
+    let hero = &slices[0];
+    assert_eq!(hero["name"], "hero.png");
+    let key = &hero["keys"][0];
+    assert_eq!(key["frame"], 0);
+    assert_eq!(
+        key["bounds"],
+        serde_json::json!({"x": 0, "y": 0, "w": 20, "h": 30})
+    );
+    assert_eq!(key["pivot"], serde_json::json!({"x": 10, "y": 30}));
+    assert!(key.get("center").is_none());
+
+    let panel = &slices[1];
+    assert_eq!(panel["name"], "panel.png");
+    let key = &panel["keys"][0];
+    assert_eq!(key["frame"], 1);
+    assert_eq!(
+        key["center"],
+        serde_json::json!({"x": 4, "y": 3, "w": 22, "h": 16})
+    );
+    assert!(key.get("pivot").is_none());
+}
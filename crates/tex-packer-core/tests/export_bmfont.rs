@@ -0,0 +1,179 @@
+mod common;
+
+use image::{Rgba, RgbaImage};
+use tex_packer_core::{
+    Atlas, FontMetrics, GlyphMetrics, InputImage, KerningPair, PackerConfig, pack_images,
+    to_bmfont_binary, to_bmfont_text,
+};
+
+/// An 8x12 glyph cell whose ink starts `top` rows down and `left` columns in.
+fn glyph(key: &str, left: u32, top: u32) -> InputImage {
+    let img = RgbaImage::from_fn(8, 12, |x, y| {
+        if x >= left && y >= top {
+            Rgba([255; 4])
+        } else {
+            Rgba([0; 4])
+        }
+    });
+    common::input(key, img)
+}
+
+fn atlas() -> Atlas {
+    let cfg = PackerConfig {
+        max_width: 64,
+        max_height: 64,
+        allow_rotation: false,
+        trim: true,
+        ..Default::default()
+    };
+    let inputs = vec![glyph("glyphs/A.png", 1, 2), glyph("glyphs/V.png", 0, 3)];
+    pack_images(inputs, cfg).unwrap().atlas
+}
+
+fn metrics() -> FontMetrics {
+    let g = |id, key: Option<&str>, yoffset, xadvance| GlyphMetrics {
+        id,
+        key: key.map(Into::into),
+        xoffset: 0,
+        yoffset,
+        xadvance,
+    };
+    FontMetrics {
+        face: "Test".into(),
+        size: 12,
+        line_height: 14,
+        base: 11,
+        glyphs: vec![
+            g(32, None, 0, 4),
+            g(65, Some("glyphs/A.png"), 1, 8),
+            g(86, Some("V"), 0, 7),
+        ],
+        kernings: vec![KerningPair {
+            first: 65,
+            second: 86,
+            amount: -1,
+        }],
+        ..Default::default()
+    }
+}
+
+fn field(line: &str, name: &str) -> i64 {
+    line.split_whitespace()
+        .find_map(|kv| kv.strip_prefix(&format!("{name}=")))
+        .unwrap()
+        .parse()
+        .unwrap()
+}
+
+#[test]
+fn text_export_places_glyphs_and_keeps_kerning() {
+    let atlas = atlas();
+    let text = to_bmfont_text(&atlas, &metrics(), &["font.png".into()]).unwrap();
+    let lines: Vec<&str> = text.lines().collect();
+    assert!(
+        lines[0].starts_with("info face=\"Test\" size=12 "),
+        "{text}"
+    );
+    assert!(
+        lines[1].starts_with("common lineHeight=14 base=11 "),
+        "{text}"
+    );
+    assert!(lines[1].contains("pages=1"));
+    assert_eq!(lines[2], "page id=0 file=\"font.png\"");
+    assert_eq!(lines[3], "chars count=3");
+
+    // Trimmed-away rows and columns move into the offsets
+    let a = atlas.pages[0]
+        .frames
+        .iter()
+        .find(|f| f.key == "glyphs/A.png")
+        .unwrap();
+    let line = lines.iter().find(|l| l.starts_with("char id=65 ")).unwrap();
+    assert_eq!(field(line, "x"), a.frame.x as i64);
+    assert_eq!((field(line, "width"), field(line, "height")), (7, 10));
+    assert_eq!((field(line, "xoffset"), field(line, "yoffset")), (1, 3));
+    assert_eq!(field(line, "xadvance"), 8);
+    let v = lines.iter().find(|l| l.starts_with("char id=86 ")).unwrap();
+    assert_eq!((field(v, "xoffset"), field(v, "yoffset")), (0, 3));
+    let space = lines.iter().find(|l| l.starts_with("char id=32 ")).unwrap();
+    assert_eq!((field(space, "width"), field(space, "xadvance")), (0, 4));
+
+    assert!(text.contains("kernings count=1\nkerning first=65 second=86 amount=-1\n"));
+}
+
+#[test]
+fn binary_export_uses_the_block_layout() {
+    let atlas = atlas();
+    let bytes = to_bmfont_binary(&atlas, &metrics(), &["font.png".into()]).unwrap();
+    assert_eq!(&bytes[..4], b"BMF\x03");
+    let mut blocks = Vec::new();
+    let mut at = 4;
+    while at < bytes.len() {
+        let len = u32::from_le_bytes(bytes[at + 1..at + 5].try_into().unwrap()) as usize;
+        blocks.push((bytes[at], &bytes[at + 5..at + 5 + len]));
+        at += 5 + len;
+    }
+    assert_eq!(at, bytes.len());
+    let kinds: Vec<u8> = blocks.iter().map(|b| b.0).collect();
+    assert_eq!(kinds, [1, 2, 3, 4, 5]);
+
+    let info = blocks[0].1;
+    assert_eq!(i16::from_le_bytes([info[0], info[1]]), 12);
+    assert_eq!(&info[14..], b"Test\0");
+    let common = blocks[1].1;
+    assert_eq!(common.len(), 15);
+    assert_eq!(u16::from_le_bytes([common[0], common[1]]), 14);
+    assert_eq!(blocks[2].1, b"font.png\0");
+
+    let chars = blocks[3].1;
+    assert_eq!(chars.len(), 3 * 20);
+    let a = &chars[20..40];
+    assert_eq!(u32::from_le_bytes(a[..4].try_into().unwrap()), 65);
+    assert_eq!(u16::from_le_bytes([a[8], a[9]]), 7);
+    assert_eq!(i16::from_le_bytes([a[14], a[15]]), 3);
+    assert_eq!(a[19], 15);
+
+    let kerning = blocks[4].1;
+    assert_eq!(kerning.len(), 10);
+    assert_eq!(i16::from_le_bytes([kerning[8], kerning[9]]), -1);
+
+    // No kerning block without pairs
+    let plain = FontMetrics {
+        kernings: Vec::new(),
+        ..metrics()
+    };
+    let bytes = to_bmfont_binary(&atlas, &plain, &[]).unwrap();
+    let text = to_bmfont_text(&atlas, &plain, &[]).unwrap();
+    assert!(!text.contains("kernings"));
+    assert!(text.contains("file=\"page_0.png\""));
+    assert!(bytes.len() < to_bmfont_binary(&atlas, &metrics(), &[]).unwrap().len());
+}
+
+#[test]
+fn unknown_and_rotated_glyphs_are_errors() {
+    let mut m = metrics();
+    m.glyphs[1].key = Some("B".into());
+    let err = to_bmfont_text(&atlas(), &m, &[]).unwrap_err();
+    assert!(err.to_string().contains("'B'"), "{err}");
+
+    let mut rotated = atlas();
+    rotated.pages[0].frames[0].rotated = true;
+    assert!(to_bmfont_binary(&rotated, &metrics(), &[]).is_err());
+
+    // Field overflow in the binary format
+    let mut m = metrics();
+    m.kernings[0].amount = 40_000;
+    assert!(to_bmfont_binary(&atlas(), &m, &[]).is_err());
+    assert!(to_bmfont_text(&atlas(), &m, &[]).is_ok());
+}
+
+#[test]
+fn metrics_deserialize_with_defaults() {
+    let m: FontMetrics = serde_json::from_str(
+        r#"{"face": "F", "glyphs": [{"id": 65, "key": "A", "xadvance": 5}], "kernings": []}"#,
+    )
+    .unwrap();
+    assert_eq!(m.glyphs[0].xoffset, 0);
+    assert_eq!(m.line_height, 0);
+    assert_eq!(m.padding, [0; 4]);
+}
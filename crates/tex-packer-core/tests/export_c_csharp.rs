@@ -0,0 +1,142 @@
+use std::process::Command;
+use tex_packer_core::{Atlas, PackerConfig, pack_layout, to_c_header, to_csharp};
+
+fn atlas() -> Atlas {
+    let sizes = vec![
+        ("ui/button_ok.png".to_string(), 30, 12),
+        ("ui/button-ok.png".to_string(), 10, 10),
+        ("9lives".to_string(), 8, 8),
+        ("why??/\"hero\"\\é.png".to_string(), 16, 20),
+    ];
+    let cfg = PackerConfig {
+        max_width: 40,
+        max_height: 40,
+        ..Default::default()
+    };
+    pack_layout(sizes, cfg).unwrap()
+}
+
+fn names(atlas: &Atlas) -> Vec<String> {
+    (0..atlas.pages.len())
+        .map(|i| format!("sheet_{i}.png"))
+        .collect()
+}
+
+#[test]
+fn c_header_defines_prefixed_indices() {
+    let atlas = atlas();
+    let h = to_c_header(&atlas, &names(&atlas), "ui-sheet");
+    assert!(h.contains("#ifndef UI_SHEET_ATLAS_H"));
+    assert!(h.contains("#define UI_SHEET_FRAME_COUNT 4"));
+    let defines: Vec<&str> = h
+        .lines()
+        .filter(|l| l.starts_with("#define UI_SHEET_FRAME_") && !l.contains("COUNT"))
+        .collect();
+    assert_eq!(
+        defines,
+        [
+            "#define UI_SHEET_FRAME_F_9LIVES 0",
+            "#define UI_SHEET_FRAME_UI_BUTTON_OK_PNG 1",
+            "#define UI_SHEET_FRAME_UI_BUTTON_OK_PNG2 2",
+            "#define UI_SHEET_FRAME_WHY_HERO_PNG 3",
+        ]
+    );
+    // Quotes, backslashes, `?` and UTF-8 bytes are escaped
+    assert!(h.contains(r#""why\?\?/\"hero\"\\\303\251.png""#));
+    assert!(h.contains("static const tex_packer_frame ui_sheet_frames[UI_SHEET_FRAME_COUNT]"));
+}
+
+/// Compiles the header with the system C compiler (skipped when there is none) and checks a
+/// frame against the atlas.
+#[test]
+fn c_header_compiles() {
+    if Command::new("cc").arg("--version").output().is_err() {
+        return;
+    }
+    let atlas = atlas();
+    let f = atlas
+        .pages
+        .iter()
+        .flat_map(|p| &p.frames)
+        .find(|f| f.key == "ui/button_ok.png")
+        .unwrap();
+    let dir = std::env::temp_dir().join(format!("tex_packer_c_header_{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    std::fs::write(
+        dir.join("atlas.h"),
+        to_c_header(&atlas, &names(&atlas), "atlas"),
+    )
+    .unwrap();
+    // Including twice must work (guards), as must a second atlas in the same unit
+    std::fs::write(
+        dir.join("other.h"),
+        to_c_header(&atlas, &names(&atlas), "other"),
+    )
+    .unwrap();
+    std::fs::write(
+        dir.join("main.c"),
+        format!(
+            "#include <string.h>
+#include \"atlas.h\"
+#include \"atlas.h\"
+#include \"other.h\"
+int main(void) {{
+    const tex_packer_frame *f = &atlas_frames[ATLAS_FRAME_UI_BUTTON_OK_PNG2];
+    if (strcmp(f->key, \"ui/button_ok.png\") != 0) return 1;
+    if (f->x != {} || f->y != {} || f->w != {} || f->h != {}) return 2;
+    if (ATLAS_PAGE_COUNT != {} || other_frames[0].w != atlas_frames[0].w) return 3;
+    if (strcmp(atlas_pages[0].image, \"sheet_0.png\") != 0) return 4;
+    return 0;
+}}
+",
+            f.frame.x,
+            f.frame.y,
+            f.frame.w,
+            f.frame.h,
+            atlas.pages.len()
+        ),
+    )
+    .unwrap();
+    let bin = dir.join("check");
+    let status = Command::new("cc")
+        .args(["-std=c89", "-pedantic", "-Wall", "-Werror", "-o"])
+        .arg(&bin)
+        .arg(dir.join("main.c"))
+        .status()
+        .unwrap();
+    assert!(status.success());
+    assert!(Command::new(&bin).status().unwrap().success());
+    let _ = std::fs::remove_dir_all(&dir);
+}
+
+#[test]
+fn csharp_class_has_tables_and_enum() {
+    let atlas = atlas();
+    let cs = to_csharp(&atlas, &names(&atlas), "ui sheet");
+    assert!(cs.contains("public static class UiSheet\n{"));
+    let members: Vec<&str> = cs
+        .lines()
+        .skip_while(|l| l.trim() != "public enum FrameKey")
+        .skip(2)
+        .take_while(|l| l.trim() != "}")
+        .map(str::trim)
+        .collect();
+    assert_eq!(
+        members,
+        [
+            "F9lives = 0,",
+            "UiButtonOkPng = 1,",
+            "UiButtonOkPng2 = 2,",
+            "WhyHeroPng = 3,",
+        ]
+    );
+    assert!(cs.contains(r#"new AtlasFrame("why??/\"hero\"\\é.png", "#));
+    assert_eq!(
+        cs.matches("new AtlasPage(\"sheet_").count(),
+        atlas.pages.len()
+    );
+    // A class named like one of its members is renamed
+    assert!(to_csharp(&atlas, &[], "frames").contains("public static class FramesData"));
+    // Braces balance
+    assert_eq!(cs.matches('{').count(), cs.matches('}').count());
+}
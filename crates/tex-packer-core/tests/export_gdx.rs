@@ -0,0 +1,90 @@
+use tex_packer_core::prelude::*;
+
+fn frame(key: &str, frame: Rect, rotated: bool, source: Rect, source_size: (u32, u32)) -> Frame {
+    Frame {
+        key: key.into(),
+        frame,
+        rotated,
+        trimmed: source.w != source_size.0 || source.h != source_size.1,
+        source,
+        source_size,
+        degenerate: false,
+        id: 0,
+        content_hash: None,
+        pivot: Default::default(),
+        nine_patch: None,
+        scale: None,
+    }
+}
+
+#[test]
+fn gdx_atlas_regions_and_pages() {
+    let cfg = PackerConfig::builder()
+        .with_max_dimensions(64, 64)
+        .allow_rotation(false)
+        .build();
+    let items = vec![("a", 40, 40), ("b", 40, 40)];
+    let mut atlas = tex_packer_core::pack_layout(items, cfg).expect("pack");
+    assert_eq!(atlas.pages.len(), 2);
+
+    // Replace frames with hand-built ones to check field semantics.
+    atlas.pages[0].frames = vec![
+        frame(
+            "walk_01.png",
+            Rect::new(2, 3, 10, 20),
+            true,
+            Rect::new(1, 4, 20, 10),
+            (24, 16),
+        ),
+        frame(
+            "dir/idle.png",
+            Rect::new(20, 0, 8, 8),
+            false,
+            Rect::new(0, 0, 8, 8),
+            (8, 8),
+        ),
+    ];
+
+    let names = vec!["atlas_0.png".to_string(), "atlas_1.png".to_string()];
+    let text = tex_packer_core::to_gdx_atlas(&atlas, &names);
+
+    // Each page starts with a blank line followed by its image name and header.
+    assert!(text.starts_with("\natlas_0.png\nsize: "));
+    assert!(text.contains("\natlas_1.png\nsize: "));
+    assert_eq!(text.matches("format: RGBA8888").count(), 2);
+
+    // Trailing _N becomes the index; size is unrotated; offset is from the bottom.
+    assert!(text.contains(
+        "walk\n  rotate: true\n  xy: 2, 3\n  size: 20, 10\n  orig: 24, 16\n  offset: 1, 2\n  index: 1\n"
+    ));
+    assert!(text.contains(
+        "dir/idle\n  rotate: false\n  xy: 20, 0\n  size: 8, 8\n  orig: 8, 8\n  offset: 0, 0\n  index: -1\n"
+    ));
+}
+
+#[test]
+fn gdx_atlas_sanitizes_page_and_region_names() {
+    let cfg = PackerConfig::builder()
+        .with_max_dimensions(64, 64)
+        .allow_rotation(false)
+        .build();
+    let mut atlas = tex_packer_core::pack_layout(vec![("a", 8, 8)], cfg).expect("pack");
+    atlas.pages[0].frames = vec![frame(
+        " odd:name\nwalk_2.png",
+        Rect::new(0, 0, 8, 8),
+        false,
+        Rect::new(0, 0, 8, 8),
+        (8, 8),
+    )];
+    let text = tex_packer_core::to_gdx_atlas(&atlas, &["c:\\atlas.png".to_string()]);
+
+    assert!(text.starts_with("\nc_\\atlas.png\nsize: "));
+    assert!(text.contains("\nodd_name_walk\n  rotate: false\n"));
+    assert!(text.contains("  index: 2\n"));
+    let imported = tex_packer_core::import_atlas(&text).expect("import");
+    assert_eq!(
+        imported.page_images,
+        vec![Some("c_\\atlas.png".to_string())]
+    );
+    assert_eq!(imported.atlas.pages[0].frames[0].key, "odd_name_walk_2");
+}
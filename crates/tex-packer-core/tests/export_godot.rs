@@ -0,0 +1,133 @@
+mod common;
+
+use tex_packer_core::{
+    Animation, AnimationFrame, GodotOptions, PackerConfig, TexPackerError, pack_images,
+    to_godot_resources,
+};
+
+const GRAY: [u8; 4] = [9, 9, 9, 255];
+
+fn cfg() -> PackerConfig {
+    PackerConfig {
+        allow_rotation: false,
+        trim: true,
+        ..Default::default()
+    }
+}
+
+/// Value of `name = ...` in a `.tres` file.
+fn field<'a>(tres: &'a str, name: &str) -> &'a str {
+    tres.lines()
+        .find_map(|l| l.strip_prefix(&format!("{name} = ")))
+        .unwrap()
+}
+
+#[test]
+fn atlas_textures_restore_trimmed_margins() {
+    let out = pack_images(vec![common::framed("ui/button.png", 10, 6, 3, GRAY)], cfg()).unwrap();
+    let options = GodotOptions {
+        res_dir: "res://atlas".into(),
+        ..Default::default()
+    };
+    let res = to_godot_resources(&out.atlas, &["atlas.png".into()], &options).unwrap();
+    assert_eq!(res.len(), 1);
+    assert_eq!(res[0].path, "ui/button.tres");
+    let tres = &res[0].contents;
+    assert!(tres.starts_with("[gd_resource type=\"AtlasTexture\" load_steps=2 format=3]"));
+    assert!(
+        tres.contains("[ext_resource type=\"Texture2D\" path=\"res://atlas/atlas.png\" id=\"1\"]")
+    );
+
+    let f = &out.atlas.pages[0].frames[0];
+    assert_eq!(
+        field(tres, "region"),
+        format!("Rect2({}, {}, 10, 6)", f.frame.x, f.frame.y)
+    );
+    // 3px trimmed on every side: offset 3,3 and 6px extra in each direction (16x12 original)
+    assert_eq!(field(tres, "margin"), "Rect2(3, 3, 6, 6)");
+}
+
+#[test]
+fn sprite_frames_follow_animations() {
+    let inputs = (0..3)
+        .map(|i| common::framed(format!("walk_{i}.png"), 8, 8, 0, GRAY))
+        .collect();
+    let mut cfg = cfg();
+    cfg.animations = vec![Animation {
+        name: "walk".into(),
+        frames: [(2, 50), (0, 100), (1, 250)]
+            .iter()
+            .map(|&(i, ms)| AnimationFrame {
+                key: format!("walk_{i}.png"),
+                duration_ms: ms,
+            })
+            .collect(),
+    }];
+    let out = pack_images(inputs, cfg).unwrap();
+    let options = GodotOptions {
+        res_dir: "res://fx/".into(),
+        sprite_frames: Some("fx_frames.tres".into()),
+    };
+    let res = to_godot_resources(&out.atlas, &["fx.png".into()], &options).unwrap();
+    assert_eq!(res.len(), 4);
+    let frames = res.iter().find(|r| r.path == "fx_frames.tres").unwrap();
+    let tres = &frames.contents;
+    assert!(tres.starts_with("[gd_resource type=\"SpriteFrames\" load_steps=4 format=3]"));
+    // Textures are referenced in playback order
+    let ext: Vec<&str> = tres
+        .lines()
+        .filter(|l| l.starts_with("[ext_resource"))
+        .collect();
+    assert_eq!(
+        ext,
+        [
+            "[ext_resource type=\"Texture2D\" path=\"res://fx/walk_2.tres\" id=\"1\"]",
+            "[ext_resource type=\"Texture2D\" path=\"res://fx/walk_0.tres\" id=\"2\"]",
+            "[ext_resource type=\"Texture2D\" path=\"res://fx/walk_1.tres\" id=\"3\"]",
+        ]
+    );
+    let durations: Vec<&str> = tres
+        .lines()
+        .filter_map(|l| l.strip_prefix("\"duration\": "))
+        .map(|d| d.trim_end_matches(','))
+        .collect();
+    assert_eq!(durations, ["0.05", "0.1", "0.25"]);
+    assert!(tres.contains("\"name\": &\"walk\""));
+}
+
+#[test]
+fn sprite_frames_fall_back_to_numbered_sequences() {
+    let inputs = vec![
+        common::framed("run_1.png", 4, 4, 0, GRAY),
+        common::framed("run_0.png", 4, 4, 0, GRAY),
+        common::framed("idle.png", 4, 4, 0, GRAY),
+    ];
+    let out = pack_images(inputs, cfg()).unwrap();
+    let options = GodotOptions {
+        sprite_frames: Some("frames.tres".into()),
+        ..Default::default()
+    };
+    let res = to_godot_resources(&out.atlas, &["a.png".into()], &options).unwrap();
+    let tres = &res.last().unwrap().contents;
+    assert!(tres.contains("path=\"res://run_0.tres\" id=\"1\""));
+    assert!(tres.contains("path=\"res://run_1.tres\" id=\"2\""));
+    assert!(!tres.contains("idle"));
+}
+
+#[test]
+fn rotated_frames_are_rejected_and_paths_stay_inside() {
+    let mut c = cfg();
+    c.allow_rotation = true;
+    c.max_width = 16;
+    c.max_height = 64;
+    let out = pack_images(vec![common::framed("../wide.png", 40, 6, 0, GRAY)], c).unwrap();
+    assert!(out.atlas.pages[0].frames[0].rotated);
+    assert!(matches!(
+        to_godot_resources(&out.atlas, &[], &GodotOptions::default()),
+        Err(TexPackerError::InvalidInput(_))
+    ));
+
+    let out = pack_images(vec![common::framed("../wide.png", 40, 6, 0, GRAY)], cfg()).unwrap();
+    let res = to_godot_resources(&out.atlas, &[], &GodotOptions::default()).unwrap();
+    assert_eq!(res[0].path, "_/wide.tres");
+}
@@ -0,0 +1,98 @@
+use std::process::Command;
+use tex_packer_core::{PackerConfig, pack_layout, to_rust_code};
+
+fn code() -> (String, tex_packer_core::Atlas) {
+    let sizes = vec![
+        ("ui/button_ok.png".to_string(), 30, 12),
+        ("ui/button-ok.png".to_string(), 10, 10),
+        ("9lives".to_string(), 8, 8),
+        ("hero \"quoted\".png".to_string(), 16, 20),
+        ("Self".to_string(), 4, 4),
+    ];
+    let cfg = PackerConfig {
+        max_width: 40,
+        max_height: 40,
+        allow_rotation: true,
+        ..Default::default()
+    };
+    let atlas = pack_layout(sizes, cfg).unwrap();
+    let names: Vec<String> = (0..atlas.pages.len())
+        .map(|i| format!("atlas_{i}.png"))
+        .collect();
+    (to_rust_code(&atlas, &names), atlas)
+}
+
+#[test]
+fn variants_are_unique_identifiers_in_key_order() {
+    let (code, atlas) = code();
+    let variants: Vec<&str> = code
+        .lines()
+        .skip_while(|l| !l.starts_with("pub enum FrameKey"))
+        .skip(1)
+        .take_while(|l| *l != "}")
+        .map(str::trim)
+        .collect();
+    assert_eq!(
+        variants,
+        [
+            "F9lives = 0,",
+            "Self_ = 1,",
+            "HeroQuotedPng = 2,",
+            "UiButtonOkPng = 3,",
+            "UiButtonOkPng2 = 4,",
+        ]
+    );
+    assert!(code.contains(r#"key: "hero \"quoted\".png""#));
+    assert_eq!(
+        code.matches("AtlasPage { image:").count(),
+        atlas.pages.len()
+    );
+}
+
+/// Compiles the generated file together with a `main` that checks it against the atlas
+/// (`ui/button-ok.png` sorts first and takes the unsuffixed variant).
+#[test]
+fn generated_code_compiles_and_matches_the_atlas() {
+    let (mut code, atlas) = code();
+    let frame = atlas
+        .pages
+        .iter()
+        .flat_map(|p| &p.frames)
+        .find(|f| f.key == "ui/button_ok.png")
+        .unwrap();
+    code.push_str(&format!(
+        "\nfn main() {{
+    let f = FrameKey::UiButtonOkPng2.frame();
+    assert_eq!((f.x, f.y, f.w, f.h, f.rotated), ({}, {}, {}, {}, {}));
+    assert_eq!(find(\"ui/button_ok.png\"), Some(f));
+    assert_eq!(find(\"missing\"), None);
+    assert_eq!(FRAMES.len(), 5);
+    assert_eq!(PAGES[f.page].image, \"atlas_{}.png\");
+}}\n",
+        frame.frame.x,
+        frame.frame.y,
+        frame.frame.w,
+        frame.frame.h,
+        frame.rotated,
+        atlas
+            .pages
+            .iter()
+            .position(|p| p.frames.iter().any(|f| f.key == frame.key))
+            .unwrap(),
+    ));
+    let dir = std::env::temp_dir().join(format!("tex_packer_rust_code_{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    let src = dir.join("atlas.rs");
+    std::fs::write(&src, code).unwrap();
+    let bin = dir.join("atlas_check");
+    let rustc = std::env::var("RUSTC").unwrap_or_else(|_| "rustc".into());
+    let status = Command::new(rustc)
+        .args(["--edition", "2021", "-D", "warnings", "-o"])
+        .arg(&bin)
+        .arg(&src)
+        .status()
+        .unwrap();
+    assert!(status.success());
+    assert!(Command::new(&bin).status().unwrap().success());
+    let _ = std::fs::remove_dir_all(&dir);
+}
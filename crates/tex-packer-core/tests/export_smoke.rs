@@ -32,3 +32,34 @@ fn export_json_and_plist_smoke() {
     assert!(plist.contains("<key>meta</key>"));
     assert!(plist.contains("textureFile")); // textureFileName or textureFileNames
 }
+
+#[test]
+fn export_stats_json_smoke() {
+    let cfg = PackerConfig::builder().with_max_dimensions(64, 64).build();
+    let items = vec![("a", 32, 16), ("b", 10, 10)];
+    let atlas = tex_packer_core::pack_layout(items, cfg).expect("pack");
+    let stats = atlas.stats();
+
+    let v = tex_packer_core::to_stats_json(&stats);
+    assert_eq!(v["pages"], 1);
+    assert_eq!(v["frames"], 2);
+    assert_eq!(v["used_area"], stats.used_frame_area);
+    assert_eq!(v["page_stats"].as_array().map(Vec::len), Some(1));
+}
+
+#[test]
+fn export_json_hash_keys_are_sorted() {
+    let cfg = PackerConfig::builder()
+        .with_max_dimensions(64, 64)
+        .sort_order(SortOrder::AreaDesc)
+        .build();
+    // Packed biggest first, written by key
+    let items = vec![("b", 4, 4), ("c", 2, 2), ("a", 8, 8)];
+    let atlas = tex_packer_core::pack_layout(items, cfg).expect("pack");
+    assert_eq!(atlas.pages[0].frames[0].key, "a");
+    let text = serde_json::to_string(&tex_packer_core::to_json_hash(&atlas)).unwrap();
+    let pos = |k: &str| text.find(&format!("\"{k}\":{{")).unwrap();
+    assert!(pos("a") < pos("b") && pos("b") < pos("c"), "{text}");
+    // Frame fields are alphabetical too
+    assert!(text.starts_with(r#"{"frames":{"a":{"frame""#), "{text}");
+}
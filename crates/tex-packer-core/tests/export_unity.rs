@@ -0,0 +1,119 @@
+mod common;
+
+use tex_packer_core::config::SpriteOptions;
+use tex_packer_core::{
+    NinePatch, PackerConfig, Pivot, TexPackerError, pack_images, to_unity_tpsheet,
+};
+
+const GRAY: [u8; 4] = [9, 9, 9, 255];
+
+fn cfg() -> PackerConfig {
+    PackerConfig {
+        allow_rotation: false,
+        trim: true,
+        ..Default::default()
+    }
+}
+
+/// `;`-separated fields of the sprite line for `name`, whitespace trimmed.
+fn fields(sheet: &str, name: &str) -> Vec<String> {
+    let line = sheet
+        .lines()
+        .find(|l| l.starts_with(&format!("{name};")))
+        .unwrap();
+    line.split(';').map(|f| f.trim().to_string()).collect()
+}
+
+#[test]
+fn header_names_the_texture_and_size() {
+    let out = pack_images(vec![common::framed("a.png", 8, 8, 0, GRAY)], cfg()).unwrap();
+    let sheet = to_unity_tpsheet(&out.atlas, 0, "atlas.png").unwrap();
+    let page = &out.atlas.pages[0];
+    assert!(sheet.contains(":format=40300\n"));
+    assert!(sheet.contains(":texture=atlas.png\n"));
+    assert!(sheet.contains(&format!(":size={}x{}\n", page.width, page.height)));
+    // name, rect, pivot, borders, 4 vertices, 2 triangles
+    assert_eq!(fields(&sheet, "a").len(), 1 + 4 + 2 + 4 + 9 + 7);
+}
+
+#[test]
+fn rects_flip_to_bottom_left_and_pivots_follow_trimming() {
+    let mut c = cfg();
+    c.sprites.insert(
+        "feet.png".into(),
+        SpriteOptions {
+            pivot: Some(Pivot::BOTTOM_CENTER),
+            ..Default::default()
+        },
+    );
+    // 10x6 content inside a 16x12 image (3px trimmed per side)
+    let out = pack_images(
+        vec![
+            common::framed("feet.png", 10, 6, 3, GRAY),
+            common::framed("other.png", 20, 20, 0, GRAY),
+        ],
+        c,
+    )
+    .unwrap();
+    let sheet = to_unity_tpsheet(&out.atlas, 0, "atlas.png").unwrap();
+    let page = &out.atlas.pages[0];
+    let f = page.frames.iter().find(|f| f.key == "feet.png").unwrap();
+    let v = fields(&sheet, "feet");
+    let nums: Vec<f64> = v[1..].iter().map(|s| s.parse().unwrap()).collect();
+    assert_eq!(
+        nums[..4],
+        [
+            f.frame.x as f64,
+            (page.height - f.frame.y - f.frame.h) as f64,
+            10.0,
+            6.0
+        ]
+    );
+    // Bottom-center of the 16x12 image: x = (8 - 3) / 10, y up = 1 - (12 - 3) / 6
+    assert_eq!((nums[4], nums[5]), (0.5, -0.5));
+    // Quad in rect-local pixels
+    assert_eq!(
+        nums[10..],
+        [
+            4.0, 0.0, 0.0, 10.0, 0.0, 10.0, 6.0, 0.0, 6.0, 2.0, 0.0, 1.0, 2.0, 0.0, 2.0, 3.0
+        ]
+    );
+}
+
+#[test]
+fn borders_come_from_nine_patch_insets() {
+    let mut c = cfg();
+    c.sprites.insert(
+        "panel.png".into(),
+        SpriteOptions {
+            nine_patch: Some(NinePatch {
+                left: 5,
+                top: 4,
+                right: 6,
+                bottom: 2,
+            }),
+            ..Default::default()
+        },
+    );
+    let out = pack_images(vec![common::framed("panel.png", 20, 16, 1, GRAY)], c).unwrap();
+    let sheet = to_unity_tpsheet(&out.atlas, 0, "atlas.png").unwrap();
+    let v = fields(&sheet, "panel");
+    // left, right, top, bottom, less the 1px trimmed on each side
+    assert_eq!(v[7..11], ["4", "5", "3", "1"]);
+}
+
+#[test]
+fn rotated_frames_are_rejected() {
+    let c = PackerConfig {
+        allow_rotation: true,
+        max_width: 16,
+        max_height: 64,
+        ..Default::default()
+    };
+    let out = pack_images(vec![common::framed("wide.png", 40, 6, 0, GRAY)], c).unwrap();
+    assert!(out.atlas.pages[0].frames[0].rotated);
+    assert!(matches!(
+        to_unity_tpsheet(&out.atlas, 0, "atlas.png"),
+        Err(TexPackerError::InvalidInput(_))
+    ));
+}
@@ -0,0 +1,68 @@
+mod common;
+
+use image::{Rgba, RgbaImage};
+use tex_packer_core::{PackerConfig, extract_frames, pack_images};
+
+/// Opaque gradient of `w`x`h` surrounded by `margin` transparent pixels.
+fn sprite(w: u32, h: u32, margin: u32, seed: u8) -> RgbaImage {
+    RgbaImage::from_fn(w + 2 * margin, h + 2 * margin, |x, y| {
+        let inside = x >= margin && y >= margin && x < w + margin && y < h + margin;
+        if inside {
+            Rgba([seed, (x * 7) as u8, (y * 13) as u8, 255])
+        } else {
+            Rgba([0, 0, 0, 0])
+        }
+    })
+}
+
+#[test]
+fn extract_round_trips_rotated_and_trimmed_frames() {
+    let originals = [
+        ("tall", sprite(6, 40, 3, 1)),
+        ("wide", sprite(40, 6, 0, 2)),
+        ("square", sprite(12, 12, 5, 3)),
+        ("odd", sprite(9, 17, 1, 4)),
+    ];
+    let inputs = originals
+        .iter()
+        .map(|(k, img)| common::input(*k, img.clone()))
+        .collect();
+    let cfg = PackerConfig {
+        max_width: 48,
+        max_height: 48,
+        allow_rotation: true,
+        trim: true,
+        texture_padding: 1,
+        texture_extrusion: 1,
+        ..Default::default()
+    };
+    let out = pack_images(inputs, cfg).unwrap();
+    let pages: Vec<RgbaImage> = out.pages.iter().map(|p| p.rgba.clone()).collect();
+    let extracted = extract_frames(&out.atlas, &pages).unwrap();
+    assert_eq!(extracted.len(), originals.len());
+    assert!(
+        out.atlas
+            .pages
+            .iter()
+            .flat_map(|p| &p.frames)
+            .any(|f| f.rotated),
+        "test should exercise a rotated frame"
+    );
+    for (key, img) in &extracted {
+        let (_, orig) = originals.iter().find(|(k, _)| k == key).unwrap();
+        assert_eq!(img.dimensions(), orig.dimensions(), "{key}");
+        assert!(img == orig, "{key} pixels differ");
+    }
+}
+
+#[test]
+fn extract_rejects_mismatched_pages() {
+    let out = pack_images(
+        vec![common::input("a", sprite(4, 4, 0, 1))],
+        PackerConfig::default(),
+    )
+    .unwrap();
+    assert!(extract_frames(&out.atlas, &[]).is_err());
+    let tiny = [RgbaImage::new(1, 1)];
+    assert!(extract_frames(&out.atlas, &tiny).is_err());
+}
@@ -0,0 +1,108 @@
+mod common;
+
+use image::{Rgba, RgbaImage};
+use tex_packer_core::config::{ExtrudeMode, RotationPolicy, SpriteOptions};
+use tex_packer_core::{InputImage, PackerConfig, TexPackerError, pack_images};
+
+/// A 4x3 gradient so every pixel is distinct.
+fn gradient(key: &str) -> InputImage {
+    common::input(
+        key,
+        RgbaImage::from_fn(4, 3, |x, y| Rgba([x as u8 * 60, y as u8 * 80, 100, 255])),
+    )
+}
+
+fn expected(mode: ExtrudeMode, i: i64, n: i64) -> i64 {
+    match mode {
+        ExtrudeMode::Clamp => i.clamp(0, n - 1),
+        ExtrudeMode::Wrap => i.rem_euclid(n),
+        ExtrudeMode::Mirror => {
+            if i < 0 {
+                -i - 1
+            } else if i >= n {
+                2 * n - 1 - i
+            } else {
+                i
+            }
+        }
+    }
+}
+
+/// Checks the 2px border around `key` against its own page content under `mode`.
+fn assert_border(out: &tex_packer_core::PackOutput, key: &str, mode: ExtrudeMode) {
+    let page = &out.pages[0];
+    let f = page.page.frames.iter().find(|f| f.key == key).unwrap();
+    let (fx, fy) = (f.frame.x as i64, f.frame.y as i64);
+    let (w, h) = (f.frame.w as i64, f.frame.h as i64);
+    for j in -2..h + 2 {
+        for i in -2..w + 2 {
+            let at = |x: i64, y: i64| *page.rgba.get_pixel(x as u32, y as u32);
+            let want = at(fx + expected(mode, i, w), fy + expected(mode, j, h));
+            assert_eq!(at(fx + i, fy + j), want, "{key} {mode:?} at ({i}, {j})");
+        }
+    }
+}
+
+fn cfg(mode: ExtrudeMode) -> PackerConfig {
+    PackerConfig {
+        texture_extrusion: 2,
+        extrude_mode: mode,
+        ..common::tight(1024, 1024)
+    }
+}
+
+#[test]
+fn border_follows_the_extrude_mode() {
+    for mode in [ExtrudeMode::Clamp, ExtrudeMode::Wrap, ExtrudeMode::Mirror] {
+        let out = pack_images(vec![gradient("tile")], cfg(mode)).unwrap();
+        assert_border(&out, "tile", mode);
+    }
+
+    // Wrap: the column left of the content repeats the rightmost column
+    let out = pack_images(vec![gradient("tile")], cfg(ExtrudeMode::Wrap)).unwrap();
+    let f = &out.pages[0].page.frames[0];
+    assert_eq!(
+        *out.pages[0].rgba.get_pixel(f.frame.x - 1, f.frame.y),
+        Rgba([180, 0, 100, 255])
+    );
+}
+
+#[test]
+fn rotated_frames_wrap_in_page_space() {
+    let mut c = cfg(ExtrudeMode::Wrap);
+    c.sprites.insert(
+        "tile".into(),
+        SpriteOptions {
+            rotate: Some(RotationPolicy::Always),
+            ..Default::default()
+        },
+    );
+    let out = pack_images(vec![gradient("tile")], c).unwrap();
+    assert!(out.pages[0].page.frames[0].rotated);
+    assert_border(&out, "tile", ExtrudeMode::Wrap);
+}
+
+#[test]
+fn sprites_can_override_the_mode() {
+    let mut c = cfg(ExtrudeMode::Wrap);
+    c.sprites.insert(
+        "ui".into(),
+        SpriteOptions {
+            extrude_mode: Some(ExtrudeMode::Clamp),
+            ..Default::default()
+        },
+    );
+    let out = pack_images(vec![gradient("tile"), gradient("ui")], c).unwrap();
+    assert_border(&out, "tile", ExtrudeMode::Wrap);
+    assert_border(&out, "ui", ExtrudeMode::Clamp);
+}
+
+#[test]
+fn extrude_mode_parses_from_strings() {
+    let c = PackerConfig::from_str_overrides([("extrude_mode", "repeat")]).unwrap();
+    assert_eq!(c.extrude_mode, ExtrudeMode::Wrap);
+    assert!(matches!(
+        PackerConfig::from_str_overrides([("extrude_mode", "smear")]),
+        Err(TexPackerError::InvalidOption { .. })
+    ));
+}
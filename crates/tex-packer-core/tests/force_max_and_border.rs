@@ -12,6 +12,7 @@ fn reserved_slot(f: &Rect, cfg: &PackerConfig) -> Rect {
 }
 
 #[test]
+#[allow(deprecated)]
 fn force_max_ignores_pow2_and_square() {
     let cfg = PackerConfig::builder()
         .with_max_dimensions(300, 180)
@@ -19,6 +20,7 @@ fn force_max_ignores_pow2_and_square() {
         .pow2(true)
         .square(true)
         .build();
+    assert_eq!(cfg.page_size, PageSizeMode::Fixed);
     let inputs = vec![("a", 10, 10)];
     let atlas = tex_packer_core::pack_layout(inputs, cfg).expect("pack");
     let p = &atlas.pages[0];
@@ -0,0 +1,115 @@
+mod common;
+
+use tex_packer_core::config::{FrameOrder, SortOrder, parse_option};
+use tex_packer_core::{
+    Atlas, InputImage, PackerConfig, pack_images, pack_images_iter, pack_layout,
+};
+
+/// Keys given in a scrambled order with sizes that make `AreaDesc` place them differently.
+fn sizes() -> Vec<(String, u32, u32)> {
+    [
+        ("m", 9, 7),
+        ("b", 4, 3),
+        ("z", 12, 12),
+        ("a", 6, 10),
+        ("q", 3, 3),
+        ("c", 8, 2),
+    ]
+    .into_iter()
+    .map(|(k, w, h)| (k.to_string(), w, h))
+    .collect()
+}
+
+fn cfg(order: FrameOrder) -> PackerConfig {
+    PackerConfig {
+        max_width: 32,
+        max_height: 32,
+        allow_rotation: false,
+        sort_order: SortOrder::AreaDesc,
+        frame_order: order,
+        ..Default::default()
+    }
+}
+
+fn keys(atlas: &Atlas) -> Vec<&str> {
+    atlas
+        .pages
+        .iter()
+        .flat_map(|p| p.frames.iter().map(|f| f.key.as_str()))
+        .collect()
+}
+
+#[test]
+fn layout_orders_frames() {
+    let packed = pack_layout(sizes(), cfg(FrameOrder::Packing)).unwrap();
+    assert_eq!(packed.pages.len(), 1);
+    assert_eq!(keys(&packed)[0], "z", "largest first under AreaDesc");
+
+    let by_key = pack_layout(sizes(), cfg(FrameOrder::Key)).unwrap();
+    assert_eq!(keys(&by_key), ["a", "b", "c", "m", "q", "z"]);
+
+    let by_input = pack_layout(sizes(), cfg(FrameOrder::Input)).unwrap();
+    assert_eq!(keys(&by_input), ["m", "b", "z", "a", "q", "c"]);
+
+    let by_pos = pack_layout(sizes(), cfg(FrameOrder::Position)).unwrap();
+    let pos: Vec<(u32, u32)> = by_pos.pages[0]
+        .frames
+        .iter()
+        .map(|f| (f.frame.y, f.frame.x))
+        .collect();
+    let mut sorted = pos.clone();
+    sorted.sort();
+    assert_eq!(pos, sorted);
+
+    // Ordering never moves a frame
+    for f in &by_key.pages[0].frames {
+        let same = packed.pages[0]
+            .frames
+            .iter()
+            .find(|g| g.key == f.key)
+            .unwrap();
+        assert_eq!(f.frame, same.frame);
+    }
+}
+
+fn images() -> Vec<InputImage> {
+    sizes()
+        .into_iter()
+        .map(|(key, w, h)| common::solid(key, w, h, [255; 4]))
+        .collect()
+}
+
+#[test]
+fn image_packing_and_streaming_order_frames() {
+    let out = pack_images(images(), cfg(FrameOrder::Input)).unwrap();
+    assert_eq!(keys(&out.atlas), ["m", "b", "z", "a", "q", "c"]);
+    // Page frames mirror the atlas
+    let page_keys: Vec<&str> = out.pages[0]
+        .page
+        .frames
+        .iter()
+        .map(|f| f.key.as_str())
+        .collect();
+    assert_eq!(page_keys, keys(&out.atlas));
+
+    let out = pack_images(images(), cfg(FrameOrder::Key)).unwrap();
+    assert_eq!(keys(&out.atlas), ["a", "b", "c", "m", "q", "z"]);
+
+    let out = pack_images_iter(images().into_iter().map(Ok), cfg(FrameOrder::Key))
+        .unwrap()
+        .into_output()
+        .unwrap();
+    assert_eq!(keys(&out.atlas), ["a", "b", "c", "m", "q", "z"]);
+}
+
+#[test]
+fn frame_order_parses() {
+    assert_eq!(
+        parse_option::<FrameOrder>("frame_order", "position").unwrap(),
+        FrameOrder::Position
+    );
+    assert!(parse_option::<FrameOrder>("frame_order", "random").is_err());
+    let mut cfg = PackerConfig::default();
+    cfg.set_str("frame_order", "input").unwrap();
+    assert_eq!(cfg.frame_order, FrameOrder::Input);
+}
@@ -0,0 +1,203 @@
+#![cfg(feature = "glyph")]
+
+use tex_packer_core::prelude::*;
+use tex_packer_core::{GlyphAtlas, GlyphFormat, GlyphKey, RasterizedGlyph};
+
+fn cfg(w: u32, h: u32) -> PackerConfig {
+    PackerConfig::builder()
+        .with_max_dimensions(w, h)
+        .texture_padding(0)
+        .build()
+}
+
+/// Solid square bitmap whose coverage encodes the glyph id.
+fn square(key: &GlyphKey) -> Option<RasterizedGlyph> {
+    if key.glyph_id == 0 {
+        return None;
+    }
+    let side = key.size_px() as u32;
+    Some(RasterizedGlyph {
+        width: side,
+        height: side,
+        left: 1,
+        top: side as i32,
+        advance: side as f32 + 2.0,
+        format: GlyphFormat::A8,
+        data: vec![key.glyph_id as u8; (side * side) as usize],
+    })
+}
+
+#[test]
+fn rasterizes_once_and_caches() {
+    let mut atlas = GlyphAtlas::new(cfg(64, 64), RuntimeStrategy::Guillotine, GlyphFormat::A8);
+    let calls = std::cell::Cell::new(0);
+    let mut raster = |k: &GlyphKey| {
+        calls.set(calls.get() + 1);
+        square(k)
+    };
+    let key = GlyphKey::new(0, 7, 8.0);
+    let a = atlas.get_or_rasterize(key, &mut raster).unwrap().unwrap();
+    let b = atlas.get_or_rasterize(key, &mut raster).unwrap().unwrap();
+    assert_eq!(a, b);
+    assert_eq!(calls.get(), 1);
+    assert_eq!((a.rect.w, a.rect.h, a.left, a.advance), (8, 8, 1, 10.0));
+
+    // Missing glyphs are reported, not cached
+    let missing = GlyphKey::new(0, 0, 8.0);
+    assert!(
+        atlas
+            .get_or_rasterize(missing, &mut raster)
+            .unwrap()
+            .is_none()
+    );
+    assert!(!atlas.contains(&missing));
+}
+
+#[test]
+fn keys_distinguish_size_and_subpixel() {
+    let mut atlas = GlyphAtlas::new(cfg(64, 64), RuntimeStrategy::Guillotine, GlyphFormat::A8);
+    let base = GlyphKey::new(1, 5, 8.0);
+    for key in [base, base.with_subpixel(1), GlyphKey::new(1, 5, 8.5)] {
+        atlas.get_or_rasterize(key, &mut square).unwrap().unwrap();
+    }
+    assert_eq!(atlas.len(), 3);
+    assert_eq!(GlyphKey::new(1, 5, 8.5).size, 544);
+}
+
+#[test]
+fn dirty_regions_are_batched_per_page() {
+    let mut atlas = GlyphAtlas::new(cfg(32, 32), RuntimeStrategy::Guillotine, GlyphFormat::A8);
+    for id in 1..=6 {
+        atlas
+            .get_or_rasterize(GlyphKey::new(0, id, 16.0), &mut square)
+            .unwrap();
+    }
+    let dirty = atlas.take_dirty();
+    assert_eq!(dirty.len(), 2);
+    assert_eq!((dirty[0].page_id, dirty[1].page_id), (0, 1));
+    assert_eq!((dirty[0].width, dirty[0].height), (32, 32));
+    assert!(atlas.take_dirty().is_empty());
+
+    // Upload data is A8: one coverage byte per pixel
+    let info = atlas.peek(&GlyphKey::new(0, 5, 16.0)).copied().unwrap();
+    let region = UpdateRegion {
+        page_id: info.page,
+        x: info.rect.x,
+        y: info.rect.y,
+        width: info.rect.w,
+        height: info.rect.h,
+    };
+    let bytes = atlas.region_pixels(&region).unwrap();
+    assert_eq!(bytes.len(), 16 * 16);
+    assert!(bytes.iter().all(|&a| a == 5));
+    assert_eq!(atlas.page_pixels(0).unwrap().len(), 32 * 32);
+}
+
+#[test]
+fn rgba_format_keeps_color() {
+    let mut atlas = GlyphAtlas::new(cfg(32, 32), RuntimeStrategy::Guillotine, GlyphFormat::Rgba8);
+    let glyph = RasterizedGlyph {
+        width: 2,
+        height: 1,
+        left: 0,
+        top: 1,
+        advance: 2.0,
+        format: GlyphFormat::Rgba8,
+        data: vec![255, 0, 0, 255, 0, 255, 0, 128],
+    };
+    let info = atlas.insert(GlyphKey::new(2, 1, 12.0), &glyph).unwrap();
+    let region = atlas.take_dirty()[0];
+    assert_eq!((region.x, region.y), (info.rect.x, info.rect.y));
+    assert_eq!(
+        atlas.region_pixels(&region).unwrap(),
+        vec![255, 0, 0, 255, 0, 255, 0, 128]
+    );
+
+    let bad = RasterizedGlyph {
+        data: vec![0; 3],
+        ..glyph
+    };
+    assert!(atlas.insert(GlyphKey::new(2, 2, 12.0), &bad).is_err());
+}
+
+#[test]
+fn empty_glyphs_keep_metrics_without_pixels() {
+    let mut atlas = GlyphAtlas::new(cfg(32, 32), RuntimeStrategy::Guillotine, GlyphFormat::A8);
+    let space = RasterizedGlyph {
+        width: 0,
+        height: 0,
+        left: 0,
+        top: 0,
+        advance: 4.0,
+        format: GlyphFormat::A8,
+        data: Vec::new(),
+    };
+    let info = atlas.insert(GlyphKey::new(0, 3, 12.0), &space).unwrap();
+    assert_eq!(info.advance, 4.0);
+    assert_eq!(info.rect.w, 0);
+    assert!(atlas.take_dirty().is_empty());
+    assert_eq!(atlas.num_pages(), 0);
+}
+
+#[test]
+fn budget_evicts_least_recently_used_glyphs() {
+    let mut atlas = GlyphAtlas::new(cfg(32, 32), RuntimeStrategy::Guillotine, GlyphFormat::A8)
+        .with_budget(RuntimeBudget {
+            max_pages: Some(1),
+            max_pixels: None,
+        });
+    for id in 1..=4 {
+        atlas
+            .get_or_rasterize(GlyphKey::new(0, id, 16.0), &mut square)
+            .unwrap();
+    }
+    atlas.get(&GlyphKey::new(0, 1, 16.0));
+    atlas
+        .get_or_rasterize(GlyphKey::new(0, 9, 16.0), &mut square)
+        .unwrap();
+    assert_eq!(atlas.drain_evicted(), vec![GlyphKey::new(0, 2, 16.0)]);
+    assert!(atlas.contains(&GlyphKey::new(0, 1, 16.0)));
+    assert_eq!(atlas.num_pages(), 1);
+    assert!(atlas.remove(&GlyphKey::new(0, 9, 16.0)));
+    assert_eq!(atlas.len(), 3);
+}
+
+#[cfg(feature = "ab_glyph")]
+#[test]
+fn ab_glyph_rasterizer_renders_outlines() {
+    use tex_packer_core::AbGlyphRasterizer;
+    use tex_packer_core::ab_glyph::{Font, FontArc};
+
+    // Uses a system font when one is available; there is no font fixture in the repo.
+    let Ok(bytes) = std::fs::read("/usr/share/fonts/truetype/dejavu/DejaVuSans.ttf") else {
+        return;
+    };
+    let font = FontArc::try_from_vec(bytes).unwrap();
+    let a = font.glyph_id('A').0 as u32;
+    let space = font.glyph_id(' ').0 as u32;
+    let mut raster = AbGlyphRasterizer::new(vec![font]).with_subpixel_bins(4);
+    let mut atlas = GlyphAtlas::new(cfg(128, 128), RuntimeStrategy::Guillotine, GlyphFormat::A8);
+
+    let info = atlas
+        .get_or_rasterize(GlyphKey::new(0, a, 24.0), &mut raster)
+        .unwrap()
+        .unwrap();
+    assert!(info.rect.w > 8 && info.rect.h > 8);
+    assert!(info.top > 0 && info.advance > 0.0);
+    let coverage = atlas.take_dirty();
+    let bytes = atlas.region_pixels(&coverage[0]).unwrap();
+    assert!(bytes.contains(&255));
+
+    let blank = atlas
+        .get_or_rasterize(GlyphKey::new(0, space, 24.0), &mut raster)
+        .unwrap()
+        .unwrap();
+    assert_eq!(blank.rect.w, 0);
+    assert!(blank.advance > 0.0);
+    assert!(
+        atlas
+            .get_or_rasterize(GlyphKey::new(5, a, 24.0), &mut raster)
+            .unwrap()
+            .is_none()
+    );
+}
@@ -0,0 +1,135 @@
+mod common;
+
+use tex_packer_core::model::PackerChoice;
+use tex_packer_core::prelude::*;
+use tex_packer_core::{TexPackerError, pack_layout};
+
+const WHITE: [u8; 4] = [255; 4];
+
+fn cfg(cell: (u32, u32)) -> PackerConfig {
+    PackerConfig {
+        family: AlgorithmFamily::Grid,
+        grid_cell_width: cell.0,
+        grid_cell_height: cell.1,
+        sort_order: SortOrder::NameAsc,
+        ..common::tight(128, 128)
+    }
+}
+
+#[test]
+fn frames_start_at_their_cell_origin() {
+    let mut c = cfg((30, 20));
+    c.border_padding = 1;
+    c.texture_padding = 2;
+    c.texture_extrusion = 1;
+    // Pitch is 30+2+2 = 34 x 20+2+2 = 24; 126/34 = 3 columns.
+    let inputs: Vec<InputImage> = (0..7)
+        .map(|i| common::solid(format!("t{i}"), 10 + i * 3, 20 - i, WHITE))
+        .collect();
+    let out = pack_images(inputs, c.clone()).unwrap();
+    assert_eq!(out.atlas.pages.len(), 1);
+    for f in &out.atlas.pages[0].frames {
+        let i: u32 = f.key[1..].parse().unwrap();
+        let (col, row) = (i % 3, i / 3);
+        // border + cell origin + extrusion + half padding
+        assert_eq!(
+            (f.frame.x, f.frame.y),
+            (1 + col * 34 + 2, 1 + row * 24 + 2),
+            "{}",
+            f.key
+        );
+    }
+    assert_eq!(PackerChoice::from_config(&c).heuristic, "30x20");
+}
+
+#[test]
+fn auto_cell_uses_the_largest_sprite_and_spills_to_new_pages() {
+    // Cells become 40x30: 3x4 = 12 per 128x128 page.
+    let inputs: Vec<InputImage> = (0..15)
+        .map(|i| {
+            common::solid(
+                format!("t{i:02}"),
+                if i == 0 { 40 } else { 8 },
+                if i == 1 { 30 } else { 8 },
+                WHITE,
+            )
+        })
+        .collect();
+    let out = pack_images(inputs, cfg((0, 0))).unwrap();
+    let counts: Vec<usize> = out.atlas.pages.iter().map(|p| p.frames.len()).collect();
+    assert_eq!(counts, vec![12, 3]);
+    let t04 = out.atlas.pages[0]
+        .frames
+        .iter()
+        .find(|f| f.key == "t04")
+        .unwrap();
+    assert_eq!((t04.frame.x, t04.frame.y), (40, 30));
+}
+
+#[test]
+fn oversized_sprite_is_rejected_unless_rotation_fits() {
+    let Err(err) = pack_images(vec![common::solid("wide", 40, 16, WHITE)], cfg((16, 40))) else {
+        panic!("expected an error");
+    };
+    assert!(
+        matches!(err, TexPackerError::InvalidInput(ref m) if m.contains("wide")),
+        "{err}"
+    );
+
+    let mut c = cfg((16, 40));
+    c.allow_rotation = true;
+    let out = pack_images(
+        vec![
+            common::solid("wide", 40, 16, WHITE),
+            common::solid("tall", 16, 40, WHITE),
+        ],
+        c,
+    )
+    .unwrap();
+    let frames = &out.atlas.pages[0].frames;
+    let wide = frames.iter().find(|f| f.key == "wide").unwrap();
+    assert!(wide.rotated);
+    assert_eq!(wide.frame, Rect::new(16, 0, 16, 40));
+}
+
+#[test]
+fn layout_only_matches_image_packing() {
+    let sizes = [(12, 12), (5, 9), (16, 3), (7, 7), (1, 16)];
+    let layout = pack_layout(
+        sizes
+            .iter()
+            .enumerate()
+            .map(|(i, &(w, h))| (format!("t{i}"), w, h))
+            .collect(),
+        cfg((0, 0)),
+    )
+    .unwrap();
+    let images = pack_images(
+        sizes
+            .iter()
+            .enumerate()
+            .map(|(i, &(w, h))| common::solid(format!("t{i}"), w, h, WHITE))
+            .collect(),
+        cfg((0, 0)),
+    )
+    .unwrap();
+    let a: Vec<_> = layout.pages[0].frames.iter().map(|f| f.frame).collect();
+    let b: Vec<_> = images.atlas.pages[0]
+        .frames
+        .iter()
+        .map(|f| f.frame)
+        .collect();
+    assert_eq!(a, b);
+    assert_eq!(a[4], Rect::new(64, 0, 1, 16));
+}
+
+#[test]
+fn grid_options_parse() {
+    let c = PackerConfig::builder().grid_cell(32, 16).build();
+    assert_eq!((c.grid_cell_width, c.grid_cell_height), (32, 16));
+    let mut c = PackerConfig::default();
+    c.set_str("family", "grid").unwrap();
+    c.set_str("grid_cell_width", "24").unwrap();
+    assert_eq!(c.family, AlgorithmFamily::Grid);
+    assert_eq!(c.grid_cell_width, 24);
+}
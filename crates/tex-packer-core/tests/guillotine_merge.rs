@@ -0,0 +1,75 @@
+mod common;
+
+use tex_packer_core::config::{GuillotineChoice, GuillotineSplit, PackerConfig};
+use tex_packer_core::model::Rect;
+use tex_packer_core::packer::Packer;
+use tex_packer_core::packer::guillotine::GuillotinePacker;
+
+fn cfg(merge: bool) -> PackerConfig {
+    PackerConfig {
+        g_merge: merge,
+        trim: true,
+        ..common::tight(256, 256)
+    }
+}
+
+fn placed(merge: bool, rects: &[(u32, u32)]) -> usize {
+    let c = cfg(merge);
+    let mut p = GuillotinePacker::new(c.clone(), c.g_choice.clone(), c.g_split.clone());
+    rects
+        .iter()
+        .enumerate()
+        .filter(|(i, (w, h))| {
+            <GuillotinePacker as Packer<String>>::pack(
+                &mut p,
+                format!("r{i}"),
+                &Rect::new(0, 0, *w, *h),
+            )
+            .is_some()
+        })
+        .count()
+}
+
+#[test]
+fn merge_leaves_room_for_late_large_rect() {
+    // Without merging, the free space left by the first six rects stays fragmented and the
+    // final 109x90 rect no longer fits on the 256x256 page.
+    let rects = [
+        (24, 14),
+        (18, 26),
+        (91, 26),
+        (92, 71),
+        (46, 71),
+        (15, 108),
+        (109, 90),
+    ];
+    assert_eq!(placed(true, &rects), rects.len());
+    assert_eq!(placed(false, &rects), rects.len() - 1);
+}
+
+#[test]
+fn merge_option_is_configurable_and_recorded() {
+    use tex_packer_core::config::AlgorithmFamily;
+    use tex_packer_core::model::PackerChoice;
+
+    assert!(PackerConfig::default().g_merge);
+    let mut c = PackerConfig::default();
+    c.set_str("g_merge", "false").unwrap();
+    assert!(!c.g_merge);
+
+    let choice = |merge| {
+        PackerChoice::from_config(&PackerConfig {
+            family: AlgorithmFamily::Guillotine,
+            g_choice: GuillotineChoice::BestAreaFit,
+            g_split: GuillotineSplit::SplitShorterLeftoverAxis,
+            g_merge: merge,
+            ..Default::default()
+        })
+        .heuristic
+    };
+    assert_eq!(choice(true), "BestAreaFit+SplitShorterLeftoverAxis");
+    assert_eq!(
+        choice(false),
+        "BestAreaFit+SplitShorterLeftoverAxis+NoMerge"
+    );
+}
@@ -0,0 +1,66 @@
+use image::{DynamicImage, ImageFormat, Rgba, Rgba32FImage, imageops};
+use tex_packer_core::{InputImage, PackerConfig, pack_images_hdr};
+
+/// `w`x`h` of HDR values (up to 40.0) inside `margin` transparent pixels.
+fn lightmap(w: u32, h: u32, margin: u32, seed: f32) -> Rgba32FImage {
+    Rgba32FImage::from_fn(w + 2 * margin, h + 2 * margin, |x, y| {
+        let inside = x >= margin && y >= margin && x < w + margin && y < h + margin;
+        if inside {
+            Rgba([seed * 10.0, x as f32 * 0.5, -(y as f32), 1.0])
+        } else {
+            Rgba([0.0; 4])
+        }
+    })
+}
+
+#[test]
+fn hdr_pages_keep_float_values_through_trim_and_rotation() {
+    let originals = [
+        ("tall", lightmap(4, 20, 2, 4.0)),
+        ("wide", lightmap(20, 4, 0, 2.5)),
+    ];
+    let inputs = originals
+        .iter()
+        .map(|(k, img)| InputImage {
+            key: k.to_string(),
+            image: DynamicImage::ImageRgba32F(img.clone()),
+        })
+        .collect();
+    let cfg = PackerConfig {
+        max_width: 24,
+        max_height: 24,
+        allow_rotation: true,
+        texture_padding: 1,
+        texture_extrusion: 1,
+        ..Default::default()
+    };
+    let out = pack_images_hdr(inputs, cfg).unwrap();
+    assert_eq!(out.atlas.meta.format, "RGBA32F");
+    assert_eq!(out.pages.len(), 1);
+    let page = &out.pages[0];
+
+    for f in &page.page.frames {
+        let (_, orig) = originals.iter().find(|(k, _)| *k == f.key).unwrap();
+        let r = f.frame;
+        let placed = imageops::crop_imm(&page.rgba, r.x, r.y, r.w, r.h).to_image();
+        let placed = if f.rotated {
+            imageops::rotate270(&placed)
+        } else {
+            placed
+        };
+        let s = f.source;
+        let expected = imageops::crop_imm(orig, s.x, s.y, s.w, s.h).to_image();
+        assert_eq!(placed, expected, "{}", f.key);
+        assert!(f.content_hash.is_some());
+    }
+    assert!(page.page.frames.iter().any(|f| f.rotated));
+    assert!(page.page.frames.iter().any(|f| f.trimmed));
+
+    // OpenEXR keeps the full range
+    let exr = page.to_exr().unwrap();
+    let decoded = image::load_from_memory_with_format(&exr, ImageFormat::OpenExr)
+        .unwrap()
+        .into_rgba32f();
+    assert_eq!(decoded, page.rgba);
+    assert!(decoded.pixels().any(|p| p[0] > 1.0));
+}
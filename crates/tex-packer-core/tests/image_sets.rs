@@ -0,0 +1,121 @@
+use image::{DynamicImage, Rgba, RgbaImage};
+use tex_packer_core::TexPackerError;
+use tex_packer_core::prelude::*;
+
+/// `w`x`h` layer filled with `color`, transparent outside `opaque` (x, y, w, h).
+fn layer(w: u32, h: u32, opaque: (u32, u32, u32, u32), color: [u8; 3]) -> DynamicImage {
+    let (ox, oy, ow, oh) = opaque;
+    let img = RgbaImage::from_fn(w, h, |x, y| {
+        let inside = x >= ox && x < ox + ow && y >= oy && y < oy + oh;
+        Rgba([color[0], color[1], color[2], if inside { 255 } else { 0 }])
+    });
+    DynamicImage::ImageRgba8(img)
+}
+
+fn set(key: &str, w: u32, h: u32) -> InputImageSet {
+    InputImageSet {
+        key: key.into(),
+        layers: vec![
+            layer(w, h, (0, 0, w, h), [200, 0, 0]),
+            layer(w, h, (0, 0, w, h), [128, 128, 255]),
+            layer(w, h, (0, 0, w, h), [0, 200, 0]),
+        ],
+    }
+}
+
+fn cfg() -> PackerConfig {
+    PackerConfig {
+        max_width: 64,
+        max_height: 64,
+        border_padding: 0,
+        texture_padding: 2,
+        trim: false,
+        ..Default::default()
+    }
+}
+
+#[test]
+fn every_layer_shares_one_layout() {
+    let sets = vec![set("hero", 20, 30), set("coin", 8, 8), set("tree", 24, 40)];
+    let out = pack_image_sets(sets, cfg()).unwrap();
+    assert_eq!(out.layers.len(), 3);
+    let colors = [[200, 0, 0], [128, 128, 255], [0, 200, 0]];
+    for (pages, color) in out.layers.iter().zip(colors) {
+        assert_eq!(pages.len(), out.atlas.pages.len());
+        for (page, meta) in pages.iter().zip(&out.atlas.pages) {
+            let rects = |fs: &[Frame]| {
+                fs.iter()
+                    .map(|f| (f.key.clone(), f.frame))
+                    .collect::<Vec<_>>()
+            };
+            assert_eq!(rects(&page.page.frames), rects(&meta.frames));
+            assert_eq!(page.rgba.dimensions(), (meta.width, meta.height));
+            for f in &meta.frames {
+                let px = page.rgba.get_pixel(f.frame.x, f.frame.y);
+                assert_eq!(px.0, [color[0], color[1], color[2], 255], "{}", f.key);
+            }
+        }
+    }
+}
+
+#[test]
+fn trimming_keeps_the_union_of_all_layers() {
+    // Diffuse is opaque at the top-left, the emissive glow at the bottom-right.
+    let sets = vec![InputImageSet {
+        key: "hero".into(),
+        layers: vec![
+            layer(32, 32, (4, 4, 10, 10), [255, 0, 0]),
+            layer(32, 32, (20, 22, 6, 4), [0, 0, 255]),
+        ],
+    }];
+    let mut c = cfg();
+    c.trim = true;
+    let out = pack_image_sets(sets, c).unwrap();
+    let f = &out.atlas.pages[0].frames[0];
+    assert!(f.trimmed);
+    assert_eq!(f.source, Rect::new(4, 4, 22, 22));
+    // The glow survives the trim in the emissive page.
+    let emissive = &out.layers[1][0].rgba;
+    let px = emissive.get_pixel(f.frame.x + 16 + 2, f.frame.y + 18 + 2);
+    assert_eq!(px.0, [0, 0, 255, 255]);
+}
+
+#[test]
+fn mismatched_sets_are_rejected() {
+    let mut short = set("hero", 8, 8);
+    short.layers.pop();
+    let Err(err) = pack_image_sets(vec![set("coin", 8, 8), short], cfg()) else {
+        panic!("expected an error");
+    };
+    assert!(
+        matches!(err, TexPackerError::InvalidInput(ref m) if m.contains("hero")),
+        "{err}"
+    );
+
+    let mut odd = set("hero", 8, 8);
+    odd.layers[2] = layer(9, 8, (0, 0, 9, 8), [0, 0, 0]);
+    let Err(err) = pack_image_sets(vec![odd], cfg()) else {
+        panic!("expected an error");
+    };
+    assert!(
+        matches!(err, TexPackerError::InvalidInput(ref m) if m.contains("layer 2")),
+        "{err}"
+    );
+
+    assert!(matches!(
+        pack_image_sets(Vec::new(), cfg()),
+        Err(TexPackerError::Empty)
+    ));
+}
+
+#[test]
+fn auto_portfolio_and_multiple_pages() {
+    let sets: Vec<InputImageSet> = (0..6).map(|i| set(&format!("s{i}"), 40, 40)).collect();
+    let mut c = cfg();
+    c.family = AlgorithmFamily::Auto;
+    let out = pack_image_sets(sets, c).unwrap();
+    assert_eq!(out.atlas.pages.len(), 6);
+    assert!(out.atlas.meta.packer.is_some());
+    assert!(out.layers.iter().all(|pages| pages.len() == 6));
+    assert_eq!(out.stats().num_frames, 6);
+}
@@ -0,0 +1,217 @@
+mod common;
+
+use tex_packer_core::{
+    Atlas, Frame, PackOutput, PackerConfig, import_atlas, pack_images, to_gdx_atlas, to_json_array,
+    to_json_hash, to_plist_hash, to_plist_hash_with_pages,
+};
+
+const RED: [u8; 4] = [200, 10, 10, 255];
+
+fn packed() -> PackOutput {
+    let cfg = PackerConfig {
+        max_width: 48,
+        max_height: 48,
+        allow_rotation: true,
+        trim: true,
+        ..Default::default()
+    };
+    pack_images(
+        vec![
+            common::framed("tall", 6, 40, 3, RED),
+            common::framed("wide", 40, 6, 0, RED),
+            common::framed("walk_1", 12, 12, 5, RED),
+            common::framed("walk_2", 9, 17, 1, RED),
+            common::framed("big", 40, 40, 1, RED),
+        ],
+        cfg,
+    )
+    .unwrap()
+}
+
+fn sorted_frames(atlas: &Atlas) -> Vec<(usize, Frame)> {
+    let mut v: Vec<(usize, Frame)> = atlas
+        .pages
+        .iter()
+        .flat_map(|p| p.frames.iter().map(move |f| (p.id, f.clone())))
+        .collect();
+    v.sort_by(|a, b| a.1.key.cmp(&b.1.key));
+    v
+}
+
+/// `trimmed_flag`: whether the format records `trimmed` (libGDX infers it from the geometry).
+fn assert_same_layout(a: &Atlas, b: &Atlas, trimmed_flag: bool) {
+    assert_eq!(a.pages.len(), b.pages.len());
+    for (pa, pb) in a.pages.iter().zip(&b.pages) {
+        assert_eq!((pa.id, pa.width, pa.height), (pb.id, pb.width, pb.height));
+    }
+    let (fa, fb) = (sorted_frames(a), sorted_frames(b));
+    assert_eq!(fa.len(), fb.len());
+    for ((pa, x), (pb, y)) in fa.iter().zip(&fb) {
+        assert_eq!(x.key, y.key);
+        assert_eq!(pa, pb, "{}", x.key);
+        assert_eq!(x.frame, y.frame, "{}", x.key);
+        assert_eq!(x.rotated, y.rotated, "{}", x.key);
+        assert_eq!(x.source, y.source, "{}", x.key);
+        assert_eq!(x.source_size, y.source_size, "{}", x.key);
+        if trimmed_flag {
+            assert_eq!(x.trimmed, y.trimmed, "{}", x.key);
+        }
+        assert_eq!(x.id, y.id, "{}", x.key);
+    }
+}
+
+#[test]
+fn round_trips_own_exporters() {
+    let out = packed();
+    assert!(
+        out.atlas.pages.len() > 1,
+        "test should cover multiple pages"
+    );
+    assert!(sorted_frames(&out.atlas).iter().any(|(_, f)| f.rotated));
+
+    let json_array = to_json_array(&out.atlas).to_string();
+    let json_hash = to_json_hash(&out.atlas).to_string();
+    assert_same_layout(
+        &out.atlas,
+        &Atlas::from_json_array(&json_array).unwrap(),
+        true,
+    );
+    assert_same_layout(
+        &out.atlas,
+        &Atlas::from_json_hash(&json_hash).unwrap(),
+        true,
+    );
+    let imported = import_atlas(&json_array).unwrap();
+    assert_eq!(imported.atlas.meta.app, out.atlas.meta.app);
+    assert_eq!(
+        sorted_frames(&imported.atlas)[0].1.content_hash,
+        sorted_frames(&out.atlas)[0].1.content_hash
+    );
+
+    assert_same_layout(
+        &out.atlas,
+        &Atlas::from_plist(&to_plist_hash(&out.atlas)).unwrap(),
+        true,
+    );
+    let names: Vec<String> = (0..out.atlas.pages.len())
+        .map(|i| format!("atlas_{i}.png"))
+        .collect();
+    let plist = import_atlas(&to_plist_hash_with_pages(&out.atlas, &names)).unwrap();
+    assert_same_layout(&out.atlas, &plist.atlas, true);
+    assert_eq!(plist.page_images[1].as_deref(), Some("atlas_1.png"));
+
+    let gdx = import_atlas(&to_gdx_atlas(&out.atlas, &names)).unwrap();
+    assert_same_layout(&out.atlas, &gdx.atlas, false);
+    assert_eq!(gdx.page_images[0].as_deref(), Some("atlas_0.png"));
+}
+
+#[test]
+fn imports_texturepacker_json_hash() {
+    // TexturePacker writes the unrotated size in `frame` for rotated sprites.
+    let text = r#"{"frames": {
+        "hero.png": {"frame": {"x":2,"y":2,"w":30,"h":10}, "rotated": true, "trimmed": true,
+            "spriteSourceSize": {"x":1,"y":4,"w":30,"h":10}, "sourceSize": {"w":32,"h":16}},
+        "coin.png": {"frame": {"x":14,"y":2,"w":8,"h":8}, "rotated": false, "trimmed": false,
+            "spriteSourceSize": {"x":0,"y":0,"w":8,"h":8}, "sourceSize": {"w":8,"h":8}}
+    },
+    "meta": {"app": "https://www.codeandweb.com/texturepacker", "version": "1.0",
+        "image": "sheet.png", "format": "RGBA8888", "size": {"w":64,"h":32}, "scale": "0.5"}}"#;
+    let imported = import_atlas(text).unwrap();
+    let atlas = &imported.atlas;
+    assert_eq!(atlas.pages.len(), 1);
+    assert_eq!((atlas.pages[0].width, atlas.pages[0].height), (64, 32));
+    assert_eq!(imported.page_images, vec![Some("sheet.png".to_string())]);
+    assert_eq!(atlas.meta.scale, 0.5);
+    let hero = atlas.pages[0]
+        .frames
+        .iter()
+        .find(|f| f.key == "hero.png")
+        .unwrap();
+    assert!(hero.rotated);
+    assert_eq!((hero.frame.w, hero.frame.h), (10, 30));
+    assert_eq!(hero.source, tex_packer_core::Rect::new(1, 4, 30, 10));
+    assert_eq!(hero.source_size, (32, 16));
+}
+
+#[test]
+fn imports_texturepacker_multipack_array() {
+    let text = r#"{"textures": [
+        {"image": "a.png", "size": {"w":16,"h":16},
+         "frames": [{"filename": "x", "frame": {"x":0,"y":0,"w":4,"h":4}, "rotated": false,
+            "trimmed": false, "spriteSourceSize": {"x":0,"y":0,"w":4,"h":4}, "sourceSize": {"w":4,"h":4}}]},
+        {"image": "b.png", "size": {"w":8,"h":8},
+         "frames": [{"filename": "y", "frame": {"x":1,"y":1,"w":2,"h":3}}]}
+    ], "meta": {"app": "texturepacker"}}"#;
+    let imported = import_atlas(text).unwrap();
+    assert_eq!(imported.atlas.pages.len(), 2);
+    assert_eq!(imported.page_images[1].as_deref(), Some("b.png"));
+    let y = &imported.atlas.pages[1].frames[0];
+    assert_eq!(y.key, "y");
+    assert_eq!(y.source_size, (2, 3));
+    assert!(!y.trimmed);
+}
+
+#[test]
+fn imports_cocos_plist_format3() {
+    let text = r#"<?xml version="1.0" encoding="UTF-8"?>
+<!DOCTYPE plist PUBLIC "-//Apple//DTD PLIST 1.0//EN" "http://www.apple.com/DTDs/PropertyList-1.0.dtd">
+<plist version="1.0">
+<dict>
+    <key>frames</key>
+    <dict>
+        <key>enemy.png</key>
+        <dict>
+            <key>aliases</key><array/>
+            <key>spriteOffset</key><string>{1,-2}</string>
+            <key>spriteSize</key><string>{20,10}</string>
+            <key>spriteSourceSize</key><string>{30,20}</string>
+            <key>textureRect</key><string>{{4,6},{20,10}}</string>
+            <key>textureRotated</key><true/>
+        </dict>
+    </dict>
+    <key>metadata</key>
+    <dict>
+        <key>format</key><integer>3</integer>
+        <key>realTextureFileName</key><string>enemies.png</string>
+        <key>size</key><string>{128,64}</string>
+        <key>textureFileName</key><string>enemies.png</string>
+    </dict>
+</dict>
+</plist>"#;
+    let imported = import_atlas(text).unwrap();
+    assert_eq!(imported.page_images, vec![Some("enemies.png".to_string())]);
+    let page = &imported.atlas.pages[0];
+    assert_eq!((page.width, page.height), (128, 64));
+    let f = &page.frames[0];
+    assert!(f.rotated && f.trimmed);
+    assert_eq!(f.frame, tex_packer_core::Rect::new(4, 6, 10, 20));
+    // centre offset (+1, -2) with y up: x = (30-20)/2 + 1, y = (20-10)/2 + 2
+    assert_eq!(f.source, tex_packer_core::Rect::new(6, 7, 20, 10));
+    assert_eq!(f.source_size, (30, 20));
+}
+
+#[test]
+fn imports_gdx_modern_format() {
+    let text = "sheet.png\nsize:256,128\nformat:RGBA8888\nfilter:Linear,Linear\nrepeat:none\n\
+                run\nbounds:2,2,16,24\noffsets:1,3,20,30\nrotate:90\nindex:4\n\
+                idle\nbounds:40,2,8,8\n";
+    let atlas = Atlas::from_gdx(text).unwrap();
+    assert_eq!((atlas.pages[0].width, atlas.pages[0].height), (256, 128));
+    let run = &atlas.pages[0].frames[0];
+    assert_eq!(run.key, "run_4");
+    assert!(run.rotated);
+    assert_eq!(run.frame, tex_packer_core::Rect::new(2, 2, 24, 16));
+    // offsets are bottom-left based: y = 30 - 3 - 24
+    assert_eq!(run.source, tex_packer_core::Rect::new(1, 3, 16, 24));
+    assert_eq!(run.source_size, (20, 30));
+    let idle = &atlas.pages[0].frames[1];
+    assert_eq!(idle.key, "idle");
+    assert!(!idle.trimmed);
+}
+
+#[test]
+fn rejects_garbage() {
+    assert!(import_atlas("{\"foo\": 1}").is_err());
+    assert!(import_atlas("<plist><dict></dict></plist>").is_err());
+    assert!(Atlas::from_gdx("sheet.png\nsize:4,4\nbroken\n").is_err());
+}
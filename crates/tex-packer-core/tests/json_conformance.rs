@@ -0,0 +1,271 @@
+mod common;
+
+use serde::de::{Deserialize, Deserializer, MapAccess, SeqAccess, Visitor};
+use serde_json::Value;
+use tex_packer_core::{
+    PackFile, PackOutput, PackerConfig, import_atlas, pack_images, to_json_array_conformant,
+    to_json_hash_conformant,
+};
+
+/// Abridged TexturePacker "JSON (Hash)" output, the shape Pixi.js and Phaser are tested against.
+const TEXTUREPACKER_HASH: &str = r#"{
+  "frames": {
+    "hero.png": {
+      "frame": {"x": 1, "y": 1, "w": 30, "h": 12},
+      "rotated": true,
+      "trimmed": true,
+      "spriteSourceSize": {"x": 2, "y": 0, "w": 30, "h": 12},
+      "sourceSize": {"w": 32, "h": 12},
+      "pivot": {"x": 0.5, "y": 0.5}
+    }
+  },
+  "meta": {
+    "app": "https://www.codeandweb.com/texturepacker",
+    "version": "1.0",
+    "image": "sheet-0.png",
+    "format": "RGBA8888",
+    "size": {"w": 64, "h": 64},
+    "scale": "1",
+    "related_multi_packs": ["sheet-1.json"]
+  }
+}"#;
+
+/// Abridged TexturePacker "JSON (Array)" output.
+const TEXTUREPACKER_ARRAY: &str = r#"{
+  "frames": [
+    {
+      "filename": "hero.png",
+      "frame": {"x": 1, "y": 1, "w": 30, "h": 12},
+      "rotated": false,
+      "trimmed": false,
+      "spriteSourceSize": {"x": 0, "y": 0, "w": 30, "h": 12},
+      "sourceSize": {"w": 30, "h": 12},
+      "pivot": {"x": 0.5, "y": 0.5}
+    }
+  ],
+  "meta": {
+    "app": "https://www.codeandweb.com/texturepacker",
+    "version": "1.0",
+    "image": "sheet-0.png",
+    "format": "RGBA8888",
+    "size": {"w": 64, "h": 64},
+    "scale": "1",
+    "related_multi_packs": ["sheet-1.json"]
+  }
+}"#;
+
+fn packed() -> PackOutput {
+    let inputs = [
+        ("tall", 6, 40),
+        ("wide", 40, 6),
+        ("a", 20, 20),
+        ("b", 24, 18),
+    ]
+    .into_iter()
+    .map(|(key, w, h)| common::solid(key, w, h, [1, 2, 3, 255]))
+    .collect();
+    let cfg = PackerConfig {
+        max_width: 44,
+        max_height: 32,
+        allow_rotation: true,
+        ..Default::default()
+    };
+    pack_images(inputs, cfg).unwrap()
+}
+
+fn files(out: &PackOutput) -> Vec<PackFile> {
+    out.atlas
+        .pages
+        .iter()
+        .map(|p| PackFile {
+            image: format!("sheet-{}.png", p.id),
+            data: format!("sheet-{}.json", p.id),
+        })
+        .collect()
+}
+
+/// A JSON document with object keys in text order (`Value` sorts them).
+#[derive(Debug)]
+enum Ordered {
+    Object(Vec<(String, Ordered)>),
+    Array(Vec<Ordered>),
+    Scalar(&'static str),
+}
+
+impl<'de> Deserialize<'de> for Ordered {
+    fn deserialize<D: Deserializer<'de>>(d: D) -> Result<Self, D::Error> {
+        struct V;
+        impl<'de> Visitor<'de> for V {
+            type Value = Ordered;
+            fn expecting(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+                f.write_str("JSON")
+            }
+            fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<Ordered, A::Error> {
+                let mut entries = Vec::new();
+                while let Some(entry) = map.next_entry()? {
+                    entries.push(entry);
+                }
+                Ok(Ordered::Object(entries))
+            }
+            fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Ordered, A::Error> {
+                let mut items = Vec::new();
+                while let Some(item) = seq.next_element()? {
+                    items.push(item);
+                }
+                Ok(Ordered::Array(items))
+            }
+            fn visit_bool<E>(self, _: bool) -> Result<Ordered, E> {
+                Ok(Ordered::Scalar("bool"))
+            }
+            fn visit_u64<E>(self, _: u64) -> Result<Ordered, E> {
+                Ok(Ordered::Scalar("number"))
+            }
+            fn visit_i64<E>(self, _: i64) -> Result<Ordered, E> {
+                Ok(Ordered::Scalar("number"))
+            }
+            fn visit_f64<E>(self, _: f64) -> Result<Ordered, E> {
+                Ok(Ordered::Scalar("number"))
+            }
+            fn visit_str<E>(self, _: &str) -> Result<Ordered, E> {
+                Ok(Ordered::Scalar("string"))
+            }
+            fn visit_unit<E>(self) -> Result<Ordered, E> {
+                Ok(Ordered::Scalar("null"))
+            }
+        }
+        d.deserialize_any(V)
+    }
+}
+
+/// Asserts `ours` has the fixture's keys in the fixture's order, recursing into objects and into
+/// the first element of arrays.
+fn assert_same_shape(ours: &Ordered, fixture: &Ordered, path: &str) {
+    match (ours, fixture) {
+        (Ordered::Object(a), Ordered::Object(b)) => {
+            let ka: Vec<&str> = a.iter().map(|(k, _)| k.as_str()).collect();
+            let kb: Vec<&str> = b.iter().map(|(k, _)| k.as_str()).collect();
+            assert_eq!(ka, kb, "{path}");
+            for ((k, x), (_, y)) in a.iter().zip(b) {
+                assert_same_shape(x, y, &format!("{path}.{k}"));
+            }
+        }
+        (Ordered::Array(a), Ordered::Array(b)) => {
+            if let (Some(x), Some(y)) = (a.first(), b.first()) {
+                assert_same_shape(x, y, &format!("{path}[0]"));
+            }
+        }
+        (Ordered::Scalar(a), Ordered::Scalar(b)) => assert_eq!(a, b, "{path}"),
+        _ => panic!("{path}: {ours:?} vs {fixture:?}"),
+    }
+}
+
+#[test]
+fn documents_match_texturepacker_field_order() {
+    let out = packed();
+    assert!(out.atlas.pages.len() > 1);
+    let files = files(&out);
+
+    let fixture: Ordered = serde_json::from_str(TEXTUREPACKER_HASH).unwrap();
+    let mut hash: Ordered =
+        serde_json::from_str(&to_json_hash_conformant(&out.atlas, 0, &files)).unwrap();
+    // Compare one frame entry against the fixture's
+    let Ordered::Object(doc) = &mut hash else {
+        panic!("hash document is not an object");
+    };
+    let Ordered::Object(frames) = &mut doc[0].1 else {
+        panic!("hash frames are not an object");
+    };
+    // Frames stay in packing order
+    let keys: Vec<&str> = frames.iter().map(|(k, _)| k.as_str()).collect();
+    let packed: Vec<&str> = out.atlas.pages[0]
+        .frames
+        .iter()
+        .map(|f| f.key.as_str())
+        .collect();
+    assert_eq!(keys, packed);
+    frames.truncate(1);
+    frames[0].0 = "hero.png".into();
+    assert_same_shape(&hash, &fixture, "hash");
+
+    let fixture: Ordered = serde_json::from_str(TEXTUREPACKER_ARRAY).unwrap();
+    let array: Ordered =
+        serde_json::from_str(&to_json_array_conformant(&out.atlas, 0, &files)).unwrap();
+    assert_same_shape(&array, &fixture, "array");
+}
+
+/// Parses a conformant document for field lookups.
+fn value(text: String) -> Value {
+    serde_json::from_str(&text).unwrap()
+}
+
+#[test]
+fn pages_link_to_their_image_and_each_other() {
+    let out = packed();
+    let files = files(&out);
+    for (idx, page) in out.atlas.pages.iter().enumerate() {
+        let doc = value(to_json_hash_conformant(&out.atlas, idx, &files));
+        let meta = &doc["meta"];
+        assert_eq!(meta["image"], files[idx].image);
+        assert_eq!(meta["size"]["w"], page.width);
+        assert_eq!(meta["size"]["h"], page.height);
+        assert_eq!(meta["scale"], "1");
+        let related: Vec<&str> = meta["related_multi_packs"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|v| v.as_str().unwrap())
+            .collect();
+        let others: Vec<&str> = files
+            .iter()
+            .filter(|f| f.data != files[idx].data)
+            .map(|f| f.data.as_str())
+            .collect();
+        assert_eq!(related, others);
+        // Only this page's frames, without tex-packer extensions
+        assert_eq!(doc["frames"].as_object().unwrap().len(), page.frames.len());
+        for f in doc["frames"].as_object().unwrap().values() {
+            assert!(f.get("page").is_none() && f.get("id").is_none());
+        }
+    }
+
+    // A single page has nothing to link
+    let single = vec![files[0].clone()];
+    let doc = value(to_json_array_conformant(&out.atlas, 0, &single));
+    assert!(doc["meta"].get("related_multi_packs").is_none());
+}
+
+#[test]
+fn rotated_frames_record_their_unrotated_size() {
+    let out = packed();
+    let files = files(&out);
+    let mut rotated = 0;
+    for (idx, page) in out.atlas.pages.iter().enumerate() {
+        let doc = value(to_json_hash_conformant(&out.atlas, idx, &files));
+        for f in &page.frames {
+            let r = &doc["frames"][&f.key]["frame"];
+            // What Pixi's Spritesheet and Phaser's atlas parser reconstruct
+            let (w, h) = (r["w"].as_u64().unwrap(), r["h"].as_u64().unwrap());
+            let on_page = if f.rotated { (h, w) } else { (w, h) };
+            assert_eq!(on_page, (f.frame.w as u64, f.frame.h as u64), "{}", f.key);
+            rotated += f.rotated as usize;
+        }
+
+        // And the documents import back into the same page
+        let imported = import_atlas(&serde_json::to_string(&doc).unwrap()).unwrap();
+        assert_eq!(imported.page_images, [Some(files[idx].image.clone())]);
+        let mut back: Vec<_> = imported.atlas.pages[0]
+            .frames
+            .iter()
+            .map(|f| (f.key.clone(), f.frame, f.rotated))
+            .collect();
+        let mut want: Vec<_> = page
+            .frames
+            .iter()
+            .map(|f| (f.key.clone(), f.frame, f.rotated))
+            .collect();
+        back.sort_by(|a, b| a.0.cmp(&b.0));
+        want.sort_by(|a, b| a.0.cmp(&b.0));
+        assert_eq!(back, want);
+    }
+    assert!(rotated > 0);
+}
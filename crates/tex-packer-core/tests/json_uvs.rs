@@ -0,0 +1,76 @@
+use tex_packer_core::{
+    JsonOptions, PackerConfig, PageSizeMode, pack_layout, to_json_array, to_json_array_with,
+    to_json_hash_with,
+};
+
+fn atlas() -> tex_packer_core::Atlas {
+    let cfg = PackerConfig {
+        max_width: 64,
+        max_height: 64,
+        texture_padding: 0,
+        allow_rotation: false,
+        page_size: PageSizeMode::Fixed,
+        ..Default::default()
+    };
+    pack_layout(vec![("a", 16, 8), ("b", 1, 1)], cfg).unwrap()
+}
+
+fn uv(v: &serde_json::Value) -> [f64; 4] {
+    ["u0", "v0", "u1", "v1"].map(|k| v[k].as_f64().unwrap())
+}
+
+#[test]
+fn uvs_are_the_frame_rect_over_the_page_size() {
+    let atlas = atlas();
+    let a = atlas.pages[0].frames.iter().find(|f| f.key == "a").unwrap();
+    let (x, y) = (a.frame.x as f64, a.frame.y as f64);
+    let options = JsonOptions {
+        uvs: true,
+        uv_inset: 0.0,
+    };
+    let doc = to_json_hash_with(&atlas, &options);
+    assert_eq!(
+        uv(&doc["frames"]["a"]["uv"]),
+        [x / 64.0, y / 64.0, (x + 16.0) / 64.0, (y + 8.0) / 64.0]
+    );
+
+    // Same values in the array layout; no `uv` unless asked for
+    let doc = to_json_array_with(&atlas, &options);
+    let frames = doc["pages"][0]["frames"].as_array().unwrap();
+    let fa = frames.iter().find(|f| f["key"] == "a").unwrap();
+    assert_eq!(
+        uv(&fa["uv"]),
+        [x / 64.0, y / 64.0, (x + 16.0) / 64.0, (y + 8.0) / 64.0]
+    );
+    assert!(
+        to_json_array(&atlas)["pages"][0]["frames"][0]
+            .get("uv")
+            .is_none()
+    );
+}
+
+#[test]
+fn half_texel_inset_shrinks_each_edge() {
+    let atlas = atlas();
+    let a = atlas.pages[0].frames.iter().find(|f| f.key == "a").unwrap();
+    let (x, y) = (a.frame.x as f64, a.frame.y as f64);
+    let doc = to_json_hash_with(
+        &atlas,
+        &JsonOptions {
+            uvs: true,
+            uv_inset: 0.5,
+        },
+    );
+    assert_eq!(
+        uv(&doc["frames"]["a"]["uv"]),
+        [
+            (x + 0.5) / 64.0,
+            (y + 0.5) / 64.0,
+            (x + 15.5) / 64.0,
+            (y + 7.5) / 64.0
+        ]
+    );
+    // A 1x1 frame collapses to its texel center instead of inverting
+    let [u0, v0, u1, v1] = uv(&doc["frames"]["b"]["uv"]);
+    assert!(u0 == u1 && v0 == v1);
+}
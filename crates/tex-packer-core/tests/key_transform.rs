@@ -0,0 +1,98 @@
+mod common;
+
+use tex_packer_core::{
+    KeyCase, KeyReplace, KeyTransform, PackerConfig, TexPackerError, pack_images,
+};
+
+const RED: [u8; 4] = [255, 0, 0, 255];
+
+#[test]
+fn default_transform_keeps_keys() {
+    let t = KeyTransform::default();
+    assert!(t.is_identity());
+    assert_eq!(t.apply("ui/Icons/Hero.png").unwrap(), "ui/Icons/Hero.png");
+}
+
+#[test]
+fn strips_prefix_directories_and_extension() {
+    let t = KeyTransform {
+        strip_prefix: Some("assets/".into()),
+        strip_extension: true,
+        ..Default::default()
+    };
+    assert_eq!(t.apply("assets/ui/hero.png").unwrap(), "ui/hero");
+    assert_eq!(t.apply("other/.hidden").unwrap(), "other/.hidden");
+    assert_eq!(
+        KeyTransform::file_stem().apply("a\\b\\c.x.png").unwrap(),
+        "c.x"
+    );
+    assert_eq!(
+        KeyTransform::file_name().apply("a/b/c.png").unwrap(),
+        "c.png"
+    );
+}
+
+#[test]
+fn regex_replace_then_case() {
+    let t = KeyTransform {
+        strip_extension: true,
+        replace: vec![
+            KeyReplace {
+                pattern: r"_(\d+)$".into(),
+                replacement: "/$1".into(),
+            },
+            KeyReplace {
+                pattern: "/".into(),
+                replacement: ".".into(),
+            },
+        ],
+        case: KeyCase::Upper,
+        ..Default::default()
+    };
+    assert_eq!(t.apply("walk/run_03.png").unwrap(), "WALK.RUN.03");
+}
+
+#[test]
+fn invalid_regex_is_a_config_error() {
+    let t = KeyTransform {
+        replace: vec![KeyReplace {
+            pattern: "(".into(),
+            replacement: String::new(),
+        }],
+        ..Default::default()
+    };
+    assert!(matches!(
+        t.apply("a"),
+        Err(TexPackerError::InvalidConfig(_))
+    ));
+    let cfg = PackerConfig::builder().key_transform(t).build();
+    assert!(matches!(
+        pack_images(vec![common::solid("a", 4, 4, RED)], cfg),
+        Err(TexPackerError::InvalidConfig(_))
+    ));
+}
+
+#[test]
+fn pack_images_uses_transformed_keys() {
+    let cfg = PackerConfig::builder()
+        .key_transform(KeyTransform {
+            case: KeyCase::Lower,
+            ..KeyTransform::file_stem()
+        })
+        .build();
+    let out = pack_images(
+        vec![
+            common::solid("sprites/Hero.png", 8, 8, RED),
+            common::solid("sprites/ui/Button.png", 6, 4, RED),
+        ],
+        cfg,
+    )
+    .unwrap();
+    let mut keys: Vec<_> = out.atlas.pages[0]
+        .frames
+        .iter()
+        .map(|f| f.key.as_str())
+        .collect();
+    keys.sort();
+    assert_eq!(keys, ["button", "hero"]);
+}
@@ -0,0 +1,223 @@
+#![cfg(feature = "ktx2")]
+
+use basis_universal::sys;
+use image::{Rgba, RgbaImage};
+use tex_packer_core::{
+    BasisCodec, PageEncoding, PageFormat, encode_ktx2, encode_ktx2_with_mips, mip_chain,
+};
+
+fn page(w: u32, h: u32, alpha: u8) -> RgbaImage {
+    let mut img = RgbaImage::new(w, h);
+    for (x, y, p) in img.enumerate_pixels_mut() {
+        *p = Rgba([(x * 255 / w) as u8, (y * 255 / h) as u8, 90, alpha]);
+    }
+    img
+}
+
+fn dfd(reader: &ktx2::Reader<&[u8]>) -> (ktx2::DfdBlockHeaderBasic, Vec<(u16, u8, u8)>) {
+    let blocks: Vec<_> = reader.dfd_blocks().collect();
+    assert_eq!(blocks.len(), 1);
+    let basic = ktx2::DfdBlockBasic::parse(blocks[0].data).expect("basic dfd");
+    let samples = basic
+        .sample_information()
+        .map(|s| (s.bit_offset, s.bit_length.get(), s.channel_type))
+        .collect();
+    (basic.header, samples)
+}
+
+/// Decodes UASTC blocks to RGBA8. `LowLevelUastcTranscoder::transcode_slice` undersizes its
+/// buffer for uncompressed targets, so the transcoder is called directly.
+fn decode_uastc(blocks: &[u8], width: u32, height: u32, has_alpha: bool) -> Vec<u8> {
+    basis_universal::transcoder_init();
+    let mut out = vec![0u8; (width * height * 4) as usize];
+    // SAFETY: `out` holds `width * height` RGBA8 pixels, the row pitch and row count passed.
+    unsafe {
+        let transcoder = sys::low_level_uastc_transcoder_new();
+        let ok = sys::low_level_uastc_transcoder_transcode_slice(
+            transcoder,
+            out.as_mut_ptr().cast(),
+            width.div_ceil(4),
+            height.div_ceil(4),
+            blocks.as_ptr(),
+            blocks.len() as u32,
+            sys::basist_block_format_cRGBA32,
+            4,
+            false,
+            has_alpha,
+            width,
+            height,
+            width,
+            std::ptr::null_mut(),
+            height,
+            0,
+            3,
+            0,
+        );
+        sys::low_level_uastc_transcoder_delete(transcoder);
+        assert!(ok, "transcode");
+    }
+    out
+}
+
+/// Mean absolute channel difference between two images of the same size.
+fn mean_error(a: &[u8], b: &[u8]) -> f64 {
+    let sum: u64 = a.iter().zip(b).map(|(x, y)| x.abs_diff(*y) as u64).sum();
+    sum as f64 / a.len() as f64
+}
+
+#[test]
+fn ktx2_uastc_reads_back() {
+    // 13x7 is not a multiple of the 4x4 block size
+    for (alpha, channel) in [(255, 0), (128, 3)] {
+        let img = page(13, 7, alpha);
+        let bytes = encode_ktx2(&img, BasisCodec::Uastc, &PageEncoding::default()).expect("encode");
+        let reader = ktx2::Reader::new(&bytes[..]).expect("valid ktx2");
+        let header = reader.header();
+        assert_eq!(header.format, None);
+        assert_eq!((header.pixel_width, header.pixel_height), (13, 7));
+        assert_eq!((header.level_count, header.face_count), (1, 1));
+        assert_eq!(header.supercompression_scheme, None);
+        assert!(reader.supercompression_global_data().is_empty());
+
+        let (dfd, samples) = dfd(&reader);
+        assert_eq!(dfd.color_model, Some(ktx2::ColorModel::UASTC));
+        assert_eq!(dfd.transfer_function, Some(ktx2::TransferFunction::SRGB));
+        assert_eq!(dfd.bytes_planes[0], 16);
+        assert_eq!(samples, vec![(0, 128, channel)]);
+        let kv: Vec<_> = reader.key_value_data().collect();
+        assert_eq!(kv[0].0, "KTXwriter");
+
+        let level = reader.levels().next().unwrap();
+        // 4x2 blocks of 16 bytes, 16-byte aligned in the file
+        assert_eq!(level.data.len(), 4 * 2 * 16);
+        assert_eq!(level.uncompressed_byte_length, level.data.len() as u64);
+        assert_eq!(
+            (level.data.as_ptr() as usize - bytes.as_ptr() as usize) % 16,
+            0
+        );
+        let rgba = decode_uastc(level.data, 13, 7, alpha < 255);
+        assert!(mean_error(&rgba, img.as_raw()) < 4.0);
+    }
+}
+
+#[test]
+fn ktx2_etc1s_uses_basis_lz_global_data() {
+    for (alpha, slices) in [(255, 1), (100, 2)] {
+        let img = page(20, 12, alpha);
+        let bytes = encode_ktx2(&img, BasisCodec::Etc1s, &PageEncoding::default()).expect("encode");
+        let reader = ktx2::Reader::new(&bytes[..]).expect("valid ktx2");
+        let header = reader.header();
+        assert_eq!((header.pixel_width, header.pixel_height), (20, 12));
+        assert_eq!(header.level_count, 1);
+        assert_eq!(
+            header.supercompression_scheme,
+            Some(ktx2::SupercompressionScheme::BasisLZ)
+        );
+        assert_eq!(header.index.sgd_byte_offset % 8, 0);
+
+        let (dfd, samples) = dfd(&reader);
+        assert_eq!(dfd.color_model, Some(ktx2::ColorModel::ETC1S));
+        assert_eq!(dfd.bytes_planes[0], 0);
+        let expected = [(0, 64, 0), (64, 64, 15)];
+        assert_eq!(samples, expected[..slices]);
+
+        // Global data: counts and lengths, one image descriptor, then the codebooks and tables
+        let sgd = reader.supercompression_global_data();
+        let u16_at = |i: usize| u16::from_le_bytes([sgd[i], sgd[i + 1]]) as usize;
+        let u32_at = |i: usize| u32::from_le_bytes(sgd[i..i + 4].try_into().unwrap()) as usize;
+        assert!(u16_at(0) > 0 && u16_at(2) > 0);
+        let (endpoints, selectors, tables) = (u32_at(4), u32_at(8), u32_at(12));
+        assert_eq!(u32_at(16), 0);
+        assert_eq!(sgd.len(), 20 + 20 + endpoints + selectors + tables);
+
+        let level = reader.levels().next().unwrap();
+        assert_eq!(level.uncompressed_byte_length, 0);
+        let (rgb_offset, rgb_len, alpha_offset, alpha_len) =
+            (u32_at(24), u32_at(28), u32_at(32), u32_at(36));
+        assert_eq!(rgb_offset, 0);
+        assert!(rgb_len > 0);
+        if slices == 2 {
+            assert_eq!(alpha_offset, rgb_len);
+            assert!(alpha_len > 0);
+        } else {
+            assert_eq!((alpha_offset, alpha_len), (0, 0));
+        }
+        assert_eq!(level.data.len(), rgb_len + alpha_len);
+    }
+}
+
+#[test]
+fn ktx2_stores_mip_levels_smallest_first() {
+    let img = page(20, 12, 128);
+    let mips = mip_chain(&img, 4);
+    let bytes = encode_ktx2_with_mips(&img, &mips, BasisCodec::Uastc, &PageEncoding::default())
+        .expect("encode");
+    let reader = ktx2::Reader::new(&bytes[..]).expect("valid ktx2");
+    assert_eq!(reader.header().level_count, 4);
+    let levels: Vec<_> = reader.levels().collect();
+    let offset = |data: &[u8]| data.as_ptr() as usize - bytes.as_ptr() as usize;
+    for (i, (w, h)) in [(20u32, 12u32), (10, 6), (5, 3), (2, 1)]
+        .into_iter()
+        .enumerate()
+    {
+        let data = levels[i].data;
+        assert_eq!(data.len() as u32, w.div_ceil(4) * h.div_ceil(4) * 16);
+        assert_eq!(offset(data) % 16, 0);
+        if i > 0 {
+            assert!(offset(data) < offset(levels[i - 1].data));
+        }
+        let source = if i == 0 { &img } else { &mips[i - 1] };
+        let rgba = decode_uastc(data, w, h, true);
+        assert!(mean_error(&rgba, source.as_raw()) < 4.0, "level {i}");
+    }
+
+    let etc1s = encode_ktx2_with_mips(&img, &mips, BasisCodec::Etc1s, &PageEncoding::default())
+        .expect("encode");
+    let reader = ktx2::Reader::new(&etc1s[..]).expect("valid ktx2");
+    assert_eq!(reader.header().level_count, 4);
+    // One image descriptor per level, each with an RGB and an alpha slice
+    let sgd = reader.supercompression_global_data();
+    let u32_at = |i: usize| u32::from_le_bytes(sgd[i..i + 4].try_into().unwrap()) as usize;
+    for (i, level) in reader.levels().enumerate() {
+        let desc = 20 + i * 20;
+        assert_eq!(level.data.len(), u32_at(desc + 8) + u32_at(desc + 16));
+    }
+
+    // Levels must halve the previous one
+    let skipped = [mips[1].clone()];
+    assert!(encode_ktx2_with_mips(&img, &skipped, BasisCodec::Uastc, &Default::default()).is_err());
+}
+
+#[test]
+fn ktx2_basis_quality_trades_size() {
+    let img = page(64, 64, 255);
+    let size = |codec, basis_quality| {
+        let enc = PageEncoding {
+            basis_quality,
+            ..Default::default()
+        };
+        encode_ktx2(&img, codec, &enc).expect("encode").len()
+    };
+    assert!(size(BasisCodec::Etc1s, 1) < size(BasisCodec::Etc1s, 255));
+    // UASTC is a fixed 8 bits per texel at every level
+    assert_eq!(size(BasisCodec::Uastc, 1), size(BasisCodec::Uastc, 255));
+    assert!(
+        encode_ktx2(
+            &RgbaImage::new(0, 4),
+            BasisCodec::Uastc,
+            &PageEncoding::default()
+        )
+        .is_err()
+    );
+}
+
+#[test]
+fn ktx2_format_and_codec_parse() {
+    assert_eq!("ktx2".parse(), Ok(PageFormat::Ktx2));
+    assert_eq!(PageFormat::Ktx2.extension(), "ktx2");
+    let mut cfg = tex_packer_core::PackerConfig::default();
+    assert_eq!(cfg.basis_codec, BasisCodec::Etc1s);
+    cfg.set_str("basis_codec", "uastc").unwrap();
+    assert_eq!(cfg.basis_codec, BasisCodec::Uastc);
+    assert!(cfg.set_str("basis_codec", "bc7").is_err());
+}
@@ -0,0 +1,132 @@
+mod common;
+
+use image::{Rgba, RgbaImage};
+use tex_packer_core::config::AlgorithmFamily;
+use tex_packer_core::{InputImage, PackOutput, PackerConfig, ScratchImage, pack_images};
+
+fn inputs() -> Vec<InputImage> {
+    (0..10u32)
+        .map(|i| {
+            let (w, h) = (6 + i * 3, 20 - i);
+            let img = RgbaImage::from_fn(w, h, |x, y| {
+                // Transparent border ring so trimming and alpha bleed have work to do
+                if x == 0 || y == 0 {
+                    Rgba([0, 0, 0, 0])
+                } else {
+                    Rgba([(x * 20) as u8, (y * 12) as u8, i as u8 * 25, 255])
+                }
+            });
+            common::input(format!("f{i}"), img)
+        })
+        .collect()
+}
+
+fn cfg(low_memory: bool) -> PackerConfig {
+    PackerConfig {
+        max_width: 64,
+        max_height: 64,
+        border_padding: 1,
+        texture_padding: 2,
+        texture_extrusion: 2,
+        alpha_bleed: Some(2),
+        allow_rotation: true,
+        trim: false,
+        low_memory,
+        ..Default::default()
+    }
+}
+
+fn page_pixels(out: &PackOutput) -> Vec<RgbaImage> {
+    out.pages
+        .iter()
+        .map(|p| p.pixels().expect("readable").into_owned())
+        .collect()
+}
+
+#[test]
+fn low_memory_pages_match_in_memory_pages() {
+    for family in [AlgorithmFamily::MaxRects, AlgorithmFamily::Auto] {
+        let ram = pack_images(
+            inputs(),
+            PackerConfig {
+                family: family.clone(),
+                ..cfg(false)
+            },
+        )
+        .expect("ram");
+        let low = pack_images(
+            inputs(),
+            PackerConfig {
+                family,
+                ..cfg(true)
+            },
+        )
+        .expect("low");
+        assert!(ram.pages.iter().all(|p| p.scratch.is_none()));
+        for p in &low.pages {
+            assert_eq!(p.rgba.dimensions(), (0, 0));
+            let s = p.scratch.as_ref().expect("scratch-backed");
+            assert_eq!(s.dimensions(), (p.page.width, p.page.height));
+        }
+        assert_eq!(page_pixels(&ram), page_pixels(&low));
+    }
+}
+
+#[test]
+fn streamed_png_round_trips() {
+    let out = pack_images(inputs(), cfg(true)).expect("pack");
+    for p in &out.pages {
+        let mut png = Vec::new();
+        p.write_png(&mut png).expect("encode");
+        let decoded = image::load_from_memory(&png).expect("decode").to_rgba8();
+        assert_eq!(decoded, *p.pixels().unwrap());
+    }
+}
+
+#[test]
+fn scratch_tiles_are_clipped_to_the_page() {
+    let s = ScratchImage::new(4, 3).expect("scratch");
+    let tile = RgbaImage::from_pixel(3, 3, Rgba([1, 2, 3, 4]));
+    s.write_tile(2, 1, &tile).unwrap();
+    let img = s.to_rgba().unwrap();
+    for (x, y, px) in img.enumerate_pixels() {
+        let inside = x >= 2 && y >= 1;
+        assert_eq!(
+            *px,
+            if inside {
+                Rgba([1, 2, 3, 4])
+            } else {
+                Rgba([0, 0, 0, 0])
+            }
+        );
+    }
+}
+
+#[test]
+fn parallel_writes_across_bands_match_in_memory_pages() {
+    // Enough frames for parallel compositing, on a page tall enough for several scratch bands
+    let inputs = || {
+        (0..120u32)
+            .map(|i| {
+                let img = RgbaImage::from_fn(10 + i % 9, 12 + i % 7, |x, y| {
+                    Rgba([(x * 9) as u8, (y * 7) as u8, i as u8, 255])
+                });
+                common::input(format!("t{i}"), img)
+            })
+            .collect::<Vec<_>>()
+    };
+    let cfg = |low_memory| PackerConfig {
+        parallel: true,
+        background_color: Some([10, 20, 30, 255]),
+        low_memory,
+        ..common::tight(128, 512)
+    };
+    let ram = pack_images(inputs(), cfg(false)).expect("ram");
+    let low = pack_images(inputs(), cfg(true)).expect("low");
+    assert!(
+        low.pages[0].page.height > 128,
+        "{}",
+        low.pages[0].page.height
+    );
+    assert_eq!(page_pixels(&ram), page_pixels(&low));
+}
@@ -27,14 +27,17 @@ fn maxrects_disjoint_on_small_set() {
         max_width: 256,
         max_height: 256,
         allow_rotation: true,
-        force_max_dimensions: false,
         border_padding: 0,
         texture_padding: 0,
+        padding: None,
+        extrude_mode: Default::default(),
+        align: 0,
+        align_size: false,
         texture_extrusion: 0,
         trim: false,
         trim_threshold: 0,
+        trim_options: Default::default(),
         texture_outlines: false,
-        power_of_two: false,
         square: false,
         use_waste_map: false,
         family: AlgorithmFamily::MaxRects,
@@ -42,14 +45,53 @@ fn maxrects_disjoint_on_small_set() {
         skyline_heuristic: tex_packer_core::config::SkylineHeuristic::BottomLeft,
         g_choice: tex_packer_core::config::GuillotineChoice::BestAreaFit,
         g_split: tex_packer_core::config::GuillotineSplit::SplitShorterLeftoverAxis,
+        g_merge: true,
+        shelf_heuristic: Default::default(),
+        grid_cell_width: 0,
+        grid_cell_height: 0,
         auto_mode: tex_packer_core::config::AutoMode::Quality,
         sort_order: SortOrder::AreaDesc,
         time_budget_ms: None,
         parallel: false,
+        deterministic: false,
+        low_memory: false,
+        key_transform: Default::default(),
+        duplicate_keys: Default::default(),
+        pivot_mode: Default::default(),
+        default_pivot: Default::default(),
+        pivots: Default::default(),
+        sprites: Default::default(),
+        animations: Default::default(),
+        minimize_page_size: false,
         mr_reference: false,
+        mr_global: false,
         auto_mr_ref_time_ms_threshold: None,
         auto_mr_ref_input_threshold: None,
         transparent_policy: tex_packer_core::config::TransparentPolicy::Keep,
+        on_unplaceable: Default::default(),
+        sdf: Default::default(),
+        mip_levels: Default::default(),
+        page_postprocess: None,
+        custom_packer: None,
+        auto_candidates: Vec::new(),
+        auto_objective: Default::default(),
+        auto_score: None,
+        auto_refine: false,
+        page_size: Default::default(),
+        rotation_direction: Default::default(),
+        min_width: 0,
+        min_height: 0,
+        max_pages: None,
+        page_grouping: tex_packer_core::config::PageGrouping::None,
+        group_max_pages: None,
+        page_balance: Default::default(),
+        frame_order: Default::default(),
+        alpha_bleed: None,
+        background_color: None,
+        page_format: tex_packer_core::config::PageFormat::Png,
+        bcn_format: tex_packer_core::config::BcnFormat::Bc7,
+        basis_codec: Default::default(),
+        page_encoding: Default::default(),
     };
 
     let mut p = MaxRectsPacker::new(cfg, MaxRectsHeuristic::BestAreaFit);
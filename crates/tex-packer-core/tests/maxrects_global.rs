@@ -0,0 +1,93 @@
+mod common;
+
+use rand::{Rng, SeedableRng};
+use tex_packer_core::model::PackerChoice;
+use tex_packer_core::prelude::*;
+
+fn heterogeneous_inputs(n: usize, seed: u64) -> Vec<InputImage> {
+    let mut rng = rand::rngs::StdRng::seed_from_u64(seed);
+    (0..n)
+        .map(|i| {
+            // Mix of slivers, squares and large tiles.
+            let (w, h) = match i % 3 {
+                0 => (rng.gen_range(4..=24), rng.gen_range(40..=120)),
+                1 => (rng.gen_range(8..=48), rng.gen_range(8..=48)),
+                _ => (rng.gen_range(60..=140), rng.gen_range(20..=70)),
+            };
+            common::solid(format!("r{i:03}"), w, h, [9, 9, 9, 255])
+        })
+        .collect()
+}
+
+fn cfg(global: bool) -> PackerConfig {
+    PackerConfig {
+        texture_padding: 1,
+        allow_rotation: true,
+        family: AlgorithmFamily::MaxRects,
+        mr_heuristic: MaxRectsHeuristic::BestAreaFit,
+        mr_global: global,
+        ..common::tight(512, 512)
+    }
+}
+
+#[test]
+fn global_insertion_places_everything_without_overlap() {
+    let out = pack_images(heterogeneous_inputs(120, 7), cfg(true)).unwrap();
+    let frames: Vec<_> = out.atlas.pages.iter().flat_map(|p| &p.frames).collect();
+    assert_eq!(frames.len(), 120);
+    out.atlas.pages.iter().for_each(common::assert_disjoint);
+}
+
+#[test]
+fn global_insertion_improves_occupancy_on_heterogeneous_sets() {
+    // Global insertion is a heuristic too, so compare the total over several sets.
+    let (mut seq_area, mut glob_area) = (0, 0);
+    for seed in 1..=6 {
+        let seq = pack_images(heterogeneous_inputs(120, seed), cfg(false)).unwrap();
+        let glob = pack_images(heterogeneous_inputs(120, seed), cfg(true)).unwrap();
+        assert!(
+            glob.atlas.pages.len() <= seq.atlas.pages.len(),
+            "seed {seed}"
+        );
+        seq_area += common::page_area(&seq.atlas.pages);
+        glob_area += common::page_area(&glob.atlas.pages);
+    }
+    assert!(
+        glob_area < seq_area,
+        "global {glob_area} vs sequential {seq_area}"
+    );
+}
+
+#[test]
+fn global_insertion_is_deterministic_and_recorded() {
+    let a = pack_images(heterogeneous_inputs(60, 11), cfg(true)).unwrap();
+    let b = pack_images(heterogeneous_inputs(60, 11), cfg(true)).unwrap();
+    assert_eq!(
+        common::placements(&a.atlas.pages),
+        common::placements(&b.atlas.pages)
+    );
+    assert_eq!(
+        PackerChoice::from_config(&cfg(true)).heuristic,
+        "BestAreaFit+Global"
+    );
+}
+
+#[test]
+fn global_insertion_applies_to_layout_only_packs() {
+    let inputs = heterogeneous_inputs(60, 3);
+    let sizes: Vec<(String, u32, u32)> = inputs
+        .iter()
+        .map(|i| (i.key.clone(), i.image.width(), i.image.height()))
+        .collect();
+    let images = pack_images(inputs, cfg(true)).unwrap();
+    let layout = pack_layout(sizes.clone(), cfg(true)).unwrap();
+    assert_eq!(
+        common::placements(&layout.pages),
+        common::placements(&images.atlas.pages)
+    );
+    let sequential = pack_layout(sizes, cfg(false)).unwrap();
+    assert_ne!(
+        common::placements(&layout.pages),
+        common::placements(&sequential.pages)
+    );
+}
@@ -0,0 +1,71 @@
+mod common;
+
+use image::{Rgba, RgbaImage};
+use tex_packer_core::{
+    InputImage, PackerConfig, TexPackerError, extract_frames, merge_atlases,
+    merge_atlases_prefixed, pack_images,
+};
+
+fn sprite(key: &str, w: u32, h: u32, shade: u8) -> InputImage {
+    let img = RgbaImage::from_fn(w + 2, h + 2, |x, y| {
+        if x == 0 || y == 0 || x == w + 1 || y == h + 1 {
+            Rgba([0, 0, 0, 0])
+        } else {
+            Rgba([shade, x as u8, y as u8, 255])
+        }
+    });
+    common::input(key, img)
+}
+
+fn cfg() -> PackerConfig {
+    PackerConfig {
+        max_width: 128,
+        max_height: 128,
+        allow_rotation: true,
+        trim: true,
+        ..Default::default()
+    }
+}
+
+#[test]
+fn merged_atlas_contains_every_sprite_unchanged() {
+    let a = pack_images(
+        vec![sprite("hero", 20, 30, 1), sprite("coin", 8, 8, 2)],
+        cfg(),
+    )
+    .unwrap();
+    let b = pack_images(vec![sprite("tree", 40, 12, 3)], cfg()).unwrap();
+    let expected: Vec<(String, RgbaImage)> = vec![
+        ("hero".into(), sprite("hero", 20, 30, 1).image.to_rgba8()),
+        ("coin".into(), sprite("coin", 8, 8, 2).image.to_rgba8()),
+        ("tree".into(), sprite("tree", 40, 12, 3).image.to_rgba8()),
+    ];
+
+    let merged = merge_atlases(vec![a, b], cfg()).unwrap();
+    assert_eq!(merged.pages.len(), 1);
+    let pages: Vec<RgbaImage> = merged.pages.iter().map(|p| p.rgba.clone()).collect();
+    let sprites = extract_frames(&merged.atlas, &pages).unwrap();
+    assert_eq!(sprites.len(), 3);
+    for (key, img) in expected {
+        let (_, got) = sprites.iter().find(|(k, _)| *k == key).unwrap();
+        assert!(*got == img, "{key} pixels differ after merge");
+    }
+}
+
+#[test]
+fn key_collisions_need_prefixes() {
+    let make = || pack_images(vec![sprite("icon", 10, 10, 1)], cfg()).unwrap();
+    let err = merge_atlases(vec![make(), make()], cfg()).err().unwrap();
+    assert!(matches!(err, TexPackerError::DuplicateKey(ref k) if k == "icon"));
+
+    let merged =
+        merge_atlases_prefixed(vec![("ui/".into(), make()), ("hud/".into(), make())], cfg())
+            .unwrap();
+    let mut keys: Vec<&str> = merged.atlas.pages[0]
+        .frames
+        .iter()
+        .map(|f| f.key.as_str())
+        .collect();
+    keys.sort();
+    assert_eq!(keys, vec!["hud/icon", "ui/icon"]);
+}
@@ -0,0 +1,102 @@
+mod common;
+
+use image::{Rgba, RgbaImage};
+use tex_packer_core::{
+    PackerConfig, downsample, max_mip_levels, mip_chain, mip_safe_padding, pack_images,
+    to_json_hash,
+};
+
+const GREEN: [u8; 4] = [90, 160, 30, 255];
+
+#[test]
+fn padding_grows_with_the_level_count() {
+    assert_eq!(mip_safe_padding(1, 0), 0);
+    assert_eq!(mip_safe_padding(2, 0), 4);
+    assert_eq!(mip_safe_padding(4, 0), 16);
+    // Extrusion on both sides counts toward the gap
+    assert_eq!(mip_safe_padding(4, 3), 10);
+    assert_eq!(mip_safe_padding(2, 4), 0);
+
+    let cfg = PackerConfig {
+        texture_padding: 2,
+        texture_extrusion: 1,
+        mip_levels: Some(3),
+        ..Default::default()
+    };
+    assert_eq!(cfg.sprite_padding().horizontal(), 6);
+    // An explicit padding above the requirement is kept
+    let wide = PackerConfig {
+        texture_padding: 20,
+        ..cfg.clone()
+    };
+    assert_eq!(wide.sprite_padding().horizontal(), 20);
+    assert!(
+        PackerConfig {
+            mip_levels: Some(0),
+            ..Default::default()
+        }
+        .validate()
+        .is_err()
+    );
+}
+
+#[test]
+fn frames_keep_the_gap_and_meta_records_the_inset() {
+    let cfg = PackerConfig {
+        max_width: 128,
+        max_height: 128,
+        texture_padding: 0,
+        allow_rotation: false,
+        mip_levels: Some(4),
+        ..Default::default()
+    };
+    let inputs = (0..6)
+        .map(|i| common::solid(format!("s{i}"), 10, 10, GREEN))
+        .collect();
+    let out = pack_images(inputs, cfg.clone()).unwrap();
+    let frames = &out.atlas.pages[0].frames;
+    for a in frames {
+        for b in frames {
+            if a.key == b.key {
+                continue;
+            }
+            let gap_x = b.frame.x as i64 - (a.frame.x + a.frame.w) as i64;
+            let gap_y = b.frame.y as i64 - (a.frame.y + a.frame.h) as i64;
+            let gap_x = gap_x.max(a.frame.x as i64 - (b.frame.x + b.frame.w) as i64);
+            let gap_y = gap_y.max(a.frame.y as i64 - (b.frame.y + b.frame.h) as i64);
+            assert!(gap_x.max(gap_y) >= 16, "{} / {}", a.key, b.key);
+        }
+    }
+    assert!(out.atlas.validate(&cfg).is_empty());
+    let json = to_json_hash(&out.atlas);
+    assert_eq!(json["meta"]["mip"]["levels"], 4);
+    assert_eq!(json["meta"]["mip"]["uv_inset"], 4.0);
+}
+
+#[test]
+fn chain_halves_down_to_one_pixel() {
+    let page = RgbaImage::from_fn(5, 3, |x, _| {
+        if x < 2 {
+            Rgba([200, 0, 0, 255])
+        } else {
+            Rgba([0, 0, 255, 0])
+        }
+    });
+    assert_eq!(max_mip_levels(5, 3), 3);
+    let chain = mip_chain(&page, 10);
+    let dims: Vec<_> = chain.iter().map(|l| l.dimensions()).collect();
+    assert_eq!(dims, [(2, 1), (1, 1)]);
+    assert!(mip_chain(&page, 1).is_empty());
+
+    // Transparent pixels do not tint the average
+    let half = downsample(&page);
+    assert_eq!(half.get_pixel(0, 0).0, [200, 0, 0, 255]);
+    let edge = RgbaImage::from_fn(2, 2, |x, _| {
+        if x == 0 {
+            Rgba([100, 100, 100, 255])
+        } else {
+            Rgba([0, 0, 0, 0])
+        }
+    });
+    assert_eq!(downsample(&edge).get_pixel(0, 0).0, [100, 100, 100, 128]);
+}
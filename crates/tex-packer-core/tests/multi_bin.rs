@@ -0,0 +1,194 @@
+mod common;
+
+use image::RgbaImage;
+use tex_packer_core::config::{PageBalance, parse_option};
+use tex_packer_core::prelude::*;
+use tex_packer_core::{VerifyOptions, verify_atlas};
+
+fn inputs(sizes: &[(u32, u32)]) -> Vec<InputImage> {
+    sizes
+        .iter()
+        .enumerate()
+        .map(|(i, &(w, h))| common::solid(format!("s{i:02}"), w, h, [40, 90, 200, 255]))
+        .collect()
+}
+
+fn cfg(family: AlgorithmFamily, balance: PageBalance) -> PackerConfig {
+    PackerConfig {
+        family,
+        page_balance: balance,
+        ..common::tight(64, 64)
+    }
+}
+
+fn key(out: &PackOutput) -> (usize, u64) {
+    let pages = &out.atlas.pages;
+    (pages.len(), common::page_area(pages))
+}
+
+fn check(out: &PackOutput, frames: usize) {
+    assert_eq!(
+        out.atlas
+            .pages
+            .iter()
+            .map(|p| p.frames.len())
+            .sum::<usize>(),
+        frames
+    );
+    let ids: Vec<usize> = out.atlas.pages.iter().map(|p| p.id).collect();
+    assert_eq!(ids, (0..ids.len()).collect::<Vec<_>>());
+    let pages: Vec<RgbaImage> = out.pages.iter().map(|p| p.rgba.clone()).collect();
+    let issues = verify_atlas(&out.atlas, Some(&pages), VerifyOptions::default());
+    assert!(issues.is_empty(), "{issues:?}");
+}
+
+/// Four pages fill-first with MaxRects, three with `MultiBin`.
+const SAVES_A_PAGE: [(u32, u32); 14] = [
+    (30, 34),
+    (28, 14),
+    (22, 20),
+    (16, 22),
+    (38, 8),
+    (4, 34),
+    (16, 10),
+    (38, 8),
+    (8, 2),
+    (8, 28),
+    (36, 32),
+    (18, 8),
+    (26, 26),
+    (8, 32),
+];
+
+/// Skyline `MultiBin` opens its third page early so the first two stay smaller.
+const OPENS_EARLY: [(u32, u32); 25] = [
+    (19, 33),
+    (8, 20),
+    (18, 25),
+    (30, 27),
+    (40, 15),
+    (22, 16),
+    (38, 6),
+    (9, 28),
+    (24, 18),
+    (14, 3),
+    (5, 2),
+    (7, 34),
+    (23, 19),
+    (3, 4),
+    (27, 18),
+    (12, 29),
+    (29, 31),
+    (18, 12),
+    (16, 8),
+    (3, 34),
+    (23, 26),
+    (14, 27),
+    (7, 35),
+    (8, 1),
+    (10, 37),
+];
+
+#[test]
+fn multi_bin_saves_a_page_over_fill_first() {
+    let fill = pack_images(
+        inputs(&SAVES_A_PAGE),
+        cfg(AlgorithmFamily::MaxRects, PageBalance::FillFirst),
+    )
+    .unwrap();
+    let multi = pack_images(
+        inputs(&SAVES_A_PAGE),
+        cfg(AlgorithmFamily::MaxRects, PageBalance::MultiBin),
+    )
+    .unwrap();
+    assert_eq!(fill.atlas.pages.len(), 4);
+    assert_eq!(multi.atlas.pages.len(), 3);
+    check(&multi, SAVES_A_PAGE.len());
+}
+
+#[test]
+fn multi_bin_is_never_worse_than_fill_first() {
+    let mut state = 0x2545_f491_u64;
+    let mut rnd = |m: u32| {
+        state = state
+            .wrapping_mul(6364136223846793005)
+            .wrapping_add(1442695040888963407);
+        (state >> 33) as u32 % m + 1
+    };
+    for family in [
+        AlgorithmFamily::MaxRects,
+        AlgorithmFamily::Skyline,
+        AlgorithmFamily::Guillotine,
+    ] {
+        for _ in 0..20 {
+            let n = 6 + rnd(20) as usize;
+            let sizes: Vec<(u32, u32)> = (0..n).map(|_| (2 * rnd(20), 2 * rnd(20))).collect();
+            let fill =
+                pack_images(inputs(&sizes), cfg(family.clone(), PageBalance::FillFirst)).unwrap();
+            let multi =
+                pack_images(inputs(&sizes), cfg(family.clone(), PageBalance::MultiBin)).unwrap();
+            assert!(key(&multi) <= key(&fill), "{family:?} {sizes:?}");
+            check(&multi, n);
+        }
+    }
+}
+
+#[test]
+fn multi_bin_parses_from_str() {
+    assert_eq!(
+        parse_option::<PageBalance>("page_balance", "multi_bin").unwrap(),
+        PageBalance::MultiBin
+    );
+    let mut c = PackerConfig::default();
+    c.set_str("page_balance", "lookahead").unwrap();
+    assert_eq!(c.page_balance, PageBalance::MultiBin);
+}
+
+#[test]
+fn multi_bin_opens_a_page_early_when_it_saves_area() {
+    let fill = pack_images(
+        inputs(&OPENS_EARLY),
+        cfg(AlgorithmFamily::Skyline, PageBalance::FillFirst),
+    )
+    .unwrap();
+    let multi = pack_images(
+        inputs(&OPENS_EARLY),
+        cfg(AlgorithmFamily::Skyline, PageBalance::MultiBin),
+    )
+    .unwrap();
+    // Packing the open pages as tightly as possible needs 10769 px of pages; opening the third
+    // page early keeps the first two smaller.
+    assert_eq!(key(&multi), (3, 9762));
+    assert!(key(&multi) < key(&fill));
+    check(&multi, OPENS_EARLY.len());
+}
+
+#[test]
+fn multi_bin_applies_to_layout_only_packs() {
+    let cases = [
+        (AlgorithmFamily::MaxRects, &SAVES_A_PAGE[..]),
+        (AlgorithmFamily::Skyline, &OPENS_EARLY[..]),
+    ];
+    for (family, sizes) in cases {
+        let layout_inputs: Vec<(String, u32, u32)> = sizes
+            .iter()
+            .enumerate()
+            .map(|(i, &(w, h))| (format!("s{i:02}"), w, h))
+            .collect();
+        let images =
+            pack_images(inputs(sizes), cfg(family.clone(), PageBalance::MultiBin)).unwrap();
+        let layout = pack_layout(
+            layout_inputs.clone(),
+            cfg(family.clone(), PageBalance::MultiBin),
+        )
+        .unwrap();
+        let fill = pack_layout(layout_inputs, cfg(family.clone(), PageBalance::FillFirst)).unwrap();
+        assert_eq!(
+            common::placements(&layout.pages),
+            common::placements(&images.atlas.pages),
+            "{family:?}"
+        );
+        let area = |pages: &[Page<String>]| (pages.len(), common::page_area(pages));
+        assert!(area(&layout.pages) < area(&fill.pages), "{family:?}");
+    }
+}
@@ -0,0 +1,221 @@
+mod common;
+
+use image::RgbaImage;
+use tex_packer_core::config::{OnUnplaceable, TransparentPolicy, parse_option};
+use tex_packer_core::{
+    Atlas, InputImage, LayoutItem, PackControl, PackerConfig, Rect, SkipReason, SkippedItem,
+    TexPackerError, pack_images, pack_images_iter, pack_layout, pack_layout_items_with,
+    to_json_hash,
+};
+
+const RED: [u8; 4] = [200, 40, 40, 255];
+
+fn inputs() -> Vec<InputImage> {
+    vec![
+        common::solid("small", 10, 10, RED),
+        common::solid("huge", 200, 50, RED),
+        common::input("clear", RgbaImage::new(8, 8)),
+        common::solid("wide", 40, 12, RED),
+    ]
+}
+
+fn cfg(policy: OnUnplaceable) -> PackerConfig {
+    PackerConfig {
+        max_width: 64,
+        max_height: 64,
+        allow_rotation: false,
+        border_padding: 1,
+        texture_padding: 2,
+        trim: true,
+        transparent_policy: TransparentPolicy::Skip,
+        on_unplaceable: policy,
+        ..Default::default()
+    }
+}
+
+fn keys(out: &tex_packer_core::PackOutput) -> Vec<&str> {
+    let mut keys: Vec<&str> = out
+        .atlas
+        .pages
+        .iter()
+        .flat_map(|p| p.frames.iter().map(|f| f.key.as_str()))
+        .collect();
+    keys.sort();
+    keys
+}
+
+#[test]
+fn error_policy_fails_the_run() {
+    let res = pack_images(inputs(), cfg(OnUnplaceable::Error));
+    assert!(matches!(
+        res,
+        Err(TexPackerError::OutOfSpace { .. } | TexPackerError::OutOfSpaceGeneric { .. })
+    ));
+}
+
+#[test]
+fn skip_returns_a_partial_result() {
+    let out = pack_images(inputs(), cfg(OnUnplaceable::Skip)).unwrap();
+    assert_eq!(keys(&out), ["small", "wide"]);
+    assert_eq!(
+        out.skipped,
+        [
+            SkippedItem {
+                key: "clear".into(),
+                reason: SkipReason::Transparent,
+            },
+            SkippedItem {
+                key: "huge".into(),
+                reason: SkipReason::TooLarge {
+                    width: 200,
+                    height: 50,
+                },
+            },
+        ]
+    );
+    assert!(out.scaled.is_empty());
+    assert!(out.atlas.validate(&cfg(OnUnplaceable::Skip)).is_empty());
+
+    // Everything too large is an empty result, not an error
+    let out = pack_images(
+        vec![common::solid("huge", 100, 100, RED)],
+        cfg(OnUnplaceable::Skip),
+    )
+    .unwrap();
+    assert!(out.atlas.pages.is_empty());
+    assert_eq!(out.skipped.len(), 1);
+}
+
+#[test]
+fn scale_down_shrinks_to_fit() {
+    let cfg = cfg(OnUnplaceable::ScaleDown);
+    let out = pack_images(inputs(), cfg.clone()).unwrap();
+    assert_eq!(keys(&out), ["huge", "small", "wide"]);
+    assert_eq!(out.scaled.len(), 1);
+    let scaled = &out.scaled[0];
+    assert_eq!((scaled.key.as_str(), scaled.original), ("huge", (200, 50)));
+    // The smallest factor that fits the 1px border and 2px padding, aspect ratio kept
+    let (w, h) = scaled.scaled;
+    assert_eq!((w, h), (60, 15));
+    assert!((scaled.scale - 0.3).abs() < 1e-6, "{}", scaled.scale);
+    let frame = out.atlas.pages[0]
+        .frames
+        .iter()
+        .find(|f| f.key == "huge")
+        .unwrap();
+    assert_eq!(frame.source_size, (w, h));
+    assert_eq!(frame.scale, Some(scaled.scale));
+    let small = out.atlas.pages[0]
+        .frames
+        .iter()
+        .find(|f| f.key == "small")
+        .unwrap();
+    assert_eq!(small.scale, None);
+    assert!(out.atlas.validate(&cfg).is_empty());
+
+    // Exported metadata carries the factor and reads back
+    let json = to_json_hash(&out.atlas);
+    assert_eq!(json["frames"]["huge"]["scale"], serde_json::json!(0.3));
+    assert!(json["frames"]["small"].get("scale").is_none());
+    let back = Atlas::from_json_hash(&json.to_string()).unwrap();
+    let frame = back.pages[0].frames.iter().find(|f| f.key == "huge");
+    assert_eq!(frame.unwrap().scale, Some(scaled.scale));
+
+    // Rotation lets a tall image scale less
+    let tall = || vec![common::solid("tall", 40, 160, RED)];
+    let upright = pack_images(tall(), cfg.clone()).unwrap().scaled[0].scaled;
+    let rotated = PackerConfig {
+        allow_rotation: true,
+        ..cfg.clone()
+    };
+    let turned = pack_images(tall(), rotated).unwrap().scaled[0].scaled;
+    assert!(turned.1 >= upright.1, "{turned:?} vs {upright:?}");
+}
+
+#[test]
+fn layout_only_applies_the_policy() {
+    let sizes = || vec![("small", 10, 10), ("huge", 200, 50), ("wide", 40, 12)];
+    assert!(pack_layout(sizes(), cfg(OnUnplaceable::Error)).is_err());
+    let atlas = pack_layout(sizes(), cfg(OnUnplaceable::Skip)).unwrap();
+    let packed = pack_images(inputs(), cfg(OnUnplaceable::Skip)).unwrap();
+    assert_eq!(
+        common::placements(&atlas.pages),
+        common::placements(&packed.atlas.pages)
+    );
+
+    // Trimmed items scale their source rect along with the size
+    let items = vec![LayoutItem {
+        key: "huge".to_string(),
+        w: 180,
+        h: 40,
+        source: Some(Rect::new(10, 5, 180, 40)),
+        source_size: Some((200, 50)),
+        trimmed: true,
+    }];
+    let cfg = cfg(OnUnplaceable::ScaleDown);
+    let out = pack_layout_items_with(items, cfg.clone(), &PackControl::default()).unwrap();
+    assert!(out.skipped.is_empty());
+    assert_eq!(out.scaled.len(), 1);
+    assert_eq!(out.scaled[0].original, (200, 50));
+    let f = &out.atlas.pages[0].frames[0];
+    let scale = f.scale.unwrap() as f64;
+    assert_eq!(f.source_size, out.scaled[0].scaled);
+    assert_eq!((f.frame.w, f.frame.h), (f.source.w, f.source.h));
+    assert_eq!(f.source.x, (10.0 * scale) as u32);
+    assert!(f.source.x + f.source.w <= f.source_size.0);
+    assert!(out.atlas.validate(&cfg).is_empty());
+}
+
+#[test]
+fn streaming_records_skips_and_decode_failures() {
+    let items = inputs()
+        .into_iter()
+        .map(Ok)
+        .chain([Err(TexPackerError::InvalidInput("broken.png".into()))]);
+    let stream = pack_images_iter(items, cfg(OnUnplaceable::Skip)).unwrap();
+    let out = stream.into_output().unwrap();
+    assert_eq!(keys(&out), ["small", "wide"]);
+    let reasons: Vec<(&str, &SkipReason)> = out
+        .skipped
+        .iter()
+        .map(|s| (s.key.as_str(), &s.reason))
+        .collect();
+    assert_eq!(reasons.len(), 3, "{reasons:?}");
+    assert!(reasons.contains(&("clear", &SkipReason::Transparent)));
+    assert!(
+        reasons
+            .iter()
+            .any(|(k, r)| *k == "huge" && matches!(r, SkipReason::TooLarge { .. }))
+    );
+    assert!(reasons.iter().any(|(k, r)| *k == "#4"
+        && matches!(r, SkipReason::Decode { error } if error.contains("broken.png"))));
+
+    // Under Error the failure still ends the stream
+    let items = vec![Err(TexPackerError::InvalidInput("broken.png".into()))];
+    let mut stream = pack_images_iter(items, cfg(OnUnplaceable::Error)).unwrap();
+    assert!(stream.next().unwrap().is_err());
+}
+
+#[test]
+fn policy_parses_and_serializes() {
+    assert_eq!(
+        parse_option::<OnUnplaceable>("on_unplaceable", "scale-down").unwrap(),
+        OnUnplaceable::ScaleDown
+    );
+    assert!(parse_option::<OnUnplaceable>("on_unplaceable", "drop").is_err());
+    let mut cfg = PackerConfig::default();
+    cfg.set_str("on_unplaceable", "skip").unwrap();
+    assert_eq!(cfg.on_unplaceable, OnUnplaceable::Skip);
+
+    let item = SkippedItem {
+        key: "a".into(),
+        reason: SkipReason::TooLarge {
+            width: 3,
+            height: 4,
+        },
+    };
+    assert_eq!(
+        serde_json::to_value(&item).unwrap(),
+        serde_json::json!({"key": "a", "reason": "too_large", "width": 3, "height": 4})
+    );
+}
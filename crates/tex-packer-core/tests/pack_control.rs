@@ -0,0 +1,132 @@
+mod common;
+
+use std::sync::{Arc, Mutex};
+use tex_packer_core::{
+    AlgorithmFamily, AutoMode, CancellationToken, InputImage, PackControl, PackPhase, PackProgress,
+    PackerConfig, TexPackerError, pack_images_with,
+};
+
+fn inputs(n: u32) -> Vec<InputImage> {
+    (0..n)
+        .map(|i| common::solid(format!("s{i:02}"), 20 + i % 7, 16, [i as u8, 0, 0, 255]))
+        .collect()
+}
+
+fn small_pages() -> PackerConfig {
+    PackerConfig {
+        max_width: 64,
+        max_height: 64,
+        family: AlgorithmFamily::Skyline,
+        ..Default::default()
+    }
+}
+
+fn recorder() -> (Arc<Mutex<Vec<PackProgress>>>, PackControl) {
+    let log = Arc::new(Mutex::new(Vec::new()));
+    let sink = Arc::clone(&log);
+    let ctl = PackControl::new().on_progress(move |p| sink.lock().unwrap().push(p));
+    (log, ctl)
+}
+
+#[test]
+fn progress_covers_every_phase() {
+    let (log, ctl) = recorder();
+    let out = pack_images_with(inputs(12), small_pages(), &ctl).unwrap();
+    let log = log.lock().unwrap();
+    let last = |phase| {
+        log.iter()
+            .filter(|p| p.phase == phase)
+            .map(|p| (p.done, p.total))
+            .max()
+    };
+    assert_eq!(last(PackPhase::Prepare), Some((12, 12)));
+    assert_eq!(last(PackPhase::Layout), Some((1, 1)));
+    let pages = out.pages.len();
+    assert!(pages > 1);
+    assert_eq!(last(PackPhase::Compose), Some((pages, pages)));
+    // Phases arrive in order.
+    let first_compose = log
+        .iter()
+        .position(|p| p.phase == PackPhase::Compose)
+        .unwrap();
+    assert!(
+        log[..first_compose]
+            .iter()
+            .all(|p| p.phase != PackPhase::Compose)
+    );
+    assert!(
+        log[first_compose..]
+            .iter()
+            .all(|p| p.phase == PackPhase::Compose)
+    );
+}
+
+#[test]
+fn auto_reports_candidates() {
+    let (log, ctl) = recorder();
+    let cfg = PackerConfig {
+        family: AlgorithmFamily::Auto,
+        auto_mode: AutoMode::Quality,
+        ..small_pages()
+    };
+    pack_images_with(inputs(8), cfg, &ctl).unwrap();
+    let layout: Vec<_> = log
+        .lock()
+        .unwrap()
+        .iter()
+        .filter(|p| p.phase == PackPhase::Layout)
+        .copied()
+        .collect();
+    assert!(layout.len() > 1);
+    assert!(layout.iter().all(|p| p.total == layout.len()));
+    assert!(layout.iter().any(|p| p.done == p.total));
+}
+
+#[test]
+fn cancelled_token_stops_before_packing() {
+    let token = CancellationToken::new();
+    token.clone().cancel();
+    let ctl = PackControl::new().cancel_token(token);
+    let res = pack_images_with(inputs(4), small_pages(), &ctl);
+    assert!(matches!(res, Err(TexPackerError::Cancelled)));
+}
+
+#[test]
+fn cancel_from_progress_callback_interrupts_run() {
+    let token = CancellationToken::new();
+    let trigger = token.clone();
+    let ctl = PackControl::new()
+        .cancel_token(token.clone())
+        .on_progress(move |p| {
+            if p.phase == PackPhase::Prepare && p.done == 3 {
+                trigger.cancel();
+            }
+        });
+    let cfg = PackerConfig {
+        parallel: false,
+        ..small_pages()
+    };
+    let res = pack_images_with(inputs(12), cfg, &ctl);
+    assert!(matches!(res, Err(TexPackerError::Cancelled)));
+    assert!(token.is_cancelled());
+}
+
+#[test]
+fn cancel_after_prepare_skips_anneal() {
+    let token = CancellationToken::new();
+    let trigger = token.clone();
+    let ctl = PackControl::new()
+        .cancel_token(token)
+        .on_progress(move |p| {
+            if p.phase == PackPhase::Prepare && p.done == p.total {
+                trigger.cancel();
+            }
+        });
+    let cfg = PackerConfig {
+        family: AlgorithmFamily::Auto,
+        auto_mode: AutoMode::Anneal,
+        ..small_pages()
+    };
+    let res = pack_images_with(inputs(6), cfg, &ctl);
+    assert!(matches!(res, Err(TexPackerError::Cancelled)));
+}
@@ -0,0 +1,105 @@
+mod common;
+
+use image::Rgba;
+use std::cell::Cell;
+use tex_packer_core::config::PageGrouping;
+use tex_packer_core::{InputImage, PackerConfig, Result, TexPackerError, pack_images_iter};
+
+fn input(i: usize, w: u32, h: u32) -> InputImage {
+    common::solid(format!("s{i:03}"), w, h, [i as u8, 7, 99, 255])
+}
+
+fn cfg() -> PackerConfig {
+    PackerConfig {
+        ..common::tight(64, 64)
+    }
+}
+
+#[test]
+fn streams_pages_while_consuming_inputs_lazily() {
+    let pulled = Cell::new(0usize);
+    let inputs = (0..20).map(|i| {
+        pulled.set(pulled.get() + 1);
+        Ok(input(i, 30, 30))
+    });
+    let mut stream = pack_images_iter(inputs, cfg()).expect("valid config");
+    let first = stream.next().expect("a page").expect("no error");
+    // Four 30x30 sprites fill a 64x64 page; the fifth closes it.
+    assert_eq!(first.page.frames.len(), 4);
+    assert_eq!(pulled.get(), 5);
+
+    let rest: Vec<_> = stream.collect::<Result<_>>().expect("remaining pages");
+    assert_eq!(rest.len(), 4);
+    assert_eq!(pulled.get(), 20);
+}
+
+#[test]
+fn streamed_pages_hold_the_sprite_pixels() {
+    let inputs = (0..9).map(|i| Ok(input(i, 10 + i as u32, 12)));
+    let out = pack_images_iter(inputs, cfg())
+        .unwrap()
+        .into_output()
+        .expect("pack");
+    let mut seen = 0;
+    for p in &out.pages {
+        assert_eq!(p.rgba.dimensions(), (p.page.width, p.page.height));
+        for f in &p.page.frames {
+            let i: u8 = f.key[1..].parse().unwrap();
+            assert_eq!(
+                *p.rgba.get_pixel(f.frame.x, f.frame.y),
+                Rgba([i, 7, 99, 255])
+            );
+            let (r, b) = (f.frame.x + f.frame.w - 1, f.frame.y + f.frame.h - 1);
+            assert_eq!(*p.rgba.get_pixel(r, b), Rgba([i, 7, 99, 255]));
+            seen += 1;
+        }
+    }
+    assert_eq!(seen, 9);
+    assert_eq!(out.atlas.pages.len(), out.pages.len());
+}
+
+#[test]
+fn input_errors_stop_the_stream() {
+    let inputs = vec![
+        Ok(input(0, 8, 8)),
+        Err(TexPackerError::InvalidInput("broken.png".into())),
+        Ok(input(2, 8, 8)),
+    ];
+    let mut stream = pack_images_iter(inputs, cfg()).unwrap();
+    assert!(matches!(
+        stream.next(),
+        Some(Err(TexPackerError::InvalidInput(_)))
+    ));
+    assert!(stream.next().is_none());
+}
+
+#[test]
+fn limits_and_unsupported_options_are_reported() {
+    let empty: Vec<Result<InputImage>> = Vec::new();
+    let err = pack_images_iter(empty, cfg()).unwrap().into_output();
+    assert!(matches!(err, Err(TexPackerError::Empty)));
+
+    let too_big = vec![Ok(input(0, 100, 10))];
+    let err = pack_images_iter(too_big, cfg()).unwrap().into_output();
+    assert!(matches!(err, Err(TexPackerError::OutOfSpace { .. })));
+
+    let limited = PackerConfig {
+        max_pages: Some(2),
+        ..cfg()
+    };
+    let inputs = (0..12).map(|i| Ok(input(i, 30, 30)));
+    let err = pack_images_iter(inputs, limited).unwrap().into_output();
+    assert!(matches!(
+        err,
+        Err(TexPackerError::TooManyPages { max_pages: 2, .. })
+    ));
+
+    let grouped = PackerConfig {
+        page_grouping: PageGrouping::Folder,
+        ..cfg()
+    };
+    assert!(matches!(
+        pack_images_iter(Vec::new(), grouped),
+        Err(TexPackerError::InvalidConfig(_))
+    ));
+}
@@ -155,7 +155,7 @@ fn test_pack_stats_wasted_area() {
         texture_padding: 0,
         texture_extrusion: 0,
         trim: false,
-        force_max_dimensions: true, // Force full page size
+        page_size: PageSizeMode::Fixed, // Force full page size
         family: AlgorithmFamily::Skyline,
         ..Default::default()
     };
@@ -173,7 +173,7 @@ fn test_pack_stats_wasted_area() {
     let wasted = stats.wasted_area();
     let waste_pct = stats.waste_percentage();
 
-    // With force_max_dimensions, should have significant wasted space
+    // With a fixed page size, should have significant wasted space
     // 32x32 texture in 256x256 page = 1024 used, 65536 total
     assert!(
         wasted > 0,
@@ -256,6 +256,87 @@ fn test_pack_stats_multiple_pages() {
     assert!(stats.max_page_height <= 128);
 }
 
+#[test]
+fn test_page_stats_match_totals() {
+    let cfg = PackerConfig {
+        max_width: 128,
+        max_height: 128,
+        border_padding: 0,
+        texture_padding: 0,
+        texture_extrusion: 0,
+        trim: false,
+        family: AlgorithmFamily::Skyline,
+        ..Default::default()
+    };
+
+    let mut inputs = Vec::new();
+    for i in 0..20 {
+        let img = DynamicImage::ImageRgba8(RgbaImage::new(40, 40));
+        inputs.push(InputImage {
+            key: format!("tex_{}", i),
+            image: img,
+        });
+    }
+
+    let result = pack_images(inputs, cfg).expect("packing should succeed");
+    let stats = result.stats();
+    let pages = result.atlas.page_stats();
+
+    assert_eq!(pages.len(), stats.num_pages);
+    for (ps, page) in pages.iter().zip(&result.atlas.pages) {
+        assert_eq!(ps.id, page.id);
+        assert_eq!(ps.num_frames, page.frames.len());
+        assert!(ps.occupancy > 0.0 && ps.occupancy <= 1.0);
+        assert_eq!(ps.wasted_area() + ps.used_frame_area, ps.page_area);
+    }
+    assert_eq!(
+        pages.iter().map(|p| p.num_frames).sum::<usize>(),
+        stats.num_frames
+    );
+    assert_eq!(
+        pages.iter().map(|p| p.used_frame_area).sum::<u64>(),
+        stats.used_frame_area
+    );
+    assert_eq!(
+        pages.iter().map(|p| p.wasted_area()).sum::<u64>(),
+        stats.wasted_area()
+    );
+}
+
+#[test]
+fn test_pack_stats_lower_bound() {
+    let cfg = PackerConfig {
+        max_width: 128,
+        max_height: 128,
+        border_padding: 2,
+        texture_padding: 2,
+        texture_extrusion: 1,
+        trim: false,
+        family: AlgorithmFamily::MaxRects,
+        ..Default::default()
+    };
+
+    let mut inputs = Vec::new();
+    for i in 0..12 {
+        let img = DynamicImage::ImageRgba8(RgbaImage::new(30, 20));
+        inputs.push(InputImage {
+            key: format!("tex_{}", i),
+            image: img,
+        });
+    }
+
+    let result = pack_images(inputs, cfg).expect("packing should succeed");
+    let stats = result.stats();
+
+    // Each frame reserves texture padding + 2 * extrusion on both axes.
+    assert_eq!(stats.lower_bound_area, 12 * (30 + 4) * (20 + 4));
+    assert!(stats.lower_bound_area <= stats.total_page_area);
+    assert!(stats.lower_bound_pages >= 1);
+    assert!(stats.lower_bound_pages <= stats.num_pages);
+    assert!(stats.bound_efficiency > stats.occupancy);
+    assert!(stats.bound_efficiency <= 1.0);
+}
+
 #[test]
 fn test_pack_stats_empty_atlas() {
     // Create an empty atlas manually
@@ -273,8 +354,16 @@ fn test_pack_stats_empty_atlas() {
             padding: (0, 0),
             extrude: 0,
             allow_rotation: false,
+            rotation_direction: Default::default(),
             trim_mode: "none".into(),
             background_color: None,
+            packer: None,
+            page_minimization: None,
+            auto_refinement: None,
+            align: None,
+            animations: Vec::new(),
+            sdf: None,
+            mip: None,
         },
     };
 
@@ -286,4 +375,89 @@ fn test_pack_stats_empty_atlas() {
     assert_eq!(stats.used_frame_area, 0);
     assert_eq!(stats.occupancy, 0.0);
     assert_eq!(stats.wasted_area(), 0);
+    assert_eq!(stats.lower_bound_area, 0);
+    assert_eq!(stats.lower_bound_pages, 0);
+    assert_eq!(stats.bound_efficiency, 0.0);
+}
+
+#[test]
+fn test_pack_stats_breakdown() {
+    let cfg = PackerConfig {
+        max_width: 64,
+        max_height: 64,
+        border_padding: 0,
+        texture_padding: 2,
+        texture_extrusion: 1,
+        trim: true,
+        allow_rotation: false,
+        family: AlgorithmFamily::Skyline,
+        ..Default::default()
+    };
+
+    // 16x16 canvases with an 8x4 opaque block: trimming drops 256 - 32 pixels each
+    let inputs = (0..6)
+        .map(|i| {
+            let mut img = RgbaImage::new(16, 16);
+            for y in 4..8 {
+                for x in 2..10 {
+                    img.put_pixel(x, y, image::Rgba([255; 4]));
+                }
+            }
+            InputImage {
+                key: format!("s{i}"),
+                image: DynamicImage::ImageRgba8(img),
+            }
+        })
+        .collect();
+
+    let result = pack_images(inputs, cfg).expect("packing should succeed");
+    let stats = result.stats();
+
+    assert_eq!(stats.num_trimmed, 6);
+    assert_eq!(stats.trimmed_pixels_saved, 6 * (256 - 32));
+    // Each frame reserves padding + 2 * extrusion = 4 px on both axes
+    assert_eq!(stats.padding_overhead, 6 * (12 * 8 - 8 * 4));
+    assert_eq!(
+        stats.padding_overhead,
+        stats.lower_bound_area - stats.used_frame_area
+    );
+
+    assert_eq!(stats.pages.len(), stats.num_pages);
+    let frames: usize = stats.pages.iter().map(|p| p.num_frames).sum();
+    assert_eq!(frames, 6);
+    assert_eq!(
+        stats.pages[0].occupancy,
+        result.atlas.pages[0].stats().occupancy
+    );
+
+    // Images carry phase timings, layout-only stats do not
+    let timings = stats.timings.expect("pack_images records timings");
+    assert_eq!(timings.encode_ms, 0);
+    assert!(result.atlas.stats().timings.is_none());
+    let json = serde_json::to_value(result.atlas.stats()).unwrap();
+    assert!(json.get("timings").is_none());
+}
+
+#[test]
+fn test_pack_stats_auto_choice() {
+    let cfg = PackerConfig {
+        max_width: 128,
+        max_height: 128,
+        family: AlgorithmFamily::Auto,
+        ..Default::default()
+    };
+    let inputs = (0..5)
+        .map(|i| InputImage {
+            key: format!("t{i}"),
+            image: DynamicImage::ImageRgba8(RgbaImage::from_pixel(
+                10 + i * 3,
+                7 + i,
+                image::Rgba([255; 4]),
+            )),
+        })
+        .collect();
+    let stats = pack_images(inputs, cfg).unwrap().stats();
+    let choice = stats.winning_config.expect("auto records the winner");
+    assert_ne!(choice.family, AlgorithmFamily::Auto);
+    assert!(!choice.heuristic.is_empty());
 }
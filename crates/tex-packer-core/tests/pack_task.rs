@@ -0,0 +1,87 @@
+mod common;
+
+use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::Duration;
+use tex_packer_core::{
+    AlgorithmFamily, CancellationToken, InputImage, PackControl, PackPhase, PackerConfig,
+    TexPackerError, pack_images, spawn_pack, spawn_pack_with,
+};
+
+fn inputs(n: u32) -> Vec<InputImage> {
+    (0..n)
+        .map(|i| common::solid(format!("s{i:02}"), 10 + i % 5, 12, [i as u8, 40, 0, 255]))
+        .collect()
+}
+
+fn cfg() -> PackerConfig {
+    PackerConfig {
+        max_width: 64,
+        max_height: 64,
+        family: AlgorithmFamily::Skyline,
+        ..Default::default()
+    }
+}
+
+#[test]
+fn spawned_pack_matches_blocking_pack() {
+    let mut handle = spawn_pack(inputs(20), cfg());
+    let out = loop {
+        if let Some(res) = handle.try_result() {
+            break res.unwrap();
+        }
+        std::thread::sleep(Duration::from_millis(1));
+    };
+    assert!(handle.is_finished());
+    assert!(handle.try_result().is_none());
+    let progress = handle.progress().unwrap();
+    assert_eq!(progress.phase, PackPhase::Compose);
+    assert_eq!(progress.done, progress.total);
+
+    let blocking = pack_images(inputs(20), cfg()).unwrap();
+    assert_eq!(out.pages.len(), blocking.pages.len());
+    for (a, b) in out.pages.iter().zip(&blocking.pages) {
+        assert_eq!(a.rgba, b.rgba);
+    }
+}
+
+#[test]
+fn wait_returns_errors() {
+    let handle = spawn_pack(Vec::new(), cfg());
+    assert!(matches!(handle.wait(), Err(TexPackerError::Empty)));
+}
+
+#[test]
+fn external_token_and_observer_are_honoured() {
+    let reports = Arc::new(AtomicUsize::new(0));
+    let seen = Arc::clone(&reports);
+    let token = CancellationToken::new();
+    let trigger = token.clone();
+    let ctl = PackControl::new()
+        .cancel_token(token)
+        .on_progress(move |p| {
+            seen.fetch_add(1, Ordering::Relaxed);
+            if p.phase == PackPhase::Prepare && p.done == 2 {
+                trigger.cancel();
+            }
+        });
+    let cfg = PackerConfig {
+        parallel: false,
+        ..cfg()
+    };
+    let handle = spawn_pack_with(inputs(20), cfg, ctl);
+    assert!(matches!(handle.wait(), Err(TexPackerError::Cancelled)));
+    assert_eq!(reports.load(Ordering::Relaxed), 2);
+}
+
+#[test]
+fn cancel_stops_the_worker() {
+    let handle = spawn_pack(inputs(20), cfg());
+    handle.cancel();
+    assert!(handle.is_cancelled());
+    // Either the worker saw the cancellation or it had already finished.
+    match handle.wait() {
+        Err(TexPackerError::Cancelled) | Ok(_) => {}
+        Err(e) => panic!("unexpected error: {e}"),
+    }
+}
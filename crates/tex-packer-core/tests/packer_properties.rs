@@ -0,0 +1,268 @@
+//! Property tests for layout invariants over arbitrary size sets and configs: every input is
+//! placed once, frames stay inside the page minus border and extrusion, padded slots never
+//! overlap, and rotated frames swap their dimensions.
+
+use proptest::prelude::*;
+use tex_packer_core::config::{Padding, PageBalance};
+use tex_packer_core::prelude::*;
+
+/// How rotated frames report their size: offline layouts give the page-space (transposed)
+/// rect, runtime sessions keep the source size and draw the content transposed.
+#[derive(Clone, Copy)]
+enum Frames {
+    PageSpace,
+    SourceSized,
+}
+
+/// Page-space rect the frame's content covers.
+fn placed(f: &Frame, frames: Frames) -> Rect {
+    match frames {
+        Frames::SourceSized if f.rotated => Rect::new(f.frame.x, f.frame.y, f.frame.h, f.frame.w),
+        _ => f.frame,
+    }
+}
+
+/// Content rect grown by extrusion on every side and by the sprite padding (leading
+/// `left`/`top`, trailing `right`/`bottom`): the slot a packer reserves, minus alignment.
+fn slot(r: &Rect, cfg: &PackerConfig) -> (u32, u32, u32, u32) {
+    let pad = cfg.sprite_padding();
+    let e = cfg.texture_extrusion;
+    (
+        r.x - e - pad.left,
+        r.y - e - pad.top,
+        r.x + r.w + e + pad.right,
+        r.y + r.h + e + pad.bottom,
+    )
+}
+
+fn check_page(page: &Page, cfg: &PackerConfig, frames: Frames) -> Result<(), TestCaseError> {
+    let e = cfg.texture_extrusion;
+    let border = cfg.border_padding;
+    for f in &page.frames {
+        let r = &placed(f, frames);
+        prop_assert!(
+            r.x >= border + e && r.y >= border + e,
+            "{} at {:?} crosses the border/extrusion margin",
+            f.key,
+            r
+        );
+        prop_assert!(
+            r.x + r.w + e <= page.width - border && r.y + r.h + e <= page.height - border,
+            "{} at {:?} leaves the {}x{} page",
+            f.key,
+            r,
+            page.width,
+            page.height
+        );
+        let pad = cfg.sprite_padding();
+        prop_assert!(
+            r.x >= e + pad.left && r.y >= e + pad.top,
+            "{} leaves no room for its leading padding",
+            f.key
+        );
+    }
+    for (i, a) in page.frames.iter().enumerate() {
+        let sa = slot(&placed(a, frames), cfg);
+        for b in &page.frames[i + 1..] {
+            let sb = slot(&placed(b, frames), cfg);
+            let apart = sa.2 <= sb.0 || sb.2 <= sa.0 || sa.3 <= sb.1 || sb.3 <= sa.1;
+            prop_assert!(
+                apart,
+                "padded slots of {} {:?} and {} {:?} overlap",
+                a.key,
+                a.frame,
+                b.key,
+                b.frame
+            );
+        }
+    }
+    Ok(())
+}
+
+fn check_atlas(
+    atlas: &Atlas,
+    sizes: &[(String, u32, u32)],
+    cfg: &PackerConfig,
+    frames: Frames,
+) -> Result<(), TestCaseError> {
+    let all: Vec<&Frame> = atlas.pages.iter().flat_map(|p| &p.frames).collect();
+    prop_assert_eq!(all.len(), sizes.len(), "every input is placed once");
+    for (key, w, h) in sizes {
+        let f = all.iter().find(|f| &f.key == key);
+        prop_assert!(f.is_some(), "{} missing", key);
+        let f = f.unwrap();
+        let r = placed(f, frames);
+        if f.rotated {
+            prop_assert!(cfg.allow_rotation, "{} rotated without allow_rotation", key);
+            prop_assert_eq!((r.w, r.h), (*h, *w), "{} rotated dims", key);
+        } else {
+            prop_assert_eq!((r.w, r.h), (*w, *h), "{} dims", key);
+        }
+    }
+    for page in &atlas.pages {
+        check_page(page, cfg, frames)?;
+    }
+    Ok(())
+}
+
+fn family() -> impl Strategy<Value = PackerConfig> {
+    prop_oneof![
+        prop::sample::select(vec![
+            SkylineHeuristic::BottomLeft,
+            SkylineHeuristic::MinWaste,
+            SkylineHeuristic::BottomLeftWm,
+            SkylineHeuristic::MinWasteWm,
+        ])
+        .prop_map(|h| PackerConfig {
+            family: AlgorithmFamily::Skyline,
+            skyline_heuristic: h,
+            ..Default::default()
+        }),
+        (
+            prop::sample::select(vec![
+                MaxRectsHeuristic::BestAreaFit,
+                MaxRectsHeuristic::BestShortSideFit,
+                MaxRectsHeuristic::BestLongSideFit,
+                MaxRectsHeuristic::BottomLeft,
+                MaxRectsHeuristic::ContactPoint,
+            ]),
+            any::<bool>(),
+            any::<bool>(),
+        )
+            .prop_map(|(h, reference, global)| PackerConfig {
+                family: AlgorithmFamily::MaxRects,
+                mr_heuristic: h,
+                mr_reference: reference,
+                mr_global: global,
+                ..Default::default()
+            }),
+        (
+            prop::sample::select(vec![
+                GuillotineChoice::BestAreaFit,
+                GuillotineChoice::BestShortSideFit,
+                GuillotineChoice::BestLongSideFit,
+                GuillotineChoice::WorstAreaFit,
+            ]),
+            prop::sample::select(vec![
+                GuillotineSplit::SplitShorterLeftoverAxis,
+                GuillotineSplit::SplitLongerLeftoverAxis,
+                GuillotineSplit::SplitMinimizeArea,
+                GuillotineSplit::SplitMaximizeArea,
+                GuillotineSplit::SplitShorterAxis,
+                GuillotineSplit::SplitLongerAxis,
+            ]),
+            any::<bool>(),
+        )
+            .prop_map(|(choice, split, merge)| PackerConfig {
+                family: AlgorithmFamily::Guillotine,
+                g_choice: choice,
+                g_split: split,
+                g_merge: merge,
+                ..Default::default()
+            }),
+    ]
+}
+
+/// Page size, spacing, rotation and (sometimes uneven) padding on top of a packer family.
+fn config() -> impl Strategy<Value = PackerConfig> {
+    (
+        family(),
+        64u32..=256,
+        64u32..=256,
+        0u32..=3,
+        0u32..=4,
+        0u32..=2,
+        any::<bool>(),
+        prop::option::of((0u32..=3, 0u32..=3, 0u32..=3, 0u32..=3)),
+    )
+        .prop_map(
+            |(base, w, h, border, texture_padding, extrude, rotation, uneven)| PackerConfig {
+                max_width: w,
+                max_height: h,
+                border_padding: border,
+                texture_padding,
+                texture_extrusion: extrude,
+                allow_rotation: rotation,
+                padding: uneven.map(|(left, top, right, bottom)| Padding {
+                    left,
+                    top,
+                    right,
+                    bottom,
+                }),
+                trim: false,
+                ..base
+            },
+        )
+}
+
+/// Sizes that fit an empty page of the smallest generated config, so layout never fails.
+fn sizes() -> impl Strategy<Value = Vec<(String, u32, u32)>> {
+    prop::collection::vec((1u32..=40, 1u32..=40), 1..40).prop_map(|v| {
+        v.into_iter()
+            .enumerate()
+            .map(|(i, (w, h))| (format!("s{i}"), w, h))
+            .collect()
+    })
+}
+
+/// 96 cases keep `cargo test` quick; `PROPTEST_CASES` still raises it for deeper runs.
+fn cases() -> u32 {
+    std::env::var("PROPTEST_CASES")
+        .ok()
+        .and_then(|v| v.parse().ok())
+        .unwrap_or(96)
+}
+
+proptest! {
+    #![proptest_config(ProptestConfig::with_cases(cases()))]
+
+    #[test]
+    fn layout_invariants(cfg in config(), sizes in sizes()) {
+        let atlas = pack_layout(sizes.clone(), cfg.clone()).unwrap();
+        check_atlas(&atlas, &sizes, &cfg, Frames::PageSpace)?;
+        let issues = atlas.validate(&cfg);
+        prop_assert!(issues.is_empty(), "{:?}", issues);
+    }
+
+    #[test]
+    fn balanced_and_minimized_layouts_keep_invariants(cfg in config(), sizes in sizes()) {
+        let cfg = PackerConfig {
+            page_balance: PageBalance::Balanced,
+            minimize_page_size: true,
+            ..cfg
+        };
+        let atlas = pack_layout(sizes.clone(), cfg.clone()).unwrap();
+        check_atlas(&atlas, &sizes, &cfg, Frames::PageSpace)?;
+        let issues = atlas.validate(&cfg);
+        prop_assert!(issues.is_empty(), "{:?}", issues);
+    }
+
+    #[test]
+    fn runtime_session_invariants(
+        cfg in config(),
+        sizes in sizes(),
+        strategy in prop_oneof![
+            Just(RuntimeStrategy::Guillotine),
+            Just(RuntimeStrategy::Shelf(ShelfPolicy::NextFit)),
+            Just(RuntimeStrategy::Shelf(ShelfPolicy::FirstFit)),
+            Just(RuntimeStrategy::Skyline(SkylineHeuristic::BottomLeft)),
+            Just(RuntimeStrategy::Skyline(SkylineHeuristic::MinWaste)),
+            Just(RuntimeStrategy::MaxRects(MaxRectsHeuristic::BestAreaFit)),
+        ],
+        evict_every in 2usize..6,
+    ) {
+        let cfg = PackerConfig { padding: None, ..cfg };
+        let mut session = AtlasSession::new(cfg.clone(), strategy);
+        let mut kept = Vec::new();
+        for (i, (key, w, h)) in sizes.iter().enumerate() {
+            session.append(key.clone(), *w, *h).unwrap();
+            kept.push((key.clone(), *w, *h));
+            // Churn: evict some earlier entries so freed space gets reused
+            if i % evict_every == evict_every - 1 {
+                let (gone, ..) = kept.remove(0);
+                prop_assert!(session.evict_by_key(&gone));
+            }
+        }
+        check_atlas(&session.snapshot_atlas(), &kept, &cfg, Frames::SourceSized)?;
+    }
+}
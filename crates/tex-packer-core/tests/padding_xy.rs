@@ -0,0 +1,150 @@
+use tex_packer_core::config::{AlgorithmFamily, Padding};
+use tex_packer_core::{PackerConfig, TexPackerError, pack_layout};
+
+const PAD: Padding = Padding {
+    left: 1,
+    top: 3,
+    right: 5,
+    bottom: 2,
+};
+
+fn sizes() -> Vec<(String, u32, u32)> {
+    (0..24)
+        .map(|i| (format!("s{i}"), 6 + (i * 7) % 23, 4 + (i * 5) % 17))
+        .chain((0..3).map(|i| (format!("tall{i}"), 8, 60)))
+        .collect()
+}
+
+/// Every frame keeps `PAD` to the page edges and reservations never overlap, so neighbours are
+/// at least `left + right` apart horizontally or `top + bottom` vertically.
+fn assert_spacing(family: AlgorithmFamily) {
+    let cfg = PackerConfig {
+        family: family.clone(),
+        max_width: 256,
+        max_height: 64,
+        allow_rotation: true,
+        trim: false,
+        padding: Some(PAD),
+        ..Default::default()
+    };
+    let atlas = pack_layout(sizes(), cfg).unwrap();
+    assert_eq!(atlas.meta.padding.1, 5);
+    let mut rotated = false;
+    for page in &atlas.pages {
+        let reserved: Vec<(u32, u32, u32, u32)> = page
+            .frames
+            .iter()
+            .map(|f| {
+                let r = f.frame;
+                rotated |= f.rotated;
+                assert!(r.x >= PAD.left && r.y >= PAD.top, "{family:?} {}", f.key);
+                assert!(r.x + r.w + PAD.right <= page.width, "{family:?} {}", f.key);
+                assert!(
+                    r.y + r.h + PAD.bottom <= page.height,
+                    "{family:?} {}",
+                    f.key
+                );
+                (
+                    r.x - PAD.left,
+                    r.y - PAD.top,
+                    r.x + r.w + PAD.right,
+                    r.y + r.h + PAD.bottom,
+                )
+            })
+            .collect();
+        for (i, a) in reserved.iter().enumerate() {
+            for b in &reserved[i + 1..] {
+                let apart = a.2 <= b.0 || b.2 <= a.0 || a.3 <= b.1 || b.3 <= a.1;
+                assert!(apart, "{family:?}: {a:?} overlaps {b:?}");
+            }
+        }
+    }
+    // The 60px-tall sprites only fit the 64px page sideways
+    assert!(rotated, "{family:?} never rotated");
+}
+
+#[test]
+fn uneven_padding_holds_for_every_family() {
+    for family in [
+        AlgorithmFamily::Skyline,
+        AlgorithmFamily::MaxRects,
+        AlgorithmFamily::Guillotine,
+        AlgorithmFamily::Shelf,
+    ] {
+        assert_spacing(family);
+    }
+}
+
+#[test]
+fn xy_splits_the_gap_around_frames() {
+    assert_eq!(
+        Padding::xy(5, 2),
+        Padding {
+            left: 2,
+            top: 1,
+            right: 3,
+            bottom: 1
+        }
+    );
+    assert_eq!(Padding::uniform(3), Padding::xy(3, 3));
+    assert_eq!(
+        (Padding::xy(5, 2).horizontal(), Padding::xy(5, 2).vertical()),
+        (5, 2)
+    );
+
+    // Without `padding`, `texture_padding` applies to both axes
+    let cfg = PackerConfig {
+        texture_padding: 4,
+        ..Default::default()
+    };
+    assert_eq!(cfg.sprite_padding(), Padding::uniform(4));
+}
+
+#[test]
+fn padding_deserializes_from_number_axes_or_edges() {
+    let base = serde_json::to_value(PackerConfig::default()).unwrap();
+    let with = |padding: serde_json::Value| {
+        let mut v = base.clone();
+        v["padding"] = padding;
+        serde_json::from_value::<PackerConfig>(v).unwrap().padding
+    };
+    assert_eq!(with(serde_json::json!(3)), Some(Padding::uniform(3)));
+    assert_eq!(
+        with(serde_json::json!({ "x": 4, "y": 2 })),
+        Some(Padding::xy(4, 2))
+    );
+    assert_eq!(
+        with(serde_json::json!({ "left": 1, "top": 3, "right": 5, "bottom": 2 })),
+        Some(PAD)
+    );
+
+    // Configs written before `padding` existed keep using `texture_padding`
+    let mut old = base.clone();
+    old.as_object_mut().unwrap().remove("padding");
+    old["texture_padding"] = serde_json::json!(6);
+    let cfg: PackerConfig = serde_json::from_value(old).unwrap();
+    assert_eq!(cfg.padding, None);
+    assert_eq!(cfg.sprite_padding(), Padding::uniform(6));
+
+    // Round trip keeps the edges
+    let cfg = PackerConfig {
+        padding: Some(PAD),
+        ..Default::default()
+    };
+    let back: PackerConfig = serde_json::from_str(&serde_json::to_string(&cfg).unwrap()).unwrap();
+    assert_eq!(back.padding, Some(PAD));
+}
+
+#[test]
+fn padding_parses_from_strings() {
+    let cfg = PackerConfig::from_str_overrides([("padding", "4,2")]).unwrap();
+    assert_eq!(cfg.padding, Some(Padding::xy(4, 2)));
+    let cfg =
+        PackerConfig::from_str_overrides([("padding", "1,3,5,2"), ("padding", "none")]).unwrap();
+    assert_eq!(cfg.padding, None);
+    assert_eq!("1,3,5,2".parse::<Padding>(), Ok(PAD));
+    assert!(matches!(
+        PackerConfig::from_str_overrides([("padding", "1,2,3")]),
+        Err(TexPackerError::InvalidOption { .. })
+    ));
+}
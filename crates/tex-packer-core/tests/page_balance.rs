@@ -0,0 +1,108 @@
+mod common;
+
+use tex_packer_core::config::{PageBalance, PageGrouping, parse_option};
+use tex_packer_core::prelude::*;
+
+fn inputs(prefix: &str, n: usize, size: u32) -> Vec<InputImage> {
+    (0..n)
+        .map(|i| {
+            common::solid(
+                format!("{prefix}{i:02}.png"),
+                size,
+                size,
+                [0, 128, 255, 255],
+            )
+        })
+        .collect()
+}
+
+fn cfg(balance: PageBalance) -> PackerConfig {
+    PackerConfig {
+        family: AlgorithmFamily::Skyline,
+        page_balance: balance,
+        ..common::tight(128, 128)
+    }
+}
+
+fn frame_counts(out: &PackOutput) -> Vec<usize> {
+    out.atlas.pages.iter().map(|p| p.frames.len()).collect()
+}
+
+#[test]
+fn balanced_spreads_frames_over_same_page_count() {
+    // 16 fill the first 128x128 page; two spill onto a nearly empty second page.
+    let fill = pack_images(inputs("a", 18, 32), cfg(PageBalance::FillFirst)).unwrap();
+    assert_eq!(frame_counts(&fill), vec![16, 2]);
+
+    let bal = pack_images(inputs("a", 18, 32), cfg(PageBalance::Balanced)).unwrap();
+    assert_eq!(frame_counts(&bal), vec![9, 9]);
+    let ids: Vec<usize> = bal.atlas.pages.iter().map(|p| p.id).collect();
+    assert_eq!(ids, vec![0, 1]);
+
+    // The largest page does not grow.
+    let largest = |o: &PackOutput| {
+        o.atlas
+            .pages
+            .iter()
+            .map(|p| p.width * p.height)
+            .max()
+            .unwrap()
+    };
+    assert!(largest(&bal) <= largest(&fill));
+    for (page, rgba) in bal.atlas.pages.iter().zip(&bal.pages) {
+        assert_eq!((page.width, page.height), rgba.rgba.dimensions());
+    }
+}
+
+#[test]
+fn balanced_keeps_every_frame_without_overlap() {
+    let out = pack_images(inputs("a", 23, 32), cfg(PageBalance::Balanced)).unwrap();
+    let total: usize = frame_counts(&out).iter().sum();
+    assert_eq!(total, 23);
+    out.atlas.pages.iter().for_each(common::assert_disjoint);
+}
+
+#[test]
+fn balanced_respects_page_groups() {
+    let mut all = inputs("ui/a", 18, 32);
+    all.extend(inputs("fx/b", 3, 32));
+    let cfg = PackerConfig {
+        page_grouping: PageGrouping::Folder,
+        ..cfg(PageBalance::Balanced)
+    };
+    let out = pack_images(all, cfg).unwrap();
+    // Groups are ordered by name: fx (1 page), then ui balanced over 2 pages.
+    assert_eq!(frame_counts(&out), vec![3, 9, 9]);
+    let ids: Vec<usize> = out.atlas.pages.iter().map(|p| p.id).collect();
+    assert_eq!(ids, vec![0, 1, 2]);
+}
+
+#[test]
+fn page_balance_parses_from_str() {
+    assert_eq!(
+        parse_option::<PageBalance>("page_balance", "balanced").unwrap(),
+        PageBalance::Balanced
+    );
+    let mut c = PackerConfig::default();
+    c.set_str("page_balance", "fill_first").unwrap();
+    assert_eq!(c.page_balance, PageBalance::FillFirst);
+    assert!(c.set_str("page_balance", "even").is_err());
+}
+
+#[test]
+fn balanced_applies_to_layout_only_packs() {
+    let sizes: Vec<(String, u32, u32)> =
+        (0..18).map(|i| (format!("a{i:02}.png"), 32, 32)).collect();
+    let atlas = pack_layout(sizes, cfg(PageBalance::Balanced)).unwrap();
+    let counts: Vec<usize> = atlas.pages.iter().map(|p| p.frames.len()).collect();
+    assert_eq!(counts, vec![9, 9]);
+
+    let images = pack_images(inputs("a", 18, 32), cfg(PageBalance::Balanced)).unwrap();
+    for (layout, image) in atlas.pages.iter().zip(&images.atlas.pages) {
+        assert_eq!((layout.width, layout.height), (image.width, image.height));
+    }
+    assert_eq!(
+        common::placements(&atlas.pages),
+        common::placements(&images.atlas.pages)
+    );
+}
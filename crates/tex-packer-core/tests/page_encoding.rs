@@ -0,0 +1,137 @@
+use image::{Rgba, RgbaImage};
+use tex_packer_core::config::{PageEncoding, PageFormat, parse_option};
+use tex_packer_core::{PackerConfig, TexPackerError, encode_page};
+
+fn page() -> RgbaImage {
+    RgbaImage::from_fn(48, 32, |x, y| {
+        Rgba([
+            (x * 5) as u8,
+            (y * 7) as u8,
+            ((x + y) * 3) as u8,
+            if x < 40 { 255 } else { 0 },
+        ])
+    })
+}
+
+#[test]
+fn webp_lossless_round_trips_exactly() {
+    let bytes = encode_page(&page(), PageFormat::Webp, &PageEncoding::default()).unwrap();
+    assert_eq!(&bytes[..4], b"RIFF");
+    assert_eq!(&bytes[8..12], b"WEBP");
+    let back = image::load_from_memory(&bytes).unwrap().to_rgba8();
+    assert_eq!(back, page());
+}
+
+#[test]
+fn webp_near_lossless_quantizes_colors_only() {
+    let enc = PageEncoding {
+        quality: 50,
+        lossless: false,
+        ..Default::default()
+    };
+    let bytes = encode_page(&page(), PageFormat::Webp, &enc).unwrap();
+    let back = image::load_from_memory(&bytes).unwrap().to_rgba8();
+    for (a, b) in back.pixels().zip(page().pixels()) {
+        // quality 50 drops 2 bits: channels land on multiples of 4, within 2 of the source
+        for c in 0..3 {
+            assert!(a[c] % 4 == 0 || a[c] == 255, "{a:?}");
+            assert!(a[c].abs_diff(b[c]) <= 2, "{a:?} vs {b:?}");
+        }
+        assert_eq!(a[3], b[3]);
+    }
+
+    // Noisy content is where dropping low bits pays off
+    let noise = RgbaImage::from_fn(64, 64, |x, y| {
+        let h = (x.wrapping_mul(73856093) ^ y.wrapping_mul(19349663)).wrapping_mul(2654435761);
+        Rgba([(h >> 8) as u8, (h >> 16) as u8, (h >> 24) as u8, 255])
+    });
+    let lossy = encode_page(&noise, PageFormat::Webp, &enc).unwrap();
+    let lossless = encode_page(&noise, PageFormat::Webp, &PageEncoding::default()).unwrap();
+    assert!(
+        lossy.len() < lossless.len(),
+        "{} vs {}",
+        lossy.len(),
+        lossless.len()
+    );
+}
+
+#[test]
+fn jpeg_and_avif_encode_with_quality() {
+    let low = PageEncoding {
+        quality: 20,
+        ..Default::default()
+    };
+    let high = PageEncoding {
+        quality: 95,
+        ..Default::default()
+    };
+    let jpeg_low = encode_page(&page(), PageFormat::Jpeg, &low).unwrap();
+    let jpeg_high = encode_page(&page(), PageFormat::Jpeg, &high).unwrap();
+    assert_eq!(&jpeg_high[..2], &[0xFF, 0xD8]);
+    assert!(jpeg_low.len() < jpeg_high.len());
+    let back = image::load_from_memory(&jpeg_high).unwrap();
+    assert_eq!((back.width(), back.height()), (48, 32));
+
+    let fast = PageEncoding {
+        speed: 10,
+        ..Default::default()
+    };
+    let avif = encode_page(&page(), PageFormat::Avif, &fast).unwrap();
+    assert_eq!(&avif[4..12], b"ftypavif");
+}
+
+#[test]
+fn formats_parse_and_name_their_files() {
+    for (name, format, ext) in [
+        ("jpeg", PageFormat::Jpeg, "jpg"),
+        ("jpg", PageFormat::Jpeg, "jpg"),
+        ("webp", PageFormat::Webp, "webp"),
+        ("avif", PageFormat::Avif, "avif"),
+    ] {
+        let parsed: PageFormat = parse_option("page_format", name).unwrap();
+        assert_eq!(parsed, format);
+        assert_eq!(parsed.extension(), ext);
+        assert_eq!(parsed.block_size(), 1);
+    }
+    assert!(matches!(
+        encode_page(&page(), PageFormat::Ktx2, &PageEncoding::default()),
+        Err(TexPackerError::InvalidConfig(_))
+    ));
+}
+
+#[test]
+fn encoding_settings_are_validated() {
+    let mut cfg = PackerConfig::default();
+    cfg.set_str("page_encoding.quality", "75").unwrap();
+    cfg.set_str("page_encoding.lossless", "false").unwrap();
+    cfg.set_str("page_encoding.speed", "3").unwrap();
+    cfg.set_str("page_encoding.basis_quality", "200").unwrap();
+    assert_eq!(
+        cfg.page_encoding,
+        PageEncoding {
+            quality: 75,
+            lossless: false,
+            speed: 3,
+            png_optimize: None,
+            basis_quality: 200,
+        }
+    );
+    assert!(cfg.validate().is_ok());
+    cfg.page_encoding.quality = 0;
+    assert!(matches!(
+        cfg.validate(),
+        Err(TexPackerError::InvalidConfig(_))
+    ));
+    cfg.page_encoding.quality = 90;
+    cfg.page_encoding.speed = 11;
+    assert!(matches!(
+        cfg.validate(),
+        Err(TexPackerError::InvalidConfig(_))
+    ));
+    cfg.page_encoding.speed = 6;
+    cfg.page_encoding.basis_quality = 0;
+    assert!(matches!(
+        cfg.validate(),
+        Err(TexPackerError::InvalidConfig(_))
+    ));
+}
@@ -0,0 +1,144 @@
+mod common;
+
+use tex_packer_core::config::PageGrouping;
+use tex_packer_core::{
+    PackerConfig, TexPackerError, pack_images, pack_layout, pack_layout_items, pipeline::LayoutItem,
+};
+
+const GRAY: [u8; 4] = [9, 9, 9, 255];
+
+fn small_pages() -> PackerConfig {
+    PackerConfig {
+        ..common::tight(32, 32)
+    }
+}
+
+#[test]
+fn max_pages_is_enforced() {
+    // Four 20x20 sprites need four 32x32 pages.
+    let inputs = || {
+        (0..4)
+            .map(|i| common::solid(format!("s{i}"), 20, 20, GRAY))
+            .collect()
+    };
+    let ok = pack_images(
+        inputs(),
+        PackerConfig {
+            max_pages: Some(4),
+            ..small_pages()
+        },
+    )
+    .expect("fits in 4 pages");
+    assert_eq!(ok.pages.len(), 4);
+
+    let err = pack_images(
+        inputs(),
+        PackerConfig {
+            max_pages: Some(3),
+            ..small_pages()
+        },
+    )
+    .err()
+    .expect("needs a 4th page");
+    match err {
+        TexPackerError::TooManyPages {
+            scope,
+            max_pages,
+            placed,
+            total,
+        } => {
+            assert_eq!(scope, "atlas");
+            assert_eq!(max_pages, 3);
+            assert_eq!(placed, 3);
+            assert_eq!(total, 4);
+        }
+        other => panic!("unexpected error: {other}"),
+    }
+
+    let layout = pack_layout(
+        (0..4).map(|i| (format!("s{i}"), 20, 20)).collect(),
+        PackerConfig {
+            max_pages: Some(2),
+            ..small_pages()
+        },
+    );
+    assert!(matches!(layout, Err(TexPackerError::TooManyPages { .. })));
+}
+
+#[test]
+fn folder_groups_never_share_pages() {
+    let inputs = vec![
+        common::solid("ui/a", 8, 8, GRAY),
+        common::solid("ui/b", 8, 8, GRAY),
+        common::solid("fx/a", 8, 8, GRAY),
+        common::solid("root", 8, 8, GRAY),
+    ];
+    let cfg = PackerConfig {
+        page_grouping: PageGrouping::Folder,
+        ..small_pages()
+    };
+    let out = pack_images(inputs, cfg).expect("pack");
+    // Groups ordered by name: "" (root), "fx", "ui".
+    let keys: Vec<Vec<String>> = out
+        .atlas
+        .pages
+        .iter()
+        .map(|p| {
+            let mut k: Vec<String> = p.frames.iter().map(|f| f.key.clone()).collect();
+            k.sort();
+            k
+        })
+        .collect();
+    assert_eq!(
+        keys,
+        vec![
+            vec!["root".to_string()],
+            vec!["fx/a".to_string()],
+            vec!["ui/a".to_string(), "ui/b".to_string()],
+        ]
+    );
+    for (i, p) in out.atlas.pages.iter().enumerate() {
+        assert_eq!(p.id, i);
+    }
+}
+
+#[test]
+fn group_max_pages_reports_group() {
+    let items: Vec<LayoutItem<String>> = ["big/a", "big/b", "small/c"]
+        .iter()
+        .map(|k| LayoutItem {
+            key: k.to_string(),
+            w: 20,
+            h: 20,
+            source: None,
+            source_size: None,
+            trimmed: false,
+        })
+        .collect();
+    let cfg = PackerConfig {
+        page_grouping: PageGrouping::Folder,
+        group_max_pages: Some(1),
+        ..small_pages()
+    };
+    match pack_layout_items(items, cfg) {
+        Err(TexPackerError::TooManyPages {
+            scope, max_pages, ..
+        }) => {
+            assert_eq!(scope, "group 'big'");
+            assert_eq!(max_pages, 1);
+        }
+        other => panic!(
+            "expected TooManyPages, got {:?}",
+            other.map(|a| a.pages.len())
+        ),
+    }
+}
+
+#[test]
+fn zero_page_limits_are_rejected() {
+    let cfg = PackerConfig {
+        max_pages: Some(0),
+        ..Default::default()
+    };
+    assert!(cfg.validate().is_err());
+}
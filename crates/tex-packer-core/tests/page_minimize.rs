@@ -0,0 +1,144 @@
+mod common;
+
+use tex_packer_core::prelude::*;
+
+fn inputs(sizes: &[(u32, u32)]) -> Vec<InputImage> {
+    sizes
+        .iter()
+        .enumerate()
+        .map(|(i, &(w, h))| common::solid(format!("tex_{i}"), w, h, [255, 0, 0, 255]))
+        .collect()
+}
+
+fn base_cfg() -> PackerConfig {
+    PackerConfig {
+        allow_rotation: true,
+        family: AlgorithmFamily::Skyline,
+        ..common::tight(512, 512)
+    }
+}
+
+fn sprites() -> Vec<(u32, u32)> {
+    let mut sizes = vec![(40, 200), (200, 40)];
+    sizes.extend(std::iter::repeat_n((30, 30), 12));
+    sizes
+}
+
+fn page_size(out: &PackOutput) -> (u32, u32) {
+    (out.atlas.pages[0].width, out.atlas.pages[0].height)
+}
+
+#[test]
+fn minimize_shrinks_page_and_records_attempts() {
+    let plain = pack_images(inputs(&sprites()), base_cfg()).unwrap();
+    assert!(plain.atlas.meta.page_minimization.is_none());
+    assert_eq!(plain.stats().page_size_attempts, 0);
+
+    let cfg = PackerConfig {
+        minimize_page_size: true,
+        ..base_cfg()
+    };
+    let out = pack_images(inputs(&sprites()), cfg).unwrap();
+    assert_eq!(out.atlas.pages.len(), 1);
+    let (w, h) = page_size(&out);
+    let (w0, h0) = page_size(&plain);
+    assert!(w * h < w0 * h0, "{w0}x{h0} -> {w}x{h}");
+
+    let record = out.atlas.meta.page_minimization.unwrap();
+    assert_eq!(record.initial_size, (w0, h0));
+    assert!(record.attempts > 0);
+    assert_eq!(out.stats().page_size_attempts, record.attempts);
+    assert_eq!(out.atlas.pages[0].frames.len(), sprites().len());
+}
+
+#[test]
+fn minimize_respects_pow2_and_square() {
+    let cfg = PackerConfig {
+        minimize_page_size: true,
+        page_size: PageSizeMode::Pow2,
+        square: true,
+        ..base_cfg()
+    };
+    let out = pack_images(inputs(&sprites()), cfg).unwrap();
+    let (w, h) = page_size(&out);
+    assert_eq!(w, h);
+    assert!(w.is_power_of_two());
+    // 200px-long sprites cannot fit below 256.
+    assert_eq!(w, 256);
+    assert!(out.atlas.meta.page_minimization.is_some());
+}
+
+#[test]
+fn minimize_respects_allowed_sizes() {
+    let cfg = PackerConfig {
+        minimize_page_size: true,
+        page_size: PageSizeMode::AllowedList {
+            sizes: vec![128, 256, 384, 512],
+        },
+        ..base_cfg()
+    };
+    let out = pack_images(inputs(&sprites()), cfg).unwrap();
+    let (w, h) = page_size(&out);
+    assert!([128, 256, 384, 512].contains(&w), "width {w}");
+    assert!([128, 256, 384, 512].contains(&h), "height {h}");
+}
+
+#[test]
+fn minimize_skips_multi_page_and_forced_dimensions() {
+    let multi = PackerConfig {
+        minimize_page_size: true,
+        max_width: 64,
+        max_height: 64,
+        ..base_cfg()
+    };
+    let out = pack_images(inputs(&[(60, 60), (60, 60)]), multi).unwrap();
+    assert_eq!(out.atlas.pages.len(), 2);
+    assert!(out.atlas.meta.page_minimization.is_none());
+
+    let forced = PackerConfig {
+        minimize_page_size: true,
+        page_size: PageSizeMode::Fixed,
+        ..base_cfg()
+    };
+    let out = pack_images(inputs(&sprites()), forced).unwrap();
+    assert_eq!(page_size(&out), (512, 512));
+    assert!(out.atlas.meta.page_minimization.is_none());
+}
+
+#[test]
+fn minimize_applies_to_auto_winner() {
+    let cfg = PackerConfig {
+        minimize_page_size: true,
+        family: AlgorithmFamily::Auto,
+        ..base_cfg()
+    };
+    let out = pack_images(inputs(&sprites()), cfg).unwrap();
+    let record = out.atlas.meta.page_minimization.unwrap();
+    let (w, h) = page_size(&out);
+    assert!(w * h <= record.initial_size.0 * record.initial_size.1);
+}
+
+#[test]
+fn minimize_applies_to_layout_only_packs() {
+    let cfg = PackerConfig {
+        minimize_page_size: true,
+        page_size: PageSizeMode::Pow2,
+        ..base_cfg()
+    };
+    let sizes: Vec<(String, u32, u32)> = sprites()
+        .into_iter()
+        .enumerate()
+        .map(|(i, (w, h))| (format!("tex_{i}"), w, h))
+        .collect();
+    let atlas = pack_layout(sizes, cfg.clone()).unwrap();
+    let images = pack_images(inputs(&sprites()), cfg).unwrap();
+    assert_eq!(
+        (atlas.pages[0].width, atlas.pages[0].height),
+        page_size(&images)
+    );
+    assert!(atlas.pages[0].width.is_power_of_two());
+    let record = atlas.meta.page_minimization.unwrap();
+    let expected = images.atlas.meta.page_minimization.unwrap();
+    assert_eq!(record.initial_size, expected.initial_size);
+    assert_eq!(record.attempts, expected.attempts);
+}
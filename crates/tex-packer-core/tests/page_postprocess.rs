@@ -0,0 +1,82 @@
+mod common;
+
+use image::{Rgba, RgbaImage};
+use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use tex_packer_core::{
+    InputImage, InputImageSet, PackerConfig, pack_image_sets, pack_images, pack_images_iter,
+};
+
+fn inputs() -> Vec<InputImage> {
+    (0..3)
+        .map(|i| common::solid(format!("s{i}"), 20, 20, [10, 20, 30, 255]))
+        .collect()
+}
+
+/// Two 32x32 pages of 20x20 frames, with a hook that stamps the page id into pixel (0, 0).
+fn cfg(calls: &Arc<AtomicUsize>) -> PackerConfig {
+    let calls = calls.clone();
+    PackerConfig::builder()
+        .with_max_dimensions(32, 32)
+        .texture_padding(0)
+        .trim(false)
+        .page_postprocess(move |rgba, page| {
+            calls.fetch_add(1, Ordering::Relaxed);
+            assert_eq!(rgba.dimensions(), (page.width, page.height));
+            rgba.put_pixel(0, 0, Rgba([page.id as u8 + 100, 0, 0, 255]));
+        })
+        .build()
+}
+
+#[test]
+fn hook_sees_every_composited_page() {
+    let calls = Arc::new(AtomicUsize::new(0));
+    let out = pack_images(inputs(), cfg(&calls)).unwrap();
+    assert_eq!(out.pages.len(), 3);
+    assert_eq!(calls.load(Ordering::Relaxed), 3);
+    for p in &out.pages {
+        assert_eq!(p.rgba.get_pixel(0, 0)[0], p.page.id as u8 + 100);
+    }
+
+    // Streaming and low-memory pages run it too
+    let calls = Arc::new(AtomicUsize::new(0));
+    let stream = pack_images_iter(inputs().into_iter().map(Ok), cfg(&calls)).unwrap();
+    let pages: Vec<_> = stream.map(Result::unwrap).collect();
+    assert_eq!(calls.load(Ordering::Relaxed), pages.len());
+    assert_eq!(pages[1].rgba.get_pixel(0, 0)[0], 101);
+
+    let calls = Arc::new(AtomicUsize::new(0));
+    let low = PackerConfig {
+        low_memory: true,
+        ..cfg(&calls)
+    };
+    let out = pack_images(inputs(), low).unwrap();
+    assert_eq!(out.pages[2].pixels().unwrap().get_pixel(0, 0)[0], 102);
+
+    let calls = Arc::new(AtomicUsize::new(0));
+    let sets = vec![InputImageSet {
+        key: "s".into(),
+        layers: vec![inputs().remove(0).image, inputs().remove(1).image],
+    }];
+    let out = pack_image_sets(sets, cfg(&calls)).unwrap();
+    assert_eq!(calls.load(Ordering::Relaxed), 2);
+    assert_eq!(out.layers[1][0].rgba.get_pixel(0, 0)[0], 100);
+}
+
+#[test]
+fn hook_survives_clone_and_json_updates_but_not_resizing() {
+    let calls = Arc::new(AtomicUsize::new(0));
+    let mut cfg = cfg(&calls);
+    cfg.set_json("pivots", &serde_json::json!({})).unwrap();
+    assert!(cfg.clone().page_postprocess.is_some());
+    assert!(cfg.set_str("page_postprocess", "x").is_err());
+    // Skipped by serde
+    let json = serde_json::to_value(&cfg).unwrap();
+    assert!(json.get("page_postprocess").is_none());
+
+    let shrink = PackerConfig::builder()
+        .with_max_dimensions(32, 32)
+        .page_postprocess(|rgba, _| *rgba = RgbaImage::new(1, 1))
+        .build();
+    assert!(pack_images(inputs(), shrink).is_err());
+}
@@ -0,0 +1,223 @@
+mod common;
+
+use image::{Rgba, RgbaImage};
+use tex_packer_core::{
+    PackerConfig, PageSizeMode, VerifyOptions, pack_images, pack_layout, verify_atlas,
+};
+
+fn cfg() -> PackerConfig {
+    PackerConfig {
+        max_width: 2048,
+        max_height: 2048,
+        texture_padding: 0,
+        allow_rotation: false,
+        ..Default::default()
+    }
+}
+
+fn page_size(inputs: Vec<(&str, u32, u32)>, cfg: PackerConfig) -> (u32, u32) {
+    let atlas = pack_layout(inputs, cfg).expect("pack");
+    (atlas.pages[0].width, atlas.pages[0].height)
+}
+
+#[test]
+fn min_dimensions_grow_small_pages() {
+    let cfg = PackerConfig {
+        min_width: 128,
+        min_height: 64,
+        ..cfg()
+    };
+    assert_eq!(page_size(vec![("a", 10, 10)], cfg.clone()), (128, 64));
+    // Content larger than the minimum is unaffected.
+    assert_eq!(page_size(vec![("a", 200, 100)], cfg), (200, 100));
+}
+
+#[test]
+fn allowed_sizes_snap_each_dimension() {
+    let cfg = PackerConfig {
+        page_size: PageSizeMode::AllowedList {
+            sizes: vec![256, 512, 1024, 2048],
+        },
+        ..cfg()
+    };
+    assert_eq!(page_size(vec![("a", 300, 100)], cfg.clone()), (512, 256));
+    assert_eq!(page_size(vec![("a", 256, 1025)], cfg.clone()), (256, 2048));
+
+    // Combined with a minimum and square pages.
+    let cfg = PackerConfig {
+        min_width: 600,
+        square: true,
+        ..cfg
+    };
+    assert_eq!(page_size(vec![("a", 10, 10)], cfg), (1024, 1024));
+}
+
+#[test]
+fn invalid_size_constraints_are_rejected() {
+    let bad_min = PackerConfig {
+        min_width: 4096,
+        ..cfg()
+    };
+    assert!(bad_min.validate().is_err());
+    let max_not_allowed = PackerConfig {
+        page_size: PageSizeMode::AllowedList {
+            sizes: vec![256, 512, 1024],
+        },
+        ..cfg()
+    };
+    assert!(max_not_allowed.validate().is_err());
+}
+
+#[test]
+fn page_size_modes() {
+    let with = |page_size: PageSizeMode| PackerConfig { page_size, ..cfg() };
+    assert_eq!(cfg().page_size, PageSizeMode::TightCrop { margin: 0 });
+    assert_eq!(
+        page_size(vec![("a", 300, 100)], with(PageSizeMode::Pow2)),
+        (512, 128)
+    );
+    assert_eq!(
+        page_size(vec![("a", 300, 100)], with(PageSizeMode::Fixed)),
+        (2048, 2048)
+    );
+    let listed = PageSizeMode::AllowedList {
+        sizes: vec![256, 512, 2048],
+    };
+    assert_eq!(page_size(vec![("a", 300, 100)], with(listed)), (512, 256));
+    let cropped = PageSizeMode::TightCrop { margin: 6 };
+    assert_eq!(
+        page_size(vec![("a", 300, 100)], with(cropped.clone())),
+        (306, 106)
+    );
+    // The margin never pushes a page past the max size.
+    assert_eq!(page_size(vec![("a", 2046, 10)], with(cropped)), (2048, 16));
+}
+
+#[test]
+fn legacy_page_size_keys_deserialize_into_a_mode() {
+    // Configs written before `page_size` existed keep their meaning.
+    let legacy = |keys: serde_json::Value| {
+        let mut json = serde_json::to_value(cfg()).unwrap();
+        let obj = json.as_object_mut().unwrap();
+        obj.remove("page_size");
+        obj.extend(keys.as_object().unwrap().clone());
+        serde_json::from_value::<PackerConfig>(json)
+            .unwrap()
+            .page_size
+    };
+    assert_eq!(
+        legacy(serde_json::json!({ "power_of_two": true })),
+        PageSizeMode::Pow2
+    );
+    assert_eq!(
+        legacy(serde_json::json!({ "power_of_two": true, "force_max_dimensions": true })),
+        PageSizeMode::Fixed
+    );
+    // With `power_of_two`, only the listed powers of two (and the max size) are kept.
+    assert_eq!(
+        legacy(serde_json::json!({ "power_of_two": true, "allowed_sizes": [384, 512, 2048] })),
+        PageSizeMode::AllowedList {
+            sizes: vec![512, 2048]
+        }
+    );
+    assert_eq!(legacy(serde_json::json!({})), PageSizeMode::default());
+    // `page_size` wins over legacy keys, and legacy keys are not written back.
+    let mut json = serde_json::to_value(cfg()).unwrap();
+    json["power_of_two"] = true.into();
+    let c: PackerConfig = serde_json::from_value(json).unwrap();
+    assert_eq!(c.page_size, PageSizeMode::default());
+    let written = serde_json::to_value(&c).unwrap();
+    assert!(written.get("power_of_two").is_none());
+    assert!(written.get("allowed_sizes").is_none());
+
+    // The same keys work one at a time through `set_str`.
+    let mut c = cfg();
+    c.set_str("power_of_two", "true").unwrap();
+    assert_eq!(c.page_size, PageSizeMode::Pow2);
+    c.set_str("force_max_dimensions", "true").unwrap();
+    assert_eq!(c.page_size, PageSizeMode::Fixed);
+    c.set_str("force_max_dimensions", "false").unwrap();
+    assert_eq!(c.page_size, PageSizeMode::default());
+    c.set_json("allowed_sizes", &serde_json::json!([512, 2048]))
+        .unwrap();
+    assert_eq!(
+        c.page_size,
+        PageSizeMode::AllowedList {
+            sizes: vec![512, 2048]
+        }
+    );
+    // Clearing a flag the mode does not stand for keeps the mode, margin included.
+    c.set_str("page_size", "tight_crop:3").unwrap();
+    c.set_str("power_of_two", "false").unwrap();
+    assert_eq!(c.page_size, PageSizeMode::TightCrop { margin: 3 });
+}
+
+#[test]
+fn page_size_mode_serde_and_strings() {
+    let json = serde_json::json!({ "tight_crop": { "margin": 3 } });
+    let mode: PageSizeMode = serde_json::from_value(json.clone()).unwrap();
+    assert_eq!(mode, PageSizeMode::TightCrop { margin: 3 });
+    assert_eq!(serde_json::to_value(&mode).unwrap(), json);
+
+    let mut c = cfg();
+    c.set_str("page_size", "allowed_list:512,1024,2048")
+        .unwrap();
+    assert_eq!(
+        c.page_size,
+        PageSizeMode::AllowedList {
+            sizes: vec![512, 1024, 2048]
+        }
+    );
+    c.set_str("page_size", "tight_crop:2").unwrap();
+    assert_eq!(c.page_size, PageSizeMode::TightCrop { margin: 2 });
+    c.set_json("page_size", &serde_json::json!("pow2")).unwrap();
+    assert_eq!(c.page_size, PageSizeMode::Pow2);
+    assert!(c.set_str("page_size", "pow3").is_err());
+}
+
+#[test]
+fn tight_crop_covers_rotated_and_extruded_frames() {
+    let inputs = || {
+        [(40, 12), (12, 30), (25, 25), (7, 44)]
+            .into_iter()
+            .enumerate()
+            .map(|(i, (w, h))| {
+                common::input(
+                    format!("s{i}"),
+                    RgbaImage::from_fn(w, h, |x, y| Rgba([i as u8 * 40, x as u8, y as u8, 255])),
+                )
+            })
+            .collect()
+    };
+    let mode = |margin| PackerConfig {
+        max_width: 64,
+        max_height: 128,
+        allow_rotation: true,
+        border_padding: 3,
+        texture_padding: 0,
+        texture_extrusion: 2,
+        page_size: PageSizeMode::TightCrop { margin },
+        ..Default::default()
+    };
+    let tight = pack_images(inputs(), mode(0)).unwrap();
+    let page = &tight.atlas.pages[0];
+    assert!(page.frames.iter().any(|f| f.rotated));
+    // The page ends exactly at the furthest extruded frame edge plus the border.
+    let reach = |edge: fn(&tex_packer_core::Frame) -> u32| {
+        page.frames.iter().map(edge).max().unwrap() + 2 + 3
+    };
+    assert_eq!(page.width, reach(|f| f.frame.x + f.frame.w));
+    assert_eq!(page.height, reach(|f| f.frame.y + f.frame.h));
+    let pages: Vec<RgbaImage> = tight.pages.iter().map(|p| p.rgba.clone()).collect();
+    assert_eq!(
+        verify_atlas(&tight.atlas, Some(&pages), VerifyOptions::default()),
+        []
+    );
+
+    let margin = pack_images(inputs(), mode(5)).unwrap();
+    let padded = &margin.atlas.pages[0];
+    assert_eq!(
+        (padded.width, padded.height),
+        ((page.width + 5).min(64), page.height + 5)
+    );
+}
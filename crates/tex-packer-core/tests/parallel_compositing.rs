@@ -0,0 +1,51 @@
+#![cfg(feature = "parallel")]
+
+mod common;
+
+use image::{Rgba, RgbaImage};
+use tex_packer_core::{InputImage, PackerConfig, pack_images};
+
+fn sprite(key: String, w: u32, h: u32, seed: u32) -> InputImage {
+    let img = RgbaImage::from_fn(w, h, |x, y| {
+        let a = if (x + y + seed).is_multiple_of(7) {
+            0
+        } else {
+            255
+        };
+        Rgba([(x * 13 + seed) as u8, (y * 29) as u8, seed as u8, a])
+    });
+    common::input(key, img)
+}
+
+fn inputs() -> Vec<InputImage> {
+    // 24 pages worth of large sprites plus one page of many small frames
+    let big = (0..24).map(|i| sprite(format!("big{i:02}"), 60, 60, i));
+    let small = (0..150).map(|i| sprite(format!("small{i:03}"), 6, 5, i));
+    big.chain(small).collect()
+}
+
+fn cfg(parallel: bool) -> PackerConfig {
+    PackerConfig {
+        max_width: 64,
+        max_height: 64,
+        texture_extrusion: 1,
+        texture_padding: 0,
+        alpha_bleed: Some(1),
+        trim: false,
+        parallel,
+        ..Default::default()
+    }
+}
+
+#[test]
+fn parallel_compositing_matches_sequential() {
+    let seq = pack_images(inputs(), cfg(false)).expect("sequential");
+    let par = pack_images(inputs(), cfg(true)).expect("parallel");
+    assert!(seq.pages.len() >= 20);
+    assert!(par.pages.iter().any(|p| p.page.frames.len() >= 64));
+    assert_eq!(seq.pages.len(), par.pages.len());
+    for (a, b) in seq.pages.iter().zip(&par.pages) {
+        assert_eq!(a.page.id, b.page.id);
+        assert_eq!(a.rgba, b.rgba, "page {}", a.page.id);
+    }
+}
@@ -0,0 +1,72 @@
+#![cfg(feature = "parallel")]
+mod common;
+
+use image::{Rgba, RgbaImage};
+use tex_packer_core::{DuplicateKeyPolicy, InputImage, PackerConfig, pack_images};
+
+/// Sprite with a transparent border whose width depends on `seed`, so trimming matters.
+fn sprite(key: &str, seed: u32) -> InputImage {
+    let border = seed % 4;
+    let img = RgbaImage::from_fn(16 + seed % 9, 12 + seed % 5, |x, y| {
+        let inside = x >= border && y >= border;
+        Rgba([
+            (x * 7 + seed) as u8,
+            (y * 11) as u8,
+            seed as u8,
+            if inside { 255 } else { 0 },
+        ])
+    });
+    common::input(key, img)
+}
+
+fn inputs() -> Vec<InputImage> {
+    // Repeated keys exercise duplicate resolution, which depends on input order.
+    (0..300)
+        .map(|i| sprite(&format!("s{}.png", i % 250), i))
+        .collect()
+}
+
+fn cfg(parallel: bool) -> PackerConfig {
+    PackerConfig {
+        max_width: 256,
+        max_height: 256,
+        duplicate_keys: DuplicateKeyPolicy::Suffix,
+        parallel,
+        ..Default::default()
+    }
+}
+
+#[test]
+fn parallel_prepare_matches_sequential() {
+    let seq = pack_images(inputs(), cfg(false)).expect("sequential");
+    let par = pack_images(inputs(), cfg(true)).expect("parallel");
+    let frames = |out: &tex_packer_core::PackOutput| -> Vec<_> {
+        out.atlas
+            .pages
+            .iter()
+            .flat_map(|p| p.frames.iter())
+            .map(|f| (f.key.clone(), f.frame, f.source, f.trimmed, f.content_hash))
+            .collect()
+    };
+    assert_eq!(frames(&seq).len(), 300);
+    assert!(frames(&seq).iter().any(|f| f.0 == "s10_2.png"));
+    assert_eq!(frames(&seq), frames(&par));
+    for (a, b) in seq.pages.iter().zip(&par.pages) {
+        assert_eq!(a.rgba, b.rgba, "page {}", a.page.id);
+    }
+}
+
+#[test]
+fn parallel_prepare_reports_duplicates_like_sequential() {
+    let err = |parallel| {
+        let cfg = PackerConfig {
+            duplicate_keys: DuplicateKeyPolicy::Error,
+            ..cfg(parallel)
+        };
+        match pack_images(inputs(), cfg) {
+            Err(e) => e.to_string(),
+            Ok(_) => panic!("duplicate keys must fail"),
+        }
+    };
+    assert_eq!(err(false), err(true));
+}
@@ -0,0 +1,151 @@
+mod common;
+
+use image::{Rgba, RgbaImage};
+use std::collections::BTreeMap;
+use tex_packer_core::{
+    KeyTransform, PackerConfig, Pivot, PivotMode, detect_pivot, import_json, import_plist,
+    pack_images, to_json_array, to_json_hash, to_plist_hash,
+};
+
+const RED: [u8; 4] = [255, 0, 0, 255];
+
+fn packed() -> tex_packer_core::PackOutput {
+    let cfg = PackerConfig::builder()
+        .key_transform(KeyTransform::file_stem())
+        .default_pivot(Pivot::BOTTOM_CENTER)
+        .pivots(BTreeMap::from([(
+            "hero".to_string(),
+            Pivot::new(0.25, 0.75),
+        )]))
+        .build();
+    pack_images(
+        vec![
+            common::solid("a/hero.png", 8, 8, RED),
+            common::solid("a/tree.png", 6, 4, RED),
+        ],
+        cfg,
+    )
+    .unwrap()
+}
+
+fn pivot_of(out: &tex_packer_core::PackOutput, key: &str) -> Pivot {
+    out.atlas.pages[0]
+        .frames
+        .iter()
+        .find(|f| f.key == key)
+        .unwrap()
+        .pivot
+}
+
+#[test]
+fn parses_named_and_numeric_pivots() {
+    assert_eq!("center".parse(), Ok(Pivot::CENTER));
+    assert_eq!("Bottom_Center".parse(), Ok(Pivot::BOTTOM_CENTER));
+    assert_eq!("top-left".parse(), Ok(Pivot::TOP_LEFT));
+    assert_eq!("right".parse(), Ok(Pivot::new(1.0, 0.5)));
+    assert_eq!(" 0.25 , 1 ".parse(), Ok(Pivot::new(0.25, 1.0)));
+    assert_eq!("middle".parse::<Pivot>(), Err(()));
+    assert_eq!(Pivot::default(), Pivot::CENTER);
+}
+
+#[test]
+fn frames_get_configured_pivots_by_final_key() {
+    let out = packed();
+    assert_eq!(pivot_of(&out, "hero"), Pivot::new(0.25, 0.75));
+    assert_eq!(pivot_of(&out, "tree"), Pivot::BOTTOM_CENTER);
+}
+
+#[test]
+fn exporters_write_and_importers_read_pivots() {
+    let out = packed();
+    let hash = to_json_hash(&out.atlas);
+    assert_eq!(hash["frames"]["hero"]["pivot"]["x"], 0.25);
+    assert_eq!(hash["frames"]["tree"]["pivot"]["y"], 1.0);
+
+    for text in [to_json_array(&out.atlas).to_string(), hash.to_string()] {
+        let back = import_json(&text).unwrap();
+        let f = |k: &str| {
+            back.atlas.pages[0]
+                .frames
+                .iter()
+                .find(|f| f.key == k)
+                .unwrap()
+                .pivot
+        };
+        assert_eq!(f("hero"), Pivot::new(0.25, 0.75));
+        assert_eq!(f("tree"), Pivot::BOTTOM_CENTER);
+    }
+
+    let plist = to_plist_hash(&out.atlas);
+    assert!(plist.contains("<key>pivot</key><string>{0.25, 0.75}</string>"));
+    let back = import_plist(&plist).unwrap();
+    let hero = back.atlas.pages[0]
+        .frames
+        .iter()
+        .find(|f| f.key == "hero")
+        .unwrap();
+    assert_eq!(hero.pivot, Pivot::new(0.25, 0.75));
+}
+
+/// 10x10 canvas with an opaque 4x2 block at (2, 6) and a single opaque pixel at (7, 1).
+fn character() -> RgbaImage {
+    let mut img = RgbaImage::new(10, 10);
+    for y in 6..8 {
+        for x in 2..6 {
+            img.put_pixel(x, y, Rgba([0, 0, 255, 255]));
+        }
+    }
+    img.put_pixel(7, 1, Rgba([0, 0, 255, 255]));
+    img
+}
+
+#[test]
+fn detects_alpha_centroid_and_feet() {
+    let img = character();
+    // Centroid of pixel centers: x = (8 * 4.0 + 7.5) / 9, y = (4 * 6.5 + 4 * 7.5 + 1.5) / 9.
+    let c = detect_pivot(&img, PivotMode::AlphaCentroid, 0).unwrap();
+    assert!((c.x - 39.5 / 9.0 / 10.0).abs() < 1e-9);
+    assert!((c.y - 57.5 / 9.0 / 10.0).abs() < 1e-9);
+    // Lowest opaque row is y = 7, spanning x = 2..=5: center 4, bottom edge 8.
+    assert_eq!(
+        detect_pivot(&img, PivotMode::BottomCenter, 0),
+        Some(Pivot::new(0.4, 0.8))
+    );
+    assert_eq!(detect_pivot(&img, PivotMode::Manual, 0), None);
+    assert_eq!(
+        detect_pivot(&RgbaImage::new(4, 4), PivotMode::AlphaCentroid, 0),
+        None
+    );
+}
+
+#[test]
+fn pivot_mode_applies_to_frames_without_explicit_pivots() {
+    let inputs = || {
+        vec![
+            common::input("hero", character()),
+            common::input("ghost", RgbaImage::new(4, 4)),
+            common::solid("pinned", 4, 4, RED),
+        ]
+    };
+    let cfg = |mode| {
+        PackerConfig::builder()
+            .pivot_mode(mode)
+            .default_pivot(Pivot::TOP_LEFT)
+            .pivots(BTreeMap::from([(
+                "pinned".to_string(),
+                Pivot::new(0.0, 1.0),
+            )]))
+            .build()
+    };
+
+    let out = pack_images(inputs(), cfg(PivotMode::BottomCenter)).unwrap();
+    assert_eq!(pivot_of(&out, "hero"), Pivot::new(0.4, 0.8));
+    // Nothing opaque to detect: falls back to `default_pivot`.
+    assert_eq!(pivot_of(&out, "ghost"), Pivot::TOP_LEFT);
+    assert_eq!(pivot_of(&out, "pinned"), Pivot::new(0.0, 1.0));
+
+    let out = pack_images(inputs(), cfg(PivotMode::Center)).unwrap();
+    assert_eq!(pivot_of(&out, "hero"), Pivot::CENTER);
+    assert_eq!(pivot_of(&out, "ghost"), Pivot::CENTER);
+    assert_eq!(pivot_of(&out, "pinned"), Pivot::new(0.0, 1.0));
+}
@@ -0,0 +1,43 @@
+#![cfg(feature = "oxipng")]
+
+use image::{Rgba, RgbaImage};
+use tex_packer_core::{PageEncoding, PageFormat, encode_page, optimize_png};
+
+fn page() -> RgbaImage {
+    // Few distinct colors: oxipng can reduce to a palette.
+    RgbaImage::from_fn(128, 128, |x, y| {
+        let c = ((x / 16 + y / 16) % 4) as u8 * 60;
+        Rgba([c, 255 - c, c / 2, 255])
+    })
+}
+
+#[test]
+fn optimized_png_is_smaller_and_lossless() {
+    let plain = encode_page(&page(), PageFormat::Png, &PageEncoding::default()).unwrap();
+    let optimized = optimize_png(&plain, 2).unwrap();
+    assert!(optimized.len() < plain.len());
+    let decoded = image::load_from_memory(&optimized).unwrap().to_rgba8();
+    assert_eq!(decoded, page());
+}
+
+#[test]
+fn encode_page_applies_png_optimize() {
+    let enc = PageEncoding {
+        png_optimize: Some(1),
+        ..Default::default()
+    };
+    let plain = encode_page(&page(), PageFormat::Png, &PageEncoding::default()).unwrap();
+    let optimized = encode_page(&page(), PageFormat::Png, &enc).unwrap();
+    assert!(optimized.len() < plain.len());
+    assert_eq!(&optimized[1..4], b"PNG");
+}
+
+#[test]
+fn png_optimize_level_is_validated() {
+    let mut cfg = tex_packer_core::PackerConfig::default();
+    cfg.set_str("page_encoding.png_optimize", "4").unwrap();
+    assert_eq!(cfg.page_encoding.png_optimize, Some(4));
+    assert!(cfg.validate().is_ok());
+    cfg.page_encoding.png_optimize = Some(7);
+    assert!(cfg.validate().is_err());
+}
@@ -26,7 +26,7 @@ fn pow2_resizes_page_dimensions() {
         .texture_padding(4)
         .texture_extrusion(2)
         .border_padding(5)
-        .pow2(true)
+        .page_size(PageSizeMode::Pow2)
         .build();
     let inputs = vec![("a", 64, 32), ("b", 40, 80), ("c", 10, 10)];
     let atlas = tex_packer_core::pack_layout(inputs, cfg.clone()).expect("pack");
@@ -64,7 +64,7 @@ fn pow2_and_square_combo() {
         .texture_padding(3)
         .texture_extrusion(1)
         .border_padding(7)
-        .pow2(true)
+        .page_size(PageSizeMode::Pow2)
         .square(true)
         .build();
     let inputs = vec![("x", 123, 77), ("y", 200, 20)];
@@ -81,7 +81,7 @@ fn pow2_and_square_combo() {
 fn force_max_dimensions_exact() {
     let cfg = PackerConfig::builder()
         .with_max_dimensions(256, 192)
-        .force_max_dimensions(true)
+        .page_size(PageSizeMode::Fixed)
         .build();
     let inputs = vec![("a", 10, 10)];
     let atlas = tex_packer_core::pack_layout(inputs, cfg.clone()).expect("pack");
@@ -94,7 +94,7 @@ fn force_max_dimensions_exact() {
 fn random_no_overlap_pow2_square() {
     let cfg = PackerConfig::builder()
         .with_max_dimensions(512, 512)
-        .pow2(true)
+        .page_size(PageSizeMode::Pow2)
         .square(true)
         .build();
     let mut rng = rand::rngs::StdRng::seed_from_u64(2024);
@@ -0,0 +1,62 @@
+#![cfg(feature = "psd")]
+
+use image::Rgba;
+use tex_packer_core::decode_psd;
+
+/// A 3x2 RGBA document; the merged image is PackBits-compressed when `rle` is set.
+fn psd(rle: bool) -> Vec<u8> {
+    // Planes: R, G, B, A; the top row is opaque, the bottom row half transparent
+    let planes: [[u8; 6]; 4] = [
+        [255, 255, 255, 255, 128, 0],
+        [0, 0, 0, 255, 128, 0],
+        [0, 0, 0, 255, 255, 0],
+        [255, 255, 255, 128, 128, 0],
+    ];
+    let mut f = Vec::new();
+    f.extend(b"8BPS");
+    f.extend(1u16.to_be_bytes());
+    f.extend([0u8; 6]);
+    f.extend(4u16.to_be_bytes());
+    f.extend(2u32.to_be_bytes());
+    f.extend(3u32.to_be_bytes());
+    f.extend(8u16.to_be_bytes());
+    f.extend(3u16.to_be_bytes());
+    for _ in 0..3 {
+        f.extend(0u32.to_be_bytes());
+    }
+    f.extend((rle as u16).to_be_bytes());
+    let rows: Vec<&[u8]> = planes.iter().flat_map(|p| p.chunks(3)).collect();
+    if rle {
+        let packed: Vec<Vec<u8>> = rows
+            .iter()
+            .map(|r| {
+                if r[0] == r[1] && r[1] == r[2] {
+                    vec![(-2i8) as u8, r[0]]
+                } else {
+                    [&[2u8][..], r].concat()
+                }
+            })
+            .collect();
+        for p in &packed {
+            f.extend((p.len() as u16).to_be_bytes());
+        }
+        f.extend(packed.concat());
+    } else {
+        f.extend(rows.concat());
+    }
+    f
+}
+
+#[test]
+fn decodes_raw_and_rle_composites() {
+    for rle in [false, true] {
+        let img = decode_psd(&psd(rle)).unwrap();
+        assert_eq!(img.dimensions(), (3, 2));
+        assert_eq!(*img.get_pixel(0, 0), Rgba([255, 0, 0, 255]));
+        // White matte removed: (128 - 127) * 255 / 128 rounds down to 1
+        assert_eq!(*img.get_pixel(0, 1), Rgba([255, 255, 255, 128]));
+        assert_eq!(*img.get_pixel(1, 1), Rgba([1, 1, 255, 128]));
+        assert_eq!(img.get_pixel(2, 1)[3], 0);
+    }
+    assert!(decode_psd(b"GIF89a").is_err());
+}
@@ -0,0 +1,193 @@
+mod common;
+
+use image::{Rgba, RgbaImage};
+use tex_packer_core::config::{RotationDirection, RotationPolicy, SpriteOptions, parse_option};
+use tex_packer_core::prelude::*;
+use tex_packer_core::{
+    IssueKind, PackFile, VerifyOptions, extract_frames, require_clockwise, to_json_hash,
+    to_json_hash_conformant, verify_atlas,
+};
+
+/// 3x2 image whose pixels encode their own coordinates.
+fn source() -> RgbaImage {
+    RgbaImage::from_fn(3, 2, |x, y| {
+        Rgba([x as u8 * 50 + 1, y as u8 * 50 + 1, 0, 255])
+    })
+}
+
+fn cfg(direction: RotationDirection) -> PackerConfig {
+    let mut cfg = PackerConfig {
+        max_width: 16,
+        max_height: 16,
+        texture_padding: 0,
+        trim: false,
+        rotation_direction: direction,
+        ..Default::default()
+    };
+    cfg.sprites.insert(
+        "s".into(),
+        SpriteOptions {
+            rotate: Some(RotationPolicy::Always),
+            ..Default::default()
+        },
+    );
+    cfg
+}
+
+fn pack(direction: RotationDirection) -> PackOutput {
+    let input = common::input("s", source());
+    pack_images(vec![input], cfg(direction)).unwrap()
+}
+
+fn pages(out: &PackOutput) -> Vec<RgbaImage> {
+    out.pages.iter().map(|p| p.rgba.clone()).collect()
+}
+
+#[test]
+fn blit_turns_frames_the_configured_way() {
+    for (direction, corner) in [
+        // The source's top-left pixel lands top-right when turned clockwise, bottom-left otherwise
+        (RotationDirection::Clockwise, (1, 0)),
+        (RotationDirection::CounterClockwise, (0, 2)),
+    ] {
+        let out = pack(direction);
+        let f = &out.atlas.pages[0].frames[0];
+        assert!(f.rotated);
+        assert_eq!((f.frame.w, f.frame.h), (2, 3));
+        let page = &out.pages[0].rgba;
+        for y in 0..3 {
+            for x in 0..2 {
+                let (sx, sy) = direction.source_of(x, y, 3, 2);
+                assert_eq!(
+                    page.get_pixel(f.frame.x + x, f.frame.y + y),
+                    source().get_pixel(sx, sy),
+                    "{direction:?} at ({x}, {y})"
+                );
+            }
+        }
+        let (cx, cy) = corner;
+        assert_eq!(
+            page.get_pixel(f.frame.x + cx, f.frame.y + cy),
+            source().get_pixel(0, 0)
+        );
+        assert_eq!(out.atlas.meta.rotation_direction, direction);
+    }
+}
+
+#[test]
+fn extract_and_verify_follow_the_meta() {
+    let out = pack(RotationDirection::CounterClockwise);
+    let pages = pages(&out);
+    let frames = extract_frames(&out.atlas, &pages).unwrap();
+    assert_eq!(frames[0].1, source());
+    assert!(verify_atlas(&out.atlas, Some(&pages), VerifyOptions::default()).is_empty());
+
+    // Read back as clockwise, the content no longer matches its hash
+    let mut atlas = out.atlas.clone();
+    atlas.meta.rotation_direction = RotationDirection::Clockwise;
+    let issues = verify_atlas(&atlas, Some(&pages), VerifyOptions::default());
+    assert_eq!(issues.len(), 1);
+    assert_eq!(issues[0].kind, IssueKind::RotationMismatch);
+}
+
+#[test]
+fn direction_round_trips_through_metadata() {
+    let out = pack(RotationDirection::CounterClockwise);
+    let json = to_json_hash(&out.atlas);
+    assert_eq!(json["meta"]["rotation_direction"], "counter_clockwise");
+    let back = Atlas::from_json_hash(&json.to_string()).unwrap();
+    assert_eq!(
+        back.meta.rotation_direction,
+        RotationDirection::CounterClockwise
+    );
+
+    // Metadata written before the option existed reads as clockwise
+    let mut old = json.clone();
+    old["meta"]
+        .as_object_mut()
+        .unwrap()
+        .remove("rotation_direction");
+    let back = Atlas::from_json_hash(&old.to_string()).unwrap();
+    assert_eq!(back.meta.rotation_direction, RotationDirection::Clockwise);
+}
+
+#[test]
+fn clockwise_only_formats_reject_counter_clockwise_frames() {
+    let ccw = pack(RotationDirection::CounterClockwise);
+    let err = require_clockwise(&ccw.atlas, "gdx").unwrap_err();
+    assert!(err.to_string().contains("frame 's'"), "{err}");
+    assert!(require_clockwise(&pack(RotationDirection::Clockwise).atlas, "gdx").is_ok());
+
+    // Without rotated frames the direction does not matter
+    let mut upright = ccw.atlas.clone();
+    upright.pages[0].frames[0].rotated = false;
+    assert!(require_clockwise(&upright, "gdx").is_ok());
+
+    let files = [PackFile {
+        image: "atlas.png".into(),
+        data: "atlas.json".into(),
+    }];
+    let text = to_json_hash_conformant(&pack(RotationDirection::Clockwise).atlas, 0, &files);
+    let doc: serde_json::Value = serde_json::from_str(&text).unwrap();
+    assert_eq!(doc["frames"]["s"]["rotated"], true);
+}
+
+#[test]
+fn runtime_pages_use_the_direction() {
+    let cfg = PackerConfig::builder()
+        .with_max_dimensions(4, 8)
+        .texture_padding(0)
+        .allow_rotation(true)
+        .rotation_direction(RotationDirection::CounterClockwise)
+        .build();
+    // After a 4x6 block only a 4x2 strip is left, so the 2x3 image has to be rotated
+    let src = RgbaImage::from_fn(2, 3, |x, y| Rgba([x as u8 + 1, y as u8 + 1, 0, 255]));
+    let mut rgba = RuntimeAtlas::new(cfg.clone(), RuntimeStrategy::Guillotine);
+    let mut rg8 = RuntimeAtlas::new(cfg, RuntimeStrategy::Guillotine)
+        .with_pixel_format(RuntimePixelFormat::Rg8);
+    for atlas in [&mut rgba, &mut rg8] {
+        atlas
+            .append_with_image("block".into(), &RgbaImage::new(4, 6))
+            .unwrap();
+        let (_, frame, _) = atlas.append_with_image("r".into(), &src).unwrap();
+        assert!(frame.rotated);
+    }
+    let (page, frame) = rgba.get_frame("r").unwrap();
+    let image = rgba.get_page_image(page).unwrap();
+    for y in 0..2 {
+        for x in 0..3 {
+            let (sx, sy) = RotationDirection::CounterClockwise.source_of(x, y, 2, 3);
+            assert_eq!(
+                image.get_pixel(frame.frame.x + x, frame.frame.y + y),
+                src.get_pixel(sx, sy)
+            );
+        }
+    }
+    let region = UpdateRegion {
+        page_id: page,
+        x: frame.frame.x,
+        y: frame.frame.y,
+        width: frame.frame.h,
+        height: frame.frame.w,
+    };
+    let rg: Vec<u8> = rgba
+        .region_bytes(&region)
+        .unwrap()
+        .chunks(4)
+        .flat_map(|p| [p[0], p[1]])
+        .collect();
+    assert_eq!(rg8.region_bytes(&region).unwrap(), rg);
+}
+
+#[test]
+fn direction_parses() {
+    assert_eq!(
+        parse_option::<RotationDirection>("rotation_direction", "ccw").unwrap(),
+        RotationDirection::CounterClockwise
+    );
+    assert!(parse_option::<RotationDirection>("rotation_direction", "left").is_err());
+    let mut cfg = PackerConfig::default();
+    cfg.set_str("rotation_direction", "counter-clockwise")
+        .unwrap();
+    assert_eq!(cfg.rotation_direction, RotationDirection::CounterClockwise);
+}
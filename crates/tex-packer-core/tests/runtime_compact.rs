@@ -0,0 +1,121 @@
+mod common;
+
+use image::{Rgba, RgbaImage};
+use tex_packer_core::prelude::*;
+
+fn cfg(w: u32, h: u32) -> PackerConfig {
+    PackerConfig::builder()
+        .with_max_dimensions(w, h)
+        .texture_padding(0)
+        .allow_rotation(false)
+        .build()
+}
+
+/// Fills a 64x64 page with 16x16 tiles, then evicts every other one.
+fn fragmented(strategy: RuntimeStrategy) -> AtlasSession {
+    let mut s = AtlasSession::new(cfg(64, 64), strategy);
+    for i in 0..16 {
+        s.append(format!("t{i:02}"), 16, 16).unwrap();
+    }
+    for i in (0..16).step_by(2) {
+        assert!(s.evict_by_key(&format!("t{i:02}")));
+    }
+    s
+}
+
+#[test]
+fn compact_makes_room_for_a_large_entry() {
+    for strategy in [
+        RuntimeStrategy::Guillotine,
+        RuntimeStrategy::Shelf(ShelfPolicy::FirstFit),
+        RuntimeStrategy::Skyline(SkylineHeuristic::BottomLeft),
+    ] {
+        let mut s = fragmented(strategy.clone());
+        let before: Vec<_> = s
+            .keys()
+            .iter()
+            .map(|k| (k.to_string(), s.get_frame(k).unwrap().1.frame))
+            .collect();
+        let moves = s.compact(0);
+        assert!(!moves.is_empty(), "{strategy:?}");
+        assert_eq!(s.texture_count(), 8);
+        common::assert_slots_disjoint(&s);
+        // Moves report exactly the entries whose slot changed, and frames follow their slots.
+        for (key, old_frame) in before {
+            let new_frame = s.get_frame(&key).unwrap().1.frame;
+            let mv = moves.iter().find(|m| m.key == key);
+            match mv {
+                Some(m) => {
+                    assert_eq!(m.old_page, 0);
+                    assert_eq!(m.new_page, 0);
+                    assert_eq!((new_frame.x, new_frame.y), (m.new_rect.x, m.new_rect.y));
+                    assert_eq!((old_frame.x, old_frame.y), (m.old_rect.x, m.old_rect.y));
+                }
+                None => assert_eq!(new_frame, old_frame),
+            }
+        }
+        // Evictions left no 32x32 hole; compaction does.
+        let (page, _) = fragmented(strategy.clone())
+            .append("big".into(), 32, 32)
+            .unwrap();
+        assert_eq!(page, 1, "{strategy:?}");
+        let (page, _) = s.append("big".into(), 32, 32).unwrap();
+        assert_eq!(page, 0, "{strategy:?}");
+    }
+}
+
+#[test]
+fn compact_unknown_page_is_a_no_op() {
+    let mut s = fragmented(RuntimeStrategy::Guillotine);
+    assert!(s.compact(7).is_empty());
+    assert_eq!(s.texture_count(), 8);
+}
+
+#[test]
+fn compact_all_moves_entries_to_lower_pages() {
+    let mut s = AtlasSession::new(cfg(32, 32), RuntimeStrategy::Guillotine);
+    for i in 0..8 {
+        s.append(format!("t{i}"), 16, 16).unwrap();
+    }
+    assert_eq!(s.stats().num_pages, 2);
+    for k in ["t0", "t1", "t2"] {
+        s.evict_by_key(k);
+    }
+    let moves = s.compact_all();
+    assert!(moves.iter().any(|m| m.old_page == 1 && m.new_page == 0));
+    let atlas = s.snapshot_atlas();
+    assert_eq!(atlas.pages[0].frames.len(), 4);
+    assert_eq!(atlas.pages[1].frames.len(), 1);
+    common::assert_slots_disjoint(&s);
+}
+
+#[test]
+fn runtime_atlas_compact_moves_pixels() {
+    let mut atlas = RuntimeAtlas::new(cfg(64, 64), RuntimeStrategy::Guillotine);
+    for i in 0..16u8 {
+        let img = RgbaImage::from_pixel(16, 16, Rgba([i * 10, 255 - i, 7, 255]));
+        atlas.append_with_image(format!("t{i:02}"), &img).unwrap();
+    }
+    for i in (0..16).step_by(2) {
+        atlas
+            .evict_by_key_with_clear(&format!("t{i:02}"), true)
+            .unwrap();
+    }
+    let moves = atlas.compact(0);
+    assert!(!moves.is_empty());
+    let page = atlas.get_page_image(0).unwrap();
+    for i in (1..16u8).step_by(2) {
+        let (_, f) = atlas.get_frame(&format!("t{i:02}")).unwrap();
+        let expected = Rgba([i * 10, 255 - i, 7, 255]);
+        for (x, y) in [(0, 0), (15, 15), (7, 3)] {
+            assert_eq!(
+                *page.get_pixel(f.frame.x + x, f.frame.y + y),
+                expected,
+                "t{i:02}"
+            );
+        }
+    }
+    // Everything outside live frames is background.
+    let live: u32 = page.pixels().filter(|p| p.0[3] != 0).count() as u32;
+    assert_eq!(live, 8 * 16 * 16);
+}
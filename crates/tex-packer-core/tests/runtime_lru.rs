@@ -0,0 +1,111 @@
+use image::{Rgba, RgbaImage};
+use tex_packer_core::prelude::*;
+
+fn cfg(w: u32, h: u32) -> PackerConfig {
+    PackerConfig::builder()
+        .with_max_dimensions(w, h)
+        .texture_padding(0)
+        .allow_rotation(false)
+        .build()
+}
+
+fn budgeted(max_pages: usize) -> AtlasSession {
+    AtlasSession::new(cfg(32, 32), RuntimeStrategy::Guillotine).with_budget(RuntimeBudget {
+        max_pages: Some(max_pages),
+        max_pixels: None,
+    })
+}
+
+#[test]
+fn evicts_least_recently_used_when_full() {
+    let mut s = budgeted(1);
+    for i in 0..4 {
+        s.append(format!("t{i}"), 16, 16).unwrap();
+    }
+    assert!(s.touch("t0"));
+    s.append("new".into(), 16, 16).unwrap();
+
+    assert_eq!(s.stats().num_pages, 1);
+    let evicted = s.drain_evicted();
+    assert_eq!(evicted.len(), 1);
+    assert_eq!(evicted[0].key, "t1");
+    assert!(!s.contains("t1"));
+    assert!(s.contains("t0") && s.contains("new"));
+    assert!(s.drain_evicted().is_empty());
+}
+
+#[test]
+fn evicts_as_many_as_needed() {
+    let mut s = budgeted(1);
+    for i in 0..4 {
+        s.append(format!("t{i}"), 16, 16).unwrap();
+    }
+    s.append("big".into(), 32, 32).unwrap();
+    let keys: Vec<String> = s.drain_evicted().into_iter().map(|e| e.key).collect();
+    assert_eq!(keys, ["t0", "t1", "t2", "t3"]);
+    assert_eq!(s.keys(), ["big"]);
+}
+
+#[test]
+fn oversized_entry_evicts_nothing() {
+    let mut s = budgeted(1);
+    s.append("a".into(), 16, 16).unwrap();
+    assert!(s.append("huge".into(), 64, 64).is_err());
+    assert!(s.contains("a"));
+    assert!(s.drain_evicted().is_empty());
+}
+
+#[test]
+fn pixel_budget_limits_pages() {
+    let mut s =
+        AtlasSession::new(cfg(32, 32), RuntimeStrategy::Guillotine).with_budget(RuntimeBudget {
+            max_pages: None,
+            max_pixels: Some(2 * 32 * 32),
+        });
+    for i in 0..3 {
+        s.append(format!("t{i}"), 32, 32).unwrap();
+    }
+    assert_eq!(s.stats().num_pages, 2);
+    assert_eq!(s.drain_evicted()[0].key, "t0");
+}
+
+#[test]
+fn unbudgeted_session_grows() {
+    let mut s = AtlasSession::new(cfg(32, 32), RuntimeStrategy::Guillotine);
+    for i in 0..3 {
+        s.append(format!("t{i}"), 32, 32).unwrap();
+    }
+    assert_eq!(s.stats().num_pages, 3);
+    assert!(s.drain_evicted().is_empty());
+    assert!(!s.touch("missing"));
+}
+
+#[test]
+fn runtime_atlas_clears_evicted_slots() {
+    let mut atlas =
+        RuntimeAtlas::new(cfg(32, 32), RuntimeStrategy::Guillotine).with_budget(RuntimeBudget {
+            max_pages: Some(1),
+            max_pixels: None,
+        });
+    let red = RgbaImage::from_pixel(32, 16, Rgba([255, 0, 0, 255]));
+    let blue = RgbaImage::from_pixel(32, 16, Rgba([0, 0, 255, 255]));
+    atlas.append_with_image("a".into(), &red).unwrap();
+    atlas.append_with_image("b".into(), &red).unwrap();
+    atlas.touch("a");
+    atlas.append_with_image("c".into(), &blue).unwrap();
+
+    let evicted = atlas.drain_evicted();
+    assert_eq!(evicted.len(), 1);
+    assert_eq!(evicted[0].key, "b");
+    let (_, frame) = atlas.get_frame("c").unwrap();
+    let page = atlas.get_page_image(0).unwrap();
+    assert_eq!(
+        *page.get_pixel(frame.frame.x, frame.frame.y),
+        Rgba([0, 0, 255, 255])
+    );
+    let (_, a) = atlas.get_frame("a").unwrap();
+    assert_eq!(
+        *page.get_pixel(a.frame.x, a.frame.y),
+        Rgba([255, 0, 0, 255])
+    );
+}
@@ -0,0 +1,157 @@
+mod common;
+
+use tex_packer_core::prelude::*;
+
+const HEURISTICS: [MaxRectsHeuristic; 5] = [
+    MaxRectsHeuristic::BestAreaFit,
+    MaxRectsHeuristic::BestShortSideFit,
+    MaxRectsHeuristic::BestLongSideFit,
+    MaxRectsHeuristic::BottomLeft,
+    MaxRectsHeuristic::ContactPoint,
+];
+
+fn cfg(w: u32, h: u32) -> PackerConfig {
+    PackerConfig::builder()
+        .with_max_dimensions(w, h)
+        .texture_padding(0)
+        .allow_rotation(false)
+        .build()
+}
+
+#[test]
+fn fills_page_exactly() {
+    for h in HEURISTICS {
+        let mut s = AtlasSession::new(cfg(64, 64), RuntimeStrategy::MaxRects(h.clone()));
+        for i in 0..16 {
+            let (page, _) = s.append(format!("t{i}"), 16, 16).unwrap();
+            assert_eq!(page, 0, "{h:?}");
+        }
+        common::assert_slots_disjoint(&s);
+        assert_eq!(s.stats().total_free_area, 0);
+    }
+}
+
+#[test]
+fn evicted_neighbours_merge_into_larger_space() {
+    for h in HEURISTICS {
+        let mut s = AtlasSession::new(cfg(64, 64), RuntimeStrategy::MaxRects(h.clone()));
+        for i in 0..16 {
+            s.append(format!("t{i}"), 16, 16).unwrap();
+        }
+        // Free a 2x2 block of tiles wherever t0 landed
+        let (_, origin) = s.get_reserved_slot("t0").unwrap();
+        let block: Vec<String> = s
+            .keys()
+            .into_iter()
+            .filter(|k| {
+                let (_, r) = s.get_reserved_slot(k).unwrap();
+                r.x >= origin.x && r.x < origin.x + 32 && r.y >= origin.y && r.y < origin.y + 32
+            })
+            .map(str::to_string)
+            .collect();
+        assert_eq!(block.len(), 4, "{h:?}");
+        for k in &block {
+            assert!(s.evict_by_key(k));
+        }
+        let (page, _) = s.append("big".into(), 32, 32).unwrap();
+        assert_eq!(page, 0, "{h:?}: freed 32x32 block was not reused");
+        assert_eq!(s.stats().num_pages, 1);
+        common::assert_slots_disjoint(&s);
+    }
+}
+
+#[test]
+fn churn_stays_disjoint() {
+    let cfg = PackerConfig::builder()
+        .with_max_dimensions(256, 256)
+        .texture_padding(2)
+        .texture_extrusion(1)
+        .allow_rotation(true)
+        .build();
+    let mut s = AtlasSession::new(
+        cfg,
+        RuntimeStrategy::MaxRects(MaxRectsHeuristic::BestShortSideFit),
+    );
+    let mut seed = 7u32;
+    let mut next = || {
+        seed = seed.wrapping_mul(1_103_515_245).wrapping_add(12_345);
+        (seed >> 16) % 40 + 4
+    };
+    for round in 0..6 {
+        for i in 0..40 {
+            let (w, h) = (next(), next());
+            s.append(format!("r{round}_{i}"), w, h).unwrap();
+        }
+        for i in (0..40).step_by(3) {
+            s.evict_by_key(&format!("r{round}_{i}"));
+        }
+        common::assert_slots_disjoint(&s);
+    }
+}
+
+#[test]
+fn compaction_and_runtime_atlas_support_maxrects() {
+    let mut atlas = RuntimeAtlas::new(
+        cfg(64, 64),
+        RuntimeStrategy::MaxRects(MaxRectsHeuristic::BestAreaFit),
+    );
+    let img = image::RgbaImage::from_pixel(16, 16, image::Rgba([9, 9, 9, 255]));
+    for i in 0..8 {
+        atlas.append_with_image(format!("t{i}"), &img).unwrap();
+    }
+    for i in (0..8).step_by(2) {
+        atlas
+            .evict_by_key_with_clear(&format!("t{i}"), true)
+            .unwrap();
+    }
+    atlas.compact_all();
+    for key in atlas.keys() {
+        let (page, frame) = atlas.get_frame(key).unwrap();
+        let px = atlas
+            .get_page_image(page)
+            .unwrap()
+            .get_pixel(frame.frame.x, frame.frame.y);
+        assert_eq!(px.0, [9, 9, 9, 255]);
+    }
+}
+
+#[test]
+fn eviction_replays_identically() {
+    let run = || {
+        let mut s = AtlasSession::new(
+            cfg(128, 128),
+            RuntimeStrategy::MaxRects(MaxRectsHeuristic::BestShortSideFit),
+        );
+        let mut seed = 1u32;
+        let mut next = || {
+            seed = seed.wrapping_mul(1_103_515_245).wrapping_add(12_345);
+            (seed >> 16) % 24 + 4
+        };
+        for i in 0..60 {
+            let (w, h) = (next(), next());
+            s.append(format!("a{i}"), w, h).unwrap();
+        }
+        for i in (0..60).step_by(3) {
+            assert!(s.evict_by_key(&format!("a{i}")));
+        }
+        for i in 0..20 {
+            let (w, h) = (next(), next());
+            s.append(format!("b{i}"), w, h).unwrap();
+        }
+        let mut frames: Vec<(String, usize, Rect)> = s
+            .keys()
+            .into_iter()
+            .map(|k| {
+                let (page, f) = s.get_frame(k).unwrap();
+                (k.to_string(), page, f.frame)
+            })
+            .collect();
+        frames.sort_by(|a, b| a.0.cmp(&b.0));
+        frames
+    };
+    // Every session hashes its keys differently, so repeated runs cover several orders
+    let first = run();
+    for _ in 0..8 {
+        assert_eq!(run(), first);
+    }
+}
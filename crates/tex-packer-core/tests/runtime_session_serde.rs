@@ -0,0 +1,140 @@
+use image::{Rgba, RgbaImage};
+use tex_packer_core::TexPackerError;
+use tex_packer_core::prelude::*;
+
+fn cfg() -> PackerConfig {
+    PackerConfig::builder()
+        .with_max_dimensions(128, 128)
+        .texture_padding(2)
+        .texture_extrusion(1)
+        .allow_rotation(true)
+        .build()
+}
+
+fn strategies() -> Vec<RuntimeStrategy> {
+    vec![
+        RuntimeStrategy::Guillotine,
+        RuntimeStrategy::Shelf(ShelfPolicy::FirstFit),
+        RuntimeStrategy::Skyline(SkylineHeuristic::MinWaste),
+        RuntimeStrategy::MaxRects(MaxRectsHeuristic::ContactPoint),
+    ]
+}
+
+fn sizes(round: u32) -> impl Iterator<Item = (String, u32, u32)> {
+    (0..12).map(move |i| {
+        (
+            format!("r{round}_{i}"),
+            8 + (i * 7 + round * 3) % 30,
+            6 + (i * 5) % 24,
+        )
+    })
+}
+
+fn placements(s: &AtlasSession) -> Vec<(String, usize, Rect, bool)> {
+    let mut out: Vec<_> = s
+        .keys()
+        .into_iter()
+        .map(|k| {
+            let (page, f) = s.get_frame(k).unwrap();
+            (k.to_string(), page, f.frame, f.rotated)
+        })
+        .collect();
+    out.sort_by(|a, b| a.0.cmp(&b.0));
+    out
+}
+
+#[test]
+fn restored_session_continues_identically() {
+    for strategy in strategies() {
+        let mut live = AtlasSession::new(cfg(), strategy.clone());
+        for (k, w, h) in sizes(0) {
+            live.append(k, w, h).unwrap();
+        }
+        for i in (0..12).step_by(3) {
+            live.evict_by_key(&format!("r0_{i}"));
+        }
+
+        let json = serde_json::to_string(&live).unwrap();
+        let mut restored: AtlasSession = serde_json::from_str(&json).unwrap();
+        assert_eq!(placements(&restored), placements(&live), "{strategy:?}");
+
+        for (k, w, h) in sizes(1) {
+            let a = live.append(k.clone(), w, h).unwrap();
+            let b = restored.append(k, w, h).unwrap();
+            assert_eq!((a.0, a.1.frame, a.1.rotated), (b.0, b.1.frame, b.1.rotated));
+        }
+        assert_eq!(restored.stats().num_free_rects, live.stats().num_free_rects);
+    }
+}
+
+#[test]
+fn budget_and_lru_order_survive_round_trip() {
+    let mut live =
+        AtlasSession::new(cfg(), RuntimeStrategy::Guillotine).with_budget(RuntimeBudget {
+            max_pages: Some(1),
+            max_pixels: None,
+        });
+    for i in 0..4 {
+        live.append(format!("t{i}"), 60, 60).unwrap();
+    }
+    live.touch("t0");
+    let mut restored: AtlasSession =
+        serde_json::from_str(&serde_json::to_string(&live).unwrap()).unwrap();
+    restored.append("new".into(), 60, 60).unwrap();
+    assert_eq!(restored.drain_evicted()[0].key, "t1");
+}
+
+#[test]
+fn runtime_atlas_restores_from_session_and_pages() {
+    let mut atlas = RuntimeAtlas::new(cfg(), RuntimeStrategy::Guillotine)
+        .with_pixel_format(RuntimePixelFormat::A8);
+    let img = RgbaImage::from_pixel(10, 10, Rgba([0, 0, 0, 200]));
+    atlas.append_with_image("a".into(), &img).unwrap();
+
+    let json = serde_json::to_string(atlas.session()).unwrap();
+    let pages: Vec<Vec<u8>> = (0..atlas.num_pages())
+        .map(|p| atlas.page_bytes(p).unwrap().to_vec())
+        .collect();
+
+    let session: AtlasSession = serde_json::from_str(&json).unwrap();
+    let mut restored =
+        RuntimeAtlas::restore(session, RuntimePixelFormat::A8, pages.clone()).unwrap();
+    assert_eq!(restored.page_bytes(0).unwrap(), &pages[0][..]);
+    assert!(restored.contains("a"));
+    let (_, _, region) = restored.append_with_image("b".into(), &img).unwrap();
+    assert!(
+        restored
+            .region_bytes(&region)
+            .unwrap()
+            .iter()
+            .all(|&a| a == 200)
+    );
+
+    let session: AtlasSession = serde_json::from_str(&json).unwrap();
+    assert!(RuntimeAtlas::restore(session, RuntimePixelFormat::Rgba8, pages).is_err());
+}
+
+#[test]
+fn runtime_atlas_restore_needs_every_page() {
+    let mut atlas = RuntimeAtlas::new(cfg(), RuntimeStrategy::Guillotine)
+        .with_pixel_format(RuntimePixelFormat::A8);
+    for key in ["a", "b"] {
+        // Each 100x100 image needs its own 128x128 page
+        let img = RgbaImage::from_pixel(100, 100, Rgba([0, 0, 0, 200]));
+        atlas.append_with_image(key.into(), &img).unwrap();
+    }
+    assert_eq!(atlas.num_pages(), 2);
+    let json = serde_json::to_string(atlas.session()).unwrap();
+    let mut pages: Vec<Vec<u8>> = (0..2)
+        .map(|p| atlas.page_bytes(p).unwrap().to_vec())
+        .collect();
+
+    for count in [1, 3] {
+        pages.resize(count, pages[0].clone());
+        let session: AtlasSession = serde_json::from_str(&json).unwrap();
+        match RuntimeAtlas::restore(session, RuntimePixelFormat::A8, pages.clone()) {
+            Err(TexPackerError::InvalidInput(msg)) => assert!(msg.contains("2 pages"), "{msg}"),
+            other => panic!("expected InvalidInput, got {:?}", other.map(|_| ())),
+        }
+    }
+}
@@ -0,0 +1,92 @@
+#![cfg(feature = "schemars")]
+mod common;
+
+use serde_json::Value;
+use tex_packer_core::schema::{atlas_array_schema, atlas_hash_schema, config_schema};
+use tex_packer_core::{
+    JsonOptions, NinePatch, PackerConfig, SpriteOptions, pack_images, to_json_array_with,
+    to_json_hash_with,
+};
+
+const UVS: JsonOptions = JsonOptions {
+    uvs: true,
+    uv_inset: 0.0,
+};
+
+fn properties<'a>(schema: &'a Value, def: Option<&str>) -> &'a serde_json::Map<String, Value> {
+    let node = match def {
+        Some(name) => &schema["$defs"][name],
+        None => schema,
+    };
+    node["properties"].as_object().expect("object schema")
+}
+
+fn assert_described(value: &Value, props: &serde_json::Map<String, Value>, what: &str) {
+    for key in value.as_object().unwrap().keys() {
+        assert!(
+            props.contains_key(key),
+            "{what}: `{key}` missing from schema"
+        );
+    }
+}
+
+fn sample_atlas() -> tex_packer_core::Atlas {
+    let inputs = vec![common::solid("panel.png", 8, 8, [1, 2, 3, 255])];
+    let cfg = PackerConfig::builder()
+        .sprite(
+            "panel.png",
+            SpriteOptions {
+                nine_patch: Some(NinePatch {
+                    left: 1,
+                    top: 1,
+                    right: 1,
+                    bottom: 1,
+                }),
+                ..Default::default()
+            },
+        )
+        .build();
+    pack_images(inputs, cfg).unwrap().atlas
+}
+
+#[test]
+fn config_schema_covers_every_field() {
+    let schema = config_schema();
+    let cfg = serde_json::to_value(PackerConfig::default()).unwrap();
+    assert_described(&cfg, properties(&schema, None), "PackerConfig");
+    assert!(schema["$defs"]["Pivot"]["oneOf"].is_array());
+}
+
+#[test]
+fn hash_schema_covers_exported_frames() {
+    let schema = atlas_hash_schema();
+    let doc = to_json_hash_with(&sample_atlas(), &UVS);
+    let frame = &doc["frames"]["panel.png"];
+    assert_described(
+        frame,
+        properties(&schema, Some("HashFrame")),
+        "json-hash frame",
+    );
+    assert_described(&doc["meta"], properties(&schema, Some("Meta")), "meta");
+    let required = schema["$defs"]["HashFrame"]["required"].as_array().unwrap();
+    assert!(required.contains(&"pageSize".into()));
+    assert!(!required.contains(&"ninePatch".into()));
+    assert!(!required.contains(&"uv".into()));
+}
+
+#[test]
+fn array_schema_covers_exported_frames() {
+    let schema = atlas_array_schema();
+    let doc = to_json_array_with(&sample_atlas(), &UVS);
+    let page = &doc["pages"][0];
+    assert_described(
+        page,
+        properties(&schema, Some("ArrayPage")),
+        "json-array page",
+    );
+    assert_described(
+        &page["frames"][0],
+        properties(&schema, Some("ArrayFrame")),
+        "json-array frame",
+    );
+}
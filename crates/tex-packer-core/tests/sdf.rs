@@ -0,0 +1,152 @@
+#![cfg(feature = "sdf")]
+mod common;
+
+use image::{DynamicImage, ImageBuffer, Rgba, RgbaImage};
+use tex_packer_core::config::{SdfMode, SdfOptions};
+use tex_packer_core::{
+    InputImageSet, PackerConfig, generate_sdf, pack_image_sets, pack_images, sdf_margin,
+    to_json_hash,
+};
+
+/// 12x12 image with an opaque 4x4 square at (4, 4).
+fn square() -> RgbaImage {
+    RgbaImage::from_fn(12, 12, |x, y| {
+        if (4..8).contains(&x) && (4..8).contains(&y) {
+            Rgba([255; 4])
+        } else {
+            Rgba([0; 4])
+        }
+    })
+}
+
+fn opts(mode: SdfMode, range: u32) -> SdfOptions {
+    SdfOptions {
+        mode,
+        range,
+        ..Default::default()
+    }
+}
+
+#[test]
+fn single_channel_field_encodes_signed_distance() {
+    let sdf = generate_sdf(&square(), &opts(SdfMode::Single, 4));
+    assert_eq!(sdf_margin(&opts(SdfMode::Single, 5)), 3);
+    // Grown by range / 2 per side
+    assert_eq!(sdf.dimensions(), (16, 16));
+    let row: Vec<u8> = (0..16).map(|x| sdf.get_pixel(x, 7)[3]).collect();
+    // The square now spans 6..10; first pixels each side of the edge straddle 0.5
+    assert_eq!(row[5], 96);
+    assert_eq!(row[6], 159);
+    assert_eq!(row[7], 223);
+    assert_eq!(row[3], 0, "{row:?}");
+    assert_eq!(
+        row[..8],
+        row[8..].iter().rev().copied().collect::<Vec<_>>()[..]
+    );
+    // Every channel carries the same field
+    assert!(
+        sdf.pixels()
+            .all(|p| p[0] == p[3] && p[1] == p[3] && p[2] == p[3])
+    );
+    // Diagonal distances are Euclidean: the outside corner neighbor is sqrt(2) away
+    assert_eq!(sdf.get_pixel(5, 5)[3], 69);
+
+    assert_eq!(
+        generate_sdf(&RgbaImage::new(0, 3), &opts(SdfMode::Single, 4)).dimensions(),
+        (0, 3)
+    );
+}
+
+#[test]
+fn multi_channel_fields_are_independent() {
+    // Red covers the left half of the square, green the right half, blue all of it
+    let src = RgbaImage::from_fn(12, 12, |x, y| {
+        let inside = (4..8).contains(&x) && (4..8).contains(&y);
+        let r = if inside && x < 6 { 255 } else { 0 };
+        let g = if inside && x >= 6 { 255 } else { 0 };
+        Rgba([
+            r,
+            g,
+            if inside { 255 } else { 0 },
+            if inside { 255 } else { 0 },
+        ])
+    });
+    let msdf = generate_sdf(&src, &opts(SdfMode::Multi, 4));
+    let single = generate_sdf(&src, &opts(SdfMode::Single, 4));
+    let left = msdf.get_pixel(6, 8);
+    assert!(left[0] > 128 && left[1] < 128, "{left:?}");
+    let right = msdf.get_pixel(9, 8);
+    assert!(right[0] < 128 && right[1] > 128, "{right:?}");
+    for (m, s) in msdf.pixels().zip(single.pixels()) {
+        assert_eq!((m[2], m[3]), (s[3], s[3]));
+    }
+
+    // Colored but transparent pixels stay outside
+    let ghost = ImageBuffer::from_pixel(4, 4, Rgba([255, 255, 255, 0]));
+    let field = generate_sdf(&ghost, &opts(SdfMode::Multi, 2));
+    assert!(field.pixels().all(|p| p.0 == [0; 4]));
+}
+
+#[test]
+fn packing_converts_inputs_and_records_the_range() {
+    let cfg = PackerConfig {
+        max_width: 64,
+        max_height: 64,
+        sdf: Some(opts(SdfMode::Single, 4)),
+        ..Default::default()
+    };
+    let input = common::input("sq", square());
+    let out = pack_images(vec![input], cfg.clone()).unwrap();
+    let f = &out.atlas.pages[0].frames[0];
+    // Trimming keeps the distance band around the 4x4 square: 4 + 2 * 2 pixels
+    assert_eq!(f.source_size, (16, 16));
+    assert_eq!((f.frame.w, f.frame.h), (8, 8));
+    let page = &out.pages[0].rgba;
+    let expected = generate_sdf(&square(), &opts(SdfMode::Single, 4));
+    for y in 0..8 {
+        for x in 0..8 {
+            assert_eq!(
+                page.get_pixel(f.frame.x + x, f.frame.y + y),
+                expected.get_pixel(f.source.x + x, f.source.y + y)
+            );
+        }
+    }
+
+    let json = to_json_hash(&out.atlas);
+    assert_eq!(json["meta"]["sdf"]["mode"], "single");
+    assert_eq!(json["meta"]["sdf"]["range"], 4);
+    let plain = pack_images(
+        vec![common::input("sq", square())],
+        PackerConfig { sdf: None, ..cfg },
+    )
+    .unwrap();
+    assert!(to_json_hash(&plain.atlas)["meta"].get("sdf").is_none());
+}
+
+#[test]
+fn options_parse_and_validate() {
+    let mut cfg = PackerConfig::default();
+    cfg.set_str("sdf.range", "6").unwrap();
+    assert_eq!(cfg.sdf, Some(opts(SdfMode::Single, 6)));
+    cfg.set_str("sdf", "msdf").unwrap();
+    assert_eq!(cfg.sdf, Some(opts(SdfMode::Multi, 6)));
+    assert!(cfg.set_str("sdf", "psdf").is_err());
+    cfg.set_str("sdf", "none").unwrap();
+    assert_eq!(cfg.sdf, None);
+
+    let bad = PackerConfig {
+        sdf: Some(opts(SdfMode::Single, 0)),
+        ..Default::default()
+    };
+    assert!(bad.validate().is_err());
+
+    let sets = vec![InputImageSet {
+        key: "sq".into(),
+        layers: vec![DynamicImage::ImageRgba8(square())],
+    }];
+    let cfg = PackerConfig {
+        sdf: Some(SdfOptions::default()),
+        ..Default::default()
+    };
+    assert!(pack_image_sets(sets, cfg).is_err());
+}
@@ -0,0 +1,114 @@
+mod common;
+
+use tex_packer_core::model::PackerChoice;
+use tex_packer_core::packer::Packer;
+use tex_packer_core::packer::shelf::ShelfPacker;
+use tex_packer_core::prelude::*;
+
+fn cfg(heuristic: ShelfHeuristic) -> PackerConfig {
+    PackerConfig {
+        family: AlgorithmFamily::Shelf,
+        shelf_heuristic: heuristic,
+        sort_order: SortOrder::HeightDesc,
+        ..common::tight(128, 128)
+    }
+}
+
+fn pack(p: &mut ShelfPacker, w: u32, h: u32) -> Option<Rect> {
+    <ShelfPacker as Packer<String>>::pack(p, String::new(), &Rect::new(0, 0, w, h)).map(|f| f.frame)
+}
+
+#[test]
+fn uniform_height_sprites_fill_rows() {
+    // 20 glyphs of 30x16: four per 128px row, rows stacked every 16px.
+    let inputs: Vec<InputImage> = (0..20)
+        .map(|i| common::solid(format!("glyph_{i:02}"), 30, 16, [255; 4]))
+        .collect();
+    let out = pack_images(inputs, cfg(ShelfHeuristic::FirstFit)).unwrap();
+    assert_eq!(out.atlas.pages.len(), 1);
+    let page = &out.atlas.pages[0];
+    assert_eq!(page.frames.len(), 20);
+    for f in &page.frames {
+        assert_eq!(f.frame.y % 16, 0, "{:?}", f.frame);
+        assert_eq!(f.frame.x % 30, 0, "{:?}", f.frame);
+    }
+    assert_eq!((page.width, page.height), (120, 80));
+}
+
+#[test]
+fn heuristics_pick_different_shelves() {
+    // Shelves: 40px (x used 100), 20px (x used 100), then a 10px-tall 20px-wide rect.
+    let layout = |h: ShelfHeuristic| {
+        let mut p = ShelfPacker::new(cfg(h), h);
+        pack(&mut p, 100, 40).unwrap();
+        pack(&mut p, 100, 20).unwrap();
+        pack(&mut p, 20, 10).unwrap()
+    };
+    // First fit: the first shelf with room.
+    assert_eq!(layout(ShelfHeuristic::FirstFit), Rect::new(100, 0, 20, 10));
+    // Best height fit: the 20px shelf wastes less height.
+    assert_eq!(
+        layout(ShelfHeuristic::BestHeightFit),
+        Rect::new(100, 40, 20, 10)
+    );
+    // Next fit: only the newest shelf is open.
+    assert_eq!(layout(ShelfHeuristic::NextFit), Rect::new(100, 40, 20, 10));
+
+    // Next fit never returns to a closed shelf.
+    let mut p = ShelfPacker::new(cfg(ShelfHeuristic::NextFit), ShelfHeuristic::NextFit);
+    pack(&mut p, 100, 40).unwrap();
+    pack(&mut p, 60, 20).unwrap(); // closes the 40px shelf
+    assert_eq!(pack(&mut p, 20, 20), Some(Rect::new(60, 40, 20, 20)));
+    assert_eq!(pack(&mut p, 80, 30), Some(Rect::new(0, 60, 80, 30)));
+}
+
+#[test]
+fn rotation_fits_a_shelf_and_page_bounds_hold() {
+    let mut c = cfg(ShelfHeuristic::FirstFit);
+    c.allow_rotation = true;
+    let mut p = ShelfPacker::new(c.clone(), c.shelf_heuristic);
+    pack(&mut p, 100, 30).unwrap();
+    // 30x20 does not fit beside (28px left); rotated 20x30 does.
+    let f = <ShelfPacker as Packer<String>>::pack(&mut p, "r".into(), &Rect::new(0, 0, 30, 20))
+        .unwrap();
+    // Open shelves are tried before a new one is opened.
+    assert!(f.rotated);
+    assert_eq!(f.frame, Rect::new(100, 0, 20, 30));
+    // Full page: nothing taller than what remains fits.
+    assert!(pack(&mut p, 128, 200).is_none());
+}
+
+#[test]
+fn shelf_is_selectable_and_in_auto_portfolio() {
+    assert_eq!(
+        tex_packer_core::config::parse_option::<AlgorithmFamily>("algorithm", "shelf").unwrap(),
+        AlgorithmFamily::Shelf
+    );
+    let choice = PackerChoice::from_config(&cfg(ShelfHeuristic::BestHeightFit));
+    assert_eq!(choice.heuristic, "BestHeightFit");
+
+    // Uniform-height rows are exactly what the shelf candidate is for.
+    let inputs: Vec<InputImage> = (0..16)
+        .map(|i| common::solid(format!("g{i:02}"), 32, 16, [255; 4]))
+        .collect();
+    let auto = PackerConfig {
+        family: AlgorithmFamily::Auto,
+        auto_mode: AutoMode::Quality,
+        deterministic: true,
+        ..cfg(ShelfHeuristic::FirstFit)
+    };
+    let out = pack_images(inputs, auto).unwrap();
+    assert_eq!(out.atlas.pages.len(), 1);
+    let used: u32 = out.atlas.pages[0]
+        .frames
+        .iter()
+        .map(|f| f.frame.w * f.frame.h)
+        .sum();
+    let page = &out.atlas.pages[0];
+    assert_eq!(
+        used,
+        page.width * page.height,
+        "{:?}",
+        out.atlas.meta.packer
+    );
+}
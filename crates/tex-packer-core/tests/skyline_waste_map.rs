@@ -33,14 +33,17 @@ fn make_cfg(use_waste_map: bool) -> PackerConfig {
         max_width: 2048,
         max_height: 2048,
         allow_rotation: true,
-        force_max_dimensions: false,
         border_padding: 0,
         texture_padding: 0,
+        padding: None,
+        extrude_mode: Default::default(),
+        align: 0,
+        align_size: false,
         texture_extrusion: 0,
         trim: false,
         trim_threshold: 0,
+        trim_options: Default::default(),
         texture_outlines: false,
-        power_of_two: false,
         square: false,
         use_waste_map,
         family: AlgorithmFamily::Skyline,
@@ -48,14 +51,53 @@ fn make_cfg(use_waste_map: bool) -> PackerConfig {
         skyline_heuristic: SkylineHeuristic::MinWaste,
         g_choice: tex_packer_core::config::GuillotineChoice::BestAreaFit,
         g_split: tex_packer_core::config::GuillotineSplit::SplitShorterLeftoverAxis,
+        g_merge: true,
+        shelf_heuristic: Default::default(),
+        grid_cell_width: 0,
+        grid_cell_height: 0,
         auto_mode: tex_packer_core::config::AutoMode::Quality,
         sort_order: SortOrder::AreaDesc,
         time_budget_ms: None,
         parallel: false,
+        deterministic: false,
+        low_memory: false,
+        key_transform: Default::default(),
+        duplicate_keys: Default::default(),
+        pivot_mode: Default::default(),
+        default_pivot: Default::default(),
+        pivots: Default::default(),
+        sprites: Default::default(),
+        animations: Default::default(),
+        minimize_page_size: false,
         mr_reference: false,
+        mr_global: false,
         auto_mr_ref_time_ms_threshold: None,
         auto_mr_ref_input_threshold: None,
         transparent_policy: tex_packer_core::config::TransparentPolicy::Keep,
+        on_unplaceable: Default::default(),
+        sdf: Default::default(),
+        mip_levels: Default::default(),
+        page_postprocess: None,
+        custom_packer: None,
+        auto_candidates: Vec::new(),
+        auto_objective: Default::default(),
+        auto_score: None,
+        auto_refine: false,
+        page_size: Default::default(),
+        rotation_direction: Default::default(),
+        min_width: 0,
+        min_height: 0,
+        max_pages: None,
+        page_grouping: tex_packer_core::config::PageGrouping::None,
+        group_max_pages: None,
+        page_balance: Default::default(),
+        frame_order: Default::default(),
+        alpha_bleed: None,
+        background_color: None,
+        page_format: tex_packer_core::config::PageFormat::Png,
+        bcn_format: tex_packer_core::config::BcnFormat::Bc7,
+        basis_codec: Default::default(),
+        page_encoding: Default::default(),
     }
 }
 
@@ -125,3 +167,78 @@ fn skyline_waste_map_improves_or_equal_occupancy() {
         occ_plain
     );
 }
+
+fn pack_all(cfg: &PackerConfig, rects: &[(u32, u32)]) -> (SkylinePacker, Vec<Frame>) {
+    let mut packer = SkylinePacker::new(cfg.clone());
+    let mut frames = Vec::new();
+    for (idx, &(w, h)) in rects.iter().enumerate() {
+        let r = Rect::new(0, 0, w, h);
+        match <SkylinePacker as Packer<String>>::pack(&mut packer, format!("r{idx}"), &r) {
+            Some(f) => frames.push(f),
+            None => break,
+        }
+    }
+    (packer, frames)
+}
+
+#[test]
+fn wm_heuristics_enable_waste_map_per_variant() {
+    // The 120px bar rests on the 50px post, leaving a 100x40 gap above the first slab that
+    // only a waste map can hand back to the 30px squares.
+    let rects = [(100, 10), (20, 50), (120, 20), (30, 30), (30, 30)];
+
+    for (heuristic, expect_map) in [
+        (SkylineHeuristic::BottomLeft, false),
+        (SkylineHeuristic::MinWaste, false),
+        (SkylineHeuristic::BottomLeftWm, true),
+        (SkylineHeuristic::MinWasteWm, true),
+    ] {
+        let cfg = PackerConfig {
+            max_width: 128,
+            max_height: 256,
+            allow_rotation: false,
+            skyline_heuristic: heuristic.clone(),
+            ..make_cfg(false)
+        };
+        let (packer, frames) = pack_all(&cfg, &rects);
+        assert!(disjoint(&frames), "{heuristic:?}");
+        let stats = <SkylinePacker as Packer<String>>::waste_map_stats(&packer);
+        assert_eq!(stats.is_some(), expect_map, "{heuristic:?}");
+        if let Some(s) = stats {
+            assert_eq!(s.recovered_area, 100 * 40, "{heuristic:?}");
+            assert_eq!(s.placements, 2, "{heuristic:?}: {s:?}");
+            assert!(s.placed_area <= s.recovered_area);
+            assert!((0.0..=1.0).contains(&s.occupancy()));
+        }
+    }
+
+    // `use_waste_map` still forces the map on for a plain heuristic.
+    let forced = PackerConfig {
+        max_width: 128,
+        allow_rotation: false,
+        skyline_heuristic: SkylineHeuristic::BottomLeft,
+        ..make_cfg(true)
+    };
+    let (packer, _) = pack_all(&forced, &rects);
+    assert!(<SkylinePacker as Packer<String>>::waste_map_stats(&packer).is_some());
+}
+
+#[test]
+fn wm_heuristics_parse_and_describe() {
+    use tex_packer_core::config::parse_option;
+    use tex_packer_core::model::PackerChoice;
+
+    let h: SkylineHeuristic = parse_option("skyline", "minwaste_wm").unwrap();
+    assert_eq!(h, SkylineHeuristic::MinWasteWm);
+    assert_eq!(h.base(), SkylineHeuristic::MinWaste);
+    assert_eq!(
+        parse_option::<SkylineHeuristic>("skyline", "bl_wm").unwrap(),
+        SkylineHeuristic::BottomLeftWm
+    );
+
+    let choice = PackerChoice::from_config(&PackerConfig {
+        skyline_heuristic: SkylineHeuristic::BottomLeft,
+        ..make_cfg(true)
+    });
+    assert_eq!(choice.heuristic, "BottomLeftWm");
+}
@@ -0,0 +1,353 @@
+mod common;
+
+use image::{Rgba, RgbaImage};
+use std::collections::BTreeMap;
+use tex_packer_core::config::RotationPolicy;
+use tex_packer_core::{
+    NinePatch, PackOutput, PackerConfig, Pivot, Rect, SpriteOptions, TexPackerError, import_gdx,
+    import_json, pack_images, pack_layout, to_gdx_atlas, to_json_hash,
+};
+
+fn base() -> tex_packer_core::PackerConfigBuilder {
+    PackerConfig::builder()
+        .with_max_dimensions(128, 128)
+        .trim(false)
+        .allow_rotation(false)
+}
+
+fn frame<'a>(out: &'a PackOutput, key: &str) -> &'a tex_packer_core::Frame {
+    out.atlas
+        .pages
+        .iter()
+        .flat_map(|p| &p.frames)
+        .find(|f| f.key == key)
+        .unwrap()
+}
+
+fn gap(a: &Rect, b: &Rect) -> u32 {
+    let dx = (b.x.saturating_sub(a.x + a.w)).max(a.x.saturating_sub(b.x + b.w));
+    let dy = (b.y.saturating_sub(a.y + a.h)).max(a.y.saturating_sub(b.y + b.h));
+    dx.max(dy)
+}
+
+#[test]
+fn excluded_sprites_are_left_out() {
+    let cfg = base()
+        .sprite(
+            "b",
+            SpriteOptions {
+                exclude: true,
+                ..Default::default()
+            },
+        )
+        .build();
+    let out = pack_images(
+        vec![
+            common::solid("a", 8, 8, [1, 0, 0, 255]),
+            common::solid("b", 8, 8, [2, 0, 0, 255]),
+        ],
+        cfg.clone(),
+    )
+    .unwrap();
+    let keys: Vec<_> = out.atlas.pages[0].frames.iter().map(|f| &f.key).collect();
+    assert_eq!(keys, ["a"]);
+
+    let atlas = pack_layout(vec![("a", 8, 8), ("b", 8, 8)], cfg).unwrap();
+    assert_eq!(atlas.pages[0].frames.len(), 1);
+}
+
+#[test]
+fn forced_and_forbidden_rotation() {
+    let rotate = |v| SpriteOptions {
+        rotate: Some(v),
+        ..Default::default()
+    };
+    let out = pack_images(
+        vec![
+            common::solid("tall", 10, 20, [1, 0, 0, 255]),
+            common::solid("free", 10, 20, [2, 0, 0, 255]),
+        ],
+        base()
+            .sprite("tall", rotate(RotationPolicy::Always))
+            .build(),
+    )
+    .unwrap();
+    let tall = frame(&out, "tall");
+    assert!(tall.rotated);
+    assert_eq!((tall.frame.w, tall.frame.h), (20, 10));
+    assert!(!frame(&out, "free").rotated);
+
+    // A 12x40 sprite only fits a 64x16 page rotated.
+    let narrow = || base().with_max_dimensions(64, 16).allow_rotation(true);
+    let out = pack_images(
+        vec![common::solid("s", 12, 40, [1, 0, 0, 255])],
+        narrow().build(),
+    )
+    .unwrap();
+    assert!(frame(&out, "s").rotated);
+    let Err(err) = pack_images(
+        vec![common::solid("s", 12, 40, [1, 0, 0, 255])],
+        narrow().sprite("s", rotate(RotationPolicy::Never)).build(),
+    ) else {
+        panic!("rotation was forbidden");
+    };
+    assert!(matches!(err, TexPackerError::OutOfSpaceGeneric { .. }));
+
+    // `never` holds with rotation wanted globally, also through the portfolio's replayed
+    // layouts; `auto` follows it
+    let cfg = base()
+        .with_max_dimensions(40, 64)
+        .allow_rotation(true)
+        .family(tex_packer_core::AlgorithmFamily::Auto)
+        .sprite("panel", rotate(RotationPolicy::Never))
+        .sprite("free", rotate(RotationPolicy::Auto))
+        .build();
+    let inputs = vec![
+        common::solid("panel", 30, 8, [1, 0, 0, 255]),
+        common::solid("free", 30, 8, [2, 0, 0, 255]),
+    ];
+    let out = pack_images(inputs, cfg.clone()).unwrap();
+    assert!(!frame(&out, "panel").rotated);
+    assert!(out.atlas.validate(&cfg).is_empty());
+
+    // Atlas::validate reports frames that contradict their policy
+    let mut atlas = out.atlas.clone();
+    for f in &mut atlas.pages[0].frames {
+        if f.key == "panel" {
+            f.rotated = true;
+            f.frame = Rect::new(f.frame.x, f.frame.y, f.frame.h, f.frame.w);
+        }
+    }
+    assert!(
+        atlas
+            .validate(&cfg)
+            .iter()
+            .any(|i| i.key.as_deref() == Some("panel")
+                && i.kind == tex_packer_core::IssueKind::RotationPolicy { expected: false })
+    );
+}
+
+#[test]
+fn per_sprite_padding_and_extrusion_reserve_space() {
+    let cfg = base()
+        .texture_padding(2)
+        .sprite(
+            "wide",
+            SpriteOptions {
+                texture_padding: Some(8),
+                texture_extrusion: Some(3),
+                ..Default::default()
+            },
+        )
+        .build();
+    let inputs = vec![
+        common::solid("wide", 20, 20, [200, 0, 0, 255]),
+        common::solid("a", 20, 20, [1, 0, 0, 255]),
+        common::solid("b", 20, 20, [2, 0, 0, 255]),
+    ];
+    let out = pack_images(inputs, cfg).unwrap();
+    let wide = frame(&out, "wide").frame;
+    // 3px extrusion on both sides plus 8px padding between the extruded edges.
+    assert!(wide.x >= 3 && wide.y >= 3, "{wide:?}");
+    for other in ["a", "b"] {
+        assert!(gap(&wide, &frame(&out, other).frame) >= 3 + 8, "{other}");
+    }
+    let page = &out.pages[0];
+    assert!(page.page.width >= wide.x + wide.w + 3);
+    assert!(page.page.height >= wide.y + wide.h + 3);
+    // Extruded pixels repeat the sprite's edge.
+    let edge = *page.rgba.get_pixel(wide.x, wide.y);
+    assert_eq!(*page.rgba.get_pixel(wide.x - 3, wide.y), edge);
+    assert_eq!(*page.rgba.get_pixel(wide.x + wide.w + 2, wide.y), edge);
+}
+
+#[test]
+fn sprite_groups_never_share_a_page() {
+    let group = |g: &str| SpriteOptions {
+        group: Some(g.into()),
+        ..Default::default()
+    };
+    let cfg = base()
+        .sprites(BTreeMap::from([
+            ("hero".to_string(), group("chars")),
+            ("enemy".to_string(), group("chars")),
+        ]))
+        .build();
+    let out = pack_images(
+        vec![
+            common::solid("hero", 8, 8, [1, 0, 0, 255]),
+            common::solid("enemy", 8, 8, [2, 0, 0, 255]),
+            common::solid("tree", 8, 8, [3, 0, 0, 255]),
+        ],
+        cfg,
+    )
+    .unwrap();
+    assert_eq!(out.pages.len(), 2);
+    let page_of = |k: &str| {
+        out.atlas
+            .pages
+            .iter()
+            .position(|p| p.frames.iter().any(|f| f.key == k))
+            .unwrap()
+    };
+    assert_eq!(page_of("hero"), page_of("enemy"));
+    assert_ne!(page_of("hero"), page_of("tree"));
+}
+
+#[test]
+fn pivot_and_nine_patch_reach_exporters() {
+    let nine = NinePatch {
+        left: 2,
+        top: 3,
+        right: 4,
+        bottom: 5,
+    };
+    let cfg = base()
+        .pivots(BTreeMap::from([("panel".to_string(), Pivot::TOP_LEFT)]))
+        .sprite(
+            "panel",
+            SpriteOptions {
+                pivot: Some(Pivot::BOTTOM_CENTER),
+                nine_patch: Some(nine),
+                ..Default::default()
+            },
+        )
+        .build();
+    let out = pack_images(
+        vec![
+            common::solid("panel", 16, 16, [1, 0, 0, 255]),
+            common::solid("x", 4, 4, [2, 0, 0, 255]),
+        ],
+        cfg,
+    )
+    .unwrap();
+    let panel = frame(&out, "panel");
+    assert_eq!(panel.pivot, Pivot::BOTTOM_CENTER);
+    assert_eq!(panel.nine_patch, Some(nine));
+    assert_eq!(frame(&out, "x").nine_patch, None);
+
+    let hash = to_json_hash(&out.atlas);
+    assert_eq!(hash["frames"]["panel"]["ninePatch"]["bottom"], 5);
+    assert!(hash["frames"]["x"].get("ninePatch").is_none());
+    let back = import_json(&hash.to_string()).unwrap();
+    let f = back.atlas.pages[0]
+        .frames
+        .iter()
+        .find(|f| f.key == "panel")
+        .unwrap();
+    assert_eq!(f.nine_patch, Some(nine));
+
+    let gdx = to_gdx_atlas(&out.atlas, &["atlas.png".into()]);
+    assert!(gdx.contains("  split: 2, 4, 3, 5\n"));
+    let back = import_gdx(&gdx).unwrap();
+    assert_eq!(back.atlas.pages[0].frames[0].nine_patch, Some(nine));
+}
+
+#[test]
+fn sprite_options_deserialize_from_manifest_style_values() {
+    let sprites: BTreeMap<String, SpriteOptions> = serde_json::from_str(
+        r#"{
+            "hero": {"padding": 4, "extrusion": 1, "pivot": "bottom-center", "rotate": false},
+            "panel": {"pivot": [0.25, 0.5], "nine_patch": {"left": 1, "top": 1, "right": 1, "bottom": 1}},
+            "tmp": {"exclude": true, "group": "misc"}
+        }"#,
+    )
+    .unwrap();
+    assert_eq!(sprites["hero"].texture_padding, Some(4));
+    assert_eq!(sprites["hero"].texture_extrusion, Some(1));
+    assert_eq!(sprites["hero"].pivot, Some(Pivot::BOTTOM_CENTER));
+    assert_eq!(sprites["hero"].rotate, Some(RotationPolicy::Never));
+    assert_eq!(sprites["panel"].pivot, Some(Pivot::new(0.25, 0.5)));
+    assert!(sprites["tmp"].exclude);
+    assert_eq!(sprites["tmp"].group.as_deref(), Some("misc"));
+}
+
+#[test]
+fn rotation_policy_accepts_names_and_bools() {
+    let sprites: BTreeMap<String, SpriteOptions> = serde_json::from_str(
+        r#"{"a": {"rotate": "always"}, "b": {"rotate": "never"}, "c": {"rotate": "auto"},
+            "d": {"rotate": true}}"#,
+    )
+    .unwrap();
+    let policy = |k: &str| sprites[k].rotate.unwrap();
+    assert_eq!(policy("a"), RotationPolicy::Always);
+    assert_eq!(policy("b"), RotationPolicy::Never);
+    assert_eq!(policy("c"), RotationPolicy::Auto);
+    assert_eq!(policy("d"), RotationPolicy::Always);
+    let json = serde_json::to_value(SpriteOptions {
+        rotate: Some(RotationPolicy::Never),
+        ..Default::default()
+    })
+    .unwrap();
+    assert_eq!(json["rotate"], "never");
+    let err = serde_json::from_str::<SpriteOptions>(r#"{"rotate": "sideways"}"#).unwrap_err();
+    assert!(err.to_string().contains("auto, always, never"), "{err}");
+
+    // An explicit `auto` overrides an inherited `never`
+    let folder = SpriteOptions {
+        rotate: Some(RotationPolicy::Never),
+        ..Default::default()
+    };
+    let own = SpriteOptions {
+        rotate: Some(RotationPolicy::Auto),
+        ..Default::default()
+    };
+    assert_eq!(own.inherit(&folder).rotate, Some(RotationPolicy::Auto));
+    assert_eq!(
+        SpriteOptions::default().inherit(&folder).rotate,
+        folder.rotate
+    );
+}
+
+#[test]
+fn per_sprite_trim_overrides_global_trim() {
+    let padded = || {
+        let mut img = RgbaImage::new(16, 16);
+        for y in 4..8 {
+            for x in 4..8 {
+                img.put_pixel(x, y, Rgba([9, 9, 9, 255]));
+            }
+        }
+        img
+    };
+    let inputs = vec![
+        common::input("keep", padded()),
+        common::input("cut", padded()),
+    ];
+    let cfg = base()
+        .sprite(
+            "cut",
+            SpriteOptions {
+                trim: Some(true),
+                ..Default::default()
+            },
+        )
+        .build();
+    let out = pack_images(inputs, cfg).unwrap();
+    assert!(!frame(&out, "keep").trimmed);
+    let cut = frame(&out, "cut");
+    assert!(cut.trimmed);
+    assert_eq!((cut.frame.w, cut.frame.h), (4, 4));
+}
+
+#[test]
+fn inherit_fills_unset_fields_from_parent() {
+    let parent = SpriteOptions {
+        texture_padding: Some(4),
+        trim: Some(false),
+        group: Some("ui".into()),
+        exclude: true,
+        ..Default::default()
+    };
+    let child = SpriteOptions {
+        texture_padding: Some(1),
+        pivot: Some(Pivot::TOP_LEFT),
+        ..Default::default()
+    };
+    let merged = child.inherit(&parent);
+    assert_eq!(merged.texture_padding, Some(1));
+    assert_eq!(merged.trim, Some(false));
+    assert_eq!(merged.group.as_deref(), Some("ui"));
+    assert_eq!(merged.pivot, Some(Pivot::TOP_LEFT));
+    assert!(merged.exclude);
+}
@@ -0,0 +1,137 @@
+#![cfg(feature = "templates")]
+
+use tex_packer_core::{
+    Atlas, AtlasTemplate, PackerConfig, TemplateScope, builtin_template, pack_layout,
+};
+
+fn atlas() -> Atlas {
+    let sizes = (0..12)
+        .map(|i| (format!("ui/icon_{i}.png"), 20, 12 + i))
+        .collect::<Vec<_>>();
+    let cfg = PackerConfig {
+        max_width: 64,
+        max_height: 64,
+        allow_rotation: false,
+        ..Default::default()
+    };
+    pack_layout(sizes, cfg).unwrap()
+}
+
+fn names(atlas: &Atlas) -> Vec<String> {
+    (0..atlas.pages.len())
+        .map(|i| format!("atlas_{i}.png"))
+        .collect()
+}
+
+#[test]
+fn builtin_templates_render_valid_json() {
+    let atlas = atlas();
+    assert!(atlas.pages.len() > 1);
+    for engine in [
+        "unity",
+        "godot",
+        "phaser3",
+        "phaser3_single",
+        "cocos",
+        "unreal",
+    ] {
+        let tpl = AtlasTemplate::parse(builtin_template(engine).unwrap()).unwrap();
+        let files = tpl.render(&atlas, &names(&atlas), "atlas").unwrap();
+        assert_eq!(files.len(), 1);
+        assert_eq!(files[0].path, None);
+        let value: serde_json::Value = serde_json::from_str(&files[0].contents)
+            .unwrap_or_else(|e| panic!("{engine}: {e}\n{}", files[0].contents));
+        assert!(value.is_object(), "{engine}");
+    }
+    assert!(builtin_template("nope").is_none());
+}
+
+#[test]
+fn helpers_compute_values() {
+    let atlas = atlas();
+    let f = &atlas.pages[0].frames[0];
+    let page = &atlas.pages[0];
+    let tpl = AtlasTemplate::parse(
+        "{{#with (lookup pages 0) as |p|}}{{#with (lookup p.sprites 0) as |s|}}\
+         {{uv s.frame p.size \"u1\"}}|{{basename s.name}}|{{stem s.name}}|{{{json s.frame}}}|\
+         {{add s.frame.x s.frame.w 1}}|{{div 7 2}}|{{mul 2 3}}|{{max 1 5 3}}|{{floor 2.7}}\
+         {{/with}}{{/with}}",
+    )
+    .unwrap();
+    let out = &tpl.render(&atlas, &names(&atlas), "atlas").unwrap()[0].contents;
+    let u1 = (f.frame.x + f.frame.w) as f64 / page.width as f64;
+    assert_eq!(
+        out,
+        &format!(
+            "{u1}|{}|{}|{{\"h\":{},\"w\":{},\"x\":{},\"y\":{}}}|{}|3.5|6|5|2",
+            f.key.rsplit('/').next().unwrap(),
+            f.key.rsplit('/').next().unwrap().trim_end_matches(".png"),
+            f.frame.h,
+            f.frame.w,
+            f.frame.x,
+            f.frame.y,
+            f.frame.x + f.frame.w + 1
+        )
+    );
+    // Helper errors surface as render errors
+    let bad = AtlasTemplate::parse("{{div 1 0}}").unwrap();
+    assert!(bad.render(&atlas, &[], "atlas").is_err());
+}
+
+#[test]
+fn manifest_writes_one_file_per_page_or_frame() {
+    let atlas = atlas();
+    let per_page = AtlasTemplate::parse(
+        "{{!-- tex-packer\neach: page\npath: {{name}}_{{page.index}}.txt\n--}}\n\
+         {{page.image}} {{page_count}} {{#each page.sprites}}{{stem name}};{{/each}}",
+    )
+    .unwrap();
+    assert_eq!(per_page.scope, TemplateScope::Page);
+    let files = per_page.render(&atlas, &names(&atlas), "ui").unwrap();
+    assert_eq!(files.len(), atlas.pages.len());
+    assert_eq!(files[1].path.as_deref(), Some("ui_1.txt"));
+    assert!(
+        files[1]
+            .contents
+            .starts_with(&format!("atlas_1.png {} ", atlas.pages.len()))
+    );
+
+    let per_frame = AtlasTemplate::parse(
+        "{{!-- tex-packer\neach: frame\npath: frames/{{stem sprite.name}}.json\n--}}\n\
+         {\"page\": {{page.index}}, \"w\": {{sprite.frame.w}}}",
+    )
+    .unwrap();
+    let files = per_frame.render(&atlas, &names(&atlas), "ui").unwrap();
+    let total: usize = atlas.pages.iter().map(|p| p.frames.len()).sum();
+    assert_eq!(files.len(), total);
+    assert!(
+        files
+            .iter()
+            .any(|f| f.path.as_deref() == Some("frames/icon_0.json"))
+    );
+    for f in &files {
+        serde_json::from_str::<serde_json::Value>(&f.contents).unwrap();
+    }
+}
+
+#[test]
+fn manifest_errors() {
+    let atlas = atlas();
+    // Per-page output needs a path
+    assert!(AtlasTemplate::parse("{{!-- tex-packer\neach: page\n--}}x").is_err());
+    assert!(AtlasTemplate::parse("{{!-- tex-packer\neach: sheet\n--}}x").is_err());
+    assert!(AtlasTemplate::parse("{{!-- tex-packer\ncolor: red\n--}}x").is_err());
+    assert!(AtlasTemplate::parse("{{#each pages}}").is_err());
+    // Paths may not leave the output directory or collide
+    for path in ["../{{name}}.txt", "/tmp/{{name}}.txt", "same.txt"] {
+        let tpl = AtlasTemplate::parse(&format!(
+            "{{{{!-- tex-packer\neach: page\npath: {path}\n--}}}}x"
+        ))
+        .unwrap();
+        assert!(tpl.render(&atlas, &[], "ui").is_err(), "{path}");
+    }
+    // An ordinary leading comment is not a manifest
+    let plain = AtlasTemplate::parse("{{!-- notes --}}ok").unwrap();
+    assert_eq!(plain.scope, TemplateScope::Atlas);
+    assert_eq!(plain.render(&atlas, &[], "ui").unwrap()[0].contents, "ok");
+}
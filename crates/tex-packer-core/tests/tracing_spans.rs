@@ -0,0 +1,135 @@
+mod common;
+
+use std::sync::{Arc, Mutex};
+
+use tex_packer_core::config::AutoMode;
+use tex_packer_core::prelude::*;
+use tracing::field::{Field, Visit};
+use tracing::span::{Attributes, Id, Record};
+use tracing::{Subscriber, subscriber};
+use tracing_subscriber::layer::{Context, Layer, SubscriberExt};
+use tracing_subscriber::registry::LookupSpan;
+
+/// Span name plus its fields as `name=value` strings, in record order.
+type SpanLog = Arc<Mutex<Vec<(String, Vec<String>)>>>;
+
+struct Recorder(SpanLog);
+
+#[derive(Default)]
+struct Fields(Vec<String>);
+
+impl Visit for Fields {
+    fn record_debug(&mut self, field: &Field, value: &dyn std::fmt::Debug) {
+        self.0.push(format!("{}={value:?}", field.name()));
+    }
+}
+
+/// Index into the log, stored in the span's extensions.
+struct Slot(usize);
+
+impl<S: Subscriber + for<'a> LookupSpan<'a>> Layer<S> for Recorder {
+    fn on_new_span(&self, attrs: &Attributes<'_>, id: &Id, ctx: Context<'_, S>) {
+        let mut fields = Fields::default();
+        attrs.record(&mut fields);
+        let mut log = self.0.lock().unwrap();
+        log.push((attrs.metadata().name().to_string(), fields.0));
+        ctx.span(id)
+            .unwrap()
+            .extensions_mut()
+            .insert(Slot(log.len() - 1));
+    }
+
+    fn on_record(&self, id: &Id, values: &Record<'_>, ctx: Context<'_, S>) {
+        let mut fields = Fields::default();
+        values.record(&mut fields);
+        let span = ctx.span(id).unwrap();
+        let idx = span.extensions().get::<Slot>().unwrap().0;
+        self.0.lock().unwrap()[idx].1.extend(fields.0);
+    }
+}
+
+fn spans(f: impl FnOnce()) -> Vec<(String, Vec<String>)> {
+    let log = SpanLog::default();
+    let sub = tracing_subscriber::registry().with(Recorder(log.clone()));
+    subscriber::with_default(sub, f);
+    Arc::try_unwrap(log).unwrap().into_inner().unwrap()
+}
+
+fn inputs() -> Vec<InputImage> {
+    (0..12)
+        .map(|i| common::solid(format!("s{i}"), 20 + i, 16, [255; 4]))
+        .collect()
+}
+
+fn has(fields: &[String], name: &str) -> bool {
+    fields.iter().any(|f| f.starts_with(&format!("{name}=")))
+}
+
+#[test]
+fn pack_images_emits_phase_spans() {
+    let cfg = PackerConfig {
+        max_width: 64,
+        max_height: 64,
+        family: AlgorithmFamily::Auto,
+        auto_mode: AutoMode::Quality,
+        parallel: false,
+        ..Default::default()
+    };
+    let log = spans(|| {
+        pack_images(inputs(), cfg).unwrap();
+    });
+    let named = |name: &'static str| log.iter().filter(move |(n, _)| n == name);
+
+    let (_, root) = named("pack_images").next().expect("root span");
+    assert!(root.contains(&"inputs=12".to_string()));
+    let (_, prepare) = named("prepare").next().expect("prepare span");
+    assert!(prepare.contains(&"kept=12".to_string()));
+
+    let (_, auto) = named("auto").next().expect("auto span");
+    assert!(has(auto, "winner"));
+    let candidates: Vec<_> = named("auto_candidate").collect();
+    assert!(candidates.len() > 1);
+    for (_, fields) in &candidates {
+        assert!(has(fields, "family") && has(fields, "pages") && has(fields, "area"));
+    }
+
+    // Every laid-out page reports what it placed
+    let pages: Vec<_> = named("page").collect();
+    assert!(!pages.is_empty());
+    for (_, fields) in &pages {
+        assert!(
+            has(fields, "frames") && has(fields, "occupancy"),
+            "{fields:?}"
+        );
+    }
+    assert!(pages.iter().any(|(_, f)| has(f, "free_rects")));
+
+    assert!(named("composite").next().is_some());
+    assert!(named("composite_page").next().is_some());
+}
+
+#[test]
+fn layout_and_stream_emit_page_spans() {
+    let cfg = PackerConfig {
+        max_width: 64,
+        max_height: 64,
+        family: AlgorithmFamily::MaxRects,
+        ..Default::default()
+    };
+    let log = spans(|| {
+        pack_layout(vec![("a", 30, 30), ("b", 40, 40)], cfg.clone()).unwrap();
+        pack_images_iter(inputs().into_iter().map(Ok), cfg)
+            .unwrap()
+            .into_output()
+            .unwrap();
+    });
+    assert!(
+        log.iter()
+            .any(|(n, f)| n == "pack_layout" && f.contains(&"inputs=2".to_string()))
+    );
+    let stream_pages = log
+        .iter()
+        .filter(|(n, f)| n == "page" && has(f, "free_rects"))
+        .count();
+    assert!(stream_pages > 0);
+}
@@ -0,0 +1,110 @@
+mod common;
+
+use image::{Rgba, RgbaImage};
+use tex_packer_core::pipeline::{compute_trim_rect, compute_trim_rect_with};
+use tex_packer_core::{PackerConfig, Rect, TrimOptions, pack_images};
+
+/// 20x20 transparent image with an opaque 3x5 block at (8, 6).
+fn sprite() -> RgbaImage {
+    let mut img = RgbaImage::new(20, 20);
+    for y in 6..11 {
+        for x in 8..11 {
+            img.put_pixel(x, y, Rgba([255, 0, 0, 255]));
+        }
+    }
+    img
+}
+
+fn source(opts: TrimOptions) -> Rect {
+    let (trim, src) = compute_trim_rect_with(&sprite(), 0, &opts);
+    let trim = trim.expect("opaque content");
+    assert_eq!((trim.w, trim.h), (src.w, src.h));
+    src
+}
+
+#[test]
+fn default_options_match_plain_trim() {
+    let plain = compute_trim_rect(&sprite(), 0).1;
+    assert_eq!(source(TrimOptions::default()), plain);
+    assert_eq!(plain, Rect::new(8, 6, 3, 5));
+}
+
+#[test]
+fn margin_keeps_pixels_per_side_within_bounds() {
+    let src = source(TrimOptions {
+        margin: [1, 2, 3, 100],
+        ..Default::default()
+    });
+    assert_eq!(src, Rect::new(7, 4, 7, 16));
+}
+
+#[test]
+fn min_size_grows_around_content() {
+    let src = source(TrimOptions {
+        min_size: 8,
+        ..Default::default()
+    });
+    assert_eq!((src.w, src.h), (8, 8));
+    assert!(src.x <= 8 && src.x + src.w >= 11);
+    assert!(src.y <= 6 && src.y + src.h >= 11);
+    // A floor larger than the image stops at the untrimmed size.
+    let src = source(TrimOptions {
+        min_size: 64,
+        ..Default::default()
+    });
+    assert_eq!(src, Rect::new(0, 0, 20, 20));
+}
+
+#[test]
+fn multiple_of_aligns_trimmed_sizes() {
+    let src = source(TrimOptions {
+        multiple_of: 4,
+        ..Default::default()
+    });
+    assert_eq!((src.w, src.h), (4, 8));
+    assert!(src.x <= 8 && src.x + src.w >= 11);
+    let src = source(TrimOptions {
+        min_size: 5,
+        multiple_of: 4,
+        ..Default::default()
+    });
+    assert_eq!((src.w, src.h), (8, 8));
+}
+
+#[test]
+fn pack_uses_trim_options() {
+    let cfg = PackerConfig::builder()
+        .with_max_dimensions(128, 128)
+        .texture_padding(0)
+        .allow_rotation(false)
+        .trim_options(TrimOptions {
+            multiple_of: 4,
+            ..Default::default()
+        })
+        .build();
+    let inputs = vec![common::input("s", sprite())];
+    let out = pack_images(inputs, cfg).unwrap();
+    let f = &out.atlas.pages[0].frames[0];
+    assert!(f.trimmed);
+    assert_eq!((f.frame.w, f.frame.h), (4, 8));
+    assert_eq!(f.source_size, (20, 20));
+}
+
+#[test]
+fn trim_options_set_str() {
+    let mut cfg = PackerConfig::default();
+    cfg.set_str("trim_options.margin", "1,2,3,4").unwrap();
+    cfg.set_str("trim_options.min_size", "16").unwrap();
+    cfg.set_str("trim_options.multiple_of", "4").unwrap();
+    assert_eq!(
+        cfg.trim_options,
+        TrimOptions {
+            margin: [1, 2, 3, 4],
+            min_size: 16,
+            multiple_of: 4,
+        }
+    );
+    cfg.set_str("trim_options.margin", "2").unwrap();
+    assert_eq!(cfg.trim_options.margin, [2; 4]);
+    assert!(cfg.set_str("trim_options.margin", "1,2").is_err());
+}
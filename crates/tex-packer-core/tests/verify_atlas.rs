@@ -0,0 +1,170 @@
+mod common;
+
+use image::{Rgba, RgbaImage};
+use tex_packer_core::{
+    Atlas, IssueKind, PackOutput, PackerConfig, Rect, VerifyOptions, pack_images, verify_atlas,
+};
+
+fn sprite(w: u32, h: u32, seed: u8) -> RgbaImage {
+    RgbaImage::from_fn(w, h, |x, y| {
+        Rgba([seed, (x * 11) as u8, (y * 5 + x) as u8, 255])
+    })
+}
+
+fn packed() -> PackOutput {
+    let inputs = [
+        ("tall", sprite(6, 30, 1)),
+        ("sq", sprite(10, 10, 2)),
+        ("wide", sprite(20, 5, 3)),
+    ]
+    .into_iter()
+    .map(|(k, img)| common::input(k, img))
+    .collect();
+    let cfg = PackerConfig {
+        max_width: 64,
+        max_height: 64,
+        allow_rotation: true,
+        border_padding: 1,
+        texture_padding: 2,
+        texture_extrusion: 1,
+        ..Default::default()
+    };
+    pack_images(inputs, cfg).unwrap()
+}
+
+fn frame_mut<'a>(atlas: &'a mut Atlas, key: &str) -> &'a mut tex_packer_core::Frame {
+    atlas.pages[0]
+        .frames
+        .iter_mut()
+        .find(|f| f.key == key)
+        .unwrap()
+}
+
+fn kinds(atlas: &Atlas, pages: Option<&[RgbaImage]>) -> Vec<(Option<String>, IssueKind)> {
+    verify_atlas(atlas, pages, VerifyOptions::default())
+        .into_iter()
+        .map(|i| (i.key, i.kind))
+        .collect()
+}
+
+#[test]
+fn packed_atlas_verifies_clean() {
+    let out = packed();
+    let pages: Vec<RgbaImage> = out.pages.iter().map(|p| p.rgba.clone()).collect();
+    assert_eq!(kinds(&out.atlas, Some(&pages)), []);
+}
+
+#[test]
+fn reports_bounds_overlap_and_padding_problems() {
+    let out = packed();
+    let mut atlas = out.atlas.clone();
+    let w = atlas.pages[0].width;
+    let sq = frame_mut(&mut atlas, "sq").frame;
+
+    // Moved onto "sq"
+    let tall = frame_mut(&mut atlas, "tall");
+    tall.frame = Rect::new(sq.x + 1, sq.y + 1, tall.frame.w, tall.frame.h);
+    let found = kinds(&atlas, None);
+    assert!(
+        found
+            .iter()
+            .any(|(_, k)| matches!(k, IssueKind::Overlap { .. })),
+        "{found:?}"
+    );
+
+    // Just past the page edge, then 1px from "sq" with padding 2
+    let mut atlas = out.atlas.clone();
+    let wide = frame_mut(&mut atlas, "wide");
+    wide.frame.x = w - wide.frame.w + 1;
+    let found = kinds(&atlas, None);
+    assert!(
+        found.iter().any(
+            |(k, i)| k.as_deref() == Some("wide") && matches!(i, IssueKind::OutOfBounds { .. })
+        ),
+        "{found:?}"
+    );
+    let mut atlas = out.atlas.clone();
+    let wide = frame_mut(&mut atlas, "wide");
+    wide.frame = Rect::new(sq.x + sq.w + 1, sq.y, wide.frame.w, wide.frame.h);
+    let found = kinds(&atlas, None);
+    assert!(
+        found.iter().any(|(_, k)| matches!(
+            k,
+            IssueKind::TooClose {
+                gap: 1,
+                padding: 2,
+                ..
+            }
+        )),
+        "{found:?}"
+    );
+    // An explicit padding of 1 accepts the 1px gap
+    let relaxed = VerifyOptions {
+        padding: Some(1),
+        ..Default::default()
+    };
+    assert!(
+        !verify_atlas(&atlas, None, relaxed)
+            .iter()
+            .any(|i| matches!(i.kind, IssueKind::TooClose { .. }))
+    );
+}
+
+#[test]
+fn reports_rotation_size_and_content_mismatches() {
+    let out = packed();
+    let pages: Vec<RgbaImage> = out.pages.iter().map(|p| p.rgba.clone()).collect();
+
+    // Non-square: the flipped flag contradicts the shape
+    let mut atlas = out.atlas.clone();
+    let tall = frame_mut(&mut atlas, "tall");
+    tall.rotated = !tall.rotated;
+    assert_eq!(
+        kinds(&atlas, None),
+        [(Some("tall".into()), IssueKind::RotationMismatch)]
+    );
+
+    // Square: only the content hash can tell
+    let mut atlas = out.atlas.clone();
+    let sq = frame_mut(&mut atlas, "sq");
+    sq.rotated = !sq.rotated;
+    assert_eq!(kinds(&atlas, None), []);
+    assert_eq!(
+        kinds(&atlas, Some(&pages)),
+        [(Some("sq".into()), IssueKind::RotationMismatch)]
+    );
+
+    let mut atlas = out.atlas.clone();
+    frame_mut(&mut atlas, "wide").source.w -= 1;
+    assert_eq!(
+        kinds(&atlas, None),
+        [(Some("wide".into()), IssueKind::SizeMismatch)]
+    );
+
+    // Repainted page pixels
+    let mut edited = pages.clone();
+    let f = frame_mut(&mut out.atlas.clone(), "wide").frame;
+    edited[0].put_pixel(f.x, f.y, Rgba([255, 0, 255, 255]));
+    assert!(matches!(
+        kinds(&out.atlas, Some(&edited))[..],
+        [(Some(ref k), IssueKind::ContentHashMismatch { .. })] if k == "wide"
+    ));
+
+    let small = [RgbaImage::new(8, 8)];
+    assert!(matches!(
+        kinds(&out.atlas, Some(&small))[..],
+        [(None, IssueKind::PageSizeMismatch { .. })]
+    ));
+}
+
+#[test]
+fn reports_duplicate_keys() {
+    let mut atlas = packed().atlas;
+    let copy = atlas.pages[0].frames[0].clone();
+    atlas.pages[0].frames.push(copy);
+    let found = kinds(&atlas, None);
+    assert!(found.contains(&(
+        Some(atlas.pages[0].frames[0].key.clone()),
+        IssueKind::DuplicateKey
+    )));
+}
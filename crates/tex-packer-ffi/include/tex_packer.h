@@ -0,0 +1,229 @@
+#ifndef TEX_PACKER_H
+#define TEX_PACKER_H
+
+/* Generated by cbindgen from crates/tex-packer-ffi/src/lib.rs; do not edit. */
+
+#include <stdbool.h>
+#include <stddef.h>
+#include <stdint.h>
+
+// Result of a fallible call.
+typedef enum TpStatus {
+  TP_STATUS_OK = 0,
+  // A required pointer argument was null.
+  TP_STATUS_NULL_POINTER = 1,
+  // An argument was malformed (bad UTF-8, unknown config key, short pixel buffer, ...).
+  TP_STATUS_INVALID_ARGUMENT = 2,
+  // Page or frame index past the end.
+  TP_STATUS_OUT_OF_RANGE = 3,
+  // Decoding an image failed.
+  TP_STATUS_IMAGE = 4,
+  // Packing failed (e.g. a sprite does not fit any page).
+  TP_STATUS_PACK = 5,
+  // A Rust panic was caught at the boundary.
+  TP_STATUS_PANIC = 6,
+} TpStatus;
+
+// Result of [`tp_pack`]: atlas metadata plus RGBA8 page pixels.
+typedef struct TpAtlas TpAtlas;
+
+// Packer settings; starts from `PackerConfig::default()`.
+typedef struct TpConfig TpConfig;
+
+// Images to pack.
+typedef struct TpInputs TpInputs;
+
+// One placed sprite. `key` stays valid until the atlas is freed.
+typedef struct TpFrame {
+  const char *key;
+  // Position and size on the page; `w`/`h` are in stored (rotated) orientation.
+  uint32_t x;
+  uint32_t y;
+  uint32_t w;
+  uint32_t h;
+  // Stored rotated 90° clockwise.
+  bool rotated;
+  bool trimmed;
+  // Trimmed region inside the original image.
+  uint32_t source_x;
+  uint32_t source_y;
+  uint32_t source_w;
+  uint32_t source_h;
+  // Original (untrimmed) image size.
+  uint32_t source_width;
+  uint32_t source_height;
+  float pivot_x;
+  float pivot_y;
+} TpFrame;
+
+#ifdef __cplusplus
+extern "C" {
+#endif // __cplusplus
+
+// Library version, e.g. `"0.1.0"`. Static; do not free.
+const char *tp_version(void);
+
+// Message of the last failed call on this thread, or null. Valid until the next failing
+// call on the same thread; do not free.
+const char *tp_last_error(void);
+
+// New config with default settings. Free with [`tp_config_free`].
+struct TpConfig *tp_config_new(void);
+
+// # Safety
+// `cfg` must be null or come from [`tp_config_new`] and not be used afterwards.
+void tp_config_free(struct TpConfig *cfg);
+
+// Sets any config field by name with a string value, using the same keys as the CLI/YAML
+// config (`"max_width"`, `"family"`, `"trim_options.margin"`, ...).
+//
+// # Safety
+// `cfg` must be a live config; `key` and `value` NUL-terminated strings.
+enum TpStatus tp_config_set(struct TpConfig *cfg, const char *key, const char *value);
+
+// Maximum page size in pixels.
+//
+// # Safety
+// `cfg` must be a live config.
+enum TpStatus tp_config_set_max_size(struct TpConfig *cfg, uint32_t width, uint32_t height);
+
+// Border padding around each page and padding between sprites.
+//
+// # Safety
+// `cfg` must be a live config.
+enum TpStatus tp_config_set_padding(struct TpConfig *cfg, uint32_t border, uint32_t texture);
+
+// Edge pixels repeated around each sprite.
+//
+// # Safety
+// `cfg` must be a live config.
+enum TpStatus tp_config_set_extrusion(struct TpConfig *cfg, uint32_t pixels);
+
+// # Safety
+// `cfg` must be a live config.
+enum TpStatus tp_config_set_rotation(struct TpConfig *cfg, bool allow);
+
+// # Safety
+// `cfg` must be a live config.
+enum TpStatus tp_config_set_trim(struct TpConfig *cfg, bool trim);
+
+// Empty input list. Free with [`tp_inputs_free`].
+struct TpInputs *tp_inputs_new(void);
+
+// # Safety
+// `inputs` must be null or come from [`tp_inputs_new`] and not be used afterwards.
+void tp_inputs_free(struct TpInputs *inputs);
+
+// Number of images added so far (0 for null).
+//
+// # Safety
+// `inputs` must be null or a live input list.
+size_t tp_inputs_len(const struct TpInputs *inputs);
+
+// Adds an RGBA8 image copied from `pixels`; rows are `stride` bytes apart (`0` means
+// `width * 4`).
+//
+// # Safety
+// `inputs` must be a live input list, `key` a NUL-terminated string and `pixels` point to at
+// least `stride * (height - 1) + width * 4` readable bytes.
+enum TpStatus tp_inputs_add_rgba(struct TpInputs *inputs,
+                                 const char *key,
+                                 const uint8_t *pixels,
+                                 uint32_t width,
+                                 uint32_t height,
+                                 size_t stride);
+
+// Decodes an encoded image (PNG, JPEG, ...) from memory and adds it.
+//
+// # Safety
+// `inputs` must be a live input list, `key` a NUL-terminated string and `data` point to `len`
+// readable bytes.
+enum TpStatus tp_inputs_add_encoded(struct TpInputs *inputs,
+                                    const char *key,
+                                    const uint8_t *data,
+                                    size_t len);
+
+// Loads an image file and adds it under `key`.
+//
+// # Safety
+// `inputs` must be a live input list; `key` and `path` NUL-terminated strings.
+enum TpStatus tp_inputs_add_file(struct TpInputs *inputs, const char *key, const char *path);
+
+// Packs a copy of `inputs` with `cfg`; on success `*out` receives an atlas to free with
+// [`tp_atlas_free`]. `inputs` and `cfg` stay usable.
+//
+// # Safety
+// `inputs` and `cfg` must be live handles; `out` must be a valid pointer.
+enum TpStatus tp_pack(const struct TpInputs *inputs,
+                      const struct TpConfig *cfg,
+                      struct TpAtlas **out);
+
+// # Safety
+// `atlas` must be null or come from [`tp_pack`] and not be used afterwards.
+void tp_atlas_free(struct TpAtlas *atlas);
+
+// Number of pages (0 for null).
+//
+// # Safety
+// `atlas` must be null or a live atlas.
+uint32_t tp_atlas_page_count(const struct TpAtlas *atlas);
+
+// Page size in pixels.
+//
+// # Safety
+// `atlas` must be a live atlas; `width` and `height` valid pointers.
+enum TpStatus tp_atlas_page_size(const struct TpAtlas *atlas,
+                                 uint32_t page,
+                                 uint32_t *width,
+                                 uint32_t *height);
+
+// RGBA8 pixels of a page, `width * 4` bytes per row, or null on error. `*len` (if not null)
+// receives the byte count. Owned by the atlas; valid until it is freed.
+//
+// # Safety
+// `atlas` must be a live atlas; `len` null or a valid pointer.
+const uint8_t *tp_atlas_page_pixels(const struct TpAtlas *atlas, uint32_t page, size_t *len);
+
+// Number of frames on a page (0 for null or out-of-range).
+//
+// # Safety
+// `atlas` must be null or a live atlas.
+uint32_t tp_atlas_frame_count(const struct TpAtlas *atlas, uint32_t page);
+
+// Frame `index` of `page`; iterate `0..tp_atlas_frame_count(atlas, page)`.
+//
+// # Safety
+// `atlas` must be a live atlas; `out` a valid pointer.
+enum TpStatus tp_atlas_frame(const struct TpAtlas *atlas,
+                             uint32_t page,
+                             uint32_t index,
+                             struct TpFrame *out);
+
+// Looks a frame up by key; `*page` (if not null) receives its page index.
+//
+// # Safety
+// `atlas` must be a live atlas, `key` a NUL-terminated string, `page` null or valid and `out`
+// a valid pointer.
+enum TpStatus tp_atlas_find_frame(const struct TpAtlas *atlas,
+                                  const char *key,
+                                  uint32_t *page,
+                                  struct TpFrame *out);
+
+// Atlas metadata as JSON (the CLI's default `json-array` layout). Free `*out` with
+// [`tp_string_free`].
+//
+// # Safety
+// `atlas` must be a live atlas; `out` a valid pointer.
+enum TpStatus tp_atlas_to_json(const struct TpAtlas *atlas, char **out);
+
+// Frees a string returned by this library.
+//
+// # Safety
+// `s` must be null or come from this library and not be used afterwards.
+void tp_string_free(char *s);
+
+#ifdef __cplusplus
+}  // extern "C"
+#endif  // __cplusplus
+
+#endif  /* TEX_PACKER_H */
@@ -0,0 +1,668 @@
+//! C ABI for tex-packer.
+//!
+//! Opaque handles (`TpConfig`, `TpInputs`, `TpAtlas`) are created and freed through this API.
+//! Fallible functions return a [`TpStatus`]; on failure [`tp_last_error`] describes the error.
+//! The C header is generated with cbindgen (see `cbindgen.toml`) into `include/tex_packer.h`.
+
+use std::cell::RefCell;
+use std::ffi::{CStr, CString, c_char};
+use std::panic::{AssertUnwindSafe, catch_unwind};
+use std::ptr;
+
+use image::{DynamicImage, RgbaImage};
+use tex_packer_core::{Atlas, Frame, InputImage, PackerConfig, pack_images, to_json_array};
+
+/// Result of a fallible call.
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TpStatus {
+    Ok = 0,
+    /// A required pointer argument was null.
+    NullPointer = 1,
+    /// An argument was malformed (bad UTF-8, unknown config key, short pixel buffer, ...).
+    InvalidArgument = 2,
+    /// Page or frame index past the end.
+    OutOfRange = 3,
+    /// Decoding an image failed.
+    Image = 4,
+    /// Packing failed (e.g. a sprite does not fit any page).
+    Pack = 5,
+    /// A Rust panic was caught at the boundary.
+    Panic = 6,
+}
+
+/// Packer settings; starts from `PackerConfig::default()`.
+pub struct TpConfig {
+    cfg: PackerConfig,
+}
+
+/// Images to pack.
+pub struct TpInputs {
+    inputs: Vec<InputImage>,
+}
+
+/// Result of [`tp_pack`]: atlas metadata plus RGBA8 page pixels.
+pub struct TpAtlas {
+    atlas: Atlas,
+    pages: Vec<RgbaImage>,
+    /// NUL-terminated frame keys, per page, for `TpFrame::key`.
+    keys: Vec<Vec<CString>>,
+}
+
+/// One placed sprite. `key` stays valid until the atlas is freed.
+#[repr(C)]
+#[derive(Debug, Clone, Copy)]
+pub struct TpFrame {
+    pub key: *const c_char,
+    /// Position and size on the page; `w`/`h` are in stored (rotated) orientation.
+    pub x: u32,
+    pub y: u32,
+    pub w: u32,
+    pub h: u32,
+    /// Stored rotated 90° clockwise.
+    pub rotated: bool,
+    pub trimmed: bool,
+    /// Trimmed region inside the original image.
+    pub source_x: u32,
+    pub source_y: u32,
+    pub source_w: u32,
+    pub source_h: u32,
+    /// Original (untrimmed) image size.
+    pub source_width: u32,
+    pub source_height: u32,
+    pub pivot_x: f32,
+    pub pivot_y: f32,
+}
+
+thread_local! {
+    static LAST_ERROR: RefCell<Option<CString>> = const { RefCell::new(None) };
+}
+
+type Failure = (TpStatus, String);
+
+fn fail<T>(status: TpStatus, msg: impl Into<String>) -> Result<T, Failure> {
+    Err((status, msg.into()))
+}
+
+/// Runs `f`, recording its error (or a caught panic) for `tp_last_error`.
+fn guard(f: impl FnOnce() -> Result<(), Failure>) -> TpStatus {
+    let (status, msg) = match catch_unwind(AssertUnwindSafe(f)) {
+        Ok(Ok(())) => return TpStatus::Ok,
+        Ok(Err(e)) => e,
+        Err(panic) => {
+            let msg = panic
+                .downcast_ref::<&str>()
+                .map(|s| s.to_string())
+                .or_else(|| panic.downcast_ref::<String>().cloned())
+                .unwrap_or_else(|| "panic".into());
+            (TpStatus::Panic, msg)
+        }
+    };
+    let msg = CString::new(msg.replace('\0', " ")).unwrap_or_default();
+    LAST_ERROR.with(|e| *e.borrow_mut() = Some(msg));
+    status
+}
+
+/// Borrows a handle argument.
+///
+/// # Safety
+/// `p` must be null or point to a live object of type `T`.
+unsafe fn handle<'a, T>(p: *const T, what: &str) -> Result<&'a T, Failure> {
+    // SAFETY: the caller guarantees `p` is null or valid
+    unsafe { p.as_ref() }.map_or_else(
+        || fail(TpStatus::NullPointer, format!("{what} is null")),
+        Ok,
+    )
+}
+
+/// # Safety
+/// `p` must be null or point to a live object of type `T` not aliased elsewhere.
+unsafe fn handle_mut<'a, T>(p: *mut T, what: &str) -> Result<&'a mut T, Failure> {
+    // SAFETY: the caller guarantees `p` is null or valid and unaliased
+    unsafe { p.as_mut() }.map_or_else(
+        || fail(TpStatus::NullPointer, format!("{what} is null")),
+        Ok,
+    )
+}
+
+/// # Safety
+/// `p` must be null or a valid NUL-terminated string.
+unsafe fn string<'a>(p: *const c_char, what: &str) -> Result<&'a str, Failure> {
+    if p.is_null() {
+        return fail(TpStatus::NullPointer, format!("{what} is null"));
+    }
+    // SAFETY: the caller guarantees a NUL-terminated string
+    unsafe { CStr::from_ptr(p) }
+        .to_str()
+        .or_else(|_| fail(TpStatus::InvalidArgument, format!("{what} is not UTF-8")))
+}
+
+/// Library version, e.g. `"0.1.0"`. Static; do not free.
+#[unsafe(no_mangle)]
+pub extern "C" fn tp_version() -> *const c_char {
+    concat!(env!("CARGO_PKG_VERSION"), "\0").as_ptr().cast()
+}
+
+/// Message of the last failed call on this thread, or null. Valid until the next failing
+/// call on the same thread; do not free.
+#[unsafe(no_mangle)]
+pub extern "C" fn tp_last_error() -> *const c_char {
+    LAST_ERROR.with(|e| e.borrow().as_ref().map_or(ptr::null(), |s| s.as_ptr()))
+}
+
+/// New config with default settings. Free with [`tp_config_free`].
+#[unsafe(no_mangle)]
+pub extern "C" fn tp_config_new() -> *mut TpConfig {
+    Box::into_raw(Box::new(TpConfig {
+        cfg: PackerConfig::default(),
+    }))
+}
+
+/// # Safety
+/// `cfg` must be null or come from [`tp_config_new`] and not be used afterwards.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn tp_config_free(cfg: *mut TpConfig) {
+    if !cfg.is_null() {
+        // SAFETY: allocated by `tp_config_new`
+        drop(unsafe { Box::from_raw(cfg) });
+    }
+}
+
+/// Sets any config field by name with a string value, using the same keys as the CLI/YAML
+/// config (`"max_width"`, `"family"`, `"trim_options.margin"`, ...).
+///
+/// # Safety
+/// `cfg` must be a live config; `key` and `value` NUL-terminated strings.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn tp_config_set(
+    cfg: *mut TpConfig,
+    key: *const c_char,
+    value: *const c_char,
+) -> TpStatus {
+    guard(|| {
+        // SAFETY: forwarded caller guarantees
+        let (cfg, key, value) = unsafe {
+            (
+                handle_mut(cfg, "cfg")?,
+                string(key, "key")?,
+                string(value, "value")?,
+            )
+        };
+        cfg.cfg
+            .set_str(key, value)
+            .or_else(|e| fail(TpStatus::InvalidArgument, e.to_string()))
+    })
+}
+
+/// Maximum page size in pixels.
+///
+/// # Safety
+/// `cfg` must be a live config.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn tp_config_set_max_size(
+    cfg: *mut TpConfig,
+    width: u32,
+    height: u32,
+) -> TpStatus {
+    guard(|| {
+        // SAFETY: forwarded caller guarantee
+        let cfg = unsafe { handle_mut(cfg, "cfg")? };
+        cfg.cfg.max_width = width;
+        cfg.cfg.max_height = height;
+        Ok(())
+    })
+}
+
+/// Border padding around each page and padding between sprites.
+///
+/// # Safety
+/// `cfg` must be a live config.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn tp_config_set_padding(
+    cfg: *mut TpConfig,
+    border: u32,
+    texture: u32,
+) -> TpStatus {
+    guard(|| {
+        // SAFETY: forwarded caller guarantee
+        let cfg = unsafe { handle_mut(cfg, "cfg")? };
+        cfg.cfg.border_padding = border;
+        cfg.cfg.texture_padding = texture;
+        Ok(())
+    })
+}
+
+/// Edge pixels repeated around each sprite.
+///
+/// # Safety
+/// `cfg` must be a live config.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn tp_config_set_extrusion(cfg: *mut TpConfig, pixels: u32) -> TpStatus {
+    guard(|| {
+        // SAFETY: forwarded caller guarantee
+        let cfg = unsafe { handle_mut(cfg, "cfg")? };
+        cfg.cfg.texture_extrusion = pixels;
+        Ok(())
+    })
+}
+
+/// # Safety
+/// `cfg` must be a live config.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn tp_config_set_rotation(cfg: *mut TpConfig, allow: bool) -> TpStatus {
+    guard(|| {
+        // SAFETY: forwarded caller guarantee
+        let cfg = unsafe { handle_mut(cfg, "cfg")? };
+        cfg.cfg.allow_rotation = allow;
+        Ok(())
+    })
+}
+
+/// # Safety
+/// `cfg` must be a live config.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn tp_config_set_trim(cfg: *mut TpConfig, trim: bool) -> TpStatus {
+    guard(|| {
+        // SAFETY: forwarded caller guarantee
+        let cfg = unsafe { handle_mut(cfg, "cfg")? };
+        cfg.cfg.trim = trim;
+        Ok(())
+    })
+}
+
+/// Empty input list. Free with [`tp_inputs_free`].
+#[unsafe(no_mangle)]
+pub extern "C" fn tp_inputs_new() -> *mut TpInputs {
+    Box::into_raw(Box::new(TpInputs { inputs: Vec::new() }))
+}
+
+/// # Safety
+/// `inputs` must be null or come from [`tp_inputs_new`] and not be used afterwards.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn tp_inputs_free(inputs: *mut TpInputs) {
+    if !inputs.is_null() {
+        // SAFETY: allocated by `tp_inputs_new`
+        drop(unsafe { Box::from_raw(inputs) });
+    }
+}
+
+/// Number of images added so far (0 for null).
+///
+/// # Safety
+/// `inputs` must be null or a live input list.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn tp_inputs_len(inputs: *const TpInputs) -> usize {
+    // SAFETY: caller guarantee
+    unsafe { inputs.as_ref() }.map_or(0, |i| i.inputs.len())
+}
+
+/// Adds an RGBA8 image copied from `pixels`; rows are `stride` bytes apart (`0` means
+/// `width * 4`).
+///
+/// # Safety
+/// `inputs` must be a live input list, `key` a NUL-terminated string and `pixels` point to at
+/// least `stride * (height - 1) + width * 4` readable bytes.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn tp_inputs_add_rgba(
+    inputs: *mut TpInputs,
+    key: *const c_char,
+    pixels: *const u8,
+    width: u32,
+    height: u32,
+    stride: usize,
+) -> TpStatus {
+    guard(|| {
+        // SAFETY: forwarded caller guarantees
+        let (inputs, key) = unsafe { (handle_mut(inputs, "inputs")?, string(key, "key")?) };
+        if pixels.is_null() {
+            return fail(TpStatus::NullPointer, "pixels is null");
+        }
+        let row = width as usize * 4;
+        let stride = if stride == 0 { row } else { stride };
+        if stride < row {
+            return fail(
+                TpStatus::InvalidArgument,
+                format!("stride {stride} is smaller than width * 4 = {row}"),
+            );
+        }
+        let mut data = Vec::with_capacity(row * height as usize);
+        for y in 0..height as usize {
+            // SAFETY: the caller guarantees `height` rows of `stride` bytes
+            let src = unsafe { std::slice::from_raw_parts(pixels.add(y * stride), row) };
+            data.extend_from_slice(src);
+        }
+        let image = RgbaImage::from_raw(width, height, data).expect("buffer sized above");
+        inputs.inputs.push(InputImage {
+            key: key.to_string(),
+            image: DynamicImage::ImageRgba8(image),
+        });
+        Ok(())
+    })
+}
+
+/// Decodes an encoded image (PNG, JPEG, ...) from memory and adds it.
+///
+/// # Safety
+/// `inputs` must be a live input list, `key` a NUL-terminated string and `data` point to `len`
+/// readable bytes.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn tp_inputs_add_encoded(
+    inputs: *mut TpInputs,
+    key: *const c_char,
+    data: *const u8,
+    len: usize,
+) -> TpStatus {
+    guard(|| {
+        // SAFETY: forwarded caller guarantees
+        let (inputs, key) = unsafe { (handle_mut(inputs, "inputs")?, string(key, "key")?) };
+        if data.is_null() {
+            return fail(TpStatus::NullPointer, "data is null");
+        }
+        // SAFETY: the caller guarantees `len` readable bytes
+        let bytes = unsafe { std::slice::from_raw_parts(data, len) };
+        let image = image::load_from_memory(bytes)
+            .or_else(|e| fail(TpStatus::Image, format!("{key}: {e}")))?;
+        inputs.inputs.push(InputImage {
+            key: key.to_string(),
+            image,
+        });
+        Ok(())
+    })
+}
+
+/// Loads an image file and adds it under `key`.
+///
+/// # Safety
+/// `inputs` must be a live input list; `key` and `path` NUL-terminated strings.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn tp_inputs_add_file(
+    inputs: *mut TpInputs,
+    key: *const c_char,
+    path: *const c_char,
+) -> TpStatus {
+    guard(|| {
+        // SAFETY: forwarded caller guarantees
+        let (inputs, key, path) = unsafe {
+            (
+                handle_mut(inputs, "inputs")?,
+                string(key, "key")?,
+                string(path, "path")?,
+            )
+        };
+        let image = image::open(path).or_else(|e| fail(TpStatus::Image, format!("{path}: {e}")))?;
+        inputs.inputs.push(InputImage {
+            key: key.to_string(),
+            image,
+        });
+        Ok(())
+    })
+}
+
+/// Packs a copy of `inputs` with `cfg`; on success `*out` receives an atlas to free with
+/// [`tp_atlas_free`]. `inputs` and `cfg` stay usable.
+///
+/// # Safety
+/// `inputs` and `cfg` must be live handles; `out` must be a valid pointer.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn tp_pack(
+    inputs: *const TpInputs,
+    cfg: *const TpConfig,
+    out: *mut *mut TpAtlas,
+) -> TpStatus {
+    guard(|| {
+        // SAFETY: forwarded caller guarantees
+        let (inputs, cfg, out) = unsafe {
+            (
+                handle(inputs, "inputs")?,
+                handle(cfg, "cfg")?,
+                handle_mut(out, "out")?,
+            )
+        };
+        *out = ptr::null_mut();
+        let owned = inputs
+            .inputs
+            .iter()
+            .map(|i| InputImage {
+                key: i.key.clone(),
+                image: i.image.clone(),
+            })
+            .collect::<Vec<_>>();
+        let packed =
+            pack_images(owned, cfg.cfg.clone()).or_else(|e| fail(TpStatus::Pack, e.to_string()))?;
+        let pages = packed
+            .pages
+            .iter()
+            .map(|p| p.pixels().map(|px| px.into_owned()))
+            .collect::<Result<Vec<_>, _>>()
+            .or_else(|e| fail(TpStatus::Pack, e.to_string()))?;
+        let keys = packed
+            .atlas
+            .pages
+            .iter()
+            .map(|p| {
+                p.frames
+                    .iter()
+                    .map(|f| CString::new(f.key.replace('\0', "")).unwrap_or_default())
+                    .collect()
+            })
+            .collect();
+        *out = Box::into_raw(Box::new(TpAtlas {
+            atlas: packed.atlas,
+            pages,
+            keys,
+        }));
+        Ok(())
+    })
+}
+
+/// # Safety
+/// `atlas` must be null or come from [`tp_pack`] and not be used afterwards.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn tp_atlas_free(atlas: *mut TpAtlas) {
+    if !atlas.is_null() {
+        // SAFETY: allocated by `tp_pack`
+        drop(unsafe { Box::from_raw(atlas) });
+    }
+}
+
+/// Number of pages (0 for null).
+///
+/// # Safety
+/// `atlas` must be null or a live atlas.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn tp_atlas_page_count(atlas: *const TpAtlas) -> u32 {
+    // SAFETY: caller guarantee
+    unsafe { atlas.as_ref() }.map_or(0, |a| a.pages.len() as u32)
+}
+
+fn page_index(atlas: &TpAtlas, page: u32) -> Result<usize, Failure> {
+    let i = page as usize;
+    if i < atlas.pages.len() {
+        Ok(i)
+    } else {
+        fail(
+            TpStatus::OutOfRange,
+            format!("page {page} of {}", atlas.pages.len()),
+        )
+    }
+}
+
+/// Page size in pixels.
+///
+/// # Safety
+/// `atlas` must be a live atlas; `width` and `height` valid pointers.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn tp_atlas_page_size(
+    atlas: *const TpAtlas,
+    page: u32,
+    width: *mut u32,
+    height: *mut u32,
+) -> TpStatus {
+    guard(|| {
+        // SAFETY: forwarded caller guarantees
+        let (atlas, width, height) = unsafe {
+            (
+                handle(atlas, "atlas")?,
+                handle_mut(width, "width")?,
+                handle_mut(height, "height")?,
+            )
+        };
+        let img = &atlas.pages[page_index(atlas, page)?];
+        (*width, *height) = img.dimensions();
+        Ok(())
+    })
+}
+
+/// RGBA8 pixels of a page, `width * 4` bytes per row, or null on error. `*len` (if not null)
+/// receives the byte count. Owned by the atlas; valid until it is freed.
+///
+/// # Safety
+/// `atlas` must be a live atlas; `len` null or a valid pointer.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn tp_atlas_page_pixels(
+    atlas: *const TpAtlas,
+    page: u32,
+    len: *mut usize,
+) -> *const u8 {
+    let mut pixels: *const u8 = ptr::null();
+    guard(|| {
+        // SAFETY: forwarded caller guarantee
+        let atlas = unsafe { handle(atlas, "atlas")? };
+        let img = &atlas.pages[page_index(atlas, page)?];
+        // SAFETY: `len` is null or valid per the caller
+        if let Some(len) = unsafe { len.as_mut() } {
+            *len = img.as_raw().len();
+        }
+        pixels = img.as_raw().as_ptr();
+        Ok(())
+    });
+    pixels
+}
+
+/// Number of frames on a page (0 for null or out-of-range).
+///
+/// # Safety
+/// `atlas` must be null or a live atlas.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn tp_atlas_frame_count(atlas: *const TpAtlas, page: u32) -> u32 {
+    // SAFETY: caller guarantee
+    unsafe { atlas.as_ref() }
+        .and_then(|a| a.atlas.pages.get(page as usize))
+        .map_or(0, |p| p.frames.len() as u32)
+}
+
+fn to_tp_frame(f: &Frame, key: &CString) -> TpFrame {
+    TpFrame {
+        key: key.as_ptr(),
+        x: f.frame.x,
+        y: f.frame.y,
+        w: f.frame.w,
+        h: f.frame.h,
+        rotated: f.rotated,
+        trimmed: f.trimmed,
+        source_x: f.source.x,
+        source_y: f.source.y,
+        source_w: f.source.w,
+        source_h: f.source.h,
+        source_width: f.source_size.0,
+        source_height: f.source_size.1,
+        pivot_x: f.pivot.x as f32,
+        pivot_y: f.pivot.y as f32,
+    }
+}
+
+/// Frame `index` of `page`; iterate `0..tp_atlas_frame_count(atlas, page)`.
+///
+/// # Safety
+/// `atlas` must be a live atlas; `out` a valid pointer.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn tp_atlas_frame(
+    atlas: *const TpAtlas,
+    page: u32,
+    index: u32,
+    out: *mut TpFrame,
+) -> TpStatus {
+    guard(|| {
+        // SAFETY: forwarded caller guarantees
+        let (atlas, out) = unsafe { (handle(atlas, "atlas")?, handle_mut(out, "out")?) };
+        let p = page_index(atlas, page)?;
+        let frames = &atlas.atlas.pages[p].frames;
+        let Some(f) = frames.get(index as usize) else {
+            return fail(
+                TpStatus::OutOfRange,
+                format!("frame {index} of {} on page {page}", frames.len()),
+            );
+        };
+        *out = to_tp_frame(f, &atlas.keys[p][index as usize]);
+        Ok(())
+    })
+}
+
+/// Looks a frame up by key; `*page` (if not null) receives its page index.
+///
+/// # Safety
+/// `atlas` must be a live atlas, `key` a NUL-terminated string, `page` null or valid and `out`
+/// a valid pointer.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn tp_atlas_find_frame(
+    atlas: *const TpAtlas,
+    key: *const c_char,
+    page: *mut u32,
+    out: *mut TpFrame,
+) -> TpStatus {
+    guard(|| {
+        // SAFETY: forwarded caller guarantees
+        let (atlas, key, out) = unsafe {
+            (
+                handle(atlas, "atlas")?,
+                string(key, "key")?,
+                handle_mut(out, "out")?,
+            )
+        };
+        for (p, pg) in atlas.atlas.pages.iter().enumerate() {
+            if let Some(i) = pg.frames.iter().position(|f| f.key == key) {
+                *out = to_tp_frame(&pg.frames[i], &atlas.keys[p][i]);
+                // SAFETY: `page` is null or valid per the caller
+                if let Some(page) = unsafe { page.as_mut() } {
+                    *page = p as u32;
+                }
+                return Ok(());
+            }
+        }
+        fail(TpStatus::OutOfRange, format!("no frame '{key}'"))
+    })
+}
+
+/// Atlas metadata as JSON (the CLI's default `json-array` layout). Free `*out` with
+/// [`tp_string_free`].
+///
+/// # Safety
+/// `atlas` must be a live atlas; `out` a valid pointer.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn tp_atlas_to_json(
+    atlas: *const TpAtlas,
+    out: *mut *mut c_char,
+) -> TpStatus {
+    guard(|| {
+        // SAFETY: forwarded caller guarantees
+        let (atlas, out) = unsafe { (handle(atlas, "atlas")?, handle_mut(out, "out")?) };
+        let json = serde_json::to_string(&to_json_array(&atlas.atlas))
+            .or_else(|e| fail(TpStatus::InvalidArgument, e.to_string()))?;
+        *out = CString::new(json)
+            .or_else(|e| fail(TpStatus::InvalidArgument, e.to_string()))?
+            .into_raw();
+        Ok(())
+    })
+}
+
+/// Frees a string returned by this library.
+///
+/// # Safety
+/// `s` must be null or come from this library and not be used afterwards.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn tp_string_free(s: *mut c_char) {
+    if !s.is_null() {
+        // SAFETY: allocated with `CString::into_raw`
+        drop(unsafe { CString::from_raw(s) });
+    }
+}
@@ -0,0 +1,162 @@
+use std::ffi::{CStr, CString, c_char};
+use std::ptr;
+
+use tex_packer::*;
+
+fn c(s: &str) -> CString {
+    CString::new(s).unwrap()
+}
+
+fn last_error() -> String {
+    let p = tp_last_error();
+    assert!(!p.is_null());
+    unsafe { CStr::from_ptr(p) }.to_string_lossy().into_owned()
+}
+
+fn solid(w: u32, h: u32, v: u8) -> Vec<u8> {
+    vec![v; (w * h * 4) as usize]
+}
+
+#[test]
+fn packs_rgba_inputs_and_exposes_frames_and_pixels() {
+    unsafe {
+        let cfg = tp_config_new();
+        assert_eq!(tp_config_set_max_size(cfg, 64, 64), TpStatus::Ok);
+        assert_eq!(tp_config_set_padding(cfg, 0, 0), TpStatus::Ok);
+        assert_eq!(tp_config_set_rotation(cfg, false), TpStatus::Ok);
+        assert_eq!(
+            tp_config_set(cfg, c("family").as_ptr(), c("maxrects").as_ptr()),
+            TpStatus::Ok
+        );
+
+        let inputs = tp_inputs_new();
+        let red = solid(16, 8, 200);
+        let key = c("red");
+        assert_eq!(
+            tp_inputs_add_rgba(inputs, key.as_ptr(), red.as_ptr(), 16, 8, 0),
+            TpStatus::Ok
+        );
+        // 4x4 sprite read from a wider buffer via the stride
+        let wide = solid(10, 4, 50);
+        assert_eq!(
+            tp_inputs_add_rgba(inputs, c("blue").as_ptr(), wide.as_ptr(), 4, 4, 40),
+            TpStatus::Ok
+        );
+        assert_eq!(tp_inputs_len(inputs), 2);
+
+        let mut atlas = ptr::null_mut();
+        assert_eq!(tp_pack(inputs, cfg, &mut atlas), TpStatus::Ok);
+        assert_eq!(tp_atlas_page_count(atlas), 1);
+        let (mut w, mut h) = (0, 0);
+        assert_eq!(tp_atlas_page_size(atlas, 0, &mut w, &mut h), TpStatus::Ok);
+        let mut len = 0;
+        let px = tp_atlas_page_pixels(atlas, 0, &mut len);
+        assert_eq!(len, (w * h * 4) as usize);
+        let pixels = std::slice::from_raw_parts(px, len);
+
+        assert_eq!(tp_atlas_frame_count(atlas, 0), 2);
+        let mut keys = Vec::new();
+        for i in 0..2 {
+            let mut f = std::mem::zeroed::<TpFrame>();
+            assert_eq!(tp_atlas_frame(atlas, 0, i, &mut f), TpStatus::Ok);
+            keys.push(CStr::from_ptr(f.key).to_str().unwrap().to_string());
+        }
+        keys.sort();
+        assert_eq!(keys, ["blue", "red"]);
+
+        let mut page = u32::MAX;
+        let mut f = std::mem::zeroed::<TpFrame>();
+        assert_eq!(
+            tp_atlas_find_frame(atlas, key.as_ptr(), &mut page, &mut f),
+            TpStatus::Ok
+        );
+        assert_eq!((page, f.w, f.h, f.rotated), (0, 16, 8, false));
+        assert_eq!((f.source_width, f.source_height), (16, 8));
+        let at = ((f.y * w + f.x) * 4) as usize;
+        assert_eq!(pixels[at], 200);
+
+        let mut json: *mut c_char = ptr::null_mut();
+        assert_eq!(tp_atlas_to_json(atlas, &mut json), TpStatus::Ok);
+        let text = CStr::from_ptr(json).to_str().unwrap().to_string();
+        tp_string_free(json);
+        let v: serde_json::Value = serde_json::from_str(&text).unwrap();
+        assert_eq!(v["pages"][0]["frames"].as_array().unwrap().len(), 2);
+
+        tp_atlas_free(atlas);
+        tp_inputs_free(inputs);
+        tp_config_free(cfg);
+    }
+}
+
+#[test]
+fn errors_are_reported_not_panicked() {
+    unsafe {
+        let cfg = tp_config_new();
+        assert_eq!(
+            tp_config_set(cfg, c("no_such_key").as_ptr(), c("1").as_ptr()),
+            TpStatus::InvalidArgument
+        );
+        assert!(last_error().contains("no_such_key"));
+        assert_eq!(
+            tp_config_set_max_size(ptr::null_mut(), 1, 1),
+            TpStatus::NullPointer
+        );
+
+        let inputs = tp_inputs_new();
+        let px = solid(4, 4, 1);
+        assert_eq!(
+            tp_inputs_add_rgba(inputs, c("a").as_ptr(), px.as_ptr(), 4, 4, 8),
+            TpStatus::InvalidArgument
+        );
+        assert_eq!(
+            tp_inputs_add_encoded(inputs, c("a").as_ptr(), px.as_ptr(), px.len()),
+            TpStatus::Image
+        );
+
+        // Too big for the page
+        tp_config_set_max_size(cfg, 8, 8);
+        let big = solid(32, 32, 1);
+        tp_inputs_add_rgba(inputs, c("big").as_ptr(), big.as_ptr(), 32, 32, 0);
+        let mut atlas = ptr::null_mut();
+        assert_eq!(tp_pack(inputs, cfg, &mut atlas), TpStatus::Pack);
+        assert!(atlas.is_null());
+        assert!(!last_error().is_empty());
+
+        tp_inputs_free(inputs);
+        tp_config_free(cfg);
+    }
+}
+
+#[test]
+fn encoded_inputs_and_index_bounds() {
+    let mut png = Vec::new();
+    image::RgbaImage::from_pixel(3, 5, image::Rgba([1, 2, 3, 255]))
+        .write_to(&mut std::io::Cursor::new(&mut png), image::ImageFormat::Png)
+        .unwrap();
+    unsafe {
+        let cfg = tp_config_new();
+        tp_config_set_trim(cfg, false);
+        let inputs = tp_inputs_new();
+        assert_eq!(
+            tp_inputs_add_encoded(inputs, c("png").as_ptr(), png.as_ptr(), png.len()),
+            TpStatus::Ok
+        );
+        let mut atlas = ptr::null_mut();
+        assert_eq!(tp_pack(inputs, cfg, &mut atlas), TpStatus::Ok);
+
+        let mut f = std::mem::zeroed::<TpFrame>();
+        assert_eq!(tp_atlas_frame(atlas, 0, 1, &mut f), TpStatus::OutOfRange);
+        assert_eq!(tp_atlas_frame(atlas, 7, 0, &mut f), TpStatus::OutOfRange);
+        assert!(tp_atlas_page_pixels(atlas, 7, ptr::null_mut()).is_null());
+        assert_eq!(
+            tp_atlas_find_frame(atlas, c("nope").as_ptr(), ptr::null_mut(), &mut f),
+            TpStatus::OutOfRange
+        );
+        assert_eq!(tp_atlas_frame_count(atlas, 7), 0);
+
+        tp_atlas_free(atlas);
+        tp_inputs_free(inputs);
+        tp_config_free(cfg);
+    }
+    assert!(!tp_version().is_null());
+}
@@ -0,0 +1,315 @@
+//! Export: page images, metadata, engine templates and stats, written with the same core
+//! exporters (and file names) as the CLI.
+
+use anyhow::Context;
+use std::fs;
+use std::path::{Path, PathBuf};
+use tex_packer_core::prelude::*;
+use tex_packer_core::{JsonOptions, PackOutput};
+
+/// Metadata format written next to the pages (the CLI's `--metadata`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExportFormat {
+    JsonHash,
+    JsonArray,
+    Plist,
+    Gdx,
+    Spine,
+    Aseprite,
+    Unity,
+    Godot,
+    RustCode,
+    CHeader,
+    CSharp,
+    Binary,
+}
+
+impl ExportFormat {
+    pub const ALL: [Self; 12] = [
+        Self::JsonHash,
+        Self::JsonArray,
+        Self::Plist,
+        Self::Gdx,
+        Self::Spine,
+        Self::Aseprite,
+        Self::Unity,
+        Self::Godot,
+        Self::RustCode,
+        Self::CHeader,
+        Self::CSharp,
+        Self::Binary,
+    ];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::JsonHash => "JSON Hash",
+            Self::JsonArray => "JSON Array",
+            Self::Plist => "Plist (Cocos2d)",
+            Self::Gdx => "libGDX .atlas",
+            Self::Spine => "Spine .atlas",
+            Self::Aseprite => "Aseprite JSON",
+            Self::Unity => "Unity .tpsheet",
+            Self::Godot => "Godot .tres",
+            Self::RustCode => "Rust code",
+            Self::CHeader => "C header",
+            Self::CSharp => "C#",
+            Self::Binary => "Binary",
+        }
+    }
+
+    /// Formats whose `rotated` flag always means a 90° clockwise turn.
+    fn clockwise_only(self) -> bool {
+        matches!(self, Self::Plist | Self::Gdx | Self::Spine | Self::Aseprite)
+    }
+
+    /// Engines that cannot use rotated frames.
+    fn forbids_rotation(self) -> bool {
+        matches!(self, Self::Unity | Self::Godot)
+    }
+}
+
+/// Built-in engine templates offered alongside the metadata (the CLI's `--engine`).
+pub const ENGINES: [&str; 7] = [
+    "unity",
+    "godot",
+    "phaser3",
+    "phaser3_single",
+    "spine",
+    "cocos",
+    "unreal",
+];
+
+/// What an export writes besides the metadata format.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ExportSettings {
+    pub format: ExportFormat,
+    /// Built-in engine template (one of [`ENGINES`]); `spine` writes the Spine atlas.
+    pub engine: Option<&'static str>,
+    /// External handlebars template rendered when no engine template is selected (the CLI's
+    /// `--template`).
+    pub template: Option<PathBuf>,
+    /// Write metadata only, no page images.
+    pub layout_only: bool,
+    /// Also write `<name>.stats.json`.
+    pub stats_json: bool,
+    /// Normalized UVs per frame in the JSON formats.
+    pub uvs: bool,
+    /// Texels to inset each UV edge by; above 0 it implies `uvs` (the CLI's `--uv-inset`).
+    pub uv_inset: f32,
+    /// Godot resource directory the output is imported under (the CLI's `--godot-res-dir`).
+    pub godot_res_dir: String,
+    /// With the Godot format, also write `<name>_frames.tres` (the CLI's
+    /// `--godot-sprite-frames`).
+    pub godot_sprite_frames: bool,
+}
+
+impl Default for ExportSettings {
+    fn default() -> Self {
+        Self {
+            format: ExportFormat::JsonHash,
+            engine: None,
+            template: None,
+            layout_only: false,
+            stats_json: false,
+            uvs: false,
+            uv_inset: 0.0,
+            godot_res_dir: "res://".into(),
+            godot_sprite_frames: false,
+        }
+    }
+}
+
+/// Page image formats the GUI can encode (KTX2 and DDS need the CLI's optional features).
+pub const PAGE_FORMATS: [PageFormat; 5] = [
+    PageFormat::Png,
+    PageFormat::Jpeg,
+    PageFormat::Webp,
+    PageFormat::Avif,
+    PageFormat::Exr,
+];
+
+/// Writes `out` to `dir` as `name`, returning every file written.
+pub fn export_atlas(
+    out: &PackOutput,
+    cfg: &PackerConfig,
+    settings: &ExportSettings,
+    dir: &Path,
+    name: &str,
+) -> anyhow::Result<Vec<PathBuf>> {
+    let atlas = &out.atlas;
+    let rotated = atlas
+        .pages
+        .iter()
+        .flat_map(|p| &p.frames)
+        .any(|f| f.rotated);
+    if rotated && settings.format.forbids_rotation() {
+        anyhow::bail!(
+            "{}: the engine cannot use rotated frames; disable rotation and pack again",
+            settings.format.label()
+        );
+    }
+    if settings.format.clockwise_only() {
+        tex_packer_core::require_clockwise(atlas, settings.format.label())?;
+    }
+    if let Some(engine) = settings.engine {
+        if matches!(engine, "spine" | "phaser3" | "phaser3_single" | "cocos") {
+            tex_packer_core::require_clockwise(atlas, engine)?;
+        }
+        let atlas_file = matches!(settings.format, ExportFormat::Gdx | ExportFormat::Spine);
+        if engine == "spine" && atlas_file && settings.format != ExportFormat::Spine {
+            anyhow::bail!(
+                "{} and the spine engine would both write {name}.atlas; pick one",
+                settings.format.label()
+            );
+        }
+    }
+
+    let num_pages = atlas.pages.len();
+    let stem = |id: usize| {
+        if num_pages == 1 {
+            name.to_string()
+        } else {
+            format!("{name}_{id}")
+        }
+    };
+    let page_names: Vec<String> = atlas
+        .pages
+        .iter()
+        .map(|p| format!("{}.{}", stem(p.id), cfg.page_format.extension()))
+        .collect();
+    let mut written = Vec::new();
+    let mut write = |path: PathBuf, contents: &[u8]| -> anyhow::Result<()> {
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent).with_context(|| format!("create {}", parent.display()))?;
+        }
+        fs::write(&path, contents).with_context(|| format!("write {}", path.display()))?;
+        written.push(path);
+        Ok(())
+    };
+
+    if !settings.layout_only {
+        for (page, file) in out.pages.iter().zip(&page_names) {
+            let bytes = match cfg.page_format {
+                PageFormat::Ktx2 | PageFormat::Dds => anyhow::bail!(
+                    "{:?} pages are not available in the GUI; use the CLI",
+                    cfg.page_format
+                ),
+                format => {
+                    tex_packer_core::encode_page(&*page.pixels()?, format, &cfg.page_encoding)?
+                }
+            };
+            write(dir.join(file), &bytes)?;
+        }
+    }
+
+    let json_options = JsonOptions {
+        uvs: settings.uvs || settings.uv_inset > 0.0,
+        uv_inset: settings.uv_inset,
+    };
+    let pretty = |v: &serde_json::Value| serde_json::to_string_pretty(v).map(String::into_bytes);
+    match settings.format {
+        ExportFormat::JsonHash => {
+            let v = tex_packer_core::to_json_hash_with(atlas, &json_options);
+            write(dir.join(format!("{name}.json")), &pretty(&v)?)?;
+        }
+        ExportFormat::JsonArray => {
+            let v = tex_packer_core::to_json_array_with(atlas, &json_options);
+            write(dir.join(format!("{name}.json")), &pretty(&v)?)?;
+        }
+        ExportFormat::Plist => {
+            let text = tex_packer_core::to_plist_hash_with_pages(atlas, &page_names);
+            write(dir.join(format!("{name}.plist")), text.as_bytes())?;
+        }
+        ExportFormat::Gdx => {
+            let text = tex_packer_core::to_gdx_atlas(atlas, &page_names);
+            write(dir.join(format!("{name}.atlas")), text.as_bytes())?;
+        }
+        ExportFormat::Spine => {
+            let text = tex_packer_core::to_spine_atlas(atlas, &page_names);
+            write(dir.join(format!("{name}.atlas")), text.as_bytes())?;
+        }
+        ExportFormat::Aseprite => {
+            for (idx, page) in atlas.pages.iter().enumerate() {
+                let v = tex_packer_core::to_aseprite_json(atlas, idx, &page_names[idx]);
+                write(dir.join(format!("{}.json", stem(page.id))), &pretty(&v)?)?;
+            }
+        }
+        ExportFormat::Unity => {
+            for (idx, page) in atlas.pages.iter().enumerate() {
+                let text = tex_packer_core::to_unity_tpsheet(atlas, idx, &page_names[idx])?;
+                write(
+                    dir.join(format!("{}.tpsheet", stem(page.id))),
+                    text.as_bytes(),
+                )?;
+            }
+        }
+        ExportFormat::Godot => {
+            let options = tex_packer_core::GodotOptions {
+                res_dir: settings.godot_res_dir.clone(),
+                sprite_frames: settings
+                    .godot_sprite_frames
+                    .then(|| format!("{name}_frames.tres")),
+            };
+            for res in tex_packer_core::to_godot_resources(atlas, &page_names, &options)? {
+                write(dir.join(&res.path), res.contents.as_bytes())?;
+            }
+        }
+        ExportFormat::RustCode => {
+            let code = tex_packer_core::to_rust_code(atlas, &page_names);
+            write(dir.join(format!("{name}.rs")), code.as_bytes())?;
+        }
+        ExportFormat::CHeader => {
+            let code = tex_packer_core::to_c_header(atlas, &page_names, name);
+            write(dir.join(format!("{name}.h")), code.as_bytes())?;
+        }
+        ExportFormat::CSharp => {
+            let code = tex_packer_core::to_csharp(atlas, &page_names, name);
+            write(dir.join(format!("{name}.cs")), code.as_bytes())?;
+        }
+        ExportFormat::Binary => {
+            let bytes = tex_packer_core::to_binary_atlas(atlas, &page_names);
+            write(dir.join(format!("{name}.bin")), &bytes)?;
+        }
+    }
+
+    match settings.engine {
+        None => {
+            if let Some(path) = &settings.template {
+                let source = fs::read_to_string(path)
+                    .with_context(|| format!("read template {}", path.display()))?;
+                let template = tex_packer_core::AtlasTemplate::parse(&source)?;
+                for file in template.render(atlas, &page_names, name)? {
+                    let path = match &file.path {
+                        Some(path) => dir.join(path),
+                        None => dir.join(format!("{name}.template.json")),
+                    };
+                    write(path, file.contents.as_bytes())?;
+                }
+            }
+        }
+        Some("spine") if settings.format == ExportFormat::Spine => {}
+        Some("spine") => {
+            let text = tex_packer_core::to_spine_atlas(atlas, &page_names);
+            write(dir.join(format!("{name}.atlas")), text.as_bytes())?;
+        }
+        Some(engine) => {
+            let source = tex_packer_core::builtin_template(engine)
+                .with_context(|| format!("unknown engine template: {engine}"))?;
+            let template = tex_packer_core::AtlasTemplate::parse(source)?;
+            for file in template.render(atlas, &page_names, name)? {
+                let path = match &file.path {
+                    Some(path) => dir.join(path),
+                    None if engine == "phaser3" => dir.join(format!("{name}.multiatlas.json")),
+                    None => dir.join(format!("{name}.template.json")),
+                };
+                write(path, file.contents.as_bytes())?;
+            }
+        }
+    }
+
+    if settings.stats_json {
+        let v = tex_packer_core::to_stats_json(&out.stats());
+        write(dir.join(format!("{name}.stats.json")), &pretty(&v)?)?;
+    }
+    Ok(written)
+}
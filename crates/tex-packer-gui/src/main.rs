@@ -1,5 +1,6 @@
 //! tex-packer-gui using egui/eframe with left/right layout
 
+mod export;
 mod presets;
 mod state;
 mod stats;
@@ -16,11 +17,20 @@ struct GuiApp {
     // Cache of egui textures for pages, recreated after packing
     page_textures: Vec<Option<egui::TextureHandle>>,
     // Async pack job
-    pack_job: Option<std::thread::JoinHandle<Result<(PackOutput, GuiPackStats), String>>>,
+    pack_job: Option<PackJob>,
     cancel_requested: bool,
     autopack_deadline: Option<Instant>,
 }
 
+/// A pack running on a core worker thread.
+struct PackJob {
+    handle: PackHandle,
+    start: Instant,
+    num_images: usize,
+    /// Config the job packs with, to validate its result against.
+    cfg: PackerConfig,
+}
+
 impl Default for GuiApp {
     fn default() -> Self {
         Self {
@@ -57,20 +67,29 @@ impl eframe::App for GuiApp {
             }
         }
 
-        // Handle cancel requests (soft cancel)
+        // Handle cancel requests: core stops at its next check and the result is ignored
         if self.state.cancel_requested {
             self.cancel_requested = true;
-            // UI stays in-progress until job finishes; we just ignore results.
+            if let Some(job) = &self.pack_job {
+                job.handle.cancel();
+            }
             self.state.cancel_requested = false;
         }
 
-        // Poll pack job completion
-        if let Some(handle) = &self.pack_job {
-            if handle.is_finished() {
-                let handle = self.pack_job.take().unwrap();
-                match handle.join().expect("pack job panicked") {
-                    Ok((out, stats)) => {
+        // Poll pack job progress and completion
+        if let Some(job) = &mut self.pack_job {
+            self.state.pack_progress = job.handle.progress();
+            ctx.request_repaint_after(Duration::from_millis(50));
+            if let Some(result) = job.handle.try_result() {
+                let pack_time_ms = job.start.elapsed().as_millis() as u64;
+                let num_images = job.num_images;
+                let cfg = job.cfg.clone();
+                self.pack_job = None;
+                match result {
+                    Ok(out) => {
                         if !self.cancel_requested {
+                            let stats = GuiPackStats::from_output(&out, num_images, pack_time_ms);
+                            self.state.issues = out.atlas.validate(&cfg);
                             self.state.result = Some(out);
                             self.state.stats = Some(stats);
                             self.page_textures.clear();
@@ -78,11 +97,12 @@ impl eframe::App for GuiApp {
                     }
                     Err(err) => {
                         if !self.cancel_requested {
-                            self.state.set_error(err);
+                            self.state.set_error(format!("Pack error: {err:?}"));
                         }
                     }
                 }
                 self.state.pack_in_progress = false;
+                self.state.pack_progress = None;
                 self.cancel_requested = false;
                 self.state.dirty_config = false;
                 // If autopack is on and further changes queued during job, rearm debounce
@@ -147,18 +167,12 @@ impl GuiApp {
         self.state.pack_in_progress = true;
         self.page_textures.clear();
         self.cancel_requested = false;
-        let handle = std::thread::spawn(move || {
-            let start = std::time::Instant::now();
-            match pack_images(inputs, cfg.clone()) {
-                Ok(out) => {
-                    let pack_time_ms = start.elapsed().as_millis() as u64;
-                    let stats = GuiPackStats::from_output(&out, num_images, pack_time_ms);
-                    Ok((out, stats))
-                }
-                Err(e) => Err(format!("Pack error: {e:?}")),
-            }
+        self.pack_job = Some(PackJob {
+            handle: spawn_pack(inputs, cfg.clone()),
+            start: Instant::now(),
+            num_images,
+            cfg,
         });
-        self.pack_job = Some(handle);
     }
 }
 
@@ -39,6 +39,7 @@ impl PackerPreset {
                 .family(AlgorithmFamily::Auto)
                 .auto_mode(AutoMode::Quality)
                 .time_budget_ms(Some(500))
+                .key_transform(KeyTransform::file_stem())
                 .build(),
             recommended_sizes: vec![(1024, 1024), (2048, 2048), (4096, 4096)],
         }
@@ -68,6 +69,7 @@ impl PackerPreset {
                 .texture_extrusion(2)
                 .family(AlgorithmFamily::Skyline)
                 .skyline_heuristic(SkylineHeuristic::MinWaste)
+                .key_transform(KeyTransform::file_stem())
                 .build(),
             recommended_sizes: vec![(1024, 1024), (2048, 2048)],
         }
@@ -97,6 +99,7 @@ impl PackerPreset {
                 .texture_extrusion(0)
                 .family(AlgorithmFamily::MaxRects)
                 .mr_heuristic(MaxRectsHeuristic::BestAreaFit)
+                .key_transform(KeyTransform::file_stem())
                 .build(),
             recommended_sizes: vec![(2048, 2048), (4096, 4096)],
         }
@@ -125,10 +128,11 @@ impl PackerPreset {
                 .trim(true)
                 .texture_padding(2)
                 .texture_extrusion(2)
-                .pow2(true)
+                .page_size(PageSizeMode::Pow2)
                 .square(true)
                 .family(AlgorithmFamily::Auto)
                 .auto_mode(AutoMode::Quality)
+                .key_transform(KeyTransform::file_stem())
                 .build(),
             recommended_sizes: vec![(512, 512), (1024, 1024), (2048, 2048)],
         }
@@ -157,10 +161,10 @@ impl PackerPreset {
                 .trim(true)
                 .texture_padding(2)
                 .texture_extrusion(2)
-                .pow2(false)
                 .square(false)
                 .family(AlgorithmFamily::Auto)
                 .auto_mode(AutoMode::Quality)
+                .key_transform(KeyTransform::file_stem())
                 .build(),
             recommended_sizes: vec![(2048, 2048), (4096, 4096)],
         }
@@ -190,9 +194,10 @@ impl PackerPreset {
                 .texture_padding(2)
                 .texture_extrusion(2)
                 .border_padding(2)
-                .pow2(true)
+                .page_size(PageSizeMode::Pow2)
                 .family(AlgorithmFamily::Auto)
                 .auto_mode(AutoMode::Quality)
+                .key_transform(KeyTransform::file_stem())
                 .build(),
             recommended_sizes: vec![(2048, 2048), (4096, 4096)],
         }
@@ -224,6 +229,7 @@ impl PackerPreset {
                 .use_waste_map(false)
                 .family(AlgorithmFamily::Skyline)
                 .skyline_heuristic(SkylineHeuristic::BottomLeft)
+                .key_transform(KeyTransform::file_stem())
                 .build(),
             recommended_sizes: vec![(2048, 2048), (4096, 4096)],
         }
@@ -258,6 +264,7 @@ impl PackerPreset {
                 .time_budget_ms(Some(5000))
                 .mr_reference(true)
                 .parallel(true)
+                .key_transform(KeyTransform::file_stem())
                 .build(),
             recommended_sizes: vec![(2048, 2048), (4096, 4096)],
         }
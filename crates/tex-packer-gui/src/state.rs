@@ -1,9 +1,11 @@
 //! Application state
 
+use crate::export::{ExportSettings, export_atlas};
 use crate::presets::PackerPreset;
 use crate::stats::PackStats;
 use std::collections::HashSet;
 use std::path::PathBuf;
+use tex_packer_core::AtlasIssue;
 use tex_packer_core::prelude::*;
 use tracing::{error, info};
 
@@ -26,6 +28,8 @@ pub struct AppState {
     // Result
     pub result: Option<PackOutput>,
     pub stats: Option<PackStats>,
+    /// Problems `Atlas::validate` found in the result.
+    pub issues: Vec<AtlasIssue>,
 
     // UI state
     pub selected_page: usize,
@@ -50,22 +54,18 @@ pub struct AppState {
     pub autopack: bool,
     pub dirty_config: bool,
     pub pack_in_progress: bool,
+    /// Latest report of the running pack job.
+    pub pack_progress: Option<PackProgress>,
     pub cancel_requested: bool,
 
     // Export
-    pub export_format: ExportFormat,
+    pub export: ExportSettings,
 
     // Inputs management
     pub excluded_keys: HashSet<String>,
     pub input_filter: String,
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
-pub enum ExportFormat {
-    Hash,
-    Array,
-}
-
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum AdvancedTab {
     General,
@@ -105,6 +105,7 @@ impl Default for AppState {
 
             result: None,
             stats: None,
+            issues: Vec::new(),
 
             selected_page: 0,
             atlas_name: "atlas".into(),
@@ -126,9 +127,10 @@ impl Default for AppState {
             autopack: false,
             dirty_config: false,
             pack_in_progress: false,
+            pack_progress: None,
             cancel_requested: false,
 
-            export_format: ExportFormat::Hash,
+            export: ExportSettings::default(),
 
             excluded_keys: HashSet::new(),
             input_filter: String::new(),
@@ -202,6 +204,12 @@ impl AppState {
         }
     }
 
+    pub fn pick_template_file(&mut self) {
+        if let Some(f) = rfd::FileDialog::new().set_directory(".").pick_file() {
+            self.export.template = Some(f);
+        }
+    }
+
     pub fn pick_output_dir(&mut self) {
         if let Some(d) = rfd::FileDialog::new().set_directory(".").pick_folder() {
             self.output_dir = Some(d);
@@ -214,7 +222,7 @@ impl AppState {
         for path in paths {
             if path.is_file() && is_image_path(path) {
                 let key = path
-                    .file_stem()
+                    .file_name()
                     .and_then(|s| s.to_str())
                     .unwrap_or("")
                     .to_string();
@@ -259,7 +267,7 @@ impl AppState {
                 let path = entry.path();
                 if is_image_path(&path) {
                     let key = path
-                        .file_stem()
+                        .file_name()
                         .and_then(|s| s.to_str())
                         .unwrap_or("")
                         .to_string();
@@ -277,47 +285,10 @@ impl AppState {
     pub fn clear_result(&mut self) {
         self.result = None;
         self.stats = None;
+        self.issues.clear();
         self.selected_page = 0;
     }
 
-    pub fn do_pack(&mut self) {
-        self.clear_result();
-        self.clear_error();
-
-        if self.inputs.is_empty() {
-            self.set_error("No inputs loaded");
-            return;
-        }
-
-        let inputs: Vec<InputImage> = self
-            .inputs
-            .iter()
-            .map(|i| InputImage {
-                key: i.key.clone(),
-                image: i.image.clone(),
-            })
-            .collect();
-
-        let num_images = inputs.len();
-        let start = std::time::Instant::now();
-
-        match pack_images(inputs, self.cfg.clone()) {
-            Ok(out) => {
-                let pack_time_ms = start.elapsed().as_millis() as u64;
-
-                // Calculate stats
-                let stats = PackStats::from_output(&out, num_images, pack_time_ms);
-                info!("{}", stats.status_string());
-
-                self.stats = Some(stats);
-                self.result = Some(out);
-            }
-            Err(e) => {
-                self.set_error(format!("Pack error: {e:?}"));
-            }
-        }
-    }
-
     pub fn do_export(&mut self) {
         let Some(outdir) = &self.output_dir else {
             self.set_error("Pick an output folder first");
@@ -328,29 +299,10 @@ impl AppState {
             return;
         };
 
-        let name = self.atlas_name.as_str();
-
-        // Write pages
-        for p in &result.pages {
-            let file = outdir.join(format!("{name}_{}.png", p.page.id));
-            if let Err(e) = p.rgba.save(&file) {
-                self.set_error(format!("Failed writing {:?}: {e}", file));
-                return;
-            }
-        }
-
-        // Write json (hash/array)
-        let json = match self.export_format {
-            ExportFormat::Hash => tex_packer_core::to_json_hash(&result.atlas),
-            ExportFormat::Array => tex_packer_core::to_json_array(&result.atlas),
-        };
-        let json_path = outdir.join(format!("{name}.json"));
-        if let Err(e) = std::fs::write(&json_path, serde_json::to_string_pretty(&json).unwrap()) {
-            self.set_error(format!("Failed writing {:?}: {e}", json_path));
-            return;
+        match export_atlas(result, &self.cfg, &self.export, outdir, &self.atlas_name) {
+            Ok(files) => info!("Exported {} files to {:?}", files.len(), outdir),
+            Err(e) => self.set_error(format!("Export failed: {e:#}")),
         }
-
-        info!("Exported atlas to {:?}", outdir);
     }
 }
 
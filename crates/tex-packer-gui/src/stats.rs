@@ -1,6 +1,6 @@
 //! Packing statistics
 
-use tex_packer_core::PackOutput;
+use tex_packer_core::{PackOutput, PackTimings};
 
 /// Statistics from a packing operation
 #[derive(Debug, Clone)]
@@ -13,6 +13,20 @@ pub struct PackStats {
     pub pack_time_ms: u64,
     pub avg_page_width: u32,
     pub avg_page_height: u32,
+    /// Theoretical minimum page count (frame area + mandatory padding)
+    pub lower_bound_pages: usize,
+    /// Achieved efficiency vs the theoretical area bound (percent)
+    pub bound_efficiency: f32,
+    pub num_rotated: usize,
+    /// Pixels removed by trimming
+    pub trimmed_pixels_saved: u64,
+    /// Area reserved for texture padding and extrusion
+    pub padding_overhead: u64,
+    /// Occupancy of each page (percent)
+    pub page_occupancy: Vec<f32>,
+    /// Algorithm that won an Auto run, e.g. "MaxRects BestAreaFit"
+    pub winning_config: Option<String>,
+    pub timings: PackTimings,
 }
 
 impl PackStats {
@@ -55,6 +69,8 @@ impl PackStats {
             0
         };
 
+        let core_stats = output.stats();
+
         Self {
             num_images,
             num_pages,
@@ -64,29 +80,65 @@ impl PackStats {
             pack_time_ms,
             avg_page_width,
             avg_page_height,
+            lower_bound_pages: core_stats.lower_bound_pages,
+            bound_efficiency: (core_stats.bound_efficiency * 100.0) as f32,
+            num_rotated: core_stats.num_rotated,
+            trimmed_pixels_saved: core_stats.trimmed_pixels_saved,
+            padding_overhead: core_stats.padding_overhead,
+            page_occupancy: core_stats
+                .pages
+                .iter()
+                .map(|p| (p.occupancy * 100.0) as f32)
+                .collect(),
+            winning_config: core_stats
+                .winning_config
+                .map(|c| format!("{:?} {}", c.family, c.heuristic)),
+            timings: core_stats.timings.unwrap_or_default(),
         }
     }
 
     /// Format as a compact status string
     pub fn status_string(&self) -> String {
         format!(
-            "{} images | {} pages | {:.1}% occupancy | {}ms",
-            self.num_images, self.num_pages, self.occupancy, self.pack_time_ms
+            "{} images | {} pages (min {}) | {:.1}% occupancy | {:.1}% of bound | {}ms",
+            self.num_images,
+            self.num_pages,
+            self.lower_bound_pages,
+            self.occupancy,
+            self.bound_efficiency,
+            self.pack_time_ms
         )
     }
 
     /// Format as detailed multi-line string
     pub fn detailed_string(&self) -> String {
-        format!(
-            "Images: {}\nPages: {}\nTotal Area: {} px²\nUsed Area: {} px²\nOccupancy: {:.2}%\nPack Time: {} ms\nAvg Page Size: {}x{}",
+        let mut s = format!(
+            "Images: {}\nPages: {} (lower bound {})\nTotal Area: {} px²\nUsed Area: {} px²\nOccupancy: {:.2}%\nBound Efficiency: {:.2}%\nPack Time: {} ms\nAvg Page Size: {}x{}\nRotated: {}\nTrimmed Saved: {} px²\nPadding Overhead: {} px²",
             self.num_images,
             self.num_pages,
+            self.lower_bound_pages,
             self.total_area,
             self.used_area,
             self.occupancy,
+            self.bound_efficiency,
             self.pack_time_ms,
             self.avg_page_width,
-            self.avg_page_height
-        )
+            self.avg_page_height,
+            self.num_rotated,
+            self.trimmed_pixels_saved,
+            self.padding_overhead
+        );
+        if let Some(choice) = &self.winning_config {
+            s.push_str(&format!("\nAuto Picked: {choice}"));
+        }
+        let t = &self.timings;
+        s.push_str(&format!(
+            "\nPhases: prepare {} ms, pack {} ms, composite {} ms",
+            t.prepare_ms, t.pack_ms, t.composite_ms
+        ));
+        for (i, occ) in self.page_occupancy.iter().enumerate() {
+            s.push_str(&format!("\nPage {i}: {occ:.1}%"));
+        }
+        s
     }
 }
@@ -1,5 +1,6 @@
 //! Setup panel UI (left side, egui)
 
+use crate::export::{ENGINES, ExportFormat, PAGE_FORMATS};
 use crate::state::AppState;
 use eframe::egui;
 use egui_extras::TableBuilder;
@@ -22,6 +23,8 @@ pub fn render(ui: &mut egui::Ui, state: &mut AppState) {
             ui.separator();
             render_advanced_section(ui, state);
             ui.separator();
+            render_export_section(ui, state);
+            ui.separator();
             render_actions(ui, state);
         });
 }
@@ -109,6 +112,16 @@ fn render_selection_section(ui: &mut egui::Ui, state: &mut AppState) {
                 if let Some(page) = result.pages.get(sel_page) {
                     if let Some(fr) = page.page.frames.iter().find(|f| f.key == sel.key) {
                         let name = fr.key.clone();
+                        // Exclusions are tracked by input key (the file name), before `key_transform`.
+                        let input_key = state
+                            .inputs
+                            .iter()
+                            .map(|i| i.key.clone())
+                            .find(|k| {
+                                state.cfg.key_transform.apply(k).ok().as_deref()
+                                    == Some(name.as_str())
+                            })
+                            .unwrap_or_else(|| name.clone());
                         ui.horizontal(|ui| {
                             ui.strong("Name:");
                             ui.label(&name);
@@ -132,7 +145,7 @@ fn render_selection_section(ui: &mut egui::Ui, state: &mut AppState) {
                             fr.source_size.0, fr.source_size.1
                         ));
                         ui.add_space(4.0);
-                        let excluded_now = state.excluded_keys.contains(&name);
+                        let excluded_now = state.excluded_keys.contains(&input_key);
                         ui.horizontal(|ui| {
                             if ui.button("Go to page").clicked() {
                                 state.selected_page = sel_page;
@@ -144,9 +157,9 @@ fn render_selection_section(ui: &mut egui::Ui, state: &mut AppState) {
                             };
                             if ui.add(btn).clicked() {
                                 if excluded_now {
-                                    state.excluded_keys.remove(&name);
+                                    state.excluded_keys.remove(&input_key);
                                 } else {
-                                    state.excluded_keys.insert(name.clone());
+                                    state.excluded_keys.insert(input_key.clone());
                                 }
                                 state.dirty_config = true;
                             }
@@ -368,14 +381,52 @@ fn render_advanced_general(ui: &mut egui::Ui, state: &mut AppState) {
         any_changed |= ui
             .toggle_value(&mut state.cfg.texture_outlines, "Debug outlines")
             .changed();
-        any_changed |= ui
-            .toggle_value(&mut state.cfg.power_of_two, "Power-of-two")
-            .changed();
         any_changed |= ui.toggle_value(&mut state.cfg.square, "Square").changed();
         any_changed |= ui
             .toggle_value(&mut state.cfg.use_waste_map, "Skyline waste-map")
             .changed();
     });
+    ui.horizontal(|ui| {
+        ui.label("Page size");
+        let tight = match state.cfg.page_size {
+            PageSizeMode::TightCrop { margin } => PageSizeMode::TightCrop { margin },
+            _ => PageSizeMode::default(),
+        };
+        for (mode, label) in [
+            (tight, "Tight crop"),
+            (PageSizeMode::Pow2, "Power-of-two"),
+            (PageSizeMode::Fixed, "Fixed (max size)"),
+        ] {
+            any_changed |= ui
+                .selectable_value(&mut state.cfg.page_size, mode, label)
+                .changed();
+        }
+        match &mut state.cfg.page_size {
+            PageSizeMode::TightCrop { margin } => {
+                ui.label("Margin");
+                any_changed |= ui
+                    .add(egui::DragValue::new(margin).range(0..=256))
+                    .changed();
+            }
+            PageSizeMode::AllowedList { sizes } => {
+                ui.label(format!("Allowed sizes {sizes:?}"));
+            }
+            PageSizeMode::Pow2 | PageSizeMode::Fixed => {}
+        }
+    });
+    if state.cfg.allow_rotation {
+        ui.horizontal(|ui| {
+            ui.label("Rotate");
+            for (direction, label) in [
+                (RotationDirection::Clockwise, "Clockwise"),
+                (RotationDirection::CounterClockwise, "Counter-clockwise"),
+            ] {
+                any_changed |= ui
+                    .selectable_value(&mut state.cfg.rotation_direction, direction, label)
+                    .changed();
+            }
+        });
+    }
     if state.cfg.trim {
         let mut thr = state.cfg.trim_threshold as i32;
         if ui
@@ -385,6 +436,16 @@ fn render_advanced_general(ui: &mut egui::Ui, state: &mut AppState) {
             state.cfg.trim_threshold = thr as u8;
             any_changed = true;
         }
+        ui.horizontal(|ui| {
+            ui.label("Trim min size");
+            any_changed |= ui
+                .add(egui::DragValue::new(&mut state.cfg.trim_options.min_size).range(0..=4096))
+                .changed();
+            ui.label("Multiple of");
+            any_changed |= ui
+                .add(egui::DragValue::new(&mut state.cfg.trim_options.multiple_of).range(0..=64))
+                .changed();
+        });
     }
     let mut tp = state.cfg.texture_padding as i32;
     let mut te = state.cfg.texture_extrusion as i32;
@@ -401,6 +462,20 @@ fn render_advanced_general(ui: &mut egui::Ui, state: &mut AppState) {
     state.cfg.texture_padding = tp as u32;
     state.cfg.texture_extrusion = te as u32;
     state.cfg.border_padding = bp as u32;
+    if state.cfg.texture_extrusion > 0 {
+        ui.horizontal(|ui| {
+            ui.label("Extrude mode");
+            for (mode, label) in [
+                (ExtrudeMode::Clamp, "Clamp"),
+                (ExtrudeMode::Wrap, "Wrap"),
+                (ExtrudeMode::Mirror, "Mirror"),
+            ] {
+                any_changed |= ui
+                    .selectable_value(&mut state.cfg.extrude_mode, mode, label)
+                    .changed();
+            }
+        });
+    }
 
     if any_changed {
         state.mark_custom();
@@ -430,6 +505,18 @@ fn render_advanced_algorithm(ui: &mut egui::Ui, state: &mut AppState) {
         {
             fam = AlgorithmFamily::Guillotine;
         }
+        if ui
+            .selectable_label(matches!(fam, AlgorithmFamily::Shelf), "Shelf")
+            .clicked()
+        {
+            fam = AlgorithmFamily::Shelf;
+        }
+        if ui
+            .selectable_label(matches!(fam, AlgorithmFamily::Grid), "Grid")
+            .clicked()
+        {
+            fam = AlgorithmFamily::Grid;
+        }
         if ui
             .selectable_label(matches!(fam, AlgorithmFamily::Auto), "Auto")
             .clicked()
@@ -458,6 +545,18 @@ fn render_advanced_algorithm(ui: &mut egui::Ui, state: &mut AppState) {
             {
                 h = SkylineHeuristic::MinWaste;
             }
+            if ui
+                .selectable_label(matches!(h, SkylineHeuristic::BottomLeftWm), "BL+WM")
+                .clicked()
+            {
+                h = SkylineHeuristic::BottomLeftWm;
+            }
+            if ui
+                .selectable_label(matches!(h, SkylineHeuristic::MinWasteWm), "MW+WM")
+                .clicked()
+            {
+                h = SkylineHeuristic::MinWasteWm;
+            }
             if h != state.cfg.skyline_heuristic {
                 state.cfg.skyline_heuristic = h;
                 any_changed = true;
@@ -518,9 +617,54 @@ fn render_advanced_algorithm(ui: &mut egui::Ui, state: &mut AppState) {
                 }
             }
         }
+        AlgorithmFamily::Shelf => {
+            ui.label("Shelf heuristic:");
+            for (label, val) in [
+                ("NextFit", ShelfHeuristic::NextFit),
+                ("FirstFit", ShelfHeuristic::FirstFit),
+                ("BestHeightFit", ShelfHeuristic::BestHeightFit),
+            ] {
+                let sel = state.cfg.shelf_heuristic == val;
+                if ui.selectable_label(sel, label).clicked() {
+                    state.cfg.shelf_heuristic = val;
+                    any_changed = true;
+                }
+            }
+        }
+        AlgorithmFamily::Grid => {
+            ui.label("Grid cell size (0 = largest sprite):");
+            let mut w = state.cfg.grid_cell_width as i32;
+            let mut h = state.cfg.grid_cell_height as i32;
+            ui.horizontal(|ui| {
+                ui.add(
+                    egui::DragValue::new(&mut w)
+                        .speed(1)
+                        .range(0..=16384)
+                        .prefix("W:"),
+                );
+                ui.add(
+                    egui::DragValue::new(&mut h)
+                        .speed(1)
+                        .range(0..=16384)
+                        .prefix("H:"),
+                );
+            });
+            let w = w.clamp(0, 16384) as u32;
+            let h = h.clamp(0, 16384) as u32;
+            if w != state.cfg.grid_cell_width || h != state.cfg.grid_cell_height {
+                state.cfg.grid_cell_width = w;
+                state.cfg.grid_cell_height = h;
+                any_changed = true;
+            }
+        }
         AlgorithmFamily::Auto => {
             ui.label("Auto mode:");
-            for (label, val) in [("Fast", AutoMode::Fast), ("Quality", AutoMode::Quality)] {
+            for (label, val) in [
+                ("Fast", AutoMode::Fast),
+                ("Quality", AutoMode::Quality),
+                ("Exhaustive", AutoMode::Exhaustive),
+                ("Anneal", AutoMode::Anneal),
+            ] {
                 let sel = state.cfg.auto_mode == val;
                 if ui.selectable_label(sel, label).clicked() {
                     state.cfg.auto_mode = val;
@@ -572,6 +716,9 @@ fn render_advanced_algorithm(ui: &mut egui::Ui, state: &mut AppState) {
                 any_changed = true;
             }
         }
+        AlgorithmFamily::Custom => {
+            ui.label("Custom packers are only available through the library API.");
+        }
     }
     if any_changed {
         state.mark_custom();
@@ -594,11 +741,136 @@ fn render_advanced_sorting(ui: &mut egui::Ui, state: &mut AppState) {
     }
 }
 
+fn render_export_section(ui: &mut egui::Ui, state: &mut AppState) {
+    egui::CollapsingHeader::new("Export")
+        .default_open(false)
+        .show(ui, |ui| {
+            let export = &mut state.export;
+            ui.horizontal(|ui| {
+                ui.label("Metadata");
+                egui::ComboBox::from_id_salt("export_format")
+                    .selected_text(export.format.label())
+                    .show_ui(ui, |ui| {
+                        for format in ExportFormat::ALL {
+                            ui.selectable_value(&mut export.format, format, format.label());
+                        }
+                    });
+            });
+            ui.horizontal(|ui| {
+                ui.label("Engine template");
+                egui::ComboBox::from_id_salt("export_engine")
+                    .selected_text(export.engine.unwrap_or("None"))
+                    .show_ui(ui, |ui| {
+                        ui.selectable_value(&mut export.engine, None, "None");
+                        for engine in ENGINES {
+                            ui.selectable_value(&mut export.engine, Some(engine), engine);
+                        }
+                    });
+            });
+            if export.engine.is_none() {
+                let mut pick = false;
+                ui.horizontal(|ui| {
+                    ui.label("Custom template");
+                    let label = export
+                        .template
+                        .as_ref()
+                        .and_then(|p| p.file_name())
+                        .map_or("None".into(), |n| n.to_string_lossy().into_owned());
+                    pick = ui.button(label).clicked();
+                    if export.template.is_some() && ui.small_button("x").clicked() {
+                        export.template = None;
+                    }
+                });
+                if pick {
+                    state.pick_template_file();
+                }
+            }
+            let export = &mut state.export;
+            ui.horizontal_wrapped(|ui| {
+                ui.toggle_value(&mut export.layout_only, "Layout only");
+                ui.toggle_value(&mut export.stats_json, "Stats JSON");
+                if matches!(
+                    export.format,
+                    ExportFormat::JsonHash | ExportFormat::JsonArray
+                ) {
+                    ui.toggle_value(&mut export.uvs, "UVs");
+                }
+            });
+            if matches!(
+                export.format,
+                ExportFormat::JsonHash | ExportFormat::JsonArray
+            ) && export.uvs
+            {
+                ui.add(
+                    egui::Slider::new(&mut export.uv_inset, 0.0..=2.0)
+                        .step_by(0.5)
+                        .text("UV inset (texels)"),
+                );
+            }
+            if export.format == ExportFormat::Godot {
+                ui.horizontal(|ui| {
+                    ui.label("Godot res dir");
+                    ui.text_edit_singleline(&mut export.godot_res_dir);
+                });
+                ui.checkbox(&mut export.godot_sprite_frames, "SpriteFrames (animations)");
+            }
+            if export.layout_only {
+                return;
+            }
+
+            // Page encoding only affects the written files, so it does not trigger a repack
+            let cfg = &mut state.cfg;
+            ui.horizontal(|ui| {
+                ui.label("Pages");
+                egui::ComboBox::from_id_salt("page_format")
+                    .selected_text(cfg.page_format.extension())
+                    .show_ui(ui, |ui| {
+                        for format in PAGE_FORMATS {
+                            ui.selectable_value(&mut cfg.page_format, format, format.extension());
+                        }
+                    });
+            });
+            let encoding = &mut cfg.page_encoding;
+            match cfg.page_format {
+                PageFormat::Jpeg => {
+                    ui.add(egui::Slider::new(&mut encoding.quality, 1..=100).text("Quality"));
+                }
+                PageFormat::Webp => {
+                    ui.checkbox(&mut encoding.lossless, "Lossless");
+                    if !encoding.lossless {
+                        ui.add(egui::Slider::new(&mut encoding.quality, 1..=100).text("Quality"));
+                    }
+                }
+                PageFormat::Avif => {
+                    ui.add(egui::Slider::new(&mut encoding.quality, 1..=100).text("Quality"));
+                    ui.add(egui::Slider::new(&mut encoding.speed, 1..=10).text("Speed"));
+                }
+                _ => {}
+            }
+        });
+}
+
 fn render_actions(ui: &mut egui::Ui, state: &mut AppState) {
     ui.horizontal(|ui| {
         if state.pack_in_progress {
             ui.add(egui::Spinner::new());
-            ui.weak("Packing...");
+            match state.pack_progress {
+                Some(p) => {
+                    let phase = match p.phase {
+                        PackPhase::Prepare => "Preparing",
+                        PackPhase::Layout => "Layout",
+                        PackPhase::Compose => "Compositing",
+                    };
+                    ui.add(
+                        egui::ProgressBar::new(p.fraction())
+                            .desired_width(120.0)
+                            .text(format!("{phase} {}/{}", p.done, p.total)),
+                    );
+                }
+                None => {
+                    ui.weak("Packing...");
+                }
+            }
             if ui.button("Cancel").clicked() {
                 state.cancel_requested = true;
             }
@@ -607,24 +879,6 @@ fn render_actions(ui: &mut egui::Ui, state: &mut AppState) {
         }
         ui.toggle_value(&mut state.autopack, "Auto Pack");
         ui.separator();
-        ui.label("Export Format:");
-        egui::ComboBox::from_id_salt("export_format")
-            .selected_text(match state.export_format {
-                crate::state::ExportFormat::Hash => "Hash",
-                crate::state::ExportFormat::Array => "Array",
-            })
-            .show_ui(ui, |ui| {
-                ui.selectable_value(
-                    &mut state.export_format,
-                    crate::state::ExportFormat::Hash,
-                    "Hash",
-                );
-                ui.selectable_value(
-                    &mut state.export_format,
-                    crate::state::ExportFormat::Array,
-                    "Array",
-                );
-            });
         let export_enabled =
             state.result.is_some() && state.output_dir.is_some() && !state.pack_in_progress;
         if ui
@@ -645,6 +899,15 @@ fn render_actions(ui: &mut egui::Ui, state: &mut AppState) {
         ui.colored_label(
             egui::Color32::from_rgb(150, 220, 150),
             stats.status_string(),
-        );
+        )
+        .on_hover_text(stats.detailed_string());
+    }
+    if !state.issues.is_empty() {
+        let list: Vec<String> = state.issues.iter().map(ToString::to_string).collect();
+        ui.colored_label(
+            egui::Color32::from_rgb(255, 190, 90),
+            format!("{} layout problem(s)", state.issues.len()),
+        )
+        .on_hover_text(list.join("\n"));
     }
 }
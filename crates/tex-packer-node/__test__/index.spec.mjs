@@ -0,0 +1,38 @@
+import assert from 'node:assert/strict'
+import { createRequire } from 'node:module'
+import test from 'node:test'
+
+const { packImages } = createRequire(import.meta.url)('../index.js')
+
+const rgba = (w, h, v) => ({ key: `raw${w}x${h}`, data: Buffer.alloc(w * h * 4, v), width: w, height: h })
+
+test('packs raw RGBA images into PNG pages', async () => {
+  const result = await packImages([rgba(16, 8, 255), rgba(4, 4, 128)], {
+    max_width: 64,
+    max_height: 64,
+    family: 'maxrects',
+    trim: false,
+  })
+  assert.equal(result.pages.length, 1)
+  const page = result.pages[0]
+  assert.deepEqual([...page.png.subarray(1, 4)].map((c) => String.fromCharCode(c)).join(''), 'PNG')
+  const meta = JSON.parse(result.json)
+  assert.equal(meta.pages[0].width, page.width)
+  assert.equal(meta.pages[0].frames.length, 2)
+  assert.ok(JSON.parse(result.jsonHash).frames.raw16x8)
+})
+
+test('re-packs its own PNG output as encoded input', async () => {
+  const first = await packImages([rgba(8, 8, 255)], { trim: false })
+  const second = await packImages([{ key: 'page', data: first.pages[0].png }], {
+    key_transform: { case: 'upper' },
+  })
+  assert.equal(JSON.parse(second.json).pages[0].frames[0].key, 'PAGE')
+})
+
+test('reports errors', async () => {
+  assert.throws(() => packImages([], { no_such_field: 1 }), /no_such_field/)
+  assert.throws(() => packImages([{ key: 'a', data: Buffer.alloc(4), width: 1 }]), /width and height/)
+  await assert.rejects(packImages([{ key: 'bad', data: Buffer.from('nope') }]), /bad/)
+  await assert.rejects(packImages([rgba(64, 64, 1)], { max_width: 16, max_height: 16 }))
+})
@@ -0,0 +1,31 @@
+/* Generated by napi-rs from src/lib.rs (`napi build` rewrites this file). */
+
+/**
+ * One image to pack. `data` is an encoded image (PNG, JPEG, WebP, ...) unless `width` and
+ * `height` are given, in which case it is raw RGBA8.
+ */
+export interface ImageInput {
+  key: string
+  data: Buffer
+  width?: number
+  height?: number
+}
+export interface PackedPage {
+  width: number
+  height: number
+  /** PNG-encoded page. */
+  png: Buffer
+}
+export interface PackResult {
+  pages: Array<PackedPage>
+  /** Metadata in the CLI's default `json-array` layout. */
+  json: string
+  /** Metadata in the TexturePacker-style `json-hash` layout. */
+  jsonHash: string
+}
+/**
+ * Packs `images` with `config` (`PackerConfig` field names, as in YAML configs, e.g.
+ * `{ max_width: 2048, family: "maxrects" }`). Config and size errors throw synchronously;
+ * decode and packing errors reject the promise.
+ */
+export function packImages(images: Array<ImageInput>, config?: Record<string, any> | undefined | null): Promise<PackResult>
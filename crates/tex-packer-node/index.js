@@ -0,0 +1,23 @@
+// Loads the native addon built by `napi build --platform` (tex-packer.<platform>-<arch>[-abi].node),
+// falling back to an unsuffixed `tex-packer.node` from `napi build`.
+const { existsSync } = require('fs')
+const { join } = require('path')
+
+function candidates() {
+  const { platform, arch } = process
+  if (platform === 'linux') {
+    const musl = !process.report?.getReport()?.header?.glibcVersionRuntime
+    return [`linux-${arch}-${musl ? 'musl' : 'gnu'}`]
+  }
+  if (platform === 'win32') return [`win32-${arch}-msvc`]
+  if (platform === 'darwin') return [`darwin-${arch}`, 'darwin-universal']
+  return [`${platform}-${arch}`]
+}
+
+const file = [...candidates().map((c) => `tex-packer.${c}.node`), 'tex-packer.node']
+  .map((name) => join(__dirname, name))
+  .find(existsSync)
+if (!file) {
+  throw new Error(`tex-packer: no native binary for ${process.platform}-${process.arch}`)
+}
+module.exports = require(file)
@@ -0,0 +1,153 @@
+//! Node.js bindings for tex-packer.
+//!
+//! `packImages` decodes, packs and PNG-encodes on the libuv thread pool and resolves with the
+//! page buffers plus metadata JSON, so build tools (webpack/vite plugins, asset scripts) can
+//! call it without blocking the event loop.
+
+use image::{DynamicImage, RgbaImage};
+use napi::bindgen_prelude::*;
+use napi::{Env, Task};
+use napi_derive::napi;
+use tex_packer_core::{
+    InputImage, PackerConfig, TexPackerError, pack_images as core_pack, to_json_array, to_json_hash,
+};
+
+fn reason(e: impl std::fmt::Display) -> Error {
+    Error::from_reason(e.to_string())
+}
+
+/// One image to pack. `data` is an encoded image (PNG, JPEG, WebP, ...) unless `width` and
+/// `height` are given, in which case it is raw RGBA8.
+#[napi(object)]
+pub struct ImageInput {
+    pub key: String,
+    pub data: Buffer,
+    pub width: Option<u32>,
+    pub height: Option<u32>,
+}
+
+#[napi(object)]
+pub struct PackedPage {
+    pub width: u32,
+    pub height: u32,
+    /// PNG-encoded page.
+    pub png: Buffer,
+}
+
+#[napi(object)]
+pub struct PackResult {
+    pub pages: Vec<PackedPage>,
+    /// Metadata in the CLI's default `json-array` layout.
+    pub json: String,
+    /// Metadata in the TexturePacker-style `json-hash` layout.
+    pub json_hash: String,
+}
+
+enum Source {
+    Encoded(Vec<u8>),
+    Rgba(u32, u32, Vec<u8>),
+}
+
+/// Background work of [`pack_images`].
+pub struct PackTask {
+    inputs: Vec<(String, Source)>,
+    cfg: PackerConfig,
+}
+
+/// Result of [`PackTask::compute`], converted to JS in `resolve`.
+pub struct Packed {
+    pages: Vec<(u32, u32, Vec<u8>)>,
+    json: String,
+    json_hash: String,
+}
+
+fn decode(key: String, source: Source) -> tex_packer_core::Result<InputImage> {
+    let image = match source {
+        Source::Encoded(bytes) => image::load_from_memory(&bytes)
+            .map_err(|e| TexPackerError::InvalidInput(format!("{key}: {e}")))?,
+        Source::Rgba(w, h, data) => {
+            let len = data.len();
+            let img = RgbaImage::from_raw(w, h, data).ok_or_else(|| {
+                TexPackerError::InvalidInput(format!(
+                    "{key}: expected {} bytes for {w}x{h} RGBA, got {len}",
+                    w as usize * h as usize * 4
+                ))
+            })?;
+            DynamicImage::ImageRgba8(img)
+        }
+    };
+    Ok(InputImage { key, image })
+}
+
+impl Task for PackTask {
+    type Output = Packed;
+    type JsValue = PackResult;
+
+    fn compute(&mut self) -> Result<Self::Output> {
+        let inputs = std::mem::take(&mut self.inputs)
+            .into_iter()
+            .map(|(key, source)| decode(key, source))
+            .collect::<tex_packer_core::Result<Vec<_>>>()
+            .map_err(reason)?;
+        let out = core_pack(inputs, self.cfg.clone()).map_err(reason)?;
+        let mut pages = Vec::with_capacity(out.pages.len());
+        for page in &out.pages {
+            let mut png = Vec::new();
+            page.write_png(&mut png).map_err(reason)?;
+            pages.push((page.page.width, page.page.height, png));
+        }
+        Ok(Packed {
+            pages,
+            json: to_json_array(&out.atlas).to_string(),
+            json_hash: to_json_hash(&out.atlas).to_string(),
+        })
+    }
+
+    fn resolve(&mut self, _env: Env, output: Self::Output) -> Result<Self::JsValue> {
+        Ok(PackResult {
+            pages: output
+                .pages
+                .into_iter()
+                .map(|(width, height, png)| PackedPage {
+                    width,
+                    height,
+                    png: png.into(),
+                })
+                .collect(),
+            json: output.json,
+            json_hash: output.json_hash,
+        })
+    }
+}
+
+/// Packs `images` with `config` (`PackerConfig` field names, as in YAML configs, e.g.
+/// `{ max_width: 2048, family: "maxrects" }`). Config and size errors throw synchronously;
+/// decode and packing errors reject the promise.
+#[napi(ts_return_type = "Promise<PackResult>")]
+pub fn pack_images(
+    images: Vec<ImageInput>,
+    config: Option<serde_json::Map<String, serde_json::Value>>,
+) -> Result<AsyncTask<PackTask>> {
+    let mut cfg = PackerConfig::default();
+    for (field, value) in config.iter().flatten() {
+        cfg.set_json(field, value).map_err(reason)?;
+    }
+    let inputs = images
+        .into_iter()
+        .map(|img| {
+            let data = img.data.to_vec();
+            let source = match (img.width, img.height) {
+                (Some(w), Some(h)) => Source::Rgba(w, h, data),
+                (None, None) => Source::Encoded(data),
+                _ => {
+                    return Err(reason(format!(
+                        "{}: give both width and height for raw RGBA, or neither",
+                        img.key
+                    )));
+                }
+            };
+            Ok((img.key, source))
+        })
+        .collect::<Result<Vec<_>>>()?;
+    Ok(AsyncTask::new(PackTask { inputs, cfg }))
+}
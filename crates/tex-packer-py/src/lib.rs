@@ -0,0 +1,189 @@
+//! Python bindings for tex-packer.
+//!
+//! Build with `maturin develop -m crates/tex-packer-py/Cargo.toml`, then:
+//!
+//! ```python
+//! import tex_packer
+//! pages, atlas = tex_packer.pack(["ui/a.png", ("hero", hero_rgba)], {"max_width": 1024})
+//! ```
+
+use std::path::PathBuf;
+
+use image::{DynamicImage, GrayAlphaImage, GrayImage, RgbImage, RgbaImage};
+use numpy::{PyArray1, PyArrayMethods, PyReadonlyArrayDyn, PyUntypedArrayMethods};
+use pyo3::create_exception;
+use pyo3::exceptions::{PyException, PyTypeError};
+use pyo3::prelude::*;
+use pyo3::types::{PyBytes, PyDict, PyTuple};
+use tex_packer_core::{InputImage, PackerConfig, pack_images, to_json_array, to_json_hash};
+
+create_exception!(
+    tex_packer,
+    TexPackerError,
+    PyException,
+    "Raised when loading inputs, applying the config, or packing fails."
+);
+
+fn err(e: impl std::fmt::Display) -> PyErr {
+    TexPackerError::new_err(e.to_string())
+}
+
+/// Converts a uint8 numpy array (`HxW` gray, `HxWx1..4` gray/gray+alpha/RGB/RGBA) to an image.
+fn image_from_array(key: &str, array: PyReadonlyArrayDyn<'_, u8>) -> PyResult<DynamicImage> {
+    let shape = array.shape().to_vec();
+    let (h, w, c) = match shape[..] {
+        [h, w] => (h, w, 1),
+        [h, w, c] if (1..=4).contains(&c) => (h, w, c),
+        _ => {
+            return Err(err(format!(
+                "{key}: expected an HxW or HxWxC (C = 1..4) uint8 array, got shape {shape:?}"
+            )));
+        }
+    };
+    let (w, h) = (w as u32, h as u32);
+    // Logical (row-major) order, so non-contiguous views work too
+    let data: Vec<u8> = array.as_array().iter().copied().collect();
+    let image = match c {
+        1 => GrayImage::from_raw(w, h, data).map(DynamicImage::ImageLuma8),
+        2 => GrayAlphaImage::from_raw(w, h, data).map(DynamicImage::ImageLumaA8),
+        3 => RgbImage::from_raw(w, h, data).map(DynamicImage::ImageRgb8),
+        _ => RgbaImage::from_raw(w, h, data).map(DynamicImage::ImageRgba8),
+    };
+    image.ok_or_else(|| err(format!("{key}: array size does not match its shape")))
+}
+
+/// Loads one source: a path (`str` / `os.PathLike`), encoded image `bytes`, or a numpy array.
+fn load_source(key: &str, source: &Bound<'_, PyAny>) -> PyResult<DynamicImage> {
+    if let Ok(bytes) = source.cast::<PyBytes>() {
+        return image::load_from_memory(bytes.as_bytes()).map_err(|e| err(format!("{key}: {e}")));
+    }
+    if let Ok(path) = source.extract::<PathBuf>() {
+        return image::open(&path).map_err(|e| err(format!("{}: {e}", path.display())));
+    }
+    if let Ok(array) = source.extract::<PyReadonlyArrayDyn<'_, u8>>() {
+        return image_from_array(key, array);
+    }
+    Err(PyTypeError::new_err(format!(
+        "{key}: expected a path, bytes or a uint8 numpy array, got {}",
+        source.get_type().name()?
+    )))
+}
+
+/// Accepts a `{key: source}` dict or a list whose items are paths (the path is the key) or
+/// `(key, source)` tuples.
+fn collect_inputs(inputs: &Bound<'_, PyAny>) -> PyResult<Vec<InputImage>> {
+    let mut out = Vec::new();
+    if let Ok(dict) = inputs.cast::<PyDict>() {
+        for (key, source) in dict.iter() {
+            let key: String = key.extract()?;
+            let image = load_source(&key, &source)?;
+            out.push(InputImage { key, image });
+        }
+        return Ok(out);
+    }
+    for item in inputs.try_iter()? {
+        let item = item?;
+        let (key, image) = match item.cast::<PyTuple>() {
+            Ok(pair) if pair.len() == 2 => {
+                let key: String = pair.get_item(0)?.extract()?;
+                let image = load_source(&key, &pair.get_item(1)?)?;
+                (key, image)
+            }
+            _ => {
+                let path: PathBuf = item.extract().map_err(|_| {
+                    PyTypeError::new_err("list items must be paths or (key, source) tuples")
+                })?;
+                let key = path.to_string_lossy().replace('\\', "/");
+                (key.clone(), load_source(&key, &item)?)
+            }
+        };
+        out.push(InputImage { key, image });
+    }
+    Ok(out)
+}
+
+/// Builds a `PackerConfig` from a dict of field names (as in YAML configs / `--print-config`),
+/// applied in order with `PackerConfig::set_json`.
+fn config_from_dict(py: Python<'_>, dict: Option<&Bound<'_, PyDict>>) -> PyResult<PackerConfig> {
+    let mut cfg = PackerConfig::default();
+    let Some(dict) = dict else {
+        return Ok(cfg);
+    };
+    let text: String = py
+        .import("json")?
+        .call_method1("dumps", (dict,))?
+        .extract()?;
+    let fields: serde_json::Map<String, serde_json::Value> =
+        serde_json::from_str(&text).map_err(err)?;
+    for (key, value) in &fields {
+        cfg.set_json(key, value).map_err(err)?;
+    }
+    Ok(cfg)
+}
+
+/// Packs images into atlas pages.
+///
+/// `inputs` is a list of paths (the path string becomes the frame key; use
+/// `key_transform` in the config to shorten it) and/or `(key, source)` tuples, or a
+/// `{key: source}` dict. A source is a path, encoded image bytes, or a uint8 numpy array
+/// shaped `HxW` or `HxWxC` with C = 1 (gray), 2 (gray + alpha), 3 (RGB) or 4 (RGBA).
+///
+/// `config` maps `PackerConfig` field names to values, e.g.
+/// `{"max_width": 2048, "family": "maxrects", "trim": True}`.
+///
+/// Returns `(pages, atlas)`: a list of `HxWx4` uint8 RGBA arrays and the atlas metadata as a
+/// dict in the `json-array` (default) or `json-hash` layout.
+#[pyfunction]
+#[pyo3(signature = (inputs, config = None, *, layout = "json-array"))]
+fn pack<'py>(
+    py: Python<'py>,
+    inputs: &Bound<'py, PyAny>,
+    config: Option<&Bound<'py, PyDict>>,
+    layout: &str,
+) -> PyResult<(Vec<Bound<'py, PyAny>>, Bound<'py, PyAny>)> {
+    let cfg = config_from_dict(py, config)?;
+    let inputs = collect_inputs(inputs)?;
+    let (atlas, pages) = py
+        .detach(|| {
+            let out = pack_images(inputs, cfg)?;
+            let pages = out
+                .pages
+                .iter()
+                .map(|p| p.pixels().map(|px| px.into_owned()))
+                .collect::<tex_packer_core::Result<Vec<_>>>()?;
+            Ok::<_, tex_packer_core::TexPackerError>((out.atlas, pages))
+        })
+        .map_err(err)?;
+
+    let meta = match layout {
+        "json-array" | "json" => to_json_array(&atlas),
+        "json-hash" => to_json_hash(&atlas),
+        other => {
+            return Err(err(format!(
+                "unknown layout '{other}': expected json-array or json-hash"
+            )));
+        }
+    };
+    let meta = py
+        .import("json")?
+        .call_method1("loads", (meta.to_string(),))?;
+
+    let pages = pages
+        .into_iter()
+        .map(|img| {
+            let (w, h) = img.dimensions();
+            PyArray1::from_vec(py, img.into_raw())
+                .reshape([h as usize, w as usize, 4])
+                .map(Bound::into_any)
+        })
+        .collect::<PyResult<Vec<_>>>()?;
+    Ok((pages, meta))
+}
+
+#[pymodule]
+fn tex_packer(m: &Bound<'_, PyModule>) -> PyResult<()> {
+    m.add("__version__", env!("CARGO_PKG_VERSION"))?;
+    m.add("TexPackerError", m.py().get_type::<TexPackerError>())?;
+    m.add_function(wrap_pyfunction!(pack, m)?)?;
+    Ok(())
+}
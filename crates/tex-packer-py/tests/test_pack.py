@@ -0,0 +1,64 @@
+# Run with: maturin develop -m crates/tex-packer-py/Cargo.toml && pytest crates/tex-packer-py/tests
+import struct
+import zlib
+
+import numpy as np
+import pytest
+
+import tex_packer
+
+
+def solid(h, w, c=4, value=255):
+    return np.full((h, w, c), value, dtype=np.uint8)
+
+
+def test_packs_arrays_and_returns_rgba_pages():
+    pages, atlas = tex_packer.pack(
+        [("a", solid(10, 20)), ("b", solid(8, 8, 3, 7)), ("g", np.zeros((4, 4), np.uint8))],
+        {"max_width": 64, "max_height": 64, "texture_padding": 0, "allow_rotation": False},
+    )
+    assert len(pages) == 1
+    page = pages[0]
+    assert page.dtype == np.uint8 and page.shape[2] == 4
+    frames = {f["key"]: f["frame"] for f in atlas["pages"][0]["frames"]}
+    assert set(frames) == {"a", "b", "g"}
+    b = frames["b"]
+    assert (page[b["y"], b["x"]] == [7, 7, 7, 255]).all()
+
+
+def png_bytes(w, h):
+    def chunk(tag, data):
+        return struct.pack(">I", len(data)) + tag + data + struct.pack(">I", zlib.crc32(tag + data))
+
+    rows = b"".join(b"\x00" + b"\xff" * (w * 4) for _ in range(h))
+    return (
+        b"\x89PNG\r\n\x1a\n"
+        + chunk(b"IHDR", struct.pack(">IIBBBBB", w, h, 8, 6, 0, 0, 0))
+        + chunk(b"IDAT", zlib.compress(rows))
+        + chunk(b"IEND", b"")
+    )
+
+
+def test_paths_bytes_and_dict_inputs(tmp_path):
+    png = tmp_path / "hero.png"
+    png.write_bytes(png_bytes(3, 2))
+    _, atlas = tex_packer.pack(
+        {"file": png, "raw": png.read_bytes()},
+        {"trim": False},
+        layout="json-hash",
+    )
+    assert set(atlas["frames"]) == {"file", "raw"}
+
+    _, atlas = tex_packer.pack([str(png)], {"key_transform": {"strip_directories": True}})
+    assert atlas["pages"][0]["frames"][0]["key"] == "hero.png"
+
+
+def test_errors():
+    with pytest.raises(tex_packer.TexPackerError):
+        tex_packer.pack([("a", solid(4, 4))], {"no_such_field": 1})
+    with pytest.raises(tex_packer.TexPackerError):
+        tex_packer.pack([("a", solid(4, 4, 5))])
+    with pytest.raises(tex_packer.TexPackerError):
+        tex_packer.pack([("a", solid(128, 128))], {"max_width": 32, "max_height": 32})
+    with pytest.raises(TypeError):
+        tex_packer.pack([("a", 42)])
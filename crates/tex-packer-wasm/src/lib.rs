@@ -0,0 +1,181 @@
+//! Browser bindings for tex-packer.
+//!
+//! Build with `wasm-pack build crates/tex-packer-wasm --target web`. From JavaScript:
+//!
+//! ```js
+//! import init, { Packer } from "./pkg/tex_packer_wasm.js";
+//! await init();
+//! const packer = new Packer();
+//! packer.set("max_width", "1024");
+//! packer.addImage("hero.png", new Uint8Array(await file.arrayBuffer()));
+//! const result = packer.pack();
+//! const meta = JSON.parse(result.json);
+//! const png = result.pagePng(0); // Uint8Array
+//! ```
+
+use image::{DynamicImage, RgbaImage};
+use tex_packer_core::{
+    Atlas, InputImage, PackerConfig, TexPackerError, pack_images, to_json_array, to_json_hash,
+};
+use wasm_bindgen::prelude::*;
+
+fn js_err(e: TexPackerError) -> JsError {
+    JsError::new(&e.to_string())
+}
+
+/// Collects images and settings, then packs them with [`Packer::pack`].
+#[wasm_bindgen]
+pub struct Packer {
+    cfg: PackerConfig,
+    inputs: Vec<InputImage>,
+}
+
+impl Default for Packer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[wasm_bindgen]
+impl Packer {
+    /// Packer with `PackerConfig::default()` and no images.
+    #[wasm_bindgen(constructor)]
+    pub fn new() -> Packer {
+        Packer {
+            cfg: PackerConfig::default(),
+            inputs: Vec::new(),
+        }
+    }
+
+    /// Sets one config field by name, using the CLI/YAML keys (`"max_width"`, `"family"`, ...).
+    pub fn set(&mut self, key: &str, value: &str) -> Result<(), JsError> {
+        self.cfg.set_str(key, value).map_err(js_err)
+    }
+
+    /// Replaces the whole config with a serialized `PackerConfig` (e.g. `--print-config` output).
+    #[wasm_bindgen(js_name = setConfigJson)]
+    pub fn set_config_json(&mut self, json: &str) -> Result<(), JsError> {
+        self.cfg = serde_json::from_str(json).map_err(|e| JsError::new(&e.to_string()))?;
+        Ok(())
+    }
+
+    /// Current config as JSON.
+    #[wasm_bindgen(js_name = configJson)]
+    pub fn config_json(&self) -> String {
+        serde_json::to_string(&self.cfg).unwrap_or_default()
+    }
+
+    /// Decodes an encoded image (PNG, JPEG, WebP, ...) and adds it under `key`.
+    #[wasm_bindgen(js_name = addImage)]
+    pub fn add_image(&mut self, key: String, bytes: &[u8]) -> Result<(), JsError> {
+        let image = image::load_from_memory(bytes).map_err(|e| js_err(e.into()))?;
+        self.inputs.push(InputImage { key, image });
+        Ok(())
+    }
+
+    /// Adds raw RGBA8 pixels, e.g. `ImageData.data` from a canvas.
+    #[wasm_bindgen(js_name = addRgba)]
+    pub fn add_rgba(
+        &mut self,
+        key: String,
+        width: u32,
+        height: u32,
+        pixels: Vec<u8>,
+    ) -> Result<(), JsError> {
+        let len = pixels.len();
+        let image = RgbaImage::from_raw(width, height, pixels).ok_or_else(|| {
+            js_err(TexPackerError::InvalidInput(format!(
+                "{key}: expected {} bytes for {width}x{height} RGBA, got {len}",
+                width as usize * height as usize * 4
+            )))
+        })?;
+        self.inputs.push(InputImage {
+            key,
+            image: DynamicImage::ImageRgba8(image),
+        });
+        Ok(())
+    }
+
+    /// Number of images added.
+    #[wasm_bindgen(getter)]
+    pub fn len(&self) -> usize {
+        self.inputs.len()
+    }
+
+    #[wasm_bindgen(js_name = isEmpty)]
+    pub fn is_empty(&self) -> bool {
+        self.inputs.is_empty()
+    }
+
+    /// Removes all images; the config is kept.
+    pub fn clear(&mut self) {
+        self.inputs.clear();
+    }
+
+    /// Packs the added images. They stay in the packer, so settings can be tweaked and
+    /// `pack` called again.
+    pub fn pack(&self) -> Result<PackResult, JsError> {
+        let inputs = self
+            .inputs
+            .iter()
+            .map(|i| InputImage {
+                key: i.key.clone(),
+                image: i.image.clone(),
+            })
+            .collect();
+        let out = pack_images(inputs, self.cfg.clone()).map_err(js_err)?;
+        let mut pages = Vec::with_capacity(out.pages.len());
+        for page in &out.pages {
+            let mut png = Vec::new();
+            page.write_png(&mut png).map_err(js_err)?;
+            pages.push(png);
+        }
+        Ok(PackResult {
+            atlas: out.atlas,
+            pages,
+        })
+    }
+}
+
+/// Output of [`Packer::pack`]: PNG-encoded pages plus atlas metadata.
+#[wasm_bindgen]
+pub struct PackResult {
+    atlas: Atlas,
+    pages: Vec<Vec<u8>>,
+}
+
+#[wasm_bindgen]
+impl PackResult {
+    /// Metadata in the CLI's default `json-array` layout (`{ pages: [...], meta }`).
+    #[wasm_bindgen(getter)]
+    pub fn json(&self) -> String {
+        to_json_array(&self.atlas).to_string()
+    }
+
+    /// Metadata in the TexturePacker-style `json-hash` layout.
+    #[wasm_bindgen(getter, js_name = jsonHash)]
+    pub fn json_hash(&self) -> String {
+        to_json_hash(&self.atlas).to_string()
+    }
+
+    #[wasm_bindgen(getter, js_name = pageCount)]
+    pub fn page_count(&self) -> usize {
+        self.pages.len()
+    }
+
+    /// PNG bytes of page `index`, or `undefined` past the end.
+    #[wasm_bindgen(js_name = pagePng)]
+    pub fn page_png(&self, index: usize) -> Option<Vec<u8>> {
+        self.pages.get(index).cloned()
+    }
+
+    #[wasm_bindgen(js_name = pageWidth)]
+    pub fn page_width(&self, index: usize) -> Option<u32> {
+        self.atlas.pages.get(index).map(|p| p.width)
+    }
+
+    #[wasm_bindgen(js_name = pageHeight)]
+    pub fn page_height(&self, index: usize) -> Option<u32> {
+        self.atlas.pages.get(index).map(|p| p.height)
+    }
+}
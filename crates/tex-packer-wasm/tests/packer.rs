@@ -0,0 +1,57 @@
+//! Native checks of the success paths; error paths construct `JsError`, which needs a JS host.
+
+use image::{ImageFormat, Rgba, RgbaImage};
+use tex_packer_wasm::Packer;
+
+fn png(w: u32, h: u32) -> Vec<u8> {
+    let mut out = Vec::new();
+    RgbaImage::from_pixel(w, h, Rgba([255, 0, 0, 255]))
+        .write_to(&mut std::io::Cursor::new(&mut out), ImageFormat::Png)
+        .unwrap();
+    out
+}
+
+#[test]
+fn packs_encoded_and_raw_images_to_png_pages() {
+    let mut packer = Packer::new();
+    packer.set("max_width", "64").unwrap();
+    packer.set("max_height", "64").unwrap();
+    packer.add_image("a.png".into(), &png(20, 10)).unwrap();
+    packer
+        .add_rgba("b".into(), 4, 4, vec![255; 4 * 4 * 4])
+        .unwrap();
+    assert_eq!(packer.len(), 2);
+
+    let result = packer.pack().unwrap();
+    assert_eq!(result.page_count(), 1);
+    let page = image::load_from_memory(&result.page_png(0).unwrap()).unwrap();
+    assert_eq!(
+        (page.width(), page.height()),
+        (
+            result.page_width(0).unwrap(),
+            result.page_height(0).unwrap()
+        )
+    );
+    assert!(result.page_png(1).is_none());
+
+    let meta: serde_json::Value = serde_json::from_str(&result.json()).unwrap();
+    assert_eq!(meta["pages"][0]["frames"].as_array().unwrap().len(), 2);
+    let hash: serde_json::Value = serde_json::from_str(&result.json_hash()).unwrap();
+    assert!(hash["frames"]["a.png"].is_object());
+}
+
+#[test]
+fn config_round_trips_through_json_and_inputs_are_reusable() {
+    let mut packer = Packer::new();
+    packer.set("texture_padding", "7").unwrap();
+    let json = packer.config_json();
+    let mut other = Packer::new();
+    other.set_config_json(&json).unwrap();
+    assert!(other.config_json().contains("\"texture_padding\":7"));
+
+    other.add_image("x".into(), &png(8, 8)).unwrap();
+    let first = other.pack().unwrap().json();
+    assert_eq!(other.pack().unwrap().json(), first);
+    other.clear();
+    assert!(other.is_empty());
+}